@@ -0,0 +1,162 @@
+//! Synchronous client, for scripts and tools that don't run an async runtime.
+//!
+//! [`CortexClient`] here wraps the async [`crate::CortexClient`] and drives
+//! each call to completion on its own current-thread Tokio runtime. Requests
+//! and responses are the same [`crate::proto`] types.
+//!
+//! Don't use it from inside an async context: blocking on a runtime from
+//! within another one panics. Use the async client there.
+//!
+//! # Example
+//! ```rust,no_run
+//! use cortex_client::blocking::CortexClient;
+//! use cortex_client::proto::CreateNodeRequest;
+//!
+//! fn main() -> anyhow::Result<()> {
+//!     let mut client = CortexClient::connect("http://localhost:9090")?;
+//!
+//!     let node = client.create_node(CreateNodeRequest {
+//!         kind: "decision".into(),
+//!         title: "Use Rust for performance-critical paths".into(),
+//!         body: "Go for I/O-bound, Rust for CPU-bound.".into(),
+//!         importance: Some(0.8),
+//!         ..Default::default()
+//!     })?;
+//!
+//!     let results = client.search("language choices", 5)?;
+//!     assert!(results.results.iter().any(|r| {
+//!         r.node.as_ref().is_some_and(|n| n.id == node.id)
+//!     }));
+//!     Ok(())
+//! }
+//! ```
+use crate::proto::{
+    CreateNodeRequest, HybridResultEntry, NodeResponse, ObserveResponse, SearchResponse,
+    StatsResponse, SubgraphResponse,
+};
+use crate::{BriefingOptions, ObserveParams};
+use tokio::runtime::{Builder, Runtime};
+
+/// A connected Cortex client with blocking methods.
+///
+/// Mirrors [`crate::CortexClient`]; see there for what each method does.
+pub struct CortexClient {
+    inner: crate::CortexClient,
+    rt: Runtime,
+}
+
+impl CortexClient {
+    /// Connect to a running Cortex server, blocking until connected.
+    ///
+    /// `addr` should be a full URI, e.g. `"http://localhost:9090"`.
+    pub fn connect(addr: impl Into<String>) -> anyhow::Result<Self> {
+        let rt = Builder::new_current_thread().enable_all().build()?;
+        let inner = rt.block_on(crate::CortexClient::connect(addr))?;
+        Ok(Self { inner, rt })
+    }
+
+    /// Create a node. Returns the stored [`NodeResponse`].
+    pub fn create_node(&mut self, req: CreateNodeRequest) -> anyhow::Result<NodeResponse> {
+        self.rt.block_on(self.inner.create_node(req))
+    }
+
+    /// Get a node by ID. Returns `None` if not found.
+    pub fn get_node(&mut self, id: &str) -> anyhow::Result<Option<NodeResponse>> {
+        self.rt.block_on(self.inner.get_node(id))
+    }
+
+    /// Semantic similarity search. Returns scored result entries.
+    pub fn search(&mut self, query: &str, limit: u32) -> anyhow::Result<SearchResponse> {
+        self.rt.block_on(self.inner.search(query, limit))
+    }
+
+    /// One page of semantic search. Pass the previous response's
+    /// `next_cursor` to continue; it is empty on the last page.
+    pub fn search_page(
+        &mut self,
+        query: &str,
+        limit: u32,
+        cursor: &str,
+    ) -> anyhow::Result<SearchResponse> {
+        self.rt
+            .block_on(self.inner.search_page(query, limit, cursor))
+    }
+
+    /// Hybrid search combining vector similarity with graph proximity.
+    pub fn search_hybrid(
+        &mut self,
+        query: &str,
+        anchor_ids: Vec<String>,
+        limit: u32,
+    ) -> anyhow::Result<Vec<HybridResultEntry>> {
+        self.rt
+            .block_on(self.inner.search_hybrid(query, anchor_ids, limit))
+    }
+
+    /// Hybrid search anchored on the top `anchor_count` vector hits.
+    pub fn search_hybrid_auto(
+        &mut self,
+        query: &str,
+        anchor_count: u32,
+        limit: u32,
+    ) -> anyhow::Result<Vec<HybridResultEntry>> {
+        self.rt
+            .block_on(self.inner.search_hybrid_auto(query, anchor_count, limit))
+    }
+
+    /// Generate a rendered context briefing for an agent. Returns markdown text.
+    pub fn briefing(&mut self, agent_id: &str) -> anyhow::Result<String> {
+        self.rt.block_on(self.inner.briefing(agent_id))
+    }
+
+    /// Generate a briefing for an agent with explicit options.
+    pub fn briefing_opts(
+        &mut self,
+        agent_id: &str,
+        opts: BriefingOptions,
+    ) -> anyhow::Result<String> {
+        self.rt.block_on(self.inner.briefing_opts(agent_id, opts))
+    }
+
+    /// Graph traversal starting from `node_id` up to `depth` hops.
+    pub fn traverse(&mut self, node_id: &str, depth: u32) -> anyhow::Result<SubgraphResponse> {
+        self.rt.block_on(self.inner.traverse(node_id, depth))
+    }
+
+    /// Create an edge between two nodes. Returns the edge ID.
+    pub fn create_edge(
+        &mut self,
+        from_id: &str,
+        to_id: &str,
+        relation: &str,
+    ) -> anyhow::Result<String> {
+        self.rt
+            .block_on(self.inner.create_edge(from_id, to_id, relation))
+    }
+
+    /// Record how a prompt variant performed for `agent`.
+    pub fn observe(
+        &mut self,
+        agent: &str,
+        params: ObserveParams,
+    ) -> anyhow::Result<ObserveResponse> {
+        self.rt.block_on(self.inner.observe(agent, params))
+    }
+
+    /// Get graph statistics.
+    pub fn stats(&mut self) -> anyhow::Result<StatsResponse> {
+        self.rt.block_on(self.inner.stats())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connect_failure_is_an_error() {
+        // Port 1 is never a Cortex server; no runtime needed by the caller
+        assert!(CortexClient::connect("http://127.0.0.1:1").is_err());
+        assert!(CortexClient::connect("not a uri").is_err());
+    }
+}
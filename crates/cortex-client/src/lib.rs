@@ -1,6 +1,7 @@
 //! Rust client for the Cortex graph memory engine.
 //!
 //! Thin wrapper over the tonic-generated gRPC client with ergonomic convenience methods.
+//! Synchronous code can use [`blocking::CortexClient`] instead.
 //!
 //! # Example
 //! ```rust,no_run
@@ -15,7 +16,7 @@
 //!         kind: "decision".into(),
 //!         title: "Use Rust for performance-critical paths".into(),
 //!         body: "Go for I/O-bound, Rust for CPU-bound.".into(),
-//!         importance: 0.8,
+//!         importance: Some(0.8),
 //!         ..Default::default()
 //!     }).await?;
 //!
@@ -30,14 +31,57 @@
 use cortex_proto::cortex::v1::{
     cortex_service_client::CortexServiceClient, BriefingRequest, CreateEdgeRequest,
     CreateNodeRequest, GetNodeRequest, HybridResultEntry, HybridSearchRequest, NodeResponse,
-    SearchResponse, SimilaritySearchRequest, StatsRequest, StatsResponse, SubgraphResponse,
-    TraverseRequest,
+    ObserveRequest, ObserveResponse, SearchResponse, SimilaritySearchRequest, StatsRequest,
+    StatsResponse, SubgraphResponse, TraverseChunk, TraverseRequest,
 };
 use tonic::transport::Channel;
 
+pub mod blocking;
+
 /// Re-export generated proto types for callers that need raw access.
 pub use cortex_proto::cortex::v1 as proto;
 
+/// How [`CortexClient::briefing_opts`] should build and render a briefing.
+#[derive(Debug, Clone, Default)]
+pub struct BriefingOptions {
+    /// Use the compact renderer (~4× denser). Ignored when `format` is set.
+    pub compact: bool,
+    /// Regenerate even if the server has a current cached briefing.
+    pub no_cache: bool,
+    /// `"markdown"`, `"compact"` or `"json"`. Empty for the default.
+    pub format: String,
+}
+
+/// One prompt-variant observation, for [`CortexClient::observe`].
+#[derive(Debug, Clone)]
+pub struct ObserveParams {
+    /// UUID of the prompt variant node.
+    pub variant_id: String,
+    /// Slug of the prompt variant, for display.
+    pub variant_slug: String,
+    /// Observed sentiment, 0.0–1.0. Default: 0.5.
+    pub sentiment: f32,
+    /// Number of corrections the user made.
+    pub correction_count: u32,
+    /// `success`, `partial`, `failure` or `unknown` (the default).
+    pub task_outcome: String,
+    /// Token cost of the interaction, if known.
+    pub token_cost: Option<u32>,
+}
+
+impl Default for ObserveParams {
+    fn default() -> Self {
+        Self {
+            variant_id: String::new(),
+            variant_slug: String::new(),
+            sentiment: 0.5,
+            correction_count: 0,
+            task_outcome: "unknown".into(),
+            token_cost: None,
+        }
+    }
+}
+
 /// A connected Cortex client.
 ///
 /// Wraps the tonic gRPC client with ergonomic methods for common operations.
@@ -90,6 +134,26 @@ impl CortexClient {
         Ok(resp.into_inner())
     }
 
+    /// One page of semantic search. Pass the previous response's
+    /// `next_cursor` to continue; it is empty on the last page.
+    pub async fn search_page(
+        &mut self,
+        query: &str,
+        limit: u32,
+        cursor: &str,
+    ) -> anyhow::Result<SearchResponse> {
+        let resp = self
+            .inner
+            .similarity_search(SimilaritySearchRequest {
+                query: query.into(),
+                limit,
+                cursor: cursor.into(),
+                ..Default::default()
+            })
+            .await?;
+        Ok(resp.into_inner())
+    }
+
     /// Hybrid search combining vector similarity with graph proximity.
     ///
     /// `anchor_ids` are node IDs that anchor the graph proximity component.
@@ -112,6 +176,28 @@ impl CortexClient {
         Ok(resp.into_inner().results)
     }
 
+    /// Hybrid search that picks its own anchors: the server anchors on the
+    /// top `anchor_count` vector hits and ranks their graph neighbourhood.
+    /// An `anchor_count` of 0 uses the server default (3).
+    pub async fn search_hybrid_auto(
+        &mut self,
+        query: &str,
+        anchor_count: u32,
+        limit: u32,
+    ) -> anyhow::Result<Vec<HybridResultEntry>> {
+        let resp = self
+            .inner
+            .hybrid_search(HybridSearchRequest {
+                query: query.into(),
+                auto_anchor: true,
+                auto_anchor_count: anchor_count,
+                limit,
+                ..Default::default()
+            })
+            .await?;
+        Ok(resp.into_inner().results)
+    }
+
     /// Generate a rendered context briefing for an agent. Returns markdown text.
     pub async fn briefing(&mut self, agent_id: &str) -> anyhow::Result<String> {
         let resp = self
@@ -124,6 +210,26 @@ impl CortexClient {
         Ok(resp.into_inner().rendered)
     }
 
+    /// Generate a briefing for an agent with explicit options. Returns the
+    /// rendered text; with `format: "json"` that is the briefing as a JSON
+    /// document.
+    pub async fn briefing_opts(
+        &mut self,
+        agent_id: &str,
+        opts: BriefingOptions,
+    ) -> anyhow::Result<String> {
+        let resp = self
+            .inner
+            .get_briefing(BriefingRequest {
+                agent_id: agent_id.into(),
+                compact: opts.compact,
+                format: opts.format,
+                no_cache: opts.no_cache,
+            })
+            .await?;
+        Ok(resp.into_inner().rendered)
+    }
+
     /// Graph traversal starting from `node_id` up to `depth` hops.
     pub async fn traverse(
         &mut self,
@@ -141,6 +247,25 @@ impl CortexClient {
         Ok(resp.into_inner())
     }
 
+    /// Breadth-first traversal from `node_id`, streamed one depth level at a
+    /// time. Each chunk carries the level's nodes and the edges that reached
+    /// them; a chunk with `truncated` set is the last.
+    pub async fn traverse_stream(
+        &mut self,
+        node_id: &str,
+        depth: u32,
+    ) -> anyhow::Result<tonic::Streaming<TraverseChunk>> {
+        let resp = self
+            .inner
+            .traverse_stream(TraverseRequest {
+                start_ids: vec![node_id.into()],
+                max_depth: depth,
+                ..Default::default()
+            })
+            .await?;
+        Ok(resp.into_inner())
+    }
+
     /// Create an edge between two nodes. Returns the edge ID.
     pub async fn create_edge(
         &mut self,
@@ -155,14 +280,39 @@ impl CortexClient {
                 to_id: to_id.into(),
                 relation: relation.into(),
                 weight: 1.0,
+                inverse: false,
             })
             .await?;
         Ok(resp.into_inner().id)
     }
 
+    /// Record how a prompt variant performed for `agent`. Returns the
+    /// observation ID, the `uses` edge weight before and after, and the
+    /// rollback it triggered, if any.
+    pub async fn observe(
+        &mut self,
+        agent: &str,
+        params: ObserveParams,
+    ) -> anyhow::Result<ObserveResponse> {
+        let resp = self
+            .inner
+            .observe(ObserveRequest {
+                agent: agent.into(),
+                variant_id: params.variant_id,
+                variant_slug: params.variant_slug,
+                sentiment_score: Some(params.sentiment),
+                correction_count: params.correction_count,
+                task_outcome: params.task_outcome,
+                token_cost: params.token_cost,
+                ..Default::default()
+            })
+            .await?;
+        Ok(resp.into_inner())
+    }
+
     /// Get graph statistics.
     pub async fn stats(&mut self) -> anyhow::Result<StatsResponse> {
-        let resp = self.inner.stats(StatsRequest {}).await?;
+        let resp = self.inner.stats(StatsRequest::default()).await?;
         Ok(resp.into_inner())
     }
 }
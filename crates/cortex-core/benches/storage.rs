@@ -6,7 +6,6 @@ use cortex_core::types::*;
 use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
 use std::sync::Arc;
 use tempfile::TempDir;
-use uuid::Uuid;
 
 fn create_test_node(kind: NodeKind, title: &str) -> Node {
     Node::new(
@@ -104,7 +103,7 @@ fn bench_filter_by_kind(c: &mut Criterion) {
         NodeKind::new("observation").unwrap(),
     ];
     for i in 0..5000 {
-        let kind = kinds[i % kinds.len()];
+        let kind = kinds[i % kinds.len()].clone();
         let n = create_test_node(kind, &format!("Node {}", i));
         storage.put_node(&n).unwrap();
     }
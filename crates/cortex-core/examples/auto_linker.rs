@@ -172,9 +172,9 @@ fn main() {
             let to = storage.get_node(edge.to).unwrap().unwrap();
 
             println!(
-                "  {}. [{}] {} → {} (weight: {:.2})",
+                "  {}. [{:?}] {} → {} (weight: {:.2})",
                 i + 1,
-                format!("{:?}", edge.relation),
+                edge.relation,
                 from.data.title,
                 to.data.title,
                 edge.weight
@@ -193,6 +193,7 @@ fn main() {
                     EdgeProvenance::AutoDedup { similarity } =>
                         format!("Auto-dedup ({:.2})", similarity),
                     EdgeProvenance::Imported { source } => format!("Imported from {}", source),
+                    EdgeProvenance::DerivedInverse { of } => format!("Inverse of {}", of),
                 }
             );
         }
@@ -75,11 +75,11 @@ fn main() {
     for (i, result) in results.iter().enumerate() {
         let node = storage.get_node(result.node_id).unwrap().unwrap();
         println!(
-            "{}. [score: {:.3}] {} — {}",
+            "{}. [score: {:.3}] {} — {:?}",
             i + 1,
             result.score,
             node.data.title,
-            format!("{:?}", node.kind)
+            node.kind
         );
     }
 }
@@ -1,10 +1,13 @@
+use crate::briefing::BriefingSectionSpec;
 use crate::linker::AutoLinkerConfig;
-use crate::vector::embedding_input;
+use crate::vector::{embedding_input, GraphProximity};
 use crate::{
     CortexError, Edge, EmbeddingService, FastEmbedService, GraphEngine, GraphEngineImpl, HnswIndex,
-    Node, NodeFilter, NodeId, NodeKind, RedbStorage, Result, Source, Storage, VectorIndex,
+    Node, NodeFilter, NodeId, NodeKind, RedbStorage, Result, SchemaValidator, Source, Storage,
+    VectorFilter, VectorIndex, WriteGateConfig,
 };
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 
 /// Config for embedded library mode.
@@ -14,6 +17,16 @@ pub struct LibraryConfig {
     pub embedding_model: String,
     /// Auto-linker config. Used if you call `run_auto_linker()`.
     pub auto_linker: AutoLinkerConfig,
+    /// Briefing sections for callers that build briefings from this handle.
+    /// `None` leaves the choice to the caller.
+    pub briefing_sections: Option<Vec<BriefingSectionSpec>>,
+    /// Graph expansion around search hits, for callers that expand them.
+    pub graph_proximity: GraphProximity,
+    /// Write gate for callers that check writes made through this handle.
+    /// `None` leaves the choice to the caller.
+    pub write_gate: Option<WriteGateConfig>,
+    /// Node kinds and metadata schemas the write gate checks against.
+    pub schema: SchemaValidator,
 }
 
 impl Default for LibraryConfig {
@@ -21,6 +34,10 @@ impl Default for LibraryConfig {
         Self {
             embedding_model: "BAAI/bge-small-en-v1.5".into(),
             auto_linker: AutoLinkerConfig::new(),
+            briefing_sections: None,
+            graph_proximity: GraphProximity::default(),
+            write_gate: None,
+            schema: SchemaValidator::default(),
         }
     }
 }
@@ -40,7 +57,7 @@ pub struct Cortex {
     embedding: Arc<FastEmbedService>,
     index: Arc<RwLock<HnswIndex>>,
     graph_engine: Arc<GraphEngineImpl<RedbStorage>>,
-    #[allow(dead_code)]
+    graph_version: Arc<AtomicU64>,
     config: LibraryConfig,
     hooks: crate::hooks::HookRegistry,
 }
@@ -59,7 +76,7 @@ impl Cortex {
             let mut any = false;
             for node in &nodes {
                 if let Some(emb) = &node.embedding {
-                    idx.insert(node.id, emb)?;
+                    idx.insert_node(node, emb)?;
                     any = true;
                 }
             }
@@ -69,18 +86,67 @@ impl Cortex {
             Arc::new(RwLock::new(idx))
         };
 
-        let graph_engine = Arc::new(GraphEngineImpl::new(storage.clone()));
+        let graph_version = Arc::new(AtomicU64::new(0));
+        let graph_engine = Arc::new(
+            GraphEngineImpl::new(storage.clone()).with_graph_version(graph_version.clone()),
+        );
 
         Ok(Self {
             storage,
             embedding,
             index,
             graph_engine,
+            graph_version,
             config,
             hooks: crate::hooks::HookRegistry::new(),
         })
     }
 
+    /// Counter bumped by every write made through this handle. Compare two
+    /// readings to tell whether the graph changed in between.
+    pub fn graph_version(&self) -> u64 {
+        self.graph_version.load(Ordering::Relaxed)
+    }
+
+    fn bump_graph_version(&self) {
+        self.graph_version.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Briefing sections configured in [`LibraryConfig::briefing_sections`].
+    pub fn briefing_sections(&self) -> Option<&[BriefingSectionSpec]> {
+        self.config.briefing_sections.as_deref()
+    }
+
+    /// Graph expansion configured in [`LibraryConfig::graph_proximity`].
+    pub fn graph_proximity(&self) -> &GraphProximity {
+        &self.config.graph_proximity
+    }
+
+    /// Write gate configured in [`LibraryConfig::write_gate`].
+    pub fn write_gate(&self) -> Option<&WriteGateConfig> {
+        self.config.write_gate.as_ref()
+    }
+
+    /// Schemas configured in [`LibraryConfig::schema`].
+    pub fn schema_validator(&self) -> &SchemaValidator {
+        &self.config.schema
+    }
+
+    /// The underlying storage, for checks that read it directly.
+    pub fn storage(&self) -> &RedbStorage {
+        &self.storage
+    }
+
+    /// The vector index over stored embeddings.
+    pub fn vector_index(&self) -> &RwLock<HnswIndex> {
+        &self.index
+    }
+
+    /// The model [`store`](Self::store) embeds nodes with.
+    pub fn embedding_service(&self) -> &FastEmbedService {
+        &self.embedding
+    }
+
     /// Register a mutation hook. Hooks are called in registration order.
     pub fn add_hook(&mut self, hook: std::sync::Arc<dyn crate::hooks::MutationHook>) {
         self.hooks.add(hook);
@@ -107,27 +173,56 @@ impl Cortex {
         self.index
             .write()
             .map_err(|_| CortexError::Validation("Vector index lock poisoned".into()))?
-            .insert(id, &emb)?;
+            .insert_node(&node, &emb)?;
+        self.bump_graph_version();
         self.hooks
             .notify_node(&node, crate::hooks::MutationAction::Created);
         Ok(id)
     }
 
-    /// Semantic similarity search. Returns nodes ranked by score.
+    /// Semantic similarity search. Returns nodes ranked by score, leaving out
+    /// nodes whose `expires_at` has passed.
     pub fn search(&self, query: &str, limit: usize) -> Result<Vec<(f32, Node)>> {
+        self.search_filtered(query, limit, &VectorFilter::new())
+    }
+
+    /// [`search`](Self::search) restricted to nodes matching `filter`. The
+    /// index applies the filter itself, and expired nodes are replaced by
+    /// fetching further, so fewer than `limit` results means there are no
+    /// more matches.
+    pub fn search_filtered(
+        &self,
+        query: &str,
+        limit: usize,
+        filter: &VectorFilter,
+    ) -> Result<Vec<(f32, Node)>> {
         let query_emb = self.embedding.embed(query)?;
-        let results = self
-            .index
-            .read()
-            .map_err(|_| CortexError::Validation("Vector index lock poisoned".into()))?
-            .search(&query_emb, limit, None)?;
-        let mut out = Vec::new();
-        for r in results {
-            if let Some(node) = self.storage.get_node(r.node_id)? {
-                out.push((r.score, node));
+        let mut fetch = limit;
+        loop {
+            let results = self
+                .index
+                .read()
+                .map_err(|_| CortexError::Validation("Vector index lock poisoned".into()))?
+                .search(&query_emb, fetch, Some(filter))?;
+            let exhausted = results.len() < fetch;
+            let mut out = Vec::with_capacity(limit);
+            for r in results {
+                if out.len() == limit {
+                    break;
+                }
+                if let Some(node) = self
+                    .storage
+                    .get_node(r.node_id)?
+                    .filter(|n| !n.is_expired())
+                {
+                    out.push((r.score, node));
+                }
+            }
+            if out.len() == limit || exhausted {
+                return Ok(out);
             }
+            fetch = fetch.saturating_mul(2);
         }
-        Ok(out)
     }
 
     /// Get a node by ID.
@@ -135,24 +230,106 @@ impl Cortex {
         self.storage.get_node(id)
     }
 
+    /// Get an edge by ID.
+    pub fn get_edge(&self, id: crate::EdgeId) -> Result<Option<Edge>> {
+        self.storage.get_edge(id)
+    }
+
+    /// Find the newest non-deleted node of `kind` with exactly this title.
+    pub fn get_node_by_title(&self, kind: &NodeKind, title: &str) -> Result<Option<Node>> {
+        self.storage.get_node_by_title(kind, title)
+    }
+
     /// List nodes with optional filter.
     pub fn list_nodes(&self, filter: NodeFilter) -> Result<Vec<Node>> {
         self.storage.list_nodes(filter)
     }
 
+    /// Update an existing node, re-embedding it if its text changed.
+    pub fn update(&self, mut node: Node) -> Result<()> {
+        let existing = self
+            .storage
+            .get_node(node.id)?
+            .filter(|n| !n.deleted)
+            .ok_or(CortexError::NodeNotFound(node.id))?;
+        if embedding_input(&existing) != embedding_input(&node) || node.embedding.is_none() {
+            node.embedding = Some(self.embedding.embed(&embedding_input(&node))?);
+        }
+        node.updated_at = chrono::Utc::now();
+        let emb = node.embedding.clone().unwrap();
+        self.storage.put_node(&node)?;
+        self.index
+            .write()
+            .map_err(|_| CortexError::Validation("Vector index lock poisoned".into()))?
+            .insert_node(&node, &emb)?;
+        self.bump_graph_version();
+        self.hooks
+            .notify_node(&node, crate::hooks::MutationAction::Updated);
+        Ok(())
+    }
+
+    /// Soft-delete a node. Returns false if it did not exist or was
+    /// already deleted.
+    pub fn delete(&self, id: NodeId) -> Result<bool> {
+        let node = match self.storage.get_node(id)? {
+            Some(node) if !node.deleted => node,
+            _ => return Ok(false),
+        };
+        self.storage.delete_node(id)?;
+        self.index
+            .write()
+            .map_err(|_| CortexError::Validation("Vector index lock poisoned".into()))?
+            .remove(id)?;
+        self.bump_graph_version();
+        self.hooks
+            .notify_node(&node, crate::hooks::MutationAction::Deleted);
+        Ok(true)
+    }
+
     /// Create an edge between two nodes.
     pub fn create_edge(&self, edge: Edge) -> Result<()> {
         self.storage.put_edge(&edge)?;
+        self.bump_graph_version();
         self.hooks
             .notify_edge(&edge, crate::hooks::MutationAction::Created);
         Ok(())
     }
 
+    /// Create an edge and, when its relation is symmetric or has a named
+    /// inverse, the inverse edge too, so the link is traversable from
+    /// either node. Returns the inverse edge if one was written.
+    pub fn create_edge_with_inverse(&self, edge: Edge) -> Result<Option<Edge>> {
+        let inverse = self.storage.put_edge_with_inverse(&edge)?;
+        self.bump_graph_version();
+        for e in std::iter::once(&edge).chain(&inverse) {
+            self.hooks
+                .notify_edge(e, crate::hooks::MutationAction::Created);
+        }
+        Ok(inverse)
+    }
+
     /// Graph traversal from a node (returns neighborhood).
     pub fn traverse(&self, from: NodeId, depth: u32) -> Result<crate::graph::Subgraph> {
         self.graph_engine.neighborhood(from, depth)
     }
 
+    /// Shortest path by hop count between two nodes, if one exists within
+    /// `max_hops` edges (unbounded when `None`).
+    pub fn shortest_path(
+        &self,
+        from: NodeId,
+        to: NodeId,
+        max_hops: Option<u32>,
+    ) -> Result<Option<crate::graph::Path>> {
+        let result = self.graph_engine.find_paths(crate::graph::PathRequest {
+            from,
+            to,
+            max_length: max_hops,
+            ..Default::default()
+        })?;
+        Ok(result.paths.into_iter().next())
+    }
+
     /// Hybrid search (vector + graph proximity). Not yet implemented.
     pub fn search_hybrid(&self, _query: &str, _limit: usize) -> Result<Vec<(f32, Node)>> {
         Err(CortexError::Validation(
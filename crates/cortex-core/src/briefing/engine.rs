@@ -1,15 +1,18 @@
 use super::cache::BriefingCache;
-use super::renderer::{BriefingRenderer, CompactRenderer, MarkdownRenderer};
-use super::{Briefing, BriefingSection};
+use super::renderer::{
+    BriefingFormat, BriefingRenderer, CompactRenderer, JsonRenderer, MarkdownRenderer,
+};
+use super::{Briefing, BriefingRelation, BriefingSection};
 use crate::error::Result;
 use crate::graph::{GraphEngine, TraversalDirection, TraversalRequest};
-use crate::storage::{NodeFilter, Storage};
+use crate::storage::{NodeCursor, NodeFilter, Storage};
 use crate::types::{Node, NodeId, NodeKind, Relation};
 use crate::vector::{EmbeddingService, HybridQuery, HybridSearch, VectorIndex};
-use chrono::Utc;
-use std::collections::HashSet;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
 
 /// Node kinds handled by the default structured section generators (Phase 1).
@@ -61,7 +64,93 @@ fn kind_to_section_title(kind: &str) -> String {
     pluralise(&title_cased)
 }
 
+/// Importance desc, then access_count desc.
+fn sort_by_importance(nodes: &mut [Node]) {
+    nodes.sort_by(|a, b| {
+        b.importance
+            .partial_cmp(&a.importance)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| b.access_count.cmp(&a.access_count))
+    });
+}
+
+/// Section keys accepted by [`BriefingConfig::sections`], in render order.
+pub const BRIEFING_SECTIONS: &[&str] = &[
+    "identity",
+    "patterns",
+    "goals",
+    "decisions",
+    "unresolved",
+    "events",
+    "discovered",
+    "active_context",
+];
+
+/// How nodes within a [`BriefingSectionSpec`] section are ordered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SectionOrder {
+    /// Highest importance first, then most accessed.
+    #[default]
+    Importance,
+    /// Newest first.
+    Recent,
+}
+
+/// A briefing section built by listing nodes of the given kinds.
+///
+/// Selected in [`BriefingConfig::sections`] by [`key`](Self::key), the
+/// plural of its first kind (`risk` → `risks`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BriefingSectionSpec {
+    pub heading: String,
+    pub kinds: Vec<String>,
+    /// Defaults to [`BriefingConfig::min_importance`].
+    #[serde(default)]
+    pub min_importance: Option<f32>,
+    /// Defaults to [`BriefingConfig::max_items_per_section`].
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub order: SectionOrder,
+}
+
+impl BriefingSectionSpec {
+    pub fn new(heading: &str, kinds: &[&str]) -> Self {
+        Self {
+            heading: heading.to_string(),
+            kinds: kinds.iter().map(|k| k.to_string()).collect(),
+            min_importance: None,
+            limit: None,
+            order: SectionOrder::Importance,
+        }
+    }
+
+    /// The key naming this section in [`BriefingConfig::sections`].
+    pub fn key(&self) -> String {
+        self.kinds.first().map(|k| pluralise(k)).unwrap_or_default()
+    }
+
+    /// The kind sections the engine builds out of the box.
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new("Patterns", &["pattern"]),
+            Self::new("Goals", &["goal"]),
+            Self::new("Key Decisions", &["decision"]),
+        ]
+    }
+
+    /// Whether any of this section's kinds falls outside the ones the
+    /// structured generators already cover.
+    fn has_custom_kind(&self) -> bool {
+        self.kinds
+            .iter()
+            .any(|k| !DEFAULT_SECTION_KINDS.contains(&k.as_str()))
+    }
+}
+
 /// Configuration for the briefing engine
+#[derive(Debug, Clone)]
 pub struct BriefingConfig {
     pub max_items_per_section: usize,
     pub max_total_items: usize,
@@ -72,6 +161,79 @@ pub struct BriefingConfig {
     pub min_importance: f32,
     pub min_weight: f32,
     pub exclude_kinds: Vec<String>,
+    /// Sections to build, by [`BRIEFING_SECTIONS`] key or
+    /// [`BriefingSectionSpec::key`]. `None` builds the default set.
+    pub sections: Option<Vec<String>>,
+    /// Per-agent overrides, keyed by agent id.
+    pub agents: HashMap<String, AgentBriefingConfig>,
+    /// Append each listed node's strongest 1-hop relations.
+    pub include_relations: bool,
+    /// Relations shown per node when `include_relations` is on.
+    pub max_relations_per_node: usize,
+    /// Sections built from node kinds. Without an agent node these replace
+    /// the graph-traversal sections; with one, only sections for custom
+    /// kinds (or ones named in `sections`) are added.
+    pub kind_sections: Vec<BriefingSectionSpec>,
+    /// Keep nodes whose `expires_at` has passed. Off by default.
+    pub include_expired: bool,
+}
+
+/// Overrides merged over [`BriefingConfig`] for one agent. Unset fields
+/// keep the global value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentBriefingConfig {
+    pub max_items_per_section: Option<usize>,
+    pub max_total_items: Option<usize>,
+    pub max_chars: Option<usize>,
+    pub min_importance: Option<f32>,
+    pub include_contradictions: Option<bool>,
+    pub sections: Option<Vec<String>>,
+    pub include_expired: Option<bool>,
+}
+
+impl BriefingConfig {
+    /// The effective config for `agent_id`: its overrides applied over this
+    /// config, or this config unchanged for agents without overrides.
+    pub fn for_agent(&self, agent_id: &str) -> BriefingConfig {
+        let mut cfg = self.clone();
+        let Some(o) = self.agents.get(agent_id) else {
+            return cfg;
+        };
+        if let Some(v) = o.max_items_per_section {
+            cfg.max_items_per_section = v;
+        }
+        if let Some(v) = o.max_total_items {
+            cfg.max_total_items = v;
+        }
+        if let Some(v) = o.max_chars {
+            cfg.max_chars = v;
+        }
+        if let Some(v) = o.min_importance {
+            cfg.min_importance = v;
+        }
+        if let Some(v) = o.include_contradictions {
+            cfg.include_contradictions = v;
+        }
+        if let Some(v) = &o.sections {
+            cfg.sections = Some(v.clone());
+        }
+        if let Some(v) = o.include_expired {
+            cfg.include_expired = v;
+        }
+        cfg
+    }
+
+    /// Whether `node` may appear: not expired, unless expired nodes are wanted.
+    fn shows(&self, node: &Node) -> bool {
+        self.include_expired || !node.is_expired()
+    }
+
+    fn wants_section(&self, key: &str) -> bool {
+        self.sections
+            .as_ref()
+            .is_none_or(|s| s.iter().any(|k| k == key))
+    }
 }
 
 impl Default for BriefingConfig {
@@ -86,6 +248,12 @@ impl Default for BriefingConfig {
             min_importance: 0.3,
             min_weight: 0.2,
             exclude_kinds: vec![],
+            sections: None,
+            agents: HashMap::new(),
+            kind_sections: BriefingSectionSpec::defaults(),
+            include_relations: false,
+            max_relations_per_node: 3,
+            include_expired: false,
         }
     }
 }
@@ -104,7 +272,8 @@ where
     embeddings: E,
     cache: Mutex<BriefingCache>,
     graph_version: Arc<AtomicU64>,
-    config: BriefingConfig,
+    config: RwLock<BriefingConfig>,
+    last_briefed: Mutex<HashMap<String, DateTime<Utc>>>,
 }
 
 impl<S, E, V, G> BriefingEngine<S, E, V, G>
@@ -130,10 +299,35 @@ where
             embeddings,
             cache,
             graph_version,
-            config,
+            config: RwLock::new(config),
+            last_briefed: Mutex::new(HashMap::new()),
         }
     }
 
+    /// The engine's current config.
+    pub fn config(&self) -> BriefingConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Replace the config, e.g. on a config reload. Cached briefings were
+    /// built under the old one, so the cache is dropped.
+    pub fn set_config(&self, config: BriefingConfig) {
+        *self.cache.lock().unwrap() = BriefingCache::new(config.cache_ttl);
+        *self.config.write().unwrap() = config;
+    }
+
+    /// When `agent_id` was last handed a briefing or diff by this engine.
+    pub fn last_briefed(&self, agent_id: &str) -> Option<DateTime<Utc>> {
+        self.last_briefed.lock().unwrap().get(agent_id).copied()
+    }
+
+    fn mark_briefed(&self, agent_id: &str, at: DateTime<Utc>) {
+        self.last_briefed
+            .lock()
+            .unwrap()
+            .insert(agent_id.to_string(), at);
+    }
+
     /// Generate a tailored briefing for the given agent.
     /// Returns a cached result if the graph version has not changed.
     pub fn generate(&self, agent_id: &str) -> Result<Briefing> {
@@ -145,48 +339,81 @@ where
             if let Some(cached) = cache.get(agent_id, current_version) {
                 let mut result = cached.clone();
                 result.cached = true;
+                self.mark_briefed(agent_id, Utc::now());
                 return Ok(result);
             }
         }
 
+        self.generate_uncached(agent_id)
+    }
+
+    /// Generate a briefing for the given agent without consulting the
+    /// cache. The result still replaces the cached entry.
+    pub fn generate_uncached(&self, agent_id: &str) -> Result<Briefing> {
+        let started_at = Utc::now();
+        let cfg = self.config.read().unwrap().for_agent(agent_id);
         let agent_node_id = self.find_agent_node(agent_id)?;
 
         let mut sections: Vec<BriefingSection> = Vec::new();
         let mut seen_ids: HashSet<NodeId> = HashSet::new();
 
         // 1. Identity & Preferences
-        let identity = self.generate_identity(agent_id, agent_node_id)?;
-        if !identity.nodes.is_empty() {
-            for n in &identity.nodes {
-                seen_ids.insert(n.id);
+        if cfg.wants_section("identity") {
+            let identity = self.generate_identity(&cfg, agent_id, agent_node_id)?;
+            if !identity.nodes.is_empty() {
+                for n in &identity.nodes {
+                    seen_ids.insert(n.id);
+                }
+                sections.push(identity);
             }
-            sections.push(identity);
         }
 
         // Graph-based sections: use agent node traversal if available,
         // otherwise fall back to global queries by node kind.
         if let Some(aid) = agent_node_id {
             // 2. Patterns (via graph traversal)
-            let patterns = self.generate_patterns(aid, &seen_ids)?;
-            if !patterns.nodes.is_empty() {
-                for n in &patterns.nodes {
-                    seen_ids.insert(n.id);
+            if cfg.wants_section("patterns") {
+                let patterns = self.generate_patterns(&cfg, aid, &seen_ids)?;
+                if !patterns.nodes.is_empty() {
+                    for n in &patterns.nodes {
+                        seen_ids.insert(n.id);
+                    }
+                    sections.push(patterns);
                 }
-                sections.push(patterns);
             }
 
             // 3. Goals (via graph traversal)
-            let goals = self.generate_goals(aid, &seen_ids)?;
-            if !goals.nodes.is_empty() {
-                for n in &goals.nodes {
-                    seen_ids.insert(n.id);
+            if cfg.wants_section("goals") {
+                let goals = self.generate_goals(&cfg, aid, &seen_ids)?;
+                if !goals.nodes.is_empty() {
+                    for n in &goals.nodes {
+                        seen_ids.insert(n.id);
+                    }
+                    sections.push(goals);
+                }
+            }
+
+            // Kind sections for built-in kinds only run here when asked for by
+            // name, and never repeat a section the graph already produced
+            for spec in &cfg.kind_sections {
+                if !cfg.wants_section(&spec.key())
+                    || !(spec.has_custom_kind() || cfg.sections.is_some())
+                    || sections.iter().any(|s| s.title == spec.heading)
+                {
+                    continue;
+                }
+                let section = self.generate_kind_section(&cfg, spec, &seen_ids)?;
+                if !section.nodes.is_empty() {
+                    for n in &section.nodes {
+                        seen_ids.insert(n.id);
+                    }
+                    sections.push(section);
                 }
-                sections.push(goals);
             }
 
             // 4. Unresolved Contradictions
-            if self.config.include_contradictions {
-                let unresolved = self.generate_unresolved(aid, &seen_ids)?;
+            if cfg.include_contradictions && cfg.wants_section("unresolved") {
+                let unresolved = self.generate_unresolved(&cfg, aid, &seen_ids)?;
                 if !unresolved.nodes.is_empty() {
                     for n in &unresolved.nodes {
                         seen_ids.insert(n.id);
@@ -196,75 +423,71 @@ where
             }
         } else {
             // No agent node — fall back to global queries by kind
-            let global_patterns = self.generate_global_by_kind("pattern", "Patterns", &seen_ids)?;
-            if !global_patterns.nodes.is_empty() {
-                for n in &global_patterns.nodes {
-                    seen_ids.insert(n.id);
+            for spec in &cfg.kind_sections {
+                if !cfg.wants_section(&spec.key()) {
+                    continue;
                 }
-                sections.push(global_patterns);
-            }
-
-            let global_goals = self.generate_global_by_kind("goal", "Goals", &seen_ids)?;
-            if !global_goals.nodes.is_empty() {
-                for n in &global_goals.nodes {
-                    seen_ids.insert(n.id);
-                }
-                sections.push(global_goals);
-            }
-
-            let global_decisions =
-                self.generate_global_by_kind("decision", "Key Decisions", &seen_ids)?;
-            if !global_decisions.nodes.is_empty() {
-                for n in &global_decisions.nodes {
-                    seen_ids.insert(n.id);
+                let section = self.generate_kind_section(&cfg, spec, &seen_ids)?;
+                if !section.nodes.is_empty() {
+                    for n in &section.nodes {
+                        seen_ids.insert(n.id);
+                    }
+                    sections.push(section);
                 }
-                sections.push(global_decisions);
             }
         }
 
         // 5. Recent Events (Phase 1 — before auto-discovery so `event` kind is excluded)
-        let events = self.generate_recent_events(agent_id, &seen_ids)?;
-        if !events.nodes.is_empty() {
-            for n in &events.nodes {
-                seen_ids.insert(n.id);
+        if cfg.wants_section("events") {
+            let events = self.generate_recent_events(&cfg, agent_id, &seen_ids)?;
+            if !events.nodes.is_empty() {
+                for n in &events.nodes {
+                    seen_ids.insert(n.id);
+                }
+                sections.push(events);
             }
-            sections.push(events);
         }
 
         // 6. Auto-discovered sections (Phase 2 — novel kinds not in DEFAULT_SECTION_KINDS)
-        let auto_sections = self.generate_auto_discovered_sections(&seen_ids)?;
-        for section in auto_sections {
-            for n in &section.nodes {
-                seen_ids.insert(n.id);
+        if cfg.wants_section("discovered") {
+            let auto_sections = self.generate_auto_discovered_sections(&cfg, &seen_ids)?;
+            for section in auto_sections {
+                for n in &section.nodes {
+                    seen_ids.insert(n.id);
+                }
+                sections.push(section);
             }
-            sections.push(section);
         }
 
         // 7. Active Context (Phase 3 — catch-all for anything not in a structured section)
-        let active = self.generate_active_context(agent_id, agent_node_id, &seen_ids)?;
-        if !active.nodes.is_empty() {
-            for n in &active.nodes {
-                seen_ids.insert(n.id);
+        if cfg.wants_section("active_context") {
+            let active = self.generate_active_context(&cfg, agent_id, agent_node_id, &seen_ids)?;
+            if !active.nodes.is_empty() {
+                for n in &active.nodes {
+                    seen_ids.insert(n.id);
+                }
+                sections.push(active);
             }
-            sections.push(active);
         }
 
         // Enforce max_total_items across all sections
         let mut total = 0usize;
         for section in &mut sections {
-            let remaining = self.config.max_total_items.saturating_sub(total);
+            let remaining = cfg.max_total_items.saturating_sub(total);
             section.nodes.truncate(remaining);
             total += section.nodes.len();
         }
         sections.retain(|s| !s.nodes.is_empty());
 
         let nodes_consulted = sections.iter().map(|s| s.nodes.len()).sum();
+        let relations = self.collect_relations(&cfg, &sections)?;
 
         let briefing = Briefing {
             agent_id: agent_id.to_string(),
             generated_at: Utc::now(),
             nodes_consulted,
             sections,
+            relations,
             cached: false,
         };
 
@@ -284,22 +507,80 @@ where
 
         // Update access counts (best-effort — failure must not block the caller)
         let _ = self.on_briefing_served(&briefing);
+        self.mark_briefed(agent_id, started_at);
+
+        Ok(briefing)
+    }
+
+    /// Generate a briefing holding only nodes created or updated after
+    /// `since`, newest first, in a single "Changes" section. Never cached.
+    pub fn generate_diff(&self, agent_id: &str, since: DateTime<Utc>) -> Result<Briefing> {
+        // Stamp before reading so writes racing with this call show up next time
+        let started_at = Utc::now();
+        let cfg = self.config.read().unwrap().for_agent(agent_id);
+        let excluded: HashSet<&str> = cfg.exclude_kinds.iter().map(|s| s.as_str()).collect();
+
+        let mut nodes: Vec<Node> = self
+            .storage
+            .list_nodes(
+                NodeFilter::new()
+                    .changed_after(since)
+                    .with_min_importance(cfg.min_importance),
+            )?
+            .into_iter()
+            .filter(|n| !excluded.contains(n.kind.as_str()) && cfg.shows(n))
+            // Serving a briefing bumps updated_at along with last_accessed_at;
+            // that alone is not a change worth reporting.
+            .filter(|n| n.created_at > since || n.updated_at != n.last_accessed_at)
+            .collect();
+        nodes.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then(a.id.cmp(&b.id)));
+        nodes.truncate(cfg.max_total_items);
+
+        let mut sections = Vec::new();
+        if !nodes.is_empty() {
+            sections.push(BriefingSection {
+                title: "Changes".to_string(),
+                nodes,
+            });
+        }
+        let relations = self.collect_relations(&cfg, &sections)?;
+        let briefing = Briefing {
+            agent_id: agent_id.to_string(),
+            generated_at: started_at,
+            nodes_consulted: sections.iter().map(|s| s.nodes.len()).sum(),
+            sections,
+            relations,
+            cached: false,
+        };
+
+        let _ = self.on_briefing_served(&briefing);
+        self.mark_briefed(agent_id, started_at);
 
         Ok(briefing)
     }
 
     /// Render a briefing to a string. compact=true gives ~4x higher density.
     pub fn render(&self, briefing: &Briefing, compact: bool) -> String {
-        if compact {
-            CompactRenderer {
-                max_chars: self.config.max_chars,
-            }
-            .render(briefing)
+        let format = if compact {
+            BriefingFormat::Compact
         } else {
-            MarkdownRenderer {
-                max_chars: self.config.max_chars,
-            }
-            .render(briefing)
+            BriefingFormat::Markdown
+        };
+        self.render_as(briefing, format)
+    }
+
+    /// Render a briefing in the given format.
+    pub fn render_as(&self, briefing: &Briefing, format: BriefingFormat) -> String {
+        let max_chars = self
+            .config
+            .read()
+            .unwrap()
+            .for_agent(&briefing.agent_id)
+            .max_chars;
+        match format {
+            BriefingFormat::Markdown => MarkdownRenderer { max_chars }.render(briefing),
+            BriefingFormat::Compact => CompactRenderer { max_chars }.render(briefing),
+            BriefingFormat::Json => JsonRenderer.render(briefing),
         }
     }
 
@@ -324,19 +605,52 @@ where
 
     // --- Helpers ---
 
-    /// Filter nodes below `min_importance` and sort by importance desc,
-    /// access_count desc. Applied uniformly across all section generators.
-    fn rank(&self, mut nodes: Vec<Node>) -> Vec<Node> {
-        nodes.retain(|n| n.importance >= self.config.min_importance);
-        nodes.sort_by(|a, b| {
-            b.importance
-                .partial_cmp(&a.importance)
-                .unwrap_or(std::cmp::Ordering::Equal)
-                .then_with(|| b.access_count.cmp(&a.access_count))
-        });
+    /// Filter nodes below `min_importance` or expired and sort by importance
+    /// desc, access_count desc. Applied uniformly across all section generators.
+    fn rank(&self, cfg: &BriefingConfig, mut nodes: Vec<Node>) -> Vec<Node> {
+        nodes.retain(|n| n.importance >= cfg.min_importance && cfg.shows(n));
+        sort_by_importance(&mut nodes);
         nodes
     }
 
+    /// Strongest 1-hop relations (by edge weight) of every node in
+    /// `sections`, capped at `max_relations_per_node` each.
+    fn collect_relations(
+        &self,
+        cfg: &BriefingConfig,
+        sections: &[BriefingSection],
+    ) -> Result<HashMap<NodeId, Vec<BriefingRelation>>> {
+        let mut relations = HashMap::new();
+        if !cfg.include_relations || cfg.max_relations_per_node == 0 {
+            return Ok(relations);
+        }
+        for node in sections.iter().flat_map(|s| &s.nodes) {
+            let mut edges: Vec<(Node, crate::types::Edge)> = self
+                .graph
+                .neighbors(node.id, TraversalDirection::Both, None)?
+                .into_iter()
+                .filter(|(n, e)| !n.deleted && cfg.shows(n) && e.weight >= cfg.min_weight)
+                .collect();
+            edges.sort_by(|(_, a), (_, b)| b.weight.total_cmp(&a.weight));
+            edges.truncate(cfg.max_relations_per_node);
+            if edges.is_empty() {
+                continue;
+            }
+            let list = edges
+                .into_iter()
+                .map(|(n, e)| BriefingRelation {
+                    relation: e.relation,
+                    outgoing: e.from == node.id,
+                    node_id: n.id,
+                    title: n.data.title,
+                    weight: e.weight,
+                })
+                .collect();
+            relations.insert(node.id, list);
+        }
+        Ok(relations)
+    }
+
     // --- Private section generators ---
 
     fn find_agent_node(&self, agent_id: &str) -> Result<Option<NodeId>> {
@@ -387,6 +701,7 @@ where
 
     fn generate_identity(
         &self,
+        cfg: &BriefingConfig,
         agent_id: &str,
         agent_node_id: Option<NodeId>,
     ) -> Result<BriefingSection> {
@@ -395,7 +710,9 @@ where
         if let Some(aid) = agent_node_id {
             // Include the Agent node itself (always, regardless of importance)
             if let Ok(Some(agent_node)) = self.storage.get_node(aid) {
-                nodes.push(agent_node);
+                if cfg.shows(&agent_node) {
+                    nodes.push(agent_node);
+                }
             }
 
             // Preferences/Facts connected via AppliesTo (either direction)
@@ -417,8 +734,8 @@ where
                 .collect();
 
             // Rank and append (keeping the agent node at the front)
-            let mut ranked = self.rank(pref_nodes);
-            ranked.truncate(self.config.max_items_per_section.saturating_sub(1));
+            let mut ranked = self.rank(cfg, pref_nodes);
+            ranked.truncate(cfg.max_items_per_section.saturating_sub(1));
             nodes.extend(ranked);
         } else {
             // Graceful degradation: no graph node, scan storage
@@ -429,13 +746,13 @@ where
                         NodeKind::new("agent").unwrap(),
                         NodeKind::new("preference").unwrap(),
                     ])
-                    .with_min_importance(self.config.min_importance)
-                    .with_limit(self.config.max_items_per_section * 2),
+                    .with_min_importance(cfg.min_importance)
+                    .with_limit(cfg.max_items_per_section * 2),
             )?;
-            nodes.extend(self.rank(fallback));
+            nodes.extend(self.rank(cfg, fallback));
         }
 
-        nodes.truncate(self.config.max_items_per_section);
+        nodes.truncate(cfg.max_items_per_section);
 
         Ok(BriefingSection {
             title: "Identity & Preferences".to_string(),
@@ -445,19 +762,19 @@ where
 
     fn generate_active_context(
         &self,
+        cfg: &BriefingConfig,
         agent_id: &str,
         agent_node_id: Option<NodeId>,
         seen: &HashSet<NodeId>,
     ) -> Result<BriefingSection> {
-        let cutoff =
-            Utc::now() - chrono::Duration::seconds(self.config.recent_window.as_secs() as i64);
+        let cutoff = Utc::now() - chrono::Duration::seconds(cfg.recent_window.as_secs() as i64);
 
         // Try agent-specific first, then fall back to global
         let mut recent = self.storage.list_nodes(
             NodeFilter::new()
                 .with_source_agent(agent_id.to_string())
                 .created_after(cutoff)
-                .with_limit(self.config.max_items_per_section * 3),
+                .with_limit(cfg.max_items_per_section * 3),
         )?;
 
         // Fallback: if agent has no recent nodes, pull from the entire graph
@@ -465,8 +782,8 @@ where
             recent = self.storage.list_nodes(
                 NodeFilter::new()
                     .created_after(cutoff)
-                    .with_min_importance(self.config.min_importance)
-                    .with_limit(self.config.max_items_per_section * 3),
+                    .with_min_importance(cfg.min_importance)
+                    .with_limit(cfg.max_items_per_section * 3),
             )?;
         }
 
@@ -474,8 +791,8 @@ where
         if recent.is_empty() {
             recent = self.storage.list_nodes(
                 NodeFilter::new()
-                    .with_min_importance(self.config.min_importance)
-                    .with_limit(self.config.max_items_per_section * 3),
+                    .with_min_importance(cfg.min_importance)
+                    .with_limit(cfg.max_items_per_section * 3),
             )?;
         }
 
@@ -517,9 +834,10 @@ where
             self.graph.clone(),
         );
 
-        let query = HybridQuery::new(query_text)
+        let mut query = HybridQuery::new(query_text)
             .with_anchors(anchors)
-            .with_limit(self.config.max_items_per_section * 2);
+            .with_limit(cfg.max_items_per_section * 2);
+        query.include_expired = cfg.include_expired;
 
         let hybrid_results = hybrid.search(query).unwrap_or_default();
 
@@ -529,16 +847,16 @@ where
                 .map(|r| r.node)
                 .filter(|n| !seen.contains(&n.id))
                 .collect();
-            candidates = self.rank(candidates);
-            candidates.truncate(self.config.max_items_per_section);
+            candidates = self.rank(cfg, candidates);
+            candidates.truncate(cfg.max_items_per_section);
             candidates
         } else {
             let candidates: Vec<Node> = recent
                 .into_iter()
                 .filter(|n| !seen.contains(&n.id))
                 .collect();
-            let mut ranked = self.rank(candidates);
-            ranked.truncate(self.config.max_items_per_section);
+            let mut ranked = self.rank(cfg, candidates);
+            ranked.truncate(cfg.max_items_per_section);
             ranked
         };
 
@@ -550,6 +868,7 @@ where
 
     fn generate_patterns(
         &self,
+        cfg: &BriefingConfig,
         agent_node_id: NodeId,
         seen: &HashSet<NodeId>,
     ) -> Result<BriefingSection> {
@@ -562,6 +881,7 @@ where
                 Relation::new("instance_of").unwrap(),
             ]),
             kind_filter: Some(vec![NodeKind::new("pattern").unwrap()]),
+            include_expired: cfg.include_expired,
             ..Default::default()
         })?;
 
@@ -571,8 +891,8 @@ where
             .filter(|n| n.id != agent_node_id && !seen.contains(&n.id))
             .collect();
 
-        let mut nodes = self.rank(candidates);
-        nodes.truncate(self.config.max_items_per_section);
+        let mut nodes = self.rank(cfg, candidates);
+        nodes.truncate(cfg.max_items_per_section);
 
         Ok(BriefingSection {
             title: "Patterns".to_string(),
@@ -582,6 +902,7 @@ where
 
     fn generate_goals(
         &self,
+        cfg: &BriefingConfig,
         agent_node_id: NodeId,
         seen: &HashSet<NodeId>,
     ) -> Result<BriefingSection> {
@@ -590,6 +911,7 @@ where
             max_depth: Some(2),
             direction: TraversalDirection::Both,
             kind_filter: Some(vec![NodeKind::new("goal").unwrap()]),
+            include_expired: cfg.include_expired,
             ..Default::default()
         })?;
 
@@ -599,8 +921,8 @@ where
             .filter(|n| n.id != agent_node_id && !seen.contains(&n.id))
             .collect();
 
-        let mut nodes = self.rank(candidates);
-        nodes.truncate(self.config.max_items_per_section);
+        let mut nodes = self.rank(cfg, candidates);
+        nodes.truncate(cfg.max_items_per_section);
 
         Ok(BriefingSection {
             title: "Goals".to_string(),
@@ -610,6 +932,7 @@ where
 
     fn generate_unresolved(
         &self,
+        cfg: &BriefingConfig,
         agent_node_id: NodeId,
         seen: &HashSet<NodeId>,
     ) -> Result<BriefingSection> {
@@ -620,6 +943,7 @@ where
             start: vec![agent_node_id],
             max_depth: Some(3),
             direction: TraversalDirection::Both,
+            include_expired: cfg.include_expired,
             ..Default::default()
         })?;
 
@@ -646,7 +970,7 @@ where
                 .partial_cmp(&a.importance)
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
-        nodes.truncate(self.config.max_items_per_section);
+        nodes.truncate(cfg.max_items_per_section);
 
         Ok(BriefingSection {
             title: "Unresolved Contradictions".to_string(),
@@ -656,11 +980,11 @@ where
 
     fn generate_recent_events(
         &self,
+        cfg: &BriefingConfig,
         agent_id: &str,
         seen: &HashSet<NodeId>,
     ) -> Result<BriefingSection> {
-        let cutoff =
-            Utc::now() - chrono::Duration::seconds(self.config.recent_window.as_secs() as i64);
+        let cutoff = Utc::now() - chrono::Duration::seconds(cfg.recent_window.as_secs() as i64);
 
         // Try agent-specific events first, fall back to global
         let mut raw = self.storage.list_nodes(
@@ -668,7 +992,7 @@ where
                 .with_source_agent(agent_id.to_string())
                 .with_kinds(vec![NodeKind::new("event").unwrap()])
                 .created_after(cutoff)
-                .with_limit(self.config.max_items_per_section * 2),
+                .with_limit(cfg.max_items_per_section * 2),
         )?;
 
         if raw.is_empty() {
@@ -676,14 +1000,14 @@ where
                 NodeFilter::new()
                     .with_kinds(vec![NodeKind::new("event").unwrap()])
                     .created_after(cutoff)
-                    .with_limit(self.config.max_items_per_section * 2),
+                    .with_limit(cfg.max_items_per_section * 2),
             )?;
         }
 
         let candidates: Vec<Node> = raw.into_iter().filter(|n| !seen.contains(&n.id)).collect();
 
-        let mut nodes = self.rank(candidates);
-        nodes.truncate(self.config.max_items_per_section);
+        let mut nodes = self.rank(cfg, candidates);
+        nodes.truncate(cfg.max_items_per_section);
 
         Ok(BriefingSection {
             title: "Recent Events".to_string(),
@@ -691,49 +1015,84 @@ where
         })
     }
 
-    /// Global fallback: query nodes by kind without requiring graph traversal.
-    /// Used when no agent node exists in the graph.
-    fn generate_global_by_kind(
+    /// Query nodes by kind without requiring graph traversal. Used for
+    /// configured kind sections and auto-discovered kinds.
+    fn generate_kind_section(
         &self,
-        kind: &str,
-        section_title: &str,
+        cfg: &BriefingConfig,
+        spec: &BriefingSectionSpec,
         seen: &HashSet<NodeId>,
     ) -> Result<BriefingSection> {
-        let candidates: Vec<Node> = self
-            .storage
-            .list_nodes(
-                NodeFilter::new()
-                    .with_kinds(vec![NodeKind::new(kind).unwrap()])
-                    .with_min_importance(self.config.min_importance)
-                    .with_limit(self.config.max_items_per_section * 2),
-            )?
-            .into_iter()
-            .filter(|n| !seen.contains(&n.id))
+        let min_importance = spec.min_importance.unwrap_or(cfg.min_importance);
+        let limit = spec.limit.unwrap_or(cfg.max_items_per_section);
+        let kinds: Vec<NodeKind> = spec
+            .kinds
+            .iter()
+            .filter_map(|k| NodeKind::new(k).ok())
             .collect();
 
-        let mut nodes = self.rank(candidates);
-        nodes.truncate(self.config.max_items_per_section);
+        let filter = NodeFilter::new()
+            .with_kinds(kinds)
+            .with_min_importance(min_importance);
+        let keep = |n: &Node| !seen.contains(&n.id) && cfg.shows(n);
+        let mut nodes: Vec<Node> = match spec.order {
+            // Listing runs newest first, so the most important nodes can be
+            // anywhere in it
+            SectionOrder::Importance => {
+                let mut nodes: Vec<Node> = self
+                    .storage
+                    .list_nodes(filter)?
+                    .into_iter()
+                    .filter(keep)
+                    .collect();
+                sort_by_importance(&mut nodes);
+                nodes
+            }
+            // ...but the most recent are at the front: page until the
+            // section is full
+            SectionOrder::Recent => {
+                let mut nodes = Vec::new();
+                let mut cursor = None;
+                while nodes.len() < limit {
+                    let mut page_filter = filter.clone().with_limit(limit);
+                    if let Some(c) = cursor {
+                        page_filter = page_filter.after(c);
+                    }
+                    let page = self.storage.list_nodes(page_filter)?;
+                    let full = page.len() == limit;
+                    cursor = page.last().map(NodeCursor::after);
+                    nodes.extend(page.into_iter().filter(keep));
+                    if !full {
+                        break;
+                    }
+                }
+                nodes
+            }
+        };
+        nodes.truncate(limit);
 
         Ok(BriefingSection {
-            title: section_title.to_string(),
+            title: spec.heading.clone(),
             nodes,
         })
     }
 
     /// Phase 2: Generate sections for node kinds not covered by the default
-    /// structured generators. Uses `generate_global_by_kind` for each novel kind.
+    /// structured generators or a kind section. Uses `generate_kind_section`
+    /// for each novel kind.
     fn generate_auto_discovered_sections(
         &self,
+        cfg: &BriefingConfig,
         seen: &HashSet<NodeId>,
     ) -> Result<Vec<BriefingSection>> {
         let all_kinds = self.storage.list_distinct_kinds()?;
 
         let default_kinds: HashSet<&str> = DEFAULT_SECTION_KINDS.iter().copied().collect();
 
-        let excluded: HashSet<&str> = self
-            .config
+        let excluded: HashSet<&str> = cfg
             .exclude_kinds
             .iter()
+            .chain(cfg.kind_sections.iter().flat_map(|s| &s.kinds))
             .map(|s| s.as_str())
             .collect();
 
@@ -746,8 +1105,9 @@ where
         let mut sections = Vec::new();
 
         for kind in novel_kinds {
-            let title = kind_to_section_title(kind.as_str());
-            let section = self.generate_global_by_kind(kind.as_str(), &title, seen)?;
+            let spec =
+                BriefingSectionSpec::new(&kind_to_section_title(kind.as_str()), &[kind.as_str()]);
+            let section = self.generate_kind_section(cfg, &spec, seen)?;
 
             if !section.nodes.is_empty() {
                 sections.push(section);
@@ -1141,6 +1501,7 @@ mod tests {
                     "test",
                 )],
             }],
+            relations: HashMap::new(),
             cached: false,
         };
 
@@ -1191,6 +1552,59 @@ mod tests {
         assert!(!b2.cached, "After version bump, cache must be invalid");
     }
 
+    #[test]
+    fn test_uncached_skips_cache_and_refreshes_it() {
+        let dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
+
+        let agent = make_node(NodeKind::new("agent").unwrap(), "kai", "kai");
+        storage.put_node(&agent).unwrap();
+
+        let (engine, _) = make_engine(storage);
+
+        engine.generate("kai").unwrap();
+        let fresh = engine.generate_uncached("kai").unwrap();
+        assert!(!fresh.cached);
+        assert!(engine.generate("kai").unwrap().cached);
+    }
+
+    #[test]
+    fn test_compact_render_is_shorter_than_markdown() {
+        let dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
+
+        let agent = make_node(NodeKind::new("agent").unwrap(), "kai", "kai");
+        storage.put_node(&agent).unwrap();
+        for title in [
+            "Use redb for storage",
+            "Prefer small PRs",
+            "JWT tokens expire after an hour",
+            "Deploys go out on Tuesdays",
+        ] {
+            let kind = if title.starts_with("Use") {
+                "decision"
+            } else {
+                "fact"
+            };
+            storage
+                .put_node(&make_node(NodeKind::new(kind).unwrap(), title, "kai"))
+                .unwrap();
+        }
+
+        let (engine, _) = make_engine(storage);
+        let briefing = engine.generate("kai").unwrap();
+        assert!(briefing.nodes_consulted > 0);
+
+        let full = engine.render_as(&briefing, BriefingFormat::Markdown);
+        let compact = engine.render_as(&briefing, BriefingFormat::Compact);
+        assert!(
+            compact.len() < full.len(),
+            "compact {} >= full {}",
+            compact.len(),
+            full.len()
+        );
+    }
+
     // Test 10: access_count incremented after briefing is served
     #[test]
     fn test_access_tracking_increments_count() {
@@ -1228,6 +1642,7 @@ mod tests {
                     "kai",
                 )],
             }],
+            relations: HashMap::new(),
             cached: false,
         };
 
@@ -1263,6 +1678,7 @@ mod tests {
                     })
                     .collect(),
             }],
+            relations: HashMap::new(),
             cached: false,
         };
 
@@ -1507,6 +1923,7 @@ mod tests {
                 title: "Facts".to_string(),
                 nodes: vec![node],
             }],
+            relations: HashMap::new(),
             cached: false,
         };
 
@@ -1641,8 +2058,7 @@ mod tests {
         let dir = TempDir::new().unwrap();
         let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
 
-        let mut experiment =
-            make_node(NodeKind::new("experiment").unwrap(), "Test A/B", "kai");
+        let mut experiment = make_node(NodeKind::new("experiment").unwrap(), "Test A/B", "kai");
         experiment.importance = 0.8;
         storage.put_node(&experiment).unwrap();
 
@@ -1666,8 +2082,7 @@ mod tests {
         let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
 
         // Low importance kind
-        let mut insight =
-            make_node(NodeKind::new("insight").unwrap(), "Small insight", "kai");
+        let mut insight = make_node(NodeKind::new("insight").unwrap(), "Small insight", "kai");
         insight.importance = 0.4;
         storage.put_node(&insight).unwrap();
 
@@ -1712,8 +2127,7 @@ mod tests {
         let dir = TempDir::new().unwrap();
         let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
 
-        let mut experiment =
-            make_node(NodeKind::new("experiment").unwrap(), "Low exp", "kai");
+        let mut experiment = make_node(NodeKind::new("experiment").unwrap(), "Low exp", "kai");
         experiment.importance = 0.1; // Below default min_importance of 0.3
         storage.put_node(&experiment).unwrap();
 
@@ -1721,10 +2135,7 @@ mod tests {
         let briefing = engine.generate("kai").unwrap();
 
         assert!(
-            !briefing
-                .sections
-                .iter()
-                .any(|s| s.title == "Experiments"),
+            !briefing.sections.iter().any(|s| s.title == "Experiments"),
             "Low-importance novel kind should not produce a section"
         );
     }
@@ -1735,8 +2146,7 @@ mod tests {
         let dir = TempDir::new().unwrap();
         let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
 
-        let mut experiment =
-            make_node(NodeKind::new("experiment").unwrap(), "Test A/B", "kai");
+        let mut experiment = make_node(NodeKind::new("experiment").unwrap(), "Test A/B", "kai");
         experiment.importance = 0.8;
         storage.put_node(&experiment).unwrap();
 
@@ -1746,16 +2156,12 @@ mod tests {
         };
         let graph = Arc::new(GraphEngineImpl::new(storage.clone()));
         let gv = Arc::new(AtomicU64::new(0));
-        let engine =
-            BriefingEngine::new(storage, graph, MockVectorIndex, MockEmbedder, gv, config);
+        let engine = BriefingEngine::new(storage, graph, MockVectorIndex, MockEmbedder, gv, config);
 
         let briefing = engine.generate("kai").unwrap();
 
         assert!(
-            !briefing
-                .sections
-                .iter()
-                .any(|s| s.title == "Experiments"),
+            !briefing.sections.iter().any(|s| s.title == "Experiments"),
             "Excluded kind should not produce a section"
         );
     }
@@ -1801,8 +2207,7 @@ mod tests {
         let dir = TempDir::new().unwrap();
         let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
 
-        let mut experiment =
-            make_node(NodeKind::new("experiment").unwrap(), "Shared exp", "kai");
+        let mut experiment = make_node(NodeKind::new("experiment").unwrap(), "Shared exp", "kai");
         experiment.importance = 0.8;
         storage.put_node(&experiment).unwrap();
 
@@ -1831,8 +2236,7 @@ mod tests {
         let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
 
         // Create a novel-kind node and a fact (to populate Active Context)
-        let mut experiment =
-            make_node(NodeKind::new("experiment").unwrap(), "Novel exp", "kai");
+        let mut experiment = make_node(NodeKind::new("experiment").unwrap(), "Novel exp", "kai");
         experiment.importance = 0.8;
         storage.put_node(&experiment).unwrap();
 
@@ -1871,8 +2275,7 @@ mod tests {
         let fact = make_node(NodeKind::new("fact").unwrap(), "A fact", "kai");
         storage.put_node(&fact).unwrap();
 
-        let experiment =
-            make_node(NodeKind::new("experiment").unwrap(), "An exp", "kai");
+        let experiment = make_node(NodeKind::new("experiment").unwrap(), "An exp", "kai");
         storage.put_node(&experiment).unwrap();
 
         // Add a second fact — should not duplicate
@@ -1886,4 +2289,364 @@ mod tests {
         assert!(kind_strs.contains(&"experiment"));
         assert!(kind_strs.contains(&"fact"));
     }
+
+    #[test]
+    fn test_per_agent_overrides_shape_briefing() {
+        let dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
+
+        let kai = make_node(NodeKind::new("agent").unwrap(), "kai", "kai");
+        storage.put_node(&kai).unwrap();
+        for i in 0..8 {
+            for kind in ["goal", "pattern"] {
+                let n = make_node(NodeKind::new(kind).unwrap(), &format!("{kind} {i}"), "ops");
+                storage.put_node(&n).unwrap();
+                storage
+                    .put_edge(&manual_edge(
+                        n.id,
+                        kai.id,
+                        Relation::new("applies_to").unwrap(),
+                    ))
+                    .unwrap();
+            }
+            let decision = make_node(
+                NodeKind::new("decision").unwrap(),
+                &format!("decision {i}"),
+                "ops",
+            );
+            storage.put_node(&decision).unwrap();
+        }
+
+        let mut config = BriefingConfig::default();
+        config.agents.insert(
+            "kai".into(),
+            AgentBriefingConfig {
+                max_items_per_section: Some(5),
+                sections: Some(vec!["goals".into(), "decisions".into()]),
+                ..Default::default()
+            },
+        );
+        let graph = Arc::new(GraphEngineImpl::new(storage.clone()));
+        let gv = Arc::new(AtomicU64::new(0));
+        let engine = BriefingEngine::new(storage, graph, MockVectorIndex, MockEmbedder, gv, config);
+
+        let kai_briefing = engine.generate("kai").unwrap();
+        let titles: Vec<&str> = kai_briefing
+            .sections
+            .iter()
+            .map(|s| s.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Goals", "Key Decisions"]);
+        assert!(kai_briefing.sections.iter().all(|s| s.nodes.len() == 5));
+
+        // No overrides: the default sections and caps
+        let other = engine.generate("ada").unwrap();
+        let titles: Vec<&str> = other.sections.iter().map(|s| s.title.as_str()).collect();
+        assert!(titles.contains(&"Patterns"), "{:?}", titles);
+        assert!(titles.contains(&"Goals"), "{:?}", titles);
+        let goals = other.sections.iter().find(|s| s.title == "Goals").unwrap();
+        assert_eq!(goals.nodes.len(), 8);
+    }
+
+    #[test]
+    fn test_for_agent_falls_back_to_defaults() {
+        let mut config = BriefingConfig::default();
+        config.agents.insert(
+            "kai".into(),
+            AgentBriefingConfig {
+                min_importance: Some(0.8),
+                ..Default::default()
+            },
+        );
+        assert_eq!(config.for_agent("kai").min_importance, 0.8);
+        assert_eq!(
+            config.for_agent("kai").max_items_per_section,
+            config.max_items_per_section
+        );
+        assert_eq!(config.for_agent("unknown").min_importance, 0.3);
+    }
+
+    #[test]
+    fn test_generate_diff_only_includes_newer_nodes() {
+        let dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
+
+        let old = make_node(NodeKind::new("fact").unwrap(), "Old fact", "kai");
+        storage.put_node(&old).unwrap();
+        let (engine, _) = make_engine(storage.clone());
+        assert!(engine.last_briefed("kai").is_none());
+
+        // A full briefing touches `old`'s access stats; that must not count as a change
+        engine.generate("kai").unwrap();
+        let since = engine.last_briefed("kai").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+
+        let new = make_node(NodeKind::new("fact").unwrap(), "New fact", "kai");
+        storage.put_node(&new).unwrap();
+        let mut edited = make_node(NodeKind::new("goal").unwrap(), "Edited goal", "kai");
+        edited.created_at = since - chrono::Duration::hours(1);
+        edited.last_accessed_at = edited.created_at;
+        storage.put_node(&edited).unwrap();
+
+        let diff = engine.generate_diff("kai", since).unwrap();
+        assert_eq!(diff.sections.len(), 1);
+        assert_eq!(diff.sections[0].title, "Changes");
+        let ids: HashSet<NodeId> = diff.sections[0].nodes.iter().map(|n| n.id).collect();
+        assert_eq!(ids, HashSet::from([new.id, edited.id]));
+        assert!(engine.render(&diff, false).contains("Changes"));
+
+        // Nothing changed since the diff itself
+        let again = engine
+            .generate_diff("kai", engine.last_briefed("kai").unwrap())
+            .unwrap();
+        assert!(again.sections.is_empty());
+    }
+
+    #[test]
+    fn test_custom_kind_section_renders() {
+        let dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
+
+        let kai = make_node(NodeKind::new("agent").unwrap(), "kai", "kai");
+        storage.put_node(&kai).unwrap();
+        let mut outage = make_node(NodeKind::new("risk").unwrap(), "Region outage", "ops");
+        outage.importance = 0.9;
+        let minor = make_node(NodeKind::new("risk").unwrap(), "Minor typo", "ops");
+        storage.put_node(&outage).unwrap();
+        storage.put_node(&minor).unwrap();
+
+        let mut config = BriefingConfig::default();
+        config.kind_sections.push(BriefingSectionSpec {
+            min_importance: Some(0.8),
+            ..BriefingSectionSpec::new("Open Risks", &["risk"])
+        });
+        let graph = Arc::new(GraphEngineImpl::new(storage.clone()));
+        let gv = Arc::new(AtomicU64::new(0));
+        let engine = BriefingEngine::new(storage, graph, MockVectorIndex, MockEmbedder, gv, config);
+
+        // With and without an agent node in the graph
+        for agent in ["kai", "ada"] {
+            let briefing = engine.generate(agent).unwrap();
+            let titles: Vec<&str> = briefing.sections.iter().map(|s| s.title.as_str()).collect();
+            // The spec claims `risk`, so auto-discovery leaves it alone
+            assert!(!titles.contains(&"Risks"), "{agent}: {titles:?}");
+            let risks = briefing
+                .sections
+                .iter()
+                .find(|s| s.title == "Open Risks")
+                .expect("custom section missing");
+            let ids: Vec<NodeId> = risks.nodes.iter().map(|n| n.id).collect();
+            assert_eq!(ids, [outage.id]);
+
+            let rendered = engine.render(&briefing, false);
+            assert!(rendered.contains("Open Risks"), "{rendered}");
+            assert!(rendered.contains("Region outage"), "{rendered}");
+        }
+    }
+
+    #[test]
+    fn test_kind_sections_look_past_newest_and_hidden_nodes() {
+        let dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
+
+        // One old, important risk behind a run of newer, minor ones
+        let base = Utc::now();
+        let mut outage = make_node(NodeKind::new("risk").unwrap(), "Region outage", "ops");
+        outage.importance = 0.9;
+        outage.created_at = base - chrono::Duration::days(30);
+        storage.put_node(&outage).unwrap();
+        for i in 0..6 {
+            let mut minor = make_node(NodeKind::new("risk").unwrap(), &format!("Typo {i}"), "ops");
+            minor.created_at = base - chrono::Duration::hours(i);
+            storage.put_node(&minor).unwrap();
+        }
+        // Newer still, but expired, so hidden
+        let mut expired = make_node(NodeKind::new("incident").unwrap(), "Stale alert", "ops");
+        expired.expires_at = Some(base - chrono::Duration::hours(1));
+        storage.put_node(&expired).unwrap();
+        let mut live = make_node(NodeKind::new("incident").unwrap(), "Disk full", "ops");
+        live.created_at = base - chrono::Duration::hours(2);
+        storage.put_node(&live).unwrap();
+
+        let mut config = BriefingConfig::default();
+        config.kind_sections.push(BriefingSectionSpec {
+            limit: Some(2),
+            ..BriefingSectionSpec::new("Risks", &["risk"])
+        });
+        config.kind_sections.push(BriefingSectionSpec {
+            limit: Some(1),
+            order: SectionOrder::Recent,
+            ..BriefingSectionSpec::new("Incidents", &["incident"])
+        });
+        let graph = Arc::new(GraphEngineImpl::new(storage.clone()));
+        let gv = Arc::new(AtomicU64::new(0));
+        let engine = BriefingEngine::new(storage, graph, MockVectorIndex, MockEmbedder, gv, config);
+
+        let briefing = engine.generate("kai").unwrap();
+        let section = |title: &str| {
+            briefing
+                .sections
+                .iter()
+                .find(|s| s.title == title)
+                .unwrap_or_else(|| panic!("{title} missing"))
+                .nodes
+                .iter()
+                .map(|n| n.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(section("Risks")[0], outage.id);
+        assert_eq!(section("Incidents"), [live.id]);
+    }
+
+    #[test]
+    fn test_relations_included_only_when_enabled() {
+        let dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
+
+        let new = make_node(NodeKind::new("decision").unwrap(), "Use Postgres", "kai");
+        let old = make_node(NodeKind::new("decision").unwrap(), "Use MySQL", "kai");
+        storage.put_node(&new).unwrap();
+        storage.put_node(&old).unwrap();
+        storage
+            .put_edge(&manual_edge(
+                new.id,
+                old.id,
+                Relation::new("supersedes").unwrap(),
+            ))
+            .unwrap();
+        // Weaker edges that the per-node cap should drop
+        for i in 0..3 {
+            let fact = make_node(NodeKind::new("fact").unwrap(), &format!("fact {i}"), "kai");
+            storage.put_node(&fact).unwrap();
+            let mut edge = manual_edge(new.id, fact.id, Relation::new("related_to").unwrap());
+            edge.weight = 0.5;
+            storage.put_edge(&edge).unwrap();
+        }
+
+        let build = |include_relations: bool| {
+            let config = BriefingConfig {
+                include_relations,
+                max_relations_per_node: 2,
+                ..Default::default()
+            };
+            let graph = Arc::new(GraphEngineImpl::new(storage.clone()));
+            BriefingEngine::new(
+                storage.clone(),
+                graph,
+                MockVectorIndex,
+                MockEmbedder,
+                Arc::new(AtomicU64::new(0)),
+                config,
+            )
+        };
+
+        let engine = build(true);
+        let briefing = engine.generate("kai").unwrap();
+        let relations = &briefing.relations[&new.id];
+        assert_eq!(relations.len(), 2);
+        assert_eq!(relations[0].relation.as_str(), "supersedes");
+        assert!(relations[0].outgoing);
+        assert_eq!(relations[0].node_id, old.id);
+        assert!(!briefing.relations[&old.id][0].outgoing);
+
+        let rendered = engine.render(&briefing, false);
+        assert!(rendered.contains("→ supersedes: Use MySQL"), "{rendered}");
+        assert!(
+            rendered.contains("← supersedes: Use Postgres"),
+            "{rendered}"
+        );
+        assert!(engine
+            .render(&briefing, true)
+            .contains("→ supersedes: Use MySQL"));
+
+        let engine = build(false);
+        let briefing = engine.generate("kai").unwrap();
+        assert!(briefing.relations.is_empty());
+        assert!(!engine.render(&briefing, false).contains("supersedes"));
+    }
+
+    #[test]
+    fn test_json_format_matches_markdown() {
+        let dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
+        for (kind, title) in [
+            ("goal", "Ship v2"),
+            ("goal", "Cut latency"),
+            ("decision", "Use Postgres"),
+            ("experiment", "Try HNSW"),
+        ] {
+            storage
+                .put_node(&make_node(NodeKind::new(kind).unwrap(), title, "kai"))
+                .unwrap();
+        }
+
+        let (engine, _) = make_engine(storage);
+        let briefing = engine.generate("kai").unwrap();
+        let markdown = engine.render_as(&briefing, BriefingFormat::Markdown);
+        let json: serde_json::Value =
+            serde_json::from_str(&engine.render_as(&briefing, BriefingFormat::Json)).unwrap();
+
+        assert_eq!(json["agent_id"], "kai");
+        let sections = json["sections"].as_array().unwrap();
+        assert_eq!(
+            sections.len(),
+            markdown.lines().filter(|l| l.starts_with("## ")).count()
+        );
+        let json_nodes: usize = sections
+            .iter()
+            .map(|s| s["nodes"].as_array().unwrap().len())
+            .sum();
+        assert_eq!(
+            json_nodes,
+            markdown.lines().filter(|l| l.starts_with("- **")).count()
+        );
+
+        let goals = sections.iter().find(|s| s["title"] == "Goals").unwrap();
+        let node = &goals["nodes"][0];
+        assert_eq!(node["kind"], "goal");
+        assert!(node["id"].as_str().unwrap().parse::<NodeId>().is_ok());
+        assert!(node["importance"].is_number());
+        assert!(node.get("embedding").is_none());
+    }
+
+    #[test]
+    fn test_expired_nodes_left_out_unless_included() {
+        let dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("t.redb")).unwrap());
+
+        let live = make_node(NodeKind::new("fact").unwrap(), "Office is open", "kai");
+        let mut expired = make_node(
+            NodeKind::new("fact").unwrap(),
+            "Office closed Monday",
+            "kai",
+        );
+        expired.expires_at = Some(Utc::now() - chrono::Duration::hours(1));
+        storage
+            .put_nodes_batch(&[live.clone(), expired.clone()])
+            .unwrap();
+
+        let ids = |briefing: &Briefing| -> HashSet<NodeId> {
+            briefing
+                .sections
+                .iter()
+                .flat_map(|s| s.nodes.iter().map(|n| n.id))
+                .collect()
+        };
+
+        let (engine, _) = make_engine(storage.clone());
+        let briefing = engine.generate("kai").unwrap();
+        assert!(ids(&briefing).contains(&live.id));
+        assert!(!ids(&briefing).contains(&expired.id));
+
+        let config = BriefingConfig {
+            include_expired: true,
+            ..Default::default()
+        };
+        let graph = Arc::new(GraphEngineImpl::new(storage.clone()));
+        let gv = Arc::new(AtomicU64::new(0));
+        let engine = BriefingEngine::new(storage, graph, MockVectorIndex, MockEmbedder, gv, config);
+        let briefing = engine.generate("kai").unwrap();
+        assert!(ids(&briefing).contains(&live.id));
+        assert!(ids(&briefing).contains(&expired.id));
+    }
 }
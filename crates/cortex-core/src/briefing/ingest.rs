@@ -124,7 +124,7 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex> FileIngest<S, E, V> {
                     self.storage.put_node(&node)?;
                     {
                         let mut index = self.vector_index.write().unwrap();
-                        let _ = index.insert(node.id, &embedding);
+                        let _ = index.insert_node(&node, &embedding);
                     }
                 }
                 Err(_) => {
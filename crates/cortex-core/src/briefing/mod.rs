@@ -3,26 +3,75 @@ pub mod engine;
 pub mod ingest;
 pub mod renderer;
 
-pub use engine::{BriefingConfig, BriefingEngine};
+pub use renderer::{BriefingFormat, JsonRenderer};
+
+pub use engine::{
+    AgentBriefingConfig, BriefingConfig, BriefingEngine, BriefingSectionSpec, SectionOrder,
+    BRIEFING_SECTIONS,
+};
 
 use chrono::{DateTime, Utc};
+use serde::ser::SerializeSeq;
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
 
-use crate::types::Node;
+use crate::types::{Node, NodeId, Relation};
 
 /// A synthesised context briefing for an agent
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Briefing {
     pub agent_id: String,
     pub generated_at: DateTime<Utc>,
     pub nodes_consulted: usize,
     pub sections: Vec<BriefingSection>,
+    /// Strongest 1-hop relations of each listed node, when
+    /// [`BriefingConfig::include_relations`] is on.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub relations: HashMap<NodeId, Vec<BriefingRelation>>,
     /// Whether this was served from cache
     pub cached: bool,
 }
 
 /// One named section within a briefing
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BriefingSection {
     pub title: String,
+    /// Serialised as `{id, kind, title, importance}` summaries.
+    #[serde(serialize_with = "serialize_node_summaries")]
     pub nodes: Vec<Node>,
 }
+
+fn serialize_node_summaries<S: Serializer>(
+    nodes: &[Node],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct Summary<'a> {
+        id: NodeId,
+        kind: &'a str,
+        title: &'a str,
+        importance: f32,
+    }
+
+    let mut seq = serializer.serialize_seq(Some(nodes.len()))?;
+    for node in nodes {
+        seq.serialize_element(&Summary {
+            id: node.id,
+            kind: node.kind.as_str(),
+            title: &node.data.title,
+            importance: node.importance,
+        })?;
+    }
+    seq.end()
+}
+
+/// An edge from a briefing node to one of its neighbours
+#[derive(Debug, Clone, Serialize)]
+pub struct BriefingRelation {
+    pub relation: Relation,
+    /// Whether the briefing node is the edge's source
+    pub outgoing: bool,
+    pub node_id: NodeId,
+    pub title: String,
+    pub weight: f32,
+}
@@ -1,4 +1,4 @@
-use super::{Briefing, BriefingSection};
+use super::{Briefing, BriefingRelation, BriefingSection};
 
 pub trait BriefingRenderer {
     fn render(&self, briefing: &Briefing) -> String;
@@ -12,6 +12,36 @@ pub struct CompactRenderer {
     pub max_chars: usize,
 }
 
+/// Serialises the briefing itself, for dashboards and other programs.
+/// Never truncated, so the output always parses.
+#[derive(Default)]
+pub struct JsonRenderer;
+
+/// Output format for [`BriefingEngine::render_as`](super::BriefingEngine::render_as).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BriefingFormat {
+    #[default]
+    Markdown,
+    Compact,
+    Json,
+}
+
+impl std::str::FromStr for BriefingFormat {
+    type Err = crate::error::CortexError;
+
+    fn from_str(s: &str) -> crate::error::Result<Self> {
+        match s {
+            "markdown" | "text" => Ok(Self::Markdown),
+            "compact" => Ok(Self::Compact),
+            "json" => Ok(Self::Json),
+            other => Err(crate::error::CortexError::Validation(format!(
+                "Unknown briefing format '{}' (expected markdown, compact or json)",
+                other
+            ))),
+        }
+    }
+}
+
 impl Default for MarkdownRenderer {
     fn default() -> Self {
         Self { max_chars: 8000 }
@@ -67,19 +97,36 @@ fn body_preview(s: &str, max_chars: usize) -> String {
     format!("{}...", &s[..byte_end])
 }
 
-fn render_section_markdown(section: &BriefingSection) -> String {
+/// `→ supersedes: Title` for outgoing edges, `← caused_by: Title` for incoming.
+fn relation_line(relation: &BriefingRelation) -> String {
+    let arrow = if relation.outgoing { '→' } else { '←' };
+    format!(
+        "{} {}: {}",
+        arrow,
+        relation.relation.as_str(),
+        truncate(&relation.title, 80)
+    )
+}
+
+fn render_section_markdown(section: &BriefingSection, briefing: &Briefing) -> String {
     let mut out = format!("## {}\n\n", section.title);
     for node in &section.nodes {
         let preview = body_preview(&node.data.body, 200);
         out.push_str(&format!("- **{}**: {}\n", node.data.title, preview));
+        for relation in briefing.relations.get(&node.id).into_iter().flatten() {
+            out.push_str(&format!("  - {}\n", relation_line(relation)));
+        }
     }
     out
 }
 
-fn render_section_compact(section: &BriefingSection) -> String {
+fn render_section_compact(section: &BriefingSection, briefing: &Briefing) -> String {
     let mut out = format!("## {}\n", section.title);
     for node in &section.nodes {
         out.push_str(&format!("- {}\n", node.data.title));
+        for relation in briefing.relations.get(&node.id).into_iter().flatten() {
+            out.push_str(&format!("  {}\n", relation_line(relation)));
+        }
     }
     out
 }
@@ -92,7 +139,7 @@ impl BriefingRenderer for MarkdownRenderer {
             briefing.generated_at.format("%Y-%m-%d %H:%M UTC")
         );
         for section in &briefing.sections {
-            out.push_str(&render_section_markdown(section));
+            out.push_str(&render_section_markdown(section, briefing));
             out.push('\n');
         }
         truncate(&out, self.max_chars)
@@ -103,8 +150,14 @@ impl BriefingRenderer for CompactRenderer {
     fn render(&self, briefing: &Briefing) -> String {
         let mut out = format!("# {}\n", briefing.agent_id);
         for section in &briefing.sections {
-            out.push_str(&render_section_compact(section));
+            out.push_str(&render_section_compact(section, briefing));
         }
         truncate(&out, self.max_chars)
     }
 }
+
+impl BriefingRenderer for JsonRenderer {
+    fn render(&self, briefing: &Briefing) -> String {
+        serde_json::to_string_pretty(briefing).unwrap_or_default()
+    }
+}
@@ -47,6 +47,12 @@ pub enum CortexError {
 
     #[error("Validation error: {0}")]
     Validation(String),
+
+    #[error("Storage is read-only")]
+    ReadOnly,
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 impl From<redb::Error> for CortexError {
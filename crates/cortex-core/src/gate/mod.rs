@@ -1,16 +1,38 @@
+pub mod pii;
 pub mod schema;
+pub mod stats;
 
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{Embedding, Node, Storage, VectorIndex};
+use crate::vector::cosine_similarity;
+use crate::{Edge, Embedding, Node, Storage, VectorIndex};
+pub use pii::{PiiConfig, PiiMode, PiiOutcome, PiiPattern};
+pub use stats::{GateFailureCount, GateStats, GateStatsSnapshot};
+
+/// Metadata key listing the checks a node failed but was stored anyway
+/// (warn mode).
+pub const GATE_WARNINGS_KEY: &str = "gate_warnings";
+
+/// What happens to a node that fails a gate check.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GateMode {
+    /// Reject the write.
+    #[default]
+    Enforce,
+    /// Store the node, recording each failure under `gate_warnings`.
+    Warn,
+}
 
 /// Configuration for the write gate.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct WriteGateConfig {
     pub enabled: bool,
+    /// Reject failing nodes, or store them with warnings. Default: enforce.
+    pub mode: GateMode,
     /// Cosine similarity above which a conflict/contradiction is flagged.
     pub conflict_threshold: f32,
     /// Cosine similarity above which a node is always rejected as a duplicate.
@@ -23,12 +45,38 @@ pub struct WriteGateConfig {
     pub require_body_length_for_importance: bool,
     /// Per-kind threshold overrides.
     pub overrides: HashMap<String, KindOverrideConfig>,
+    /// Per-kind keyword rules for the substance check. An entry replaces the
+    /// built-in rules for that kind; an empty entry turns them off. Any
+    /// kind, including custom ones, can be given rules.
+    pub kind_keywords: HashMap<String, KindKeywordRules>,
+    /// Personal data detection. Disabled by default.
+    pub pii: PiiConfig,
+    /// Callers whose writes are validated upstream (e.g. an ingest
+    /// pipeline). Matched against the caller's self-asserted `x-agent-id`,
+    /// not the node's `source.agent`, so only list names on a network where
+    /// every client is trusted. Their nodes skip the substance and
+    /// specificity checks but still go through conflict detection.
+    pub trusted_agents: Vec<String>,
+    /// Reject a new `supersedes` edge that would close a cycle (A
+    /// supersedes B supersedes ... supersedes A). Off by default.
+    pub reject_supersedes_cycles: bool,
+    /// Relative time references the specificity check rejects in the title
+    /// or at the start of the body. Case-insensitive. Empty = no check.
+    pub relative_terms: Vec<String>,
+    /// Words that, as the first word of the body, are an unresolved
+    /// reference unless the title names a proper noun. Case-insensitive.
+    /// Empty = no check.
+    pub ambiguous_starts: Vec<String>,
+    /// Capitalised title words that don't count as a proper noun when
+    /// resolving an ambiguous start. Matched exactly.
+    pub stopwords: Vec<String>,
 }
 
 impl Default for WriteGateConfig {
     fn default() -> Self {
         Self {
             enabled: true,
+            mode: GateMode::default(),
             conflict_threshold: 0.85,
             duplicate_threshold: 0.92,
             min_title_length: 10,
@@ -36,16 +84,149 @@ impl Default for WriteGateConfig {
             require_tags_above_importance: 0.7,
             require_body_length_for_importance: true,
             overrides: HashMap::new(),
+            kind_keywords: HashMap::new(),
+            pii: PiiConfig::default(),
+            trusted_agents: Vec::new(),
+            reject_supersedes_cycles: false,
+            relative_terms: words(&[
+                "yesterday",
+                "last week",
+                "last month",
+                "last year",
+                "this morning",
+                "this afternoon",
+                "this evening",
+                "last night",
+                "earlier today",
+            ]),
+            ambiguous_starts: words(&["he", "she", "they", "it"]),
+            stopwords: words(&[
+                "The", "A", "An", "This", "That", "These", "Those", "He", "She", "They", "It",
+                "In", "On", "At", "For", "With",
+            ]),
         }
     }
 }
 
+fn words(w: &[&str]) -> Vec<String> {
+    w.iter().map(|s| s.to_string()).collect()
+}
+
+impl WriteGateConfig {
+    /// Whether writes made by `caller` skip the substance and specificity
+    /// checks.
+    pub fn is_trusted(&self, caller: &str) -> bool {
+        self.trusted_agents.iter().any(|a| a == caller)
+    }
+
+    /// Keyword rules in force for `kind`: the configured entry, else the
+    /// built-in rules.
+    pub fn keyword_rules(&self, kind: &str) -> Option<KindKeywordRules> {
+        self.kind_keywords
+            .get(kind)
+            .cloned()
+            .or_else(|| KindKeywordRules::builtin(kind))
+    }
+}
+
+/// Keyword heuristics for one node kind. Matching is case-insensitive
+/// substring matching on the body.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct KindKeywordRules {
+    /// The body must contain at least one of these. Empty = no requirement.
+    pub required: Vec<String>,
+    /// The body must not contain any of these.
+    pub forbidden: Vec<String>,
+    /// The body must not start with any of these.
+    pub forbidden_prefixes: Vec<String>,
+    /// Rejection reason. Default: generated from the failing rule.
+    pub reason: Option<String>,
+    /// Suggestion shown with the rejection.
+    pub suggestion: Option<String>,
+}
+
+impl KindKeywordRules {
+    /// Built-in English rules for `decision`, `fact` and `pattern`.
+    pub fn builtin(kind: &str) -> Option<Self> {
+        let words = |w: &[&str]| w.iter().map(|s| s.to_string()).collect();
+        match kind {
+            "decision" => Some(Self {
+                required: words(&[
+                    "decided", "chose", "will", "should", "use", "adopt", "switch", "selected",
+                    "going to", "opted",
+                ]),
+                reason: Some("Decision nodes must contain an action or choice (e.g., 'decided', 'chose', 'will use', 'should adopt')".into()),
+                suggestion: Some("Rewrite as a concrete decision: what was decided and why".into()),
+                ..Default::default()
+            }),
+            "fact" => Some(Self {
+                forbidden_prefixes: words(&["i think", "maybe", "probably"]),
+                reason: Some("Fact nodes must not start with hedging language ('I think', 'maybe', 'probably') — use kind=observation instead".into()),
+                suggestion: Some("Either state as a confirmed fact or change kind to 'observation'".into()),
+                ..Default::default()
+            }),
+            "pattern" => Some(Self {
+                required: words(&[
+                    "when",
+                    "always",
+                    "never",
+                    "tends to",
+                    "pattern",
+                    "recurring",
+                    "consistently",
+                    "typically",
+                    "usually",
+                ]),
+                reason: Some("Pattern nodes must reference a recurring behavior (e.g., 'when', 'always', 'tends to', 'pattern')".into()),
+                suggestion: Some("Describe the recurring behavior pattern explicitly".into()),
+                ..Default::default()
+            }),
+            _ => None,
+        }
+    }
+
+    /// Describe the first rule `body` breaks, if any.
+    fn violation(&self, kind: &str, body: &str) -> Option<String> {
+        let body = body.to_lowercase();
+        if !self.required.is_empty()
+            && !self
+                .required
+                .iter()
+                .any(|w| body.contains(&w.to_lowercase()))
+        {
+            return Some(format!(
+                "{kind} nodes must contain one of: {}",
+                self.required.join(", ")
+            ));
+        }
+        if let Some(w) = self
+            .forbidden
+            .iter()
+            .find(|w| body.contains(&w.to_lowercase()))
+        {
+            return Some(format!("{kind} nodes must not contain '{w}'"));
+        }
+        if let Some(p) = self
+            .forbidden_prefixes
+            .iter()
+            .find(|p| body.starts_with(&p.to_lowercase()))
+        {
+            return Some(format!("{kind} nodes must not start with '{p}'"));
+        }
+        None
+    }
+}
+
 /// Per-kind config overrides.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
 pub struct KindOverrideConfig {
     pub min_body_length: Option<usize>,
     pub conflict_threshold: Option<f32>,
+    /// Skip the relative time check, for kinds such as `event` whose
+    /// bodies are expected to say when things happened.
+    pub allow_relative_time: bool,
 }
 
 /// Which gate check produced a rejection.
@@ -56,6 +237,9 @@ pub enum GateCheck {
     Specificity,
     Conflict,
     Schema,
+    Pii,
+    Cycle,
+    Kind,
 }
 
 impl std::fmt::Display for GateCheck {
@@ -65,6 +249,9 @@ impl std::fmt::Display for GateCheck {
             GateCheck::Specificity => write!(f, "specificity"),
             GateCheck::Conflict => write!(f, "conflict"),
             GateCheck::Schema => write!(f, "schema"),
+            GateCheck::Pii => write!(f, "pii"),
+            GateCheck::Cycle => write!(f, "cycle"),
+            GateCheck::Kind => write!(f, "kind"),
         }
     }
 }
@@ -92,8 +279,267 @@ pub enum GateResult {
 pub struct WriteGate;
 
 impl WriteGate {
+    /// Settle a failed check under `mode`. Enforce hands the rejection back
+    /// for the caller to return. Warn records it on the node and lets the
+    /// write through.
+    pub fn admit(
+        node: &mut Node,
+        rejection: GateRejection,
+        mode: GateMode,
+    ) -> Result<(), GateRejection> {
+        match mode {
+            GateMode::Enforce => Err(rejection),
+            GateMode::Warn => {
+                let warning = format!("{}: {}", rejection.check, rejection.reason);
+                match node
+                    .data
+                    .metadata
+                    .entry(GATE_WARNINGS_KEY.to_string())
+                    .or_insert_with(|| serde_json::Value::Array(Vec::new()))
+                {
+                    serde_json::Value::Array(warnings) => warnings.push(warning.into()),
+                    other => *other = serde_json::Value::Array(vec![warning.into()]),
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Warnings recorded on a node by [`WriteGate::admit`] in warn mode.
+    pub fn warnings(node: &Node) -> Vec<String> {
+        node.data
+            .metadata
+            .get(GATE_WARNINGS_KEY)
+            .and_then(|v| v.as_array())
+            .map(|a| {
+                a.iter()
+                    .filter_map(|w| w.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Check 1: Substance — is this worth storing?
+    /// Returns the first failure; see [`WriteGate::check_all`] for every one.
     pub fn check_substance(node: &Node, config: &WriteGateConfig) -> GateResult {
+        first(Self::substance_rejections(node, config))
+    }
+
+    /// Check 2: Specificity — is this useful standalone?
+    /// Returns the first failure; see [`WriteGate::check_all`] for every one.
+    pub fn check_specificity(node: &Node, config: &WriteGateConfig) -> GateResult {
+        first(Self::specificity_rejections(node, config))
+    }
+
+    /// Check 3: Conflict — does this contradict existing knowledge?
+    ///
+    /// Requires a pre-computed embedding for the incoming node.
+    /// Returns `GateResult::Pass` if the vector index is empty or search fails,
+    /// so a search error never silently blocks writes.
+    pub fn check_conflict<S: Storage, V: VectorIndex>(
+        node: &Node,
+        embedding: &Embedding,
+        vector_index: &V,
+        storage: &S,
+        config: &WriteGateConfig,
+    ) -> GateResult {
+        let conflict_threshold = Self::conflict_threshold(node, config);
+        let results = match vector_index.search(embedding, 5, None) {
+            Ok(r) => r,
+            Err(_) => return GateResult::Pass,
+        };
+
+        for result in &results {
+            if result.score <= config.duplicate_threshold.min(conflict_threshold) {
+                continue;
+            }
+            if let Ok(Some(existing)) = storage.get_node(result.node_id) {
+                if let Some(r) = Self::conflict_with(node, &existing, result.score, config) {
+                    return GateResult::Reject(r);
+                }
+            }
+        }
+
+        GateResult::Pass
+    }
+
+    /// Check 3 against nodes that are not indexed yet, such as the earlier
+    /// items of one import. Each node in `earlier` must carry its embedding.
+    pub fn check_conflict_among(
+        node: &Node,
+        embedding: &Embedding,
+        earlier: &[Node],
+        config: &WriteGateConfig,
+    ) -> GateResult {
+        for existing in earlier {
+            let Some(other) = existing.embedding.as_ref() else {
+                continue;
+            };
+            let score = cosine_similarity(embedding, other);
+            if let Some(r) = Self::conflict_with(node, existing, score, config) {
+                return GateResult::Reject(r);
+            }
+        }
+        GateResult::Pass
+    }
+
+    fn conflict_threshold(node: &Node, config: &WriteGateConfig) -> f32 {
+        config
+            .overrides
+            .get(node.kind.as_str())
+            .and_then(|o| o.conflict_threshold)
+            .unwrap_or(config.conflict_threshold)
+    }
+
+    /// How `node` stands against an existing node `score` similar to it.
+    fn conflict_with(
+        node: &Node,
+        existing: &Node,
+        score: f32,
+        config: &WriteGateConfig,
+    ) -> Option<GateRejection> {
+        let conflict_threshold = Self::conflict_threshold(node, config);
+        let same_kind = existing.kind == node.kind;
+        let same_agent = existing.source.agent == node.source.agent;
+
+        // Hard duplicate — always reject regardless of kind/agent
+        let duplicate = score > config.duplicate_threshold
+            || (score > conflict_threshold && same_kind && same_agent);
+        if duplicate {
+            return Some(GateRejection {
+                check: GateCheck::Conflict,
+                reason: format!("Near-duplicate found (similarity: {:.2})", score),
+                suggestion: "Update the existing node instead of creating a duplicate".to_string(),
+                existing_node: Some(existing.id.to_string()),
+                existing_title: Some(existing.data.title.clone()),
+            });
+        }
+        // Conflict threshold — same kind → flag
+        if score > conflict_threshold && same_kind {
+            return Some(GateRejection {
+                check: GateCheck::Conflict,
+                reason: format!(
+                    "Potential contradiction with existing node (similarity: {:.2})",
+                    score
+                ),
+                suggestion: "If this supersedes the existing node, use PATCH /nodes/:id or add a 'supersedes' edge".to_string(),
+                existing_node: Some(existing.id.to_string()),
+                existing_title: Some(existing.data.title.clone()),
+            });
+        }
+        // Different kind: related — log at call site, do not reject
+        None
+    }
+
+    /// Check 4: Schema — does this node satisfy per-kind schema constraints?
+    pub fn check_schema(node: &Node, validator: &schema::SchemaValidator) -> GateResult {
+        match validator.validate(node) {
+            Ok(()) => GateResult::Pass,
+            Err(violations) => {
+                let reasons: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
+                GateResult::Reject(GateRejection {
+                    check: GateCheck::Schema,
+                    reason: format!("Schema validation failed: {}", reasons.join("; ")),
+                    suggestion: "Ensure node metadata matches the schema defined for this kind"
+                        .to_string(),
+                    existing_node: None,
+                    existing_title: None,
+                })
+            }
+        }
+    }
+
+    /// Check 5: PII — does the title or body contain personal data?
+    /// The outcome follows `config.pii.mode`; see [`PiiOutcome`].
+    pub fn check_pii(node: &Node, config: &WriteGateConfig) -> PiiOutcome {
+        pii::check(node, &config.pii)
+    }
+
+    /// Check 6: Cycle — would this `supersedes` edge close a cycle? Only
+    /// run with `reject_supersedes_cycles` set; edges have nowhere to
+    /// record warnings, so a failure always rejects.
+    pub fn check_edge<S: Storage>(
+        edge: &Edge,
+        storage: &S,
+        config: &WriteGateConfig,
+    ) -> crate::Result<GateResult> {
+        if !config.reject_supersedes_cycles
+            || edge.relation != crate::relations::defaults::supersedes()
+            || !crate::graph::would_create_cycle(storage, edge)?
+        {
+            return Ok(GateResult::Pass);
+        }
+        Ok(GateResult::Reject(GateRejection {
+            check: GateCheck::Cycle,
+            reason: format!(
+                "{} already supersedes {}, directly or indirectly",
+                edge.to, edge.from
+            ),
+            suggestion: "Remove the older supersedes edge first, or link in the other direction"
+                .to_string(),
+            existing_node: None,
+            existing_title: None,
+        }))
+    }
+
+    /// Check 7: Kind — is the node's kind registered? Only fails with a
+    /// strict [`KindRegistry`](crate::KindRegistry); a typo'd kind is never
+    /// stored, so a failure always rejects.
+    pub fn check_kind(node: &Node, validator: &schema::SchemaValidator) -> GateResult {
+        match validator.kinds().check(&node.kind) {
+            Ok(()) => GateResult::Pass,
+            Err(e) => GateResult::Reject(GateRejection {
+                check: GateCheck::Kind,
+                reason: match e {
+                    crate::CortexError::Validation(reason) => reason,
+                    e => e.to_string(),
+                },
+                suggestion:
+                    "Use a registered kind (`cortex config kinds`) or add it to [schema] node_kinds"
+                        .to_string(),
+                existing_node: None,
+                existing_title: None,
+            }),
+        }
+    }
+
+    /// Run the substance, specificity and conflict checks and collect every
+    /// failure, so a caller can fix them all in one pass. Schema validation
+    /// needs a validator and is run separately with [`WriteGate::check_schema`].
+    ///
+    /// `caller` is the identity making the write. A trusted caller (see
+    /// [`WriteGateConfig::is_trusted`]) only gets the conflict check.
+    pub fn check_all<S: Storage, V: VectorIndex>(
+        node: &Node,
+        embedding: &Embedding,
+        vector_index: &V,
+        storage: &S,
+        config: &WriteGateConfig,
+        caller: &str,
+    ) -> Vec<GateRejection> {
+        let mut rejections = Self::check_text(node, config, caller);
+        if let GateResult::Reject(r) =
+            Self::check_conflict(node, embedding, vector_index, storage, config)
+        {
+            rejections.push(r);
+        }
+        rejections
+    }
+
+    /// The substance and specificity part of [`WriteGate::check_all`]: every
+    /// failure of the checks that only read the node's text, so they can run
+    /// before it is embedded. Empty for a trusted caller.
+    pub fn check_text(node: &Node, config: &WriteGateConfig, caller: &str) -> Vec<GateRejection> {
+        if config.is_trusted(caller) {
+            return Vec::new();
+        }
+        let mut rejections = Self::substance_rejections(node, config);
+        rejections.extend(Self::specificity_rejections(node, config));
+        rejections
+    }
+
+    fn substance_rejections(node: &Node, config: &WriteGateConfig) -> Vec<GateRejection> {
+        let mut rejections = Vec::new();
         let kind_str = node.kind.as_str();
         let min_body = config
             .overrides
@@ -105,7 +551,7 @@ impl WriteGate {
         let body = &node.data.body;
 
         if title.len() < config.min_title_length {
-            return GateResult::Reject(GateRejection {
+            rejections.push(GateRejection {
                 check: GateCheck::Substance,
                 reason: format!(
                     "Title too short ({} chars, minimum {})",
@@ -121,7 +567,7 @@ impl WriteGate {
         }
 
         if body.len() < min_body {
-            return GateResult::Reject(GateRejection {
+            rejections.push(GateRejection {
                 check: GateCheck::Substance,
                 reason: format!(
                     "Body too short ({} chars, minimum {})",
@@ -136,7 +582,7 @@ impl WriteGate {
         }
 
         if body.trim() == title.trim() {
-            return GateResult::Reject(GateRejection {
+            rejections.push(GateRejection {
                 check: GateCheck::Substance,
                 reason: "Body is identical to title — no additional context".to_string(),
                 suggestion: "Add detail in the body that expands on the title".to_string(),
@@ -148,7 +594,7 @@ impl WriteGate {
         let trimmed_body = body.trim();
 
         if is_pure_url(trimmed_body) {
-            return GateResult::Reject(GateRejection {
+            rejections.push(GateRejection {
                 check: GateCheck::Substance,
                 reason: "Body is a bare URL with no context".to_string(),
                 suggestion: "Add a description of what this URL contains or why it matters"
@@ -159,7 +605,7 @@ impl WriteGate {
         }
 
         if trimmed_body.split_whitespace().count() <= 1 {
-            return GateResult::Reject(GateRejection {
+            rejections.push(GateRejection {
                 check: GateCheck::Substance,
                 reason: "Body is a single word — not enough context".to_string(),
                 suggestion: "Add more context to make this useful as a standalone memory"
@@ -170,7 +616,7 @@ impl WriteGate {
         }
 
         if is_just_timestamp(trimmed_body) {
-            return GateResult::Reject(GateRejection {
+            rejections.push(GateRejection {
                 check: GateCheck::Substance,
                 reason: "Body appears to be just a timestamp — no substantive content".to_string(),
                 suggestion: "Add context about what the timestamp refers to".to_string(),
@@ -179,123 +625,73 @@ impl WriteGate {
             });
         }
 
-        // Kind-specific checks
-        let body_lower = body.to_lowercase();
-        match kind_str {
-            "decision" => {
-                let decision_words = [
-                    "decided", "chose", "will", "should", "use", "adopt", "switch", "selected",
-                    "going to", "opted",
-                ];
-                if !decision_words.iter().any(|w| body_lower.contains(w)) {
-                    return GateResult::Reject(GateRejection {
-                        check: GateCheck::Substance,
-                        reason: "Decision nodes must contain an action or choice (e.g., 'decided', 'chose', 'will use', 'should adopt')".to_string(),
-                        suggestion: "Rewrite as a concrete decision: what was decided and why"
-                            .to_string(),
-                        existing_node: None,
-                        existing_title: None,
-                    });
-                }
-            }
-            "fact" => {
-                let hedges = ["i think", "maybe", "probably"];
-                if hedges.iter().any(|h| body_lower.starts_with(h)) {
-                    return GateResult::Reject(GateRejection {
-                        check: GateCheck::Substance,
-                        reason: "Fact nodes must not start with hedging language ('I think', 'maybe', 'probably') — use kind=observation instead".to_string(),
-                        suggestion: "Either state as a confirmed fact or change kind to 'observation'".to_string(),
-                        existing_node: None,
-                        existing_title: None,
-                    });
-                }
-            }
-            "pattern" => {
-                let pattern_words = [
-                    "when",
-                    "always",
-                    "never",
-                    "tends to",
-                    "pattern",
-                    "recurring",
-                    "consistently",
-                    "typically",
-                    "usually",
-                ];
-                if !pattern_words.iter().any(|w| body_lower.contains(w)) {
-                    return GateResult::Reject(GateRejection {
-                        check: GateCheck::Substance,
-                        reason: "Pattern nodes must reference a recurring behavior (e.g., 'when', 'always', 'tends to', 'pattern')".to_string(),
-                        suggestion: "Describe the recurring behavior pattern explicitly"
-                            .to_string(),
-                        existing_node: None,
-                        existing_title: None,
-                    });
-                }
+        // Kind-specific keyword checks
+        if let Some(rules) = config.keyword_rules(kind_str) {
+            if let Some(reason) = rules.violation(kind_str, body) {
+                rejections.push(GateRejection {
+                    check: GateCheck::Substance,
+                    reason: rules.reason.clone().unwrap_or(reason),
+                    suggestion: rules.suggestion.clone().unwrap_or_else(|| {
+                        format!("Revise the body to meet the keyword rules for '{kind_str}'")
+                    }),
+                    existing_node: None,
+                    existing_title: None,
+                });
             }
-            _ => {}
         }
 
-        GateResult::Pass
+        rejections
     }
 
-    /// Check 2: Specificity — is this useful standalone?
-    pub fn check_specificity(node: &Node, config: &WriteGateConfig) -> GateResult {
+    fn specificity_rejections(node: &Node, config: &WriteGateConfig) -> Vec<GateRejection> {
+        let mut rejections = Vec::new();
         let title = &node.data.title;
         let body = &node.data.body;
         let importance = node.importance;
 
-        if has_unresolved_pronouns(title, body) {
-            return GateResult::Reject(GateRejection {
+        if let Some(word) = unresolved_pronoun(title, body, config) {
+            rejections.push(GateRejection {
                 check: GateCheck::Specificity,
-                reason: "Body starts with an unresolved pronoun ('He', 'She', 'They', 'It') — the reference is ambiguous without conversation context".to_string(),
+                reason: format!("Body starts with an unresolved pronoun ('{word}') — the reference is ambiguous without conversation context"),
                 suggestion: "Replace the pronoun with the actual entity name".to_string(),
                 existing_node: None,
                 existing_title: None,
             });
         }
 
-        if has_unanchored_temporal(title, body) {
-            return GateResult::Reject(GateRejection {
+        let allow_relative_time = config
+            .overrides
+            .get(node.kind.as_str())
+            .is_some_and(|o| o.allow_relative_time);
+        let temporal = unanchored_temporal(title, body, config).filter(|_| !allow_relative_time);
+        if let Some(term) = temporal {
+            rejections.push(GateRejection {
                 check: GateCheck::Specificity,
-                reason: "Title or body uses a relative time reference without anchoring (e.g., 'yesterday', 'last week') — ambiguous outside original context".to_string(),
+                reason: format!("Title or body uses a relative time reference without anchoring ('{term}') — ambiguous outside original context"),
                 suggestion: "Use a specific date or event anchor instead of relative time references".to_string(),
                 existing_node: None,
                 existing_title: None,
             });
         }
 
-        if config.require_body_length_for_importance {
-            if importance >= 0.9 && body.len() < 100 {
-                return GateResult::Reject(GateRejection {
-                    check: GateCheck::Specificity,
-                    reason: format!(
-                        "High importance ({:.1}) but body is only {} chars",
-                        importance,
-                        body.len()
-                    ),
-                    suggestion: "Either add more detail or reduce importance".to_string(),
-                    existing_node: None,
-                    existing_title: None,
-                });
-            }
-            if importance >= 0.8 && body.len() < 50 {
-                return GateResult::Reject(GateRejection {
-                    check: GateCheck::Specificity,
-                    reason: format!(
-                        "High importance ({:.1}) but body is only {} chars",
-                        importance,
-                        body.len()
-                    ),
-                    suggestion: "Either add more detail or reduce importance".to_string(),
-                    existing_node: None,
-                    existing_title: None,
-                });
-            }
+        let too_brief =
+            (importance >= 0.9 && body.len() < 100) || (importance >= 0.8 && body.len() < 50);
+        if config.require_body_length_for_importance && too_brief {
+            rejections.push(GateRejection {
+                check: GateCheck::Specificity,
+                reason: format!(
+                    "High importance ({:.1}) but body is only {} chars",
+                    importance,
+                    body.len()
+                ),
+                suggestion: "Either add more detail or reduce importance".to_string(),
+                existing_node: None,
+                existing_title: None,
+            });
         }
 
         if importance >= config.require_tags_above_importance && node.data.tags.is_empty() {
-            return GateResult::Reject(GateRejection {
+            rejections.push(GateRejection {
                 check: GateCheck::Specificity,
                 reason: format!(
                     "High importance ({:.1}) nodes should have tags for discoverability",
@@ -307,101 +703,14 @@ impl WriteGate {
             });
         }
 
-        GateResult::Pass
-    }
-
-    /// Check 3: Conflict — does this contradict existing knowledge?
-    ///
-    /// Requires a pre-computed embedding for the incoming node.
-    /// Returns `GateResult::Pass` if the vector index is empty or search fails,
-    /// so a search error never silently blocks writes.
-    pub fn check_conflict<S: Storage, V: VectorIndex>(
-        node: &Node,
-        embedding: &Embedding,
-        vector_index: &V,
-        storage: &S,
-        config: &WriteGateConfig,
-    ) -> GateResult {
-        let kind_str = node.kind.as_str();
-        let conflict_threshold = config
-            .overrides
-            .get(kind_str)
-            .and_then(|o| o.conflict_threshold)
-            .unwrap_or(config.conflict_threshold);
-
-        let results = match vector_index.search(embedding, 5, None) {
-            Ok(r) => r,
-            Err(_) => return GateResult::Pass,
-        };
-
-        for result in &results {
-            let score = result.score;
-
-            // Hard duplicate — always reject regardless of kind/agent
-            if score > config.duplicate_threshold {
-                if let Ok(Some(existing)) = storage.get_node(result.node_id) {
-                    return GateResult::Reject(GateRejection {
-                        check: GateCheck::Conflict,
-                        reason: format!("Near-duplicate found (similarity: {:.2})", score),
-                        suggestion: "Update the existing node instead of creating a duplicate"
-                            .to_string(),
-                        existing_node: Some(existing.id.to_string()),
-                        existing_title: Some(existing.data.title.clone()),
-                    });
-                }
-            }
-
-            // Conflict threshold — same kind → flag
-            if score > conflict_threshold {
-                if let Ok(Some(existing)) = storage.get_node(result.node_id) {
-                    let same_kind = existing.kind.as_str() == kind_str;
-                    let same_agent = existing.source.agent == node.source.agent;
-
-                    if same_kind && same_agent {
-                        return GateResult::Reject(GateRejection {
-                            check: GateCheck::Conflict,
-                            reason: format!("Near-duplicate found (similarity: {:.2})", score),
-                            suggestion: "Update the existing node instead of creating a duplicate"
-                                .to_string(),
-                            existing_node: Some(existing.id.to_string()),
-                            existing_title: Some(existing.data.title.clone()),
-                        });
-                    } else if same_kind {
-                        return GateResult::Reject(GateRejection {
-                            check: GateCheck::Conflict,
-                            reason: format!(
-                                "Potential contradiction with existing node (similarity: {:.2})",
-                                score
-                            ),
-                            suggestion: "If this supersedes the existing node, use PATCH /nodes/:id or add a 'supersedes' edge".to_string(),
-                            existing_node: Some(existing.id.to_string()),
-                            existing_title: Some(existing.data.title.clone()),
-                        });
-                    }
-                    // Different kind: related — log at call site, do not reject
-                }
-            }
-        }
-
-        GateResult::Pass
+        rejections
     }
+}
 
-    /// Check 4: Schema — does this node satisfy per-kind schema constraints?
-    pub fn check_schema(node: &Node, validator: &schema::SchemaValidator) -> GateResult {
-        match validator.validate(node) {
-            Ok(()) => GateResult::Pass,
-            Err(violations) => {
-                let reasons: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
-                GateResult::Reject(GateRejection {
-                    check: GateCheck::Schema,
-                    reason: format!("Schema validation failed: {}", reasons.join("; ")),
-                    suggestion: "Ensure node metadata matches the schema defined for this kind"
-                        .to_string(),
-                    existing_node: None,
-                    existing_title: None,
-                })
-            }
-        }
+fn first(rejections: Vec<GateRejection>) -> GateResult {
+    match rejections.into_iter().next() {
+        Some(r) => GateResult::Reject(r),
+        None => GateResult::Pass,
     }
 }
 
@@ -447,54 +756,45 @@ fn is_just_timestamp(s: &str) -> bool {
     false
 }
 
-/// Returns true if the body starts with an unresolved third-person pronoun AND
-/// the title doesn't appear to name the referent.
-fn has_unresolved_pronouns(title: &str, body: &str) -> bool {
-    let body_lower = body.trim_start().to_lowercase();
-    let ambiguous_starts = ["he ", "she ", "they ", "it "];
-    if !ambiguous_starts.iter().any(|p| body_lower.starts_with(p)) {
-        return false;
-    }
+/// The configured ambiguous start the body opens with, unless the title
+/// appears to name the referent.
+fn unresolved_pronoun<'a>(title: &str, body: &str, config: &'a WriteGateConfig) -> Option<&'a str> {
+    let first = body.split_whitespace().next()?.to_lowercase();
+    let word = config
+        .ambiguous_starts
+        .iter()
+        .find(|w| w.to_lowercase() == first)?;
 
     // The title resolves the reference if it starts with or contains a proper
     // noun (heuristic: capitalised word that isn't a common article/pronoun).
-    let stopwords = [
-        "The", "A", "An", "This", "That", "These", "Those", "He", "She", "They", "It", "In", "On",
-        "At", "For", "With",
-    ];
     let title_has_proper_noun = title.split_whitespace().any(|w| {
-        w.chars().next().map(|c| c.is_uppercase()).unwrap_or(false)
-            && !stopwords.contains(&w)
+        w.chars().next().is_some_and(|c| c.is_uppercase())
+            && !config.stopwords.iter().any(|s| s == w)
             && w.len() > 2
     });
 
-    !title_has_proper_noun
+    (!title_has_proper_noun).then_some(word.as_str())
 }
 
-/// Returns true if the title or the opening of the body uses an unanchored
-/// relative time reference (e.g. "yesterday", "last week").
-fn has_unanchored_temporal(title: &str, body: &str) -> bool {
-    let relative_terms = [
-        "yesterday",
-        "last week",
-        "last month",
-        "last year",
-        "this morning",
-        "this afternoon",
-        "this evening",
-        "last night",
-        "earlier today",
-    ];
-
+/// The first configured relative time term found in the title or at the
+/// opening of the body (e.g. "yesterday", "last week").
+fn unanchored_temporal<'a>(
+    title: &str,
+    body: &str,
+    config: &'a WriteGateConfig,
+) -> Option<&'a str> {
     let title_lower = title.to_lowercase();
-    if relative_terms.iter().any(|t| title_lower.contains(t)) {
-        return true;
-    }
-
     // Only check the opening ~60 chars of the body to avoid false positives
     // in longer narrative content.
-    let body_start = &body[..body.len().min(60)].to_lowercase();
-    relative_terms.iter().any(|t| body_start.starts_with(t))
+    let body_start: String = body.chars().take(60).collect::<String>().to_lowercase();
+    config
+        .relative_terms
+        .iter()
+        .find(|t| {
+            let t = t.to_lowercase();
+            title_lower.contains(&t) || body_start.starts_with(&t)
+        })
+        .map(String::as_str)
 }
 
 #[cfg(test)]
@@ -528,6 +828,229 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn warn_mode_stores_substandard_node_with_warnings() {
+        use crate::storage::RedbStorage;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let storage = RedbStorage::open(tmp.path().join("gate.redb")).unwrap();
+        let config = WriteGateConfig::default();
+
+        // Enforce: the failure comes back as a rejection
+        let mut node = make_node("fact", "Short", "This is a sufficiently long body.", 0.5);
+        let GateResult::Reject(r) = WriteGate::check_substance(&node, &config) else {
+            panic!("expected a substance rejection");
+        };
+        assert!(WriteGate::admit(&mut node, r.clone(), GateMode::Enforce).is_err());
+        assert!(WriteGate::warnings(&node).is_empty());
+
+        // Warn: the node is let through and keeps the reason after a round trip
+        WriteGate::admit(&mut node, r, GateMode::Warn).unwrap();
+        storage.put_node(&node).unwrap();
+        let stored = storage.get_node(node.id).unwrap().unwrap();
+        let warnings = WriteGate::warnings(&stored);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("substance: Title too short"));
+    }
+
+    #[test]
+    fn check_all_reports_every_failure() {
+        use crate::storage::RedbStorage;
+        use crate::vector::HnswIndex;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let storage = RedbStorage::open(tmp.path().join("gate.redb")).unwrap();
+        let index = HnswIndex::new(4);
+        let config = WriteGateConfig::default();
+
+        let mut node = make_node("fact", "Short", "maybe", 0.8);
+        node.data.tags.clear();
+
+        let rejections = WriteGate::check_all(
+            &node,
+            &vec![1.0, 0.0, 0.0, 0.0],
+            &index,
+            &storage,
+            &config,
+            "test",
+        );
+        let reasons: Vec<&str> = rejections.iter().map(|r| r.reason.as_str()).collect();
+        assert!(reasons[0].starts_with("Title too short"), "{reasons:?}");
+        assert!(reasons.iter().any(|r| r.starts_with("Body too short")));
+        assert!(reasons
+            .iter()
+            .any(|r| r.starts_with("Body is a single word")));
+        assert!(reasons
+            .iter()
+            .any(|r| r.starts_with("Fact nodes must not start")));
+        assert!(reasons.iter().any(|r| r.contains("should have tags")));
+        assert_eq!(
+            rejections
+                .iter()
+                .filter(|r| r.check == GateCheck::Specificity)
+                .count(),
+            2,
+            "{reasons:?}"
+        );
+
+        // The single-check methods still stop at the first failure
+        let GateResult::Reject(first) = WriteGate::check_substance(&node, &config) else {
+            panic!("expected a substance rejection");
+        };
+        assert_eq!(first.reason, rejections[0].reason);
+    }
+
+    #[test]
+    fn trusted_caller_skips_substance_and_specificity() {
+        use crate::storage::RedbStorage;
+        use crate::vector::HnswIndex;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let storage = RedbStorage::open(tmp.path().join("gate.redb")).unwrap();
+        let mut index = HnswIndex::new(4);
+        let config = WriteGateConfig {
+            trusted_agents: vec!["warren-ingest".into()],
+            ..Default::default()
+        };
+
+        let mut node = make_node("event", "Deploy", "ok", 0.5);
+        let embedding = vec![1.0, 0.0, 0.0, 0.0];
+        let check = |node: &Node, caller: &str| {
+            WriteGate::check_all(node, &embedding, &index, &storage, &config, caller)
+        };
+        assert!(!check(&node, "anonymous").is_empty());
+        assert!(check(&node, "warren-ingest").is_empty());
+
+        // Claiming a trusted source agent in the node itself gains nothing
+        node.source.agent = "warren-ingest".into();
+        assert!(!check(&node, "anonymous").is_empty());
+
+        // Conflict detection still applies to trusted writes
+        let existing = make_node("event", "Deploy", "ok", 0.5);
+        storage.put_node(&existing).unwrap();
+        index.insert(existing.id, &embedding).unwrap();
+        let rejections = WriteGate::check_all(
+            &node,
+            &embedding,
+            &index,
+            &storage,
+            &config,
+            "warren-ingest",
+        );
+        assert_eq!(rejections.len(), 1);
+        assert_eq!(rejections[0].check, GateCheck::Conflict);
+    }
+
+    #[test]
+    fn supersedes_cycle_rejected_only_when_enabled() {
+        use crate::relations::defaults as relations;
+        use crate::storage::RedbStorage;
+        use crate::EdgeProvenance;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let storage = RedbStorage::open(tmp.path().join("gate.redb")).unwrap();
+        let old = make_node("decision", "Use Postgres", "Postgres for everything.", 0.5);
+        let new = make_node("decision", "Use redb", "An embedded store is enough.", 0.5);
+        storage.put_node(&old).unwrap();
+        storage.put_node(&new).unwrap();
+        let supersedes = |from: &Node, to: &Node| {
+            Edge::new(
+                from.id,
+                to.id,
+                relations::supersedes(),
+                1.0,
+                EdgeProvenance::Manual {
+                    created_by: "test".into(),
+                },
+            )
+        };
+        storage.put_edge(&supersedes(&new, &old)).unwrap();
+
+        let back = supersedes(&old, &new);
+        let config = WriteGateConfig::default();
+        assert!(matches!(
+            WriteGate::check_edge(&back, &storage, &config).unwrap(),
+            GateResult::Pass
+        ));
+
+        let config = WriteGateConfig {
+            reject_supersedes_cycles: true,
+            ..Default::default()
+        };
+        let GateResult::Reject(r) = WriteGate::check_edge(&back, &storage, &config).unwrap() else {
+            panic!("expected a cycle rejection");
+        };
+        assert_eq!(r.check, GateCheck::Cycle);
+        // Other relations may loop
+        let mut related = back.clone();
+        related.relation = relations::related_to();
+        assert!(matches!(
+            WriteGate::check_edge(&related, &storage, &config).unwrap(),
+            GateResult::Pass
+        ));
+    }
+
+    #[test]
+    fn custom_kind_keyword_rules_are_enforced() {
+        let mut config = WriteGateConfig::default();
+        config.kind_keywords.insert(
+            "incident".into(),
+            KindKeywordRules {
+                required: vec!["Impact".into()],
+                forbidden_prefixes: vec!["todo".into()],
+                ..Default::default()
+            },
+        );
+
+        let node = make_node(
+            "incident",
+            "Checkout outage on the payments service",
+            "Payments returned 502s for twelve minutes after the deploy",
+            0.5,
+        );
+        let GateResult::Reject(r) = WriteGate::check_substance(&node, &config) else {
+            panic!("expected a keyword rejection");
+        };
+        assert_eq!(r.reason, "incident nodes must contain one of: Impact");
+
+        let node = make_node(
+            "incident",
+            "Checkout outage on the payments service",
+            "Payments returned 502s for twelve minutes; impact was limited to EU users",
+            0.5,
+        );
+        assert!(matches!(
+            WriteGate::check_substance(&node, &config),
+            GateResult::Pass
+        ));
+
+        let node = make_node(
+            "incident",
+            "Checkout outage on the payments service",
+            "TODO: write up the impact of the payments outage",
+            0.5,
+        );
+        assert!(matches!(
+            WriteGate::check_substance(&node, &config),
+            GateResult::Reject(_)
+        ));
+
+        // An empty entry switches off the built-in rules for that kind
+        config
+            .kind_keywords
+            .insert("pattern".into(), KindKeywordRules::default());
+        let node = make_node(
+            "pattern",
+            "Agent communication style",
+            "Kai sends structured JSON responses to all downstream services",
+            0.5,
+        );
+        assert!(matches!(
+            WriteGate::check_substance(&node, &config),
+            GateResult::Pass
+        ));
+    }
+
     #[test]
     fn substance_rejects_short_body() {
         let node = make_node("fact", "A long enough title here", "Too short", 0.5);
@@ -689,6 +1212,70 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn specificity_uses_configured_word_lists() {
+        let config = WriteGateConfig {
+            relative_terms: vec!["hier".into(), "la semaine dernière".into()],
+            ambiguous_starts: vec!["il".into(), "elle".into()],
+            ..Default::default()
+        };
+        let check = |title: &str, body: &str| {
+            let node = make_node("fact", title, body, 0.5);
+            WriteGate::check_specificity(&node, &config)
+        };
+
+        // The configured terms are enforced...
+        assert!(matches!(
+            check(
+                "Notes de la réunion",
+                "Hier nous avons migré le service vers kubernetes"
+            ),
+            GateResult::Reject(r) if r.reason.contains("'hier'")
+        ));
+        assert!(matches!(
+            check(
+                "notes de réunion",
+                "Il a décidé de migrer le service vers kubernetes"
+            ),
+            GateResult::Reject(r) if r.reason.contains("'il'")
+        ));
+        // ...and the replaced defaults are not
+        assert!(matches!(
+            check(
+                "yesterday meeting notes",
+                "He decided to migrate the service to kubernetes next quarter"
+            ),
+            GateResult::Pass
+        ));
+    }
+
+    #[test]
+    fn specificity_allows_relative_time_for_overridden_kind() {
+        let node = make_node(
+            "event",
+            "Yesterday meeting notes",
+            "We discussed the roadmap and assigned owners to each epic",
+            0.5,
+        );
+        let mut config = WriteGateConfig::default();
+        config.overrides.insert(
+            "event".into(),
+            KindOverrideConfig {
+                allow_relative_time: true,
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            WriteGate::check_specificity(&node, &config),
+            GateResult::Pass
+        ));
+        let fact = make_node("fact", &node.data.title, &node.data.body, 0.5);
+        assert!(matches!(
+            WriteGate::check_specificity(&fact, &config),
+            GateResult::Reject(_)
+        ));
+    }
+
     #[test]
     fn specificity_rejects_high_importance_low_body() {
         let node = make_node(
@@ -697,8 +1284,10 @@ mod tests {
             "cortex-core must have zero network deps",
             0.9,
         );
-        let mut config = WriteGateConfig::default();
-        config.require_body_length_for_importance = true;
+        let config = WriteGateConfig {
+            require_body_length_for_importance: true,
+            ..Default::default()
+        };
         // tags are set by make_node, so tag check passes; body length fails
         assert!(matches!(
             WriteGate::check_specificity(&node, &config),
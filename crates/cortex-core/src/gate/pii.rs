@@ -0,0 +1,376 @@
+//! Personal data detection for the write gate.
+//!
+//! Scans a node's title and body with configurable regex patterns. Defaults
+//! cover email addresses, phone numbers, SSN-like identifiers and card
+//! numbers (Luhn-checked). Depending on [`PiiMode`], a match rejects the
+//! node, is recorded as a warning, or is replaced with `[REDACTED]`.
+
+use super::{GateCheck, GateRejection};
+use crate::Node;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Replacement text for redacted matches.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// What to do when personal data is found.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PiiMode {
+    /// Reject the write.
+    #[default]
+    Reject,
+    /// Store the node with every match replaced by `[REDACTED]`.
+    Redact,
+    /// Store the node unchanged, recording a gate warning.
+    Warn,
+}
+
+/// A named detection pattern. The regex is compiled once, when the pattern
+/// is built or deserialized; [`PiiConfig::validate`] reports one that does
+/// not compile, and the check skips it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "PiiPatternSpec", into = "PiiPatternSpec")]
+pub struct PiiPattern {
+    pub name: String,
+    /// Only count matches whose digits pass the Luhn checksum.
+    pub luhn: bool,
+    source: String,
+    compiled: Result<Regex, String>,
+}
+
+/// How a [`PiiPattern`] is written in config.
+#[derive(Serialize, Deserialize)]
+struct PiiPatternSpec {
+    name: String,
+    regex: String,
+    #[serde(default)]
+    luhn: bool,
+}
+
+impl From<PiiPatternSpec> for PiiPattern {
+    fn from(spec: PiiPatternSpec) -> Self {
+        Self::new(&spec.name, &spec.regex, spec.luhn)
+    }
+}
+
+impl From<PiiPattern> for PiiPatternSpec {
+    fn from(pattern: PiiPattern) -> Self {
+        Self {
+            name: pattern.name,
+            regex: pattern.source,
+            luhn: pattern.luhn,
+        }
+    }
+}
+
+impl PartialEq for PiiPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.luhn == other.luhn && self.source == other.source
+    }
+}
+
+impl PiiPattern {
+    pub fn new(name: &str, regex: &str, luhn: bool) -> Self {
+        Self {
+            name: name.into(),
+            luhn,
+            source: regex.into(),
+            compiled: Regex::new(regex).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// The pattern's regex source.
+    pub fn regex(&self) -> &str {
+        &self.source
+    }
+
+    /// Email, phone, SSN-like and card-number patterns.
+    pub fn defaults() -> Vec<Self> {
+        [
+            (
+                "email",
+                r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}",
+                false,
+            ),
+            (
+                "phone",
+                r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{3}\)\s?|\b\d{3}[\s.-])\d{3}[\s.-]\d{4}\b",
+                false,
+            ),
+            ("ssn", r"\b\d{3}-\d{2}-\d{4}\b", false),
+            ("card", r"\b(?:\d[ -]?){12,18}\d\b", true),
+        ]
+        .into_iter()
+        .map(|(name, regex, luhn)| Self::new(name, regex, luhn))
+        .collect()
+    }
+}
+
+/// Configuration for the PII check. Disabled by default.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PiiConfig {
+    pub enabled: bool,
+    pub mode: PiiMode,
+    /// Patterns to scan for. Setting this replaces the defaults.
+    pub patterns: Vec<PiiPattern>,
+}
+
+impl PiiConfig {
+    /// Every pattern whose regex does not compile, naming the pattern.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let errors: Vec<String> = self
+            .patterns
+            .iter()
+            .filter_map(|p| {
+                let e = p.compiled.as_ref().err()?;
+                Some(format!("pattern '{}': invalid regex: {}", p.name, e))
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Default for PiiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: PiiMode::default(),
+            patterns: PiiPattern::defaults(),
+        }
+    }
+}
+
+/// Result of the PII check.
+#[derive(Debug)]
+pub enum PiiOutcome {
+    /// Nothing found, or the check is disabled.
+    Clean,
+    /// Reject mode: the node must not be stored.
+    Reject(GateRejection),
+    /// Warn mode: store the node, recording this as a warning.
+    Warn(GateRejection),
+    /// Redact mode: store this copy instead of the original.
+    Redacted(Box<Node>),
+}
+
+pub(super) fn check(node: &Node, config: &PiiConfig) -> PiiOutcome {
+    if !config.enabled {
+        return PiiOutcome::Clean;
+    }
+
+    let mut found = Vec::new();
+    let mut redacted = node.clone();
+    for pattern in &config.patterns {
+        let Ok(re) = &pattern.compiled else {
+            continue;
+        };
+        let is_match = |m: &str| !pattern.luhn || luhn_valid(m);
+        let hit = [&node.data.title, &node.data.body]
+            .iter()
+            .any(|text| re.find_iter(text).any(|m| is_match(m.as_str())));
+        if !hit {
+            continue;
+        }
+        found.push(pattern.name.clone());
+        if config.mode == PiiMode::Redact {
+            for text in [&mut redacted.data.title, &mut redacted.data.body] {
+                *text = re
+                    .replace_all(text, |caps: &regex::Captures| {
+                        if is_match(&caps[0]) {
+                            REDACTED.to_string()
+                        } else {
+                            caps[0].to_string()
+                        }
+                    })
+                    .into_owned();
+            }
+        }
+    }
+
+    if found.is_empty() {
+        return PiiOutcome::Clean;
+    }
+    let rejection = GateRejection {
+        check: GateCheck::Pii,
+        // Name the kinds of data only; echoing the matches would leak them
+        reason: format!("Possible personal data detected: {}", found.join(", ")),
+        suggestion: "Remove personal data from the title and body before storing".to_string(),
+        existing_node: None,
+        existing_title: None,
+    };
+    match config.mode {
+        PiiMode::Reject => PiiOutcome::Reject(rejection),
+        PiiMode::Warn => PiiOutcome::Warn(rejection),
+        PiiMode::Redact => PiiOutcome::Redacted(Box::new(redacted)),
+    }
+}
+
+/// Luhn checksum over the digits of `s`, for 13–19 digit card numbers.
+fn luhn_valid(s: &str) -> bool {
+    let digits: Vec<u32> = s.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NodeKind, Source};
+
+    fn node_with_body(body: &str) -> Node {
+        Node::new(
+            NodeKind::new("observation").unwrap(),
+            "Customer support conversation".to_string(),
+            body.to_string(),
+            Source {
+                agent: "test".to_string(),
+                session: None,
+                channel: None,
+            },
+            0.5,
+        )
+    }
+
+    fn enabled(mode: PiiMode) -> PiiConfig {
+        PiiConfig {
+            enabled: true,
+            mode,
+            ..Default::default()
+        }
+    }
+
+    fn detected(body: &str) -> Option<String> {
+        match check(&node_with_body(body), &enabled(PiiMode::Reject)) {
+            PiiOutcome::Reject(r) => Some(r.reason),
+            PiiOutcome::Clean => None,
+            other => panic!("unexpected outcome {other:?}"),
+        }
+    }
+
+    #[test]
+    fn detects_email() {
+        let reason = detected("Reach the customer at jane.doe@example.com for follow-up").unwrap();
+        assert!(reason.ends_with("email"), "{reason}");
+    }
+
+    #[test]
+    fn detects_phone() {
+        for body in [
+            "Customer called from 555-867-5309 about billing",
+            "Customer called from (555) 867-5309 about billing",
+            "Customer called from +44 207.946.0958 about billing",
+        ] {
+            let reason = detected(body).unwrap();
+            assert!(reason.contains("phone"), "{body}: {reason}");
+        }
+    }
+
+    #[test]
+    fn detects_ssn() {
+        let reason = detected("Applicant SSN on file is 078-05-1120 per the form").unwrap();
+        assert!(reason.contains("ssn"), "{reason}");
+    }
+
+    #[test]
+    fn detects_luhn_valid_card_only() {
+        let reason = detected("Paid with card 4111 1111 1111 1111 at checkout").unwrap();
+        assert!(reason.contains("card"), "{reason}");
+        // Same shape, bad checksum: an order number, not a card
+        assert!(detected("Order reference 4111 1111 1111 1112 shipped").is_none());
+    }
+
+    #[test]
+    fn clean_text_and_disabled_check_pass() {
+        assert!(detected("Deploys run at 14:00 UTC from the release branch").is_none());
+        let node = node_with_body("Reach the customer at jane.doe@example.com");
+        assert!(matches!(
+            check(&node, &PiiConfig::default()),
+            PiiOutcome::Clean
+        ));
+    }
+
+    #[test]
+    fn warn_mode_reports_without_rejecting() {
+        let node = node_with_body("Reach the customer at jane.doe@example.com");
+        let PiiOutcome::Warn(r) = check(&node, &enabled(PiiMode::Warn)) else {
+            panic!("expected a warning");
+        };
+        assert_eq!(r.check, GateCheck::Pii);
+        // The matched value itself is never echoed back
+        assert!(!r.reason.contains("jane.doe"));
+    }
+
+    #[test]
+    fn redact_mode_replaces_matches() {
+        let node = node_with_body(
+            "Reach jane.doe@example.com or 555-867-5309; card 4111-1111-1111-1111, order 1234-5678",
+        );
+        let PiiOutcome::Redacted(redacted) = check(&node, &enabled(PiiMode::Redact)) else {
+            panic!("expected a redacted node");
+        };
+        assert_eq!(
+            redacted.data.body,
+            "Reach [REDACTED] or [REDACTED]; card [REDACTED], order 1234-5678"
+        );
+        assert_eq!(redacted.id, node.id);
+    }
+
+    #[test]
+    fn custom_patterns_replace_defaults() {
+        let config = PiiConfig {
+            enabled: true,
+            mode: PiiMode::Reject,
+            patterns: vec![PiiPattern::new("employee_id", r"\bEMP-\d{6}\b", false)],
+        };
+        let node = node_with_body("Badge EMP-004211 was used; contact jane@example.com");
+        let PiiOutcome::Reject(r) = check(&node, &config) else {
+            panic!("expected a rejection");
+        };
+        assert_eq!(r.reason, "Possible personal data detected: employee_id");
+    }
+
+    #[test]
+    fn invalid_patterns_fail_validation() {
+        let config: PiiConfig = serde_json::from_str(
+            r#"{"enabled": true, "patterns": [
+                {"name": "ticket", "regex": "TCK-\\d+"},
+                {"name": "broken", "regex": "(unclosed"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.patterns[0].regex(), r"TCK-\d+");
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("pattern 'broken'"), "{errors:?}");
+        assert!(PiiConfig::default().validate().is_ok());
+
+        // The valid pattern still applies
+        let node = node_with_body("Escalated as TCK-1042 yesterday");
+        assert!(matches!(check(&node, &config), PiiOutcome::Reject(_)));
+    }
+}
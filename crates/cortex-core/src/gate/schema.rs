@@ -2,7 +2,10 @@
 //!
 //! When schemas are defined (in `cortex.toml`), nodes of those kinds have their
 //! `metadata` fields validated at write time. Kinds without schemas pass freely.
+//! The validator also carries the [`KindRegistry`] that write paths check
+//! incoming kinds against.
 
+use crate::kinds::KindRegistry;
 use crate::Node;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -59,19 +62,34 @@ impl std::fmt::Display for SchemaViolation {
 #[derive(Debug, Clone, Default)]
 pub struct SchemaValidator {
     schemas: HashMap<String, KindSchema>,
+    kinds: KindRegistry,
 }
 
 impl SchemaValidator {
-    /// Create a new validator with the given schemas (kind -> schema).
+    /// Create a new validator with the given schemas (kind -> schema) and
+    /// the built-in, lenient kind registry.
     pub fn new(schemas: HashMap<String, KindSchema>) -> Self {
-        Self { schemas }
+        Self {
+            schemas,
+            kinds: KindRegistry::default(),
+        }
     }
 
     /// Create an empty validator that passes everything.
     pub fn empty() -> Self {
-        Self {
-            schemas: HashMap::new(),
-        }
+        Self::default()
+    }
+
+    /// Use `kinds` as the registry of allowed node kinds.
+    pub fn with_kinds(mut self, kinds: KindRegistry) -> Self {
+        self.kinds = kinds;
+        self
+    }
+
+    /// The registry new node kinds are checked against with
+    /// [`NodeKind::new_checked`](crate::NodeKind::new_checked).
+    pub fn kinds(&self) -> &KindRegistry {
+        &self.kinds
     }
 
     /// Returns true if any schemas are configured.
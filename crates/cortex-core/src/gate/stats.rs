@@ -0,0 +1,224 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::GateRejection;
+
+/// Length of the rolling accepted/rejected window, in minutes.
+pub const GATE_STATS_WINDOW_MINUTES: i64 = 60;
+
+/// Running tally of write gate outcomes: accepted and rejected nodes, and
+/// failed checks by check and node kind. Shared by every gate call site so
+/// thresholds can be tuned from what actually gets rejected.
+#[derive(Debug, Default)]
+pub struct GateStats {
+    tally: Mutex<Tally>,
+}
+
+#[derive(Debug, Default)]
+struct Tally {
+    accepted: u64,
+    rejected: u64,
+    /// (check, kind) -> failures
+    failures: BTreeMap<(String, String), u64>,
+    /// (minute since the epoch, accepted, rejected), oldest first
+    recent: VecDeque<(i64, u64, u64)>,
+}
+
+/// A copy of [`GateStats`] at one point in time.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct GateStatsSnapshot {
+    pub accepted: u64,
+    pub rejected: u64,
+    /// Accepted over the last [`GATE_STATS_WINDOW_MINUTES`].
+    pub recent_accepted: u64,
+    /// Rejected over the last [`GATE_STATS_WINDOW_MINUTES`].
+    pub recent_rejected: u64,
+    /// Failures per check, over all kinds.
+    pub by_check: BTreeMap<String, u64>,
+    /// Failures per check and kind, by check then kind.
+    pub failures: Vec<GateFailureCount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GateFailureCount {
+    pub check: String,
+    pub kind: String,
+    pub count: u64,
+}
+
+impl GateStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one node's pass through the gate: the checks it failed and
+    /// whether it was stored. In warn mode a node can fail checks and still
+    /// be accepted.
+    pub fn record(&self, kind: &str, failed: &[GateRejection], accepted: bool) {
+        self.record_at(Utc::now(), kind, failed, accepted);
+    }
+
+    fn record_at(&self, at: DateTime<Utc>, kind: &str, failed: &[GateRejection], accepted: bool) {
+        let mut tally = self.tally.lock().unwrap();
+        for r in failed {
+            *tally
+                .failures
+                .entry((r.check.to_string(), kind.to_string()))
+                .or_default() += 1;
+        }
+        if accepted {
+            tally.accepted += 1;
+        } else {
+            tally.rejected += 1;
+        }
+
+        let minute = at.timestamp().div_euclid(60);
+        match tally.recent.back_mut() {
+            Some((m, a, r)) if *m == minute => {
+                if accepted {
+                    *a += 1;
+                } else {
+                    *r += 1;
+                }
+            }
+            _ => tally
+                .recent
+                .push_back((minute, accepted as u64, !accepted as u64)),
+        }
+        prune(&mut tally.recent, minute);
+    }
+
+    pub fn snapshot(&self) -> GateStatsSnapshot {
+        self.snapshot_at(Utc::now())
+    }
+
+    fn snapshot_at(&self, at: DateTime<Utc>) -> GateStatsSnapshot {
+        let mut tally = self.tally.lock().unwrap();
+        prune(&mut tally.recent, at.timestamp().div_euclid(60));
+
+        let mut by_check: BTreeMap<String, u64> = BTreeMap::new();
+        let failures = tally
+            .failures
+            .iter()
+            .map(|((check, kind), &count)| {
+                *by_check.entry(check.clone()).or_default() += count;
+                GateFailureCount {
+                    check: check.clone(),
+                    kind: kind.clone(),
+                    count,
+                }
+            })
+            .collect();
+        GateStatsSnapshot {
+            accepted: tally.accepted,
+            rejected: tally.rejected,
+            recent_accepted: tally.recent.iter().map(|&(_, a, _)| a).sum(),
+            recent_rejected: tally.recent.iter().map(|&(_, _, r)| r).sum(),
+            by_check,
+            failures,
+        }
+    }
+}
+
+/// Drop minutes that have left the window ending at `minute`.
+fn prune(recent: &mut VecDeque<(i64, u64, u64)>, minute: i64) {
+    while recent
+        .front()
+        .is_some_and(|&(m, _, _)| m <= minute - GATE_STATS_WINDOW_MINUTES)
+    {
+        recent.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Node, NodeKind, Source, WriteGate, WriteGateConfig};
+
+    fn node(kind: &str, title: &str, body: &str) -> Node {
+        let mut n = Node::new(
+            NodeKind::new(kind).unwrap(),
+            title.to_string(),
+            body.to_string(),
+            Source {
+                agent: "test".to_string(),
+                session: None,
+                channel: None,
+            },
+            0.5,
+        );
+        n.data.tags = vec!["test".into()];
+        n
+    }
+
+    #[test]
+    fn test_counts_failures_by_check_and_kind() {
+        let config = WriteGateConfig::default();
+        let stats = GateStats::new();
+        let nodes = [
+            // Passes
+            node(
+                "fact",
+                "Redb storage engine",
+                "Cortex stores the graph in a single redb file",
+            ),
+            // Substance: title and body too short
+            node("fact", "Short", "Too brief"),
+            // Substance: decision without a choice
+            node(
+                "decision",
+                "Storage engine review",
+                "The storage engines were compared at length",
+            ),
+            // Specificity: relative time reference
+            node(
+                "event",
+                "Yesterday's deploy of the API",
+                "The API was rolled out to every region",
+            ),
+        ];
+        for n in &nodes {
+            let failed = [
+                WriteGate::substance_rejections(n, &config),
+                WriteGate::specificity_rejections(n, &config),
+            ]
+            .concat();
+            stats.record(n.kind.as_str(), &failed, failed.is_empty());
+        }
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.accepted, 1);
+        assert_eq!(snapshot.rejected, 3);
+        assert_eq!((snapshot.recent_accepted, snapshot.recent_rejected), (1, 3));
+        assert_eq!(snapshot.by_check["substance"], 3);
+        assert_eq!(snapshot.by_check["specificity"], 1);
+        let count = |check: &str, kind: &str| {
+            snapshot
+                .failures
+                .iter()
+                .find(|f| f.check == check && f.kind == kind)
+                .map(|f| f.count)
+        };
+        assert_eq!(count("substance", "fact"), Some(2));
+        assert_eq!(count("substance", "decision"), Some(1));
+        assert_eq!(count("specificity", "event"), Some(1));
+        assert_eq!(count("specificity", "fact"), None);
+    }
+
+    #[test]
+    fn test_recent_window_rolls_over() {
+        let stats = GateStats::new();
+        let start = Utc::now();
+        stats.record_at(start, "fact", &[], true);
+        stats.record_at(start, "fact", &[], false);
+        let later = start + chrono::Duration::minutes(GATE_STATS_WINDOW_MINUTES + 1);
+        stats.record_at(later, "fact", &[], true);
+
+        let snapshot = stats.snapshot_at(later);
+        assert_eq!((snapshot.accepted, snapshot.rejected), (2, 1));
+        assert_eq!((snapshot.recent_accepted, snapshot.recent_rejected), (1, 0));
+    }
+}
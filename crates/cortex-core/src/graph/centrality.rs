@@ -0,0 +1,78 @@
+use crate::error::{CortexError, Result};
+use crate::graph::PageRankConfig;
+use crate::storage::{NodeFilter, Storage};
+use crate::types::NodeId;
+use std::collections::HashMap;
+
+/// Iterative PageRank over all live nodes and the edges between them.
+///
+/// Ranks sum to 1.0. Nodes with no outgoing edges spread their rank evenly
+/// across the graph so it is not lost between iterations.
+pub fn pagerank<S: Storage>(storage: &S, config: &PageRankConfig) -> Result<HashMap<NodeId, f32>> {
+    if !(0.0..=1.0).contains(&config.damping) {
+        return Err(CortexError::Validation(format!(
+            "PageRank damping must be within 0.0..=1.0, got {}",
+            config.damping
+        )));
+    }
+
+    let nodes = storage.list_nodes(NodeFilter::new())?;
+    let n = nodes.len();
+    if n == 0 {
+        return Ok(HashMap::new());
+    }
+
+    let index: HashMap<NodeId, usize> = nodes.iter().enumerate().map(|(i, n)| (n.id, i)).collect();
+
+    // Outgoing adjacency as (target index, share of the source's rank)
+    let mut outgoing: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
+    for (i, node) in nodes.iter().enumerate() {
+        let targets: Vec<(usize, f64)> = storage
+            .edges_from(node.id)?
+            .into_iter()
+            .filter_map(|e| {
+                let w = if config.weighted {
+                    e.weight.max(0.0) as f64
+                } else {
+                    1.0
+                };
+                index.get(&e.to).map(|&j| (j, w))
+            })
+            .filter(|&(_, w)| w > 0.0)
+            .collect();
+        let total: f64 = targets.iter().map(|(_, w)| w).sum();
+        outgoing[i] = targets.into_iter().map(|(j, w)| (j, w / total)).collect();
+    }
+
+    let damping = config.damping as f64;
+    let base = (1.0 - damping) / n as f64;
+    let mut rank = vec![1.0 / n as f64; n];
+
+    for _ in 0..config.max_iterations {
+        let dangling: f64 = rank
+            .iter()
+            .zip(&outgoing)
+            .filter(|(_, out)| out.is_empty())
+            .map(|(r, _)| r)
+            .sum();
+
+        let mut next = vec![base + damping * dangling / n as f64; n];
+        for (i, out) in outgoing.iter().enumerate() {
+            for &(j, share) in out {
+                next[j] += damping * rank[i] * share;
+            }
+        }
+
+        let delta: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if delta < config.tolerance as f64 {
+            break;
+        }
+    }
+
+    Ok(nodes
+        .iter()
+        .zip(rank)
+        .map(|(node, r)| (node.id, r as f32))
+        .collect())
+}
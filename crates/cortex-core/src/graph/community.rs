@@ -0,0 +1,137 @@
+use crate::error::Result;
+use crate::graph::{Community, CommunityConfig};
+use crate::storage::{NodeFilter, Storage};
+use crate::types::NodeId;
+use std::collections::HashMap;
+
+/// Label propagation over the undirected projection of the edge set.
+///
+/// Every node starts in its own community and repeatedly adopts the label
+/// carrying the most edge weight among its neighbours, until no label changes.
+/// Nodes are visited in id order (UUIDv7, i.e. creation order) and ties go to
+/// the lowest label, so results are deterministic for a given graph.
+///
+/// Communities are returned largest first.
+pub fn detect_communities<S: Storage>(
+    storage: &S,
+    config: &CommunityConfig,
+) -> Result<Vec<Community>> {
+    let mut nodes = storage.list_nodes(NodeFilter::new())?;
+    nodes.sort_by_key(|n| n.id);
+    let n = nodes.len();
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    let index: HashMap<NodeId, usize> = nodes.iter().enumerate().map(|(i, n)| (n.id, i)).collect();
+
+    // Undirected, weighted adjacency. Parallel and reciprocal edges add up.
+    let mut adjacency: Vec<HashMap<usize, f64>> = vec![HashMap::new(); n];
+    let mut total_weight = 0.0;
+    for (i, node) in nodes.iter().enumerate() {
+        for edge in storage.edges_from(node.id)? {
+            let Some(&j) = index.get(&edge.to) else {
+                continue;
+            };
+            if i == j {
+                continue;
+            }
+            let w = if config.weighted {
+                edge.weight.max(0.0) as f64
+            } else {
+                1.0
+            };
+            if w == 0.0 {
+                continue;
+            }
+            *adjacency[i].entry(j).or_default() += w;
+            *adjacency[j].entry(i).or_default() += w;
+            total_weight += w;
+        }
+    }
+
+    let mut labels: Vec<usize> = (0..n).collect();
+    for _ in 0..config.max_iterations {
+        let mut changed = false;
+        for i in 0..n {
+            if adjacency[i].is_empty() {
+                continue;
+            }
+            let mut votes: HashMap<usize, f64> = HashMap::new();
+            for (&j, &w) in &adjacency[i] {
+                *votes.entry(labels[j]).or_default() += w;
+            }
+            let best = votes
+                .into_iter()
+                .max_by(|a, b| {
+                    a.1.partial_cmp(&b.1)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                        .then(b.0.cmp(&a.0))
+                })
+                .map(|(label, _)| label)
+                .unwrap_or(labels[i]);
+            if best != labels[i] {
+                labels[i] = best;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Group members and compute each community's modularity contribution:
+    // Q_c = L_c / m - (d_c / 2m)^2, where L_c is internal edge weight and d_c
+    // the summed weighted degree of its members.
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (i, &label) in labels.iter().enumerate() {
+        groups.entry(label).or_default().push(i);
+    }
+
+    let mut communities: Vec<Community> = groups
+        .into_values()
+        .map(|members| {
+            let mut internal = 0.0;
+            let mut degree_sum = 0.0;
+            let mut scored: Vec<(usize, f64)> = members
+                .iter()
+                .map(|&i| {
+                    let degree: f64 = adjacency[i].values().sum();
+                    let inside: f64 = adjacency[i]
+                        .iter()
+                        .filter(|(j, _)| labels[**j] == labels[i])
+                        .map(|(_, w)| w)
+                        .sum();
+                    degree_sum += degree;
+                    internal += inside;
+                    (i, inside)
+                })
+                .collect();
+
+            let modularity = if total_weight > 0.0 {
+                // `internal` counted each edge from both ends
+                (internal / 2.0) / total_weight - (degree_sum / (2.0 * total_weight)).powi(2)
+            } else {
+                0.0
+            };
+
+            scored.sort_by(|a, b| {
+                b.1.partial_cmp(&a.1)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(a.0.cmp(&b.0))
+            });
+            Community {
+                nodes: scored.into_iter().map(|(i, _)| nodes[i].id).collect(),
+                modularity: modularity as f32,
+            }
+        })
+        .filter(|c| c.nodes.len() >= config.min_size)
+        .collect();
+
+    communities.sort_by(|a, b| {
+        b.nodes
+            .len()
+            .cmp(&a.nodes.len())
+            .then(a.nodes[0].cmp(&b.nodes[0]))
+    });
+    Ok(communities)
+}
@@ -0,0 +1,72 @@
+use crate::error::Result;
+use crate::storage::Storage;
+use crate::types::{Edge, NodeId};
+use std::collections::{HashMap, HashSet};
+
+/// Cycles in the directed graph `adjacency`, found by a depth-first search
+/// from each of `roots` in turn. Each cycle is the path from the first of
+/// its nodes the search reached round to the node whose edge closes it.
+///
+/// Iterative, so long chains don't overflow the stack.
+pub(crate) fn cycles_in(
+    roots: &[NodeId],
+    adjacency: &HashMap<NodeId, Vec<NodeId>>,
+) -> Vec<Vec<NodeId>> {
+    let mut visited: HashSet<NodeId> = HashSet::new();
+    let mut on_path: HashSet<NodeId> = HashSet::new();
+    let mut cycles = Vec::new();
+
+    for &root in roots {
+        if !visited.insert(root) {
+            continue;
+        }
+        on_path.insert(root);
+        // The current path, each node with the index of its next neighbour
+        let mut stack: Vec<(NodeId, usize)> = vec![(root, 0)];
+
+        while let Some(&(node, next)) = stack.last() {
+            let Some(&to) = adjacency.get(&node).and_then(|n| n.get(next)) else {
+                on_path.remove(&node);
+                stack.pop();
+                continue;
+            };
+            let top = stack.len() - 1;
+            stack[top].1 += 1;
+
+            if on_path.contains(&to) {
+                if let Some(pos) = stack.iter().position(|&(n, _)| n == to) {
+                    cycles.push(stack[pos..].iter().map(|&(n, _)| n).collect());
+                }
+            } else if visited.insert(to) {
+                on_path.insert(to);
+                stack.push((to, 0));
+            }
+        }
+    }
+
+    cycles
+}
+
+/// Whether storing `edge` would close a cycle of its relation, i.e. whether
+/// `edge.to` already reaches `edge.from` through edges of that relation.
+pub fn would_create_cycle<S: Storage>(storage: &S, edge: &Edge) -> Result<bool> {
+    if edge.from == edge.to {
+        return Ok(true);
+    }
+    let mut seen = HashSet::from([edge.to]);
+    let mut frontier = vec![edge.to];
+    while let Some(id) = frontier.pop() {
+        for next in storage.edges_from(id)? {
+            if next.relation != edge.relation {
+                continue;
+            }
+            if next.to == edge.from {
+                return Ok(true);
+            }
+            if seen.insert(next.to) {
+                frontier.push(next.to);
+            }
+        }
+    }
+    Ok(false)
+}
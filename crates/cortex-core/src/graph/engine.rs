@@ -1,18 +1,28 @@
 use crate::error::Result;
 use crate::graph::{
-    cache::AdjacencyCache, paths, traversal, PathRequest, PathResult, Subgraph, TraversalBudget,
-    TraversalDirection, TraversalRequest, TraversalStrategy,
+    cache::AdjacencyCache, centrality, community, cycles, paths, traversal, Community,
+    CommunityConfig, PageRankConfig, PathRequest, PathResult, Subgraph, TraversalBudget,
+    TraversalDirection, TraversalLevel, TraversalRequest, TraversalStrategy, TraversalStream,
 };
 use crate::storage::{NodeFilter, Storage};
 use crate::types::{Edge, Node, NodeId, Relation};
-use std::collections::{HashSet, VecDeque};
-use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
 
 /// Graph query and traversal engine
 pub trait GraphEngine: Send + Sync {
     /// Core traversal. Everything else builds on this.
     fn traverse(&self, request: TraversalRequest) -> Result<Subgraph>;
 
+    /// Breadth-first traversal yielded a level at a time, for subgraphs too
+    /// large to build in one go. See [`TraversalStream`].
+    fn traverse_stream(
+        &self,
+        request: TraversalRequest,
+    ) -> Box<dyn Iterator<Item = Result<TraversalLevel>> + Send>;
+
     /// Find paths between two nodes.
     fn find_paths(&self, request: PathRequest) -> Result<PathResult>;
 
@@ -29,6 +39,15 @@ pub trait GraphEngine: Send + Sync {
     /// Everything within N hops of a node.
     fn neighborhood(&self, id: NodeId, depth: u32) -> Result<Subgraph>;
 
+    /// Neighborhood of a node as the graph stood at `as_of`. For direction,
+    /// relation or limit control, set `TraversalRequest::as_of` directly.
+    fn traverse_as_of(&self, start: NodeId, depth: u32, as_of: DateTime<Utc>) -> Result<Subgraph>;
+
+    /// The induced subgraph of every node matching `filter`: those nodes and
+    /// only the edges with both endpoints among them. All nodes are at
+    /// depth 0.
+    fn subgraph_by_filter(&self, filter: NodeFilter) -> Result<Subgraph>;
+
     /// All nodes that a given node can reach (transitive closure).
     fn reachable(&self, id: NodeId, direction: TraversalDirection) -> Result<Vec<NodeId>>;
 
@@ -40,8 +59,9 @@ pub trait GraphEngine: Send + Sync {
     /// Useful for finding "leaf outcomes" or "terminal states."
     fn leaves(&self, relation: Relation) -> Result<Vec<Node>>;
 
-    /// Detect cycles in the graph (or within a subgraph).
-    fn find_cycles(&self) -> Result<Vec<Vec<NodeId>>>;
+    /// Detect cycles among live nodes, optionally following only edges of
+    /// one relation. Each cycle is listed once, as the nodes along it.
+    fn find_cycles(&self, relation: Option<Relation>) -> Result<Vec<Vec<NodeId>>>;
 
     /// Connected components. Groups of nodes that can reach each other.
     fn components(&self) -> Result<Vec<Vec<NodeId>>>;
@@ -49,6 +69,21 @@ pub trait GraphEngine: Send + Sync {
     /// Degree centrality: which nodes have the most connections?
     /// Returns nodes sorted by total edge count (in + out).
     fn most_connected(&self, limit: usize) -> Result<Vec<(Node, usize)>>;
+
+    /// PageRank centrality: which nodes does the graph's structure point at?
+    /// Scores sum to 1.0 across all live nodes.
+    fn pagerank(&self, config: PageRankConfig) -> Result<HashMap<NodeId, f32>>;
+
+    /// Clusters of tightly-connected nodes (label propagation over edges
+    /// treated as undirected). Largest community first.
+    fn detect_communities(&self, config: CommunityConfig) -> Result<Vec<Community>>;
+}
+
+/// PageRank scores memoised against the graph version they were computed at.
+struct CachedPageRank {
+    version: u64,
+    config: PageRankConfig,
+    scores: HashMap<NodeId, f32>,
 }
 
 /// Implementation of the graph engine
@@ -56,6 +91,8 @@ pub struct GraphEngineImpl<S: Storage> {
     storage: Arc<S>,
     budget: TraversalBudget,
     cache: AdjacencyCache,
+    graph_version: Option<Arc<AtomicU64>>,
+    pagerank_cache: RwLock<Option<CachedPageRank>>,
 }
 
 impl<S: Storage> GraphEngineImpl<S> {
@@ -66,6 +103,8 @@ impl<S: Storage> GraphEngineImpl<S> {
             storage,
             budget: TraversalBudget::default(),
             cache,
+            graph_version: None,
+            pagerank_cache: RwLock::new(None),
         }
     }
 
@@ -76,9 +115,19 @@ impl<S: Storage> GraphEngineImpl<S> {
             storage,
             budget,
             cache,
+            graph_version: None,
+            pagerank_cache: RwLock::new(None),
         }
     }
 
+    /// Share the server's graph version counter so expensive whole-graph
+    /// results (PageRank) are reused until the next write bumps it.
+    /// Without it, every call recomputes.
+    pub fn with_graph_version(mut self, graph_version: Arc<AtomicU64>) -> Self {
+        self.graph_version = Some(graph_version);
+        self
+    }
+
     /// Ensure the adjacency cache is valid, rebuilding if necessary
     fn ensure_cache(&self) -> Result<()> {
         if !self.cache.is_valid() {
@@ -125,8 +174,30 @@ impl<S: Storage> GraphEngineImpl<S> {
 }
 
 impl<S: Storage + 'static> GraphEngine for GraphEngineImpl<S> {
+    #[tracing::instrument(
+        name = "graph.traverse",
+        skip_all,
+        fields(
+            start = request.start.len(),
+            max_depth = ?request.max_depth,
+            nodes = tracing::field::Empty,
+        )
+    )]
     fn traverse(&self, request: TraversalRequest) -> Result<Subgraph> {
-        traversal::traverse(self.storage.as_ref(), request, &self.budget)
+        let subgraph = traversal::traverse(self.storage.as_ref(), request, &self.budget)?;
+        tracing::Span::current().record("nodes", subgraph.nodes.len());
+        Ok(subgraph)
+    }
+
+    fn traverse_stream(
+        &self,
+        request: TraversalRequest,
+    ) -> Box<dyn Iterator<Item = Result<TraversalLevel>> + Send> {
+        Box::new(TraversalStream::new(
+            self.storage.clone(),
+            request,
+            self.budget.clone(),
+        ))
     }
 
     fn find_paths(&self, request: PathRequest) -> Result<PathResult> {
@@ -183,9 +254,50 @@ impl<S: Storage + 'static> GraphEngine for GraphEngineImpl<S> {
             strategy: TraversalStrategy::Bfs,
             include_start: true,
             created_after: None,
+            as_of: None,
+            include_expired: false,
+            include_deleted: false,
+        })
+    }
+
+    fn traverse_as_of(&self, start: NodeId, depth: u32, as_of: DateTime<Utc>) -> Result<Subgraph> {
+        self.traverse(TraversalRequest {
+            start: vec![start],
+            max_depth: Some(depth),
+            direction: TraversalDirection::Both,
+            as_of: Some(as_of),
+            ..TraversalRequest::default()
         })
     }
 
+    fn subgraph_by_filter(&self, filter: NodeFilter) -> Result<Subgraph> {
+        self.ensure_cache()?;
+        // One extra node tells "exactly `limit` matched" from "more did"
+        let limit = filter.limit;
+        let mut filter = filter;
+        filter.limit = limit.map(|l| l.saturating_add(1));
+        let mut nodes = self.storage.list_nodes(filter)?;
+
+        let mut subgraph = Subgraph::new();
+        subgraph.truncated = limit.is_some_and(|l| nodes.len() > l);
+        if let Some(l) = limit {
+            nodes.truncate(l);
+        }
+        subgraph.visited_count = nodes.len();
+        for node in nodes {
+            subgraph.depths.insert(node.id, 0);
+            subgraph.nodes.insert(node.id, node);
+        }
+        for &id in subgraph.nodes.keys() {
+            subgraph.edges.extend(
+                self.cached_edges_from(id)?
+                    .into_iter()
+                    .filter(|e| subgraph.nodes.contains_key(&e.to)),
+            );
+        }
+        Ok(subgraph)
+    }
+
     fn reachable(&self, id: NodeId, direction: TraversalDirection) -> Result<Vec<NodeId>> {
         let subgraph = self.traverse(TraversalRequest {
             start: vec![id],
@@ -198,6 +310,9 @@ impl<S: Storage + 'static> GraphEngine for GraphEngineImpl<S> {
             strategy: TraversalStrategy::Bfs,
             include_start: false,
             created_after: None,
+            as_of: None,
+            include_expired: false,
+            include_deleted: false,
         })?;
 
         Ok(subgraph.nodes.keys().copied().collect())
@@ -253,30 +368,30 @@ impl<S: Storage + 'static> GraphEngine for GraphEngineImpl<S> {
         Ok(leaves)
     }
 
-    fn find_cycles(&self) -> Result<Vec<Vec<NodeId>>> {
+    fn find_cycles(&self, relation: Option<Relation>) -> Result<Vec<Vec<NodeId>>> {
         self.ensure_cache()?;
-        let all_nodes = self.storage.list_nodes(NodeFilter::new())?;
-
-        let mut visited = HashSet::new();
-        let mut rec_stack = HashSet::new();
-        let mut cycles = Vec::new();
-
-        for node in &all_nodes {
-            if node.deleted {
-                continue;
-            }
-            if !visited.contains(&node.id) {
-                self.find_cycles_dfs(
-                    node.id,
-                    &mut visited,
-                    &mut rec_stack,
-                    &mut Vec::new(),
-                    &mut cycles,
-                )?;
-            }
+        let live: Vec<NodeId> = self
+            .storage
+            .list_nodes(NodeFilter::new())?
+            .into_iter()
+            .filter(|n| !n.deleted)
+            .map(|n| n.id)
+            .collect();
+        let live_set: HashSet<NodeId> = live.iter().copied().collect();
+
+        let mut adjacency: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for &id in &live {
+            let targets = self
+                .cached_edges_from(id)?
+                .into_iter()
+                .filter(|e| relation.as_ref().is_none_or(|r| &e.relation == r))
+                .filter(|e| live_set.contains(&e.to))
+                .map(|e| e.to)
+                .collect();
+            adjacency.insert(id, targets);
         }
 
-        Ok(cycles)
+        Ok(cycles::cycles_in(&live, &adjacency))
     }
 
     fn components(&self) -> Result<Vec<Vec<NodeId>>> {
@@ -319,11 +434,40 @@ impl<S: Storage + 'static> GraphEngine for GraphEngineImpl<S> {
         }
 
         // Sort by degree descending
-        node_degrees.sort_by(|a, b| b.1.cmp(&a.1));
+        node_degrees.sort_by_key(|b| std::cmp::Reverse(b.1));
 
         // Take top N
         Ok(node_degrees.into_iter().take(limit).collect())
     }
+
+    fn pagerank(&self, config: PageRankConfig) -> Result<HashMap<NodeId, f32>> {
+        let Some(ref graph_version) = self.graph_version else {
+            return centrality::pagerank(self.storage.as_ref(), &config);
+        };
+
+        let version = graph_version.load(Ordering::Relaxed);
+        if let Ok(cached) = self.pagerank_cache.read() {
+            if let Some(ref c) = *cached {
+                if c.version == version && c.config == config {
+                    return Ok(c.scores.clone());
+                }
+            }
+        }
+
+        let scores = centrality::pagerank(self.storage.as_ref(), &config)?;
+        if let Ok(mut cached) = self.pagerank_cache.write() {
+            *cached = Some(CachedPageRank {
+                version,
+                config,
+                scores: scores.clone(),
+            });
+        }
+        Ok(scores)
+    }
+
+    fn detect_communities(&self, config: CommunityConfig) -> Result<Vec<Community>> {
+        community::detect_communities(self.storage.as_ref(), &config)
+    }
 }
 
 /// Blanket impl: Arc<G> forwards all GraphEngine calls to G.
@@ -332,6 +476,12 @@ impl<G: GraphEngine> GraphEngine for std::sync::Arc<G> {
     fn traverse(&self, request: TraversalRequest) -> Result<Subgraph> {
         (**self).traverse(request)
     }
+    fn traverse_stream(
+        &self,
+        request: TraversalRequest,
+    ) -> Box<dyn Iterator<Item = Result<TraversalLevel>> + Send> {
+        (**self).traverse_stream(request)
+    }
     fn find_paths(&self, request: PathRequest) -> Result<PathResult> {
         (**self).find_paths(request)
     }
@@ -346,6 +496,12 @@ impl<G: GraphEngine> GraphEngine for std::sync::Arc<G> {
     fn neighborhood(&self, id: NodeId, depth: u32) -> Result<Subgraph> {
         (**self).neighborhood(id, depth)
     }
+    fn traverse_as_of(&self, start: NodeId, depth: u32, as_of: DateTime<Utc>) -> Result<Subgraph> {
+        (**self).traverse_as_of(start, depth, as_of)
+    }
+    fn subgraph_by_filter(&self, filter: NodeFilter) -> Result<Subgraph> {
+        (**self).subgraph_by_filter(filter)
+    }
     fn reachable(&self, id: NodeId, direction: TraversalDirection) -> Result<Vec<NodeId>> {
         (**self).reachable(id, direction)
     }
@@ -355,8 +511,8 @@ impl<G: GraphEngine> GraphEngine for std::sync::Arc<G> {
     fn leaves(&self, relation: Relation) -> Result<Vec<Node>> {
         (**self).leaves(relation)
     }
-    fn find_cycles(&self) -> Result<Vec<Vec<NodeId>>> {
-        (**self).find_cycles()
+    fn find_cycles(&self, relation: Option<Relation>) -> Result<Vec<Vec<NodeId>>> {
+        (**self).find_cycles(relation)
     }
     fn components(&self) -> Result<Vec<Vec<NodeId>>> {
         (**self).components()
@@ -364,42 +520,15 @@ impl<G: GraphEngine> GraphEngine for std::sync::Arc<G> {
     fn most_connected(&self, limit: usize) -> Result<Vec<(Node, usize)>> {
         (**self).most_connected(limit)
     }
+    fn pagerank(&self, config: PageRankConfig) -> Result<HashMap<NodeId, f32>> {
+        (**self).pagerank(config)
+    }
+    fn detect_communities(&self, config: CommunityConfig) -> Result<Vec<Community>> {
+        (**self).detect_communities(config)
+    }
 }
 
 impl<S: Storage> GraphEngineImpl<S> {
-    /// Helper for cycle detection using DFS
-    fn find_cycles_dfs(
-        &self,
-        node: NodeId,
-        visited: &mut HashSet<NodeId>,
-        rec_stack: &mut HashSet<NodeId>,
-        path: &mut Vec<NodeId>,
-        cycles: &mut Vec<Vec<NodeId>>,
-    ) -> Result<()> {
-        visited.insert(node);
-        rec_stack.insert(node);
-        path.push(node);
-
-        let outgoing = self.cached_edges_from(node)?;
-
-        for edge in outgoing {
-            if !visited.contains(&edge.to) {
-                self.find_cycles_dfs(edge.to, visited, rec_stack, path, cycles)?;
-            } else if rec_stack.contains(&edge.to) {
-                // Found a cycle
-                if let Some(pos) = path.iter().position(|&x| x == edge.to) {
-                    let cycle = path[pos..].to_vec();
-                    cycles.push(cycle);
-                }
-            }
-        }
-
-        path.pop();
-        rec_stack.remove(&node);
-
-        Ok(())
-    }
-
     /// Helper for connected components using BFS
     fn component_bfs(
         &self,
@@ -0,0 +1,122 @@
+//! Serialise a [`Subgraph`] to interchange formats.
+//!
+//! - **DOT** for Graphviz (`dot -Tsvg`).
+//! - **GraphML** for Gephi, yEd and networkx.
+//!
+//! Output is deterministic: nodes are sorted by ID, and edges by ID with
+//! duplicates removed. This makes exports diffable across runs.
+
+use crate::graph::Subgraph;
+use crate::types::{Edge, Node};
+use std::collections::HashSet;
+
+/// Render a subgraph as a Graphviz digraph.
+///
+/// Nodes carry `label`, `kind` and `importance` attributes. Edges carry
+/// `label`, `relation` and `cortex_weight`. Graphviz reserves `weight` for
+/// an integer layout hint, so the edge weight uses a different name.
+pub fn to_dot(subgraph: &Subgraph) -> String {
+    let mut out = String::from("digraph cortex {\n  rankdir=LR;\n  node [shape=box];\n");
+
+    for node in sorted_nodes(subgraph) {
+        out.push_str(&format!(
+            "  \"{}\" [label=\"{}\\n[{}]\", kind=\"{}\", importance={}];\n",
+            node.id,
+            dot_escape(&node.data.title),
+            dot_escape(node.kind.as_str()),
+            dot_escape(node.kind.as_str()),
+            node.importance
+        ));
+    }
+
+    for edge in sorted_edges(subgraph) {
+        out.push_str(&format!(
+            "  \"{}\" -> \"{}\" [label=\"{}\", relation=\"{}\", cortex_weight={}];\n",
+            edge.from,
+            edge.to,
+            dot_escape(edge.relation.as_str()),
+            dot_escape(edge.relation.as_str()),
+            edge.weight
+        ));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Render a subgraph as a GraphML document.
+///
+/// Nodes carry `kind`, `title` and `importance`. Edges carry `relation`
+/// and `weight`.
+pub fn to_graphml(subgraph: &Subgraph) -> String {
+    let mut out = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="kind" for="node" attr.name="kind" attr.type="string"/>
+  <key id="title" for="node" attr.name="title" attr.type="string"/>
+  <key id="importance" for="node" attr.name="importance" attr.type="float"/>
+  <key id="relation" for="edge" attr.name="relation" attr.type="string"/>
+  <key id="weight" for="edge" attr.name="weight" attr.type="float"/>
+  <graph id="cortex" edgedefault="directed">
+"#,
+    );
+
+    for node in sorted_nodes(subgraph) {
+        out.push_str(&format!(
+            "    <node id=\"{}\">\n      <data key=\"kind\">{}</data>\n      <data key=\"title\">{}</data>\n      <data key=\"importance\">{}</data>\n    </node>\n",
+            node.id,
+            xml_escape(node.kind.as_str()),
+            xml_escape(&node.data.title),
+            node.importance
+        ));
+    }
+
+    for edge in sorted_edges(subgraph) {
+        out.push_str(&format!(
+            "    <edge id=\"{}\" source=\"{}\" target=\"{}\">\n      <data key=\"relation\">{}</data>\n      <data key=\"weight\">{}</data>\n    </edge>\n",
+            edge.id,
+            edge.from,
+            edge.to,
+            xml_escape(edge.relation.as_str()),
+            edge.weight
+        ));
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+fn sorted_nodes(subgraph: &Subgraph) -> Vec<&Node> {
+    let mut nodes: Vec<&Node> = subgraph.nodes.values().collect();
+    nodes.sort_by_key(|n| n.id);
+    nodes
+}
+
+fn sorted_edges(subgraph: &Subgraph) -> Vec<&Edge> {
+    let mut seen = HashSet::new();
+    let mut edges: Vec<&Edge> = subgraph
+        .edges
+        .iter()
+        .filter(|e| seen.insert(e.id))
+        .collect();
+    edges.sort_by_key(|e| e.id);
+    edges
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "")
+}
+
+fn xml_escape(s: &str) -> String {
+    s.chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\t'))
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
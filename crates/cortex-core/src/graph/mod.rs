@@ -1,5 +1,9 @@
 mod cache;
+mod centrality;
+mod community;
+pub(crate) mod cycles;
 mod engine;
+pub mod export;
 mod paths;
 mod subgraph;
 mod temporal;
@@ -7,9 +11,11 @@ mod traversal;
 mod types;
 
 pub use cache::AdjacencyCache;
+pub use cycles::would_create_cycle;
 pub use engine::{GraphEngine, GraphEngineImpl};
 pub use subgraph::Subgraph;
 pub use temporal::{TemporalQueries, TemporalQueriesImpl};
+pub use traversal::TraversalStream;
 pub use types::*;
 
 #[cfg(test)]
@@ -1,9 +1,9 @@
 use crate::error::Result;
-use crate::graph::{Path, PathRequest, PathResult};
+use crate::graph::{Path, PathRequest, PathResult, PathStrategy, TraversalDirection};
 use crate::storage::Storage;
-use crate::types::{EdgeId, NodeId};
+use crate::types::{Edge, EdgeId, NodeId};
 use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::collections::{BinaryHeap, HashMap, HashSet};
 
 /// State for Dijkstra's algorithm
 #[derive(Clone, Debug)]
@@ -42,71 +42,248 @@ impl Ord for DijkstraState {
 pub fn find_paths<S: Storage>(storage: &S, request: PathRequest) -> Result<PathResult> {
     if request.max_paths == 1 {
         // Single shortest path
-        if request.min_weight.is_some() {
-            find_weighted_shortest_path(storage, &request)
-        } else {
-            find_unweighted_shortest_path(storage, &request)
-        }
+        find_shortest_path(storage, &request)
     } else {
         // K-shortest paths using Yen's algorithm
         find_k_shortest_paths(storage, &request)
     }
 }
 
-/// Find unweighted shortest path using BFS
+/// Whether the request is answered by Dijkstra. A `min_weight` asks for
+/// strong paths, so it implies the weighted strategy.
+fn is_weighted(request: &PathRequest) -> bool {
+    request.strategy == PathStrategy::Weighted || request.min_weight.is_some()
+}
+
+/// Dispatch to BFS or Dijkstra according to the request's strategy
+fn find_shortest_path<S: Storage>(storage: &S, request: &PathRequest) -> Result<PathResult> {
+    if is_weighted(request) {
+        find_weighted_shortest_path(storage, request)
+    } else {
+        find_unweighted_shortest_path(storage, request)
+    }
+}
+
+/// Dijkstra cost of traversing an edge. `1 / weight` rather than
+/// `1 - weight` so that full-strength edges still cost one hop each and
+/// ties between strong chains fall back to the shorter one.
+fn edge_cost(weight: f32) -> f32 {
+    1.0 / weight
+}
+
+/// One side of a bidirectional search: how far a node is from that side's
+/// origin, and the neighbour and edge leading back towards it.
+#[derive(Clone, Copy)]
+struct Visit {
+    depth: u32,
+    via: Option<(NodeId, EdgeId)>,
+}
+
+/// Whether a path may use `edge` under the request's filters.
+fn edge_allowed(request: &PathRequest, edge: &Edge) -> bool {
+    request
+        .relation_filter
+        .as_ref()
+        .is_none_or(|relations| relations.contains(&edge.relation))
+        && request.min_weight.is_none_or(|min| edge.weight >= min)
+}
+
+/// Find the fewest-hop path by bidirectional BFS.
+///
+/// Frontiers grow from `from` along outgoing edges and from `to` along
+/// incoming ones, the smaller of the two by a whole level at a time, until
+/// they meet. Of the meeting nodes found in that level, the one with the
+/// shortest total wins, so the path is as short as a one-sided BFS would
+/// find, while long paths explore far fewer nodes.
 fn find_unweighted_shortest_path<S: Storage>(
     storage: &S,
     request: &PathRequest,
 ) -> Result<PathResult> {
-    let mut queue = VecDeque::new();
-    let mut visited = HashSet::new();
-    let mut parent: HashMap<NodeId, (NodeId, EdgeId)> = HashMap::new();
+    if request.from == request.to {
+        return Ok(PathResult {
+            paths: vec![Path::new(vec![request.from], vec![], 1.0)],
+            explored: 0,
+        });
+    }
 
-    queue.push_back(request.from);
-    visited.insert(request.from);
+    let start = Visit {
+        depth: 0,
+        via: None,
+    };
+    let mut forward: HashMap<NodeId, Visit> = HashMap::from([(request.from, start)]);
+    let mut backward: HashMap<NodeId, Visit> = HashMap::from([(request.to, start)]);
+    let mut forward_frontier = vec![request.from];
+    let mut backward_frontier = vec![request.to];
+    let (mut forward_depth, mut backward_depth) = (0, 0);
+    let mut explored = 0;
+
+    while !forward_frontier.is_empty() && !backward_frontier.is_empty() {
+        // Not met yet, so any path is longer than the two depths together
+        if request
+            .max_length
+            .is_some_and(|max| forward_depth + backward_depth >= max)
+        {
+            break;
+        }
 
-    while let Some(current) = queue.pop_front() {
-        if current == request.to {
-            // Found path, reconstruct it
-            let path = reconstruct_path(request.from, request.to, &parent, storage)?;
-            return Ok(PathResult { paths: vec![path] });
+        let meets = if forward_frontier.len() <= backward_frontier.len() {
+            explored += forward_frontier.len();
+            forward_depth += 1;
+            let (next, meets) = expand_level(
+                storage,
+                request,
+                &forward_frontier,
+                forward_depth,
+                &mut forward,
+                &backward,
+                TraversalDirection::Outgoing,
+            )?;
+            forward_frontier = next;
+            meets
+        } else {
+            explored += backward_frontier.len();
+            backward_depth += 1;
+            let (next, meets) = expand_level(
+                storage,
+                request,
+                &backward_frontier,
+                backward_depth,
+                &mut backward,
+                &forward,
+                TraversalDirection::Incoming,
+            )?;
+            backward_frontier = next;
+            meets
+        };
+
+        let best = meets
+            .into_iter()
+            .map(|id| (forward[&id].depth + backward[&id].depth, id))
+            .filter(|&(length, _)| request.max_length.is_none_or(|max| length <= max))
+            .min_by_key(|&(length, _)| length);
+        if let Some((_, meet)) = best {
+            let path = join_at(storage, meet, &forward, &backward)?;
+            return Ok(PathResult {
+                paths: vec![path],
+                explored,
+            });
         }
+    }
 
-        // Check max length
-        let current_depth = calculate_depth(request.from, current, &parent);
-        if let Some(max_len) = request.max_length {
-            if current_depth >= max_len {
+    Ok(PathResult {
+        paths: vec![],
+        explored,
+    })
+}
+
+/// Expand every node of `frontier` one hop in `direction`, recording new
+/// nodes in `seen` at `depth`. Returns the next frontier and the new nodes
+/// the other side has already seen.
+fn expand_level<S: Storage>(
+    storage: &S,
+    request: &PathRequest,
+    frontier: &[NodeId],
+    depth: u32,
+    seen: &mut HashMap<NodeId, Visit>,
+    other: &HashMap<NodeId, Visit>,
+    direction: TraversalDirection,
+) -> Result<(Vec<NodeId>, Vec<NodeId>)> {
+    let mut next = Vec::new();
+    let mut meets = Vec::new();
+    for &node in frontier {
+        let edges = match direction {
+            TraversalDirection::Incoming => storage.edges_to(node)?,
+            _ => storage.edges_from(node)?,
+        };
+        for edge in edges.iter().filter(|e| edge_allowed(request, e)) {
+            let neighbour = match direction {
+                TraversalDirection::Incoming => edge.from,
+                _ => edge.to,
+            };
+            if seen.contains_key(&neighbour) {
                 continue;
             }
+            seen.insert(
+                neighbour,
+                Visit {
+                    depth,
+                    via: Some((node, edge.id)),
+                },
+            );
+            if other.contains_key(&neighbour) {
+                meets.push(neighbour);
+            }
+            next.push(neighbour);
         }
+    }
+    Ok((next, meets))
+}
 
-        // Get outgoing edges
-        let edges = storage.edges_from(current)?;
+/// The path from the forward origin to `meet`, then on to the backward one.
+fn join_at<S: Storage>(
+    storage: &S,
+    meet: NodeId,
+    forward: &HashMap<NodeId, Visit>,
+    backward: &HashMap<NodeId, Visit>,
+) -> Result<Path> {
+    let mut nodes = vec![meet];
+    let mut edges = Vec::new();
+    let mut current = meet;
+    while let Some((prev, edge_id)) = forward[&current].via {
+        nodes.push(prev);
+        edges.push(edge_id);
+        current = prev;
+    }
+    nodes.reverse();
+    edges.reverse();
 
-        for edge in edges {
-            // Apply filters
-            if let Some(ref relations) = request.relation_filter {
-                if !relations.contains(&edge.relation) {
-                    continue;
-                }
-            }
+    current = meet;
+    while let Some((next, edge_id)) = backward[&current].via {
+        nodes.push(next);
+        edges.push(edge_id);
+        current = next;
+    }
 
-            if let Some(min_weight) = request.min_weight {
-                if edge.weight < min_weight {
-                    continue;
-                }
-            }
+    let total_weight = calculate_path_weight(storage, &edges)?;
+    Ok(Path::new(nodes, edges, total_weight))
+}
 
-            if !visited.contains(&edge.to) {
-                visited.insert(edge.to);
+/// One-sided BFS from `from`, the reference the bidirectional search is
+/// checked against.
+#[cfg(test)]
+pub(crate) fn find_unweighted_shortest_path_one_sided<S: Storage>(
+    storage: &S,
+    request: &PathRequest,
+) -> Result<PathResult> {
+    let mut queue = std::collections::VecDeque::from([(request.from, 0)]);
+    let mut visited = HashSet::from([request.from]);
+    let mut parent: HashMap<NodeId, (NodeId, EdgeId)> = HashMap::new();
+    let mut explored = 0;
+
+    while let Some((current, depth)) = queue.pop_front() {
+        if current == request.to {
+            let path = reconstruct_path(request.from, request.to, &parent, storage)?;
+            return Ok(PathResult {
+                paths: vec![path],
+                explored,
+            });
+        }
+        if request.max_length.is_some_and(|max| depth >= max) {
+            continue;
+        }
+        explored += 1;
+        for edge in storage.edges_from(current)? {
+            if edge_allowed(request, &edge) && visited.insert(edge.to) {
                 parent.insert(edge.to, (current, edge.id));
-                queue.push_back(edge.to);
+                queue.push_back((edge.to, depth + 1));
             }
         }
     }
 
-    // No path found
-    Ok(PathResult { paths: vec![] })
+    Ok(PathResult {
+        paths: vec![],
+        explored,
+    })
 }
 
 /// Find weighted shortest path using Dijkstra (higher weight = lower cost)
@@ -141,6 +318,7 @@ fn find_weighted_shortest_path<S: Storage>(
 
             return Ok(PathResult {
                 paths: vec![Path::new(path, edge_path, total_weight)],
+                explored: visited.len(),
             });
         }
 
@@ -173,9 +351,14 @@ fn find_weighted_shortest_path<S: Storage>(
                 }
             }
 
+            // Zero-weight edges would cost infinity; treat them as absent
+            if edge.weight <= 0.0 {
+                continue;
+            }
+
             if !visited.contains(&edge.to) {
                 // Higher weight = lower cost (invert for Dijkstra)
-                let new_cost = cost + (1.0 - edge.weight);
+                let new_cost = cost + edge_cost(edge.weight);
 
                 let mut new_path = path.clone();
                 new_path.push(edge.to);
@@ -194,7 +377,10 @@ fn find_weighted_shortest_path<S: Storage>(
     }
 
     // No path found
-    Ok(PathResult { paths: vec![] })
+    Ok(PathResult {
+        paths: vec![],
+        explored: visited.len(),
+    })
 }
 
 /// Find k-shortest paths using Yen's algorithm
@@ -202,14 +388,14 @@ fn find_k_shortest_paths<S: Storage>(storage: &S, request: &PathRequest) -> Resu
     let mut result_paths = Vec::new();
 
     // Find first shortest path
-    let first_path_result = if request.min_weight.is_some() {
-        find_weighted_shortest_path(storage, request)?
-    } else {
-        find_unweighted_shortest_path(storage, request)?
-    };
+    let first_path_result = find_shortest_path(storage, request)?;
+    let mut explored = first_path_result.explored;
 
     if first_path_result.paths.is_empty() {
-        return Ok(PathResult { paths: vec![] });
+        return Ok(PathResult {
+            paths: vec![],
+            explored,
+        });
     }
 
     result_paths.push(first_path_result.paths[0].clone());
@@ -239,13 +425,11 @@ fn find_k_shortest_paths<S: Storage>(storage: &S, request: &PathRequest) -> Resu
                 relation_filter: request.relation_filter.clone(),
                 min_weight: request.min_weight,
                 max_paths: 1,
+                strategy: request.strategy,
             };
 
-            let spur_result = if request.min_weight.is_some() {
-                find_weighted_shortest_path(storage, &spur_request)?
-            } else {
-                find_unweighted_shortest_path(storage, &spur_request)?
-            };
+            let spur_result = find_shortest_path(storage, &spur_request)?;
+            explored += spur_result.explored;
 
             if !spur_result.paths.is_empty() {
                 let spur_path = &spur_result.paths[0];
@@ -272,14 +456,23 @@ fn find_k_shortest_paths<S: Storage>(storage: &S, request: &PathRequest) -> Resu
             break;
         }
 
-        // Sort candidates by length/weight
-        candidates.sort_by(|a, b| {
-            a.length.cmp(&b.length).then(
-                b.total_weight
-                    .partial_cmp(&a.total_weight)
-                    .unwrap_or(Ordering::Equal),
-            )
-        });
+        // Sort candidates by length/weight, or by total cost when weighted
+        if is_weighted(request) {
+            let mut costed = candidates
+                .drain(..)
+                .map(|p| Ok((calculate_path_cost(storage, &p.edges)?, p)))
+                .collect::<Result<Vec<_>>>()?;
+            costed.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+            candidates.extend(costed.into_iter().map(|(_, p)| p));
+        } else {
+            candidates.sort_by(|a, b| {
+                a.length.cmp(&b.length).then(
+                    b.total_weight
+                        .partial_cmp(&a.total_weight)
+                        .unwrap_or(Ordering::Equal),
+                )
+            });
+        }
 
         // Take the best candidate
         if let Some(best) = candidates.first() {
@@ -290,10 +483,12 @@ fn find_k_shortest_paths<S: Storage>(storage: &S, request: &PathRequest) -> Resu
 
     Ok(PathResult {
         paths: result_paths,
+        explored,
     })
 }
 
 /// Reconstruct path from parent map
+#[cfg(test)]
 fn reconstruct_path<S: Storage>(
     start: NodeId,
     end: NodeId,
@@ -324,21 +519,17 @@ fn reconstruct_path<S: Storage>(
     Ok(Path::new(nodes, edges, total_weight))
 }
 
-/// Calculate depth of a node in BFS traversal
-fn calculate_depth(start: NodeId, node: NodeId, parent: &HashMap<NodeId, (NodeId, EdgeId)>) -> u32 {
-    let mut depth = 0;
-    let mut current = node;
+/// Calculate total Dijkstra cost of a path (sum of `edge_cost`)
+fn calculate_path_cost<S: Storage>(storage: &S, edge_ids: &[EdgeId]) -> Result<f32> {
+    let mut cost = 0.0;
 
-    while current != start {
-        if let Some(&(prev, _)) = parent.get(&current) {
-            depth += 1;
-            current = prev;
-        } else {
-            break;
+    for edge_id in edge_ids {
+        if let Some(edge) = storage.get_edge(*edge_id)? {
+            cost += edge_cost(edge.weight);
         }
     }
 
-    depth
+    Ok(cost)
 }
 
 /// Calculate total weight of a path (product of edge weights)
@@ -1,6 +1,7 @@
 use crate::graph::*;
-use crate::storage::{RedbStorage, Storage};
+use crate::storage::{NodeFilter, RedbStorage, Storage};
 use crate::types::*;
+use std::collections::HashSet;
 use std::sync::Arc;
 use tempfile::TempDir;
 use uuid::Uuid;
@@ -144,7 +145,7 @@ fn test_weighted_traversal() {
         })
         .unwrap();
 
-    assert!(result.nodes.len() > 0);
+    assert!(!result.nodes.is_empty());
 }
 
 #[test]
@@ -268,6 +269,231 @@ fn test_incoming_direction() {
     assert!(result.nodes.len() >= 3);
 }
 
+fn outgoing_from(engine: &GraphEngineImpl<RedbStorage>, start: NodeId) -> HashSet<NodeId> {
+    engine
+        .traverse(TraversalRequest {
+            start: vec![start],
+            max_depth: Some(1),
+            direction: TraversalDirection::Outgoing,
+            include_start: false,
+            ..Default::default()
+        })
+        .unwrap()
+        .nodes
+        .into_keys()
+        .collect()
+}
+
+#[test]
+fn test_symmetric_edge_traversable_both_ways() {
+    let (storage, _temp) = create_test_storage();
+    let a = create_test_node(NodeKind::new("fact").unwrap(), "Fact A");
+    let b = create_test_node(NodeKind::new("fact").unwrap(), "Fact B");
+    let c = create_test_node(NodeKind::new("fact").unwrap(), "Fact C");
+    storage
+        .put_nodes_batch(&[a.clone(), b.clone(), c.clone()])
+        .unwrap();
+    let engine = GraphEngineImpl::new(storage.clone());
+
+    // Plain put_edge stores one direction only
+    let one_way = create_test_edge(a.id, c.id, Relation::new("similar_to").unwrap(), 0.8);
+    storage.put_edge(&one_way).unwrap();
+    assert!(outgoing_from(&engine, c.id).is_empty());
+
+    let edge = create_test_edge(a.id, b.id, Relation::new("similar_to").unwrap(), 0.8);
+    let inverse = storage.put_edge_with_inverse(&edge).unwrap().unwrap();
+    assert_eq!((inverse.from, inverse.to), (b.id, a.id));
+    assert_eq!(inverse.relation.as_str(), "similar_to");
+    assert_eq!(inverse.weight, edge.weight);
+    assert_eq!(
+        inverse.provenance,
+        EdgeProvenance::DerivedInverse { of: edge.id }
+    );
+
+    assert!(outgoing_from(&engine, a.id).contains(&b.id));
+    assert_eq!(outgoing_from(&engine, b.id), HashSet::from([a.id]));
+
+    // The caller already linked d -> a: the inverse is not duplicated
+    let d = create_test_node(NodeKind::new("fact").unwrap(), "Fact D");
+    storage.put_node(&d).unwrap();
+    let back = create_test_edge(d.id, a.id, Relation::new("similar_to").unwrap(), 0.8);
+    storage.put_edge(&back).unwrap();
+    let forth = create_test_edge(a.id, d.id, Relation::new("similar_to").unwrap(), 0.8);
+    assert!(storage.put_edge_with_inverse(&forth).unwrap().is_none());
+    assert_eq!(storage.edges_from(d.id).unwrap(), [back]);
+    assert!(storage.get_edge(forth.id).unwrap().is_some());
+}
+
+#[test]
+fn test_asymmetric_edge_creates_named_inverse() {
+    let (storage, _temp) = create_test_storage();
+    let new = create_test_node(NodeKind::new("decision").unwrap(), "Use Postgres");
+    let old = create_test_node(NodeKind::new("decision").unwrap(), "Use MySQL");
+    storage
+        .put_nodes_batch(&[new.clone(), old.clone()])
+        .unwrap();
+
+    let edge = create_test_edge(new.id, old.id, Relation::new("supersedes").unwrap(), 1.0);
+    let inverse = storage.put_edge_with_inverse(&edge).unwrap().unwrap();
+    assert_eq!((inverse.from, inverse.to), (old.id, new.id));
+    assert_eq!(inverse.relation.as_str(), "superseded_by");
+
+    let from_old = storage.edges_from(old.id).unwrap();
+    assert_eq!(from_old.len(), 1);
+    assert_eq!(from_old[0].relation.as_str(), "superseded_by");
+    assert_eq!(
+        outgoing_from(&GraphEngineImpl::new(storage.clone()), old.id),
+        HashSet::from([new.id])
+    );
+
+    // Lookups work from the inverse side too
+    let rel = Relation::new("superseded_by").unwrap();
+    assert_eq!(rel.inverse().unwrap().as_str(), "supersedes");
+    assert!(!rel.is_symmetric());
+
+    // No inverse known: only the edge itself is written
+    let led_to = create_test_edge(old.id, new.id, Relation::new("led_to").unwrap(), 0.5);
+    assert!(storage.put_edge_with_inverse(&led_to).unwrap().is_none());
+    let from_new = storage.edges_from(new.id).unwrap();
+    assert_eq!(from_new.len(), 1);
+    assert_eq!(from_new[0].relation.as_str(), "supersedes");
+}
+
+#[test]
+fn test_deleting_either_half_of_an_inverse_pair_deletes_both() {
+    let (storage, _temp) = create_test_storage();
+    let a = create_test_node(NodeKind::new("fact").unwrap(), "A");
+    let b = create_test_node(NodeKind::new("fact").unwrap(), "B");
+    storage.put_nodes_batch(&[a.clone(), b.clone()]).unwrap();
+
+    let edge = create_test_edge(a.id, b.id, Relation::new("supersedes").unwrap(), 1.0);
+    let inverse = storage.put_edge_with_inverse(&edge).unwrap().unwrap();
+    assert_eq!(storage.paired_edge(&edge).unwrap().unwrap().id, inverse.id);
+    assert_eq!(storage.paired_edge(&inverse).unwrap().unwrap().id, edge.id);
+    storage.delete_edge(edge.id).unwrap();
+    assert!(storage.get_edge(inverse.id).unwrap().is_none());
+    assert_eq!(storage.stats().unwrap().edge_count, 0);
+
+    let edge = create_test_edge(a.id, b.id, Relation::new("similar_to").unwrap(), 0.8);
+    let inverse = storage.put_edge_with_inverse(&edge).unwrap().unwrap();
+    storage.delete_edge(inverse.id).unwrap();
+    assert!(storage.get_edge(edge.id).unwrap().is_none());
+
+    // A manual edge in the reverse direction is not a pair
+    let forward = create_test_edge(a.id, b.id, Relation::new("related_to").unwrap(), 0.5);
+    let reverse = create_test_edge(b.id, a.id, Relation::new("related_to").unwrap(), 0.5);
+    storage
+        .put_edges_batch(&[forward.clone(), reverse.clone()])
+        .unwrap();
+    storage.delete_edge(forward.id).unwrap();
+    assert!(storage.get_edge(reverse.id).unwrap().is_some());
+}
+
+/// A root with five children, each with five children of its own.
+fn build_wide_tree(storage: &RedbStorage) -> Node {
+    let root = create_test_node(NodeKind::new("fact").unwrap(), "Root");
+    storage.put_node(&root).unwrap();
+    for i in 0..5 {
+        let child = create_test_node(NodeKind::new("fact").unwrap(), &format!("Child {i}"));
+        storage.put_node(&child).unwrap();
+        storage
+            .put_edge(&create_test_edge(
+                root.id,
+                child.id,
+                Relation::new("related_to").unwrap(),
+                1.0,
+            ))
+            .unwrap();
+        for j in 0..5 {
+            let leaf = create_test_node(NodeKind::new("fact").unwrap(), &format!("Leaf {i}.{j}"));
+            storage.put_node(&leaf).unwrap();
+            storage
+                .put_edge(&create_test_edge(
+                    child.id,
+                    leaf.id,
+                    Relation::new("related_to").unwrap(),
+                    1.0,
+                ))
+                .unwrap();
+        }
+    }
+    root
+}
+
+#[test]
+fn test_traverse_stream_matches_traverse() {
+    let (storage, _temp) = create_test_storage();
+    let root = build_wide_tree(&storage);
+    let engine = GraphEngineImpl::new(storage.clone());
+    let request = TraversalRequest {
+        start: vec![root.id],
+        max_depth: Some(2),
+        ..Default::default()
+    };
+
+    let levels: Vec<TraversalLevel> = engine
+        .traverse_stream(request.clone())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        levels.iter().map(|l| l.nodes.len()).collect::<Vec<_>>(),
+        [1, 5, 25]
+    );
+    assert!(levels.iter().all(|l| !l.truncated));
+
+    let whole = engine.traverse(request).unwrap();
+    let streamed: HashSet<NodeId> = levels
+        .iter()
+        .flat_map(|l| l.nodes.iter().map(|n| n.id))
+        .collect();
+    assert_eq!(streamed, whole.nodes.keys().copied().collect());
+    let streamed_edges: Vec<EdgeId> = levels
+        .iter()
+        .flat_map(|l| l.edges.iter().map(|e| e.id))
+        .collect();
+    assert_eq!(streamed_edges.len(), 30);
+    assert_eq!(
+        streamed_edges.iter().copied().collect::<HashSet<_>>(),
+        whole.edges.iter().map(|e| e.id).collect()
+    );
+    for level in &levels {
+        for edge in &level.edges {
+            assert_eq!(whole.depths[&edge.to], level.depth);
+        }
+    }
+}
+
+#[test]
+fn test_traverse_stream_stops_at_budget() {
+    let (storage, _temp) = create_test_storage();
+    let root = build_wide_tree(&storage);
+    let budget = TraversalBudget {
+        max_visited: 10,
+        ..Default::default()
+    };
+    let engine = GraphEngineImpl::with_budget(storage.clone(), budget);
+
+    let mut stream = engine.traverse_stream(TraversalRequest {
+        start: vec![root.id],
+        max_depth: None,
+        ..Default::default()
+    });
+    let mut levels = Vec::new();
+    for level in stream.by_ref() {
+        levels.push(level.unwrap());
+    }
+    assert!(stream.next().is_none());
+
+    let depths: Vec<u32> = levels.iter().map(|l| l.depth).collect();
+    assert!(depths.windows(2).all(|w| w[0] <= w[1]), "{depths:?}");
+    let last = levels.last().unwrap();
+    assert!(last.truncated);
+    assert_eq!(last.visited_count, 10);
+    assert!(levels[..levels.len() - 1].iter().all(|l| !l.truncated));
+    let total: usize = levels.iter().map(|l| l.nodes.len()).sum();
+    assert_eq!(total, 10);
+}
+
 #[test]
 fn test_shortest_path() {
     let (storage, _temp) = create_test_storage();
@@ -295,6 +521,66 @@ fn test_shortest_path() {
     assert_eq!(path.length, 2);
 }
 
+#[test]
+fn test_bidirectional_path_matches_one_sided_bfs() {
+    let (storage, _temp) = create_test_storage();
+    let rel = || Relation::new("led_to").unwrap();
+
+    // A 30-node chain where every link also fans out to three dead ends
+    let chain: Vec<Node> = (0..30)
+        .map(|i| create_test_node(NodeKind::new("event").unwrap(), &format!("Step {i}")))
+        .collect();
+    for node in &chain {
+        storage.put_node(node).unwrap();
+    }
+    for pair in chain.windows(2) {
+        storage
+            .put_edge(&create_test_edge(pair[0].id, pair[1].id, rel(), 1.0))
+            .unwrap();
+        for j in 0..3 {
+            let leaf = create_test_node(NodeKind::new("fact").unwrap(), &format!("Leaf {j}"));
+            storage.put_node(&leaf).unwrap();
+            storage
+                .put_edge(&create_test_edge(pair[0].id, leaf.id, rel(), 1.0))
+                .unwrap();
+        }
+    }
+
+    let request = PathRequest {
+        from: chain[0].id,
+        to: chain[29].id,
+        ..Default::default()
+    };
+    let engine = GraphEngineImpl::new(storage.clone());
+    let both = engine.find_paths(request.clone()).unwrap();
+    let one =
+        super::paths::find_unweighted_shortest_path_one_sided(storage.as_ref(), &request).unwrap();
+
+    assert_eq!(both.paths.len(), 1);
+    assert_eq!(both.paths[0].nodes, one.paths[0].nodes);
+    assert_eq!(both.paths[0].edges, one.paths[0].edges);
+    assert_eq!(both.paths[0].length, 29);
+    assert!(
+        both.explored * 2 < one.explored,
+        "bidirectional explored {}, one-sided {}",
+        both.explored,
+        one.explored
+    );
+
+    // A length cap below the distance finds nothing either way
+    let capped = PathRequest {
+        max_length: Some(28),
+        ..request
+    };
+    assert!(engine.find_paths(capped.clone()).unwrap().paths.is_empty());
+    assert!(
+        super::paths::find_unweighted_shortest_path_one_sided(storage.as_ref(), &capped)
+            .unwrap()
+            .paths
+            .is_empty()
+    );
+}
+
 #[test]
 fn test_no_path_exists() {
     let (storage, _temp) = create_test_storage();
@@ -319,6 +605,76 @@ fn test_no_path_exists() {
     assert_eq!(result.paths.len(), 0);
 }
 
+/// A -> W -> Z over two weak edges, and A -> S1 -> S2 -> Z over three strong ones.
+fn build_divergent_path_graph(storage: &RedbStorage) -> (Node, Node, Node, Node, Node) {
+    let a = create_test_node(NodeKind::new("fact").unwrap(), "A");
+    let w = create_test_node(NodeKind::new("fact").unwrap(), "Weak W");
+    let s1 = create_test_node(NodeKind::new("fact").unwrap(), "Strong S1");
+    let s2 = create_test_node(NodeKind::new("fact").unwrap(), "Strong S2");
+    let z = create_test_node(NodeKind::new("fact").unwrap(), "Z");
+    for node in [&a, &w, &s1, &s2, &z] {
+        storage.put_node(node).unwrap();
+    }
+
+    let rel = || Relation::new("related_to").unwrap();
+    for (from, to, weight) in [
+        (a.id, w.id, 0.2),
+        (w.id, z.id, 0.2),
+        (a.id, s1.id, 0.9),
+        (s1.id, s2.id, 0.9),
+        (s2.id, z.id, 0.9),
+    ] {
+        storage
+            .put_edge(&create_test_edge(from, to, rel(), weight))
+            .unwrap();
+    }
+
+    (a, w, s1, s2, z)
+}
+
+#[test]
+fn test_path_strategies_diverge_on_weights() {
+    let (storage, _temp) = create_test_storage();
+    let (a, w, s1, s2, z) = build_divergent_path_graph(&storage);
+    let engine = GraphEngineImpl::new(storage.clone());
+
+    let hops = engine
+        .find_paths(PathRequest {
+            from: a.id,
+            to: z.id,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(hops.paths.len(), 1);
+    assert_eq!(hops.paths[0].nodes, vec![a.id, w.id, z.id]);
+    assert_eq!(hops.paths[0].length, 2);
+
+    let weighted = engine
+        .find_paths(PathRequest {
+            from: a.id,
+            to: z.id,
+            strategy: PathStrategy::Weighted,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(weighted.paths.len(), 1);
+    let path = &weighted.paths[0];
+    assert_eq!(path.nodes, vec![a.id, s1.id, s2.id, z.id]);
+    assert_eq!(path.length, 3);
+    assert!(path.total_weight > hops.paths[0].total_weight);
+
+    // A weight floor asks for strong paths, so it implies Dijkstra
+    let floored = engine
+        .find_paths(PathRequest {
+            from: a.id,
+            to: z.id,
+            min_weight: Some(0.1),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(floored.paths[0].nodes, vec![a.id, s1.id, s2.id, z.id]);
+}
+
 #[test]
 fn test_neighbors() {
     let (storage, _temp) = create_test_storage();
@@ -440,9 +796,118 @@ fn test_find_cycles() {
 
     let engine = GraphEngineImpl::new(storage.clone());
 
-    let cycles = engine.find_cycles().unwrap();
+    let cycles = engine.find_cycles(None).unwrap();
+    assert_eq!(cycles.len(), 1);
+    let members: HashSet<NodeId> = cycles[0].iter().copied().collect();
+    assert_eq!(members, HashSet::from([a.id, b.id, c.id]));
+
+    // None of the edges are `supersedes`
+    let supersedes = Relation::new("supersedes").unwrap();
+    assert!(engine.find_cycles(Some(supersedes)).unwrap().is_empty());
+}
+
+#[test]
+fn test_find_cycles_in_supersedes_chain() {
+    let (storage, _temp) = create_test_storage();
+    let supersedes = Relation::new("supersedes").unwrap();
+
+    // v3 supersedes v2 supersedes v1, and v1 was wrongly marked as
+    // superseding v3. A separate acyclic chain hangs off v1.
+    let v1 = create_test_node(NodeKind::new("decision").unwrap(), "v1");
+    let v2 = create_test_node(NodeKind::new("decision").unwrap(), "v2");
+    let v3 = create_test_node(NodeKind::new("decision").unwrap(), "v3");
+    let other = create_test_node(NodeKind::new("decision").unwrap(), "other");
+    for n in [&v1, &v2, &v3, &other] {
+        storage.put_node(n).unwrap();
+    }
+    for (from, to) in [
+        (v3.id, v2.id),
+        (v2.id, v1.id),
+        (v1.id, v3.id),
+        (other.id, v1.id),
+    ] {
+        storage
+            .put_edge(&create_test_edge(from, to, supersedes.clone(), 1.0))
+            .unwrap();
+    }
+    // A `related_to` back-edge doesn't form a `supersedes` cycle
+    storage
+        .put_edge(&create_test_edge(
+            v1.id,
+            other.id,
+            Relation::new("related_to").unwrap(),
+            1.0,
+        ))
+        .unwrap();
+
+    let engine = GraphEngineImpl::new(storage.clone());
+    let cycles = engine.find_cycles(Some(supersedes.clone())).unwrap();
+    assert_eq!(cycles.len(), 1);
+    let members: HashSet<NodeId> = cycles[0].iter().copied().collect();
+    assert_eq!(members, HashSet::from([v1.id, v2.id, v3.id]));
+
+    // Closing the chain again would be caught before the write
+    let back = create_test_edge(v1.id, v2.id, supersedes.clone(), 1.0);
+    assert!(would_create_cycle(storage.as_ref(), &back).unwrap());
+    let fresh = create_test_node(NodeKind::new("decision").unwrap(), "v4");
+    storage.put_node(&fresh).unwrap();
+    let forward = create_test_edge(fresh.id, v3.id, supersedes, 1.0);
+    assert!(!would_create_cycle(storage.as_ref(), &forward).unwrap());
+}
+
+#[test]
+fn test_subgraph_by_filter_keeps_only_inner_edges() {
+    let (storage, _temp) = create_test_storage();
+    let infra = NodeKind::new("infra").unwrap();
+    let lb = create_test_node(infra.clone(), "Load balancer");
+    let db = create_test_node(infra.clone(), "Database");
+    let cache = create_test_node(infra.clone(), "Cache");
+    let decision = create_test_node(NodeKind::new("decision").unwrap(), "Use Postgres");
+    for n in [&lb, &db, &cache, &decision] {
+        storage.put_node(n).unwrap();
+    }
+    let depends_on = Relation::new("depends_on").unwrap();
+    let inner = create_test_edge(lb.id, db.id, depends_on.clone(), 1.0);
+    let inner_too = create_test_edge(cache.id, db.id, depends_on, 0.5);
+    // One endpoint outside the filter, in each direction
+    let inward = create_test_edge(
+        decision.id,
+        db.id,
+        Relation::new("applies_to").unwrap(),
+        1.0,
+    );
+    let outward = create_test_edge(
+        db.id,
+        decision.id,
+        Relation::new("informed_by").unwrap(),
+        1.0,
+    );
+    for e in [&inner, &inner_too, &inward, &outward] {
+        storage.put_edge(e).unwrap();
+    }
 
-    assert!(cycles.len() > 0); // Should detect the cycle
+    let engine = GraphEngineImpl::new(storage.clone());
+    let subgraph = engine
+        .subgraph_by_filter(NodeFilter::new().with_kinds(vec![infra]))
+        .unwrap();
+
+    let ids: HashSet<NodeId> = subgraph.nodes.keys().copied().collect();
+    assert_eq!(ids, HashSet::from([lb.id, db.id, cache.id]));
+    let edges: HashSet<EdgeId> = subgraph.edges.iter().map(|e| e.id).collect();
+    assert_eq!(edges, HashSet::from([inner.id, inner_too.id]));
+    assert!(subgraph.depths.values().all(|&d| d == 0));
+    assert!(!subgraph.truncated);
+
+    // Exactly `limit` matches is not a truncation; one more is
+    let filter = NodeFilter::new().with_kinds(vec![NodeKind::new("infra").unwrap()]);
+    let all = engine
+        .subgraph_by_filter(filter.clone().with_limit(3))
+        .unwrap();
+    assert_eq!(all.nodes.len(), 3);
+    assert!(!all.truncated);
+    let cut = engine.subgraph_by_filter(filter.with_limit(2)).unwrap();
+    assert_eq!(cut.nodes.len(), 2);
+    assert!(cut.truncated);
 }
 
 #[test]
@@ -825,3 +1290,309 @@ fn test_connected_components_isolated_nodes() {
     let components = engine.components().unwrap();
     assert_eq!(components.len(), 3);
 }
+
+#[test]
+fn test_pagerank_ranks_hub_highest() {
+    let (storage, _temp) = create_test_storage();
+    let hub = create_test_node(NodeKind::new("fact").unwrap(), "Hub");
+    storage.put_node(&hub).unwrap();
+    let spokes: Vec<Node> = (0..4)
+        .map(|i| {
+            let n = create_test_node(NodeKind::new("fact").unwrap(), &format!("Spoke {}", i));
+            storage.put_node(&n).unwrap();
+            storage
+                .put_edge(&create_test_edge(
+                    n.id,
+                    hub.id,
+                    Relation::new("related_to").unwrap(),
+                    1.0,
+                ))
+                .unwrap();
+            n
+        })
+        .collect();
+
+    let engine = GraphEngineImpl::new(storage.clone());
+    let scores = engine.pagerank(PageRankConfig::default()).unwrap();
+
+    assert_eq!(scores.len(), 5);
+    let total: f32 = scores.values().sum();
+    assert!((total - 1.0).abs() < 1e-4, "ranks sum to {}", total);
+    for spoke in &spokes {
+        assert!(scores[&hub.id] > scores[&spoke.id]);
+    }
+}
+
+#[test]
+fn test_pagerank_weighted_follows_strong_edges() {
+    let (storage, _temp) = create_test_storage();
+    let src = create_test_node(NodeKind::new("fact").unwrap(), "Source");
+    let strong = create_test_node(NodeKind::new("fact").unwrap(), "Strong");
+    let weak = create_test_node(NodeKind::new("fact").unwrap(), "Weak");
+    for n in [&src, &strong, &weak] {
+        storage.put_node(n).unwrap();
+    }
+    let rel = Relation::new("related_to").unwrap();
+    storage
+        .put_edge(&create_test_edge(src.id, strong.id, rel.clone(), 0.9))
+        .unwrap();
+    storage
+        .put_edge(&create_test_edge(src.id, weak.id, rel, 0.1))
+        .unwrap();
+
+    let engine = GraphEngineImpl::new(storage.clone());
+    let weighted = engine.pagerank(PageRankConfig::default()).unwrap();
+    assert!(weighted[&strong.id] > weighted[&weak.id]);
+
+    let unweighted = engine
+        .pagerank(PageRankConfig {
+            weighted: false,
+            ..Default::default()
+        })
+        .unwrap();
+    assert!((unweighted[&strong.id] - unweighted[&weak.id]).abs() < 1e-6);
+}
+
+#[test]
+fn test_pagerank_cached_until_graph_version_changes() {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    let (storage, _temp) = create_test_storage();
+    let a = create_test_node(NodeKind::new("fact").unwrap(), "A");
+    storage.put_node(&a).unwrap();
+
+    let version = Arc::new(AtomicU64::new(0));
+    let engine = GraphEngineImpl::new(storage.clone()).with_graph_version(version.clone());
+    assert_eq!(engine.pagerank(PageRankConfig::default()).unwrap().len(), 1);
+
+    let b = create_test_node(NodeKind::new("fact").unwrap(), "B");
+    storage.put_node(&b).unwrap();
+    // Version unchanged: cached result
+    assert_eq!(engine.pagerank(PageRankConfig::default()).unwrap().len(), 1);
+
+    version.fetch_add(1, Ordering::Relaxed);
+    assert_eq!(engine.pagerank(PageRankConfig::default()).unwrap().len(), 2);
+}
+
+#[test]
+fn test_detect_communities_separates_cliques() {
+    let (storage, _temp) = create_test_storage();
+    let rel = || Relation::new("related_to").unwrap();
+
+    let clique = |prefix: &str| -> Vec<Node> {
+        let nodes: Vec<Node> = (0..4)
+            .map(|i| create_test_node(NodeKind::new("fact").unwrap(), &format!("{prefix}{i}")))
+            .collect();
+        for n in &nodes {
+            storage.put_node(n).unwrap();
+        }
+        for i in 0..nodes.len() {
+            for j in (i + 1)..nodes.len() {
+                storage
+                    .put_edge(&create_test_edge(nodes[i].id, nodes[j].id, rel(), 0.9))
+                    .unwrap();
+            }
+        }
+        nodes
+    };
+    let left = clique("L");
+    let right = clique("R");
+    // One weak bridge between the two clusters
+    storage
+        .put_edge(&create_test_edge(left[0].id, right[0].id, rel(), 0.1))
+        .unwrap();
+    let loner = create_test_node(NodeKind::new("fact").unwrap(), "Loner");
+    storage.put_node(&loner).unwrap();
+
+    let engine = GraphEngineImpl::new(storage.clone());
+    let communities = engine
+        .detect_communities(CommunityConfig::default())
+        .unwrap();
+
+    assert_eq!(communities.len(), 3);
+    let as_set = |c: &Community| c.nodes.iter().copied().collect::<HashSet<_>>();
+    let left_ids: HashSet<_> = left.iter().map(|n| n.id).collect();
+    let right_ids: HashSet<_> = right.iter().map(|n| n.id).collect();
+    assert!(communities.iter().any(|c| as_set(c) == left_ids));
+    assert!(communities.iter().any(|c| as_set(c) == right_ids));
+    assert_eq!(communities[2].nodes, vec![loner.id]);
+
+    let modularity: f32 = communities.iter().map(|c| c.modularity).sum();
+    assert!(modularity > 0.3, "modularity {}", modularity);
+
+    let without_singletons = engine
+        .detect_communities(CommunityConfig {
+            min_size: 2,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(without_singletons.len(), 2);
+}
+
+#[test]
+fn test_traverse_as_of_shrinks_with_earlier_timestamps() {
+    use chrono::{Duration, Utc};
+
+    let (storage, _temp) = create_test_storage();
+    let now = Utc::now();
+    let day = |n: i64| now - Duration::days(10 - n);
+
+    // A (day 1) -> B (day 2) -> C (day 3); A -> D (day 2, deleted day 4)
+    let mut nodes = Vec::new();
+    for (title, created) in [("A", day(1)), ("B", day(2)), ("C", day(3)), ("D", day(2))] {
+        let mut node = create_test_node(NodeKind::new("fact").unwrap(), title);
+        node.created_at = created;
+        node.updated_at = created;
+        nodes.push(node);
+    }
+    for node in &nodes {
+        storage.put_node(node).unwrap();
+    }
+    for (from, to, created) in [(0, 1, day(2)), (1, 2, day(3)), (0, 3, day(2))] {
+        let mut edge = create_test_edge(
+            nodes[from].id,
+            nodes[to].id,
+            Relation::new("related_to").unwrap(),
+            1.0,
+        );
+        edge.created_at = created;
+        storage.put_edge(&edge).unwrap();
+    }
+    nodes[3].deleted = true;
+    nodes[3].updated_at = day(4);
+    storage.put_node(&nodes[3]).unwrap();
+
+    let engine = GraphEngineImpl::new(storage.clone());
+    let visible = |as_of| -> HashSet<String> {
+        engine
+            .traverse_as_of(nodes[0].id, 3, as_of)
+            .unwrap()
+            .nodes
+            .values()
+            .map(|n| n.data.title.clone())
+            .collect()
+    };
+    let titles = |t: &[&str]| -> HashSet<String> { t.iter().map(|s| s.to_string()).collect() };
+
+    assert_eq!(visible(day(5)), titles(&["A", "B", "C"]));
+    assert_eq!(visible(day(3)), titles(&["A", "B", "C", "D"]));
+    assert_eq!(visible(day(2)), titles(&["A", "B", "D"]));
+    assert_eq!(visible(day(1)), titles(&["A"]));
+    assert!(visible(day(0)).is_empty());
+
+    // Edges follow the same cut-off
+    let subgraph = engine.traverse_as_of(nodes[0].id, 3, day(2)).unwrap();
+    let edge_ids: HashSet<_> = subgraph.edges.iter().map(|e| e.id).collect();
+    assert_eq!(edge_ids.len(), 2);
+
+    // Direction and relation controls still apply via TraversalRequest
+    let subgraph = engine
+        .traverse(TraversalRequest {
+            start: vec![nodes[1].id],
+            direction: TraversalDirection::Incoming,
+            as_of: Some(day(3)),
+            ..TraversalRequest::default()
+        })
+        .unwrap();
+    let incoming: HashSet<_> = subgraph.nodes.keys().copied().collect();
+    assert_eq!(incoming, HashSet::from([nodes[0].id, nodes[1].id]));
+}
+
+/// Fixed-ID subgraph with awkward characters for export golden files.
+fn export_sample_subgraph() -> Subgraph {
+    let mut decision = create_test_node(
+        NodeKind::new("decision").unwrap(),
+        "Use <redb> & \"embedded\" storage",
+    );
+    decision.id = Uuid::from_u128(1);
+    decision.importance = 0.9;
+    let mut fact = create_test_node(NodeKind::new("fact").unwrap(), "redb is ACID");
+    fact.id = Uuid::from_u128(2);
+    let mut goal = create_test_node(NodeKind::new("goal").unwrap(), "Zero-ops\nstorage");
+    goal.id = Uuid::from_u128(3);
+    goal.importance = 0.75;
+
+    let mut informed = create_test_edge(
+        decision.id,
+        fact.id,
+        Relation::new("informed_by").unwrap(),
+        0.8,
+    );
+    informed.id = Uuid::from_u128(10);
+    let mut serves = create_test_edge(decision.id, goal.id, Relation::new("led_to").unwrap(), 1.0);
+    serves.id = Uuid::from_u128(11);
+
+    let mut subgraph = Subgraph::new();
+    for node in [goal, fact, decision] {
+        subgraph.nodes.insert(node.id, node);
+    }
+    // Bidirectional traversals can report an edge twice; exports dedupe.
+    subgraph.edges = vec![serves.clone(), informed, serves];
+    subgraph
+}
+
+/// Minimal well-formedness check: every tag closes in order, and no stray `&` or `<`.
+fn assert_well_formed_xml(xml: &str) {
+    let body = xml
+        .strip_prefix("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n")
+        .expect("XML declaration");
+    let mut stack = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find('<') {
+        let text = &rest[..start];
+        for (i, _) in text.match_indices('&') {
+            let entity = &text[i..text[i..].find(';').map_or(text.len(), |j| i + j + 1)];
+            assert!(
+                ["&amp;", "&lt;", "&gt;", "&quot;", "&apos;"].contains(&entity),
+                "bad entity {entity:?}"
+            );
+        }
+        let end = start + rest[start..].find('>').expect("unterminated tag");
+        let tag = &rest[start + 1..end];
+        if let Some(name) = tag.strip_prefix('/') {
+            assert_eq!(stack.pop(), Some(name.to_string()), "mismatched close tag");
+        } else if !tag.ends_with('/') {
+            let name = tag.split_whitespace().next().unwrap();
+            stack.push(name.to_string());
+        }
+        assert_eq!(
+            tag.matches('"').count() % 2,
+            0,
+            "unbalanced quotes in {tag}"
+        );
+        rest = &rest[end + 1..];
+    }
+    assert!(stack.is_empty(), "unclosed tags: {stack:?}");
+    assert!(rest.trim().is_empty());
+}
+
+/// Minimal DOT syntax check: one digraph block, every statement terminated,
+/// quotes balanced once escapes are removed.
+fn assert_valid_dot(dot: &str) {
+    let lines: Vec<&str> = dot.lines().collect();
+    assert!(lines[0].starts_with("digraph ") && lines[0].ends_with('{'));
+    assert_eq!(*lines.last().unwrap(), "}");
+    for line in &lines[1..lines.len() - 1] {
+        let unescaped = line.replace("\\\\", "").replace("\\\"", "");
+        assert_eq!(unescaped.matches('"').count() % 2, 0, "unbalanced: {line}");
+        assert!(line.ends_with(';'), "unterminated statement: {line}");
+        assert_eq!(
+            unescaped.matches('[').count(),
+            unescaped.matches(']').count()
+        );
+    }
+}
+
+#[test]
+fn test_export_dot_matches_golden() {
+    let dot = export::to_dot(&export_sample_subgraph());
+    assert_valid_dot(&dot);
+    assert_eq!(dot, include_str!("testdata/sample.dot"));
+}
+
+#[test]
+fn test_export_graphml_matches_golden() {
+    let graphml = export::to_graphml(&export_sample_subgraph());
+    assert_well_formed_xml(&graphml);
+    assert_eq!(graphml, include_str!("testdata/sample.graphml"));
+}
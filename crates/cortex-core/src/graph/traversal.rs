@@ -1,11 +1,14 @@
 use crate::error::Result;
 use crate::graph::{
-    Subgraph, TraversalBudget, TraversalDirection, TraversalRequest, TraversalStrategy,
+    Subgraph, TraversalBudget, TraversalDirection, TraversalLevel, TraversalRequest,
+    TraversalStrategy,
 };
 use crate::storage::Storage;
-use crate::types::{Edge, NodeId};
+use crate::types::{Edge, EdgeId, Node, NodeId};
+use chrono::{DateTime, Utc};
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashSet, VecDeque};
+use std::sync::Arc;
 use std::time::Instant;
 
 /// Weighted node for priority queue traversal
@@ -39,6 +42,18 @@ impl Ord for WeightedNode {
     }
 }
 
+/// Whether a node existed at `as_of`: created by then and not yet tombstoned.
+/// The deletion time is `deleted_at`, or `updated_at` for older tombstones.
+fn node_visible_at(node: &Node, as_of: DateTime<Utc>) -> bool {
+    node.created_at <= as_of
+        && !(node.deleted && node.deleted_at.unwrap_or(node.updated_at) <= as_of)
+}
+
+/// Whether an edge existed at `as_of`.
+fn edge_visible_at(edge: &Edge, as_of: DateTime<Utc>) -> bool {
+    edge.created_at <= as_of
+}
+
 /// Perform graph traversal according to the request
 pub fn traverse<S: Storage>(
     storage: &S,
@@ -95,6 +110,19 @@ fn traverse_bfs<S: Storage>(
                 continue;
             }
         }
+        if let Some(as_of) = request.as_of {
+            if !node_visible_at(&current_node, as_of) {
+                continue;
+            }
+        }
+        if !request.include_expired
+            && current_node.is_expired_at(request.as_of.unwrap_or_else(Utc::now))
+        {
+            continue;
+        }
+        if current_node.deleted && !request.include_deleted && request.as_of.is_none() {
+            continue;
+        }
 
         // Add node if it matches kind filter and we're including it
         let should_include = if depth == 0 && !request.include_start {
@@ -158,6 +186,11 @@ fn traverse_bfs<S: Storage>(
                     continue;
                 }
             }
+            if let Some(as_of) = request.as_of {
+                if !edge_visible_at(&edge, as_of) {
+                    continue;
+                }
+            }
 
             // Determine next node
             let next_id = if edge.from == current_id {
@@ -229,6 +262,19 @@ fn traverse_dfs<S: Storage>(
                 continue;
             }
         }
+        if let Some(as_of) = request.as_of {
+            if !node_visible_at(&current_node, as_of) {
+                continue;
+            }
+        }
+        if !request.include_expired
+            && current_node.is_expired_at(request.as_of.unwrap_or_else(Utc::now))
+        {
+            continue;
+        }
+        if current_node.deleted && !request.include_deleted && request.as_of.is_none() {
+            continue;
+        }
 
         // Add node if it matches kind filter
         let should_include = if depth == 0 && !request.include_start {
@@ -284,6 +330,11 @@ fn traverse_dfs<S: Storage>(
                     continue;
                 }
             }
+            if let Some(as_of) = request.as_of {
+                if !edge_visible_at(&edge, as_of) {
+                    continue;
+                }
+            }
 
             let next_id = if edge.from == current_id {
                 edge.to
@@ -366,6 +417,19 @@ fn traverse_weighted<S: Storage>(
                 continue;
             }
         }
+        if let Some(as_of) = request.as_of {
+            if !node_visible_at(&current_node, as_of) {
+                continue;
+            }
+        }
+        if !request.include_expired
+            && current_node.is_expired_at(request.as_of.unwrap_or_else(Utc::now))
+        {
+            continue;
+        }
+        if current_node.deleted && !request.include_deleted && request.as_of.is_none() {
+            continue;
+        }
 
         // Add node if it matches kind filter
         let should_include = if depth == 0 && !request.include_start {
@@ -420,6 +484,11 @@ fn traverse_weighted<S: Storage>(
                     continue;
                 }
             }
+            if let Some(as_of) = request.as_of {
+                if !edge_visible_at(&edge, as_of) {
+                    continue;
+                }
+            }
 
             let next_id = if edge.from == current_id {
                 edge.to
@@ -449,6 +518,200 @@ fn traverse_weighted<S: Storage>(
     Ok(result)
 }
 
+/// Breadth-first traversal that yields one [`TraversalLevel`] at a time, so
+/// a caller can consume a large subgraph incrementally and stop early.
+///
+/// Visits nodes in the same order as a `Bfs` [`traverse`] and applies the
+/// same filters, limit and budget. The strategy in the request is ignored.
+/// Once the budget or limit is hit, the level in progress is yielded with
+/// `truncated` set and the stream ends. An error ends it too.
+pub struct TraversalStream<S> {
+    storage: Arc<S>,
+    request: TraversalRequest,
+    budget: TraversalBudget,
+    start_time: Instant,
+    depth: u32,
+    /// Nodes to process at `depth`
+    level: Vec<NodeId>,
+    /// Every node queued so far
+    queued: HashSet<NodeId>,
+    /// Nodes yielded so far
+    included: HashSet<NodeId>,
+    /// Followed edges waiting for their second endpoint
+    pending_edges: Vec<Edge>,
+    emitted_edges: HashSet<EdgeId>,
+    visited_count: usize,
+    done: bool,
+}
+
+impl<S: Storage> TraversalStream<S> {
+    pub fn new(storage: Arc<S>, request: TraversalRequest, budget: TraversalBudget) -> Self {
+        let mut queued = HashSet::new();
+        let level = request
+            .start
+            .iter()
+            .copied()
+            .filter(|id| queued.insert(*id))
+            .collect();
+        Self {
+            storage,
+            request,
+            budget,
+            start_time: Instant::now(),
+            depth: 0,
+            level,
+            queued,
+            included: HashSet::new(),
+            pending_edges: Vec::new(),
+            emitted_edges: HashSet::new(),
+            visited_count: 0,
+            done: false,
+        }
+    }
+
+    fn over_budget(&self) -> bool {
+        self.visited_count >= self.budget.max_visited
+            || self.start_time.elapsed().as_millis() > self.budget.max_time_ms as u128
+    }
+
+    fn node_visible(&self, node: &Node) -> bool {
+        let request = &self.request;
+        request
+            .created_after
+            .is_none_or(|after| node.created_at >= after)
+            && request
+                .as_of
+                .is_none_or(|as_of| node_visible_at(node, as_of))
+            && (request.include_expired
+                || !node.is_expired_at(request.as_of.unwrap_or_else(Utc::now)))
+            && (!node.deleted || request.include_deleted || request.as_of.is_some())
+    }
+
+    fn edge_followed(&self, edge: &Edge) -> bool {
+        let request = &self.request;
+        request
+            .relation_filter
+            .as_ref()
+            .is_none_or(|relations| relations.contains(&edge.relation))
+            && request.min_weight.is_none_or(|min| edge.weight >= min)
+            && request
+                .created_after
+                .is_none_or(|after| edge.created_at >= after)
+            && request
+                .as_of
+                .is_none_or(|as_of| edge_visible_at(edge, as_of))
+    }
+
+    fn next_level(&mut self) -> Result<TraversalLevel> {
+        let depth = self.depth;
+        let mut out = TraversalLevel {
+            depth,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            visited_count: 0,
+            truncated: false,
+        };
+        let mut next = Vec::new();
+
+        for id in std::mem::take(&mut self.level) {
+            if self.over_budget() {
+                out.truncated = true;
+                break;
+            }
+            self.visited_count += 1;
+
+            let Some(node) = self.storage.get_node(id)? else {
+                continue;
+            };
+            if !self.node_visible(&node) {
+                continue;
+            }
+
+            let include = (depth > 0 || self.request.include_start)
+                && self
+                    .request
+                    .kind_filter
+                    .as_ref()
+                    .is_none_or(|kinds| kinds.contains(&node.kind));
+            if include {
+                self.included.insert(id);
+                out.nodes.push(node);
+                if self
+                    .request
+                    .limit
+                    .is_some_and(|limit| self.included.len() >= limit)
+                {
+                    out.truncated = true;
+                    break;
+                }
+            }
+
+            if self.request.max_depth.is_some_and(|max| depth >= max) {
+                continue;
+            }
+            let edges = get_edges_for_node(self.storage.as_ref(), id, &self.request.direction)?;
+            // Circuit breaker on the size of the next level
+            if next.len() >= self.budget.max_nodes_per_level {
+                out.truncated = true;
+                break;
+            }
+            for edge in edges {
+                if !self.edge_followed(&edge) {
+                    continue;
+                }
+                let next_id = if edge.from == id { edge.to } else { edge.from };
+                if self.queued.insert(next_id) {
+                    next.push(next_id);
+                }
+                self.pending_edges.push(edge);
+            }
+        }
+
+        // Yield edges whose endpoints are both in; keep those still waiting
+        // on a node of the next level
+        let waiting: HashSet<NodeId> = if out.truncated {
+            HashSet::new()
+        } else {
+            next.iter().copied().collect()
+        };
+        let pending = std::mem::take(&mut self.pending_edges);
+        for edge in pending {
+            let from_in = self.included.contains(&edge.from);
+            let to_in = self.included.contains(&edge.to);
+            if from_in && to_in {
+                if self.emitted_edges.insert(edge.id) {
+                    out.edges.push(edge);
+                }
+            } else if (from_in || waiting.contains(&edge.from))
+                && (to_in || waiting.contains(&edge.to))
+            {
+                self.pending_edges.push(edge);
+            }
+        }
+
+        out.visited_count = self.visited_count;
+        self.done = out.truncated || next.is_empty();
+        self.level = next;
+        self.depth += 1;
+        Ok(out)
+    }
+}
+
+impl<S: Storage> Iterator for TraversalStream<S> {
+    type Item = Result<TraversalLevel>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.level.is_empty() {
+            return None;
+        }
+        let level = self.next_level();
+        if level.is_err() {
+            self.done = true;
+        }
+        Some(level)
+    }
+}
+
 /// Helper function to get edges for a node based on direction
 fn get_edges_for_node<S: Storage>(
     storage: &S,
@@ -1,4 +1,4 @@
-use crate::types::{EdgeId, NodeId, NodeKind, Relation};
+use crate::types::{Edge, EdgeId, Node, NodeId, NodeKind, Relation};
 use chrono::{DateTime, Utc};
 
 /// Request for graph traversal
@@ -37,6 +37,18 @@ pub struct TraversalRequest {
 
     /// Time boundary. Only follow edges/nodes created after this time.
     pub created_after: Option<DateTime<Utc>>,
+
+    /// Point-in-time view. Only nodes/edges that existed at this instant
+    /// (created at or before it, not soft-deleted by then) are visible.
+    pub as_of: Option<DateTime<Utc>>,
+
+    /// Also return nodes whose `expires_at` has passed (as of `as_of`, or
+    /// now). Expired nodes are skipped like nodes outside `as_of`.
+    pub include_expired: bool,
+
+    /// Also return soft-deleted nodes. Ignored with `as_of`, which already
+    /// shows a node up to the moment it was deleted.
+    pub include_deleted: bool,
 }
 
 impl Default for TraversalRequest {
@@ -52,6 +64,9 @@ impl Default for TraversalRequest {
             strategy: TraversalStrategy::Bfs,
             include_start: true,
             created_after: None,
+            as_of: None,
+            include_expired: false,
+            include_deleted: false,
         }
     }
 }
@@ -105,6 +120,23 @@ pub struct PathRequest {
 
     /// How many paths to return. Default 1 (shortest).
     pub max_paths: usize,
+
+    /// What "shortest" means. Default: fewest hops.
+    pub strategy: PathStrategy,
+}
+
+/// Cost model for path finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathStrategy {
+    /// Fewest edges (BFS). Edge weights are ignored; setting `min_weight`
+    /// switches the request to `Weighted`.
+    #[default]
+    HopCount,
+
+    /// Lowest total cost (Dijkstra), where each edge costs `1 / weight`.
+    /// A chain of strong edges beats a shorter chain of weak ones.
+    /// Best for: "how are these most strongly related?"
+    Weighted,
 }
 
 impl Default for PathRequest {
@@ -116,6 +148,7 @@ impl Default for PathRequest {
             relation_filter: None,
             min_weight: None,
             max_paths: 1,
+            strategy: PathStrategy::HopCount,
         }
     }
 }
@@ -125,6 +158,9 @@ impl Default for PathRequest {
 pub struct PathResult {
     /// Ordered list of paths, shortest first.
     pub paths: Vec<Path>,
+
+    /// Nodes whose edges the search expanded. For diagnostics.
+    pub explored: usize,
 }
 
 /// A path through the graph
@@ -178,3 +214,88 @@ impl Default for TraversalBudget {
         }
     }
 }
+
+/// One depth level of a streamed breadth-first traversal.
+#[derive(Debug, Clone)]
+pub struct TraversalLevel {
+    /// Distance from the nearest start node.
+    pub depth: u32,
+
+    /// Nodes first reached at this depth that pass the filters.
+    pub nodes: Vec<Node>,
+
+    /// Edges whose second endpoint arrived in this level. Each edge is
+    /// yielded once, after both its endpoints.
+    pub edges: Vec<Edge>,
+
+    /// Total nodes visited so far, across all levels.
+    pub visited_count: usize,
+
+    /// The budget or limit stopped the traversal during this level. It is
+    /// the last level yielded.
+    pub truncated: bool,
+}
+
+/// Parameters for PageRank centrality
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageRankConfig {
+    /// Probability of following an edge rather than jumping to a random node.
+    pub damping: f32,
+
+    /// Stop after this many iterations even if not converged.
+    pub max_iterations: usize,
+
+    /// Stop once the total (L1) change in rank between iterations drops below this.
+    pub tolerance: f32,
+
+    /// Split each node's rank across its outgoing edges in proportion to
+    /// edge weight instead of evenly.
+    pub weighted: bool,
+}
+
+impl Default for PageRankConfig {
+    fn default() -> Self {
+        Self {
+            damping: 0.85,
+            max_iterations: 100,
+            tolerance: 1e-6,
+            weighted: true,
+        }
+    }
+}
+
+/// Parameters for community detection
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommunityConfig {
+    /// Stop label propagation after this many passes even if labels still change.
+    pub max_iterations: usize,
+
+    /// Use edge weights as connection strength instead of counting every edge as 1.
+    pub weighted: bool,
+
+    /// Drop communities smaller than this from the result (isolated nodes are
+    /// singleton communities). Modularity is still computed over all of them.
+    pub min_size: usize,
+}
+
+impl Default for CommunityConfig {
+    fn default() -> Self {
+        Self {
+            max_iterations: 50,
+            weighted: true,
+            min_size: 1,
+        }
+    }
+}
+
+/// A cluster of tightly-connected nodes
+#[derive(Debug, Clone)]
+pub struct Community {
+    /// Member nodes, most internally connected first — the head of the list
+    /// is a good representative for the cluster.
+    pub nodes: Vec<NodeId>,
+
+    /// This community's contribution to the partition's modularity.
+    /// Summing over all communities gives the total modularity (-0.5..1.0).
+    pub modularity: f32,
+}
@@ -1,4 +1,6 @@
+use crate::error::{CortexError, Result};
 use crate::NodeKind;
+use serde::{Deserialize, Serialize};
 
 /// The 8 default node kinds shipped with Cortex.
 /// Users may define additional kinds in cortex.toml.
@@ -47,3 +49,169 @@ pub mod defaults {
         ]
     }
 }
+
+/// A registered node kind.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KindDefinition {
+    pub name: NodeKind,
+    /// Human-readable name, e.g. "Architecture decision". Defaults to the kind.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Importance given to new nodes of this kind when the writer sets none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_importance: Option<f32>,
+}
+
+impl KindDefinition {
+    pub fn new(name: NodeKind) -> Self {
+        Self {
+            name,
+            display_name: None,
+            default_importance: None,
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(self.name.as_str())
+    }
+}
+
+/// The node kinds a deployment knows about. The built-in kinds are always
+/// registered. A strict registry makes [`NodeKind::new_checked`] reject
+/// any other kind, so a typo like `decison` fails instead of quietly
+/// starting a new kind; a lenient one (the default) accepts it.
+#[derive(Debug, Clone)]
+pub struct KindRegistry {
+    kinds: Vec<KindDefinition>,
+    strict: bool,
+}
+
+impl Default for KindRegistry {
+    fn default() -> Self {
+        Self {
+            kinds: defaults::all()
+                .into_iter()
+                .map(KindDefinition::new)
+                .collect(),
+            strict: false,
+        }
+    }
+}
+
+impl KindRegistry {
+    /// The built-in kinds, lenient.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Add a kind, replacing any definition with the same name (so a
+    /// built-in kind can be given a display name or default importance).
+    pub fn register(&mut self, definition: KindDefinition) {
+        match self.kinds.iter_mut().find(|k| k.name == definition.name) {
+            Some(existing) => *existing = definition,
+            None => self.kinds.push(definition),
+        }
+    }
+
+    pub fn get(&self, kind: &NodeKind) -> Option<&KindDefinition> {
+        self.kinds.iter().find(|k| &k.name == kind)
+    }
+
+    pub fn contains(&self, kind: &NodeKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    /// Registered kinds, built-ins first, then in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &KindDefinition> {
+        self.kinds.iter()
+    }
+
+    pub fn default_importance(&self, kind: &NodeKind) -> Option<f32> {
+        self.get(kind).and_then(|k| k.default_importance)
+    }
+
+    /// Importance for a new node of `kind`: `given` if the writer set one,
+    /// else the kind's default, else 0.5.
+    pub fn importance(&self, kind: &NodeKind, given: Option<f32>) -> f32 {
+        given
+            .or_else(|| self.default_importance(kind))
+            .unwrap_or(0.5)
+    }
+
+    /// In strict mode, fail for a kind that isn't registered.
+    pub fn check(&self, kind: &NodeKind) -> Result<()> {
+        if !self.strict || self.contains(kind) {
+            return Ok(());
+        }
+        let known: Vec<&str> = self.kinds.iter().map(|k| k.name.as_str()).collect();
+        Err(CortexError::Validation(format!(
+            "Unknown node kind '{}'; registered kinds: {}",
+            kind,
+            known.join(", ")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_registry_rejects_unregistered_kind() {
+        let mut registry = KindRegistry::new().strict(true);
+        registry.register(KindDefinition {
+            name: NodeKind::new("risk").unwrap(),
+            display_name: Some("Risk".into()),
+            default_importance: Some(0.8),
+        });
+
+        assert!(NodeKind::new_checked("decision", &registry).is_ok());
+        assert!(NodeKind::new_checked("risk", &registry).is_ok());
+        let err = NodeKind::new_checked("decison", &registry).unwrap_err();
+        assert!(err.to_string().contains("Unknown node kind 'decison'"));
+        // Format errors still come first
+        assert!(NodeKind::new_checked("Not A Kind", &registry).is_err());
+
+        let risk = NodeKind::new("risk").unwrap();
+        assert_eq!(registry.default_importance(&risk), Some(0.8));
+        assert_eq!(registry.get(&risk).unwrap().display_name(), "Risk");
+        assert_eq!(
+            registry.get(&defaults::fact()).unwrap().display_name(),
+            "fact"
+        );
+    }
+
+    #[test]
+    fn lenient_registry_allows_unregistered_kind() {
+        let registry = KindRegistry::new();
+        assert!(!registry.is_strict());
+        let kind = NodeKind::new_checked("decison", &registry).unwrap();
+        assert_eq!(kind.as_str(), "decison");
+        assert!(!registry.contains(&kind));
+        assert!(NodeKind::new_checked("Not A Kind", &registry).is_err());
+    }
+
+    #[test]
+    fn register_replaces_builtin_definition() {
+        let mut registry = KindRegistry::new();
+        let count = registry.iter().count();
+        registry.register(KindDefinition {
+            display_name: Some("Decision record".into()),
+            ..KindDefinition::new(defaults::decision())
+        });
+        assert_eq!(registry.iter().count(), count);
+        assert_eq!(
+            registry.get(&defaults::decision()).unwrap().display_name(),
+            "Decision record"
+        );
+    }
+}
@@ -15,33 +15,52 @@ pub mod storage;
 pub mod types;
 pub mod vector;
 
+#[cfg(test)]
+mod testing;
+
 pub use api::{Cortex, LibraryConfig};
 pub use error::{CortexError, Result};
 pub use gate::schema::{FieldSchema, FieldType, KindSchema, SchemaValidator, SchemaViolation};
 pub use gate::{
-    GateCheck, GateRejection, GateResult, KindOverrideConfig, WriteGate, WriteGateConfig,
+    GateCheck, GateFailureCount, GateMode, GateRejection, GateResult, GateStats, GateStatsSnapshot,
+    KindKeywordRules, KindOverrideConfig, PiiConfig, PiiMode, PiiOutcome, PiiPattern, WriteGate,
+    WriteGateConfig,
 };
 pub use graph::{
-    GraphEngine, GraphEngineImpl, Path, PathRequest, PathResult, Subgraph, TraversalBudget,
-    TraversalDirection, TraversalRequest, TraversalStrategy,
+    would_create_cycle, Community, CommunityConfig, GraphEngine, GraphEngineImpl, PageRankConfig,
+    Path, PathRequest, PathResult, PathStrategy, Subgraph, TraversalBudget, TraversalDirection,
+    TraversalLevel, TraversalRequest, TraversalStrategy, TraversalStream,
 };
 pub use hooks::{HookRegistry, MutationAction, MutationHook};
+pub use kinds::{KindDefinition, KindRegistry};
 pub use linker::{
     AutoLinker, AutoLinkerConfig, AutoLinkerMetrics, ConfigRule, Contradiction,
-    ContradictionDetector, DecayConfig, DecayEngine, DedupAction, DedupScanner, DuplicatePair,
-    LinkRule, ProposedEdge, Resolution, RuleCondition, SimilarityLinkRule, StructuralRule,
+    ContradictionDetector, ContradictionStrategy, DecayConfig, DecayEngine, DedupAction,
+    DedupScanner, DuplicatePair, LinkRule, ProposedEdge, Resolution, RuleCondition,
+    SimilarityLinkRule, StructuralRule, TemporalCoOccurrenceRule,
 };
 pub use policies::{
-    AuditAction, AuditEntry, AuditFilter, AuditLog, KindRetention, RetentionConfig,
-    RetentionEngine, RetentionMaxNodes,
+    restore_archive, ArchiveRecord, AuditAction, AuditEntry, AuditFilter, AuditLog, Eviction,
+    EvictionMode, EvictionReason, KindRetention, RestoreSummary, RetentionConfig, RetentionEngine,
+    RetentionMaxNodes, PINNED_TAG,
+};
+pub use query::{
+    parse_and_compile as parse_filter, run_query, CmpOp, FieldFilter, FilterExpr, QueryHit,
+};
+pub use storage::{
+    diff_storage, merge_storage, read_jsonl, repair, verify, write_jsonl, DuplicateTitle,
+    FieldChange, GraphDiff, JsonlCounts, JsonlRecord, MergeConflict, MergeOptions, MergeReport,
+    NodeChange, NodeCursor, NodeFilter, RedbStorage, RelationCycle, RepairSummary, SnapshotInfo,
+    Storage, StorageStats, StorageTransaction, VerifyConfig, VerifyReport, CURRENT_SCHEMA_VERSION,
+    SNAPSHOT_GRAPH_VERSION_KEY,
 };
-pub use query::{parse_and_compile as parse_filter, CmpOp, FieldFilter, FilterExpr};
-pub use storage::{NodeFilter, RedbStorage, Storage, StorageStats, CURRENT_SCHEMA_VERSION};
 pub use types::*;
 pub use vector::{
-    apply_score_decay, embedding_input, EmbeddingService, FastEmbedService, HnswIndex, HybridQuery,
-    HybridResult, HybridSearch, RwLockVectorIndex, ScoreDecayConfig, SimilarityConfig,
-    SimilarityResult, VectorFilter, VectorIndex,
+    apply_confidence_weight, apply_recency_boost, apply_score_decay, embedding_input, mmr_rerank,
+    page_by_score, page_search, reindex, replay_writes, CachingEmbeddingService,
+    EmbeddingCacheStats, EmbeddingService, FastEmbedService, GraphProximity, HnswIndex,
+    HybridQuery, HybridResult, HybridSearch, ReindexSummary, RwLockVectorIndex, ScoreDecayConfig,
+    SearchCursor, SimilarityConfig, SimilarityResult, VectorFilter, VectorIndex,
 };
 
 #[cfg(test)]
@@ -2,20 +2,47 @@ use crate::error::Result;
 use crate::graph::GraphEngine;
 use crate::linker::{
     AutoLinkerConfig, AutoLinkerMetrics, ConfigRule, ContradictionDetector, DecayEngine,
-    DedupScanner, LinkRule, ProposedEdge, SimilarityLinkRule, StructuralRule,
+    DedupScanner, LinkRule, ProposedEdge, Resolution, SimilarityLinkRule, StructuralRule,
+    TemporalCoOccurrenceRule,
 };
 use crate::storage::{NodeFilter, Storage};
-use crate::types::{EdgeProvenance, Node, NodeId, Relation};
+use crate::types::{Edge, EdgeProvenance, Embedding, Node, NodeId, Relation};
 use crate::vector::{embedding_input, EmbeddingService, VectorIndex};
 use chrono::{DateTime, Utc};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::Instant;
 
+/// Legacy cursor, whole seconds. Read only as a fallback.
 const CURSOR_KEY: &str = "auto_linker_cursor";
+/// Cursor in microseconds, so nodes changed in the same second as the last
+/// processed one are not rescanned.
+const CURSOR_MICROS_KEY: &str = "auto_linker_cursor_us";
 const CYCLE_COUNT_KEY: &str = "auto_linker_cycle_count";
 const LAST_THRESHOLD_KEY: &str = "auto_linker_last_threshold";
 const LAST_MODEL_KEY: &str = "auto_linker_last_model";
 
+/// A `supersedes` edge settling a contradiction, whose target is demoted
+/// once it is written.
+fn is_contradiction_supersede(proposed: &ProposedEdge) -> bool {
+    proposed.relation.as_str() == "supersedes"
+        && matches!(
+            proposed.provenance,
+            EdgeProvenance::AutoContradiction { .. }
+        )
+}
+
+/// Edges the linker created (and may therefore prune), as opposed to
+/// manual or imported ones and the inverses written alongside them.
+fn is_auto_edge(edge: &Edge) -> bool {
+    !matches!(
+        edge.provenance,
+        EdgeProvenance::Manual { .. }
+            | EdgeProvenance::Imported { .. }
+            | EdgeProvenance::DerivedInverse { .. }
+    )
+}
+
 /// Auto-linker: Background process for self-growing graph
 pub struct AutoLinker<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> {
     storage: Arc<S>,
@@ -35,6 +62,12 @@ pub struct AutoLinker<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphE
     similarity_rule: SimilarityLinkRule,
     /// Pre-allocated contradiction detector
     contradiction_detector: ContradictionDetector,
+    /// Same-session temporal co-occurrence rule, if enabled
+    co_occurrence_rule: Option<TemporalCoOccurrenceRule>,
+    /// Library-supplied rules, evaluated after the built-in ones
+    custom_rules: Vec<Box<dyn LinkRule>>,
+    /// Bumped after a cycle that wrote edges
+    graph_version: Option<Arc<AtomicU64>>,
 }
 
 impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker<S, E, V, G> {
@@ -57,22 +90,13 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
         metrics.update_cursor(cursor);
         metrics.cycles = cycle_count;
 
-        let structural_rules = if config.use_legacy_rules() {
-            vec![
-                StructuralRule::same_agent(),
-                StructuralRule::temporal_proximity(),
-                StructuralRule::shared_tags(),
-                StructuralRule::decision_to_event(),
-                StructuralRule::observation_to_pattern(),
-                StructuralRule::fact_supersedes(),
-            ]
-        } else {
-            Vec::new()
-        };
+        let structural_rules = Self::structural_rules(&config);
         let config_rules = config.rules.clone();
         let similarity_rule = SimilarityLinkRule;
         let contradiction_detector =
-            ContradictionDetector::new(config.similarity.contradiction_threshold);
+            ContradictionDetector::new(config.similarity.contradiction_threshold)
+                .with_strategy(config.contradiction_strategy);
+        let co_occurrence_rule = config.co_occurrence.clone();
 
         Ok(Self {
             storage,
@@ -88,11 +112,73 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
             config_rules,
             similarity_rule,
             contradiction_detector,
+            co_occurrence_rule,
+            custom_rules: Vec::new(),
+            graph_version: None,
         })
     }
 
+    fn structural_rules(config: &AutoLinkerConfig) -> Vec<StructuralRule> {
+        if !config.use_legacy_rules() {
+            return Vec::new();
+        }
+        vec![
+            StructuralRule::same_agent(),
+            StructuralRule::temporal_proximity(),
+            StructuralRule::shared_tags(),
+            StructuralRule::decision_to_event(),
+            StructuralRule::observation_to_pattern(),
+            StructuralRule::fact_supersedes(),
+        ]
+    }
+
+    /// Replace the config, e.g. on a config reload. The cursor, metrics and
+    /// custom rules carry over. A changed similarity threshold rescans from
+    /// the epoch on the next cycle, as it would after a restart.
+    pub fn set_config(&mut self, config: AutoLinkerConfig) -> Result<()> {
+        config.validate()?;
+        self.decay_engine = DecayEngine::new(self.storage.clone(), config.decay.clone());
+        self.structural_rules = Self::structural_rules(&config);
+        self.config_rules = config.rules.clone();
+        self.contradiction_detector =
+            ContradictionDetector::new(config.similarity.contradiction_threshold)
+                .with_strategy(config.contradiction_strategy);
+        self.co_occurrence_rule = config.co_occurrence.clone();
+        self.config = config;
+        Ok(())
+    }
+
+    /// The config in effect.
+    pub fn config(&self) -> &AutoLinkerConfig {
+        &self.config
+    }
+
+    /// Register a custom link rule. See [`LinkRule`] for the contract.
+    pub fn with_rule(mut self, rule: impl LinkRule + 'static) -> Self {
+        self.custom_rules.push(Box::new(rule));
+        self
+    }
+
+    /// Bump `graph_version` after each cycle that creates, prunes or deletes
+    /// edges, so version-keyed caches see the linker's writes.
+    pub fn with_graph_version(mut self, graph_version: Arc<AtomicU64>) -> Self {
+        self.graph_version = Some(graph_version);
+        self
+    }
+
+    /// Register several custom link rules, evaluated in order.
+    pub fn with_rules(mut self, rules: Vec<Box<dyn LinkRule>>) -> Self {
+        self.custom_rules.extend(rules);
+        self
+    }
+
     /// Load cursor from persistent storage
     fn load_cursor(storage: &Arc<S>) -> Result<DateTime<Utc>> {
+        if let Some(bytes) = storage.get_metadata(CURSOR_MICROS_KEY)? {
+            let micros: i64 =
+                bincode::deserialize(&bytes).map_err(crate::error::CortexError::Serialization)?;
+            return Ok(DateTime::from_timestamp_micros(micros).unwrap_or_else(Utc::now));
+        }
         match storage.get_metadata(CURSOR_KEY)? {
             Some(bytes) => {
                 let timestamp: i64 = bincode::deserialize(&bytes)
@@ -108,10 +194,10 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
 
     /// Save cursor to persistent storage
     fn save_cursor(&self) -> Result<()> {
-        let timestamp = self.cursor.timestamp();
+        let micros = self.cursor.timestamp_micros();
         let bytes =
-            bincode::serialize(&timestamp).map_err(crate::error::CortexError::Serialization)?;
-        self.storage.put_metadata(CURSOR_KEY, &bytes)
+            bincode::serialize(&micros).map_err(crate::error::CortexError::Serialization)?;
+        self.storage.put_metadata(CURSOR_MICROS_KEY, &bytes)
     }
 
     /// Load cycle count from persistent storage
@@ -181,7 +267,55 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
         Ok(())
     }
 
-    /// Run a single processing cycle
+    /// Re-evaluate every node, not just those changed since the cursor.
+    /// Large graphs are worked through over several cycles, `max_nodes_per_cycle`
+    /// at a time, as the cursor advances again from the epoch.
+    pub fn run_full_cycle(&mut self) -> Result<()> {
+        self.cursor = DateTime::<Utc>::UNIX_EPOCH;
+        self.metrics.update_cursor(self.cursor);
+        self.run_cycle()
+    }
+
+    /// Dry run: the edges the next `run_cycle` would propose, without writing
+    /// anything. No edges, embeddings, cursor or metrics are changed.
+    /// Displacement under `max_edges_per_node` is not simulated.
+    pub fn preview_cycle(&self) -> Result<Vec<ProposedEdge>> {
+        self.preview_since(self.cursor)
+    }
+
+    /// Dry run over every node, as `run_full_cycle` would see the graph.
+    pub fn preview_full_cycle(&self) -> Result<Vec<ProposedEdge>> {
+        self.preview_since(DateTime::<Utc>::UNIX_EPOCH)
+    }
+
+    fn preview_since(&self, since: DateTime<Utc>) -> Result<Vec<ProposedEdge>> {
+        let mut proposed_edges = Vec::new();
+        for node in self
+            .get_nodes_changed_since(since)?
+            .iter()
+            .take(self.config.max_nodes_per_cycle)
+        {
+            let embedding = match &node.embedding {
+                Some(emb) => emb.clone(),
+                None => self.embedding_service.embed(&embedding_input(node))?,
+            };
+            let (edges, _) = self.propose_for_node(node, &embedding)?;
+            proposed_edges.extend(edges);
+        }
+        proposed_edges.truncate(self.config.max_edges_per_cycle);
+        Ok(proposed_edges)
+    }
+
+    /// Run a single processing cycle over nodes changed since the cursor
+    #[tracing::instrument(
+        name = "auto_linker.cycle",
+        skip_all,
+        fields(
+            cycle = self.cycle_count,
+            nodes = tracing::field::Empty,
+            edges_created = tracing::field::Empty,
+        )
+    )]
     pub fn run_cycle(&mut self) -> Result<()> {
         let start = Instant::now();
         self.metrics.reset_cycle_metrics();
@@ -192,7 +326,9 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
         self.check_config_change()?;
 
         // 1. Scan for new/updated nodes since cursor
-        let new_nodes = self.get_nodes_since_cursor()?;
+        let new_nodes = self.get_nodes_changed_since(self.cursor)?;
+        let scanned = new_nodes.len();
+        self.metrics.add_nodes_scanned(scanned as u64);
 
         if new_nodes.is_empty()
             && !self
@@ -209,6 +345,8 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
             .into_iter()
             .take(self.config.max_nodes_per_cycle)
             .collect();
+        let span = tracing::Span::current();
+        span.record("nodes", nodes_to_process.len());
 
         let mut proposed_edges = Vec::new();
 
@@ -216,52 +354,10 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
             // Ensure node has embedding
             let embedding = self.ensure_embedding(node)?;
 
-            // Find similar nodes
-            let vector_index = self.vector_index.read().unwrap();
-            let similar = vector_index.search(&embedding, 100, None)?;
-            drop(vector_index);
-
-            let mut node_edge_count = 0;
-
-            // Pre-load existing outgoing edges for this node (batch check)
-            let existing_edges = self.storage.edges_from(node.id)?;
-            let existing_set: std::collections::HashSet<(NodeId, String)> = existing_edges
-                .iter()
-                .map(|e| (e.to, format!("{:?}", e.relation)))
-                .collect();
-
-            for result in similar {
-                // Skip self
-                if result.node_id == node.id {
-                    continue;
-                }
-
-                // Get neighbor node (skip deleted)
-                let neighbor = match self.storage.get_node(result.node_id)? {
-                    Some(n) if !n.deleted => n,
-                    _ => continue,
-                };
-
-                // Apply link rules
-                let edges = self.apply_link_rules(node, &neighbor, result.score)?;
-
-                // Filter out edges that already exist (using pre-loaded set)
-                for edge in edges {
-                    if edge.relation.as_str() == "contradicts" {
-                        self.metrics.add_contradictions_found(1);
-                    }
-                    let key = (edge.to, format!("{:?}", edge.relation));
-                    if !existing_set.contains(&key) {
-                        node_edge_count += 1;
-                        proposed_edges.push(edge);
-                    }
-                }
-
-                // Check per-node limit
-                if node_edge_count >= self.config.max_edges_per_node {
-                    break;
-                }
-            }
+            let (edges, contradictions) = self.propose_for_node(node, &embedding)?;
+            let node_edge_count = edges.len();
+            self.metrics.add_contradictions_found(contradictions);
+            proposed_edges.extend(edges);
 
             // Check for generic content
             if node_edge_count >= self.config.generic_content_threshold {
@@ -274,9 +370,10 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
 
             self.metrics.add_nodes_processed(1);
 
-            // Update cursor to this node's timestamp
-            if node.created_at > self.cursor {
-                self.cursor = node.created_at;
+            // Advance the cursor past this node's latest change
+            let changed_at = node.created_at.max(node.updated_at);
+            if changed_at > self.cursor {
+                self.cursor = changed_at;
             }
         }
 
@@ -286,11 +383,22 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
             .take(self.config.max_edges_per_cycle)
             .collect();
 
+        let mut edges_created = 0;
         for proposed in edges_to_create {
+            if !self.make_room_for(&proposed)? {
+                continue;
+            }
+            let resolves_contradiction = is_contradiction_supersede(&proposed);
             let edge = proposed.to_edge();
             // Edge already pre-filtered in the loop above; just create
             match self.storage.put_edge(&edge) {
-                Ok(()) => self.metrics.add_edges_created(1),
+                Ok(()) => {
+                    self.metrics.add_edges_created(1);
+                    edges_created += 1;
+                    if resolves_contradiction {
+                        self.demote_loser(edge.to)?;
+                    }
+                }
                 Err(crate::error::CortexError::DuplicateEdge { .. }) => {
                     // Race condition or edge created between check and insert — skip
                     continue;
@@ -303,6 +411,8 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
             }
         }
 
+        span.record("edges_created", edges_created);
+
         // 4. Decay pass (periodic)
         if self
             .cycle_count
@@ -346,13 +456,21 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
         self.metrics.update_cursor(self.cursor);
         self.metrics.set_cycle_duration(start.elapsed());
 
+        let m = &self.metrics;
+        if m.edges_created + m.edges_pruned + m.edges_deleted + m.duplicates_found > 0 {
+            if let Some(ref version) = self.graph_version {
+                version.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
         // Update stats
         let stats = self.storage.stats()?;
         self.metrics.set_total_nodes(stats.node_count);
         self.metrics.set_total_edges(stats.edge_count);
 
-        // Estimate backlog from what we didn't process this cycle
-        self.metrics.set_backlog_size(0); // Will be accurate on next cycle scan
+        // Backlog is whatever the scan found beyond max_nodes_per_cycle
+        self.metrics
+            .set_backlog_size(scanned.saturating_sub(nodes_to_process.len()) as u64);
 
         // Persist cursor and cycle count
         self.save_cursor()?;
@@ -363,15 +481,172 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
         Ok(())
     }
 
-    /// Get nodes created/updated since cursor
-    fn get_nodes_since_cursor(&self) -> Result<Vec<Node>> {
-        let all_nodes = self.storage.list_nodes(NodeFilter::new())?;
-        let filtered: Vec<_> = all_nodes
-            .into_iter()
-            .filter(|n| n.created_at > self.cursor || n.updated_at > self.cursor)
-            .filter(|n| !n.deleted)
+    /// Enforce `max_edges_per_node` on both endpoints of a proposed edge.
+    /// If an endpoint is full, the proposal only goes ahead when it outweighs
+    /// that endpoint's weakest auto edge of the same relation, which is pruned.
+    /// Returns false when the proposal should be dropped, including when the
+    /// edge already exists, so nothing is pruned for a write that would fail.
+    fn make_room_for(&mut self, proposed: &ProposedEdge) -> Result<bool> {
+        let mut displaced = Vec::new();
+        for endpoint in [proposed.from, proposed.to] {
+            let edges: Vec<Edge> = self
+                .storage
+                .edges_from(endpoint)?
+                .into_iter()
+                .chain(self.storage.edges_to(endpoint)?)
+                .filter(|e| e.relation == proposed.relation)
+                .collect();
+            if edges
+                .iter()
+                .any(|e| e.from == proposed.from && e.to == proposed.to)
+            {
+                return Ok(false);
+            }
+            let mut auto_edges: Vec<&Edge> = edges.iter().filter(|e| is_auto_edge(e)).collect();
+            if auto_edges.len() < self.config.max_edges_per_node {
+                continue;
+            }
+            auto_edges.sort_by(|a, b| a.weight.total_cmp(&b.weight));
+            // A cap of zero leaves no edge to displace
+            let Some(weakest) = auto_edges.first() else {
+                return Ok(false);
+            };
+            if proposed.weight <= weakest.weight {
+                return Ok(false);
+            }
+            displaced.push(weakest.id);
+        }
+
+        displaced.dedup();
+        for id in displaced {
+            self.storage.delete_edge(id)?;
+            self.metrics.add_edges_pruned(1);
+        }
+        Ok(true)
+    }
+
+    /// Scale down the importance of a node that lost a contradiction.
+    /// Runs once per pair, when the `supersedes` edge is first created.
+    fn demote_loser(&self, id: NodeId) -> Result<()> {
+        if self.config.loser_importance_factor >= 1.0 {
+            return Ok(());
+        }
+        if let Some(mut loser) = self.storage.get_node(id)? {
+            loser.importance *= self.config.loser_importance_factor;
+            loser.updated_at = Utc::now();
+            self.storage.put_node(&loser)?;
+            self.vector_index.write().unwrap().update_metadata(&loser);
+        }
+        Ok(())
+    }
+
+    /// Evaluate every rule for one backlog node against its vector neighbours
+    /// (and same-session peers), dropping proposals that match an existing
+    /// outgoing edge. Read-only. Also returns the number of contradictions seen.
+    fn propose_for_node(
+        &self,
+        node: &Node,
+        embedding: &Embedding,
+    ) -> Result<(Vec<ProposedEdge>, u64)> {
+        let mut proposed_edges = Vec::new();
+        let mut contradictions = 0;
+
+        // Find similar nodes
+        let vector_index = self.vector_index.read().unwrap();
+        let similar = vector_index.search(embedding, 100, None)?;
+        drop(vector_index);
+        let similar_ids: std::collections::HashSet<NodeId> =
+            similar.iter().map(|r| r.node_id).collect();
+
+        // Pre-load existing outgoing edges for this node (batch check)
+        let existing_edges = self.storage.edges_from(node.id)?;
+        let existing_set: std::collections::HashSet<(NodeId, String)> = existing_edges
+            .iter()
+            .map(|e| (e.to, format!("{:?}", e.relation)))
             .collect();
-        Ok(filtered)
+
+        for result in similar {
+            // Skip self
+            if result.node_id == node.id {
+                continue;
+            }
+
+            // Get neighbor node (skip deleted)
+            let neighbor = match self.storage.get_node(result.node_id)? {
+                Some(n) if !n.deleted => n,
+                _ => continue,
+            };
+
+            // Apply link rules
+            let edges = self.apply_link_rules(node, &neighbor, result.score)?;
+
+            // Filter out edges that already exist (using pre-loaded set)
+            for edge in edges {
+                if edge.relation.as_str() == "contradicts" {
+                    contradictions += 1;
+                }
+                let key = (edge.to, format!("{:?}", edge.relation));
+                if !existing_set.contains(&key) {
+                    proposed_edges.push(edge);
+                }
+            }
+
+            // Check per-node limit
+            if proposed_edges.len() >= self.config.max_edges_per_node {
+                break;
+            }
+        }
+
+        // Same-session peers the vector search didn't surface
+        if proposed_edges.len() < self.config.max_edges_per_node {
+            for edge in self.co_occurrence_edges(node, &similar_ids)? {
+                if proposed_edges.len() >= self.config.max_edges_per_node {
+                    break;
+                }
+                if !existing_set.contains(&(edge.to, format!("{:?}", edge.relation))) {
+                    proposed_edges.push(edge);
+                }
+            }
+        }
+
+        Ok((proposed_edges, contradictions))
+    }
+
+    /// Co-occurrence proposals against same-session nodes inside the rule's
+    /// window, skipping any already evaluated as vector neighbours.
+    fn co_occurrence_edges(
+        &self,
+        node: &Node,
+        already_seen: &std::collections::HashSet<NodeId>,
+    ) -> Result<Vec<ProposedEdge>> {
+        let Some(ref rule) = self.co_occurrence_rule else {
+            return Ok(Vec::new());
+        };
+        if node.source.session.is_none() {
+            return Ok(Vec::new());
+        }
+
+        let peers = self.storage.list_nodes(
+            NodeFilter::new()
+                .created_after(node.created_at - rule.window)
+                .created_before(node.created_at + rule.window),
+        )?;
+        Ok(peers
+            .iter()
+            .filter(|peer| !peer.deleted && !already_seen.contains(&peer.id))
+            .filter_map(|peer| rule.evaluate(node, peer, 0.0, &self.config.similarity))
+            .collect())
+    }
+
+    /// Get nodes created/updated since `since`, oldest change first so a
+    /// truncated cycle leaves the cursor at a safe resume point
+    fn get_nodes_changed_since(&self, since: DateTime<Utc>) -> Result<Vec<Node>> {
+        let mut changed = self
+            .storage
+            .list_nodes(NodeFilter::new().changed_after(since))?;
+        changed.retain(|n| !n.deleted);
+        changed.sort_by_key(|n| n.created_at.max(n.updated_at));
+        Ok(changed)
     }
 
     /// Ensure node has an embedding, generate if missing
@@ -391,7 +666,7 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
 
         // Index it
         let mut vector_index = self.vector_index.write().unwrap();
-        vector_index.insert(node.id, &embedding)?;
+        vector_index.insert_node(&updated, &embedding)?;
         drop(vector_index);
 
         Ok(embedding)
@@ -426,6 +701,19 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
             edges.extend(rule.evaluate(node, neighbor, score));
         }
 
+        if let Some(ref rule) = self.co_occurrence_rule {
+            if let Some(edge) = rule.evaluate(node, neighbor, score, &self.config.similarity) {
+                edges.push(edge);
+            }
+        }
+
+        // Library-supplied rules
+        for rule in &self.custom_rules {
+            if let Some(edge) = rule.evaluate(node, neighbor, score, &self.config.similarity) {
+                edges.push(edge);
+            }
+        }
+
         // Contradiction detection (pre-allocated)
         if let Some(contradiction) = self.contradiction_detector.check(node, neighbor, score) {
             // Create Contradicts edge
@@ -435,9 +723,29 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
                 relation: Relation::new("contradicts").unwrap(),
                 weight: contradiction.similarity,
                 provenance: EdgeProvenance::AutoContradiction {
-                    reason: contradiction.reason,
+                    reason: contradiction.reason.clone(),
                 },
             });
+
+            if let Resolution::Supersede { keep, retire } = contradiction.suggested_resolution {
+                if self.config.supersede_on_contradiction {
+                    let supersedes = Relation::new("supersedes").unwrap();
+                    // Take over any structural proposal for the same pair so
+                    // the loser is still demoted
+                    edges.retain(|e| {
+                        !(e.from == keep && e.to == retire && e.relation == supersedes)
+                    });
+                    edges.push(ProposedEdge {
+                        from: keep,
+                        to: retire,
+                        relation: supersedes,
+                        weight: contradiction.similarity,
+                        provenance: EdgeProvenance::AutoContradiction {
+                            reason: contradiction.reason,
+                        },
+                    });
+                }
+            }
         }
 
         Ok(edges)
@@ -463,7 +771,9 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> AutoLinker
 mod tests {
     use super::*;
     use crate::graph::GraphEngineImpl;
+    use crate::linker::{ConfigRule, ContradictionStrategy, RuleCondition};
     use crate::storage::RedbStorage;
+    use crate::testing::FixedEmbedder;
     use crate::types::{NodeKind, Source};
     use crate::vector::{FastEmbedService, HnswIndex, SimilarityConfig};
     use std::sync::Arc;
@@ -725,4 +1035,517 @@ mod tests {
             "Model change should reset cursor to epoch"
         );
     }
+
+    fn test_node(title: &str) -> Node {
+        Node::new(
+            NodeKind::new("fact").unwrap(),
+            title.into(),
+            format!("{title} body"),
+            Source {
+                agent: "test".into(),
+                session: None,
+                channel: None,
+            },
+            0.5,
+        )
+    }
+
+    fn auto_edges(storage: &RedbStorage, id: NodeId, relation: &Relation) -> Vec<Edge> {
+        storage
+            .edges_from(id)
+            .unwrap()
+            .into_iter()
+            .chain(storage.edges_to(id).unwrap())
+            .filter(|e| &e.relation == relation && is_auto_edge(e))
+            .collect()
+    }
+
+    #[test]
+    fn test_max_edges_per_node_caps_similarity_edges() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(temp_dir.path().join("cap.redb")).unwrap());
+        let vector_index = Arc::new(RwLock::new(HnswIndex::new(4)));
+
+        // Every pair lands between the link (0.75) and dedup thresholds.
+        let mut ids = Vec::new();
+        for i in 0..8 {
+            let angle = i as f32 * 0.7;
+            let mut node = test_node(&format!("Topic {i}"));
+            let embedding = vec![1.0, 0.3 * angle.cos(), 0.3 * angle.sin(), 0.0];
+            vector_index
+                .write()
+                .unwrap()
+                .insert(node.id, &embedding)
+                .unwrap();
+            node.embedding = Some(embedding);
+            storage.put_node(&node).unwrap();
+            ids.push(node.id);
+        }
+
+        let config = AutoLinkerConfig::new()
+            .with_similarity(SimilarityConfig::new().with_dedup_threshold(0.9999))
+            .with_legacy_rules_enabled(false)
+            .with_rules(vec![ConfigRule {
+                name: "similar-facts".into(),
+                from_kind: "fact".into(),
+                to_kind: "fact".into(),
+                relation: "similar_to".into(),
+                weight: 0.0,
+                weight_from_score: true,
+                bidirectional: false,
+                condition: RuleCondition::MinSimilarity { threshold: 0.75 },
+            }])
+            .with_max_edges_per_node(3);
+        let mut linker = AutoLinker::new(
+            storage.clone(),
+            Arc::new(GraphEngineImpl::new(storage.clone())),
+            vector_index,
+            Arc::new(FixedEmbedder(vec![1.0, 0.0, 0.0, 0.0])),
+            config,
+        )
+        .unwrap();
+        linker.run_cycle().unwrap();
+
+        assert!(linker.metrics().edges_created > 0);
+        let similar_to = Relation::new("similar_to").unwrap();
+        for id in ids {
+            let count = auto_edges(&storage, id, &similar_to).len();
+            assert!(count <= 3, "node {id} has {count} similar_to auto edges");
+        }
+    }
+
+    #[test]
+    fn test_zero_edge_cap_drops_proposals() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(temp_dir.path().join("cap.redb")).unwrap());
+        let (a, b) = (test_node("a"), test_node("b"));
+        storage.put_node(&a).unwrap();
+        storage.put_node(&b).unwrap();
+        let mut linker = AutoLinker::new(
+            storage.clone(),
+            Arc::new(GraphEngineImpl::new(storage.clone())),
+            Arc::new(RwLock::new(HnswIndex::new(4))),
+            Arc::new(FixedEmbedder(vec![1.0, 0.0, 0.0, 0.0])),
+            AutoLinkerConfig::new(),
+        )
+        .unwrap();
+        // Validation rejects a zero cap, but the linker must not rely on it
+        linker.config.max_edges_per_node = 0;
+
+        let proposed = ProposedEdge {
+            from: a.id,
+            to: b.id,
+            relation: Relation::new("similar_to").unwrap(),
+            weight: 0.9,
+            provenance: EdgeProvenance::AutoSimilarity { score: 0.9 },
+        };
+        assert!(!linker.make_room_for(&proposed).unwrap());
+    }
+
+    #[test]
+    fn test_full_node_displaces_weakest_auto_edge() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(temp_dir.path().join("cap.redb")).unwrap());
+        let nodes: Vec<Node> = ["hub", "a", "b", "c", "d"]
+            .iter()
+            .map(|t| test_node(t))
+            .collect();
+        for node in &nodes {
+            storage.put_node(node).unwrap();
+        }
+        let related_to = Relation::new("related_to").unwrap();
+        let auto = |to: usize, weight: f32| ProposedEdge {
+            from: nodes[0].id,
+            to: nodes[to].id,
+            relation: related_to.clone(),
+            weight,
+            provenance: EdgeProvenance::AutoSimilarity { score: weight },
+        };
+        let weak = auto(1, 0.5).to_edge();
+        storage.put_edge(&weak).unwrap();
+        storage.put_edge(&auto(2, 0.9).to_edge()).unwrap();
+        // Manual edges neither count towards nor get pruned by the cap.
+        let manual = Edge::new(
+            nodes[0].id,
+            nodes[4].id,
+            related_to.clone(),
+            0.1,
+            EdgeProvenance::Manual {
+                created_by: "test".into(),
+            },
+        );
+        storage.put_edge(&manual).unwrap();
+
+        let mut linker = AutoLinker::new(
+            storage.clone(),
+            Arc::new(GraphEngineImpl::new(storage.clone())),
+            Arc::new(RwLock::new(HnswIndex::new(4))),
+            Arc::new(FixedEmbedder(vec![1.0, 0.0, 0.0, 0.0])),
+            AutoLinkerConfig::new().with_max_edges_per_node(2),
+        )
+        .unwrap();
+
+        assert!(!linker.make_room_for(&auto(3, 0.4)).unwrap());
+        assert!(storage.get_edge(weak.id).unwrap().is_some());
+
+        // Re-proposing an existing edge must not prune anything for it
+        assert!(!linker.make_room_for(&auto(2, 0.95)).unwrap());
+        assert!(storage.get_edge(weak.id).unwrap().is_some());
+
+        assert!(linker.make_room_for(&auto(3, 0.7)).unwrap());
+        assert!(storage.get_edge(weak.id).unwrap().is_none());
+        assert!(storage.get_edge(manual.id).unwrap().is_some());
+        assert_eq!(linker.metrics().edges_pruned, 1);
+    }
+
+    #[test]
+    fn test_co_occurrence_links_same_session_despite_dissimilar_embeddings() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(temp_dir.path().join("co.redb")).unwrap());
+        let vector_index = Arc::new(RwLock::new(HnswIndex::new(4)));
+
+        // Orthogonal embeddings: nothing clears the similarity threshold.
+        let mut nodes = Vec::new();
+        for (i, (title, session)) in [
+            ("Observed p99 spike", "s1"),
+            ("Decided to add cache", "s1"),
+            ("Unrelated chat", "s2"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let mut node = test_node(title);
+            node.source.session = Some(session.into());
+            let mut embedding = vec![0.0; 4];
+            embedding[i] = 1.0;
+            vector_index
+                .write()
+                .unwrap()
+                .insert(node.id, &embedding)
+                .unwrap();
+            node.embedding = Some(embedding);
+            storage.put_node(&node).unwrap();
+            nodes.push(node);
+        }
+
+        let config = AutoLinkerConfig::new()
+            .with_legacy_rules_enabled(false)
+            .with_co_occurrence(TemporalCoOccurrenceRule::default());
+        let mut linker = AutoLinker::new(
+            storage.clone(),
+            Arc::new(GraphEngineImpl::new(storage.clone())),
+            vector_index,
+            Arc::new(FixedEmbedder(vec![1.0, 0.0, 0.0, 0.0])),
+            config,
+        )
+        .unwrap();
+        linker.run_cycle().unwrap();
+
+        let linked = |a: &Node, b: &Node| {
+            storage
+                .edges_from(a.id)
+                .unwrap()
+                .into_iter()
+                .chain(storage.edges_from(b.id).unwrap())
+                .any(|e| {
+                    (e.to == b.id || e.to == a.id)
+                        && matches!(
+                            e.provenance,
+                            EdgeProvenance::AutoStructural { ref rule } if rule == "temporal_co_occurrence"
+                        )
+                })
+        };
+        assert!(linked(&nodes[0], &nodes[1]));
+        assert!(!linked(&nodes[0], &nodes[2]));
+        assert!(!linked(&nodes[1], &nodes[2]));
+    }
+
+    /// Example downstream rule: code nodes touching the same file are related.
+    struct SameFileRule;
+
+    impl LinkRule for SameFileRule {
+        fn evaluate(
+            &self,
+            node: &Node,
+            neighbor: &Node,
+            _score: f32,
+            _config: &SimilarityConfig,
+        ) -> Option<ProposedEdge> {
+            let file_of = |n: &Node| {
+                n.data
+                    .body
+                    .lines()
+                    .find_map(|l| l.strip_prefix("file: "))
+                    .map(str::to_owned)
+            };
+            let path = file_of(node)?;
+            if file_of(neighbor) != Some(path) {
+                return None;
+            }
+            Some(ProposedEdge {
+                from: node.id,
+                to: neighbor.id,
+                relation: Relation::new("same_file").unwrap(),
+                weight: 0.8,
+                provenance: EdgeProvenance::AutoStructural {
+                    rule: "same_file".into(),
+                },
+            })
+        }
+    }
+
+    #[test]
+    fn test_custom_link_rule_is_applied() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(temp_dir.path().join("custom.redb")).unwrap());
+        let vector_index = Arc::new(RwLock::new(HnswIndex::new(4)));
+
+        let mut nodes = Vec::new();
+        for (i, path) in ["src/lib.rs", "src/lib.rs", "src/main.rs"]
+            .into_iter()
+            .enumerate()
+        {
+            let mut node = test_node(&format!("Function {i}"));
+            node.data.body = format!("file: {path}\nfn handler() {{}}");
+            let mut embedding = vec![0.0; 4];
+            embedding[i] = 1.0;
+            vector_index
+                .write()
+                .unwrap()
+                .insert(node.id, &embedding)
+                .unwrap();
+            node.embedding = Some(embedding);
+            storage.put_node(&node).unwrap();
+            nodes.push(node);
+        }
+
+        let graph_version = Arc::new(AtomicU64::new(0));
+        let mut linker = AutoLinker::new(
+            storage.clone(),
+            Arc::new(GraphEngineImpl::new(storage.clone())),
+            vector_index,
+            Arc::new(FixedEmbedder(vec![1.0, 0.0, 0.0, 0.0])),
+            AutoLinkerConfig::new().with_legacy_rules_enabled(false),
+        )
+        .unwrap()
+        .with_rule(SameFileRule)
+        .with_graph_version(graph_version.clone());
+        linker.run_cycle().unwrap();
+        assert_eq!(graph_version.load(Ordering::Relaxed), 1);
+        // Nothing new to link, so no bump
+        linker.run_cycle().unwrap();
+        assert_eq!(graph_version.load(Ordering::Relaxed), 1);
+
+        let same_file = Relation::new("same_file").unwrap();
+        let same_file_pairs: Vec<(NodeId, NodeId)> = nodes
+            .iter()
+            .flat_map(|n| storage.edges_from(n.id).unwrap())
+            .filter(|e| e.relation == same_file)
+            .map(|e| (e.from, e.to))
+            .collect();
+        assert!(!same_file_pairs.is_empty());
+        for (from, to) in same_file_pairs {
+            let pair = [from, to];
+            assert!(pair.contains(&nodes[0].id) && pair.contains(&nodes[1].id));
+        }
+    }
+
+    #[test]
+    fn test_incremental_cycle_scans_only_changed_nodes() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(temp_dir.path().join("incr.redb")).unwrap());
+        let vector_index = Arc::new(RwLock::new(HnswIndex::new(4)));
+
+        let mut nodes = Vec::new();
+        for i in 0..3 {
+            let mut node = test_node(&format!("Topic {i}"));
+            let mut embedding = vec![0.0; 4];
+            embedding[i] = 1.0;
+            vector_index
+                .write()
+                .unwrap()
+                .insert(node.id, &embedding)
+                .unwrap();
+            node.embedding = Some(embedding);
+            storage.put_node(&node).unwrap();
+            nodes.push(node);
+        }
+
+        let mut linker = AutoLinker::new(
+            storage.clone(),
+            Arc::new(GraphEngineImpl::new(storage.clone())),
+            vector_index,
+            Arc::new(FixedEmbedder(vec![1.0, 0.0, 0.0, 0.0])),
+            AutoLinkerConfig::new(),
+        )
+        .unwrap();
+
+        linker.run_cycle().unwrap();
+        assert_eq!(linker.metrics().nodes_scanned, 3);
+
+        // Nothing changed: nothing to scan
+        linker.run_cycle().unwrap();
+        assert_eq!(linker.metrics().nodes_scanned, 0);
+        assert_eq!(linker.metrics().nodes_processed, 0);
+
+        // One edit: only that node is rescanned
+        let mut edited = nodes[1].clone();
+        edited.data.body = "Revised body".into();
+        edited.updated_at = Utc::now();
+        storage.put_node(&edited).unwrap();
+        linker.run_cycle().unwrap();
+        assert_eq!(linker.metrics().nodes_scanned, 1);
+
+        // A manual full cycle rescans everything
+        linker.run_full_cycle().unwrap();
+        assert_eq!(linker.metrics().nodes_scanned, 3);
+        linker.run_cycle().unwrap();
+        assert_eq!(linker.metrics().nodes_scanned, 0);
+    }
+
+    #[test]
+    fn test_preview_cycle_proposes_without_writing() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(temp_dir.path().join("preview.redb")).unwrap());
+        let vector_index = Arc::new(RwLock::new(HnswIndex::new(4)));
+
+        for i in 0..4 {
+            let angle = i as f32 * 0.7;
+            let mut node = test_node(&format!("Topic {i}"));
+            let embedding = vec![1.0, 0.3 * angle.cos(), 0.3 * angle.sin(), 0.0];
+            vector_index
+                .write()
+                .unwrap()
+                .insert(node.id, &embedding)
+                .unwrap();
+            node.embedding = Some(embedding);
+            storage.put_node(&node).unwrap();
+        }
+
+        let mut linker = AutoLinker::new(
+            storage.clone(),
+            Arc::new(GraphEngineImpl::new(storage.clone())),
+            vector_index,
+            Arc::new(FixedEmbedder(vec![1.0, 0.0, 0.0, 0.0])),
+            AutoLinkerConfig::new()
+                .with_similarity(SimilarityConfig::new().with_dedup_threshold(0.9999)),
+        )
+        .unwrap();
+        let cursor = linker.cursor();
+
+        let preview = linker.preview_cycle().unwrap();
+        assert!(!preview.is_empty());
+        assert!(preview.iter().any(|e| e.rule() == "similarity"));
+        assert_eq!(storage.stats().unwrap().edge_count, 0);
+        assert_eq!(linker.cursor(), cursor);
+
+        // Previewing is repeatable, and a real cycle then creates those edges
+        assert_eq!(linker.preview_cycle().unwrap().len(), preview.len());
+        linker.run_cycle().unwrap();
+        assert!(storage.stats().unwrap().edge_count > 0);
+    }
+
+    /// Run one cycle over an older, important but doubtful fact and a newer,
+    /// minor but confident fact that negates it. Returns both nodes as
+    /// stored afterwards.
+    fn resolve_contradiction(
+        strategy: ContradictionStrategy,
+    ) -> (TempDir, Arc<RedbStorage>, Node, Node) {
+        let temp_dir = TempDir::new().unwrap();
+        let storage =
+            Arc::new(RedbStorage::open(temp_dir.path().join("contradiction.redb")).unwrap());
+        let vector_index = Arc::new(RwLock::new(HnswIndex::new(4)));
+
+        let mut older = test_node("System online");
+        older.data.body = "The system is running".into();
+        older.importance = 0.9;
+        older.confidence = 0.3;
+        older.created_at -= chrono::Duration::hours(1);
+        let mut newer = test_node("System offline");
+        newer.data.body = "The system is not running".into();
+        newer.importance = 0.4;
+        newer.confidence = 0.8;
+        for (node, embedding) in [
+            (&mut older, vec![1.0, 0.2, 0.0, 0.0]),
+            (&mut newer, vec![1.0, 0.0, 0.2, 0.0]),
+        ] {
+            vector_index
+                .write()
+                .unwrap()
+                .insert(node.id, &embedding)
+                .unwrap();
+            node.embedding = Some(embedding);
+            storage.put_node(node).unwrap();
+        }
+
+        let mut linker = AutoLinker::new(
+            storage.clone(),
+            Arc::new(GraphEngineImpl::new(storage.clone())),
+            vector_index,
+            Arc::new(FixedEmbedder(vec![1.0, 0.0, 0.0, 0.0])),
+            AutoLinkerConfig::new()
+                .with_similarity(SimilarityConfig::new().with_dedup_threshold(0.9999))
+                .with_legacy_rules_enabled(false)
+                .with_contradiction_strategy(strategy)
+                .with_supersede_on_contradiction(true),
+        )
+        .unwrap();
+        linker.run_cycle().unwrap();
+        // A second cycle sees the demoted node again but must not demote twice
+        linker.run_cycle().unwrap();
+
+        assert!(!auto_edges(&storage, older.id, &Relation::new("contradicts").unwrap()).is_empty());
+        let older = storage.get_node(older.id).unwrap().unwrap();
+        let newer = storage.get_node(newer.id).unwrap().unwrap();
+        (temp_dir, storage, older, newer)
+    }
+
+    fn supersedes(storage: &RedbStorage, from: NodeId) -> Vec<NodeId> {
+        storage
+            .edges_from(from)
+            .unwrap()
+            .into_iter()
+            .filter(|e| e.relation.as_str() == "supersedes")
+            .map(|e| e.to)
+            .collect()
+    }
+
+    #[test]
+    fn test_flag_only_leaves_contradiction_unresolved() {
+        let (_dir, storage, older, newer) = resolve_contradiction(ContradictionStrategy::FlagOnly);
+        assert!(supersedes(&storage, older.id).is_empty());
+        assert!(supersedes(&storage, newer.id).is_empty());
+        assert_eq!(older.importance, 0.9);
+        assert_eq!(newer.importance, 0.4);
+    }
+
+    #[test]
+    fn test_keep_newest_supersedes_older_node() {
+        let (_dir, storage, older, newer) =
+            resolve_contradiction(ContradictionStrategy::KeepNewest);
+        assert_eq!(supersedes(&storage, newer.id), vec![older.id]);
+        assert!(supersedes(&storage, older.id).is_empty());
+        assert!((older.importance - 0.45).abs() < 1e-6);
+        assert_eq!(newer.importance, 0.4);
+    }
+
+    #[test]
+    fn test_keep_highest_importance_supersedes_minor_node() {
+        let (_dir, storage, older, newer) =
+            resolve_contradiction(ContradictionStrategy::KeepHighestImportance);
+        assert_eq!(supersedes(&storage, older.id), vec![newer.id]);
+        assert!(supersedes(&storage, newer.id).is_empty());
+        assert_eq!(older.importance, 0.9);
+        assert!((newer.importance - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_keep_highest_confidence_supersedes_doubtful_node() {
+        let (_dir, storage, older, newer) =
+            resolve_contradiction(ContradictionStrategy::KeepHighestConfidence);
+        assert_eq!(supersedes(&storage, newer.id), vec![older.id]);
+        assert!(supersedes(&storage, older.id).is_empty());
+        assert!((older.importance - 0.45).abs() < 1e-6);
+        assert_eq!(newer.confidence, 0.8);
+    }
 }
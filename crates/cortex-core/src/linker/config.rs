@@ -1,5 +1,5 @@
 use crate::error::{CortexError, Result};
-use crate::linker::rules::ProposedEdge;
+use crate::linker::rules::{ContradictionStrategy, ProposedEdge, TemporalCoOccurrenceRule};
 use crate::types::{EdgeProvenance, Node, NodeKind, Relation};
 use crate::vector::SimilarityConfig;
 use serde::{Deserialize, Serialize};
@@ -28,7 +28,9 @@ pub struct AutoLinkerConfig {
     /// Maximum edges to create per cycle. Safety valve. Default: 2000.
     pub max_edges_per_cycle: usize,
 
-    /// Maximum auto-edges per node. Generic content prevention. Default: 50.
+    /// Maximum auto-created edges of any one relation a node may hold
+    /// (either direction). Stops generic content becoming a hub. When full,
+    /// a new edge only lands by displacing the weakest one. Default: 50.
     pub max_edges_per_node: usize,
 
     /// Generic content detection threshold. If a node has this many neighbors
@@ -51,6 +53,21 @@ pub struct AutoLinkerConfig {
     /// Whether to run the hardcoded legacy structural rules.
     /// None = auto: true when no config rules, false when config rules exist.
     pub legacy_rules_enabled: Option<bool>,
+
+    /// Link same-session nodes created close together, regardless of
+    /// embedding similarity. Default: None (disabled).
+    pub co_occurrence: Option<TemporalCoOccurrenceRule>,
+
+    /// How detected contradictions are resolved. Default: KeepNewest.
+    pub contradiction_strategy: ContradictionStrategy,
+
+    /// Record a resolved contradiction as a `supersedes` edge from the
+    /// winner to the loser. Default: false (only the `contradicts` edge).
+    pub supersede_on_contradiction: bool,
+
+    /// Multiplier applied to the loser's importance when its `supersedes`
+    /// edge is created. 1.0 leaves importance alone. Default: 0.5.
+    pub loser_importance_factor: f32,
 }
 
 impl Default for AutoLinkerConfig {
@@ -69,6 +86,10 @@ impl Default for AutoLinkerConfig {
             embedding_model: "BAAI/bge-small-en-v1.5".into(),
             rules: Vec::new(),
             legacy_rules_enabled: None,
+            co_occurrence: None,
+            contradiction_strategy: ContradictionStrategy::default(),
+            supersede_on_contradiction: false,
+            loser_importance_factor: 0.5,
         }
     }
 }
@@ -103,6 +124,11 @@ impl AutoLinkerConfig {
         self
     }
 
+    pub fn with_max_edges_per_node(mut self, max: usize) -> Self {
+        self.max_edges_per_node = max;
+        self
+    }
+
     pub fn with_embedding_model(mut self, model: String) -> Self {
         self.embedding_model = model;
         self
@@ -118,6 +144,26 @@ impl AutoLinkerConfig {
         self
     }
 
+    pub fn with_co_occurrence(mut self, rule: TemporalCoOccurrenceRule) -> Self {
+        self.co_occurrence = Some(rule);
+        self
+    }
+
+    pub fn with_contradiction_strategy(mut self, strategy: ContradictionStrategy) -> Self {
+        self.contradiction_strategy = strategy;
+        self
+    }
+
+    pub fn with_supersede_on_contradiction(mut self, enabled: bool) -> Self {
+        self.supersede_on_contradiction = enabled;
+        self
+    }
+
+    pub fn with_loser_importance_factor(mut self, factor: f32) -> Self {
+        self.loser_importance_factor = factor;
+        self
+    }
+
     /// Whether legacy hardcoded structural rules should run.
     /// Auto-resolves: true if no config rules, false if config rules exist.
     pub fn use_legacy_rules(&self) -> bool {
@@ -146,6 +192,25 @@ impl AutoLinkerConfig {
             ));
         }
 
+        if !(0.0..=1.0).contains(&self.loser_importance_factor) {
+            return Err(CortexError::Validation(
+                "loser_importance_factor must be between 0.0 and 1.0".into(),
+            ));
+        }
+
+        if let Some(ref rule) = self.co_occurrence {
+            if rule.window <= chrono::Duration::zero() {
+                return Err(CortexError::Validation(
+                    "co_occurrence window must be > 0".into(),
+                ));
+            }
+            if !(0.0..=1.0).contains(&rule.max_weight) {
+                return Err(CortexError::Validation(
+                    "co_occurrence max_weight must be between 0.0 and 1.0".into(),
+                ));
+            }
+        }
+
         // Validate config rules
         let mut rule_names = HashSet::new();
         for rule in &self.rules {
@@ -291,17 +356,14 @@ impl ConfigRule {
     /// Validate this rule's configuration.
     pub fn validate(&self) -> Result<()> {
         if self.name.is_empty() {
-            return Err(CortexError::Validation(
-                "Rule name cannot be empty".into(),
-            ));
+            return Err(CortexError::Validation("Rule name cannot be empty".into()));
         }
         // Validate kinds and relation using the same rules as NodeKind/Relation
         NodeKind::new(&self.from_kind).map_err(|e| {
             CortexError::Validation(format!("Rule '{}' from_kind: {}", self.name, e))
         })?;
-        NodeKind::new(&self.to_kind).map_err(|e| {
-            CortexError::Validation(format!("Rule '{}' to_kind: {}", self.name, e))
-        })?;
+        NodeKind::new(&self.to_kind)
+            .map_err(|e| CortexError::Validation(format!("Rule '{}' to_kind: {}", self.name, e)))?;
         Relation::new(&self.relation).map_err(|e| {
             CortexError::Validation(format!("Rule '{}' relation: {}", self.name, e))
         })?;
@@ -427,53 +489,45 @@ impl RuleCondition {
     /// Validate this condition's parameters.
     pub fn validate(&self, rule_name: &str) -> Result<()> {
         match self {
-            Self::MinSimilarity { threshold } => {
-                if !(0.0..=1.0).contains(threshold) {
-                    return Err(CortexError::Validation(format!(
-                        "Rule '{}' min_similarity threshold must be between 0.0 and 1.0",
-                        rule_name
-                    )));
-                }
+            Self::MinSimilarity { threshold } if !(0.0..=1.0).contains(threshold) => {
+                return Err(CortexError::Validation(format!(
+                    "Rule '{}' min_similarity threshold must be between 0.0 and 1.0",
+                    rule_name
+                )));
             }
-            Self::SharedTags { min_shared } => {
-                if *min_shared == 0 {
-                    return Err(CortexError::Validation(format!(
-                        "Rule '{}' min_shared must be > 0",
-                        rule_name
-                    )));
-                }
+            Self::SharedTags { min_shared } if *min_shared == 0 => {
+                return Err(CortexError::Validation(format!(
+                    "Rule '{}' min_shared must be > 0",
+                    rule_name
+                )));
             }
-            Self::TemporalProximity { window_minutes } => {
-                if *window_minutes == 0 {
-                    return Err(CortexError::Validation(format!(
-                        "Rule '{}' window_minutes must be > 0",
-                        rule_name
-                    )));
-                }
+            Self::TemporalProximity { window_minutes } if *window_minutes == 0 => {
+                return Err(CortexError::Validation(format!(
+                    "Rule '{}' window_minutes must be > 0",
+                    rule_name
+                )));
             }
-            Self::BodyFieldRef { field, match_field } => {
-                if field.is_empty() || match_field.is_empty() {
-                    return Err(CortexError::Validation(format!(
-                        "Rule '{}' body_field_ref field and match_field cannot be empty",
-                        rule_name
-                    )));
-                }
+            Self::BodyFieldRef { field, match_field }
+                if field.is_empty() || match_field.is_empty() =>
+            {
+                return Err(CortexError::Validation(format!(
+                    "Rule '{}' body_field_ref field and match_field cannot be empty",
+                    rule_name
+                )));
             }
-            Self::BodyFieldContains { field, match_field } => {
-                if field.is_empty() || match_field.is_empty() {
-                    return Err(CortexError::Validation(format!(
-                        "Rule '{}' body_field_contains field and match_field cannot be empty",
-                        rule_name
-                    )));
-                }
+            Self::BodyFieldContains { field, match_field }
+                if field.is_empty() || match_field.is_empty() =>
+            {
+                return Err(CortexError::Validation(format!(
+                    "Rule '{}' body_field_contains field and match_field cannot be empty",
+                    rule_name
+                )));
             }
-            Self::TagReferencesTitle { tag_prefix } => {
-                if tag_prefix.is_empty() {
-                    return Err(CortexError::Validation(format!(
-                        "Rule '{}' tag_prefix cannot be empty",
-                        rule_name
-                    )));
-                }
+            Self::TagReferencesTitle { tag_prefix } if tag_prefix.is_empty() => {
+                return Err(CortexError::Validation(format!(
+                    "Rule '{}' tag_prefix cannot be empty",
+                    rule_name
+                )));
             }
             _ => {}
         }
@@ -33,8 +33,12 @@ impl<S: Storage> DecayEngine<S> {
         }
 
         for edge in all_edges {
-            // Skip manual edges if configured
-            if self.config.exempt_manual && matches!(edge.provenance, EdgeProvenance::Manual { .. })
+            // Skip manual edges (and their inverses) if configured
+            if self.config.exempt_manual
+                && matches!(
+                    edge.provenance,
+                    EdgeProvenance::Manual { .. } | EdgeProvenance::DerivedInverse { .. }
+                )
             {
                 continue;
             }
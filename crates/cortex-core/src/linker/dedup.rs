@@ -325,6 +325,12 @@ impl<S: Storage, V: VectorIndex, G: GraphEngine> DedupScanner<S, V, G> {
         tombstoned.updated_at = Utc::now();
         self.storage.put_node(&tombstoned)?;
 
+        // Filters see the merged tags and importance; the retired node
+        // leaves search
+        let mut vector_index = self.vector_index.write().unwrap();
+        vector_index.update_metadata(&updated_keep);
+        vector_index.remove(retire)?;
+
         Ok(())
     }
 }
@@ -11,10 +11,15 @@ pub struct AutoLinkerMetrics {
     /// Nodes processed this cycle.
     pub nodes_processed: u64,
 
+    /// Nodes found changed since the cursor this cycle. Anything beyond
+    /// `max_nodes_per_cycle` is left as backlog for the next cycle.
+    #[serde(default)]
+    pub nodes_scanned: u64,
+
     /// Edges created this cycle.
     pub edges_created: u64,
 
-    /// Edges pruned by decay this cycle.
+    /// Edges pruned by decay or displaced by the per-node cap this cycle.
     pub edges_pruned: u64,
 
     /// Edges deleted this cycle.
@@ -48,6 +53,7 @@ impl Default for AutoLinkerMetrics {
         Self {
             cycles: 0,
             nodes_processed: 0,
+            nodes_scanned: 0,
             edges_created: 0,
             edges_pruned: 0,
             edges_deleted: 0,
@@ -70,6 +76,7 @@ impl AutoLinkerMetrics {
     /// Reset per-cycle metrics (called at start of each cycle)
     pub fn reset_cycle_metrics(&mut self) {
         self.nodes_processed = 0;
+        self.nodes_scanned = 0;
         self.edges_created = 0;
         self.edges_pruned = 0;
         self.edges_deleted = 0;
@@ -97,6 +104,11 @@ impl AutoLinkerMetrics {
         self.nodes_processed += count;
     }
 
+    /// Add nodes scanned
+    pub fn add_nodes_scanned(&mut self, count: u64) {
+        self.nodes_scanned += count;
+    }
+
     /// Add edges created
     pub fn add_edges_created(&mut self, count: u64) {
         self.edges_created += count;
@@ -140,9 +152,10 @@ impl AutoLinkerMetrics {
     /// Get a summary string for logging
     pub fn summary(&self) -> String {
         format!(
-            "Cycle #{}: processed {} nodes, created {} edges, pruned {}, deleted {}, \
+            "Cycle #{}: scanned {} nodes, processed {}, created {} edges, pruned {}, deleted {}, \
              found {} duplicates, {} contradictions in {:?} | Backlog: {} | Total: {} nodes, {} edges",
             self.cycles,
+            self.nodes_scanned,
             self.nodes_processed,
             self.edges_created,
             self.edges_pruned,
@@ -24,6 +24,6 @@ pub use decay::DecayEngine;
 pub use dedup::{DedupAction, DedupScanner, DuplicatePair};
 pub use metrics::AutoLinkerMetrics;
 pub use rules::{
-    Contradiction, ContradictionDetector, LinkRule, ProposedEdge, Resolution, SimilarityLinkRule,
-    StructuralRule,
+    Contradiction, ContradictionDetector, ContradictionStrategy, LinkRule, ProposedEdge,
+    Resolution, SimilarityLinkRule, StructuralRule, TemporalCoOccurrenceRule,
 };
@@ -1,6 +1,7 @@
 use crate::types::{Edge, EdgeProvenance, Node, NodeId, Relation};
 use crate::vector::SimilarityConfig;
 use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 /// Proposed edge from link rule evaluation
@@ -14,6 +15,19 @@ pub struct ProposedEdge {
 }
 
 impl ProposedEdge {
+    /// Name of the rule that proposed this edge, for display.
+    pub fn rule(&self) -> &str {
+        match &self.provenance {
+            EdgeProvenance::AutoSimilarity { .. } => "similarity",
+            EdgeProvenance::AutoStructural { rule } => rule,
+            EdgeProvenance::AutoContradiction { .. } => "contradiction",
+            EdgeProvenance::AutoDedup { .. } => "dedup",
+            EdgeProvenance::Manual { .. } => "manual",
+            EdgeProvenance::Imported { .. } => "imported",
+            EdgeProvenance::DerivedInverse { .. } => "inverse",
+        }
+    }
+
     pub fn to_edge(self) -> Edge {
         Edge::new(
             self.from,
@@ -25,8 +39,38 @@ impl ProposedEdge {
     }
 }
 
-/// Trait for link rules that evaluate potential edges
-pub trait LinkRule {
+/// A rule that proposes an edge for a candidate node pair.
+///
+/// Built-in rules implement this, and library users can add their own with
+/// [`AutoLinker::with_rule`](crate::linker::AutoLinker::with_rule).
+///
+/// # Contract
+///
+/// The auto-linker calls `evaluate` once per cycle for each pair of:
+/// - `node`: a new or updated node from the backlog, and
+/// - `neighbor`: one of its nearest vector neighbours, which is never the
+///   node itself and never deleted.
+///
+/// `score` is their cosine similarity. `config` holds the linker's
+/// similarity thresholds.
+///
+/// Return `None` to propose nothing. Otherwise return a [`ProposedEdge`]:
+/// - `from` is `node.id` and `to` is `neighbor.id`, so edges point away
+///   from the newer node.
+/// - `weight` is in `0.0..=1.0`.
+/// - `provenance` is an `Auto*` variant, so decay, pruning and the
+///   per-node cap treat the edge as machine-made.
+///
+/// Weights do not combine. Rules run in a fixed order: similarity,
+/// structural, config, co-occurrence, then custom rules in registration
+/// order. The first proposal for a `(from, to, relation)` triple is the
+/// one stored. Later proposals for the same triple are dropped as
+/// duplicates, as are proposals matching an edge that already exists. A
+/// rule that wants to outrank a built-in should use its own relation.
+///
+/// Rules must be cheap and side-effect free. They run inside the linker
+/// cycle for up to 100 neighbours per backlog node.
+pub trait LinkRule: Send + Sync {
     fn evaluate(
         &self,
         node: &Node,
@@ -61,6 +105,65 @@ impl LinkRule for SimilarityLinkRule {
     }
 }
 
+/// Co-occurrence rule: nodes from the same session created close together
+/// usually belong to the same line of work (a decision and the observation
+/// that prompted it), even when their embeddings are far apart.
+/// Weight falls linearly from `max_weight` at zero gap to nothing at `window`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemporalCoOccurrenceRule {
+    pub window: Duration,
+    pub max_weight: f32,
+}
+
+impl Default for TemporalCoOccurrenceRule {
+    fn default() -> Self {
+        Self {
+            window: Duration::minutes(15),
+            max_weight: 0.5,
+        }
+    }
+}
+
+impl TemporalCoOccurrenceRule {
+    pub fn new(window: Duration, max_weight: f32) -> Self {
+        Self { window, max_weight }
+    }
+}
+
+impl LinkRule for TemporalCoOccurrenceRule {
+    fn evaluate(
+        &self,
+        node: &Node,
+        neighbor: &Node,
+        _score: f32,
+        _config: &SimilarityConfig,
+    ) -> Option<ProposedEdge> {
+        if node.id == neighbor.id
+            || node.source.session.is_none()
+            || node.source.session != neighbor.source.session
+        {
+            return None;
+        }
+
+        let gap = (node.created_at - neighbor.created_at).abs();
+        if gap >= self.window {
+            return None;
+        }
+
+        let closeness =
+            1.0 - gap.num_milliseconds() as f32 / self.window.num_milliseconds().max(1) as f32;
+        Some(ProposedEdge {
+            from: node.id,
+            to: neighbor.id,
+            relation: Relation::new("related_to").unwrap(),
+            weight: self.max_weight * closeness,
+            provenance: EdgeProvenance::AutoStructural {
+                rule: "temporal_co_occurrence".into(),
+            },
+        })
+    }
+}
+
 /// Structural rules based on metadata
 #[derive(Debug, Clone)]
 pub enum StructuralRule {
@@ -303,16 +406,53 @@ pub struct Contradiction {
 /// Suggested resolution for a contradiction
 #[derive(Debug, Clone)]
 pub enum Resolution {
-    /// Supersede: newer replaces older
+    /// Supersede: `keep` replaces `retire`
     Supersede { keep: NodeId, retire: NodeId },
 
     /// Manual review required
     ManualReview,
 }
 
+/// How the auto-linker settles a detected contradiction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContradictionStrategy {
+    /// Only create the `contradicts` edge and leave resolution to a human.
+    FlagOnly,
+    /// The more recently created node wins.
+    #[default]
+    KeepNewest,
+    /// The node with the higher importance wins. Ties go to the newer node.
+    KeepHighestImportance,
+    /// The node with the higher `confidence` wins. Ties go to the newer node.
+    KeepHighestConfidence,
+}
+
+impl ContradictionStrategy {
+    /// Pick a winner between two contradicting nodes.
+    pub fn resolve(&self, a: &Node, b: &Node) -> Resolution {
+        let a_wins = match self {
+            Self::FlagOnly => return Resolution::ManualReview,
+            Self::KeepNewest => None,
+            Self::KeepHighestImportance => a.importance.partial_cmp(&b.importance),
+            Self::KeepHighestConfidence => a.confidence.partial_cmp(&b.confidence),
+        }
+        .filter(|ord| ord.is_ne())
+        .map(|ord| ord.is_gt())
+        .unwrap_or(a.created_at > b.created_at);
+
+        let (keep, retire) = if a_wins { (a, b) } else { (b, a) };
+        Resolution::Supersede {
+            keep: keep.id,
+            retire: retire.id,
+        }
+    }
+}
+
 /// Detects contradictions between similar nodes
 pub struct ContradictionDetector {
     threshold: f32,
+    strategy: ContradictionStrategy,
 }
 
 impl Default for ContradictionDetector {
@@ -323,7 +463,15 @@ impl Default for ContradictionDetector {
 
 impl ContradictionDetector {
     pub fn new(threshold: f32) -> Self {
-        Self { threshold }
+        Self {
+            threshold,
+            strategy: ContradictionStrategy::default(),
+        }
+    }
+
+    pub fn with_strategy(mut self, strategy: ContradictionStrategy) -> Self {
+        self.strategy = strategy;
+        self
     }
 
     /// Check if two highly similar nodes contain contradictory information
@@ -334,21 +482,12 @@ impl ContradictionDetector {
 
         // Check for negation patterns
         if self.has_negation_pattern(a, b) {
-            let (newer, older) = if a.created_at > b.created_at {
-                (a, b)
-            } else {
-                (b, a)
-            };
-
             return Some(Contradiction {
                 node_a: a.id,
                 node_b: b.id,
                 similarity,
                 reason: "Negation pattern detected".into(),
-                suggested_resolution: Resolution::Supersede {
-                    keep: newer.id,
-                    retire: older.id,
-                },
+                suggested_resolution: self.strategy.resolve(a, b),
                 detected_at: Utc::now(),
             });
         }
@@ -420,6 +559,48 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_temporal_co_occurrence_rule() {
+        let rule = TemporalCoOccurrenceRule::new(Duration::minutes(10), 0.6);
+        let config = SimilarityConfig::default();
+        let in_session = |title: &str, session: &str, offset_mins: i64| {
+            let mut node = create_test_node(NodeKind::new("fact").unwrap(), title, "Body");
+            node.source.session = Some(session.into());
+            node.created_at += Duration::minutes(offset_mins);
+            node
+        };
+
+        let decision = in_session("Switch to redb", "s1", 0);
+        let observation = in_session("Sled compaction stalls", "s1", -5);
+        let other_session = in_session("Sled compaction stalls", "s2", -5);
+        let too_late = in_session("Unrelated follow-up", "s1", 10);
+
+        // Same session within window: edge, weight halved at half the window
+        let edge = rule
+            .evaluate(&decision, &observation, 0.0, &config)
+            .expect("same-session nodes 5 minutes apart should link");
+        assert_eq!(edge.relation, Relation::new("related_to").unwrap());
+        assert!((edge.weight - 0.3).abs() < 1e-3);
+
+        // Different session, outside window, or no session: no edge
+        assert!(rule
+            .evaluate(&decision, &other_session, 0.0, &config)
+            .is_none());
+        assert!(rule.evaluate(&decision, &too_late, 0.0, &config).is_none());
+        let mut sessionless = observation.clone();
+        sessionless.source.session = None;
+        let mut sessionless_decision = decision.clone();
+        sessionless_decision.source.session = None;
+        assert!(rule
+            .evaluate(&sessionless_decision, &sessionless, 0.0, &config)
+            .is_none());
+
+        // Closer in time means a stronger edge
+        let near = in_session("Benchmark results", "s1", -1);
+        let near_edge = rule.evaluate(&decision, &near, 0.0, &config).unwrap();
+        assert!(near_edge.weight > edge.weight);
+    }
+
     #[test]
     fn test_shared_tags_rule() {
         let rule = StructuralRule::shared_tags();
@@ -461,4 +642,53 @@ mod tests {
         assert_eq!(contradiction.similarity, 0.85);
         assert!(contradiction.reason.contains("Negation"));
     }
+
+    #[test]
+    fn test_contradiction_strategies_pick_winner() {
+        let mut older = create_test_node(NodeKind::new("fact").unwrap(), "Old", "Running");
+        older.created_at -= Duration::hours(1);
+        older.importance = 0.9;
+        let mut newer = create_test_node(NodeKind::new("fact").unwrap(), "New", "Not running");
+        newer.importance = 0.4;
+
+        let winner =
+            |strategy: ContradictionStrategy, a: &Node, b: &Node| match strategy.resolve(a, b) {
+                Resolution::Supersede { keep, retire } => {
+                    assert_ne!(keep, retire);
+                    Some(keep)
+                }
+                Resolution::ManualReview => None,
+            };
+
+        assert_eq!(
+            winner(ContradictionStrategy::FlagOnly, &older, &newer),
+            None
+        );
+        assert_eq!(
+            winner(ContradictionStrategy::KeepNewest, &older, &newer),
+            Some(newer.id)
+        );
+        assert_eq!(
+            winner(ContradictionStrategy::KeepHighestImportance, &newer, &older),
+            Some(older.id)
+        );
+
+        // Confidence ignores importance; equal confidence goes to the newer node
+        older.confidence = 0.6;
+        newer.confidence = 0.95;
+        assert_eq!(
+            winner(ContradictionStrategy::KeepHighestConfidence, &older, &newer),
+            Some(newer.id)
+        );
+        newer.confidence = 0.3;
+        assert_eq!(
+            winner(ContradictionStrategy::KeepHighestConfidence, &newer, &older),
+            Some(older.id)
+        );
+        older.confidence = 0.3;
+        assert_eq!(
+            winner(ContradictionStrategy::KeepHighestConfidence, &older, &newer),
+            Some(newer.id)
+        );
+    }
 }
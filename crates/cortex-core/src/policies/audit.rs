@@ -7,14 +7,15 @@ use uuid::Uuid;
 
 const AUDIT_TABLE: TableDefinition<u128, &[u8]> = TableDefinition::new("audit");
 
-/// A single record of a mutation event.
+/// A single record of a mutation event, or of a read when read auditing is on.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditEntry {
     /// When this action occurred.
     pub timestamp: DateTime<Utc>,
     /// What happened.
     pub action: AuditAction,
-    /// The node or edge that was affected.
+    /// The node or edge that was affected. Nil for reads without a single
+    /// subject (e.g. a search); the query lives in `details`.
     pub target_id: Uuid,
     /// Which agent or process caused this action.
     pub actor: String,
@@ -22,7 +23,7 @@ pub struct AuditEntry {
     pub details: Option<String>,
 }
 
-/// The type of mutation that was recorded.
+/// The type of event that was recorded.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum AuditAction {
     NodeCreated,
@@ -35,6 +36,16 @@ pub enum AuditAction {
     NodeMerged,
     BriefingGenerated,
     SchemaUpgraded,
+    /// Read: a similarity or hybrid search. Only recorded when read auditing is enabled.
+    Search,
+    /// Read: a graph traversal. Only recorded when read auditing is enabled.
+    Traverse,
+    /// Read: a briefing served to an agent. Only recorded when read auditing is enabled.
+    Briefing,
+    /// A node from a trusted agent skipped write gate checks.
+    GateBypassed,
+    /// A soft-deleted node was brought back.
+    NodeRestored,
 }
 
 impl std::fmt::Display for AuditAction {
@@ -50,6 +61,40 @@ impl std::fmt::Display for AuditAction {
             AuditAction::NodeMerged => write!(f, "node.merged"),
             AuditAction::BriefingGenerated => write!(f, "briefing.generated"),
             AuditAction::SchemaUpgraded => write!(f, "schema.upgraded"),
+            AuditAction::Search => write!(f, "search"),
+            AuditAction::Traverse => write!(f, "traverse"),
+            AuditAction::Briefing => write!(f, "briefing"),
+            AuditAction::GateBypassed => write!(f, "gate.bypassed"),
+            AuditAction::NodeRestored => write!(f, "node.restored"),
+        }
+    }
+}
+
+impl std::str::FromStr for AuditAction {
+    type Err = crate::CortexError;
+
+    /// Parse the `Display` form (`node.created`, `search`, ...).
+    fn from_str(s: &str) -> crate::Result<Self> {
+        match s {
+            "node.created" => Ok(AuditAction::NodeCreated),
+            "node.updated" => Ok(AuditAction::NodeUpdated),
+            "node.deleted" => Ok(AuditAction::NodeDeleted),
+            "node.hard_deleted" => Ok(AuditAction::NodeHardDeleted),
+            "edge.created" => Ok(AuditAction::EdgeCreated),
+            "edge.decayed" => Ok(AuditAction::EdgeDecayed),
+            "edge.pruned" => Ok(AuditAction::EdgePruned),
+            "node.merged" => Ok(AuditAction::NodeMerged),
+            "briefing.generated" => Ok(AuditAction::BriefingGenerated),
+            "schema.upgraded" => Ok(AuditAction::SchemaUpgraded),
+            "search" => Ok(AuditAction::Search),
+            "traverse" => Ok(AuditAction::Traverse),
+            "briefing" => Ok(AuditAction::Briefing),
+            "gate.bypassed" => Ok(AuditAction::GateBypassed),
+            "node.restored" => Ok(AuditAction::NodeRestored),
+            other => Err(crate::CortexError::Validation(format!(
+                "Unknown audit action '{}'",
+                other
+            ))),
         }
     }
 }
@@ -95,8 +140,69 @@ impl AuditLog {
         Ok(())
     }
 
+    /// Record a read (search, traverse, briefing). Fire-and-forget: failures
+    /// are logged rather than returned so reads never fail on audit errors.
+    pub fn log_read(
+        &self,
+        action: AuditAction,
+        actor: &str,
+        target_id: Uuid,
+        details: serde_json::Value,
+    ) {
+        let entry = AuditEntry {
+            timestamp: Utc::now(),
+            action,
+            target_id,
+            actor: actor.to_string(),
+            details: Some(details.to_string()),
+        };
+        if let Err(e) = self.log(entry) {
+            log::error!("Audit log write failed: {}", e);
+        }
+    }
+
     /// Query audit entries with optional filters.
     pub fn query(&self, filter: AuditFilter) -> crate::Result<Vec<AuditEntry>> {
+        let mut entries = Vec::new();
+        self.for_each_matching(&filter, |entry| {
+            entries.push(entry);
+            Ok(())
+        })?;
+        Ok(entries)
+    }
+
+    /// Stream every matching entry to `writer` as newline-delimited JSON, one
+    /// self-contained record per line. Entries are written as they are read,
+    /// so memory use does not grow with the size of the log.
+    /// Returns the number of entries written.
+    pub fn export<W: std::io::Write>(
+        &self,
+        mut writer: W,
+        filter: AuditFilter,
+    ) -> crate::Result<usize> {
+        let mut written = 0;
+        self.for_each_matching(&filter, |entry| {
+            serde_json::to_writer(&mut writer, &entry)
+                .map_err(|e| crate::CortexError::Validation(format!("Audit export: {}", e)))?;
+            writer
+                .write_all(b"\n")
+                .map_err(|e| crate::CortexError::Validation(format!("Audit export: {}", e)))?;
+            written += 1;
+            Ok(())
+        })?;
+        writer
+            .flush()
+            .map_err(|e| crate::CortexError::Validation(format!("Audit export: {}", e)))?;
+        Ok(written)
+    }
+
+    /// Walk entries in time order from `filter.since`, calling `f` for each one
+    /// that matches, until `filter.limit` is reached.
+    fn for_each_matching(
+        &self,
+        filter: &AuditFilter,
+        mut f: impl FnMut(AuditEntry) -> crate::Result<()>,
+    ) -> crate::Result<()> {
         let read_txn = self
             .db
             .begin_read()
@@ -105,15 +211,16 @@ impl AuditLog {
             .open_table(AUDIT_TABLE)
             .map_err(|e| crate::CortexError::Validation(format!("Audit table: {}", e)))?;
 
-        let since_nanos = filter
+        // Keys are `(nanos << 32) | seq` (see `log`), so shift to compare.
+        let since_key = filter
             .since
             .and_then(|t| t.timestamp_nanos_opt())
-            .map(|n| n as u128)
+            .map(|n| (n as u128) << 32)
             .unwrap_or(0);
 
-        let mut entries = Vec::new();
+        let mut matched = 0;
         for result in table
-            .range(since_nanos..)
+            .range(since_key..)
             .map_err(|e| crate::CortexError::Validation(format!("Audit range: {}", e)))?
         {
             let (_, value) =
@@ -139,15 +246,16 @@ impl AuditLog {
                 }
             }
 
-            entries.push(entry);
+            f(entry)?;
+            matched += 1;
             if let Some(limit) = filter.limit {
-                if entries.len() >= limit {
+                if matched >= limit {
                     break;
                 }
             }
         }
 
-        Ok(entries)
+        Ok(())
     }
 }
 
@@ -243,6 +351,53 @@ mod tests {
         assert_eq!(entries[0].action, AuditAction::NodeCreated);
     }
 
+    #[test]
+    fn test_log_read_queryable_by_action_and_actor() {
+        let (log, _dir) = make_audit_log();
+        log.log(make_entry(AuditAction::NodeCreated, "kai"))
+            .unwrap();
+        log.log_read(
+            AuditAction::Search,
+            "kai",
+            Uuid::nil(),
+            serde_json::json!({ "query": "deploy plan" }),
+        );
+        log.log_read(
+            AuditAction::Search,
+            "dutybound",
+            Uuid::nil(),
+            serde_json::json!({ "query": "budget" }),
+        );
+
+        let entries = log
+            .query(AuditFilter {
+                actor: Some("kai".to_string()),
+                action: Some("search".parse().unwrap()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, AuditAction::Search);
+        let details: serde_json::Value =
+            serde_json::from_str(entries[0].details.as_deref().unwrap()).unwrap();
+        assert_eq!(details["query"], "deploy plan");
+    }
+
+    #[test]
+    fn test_action_from_str_round_trips_display() {
+        for action in [
+            AuditAction::NodeCreated,
+            AuditAction::NodeHardDeleted,
+            AuditAction::BriefingGenerated,
+            AuditAction::Search,
+            AuditAction::Traverse,
+            AuditAction::Briefing,
+        ] {
+            assert_eq!(action.to_string().parse::<AuditAction>().unwrap(), action);
+        }
+        assert!("bogus".parse::<AuditAction>().is_err());
+    }
+
     #[test]
     fn test_query_filter_by_node_id() {
         let (log, _dir) = make_audit_log();
@@ -268,6 +423,48 @@ mod tests {
         assert_eq!(entries[0].target_id, target);
     }
 
+    #[test]
+    fn test_export_writes_one_record_per_line() {
+        let (log, _dir) = make_audit_log();
+        let old = AuditEntry {
+            timestamp: Utc::now() - chrono::Duration::days(40),
+            ..make_entry(AuditAction::NodeDeleted, "kai")
+        };
+        log.log(old).unwrap();
+        let created = AuditEntry {
+            details: Some("title: Deploy plan".into()),
+            ..make_entry(AuditAction::NodeCreated, "kai")
+        };
+        log.log(created.clone()).unwrap();
+        log.log(make_entry(AuditAction::EdgeCreated, "auto-linker"))
+            .unwrap();
+
+        let mut buf = Vec::new();
+        let written = log
+            .export(
+                &mut buf,
+                AuditFilter {
+                    since: Some(Utc::now() - chrono::Duration::days(30)),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(written, 2);
+
+        let lines: Vec<AuditEntry> = String::from_utf8(buf)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].action, AuditAction::NodeCreated);
+        assert_eq!(lines[0].actor, "kai");
+        assert_eq!(lines[0].target_id, created.target_id);
+        assert_eq!(lines[0].timestamp, created.timestamp);
+        assert_eq!(lines[0].details.as_deref(), Some("title: Deploy plan"));
+        assert_eq!(lines[1].action, AuditAction::EdgeCreated);
+    }
+
     #[test]
     fn test_query_limit() {
         let (log, _dir) = make_audit_log();
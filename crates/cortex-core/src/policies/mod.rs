@@ -2,4 +2,7 @@ pub mod audit;
 pub mod retention;
 
 pub use audit::{AuditAction, AuditEntry, AuditFilter, AuditLog};
-pub use retention::{KindRetention, RetentionConfig, RetentionEngine, RetentionMaxNodes};
+pub use retention::{
+    restore_archive, ArchiveRecord, Eviction, EvictionMode, EvictionReason, KindRetention,
+    RestoreSummary, RetentionConfig, RetentionEngine, RetentionMaxNodes, PINNED_TAG,
+};
@@ -1,11 +1,12 @@
 use crate::error::{CortexError, Result};
 use crate::storage::{NodeFilter, Storage};
-use crate::types::{Node, NodeId, NodeKind};
+use crate::types::{Edge, Node, NodeId, NodeKind};
 use crate::vector::{apply_score_decay, ScoreDecayConfig};
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use uuid::Uuid;
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 
 /// Per-kind retention settings.
 ///
@@ -80,18 +81,193 @@ pub struct RetentionConfig {
     pub max_nodes: Option<RetentionMaxNodes>,
     /// Days of inactivity (since last access) required beyond TTL before deletion.
     /// Default: 30. A node accessed within this window survives even past TTL.
+    /// Also how long a soft-deleted node stays restorable before it is purged.
     #[serde(default = "default_grace_days")]
     pub grace_days: u64,
     /// Don't soft-delete a node if live (non-deleted) nodes still reference it
     /// via inbound edges. Default: true.
     #[serde(default = "default_true")]
     pub protect_with_inbound_edges: bool,
+    /// Hard per-kind time-to-live, e.g. `observation = "30d"`. Unlike `by_kind`,
+    /// there is no grace period, score gate, or edge protection: a node older
+    /// than its kind's TTL is removed by `evict_expired`.
+    /// Units: `s`, `m`, `h`, `d`, `w` (combinable, e.g. `"1d12h"`).
+    #[serde(default)]
+    pub expire_after: HashMap<String, String>,
+    /// Nodes at or above this importance never expire via `expire_after`.
+    /// `None` = 0.8.
+    #[serde(default)]
+    pub expire_exempt_importance: Option<f32>,
+    /// Nodes carrying any of these tags are never removed by retention —
+    /// neither TTL expiry nor max-nodes eviction. The reserved [`PINNED_TAG`]
+    /// is always protected and need not be listed.
+    #[serde(default)]
+    pub protected_tags: Vec<String>,
+    /// What happens to evicted nodes. Default: soft-delete.
+    #[serde(default)]
+    pub eviction: EvictionMode,
+}
+
+/// How `RetentionEngine` removes nodes it has decided to evict.
+///
+/// ```toml
+/// [retention.eviction]
+/// mode = "archive"
+/// path = "/var/lib/cortex/archive.jsonl"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum EvictionMode {
+    /// Soft-delete the node and drop its outbound edges.
+    #[default]
+    Delete,
+    /// Append the node and all its edges to a JSONL archive, then hard-delete
+    /// it. Restore with [`restore_archive`] / `cortex restore-archive`.
+    Archive { path: PathBuf },
+}
+
+/// Why a node was evicted.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionReason {
+    /// Past the node's own `expires_at`.
+    ExpiresAt,
+    /// Older than its kind's `expire_after` TTL.
+    Expired,
+    /// Past its `by_kind` / `default_ttl_days` TTL and all retention conditions.
+    Ttl,
+    /// Evicted to bring the graph back under `max_nodes`.
+    MaxNodes,
+}
+
+/// One line of an eviction archive: the node, every edge touching it, and
+/// why and when it was evicted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveRecord {
+    pub node: Node,
+    pub edges: Vec<Edge>,
+    pub reason: EvictionReason,
+    pub archived_at: DateTime<Utc>,
+}
+
+/// Counts returned by [`restore_archive`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestoreSummary {
+    pub nodes: usize,
+    pub edges: usize,
+    /// Edges skipped because an endpoint is missing or the edge already exists.
+    pub skipped_edges: usize,
+}
+
+/// Re-insert every node and edge from an eviction archive.
+///
+/// Nodes are written first so edges between two archived nodes resolve.
+/// Edges whose endpoints are no longer present are skipped.
+pub fn restore_archive<S: Storage>(storage: &S, path: &Path) -> Result<RestoreSummary> {
+    let file = std::fs::File::open(path).map_err(|e| {
+        CortexError::Validation(format!("open archive {} failed: {}", path.display(), e))
+    })?;
+
+    let mut records = Vec::new();
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line =
+            line.map_err(|e| CortexError::Validation(format!("read archive failed: {}", e)))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: ArchiveRecord = serde_json::from_str(&line).map_err(|e| {
+            CortexError::Validation(format!("archive line {}: invalid record: {}", i + 1, e))
+        })?;
+        records.push(record);
+    }
+
+    let mut summary = RestoreSummary::default();
+    for record in &records {
+        storage.put_node(&record.node)?;
+        summary.nodes += 1;
+    }
+
+    let mut seen = HashSet::new();
+    for edge in records.iter().flat_map(|r| &r.edges) {
+        if !seen.insert(edge.id) {
+            continue;
+        }
+        let endpoints_live = storage.get_node(edge.from)?.is_some_and(|n| !n.deleted)
+            && storage.get_node(edge.to)?.is_some_and(|n| !n.deleted);
+        if !endpoints_live || storage.get_edge(edge.id)?.is_some() {
+            summary.skipped_edges += 1;
+            continue;
+        }
+        match storage.put_edge(edge) {
+            Ok(()) => summary.edges += 1,
+            Err(CortexError::DuplicateEdge { .. }) => summary.skipped_edges += 1,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(summary)
 }
 
+/// Reserved tag that exempts a node from every retention pass.
+pub const PINNED_TAG: &str = "pinned";
+
 fn default_grace_days() -> u64 {
     30
 }
 
+const DEFAULT_EXPIRE_EXEMPT_IMPORTANCE: f32 = 0.8;
+
+/// Parse a TTL like `"30d"`, `"12h"` or `"1d12h"` into a duration.
+/// The result is always positive.
+pub fn parse_ttl(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let invalid = || {
+        CortexError::Validation(format!(
+            "Cannot parse TTL '{}': expected format like '30d', '12h', '1d12h'",
+            s
+        ))
+    };
+    if s.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut remaining = s;
+    let mut total_seconds: i64 = 0;
+    while !remaining.is_empty() {
+        let split_at = remaining
+            .find(|c: char| c.is_alphabetic())
+            .ok_or_else(invalid)?;
+        let digits = &remaining[..split_at];
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let num: i64 = digits.parse().map_err(|_| invalid())?;
+
+        let rest = &remaining[split_at..];
+        let unit_end = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let unit_seconds = match &rest[..unit_end] {
+            "s" => 1,
+            "m" => 60,
+            "h" => 3600,
+            "d" => 86400,
+            "w" => 7 * 86400,
+            _ => return Err(invalid()),
+        };
+        total_seconds = num
+            .checked_mul(unit_seconds)
+            .and_then(|secs| total_seconds.checked_add(secs))
+            .ok_or_else(invalid)?;
+        remaining = &rest[unit_end..];
+    }
+
+    if total_seconds <= 0 {
+        return Err(invalid());
+    }
+    Duration::try_seconds(total_seconds).ok_or_else(invalid)
+}
+
 fn default_true() -> bool {
     true
 }
@@ -118,15 +294,22 @@ impl RetentionEngine {
     }
 
     /// Check whether a single node is eligible for conditional deletion.
-    /// All conditions must be true for the node to be deletable.
+    /// All conditions must be true for the node to be deletable. Nodes in
+    /// `evicting` count as already gone when checking inbound edges.
     fn should_delete<S: Storage>(
         &self,
         node: &Node,
         kind_retention: &KindRetention,
         storage: &S,
+        evicting: &HashSet<NodeId>,
     ) -> Result<bool> {
         let now = Utc::now();
 
+        // 0. Pinned / protected-tag nodes are kept regardless of age
+        if self.is_protected(node) {
+            return Ok(false);
+        }
+
         // 1. Age exceeds TTL
         let age_days = (now - node.created_at).num_days();
         if age_days <= kind_retention.ttl_days as i64 {
@@ -154,6 +337,9 @@ impl RetentionEngine {
             let inbound = storage.edges_to(node.id)?;
             for edge in &inbound {
                 // Check if the source node is still alive
+                if evicting.contains(&edge.from) {
+                    continue;
+                }
                 if let Some(source) = storage.get_node(edge.from)? {
                     if !source.deleted {
                         return Ok(false);
@@ -175,13 +361,140 @@ impl RetentionEngine {
         Ok(())
     }
 
-    /// Soft-delete nodes that have exceeded their TTL and meet all retention conditions,
+    /// True if the node carries `pinned` or one of the configured protected tags.
+    fn is_protected(&self, node: &Node) -> bool {
+        self.is_protected_tags(&node.data.tags)
+    }
+
+    fn is_protected_tags(&self, tags: &[String]) -> bool {
+        tags.iter()
+            .any(|t| t == PINNED_TAG || self.config.protected_tags.contains(t))
+    }
+
+    /// Remove a node according to the configured `EvictionMode`.
+    fn evict<S: Storage>(&self, node: &Node, reason: EvictionReason, storage: &S) -> Result<()> {
+        match &self.config.eviction {
+            EvictionMode::Delete => {
+                self.cleanup_outbound_edges(node.id, storage)?;
+                storage.delete_node(node.id)
+            }
+            EvictionMode::Archive { path } => {
+                let mut edges = storage.edges_from(node.id)?;
+                edges.extend(storage.edges_to(node.id)?);
+                let record = ArchiveRecord {
+                    node: node.clone(),
+                    edges,
+                    reason,
+                    archived_at: Utc::now(),
+                };
+                Self::append_archive(path, &record)?;
+                storage.hard_delete_node(node.id)
+            }
+        }
+    }
+
+    fn append_archive(path: &Path, record: &ArchiveRecord) -> Result<()> {
+        let line = serde_json::to_string(record)
+            .map_err(|e| CortexError::Validation(format!("archive serialize failed: {}", e)))?;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| CortexError::Validation(format!("create_dir_all failed: {}", e)))?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| {
+                CortexError::Validation(format!("open archive {} failed: {}", path.display(), e))
+            })?;
+        // Flush before the node is removed so a crash never loses it
+        writeln!(file, "{}", line)
+            .and_then(|_| file.sync_data())
+            .map_err(|e| CortexError::Validation(format!("write archive failed: {}", e)))
+    }
+
+    /// Evict nodes past their own `expires_at`, or older than their kind's
+    /// `expire_after` TTL.
+    ///
+    /// Nodes carrying a protected tag are kept, as are nodes at or above
+    /// `expire_exempt_importance` that only hit a kind TTL. Returns the
+    /// number of nodes evicted.
+    pub fn evict_expired<S: Storage>(&self, storage: &S) -> Result<usize> {
+        let mut planned = Vec::new();
+        self.plan_expired(storage, &mut planned)?;
+        self.apply(storage, &planned)
+    }
+
+    fn plan_expired<S: Storage>(&self, storage: &S, planned: &mut Vec<Eviction>) -> Result<()> {
+        let now = Utc::now();
+        let exempt_importance = self
+            .config
+            .expire_exempt_importance
+            .unwrap_or(DEFAULT_EXPIRE_EXEMPT_IMPORTANCE);
+
+        // A node's own expiry was set on purpose, so importance doesn't save it
+        for header in storage.list_node_headers(NodeFilter::new())? {
+            if !header.is_expired_at(now) || self.is_protected_tags(&header.tags) {
+                continue;
+            }
+            if let Some(node) = storage.get_node(header.id)? {
+                planned.push(Eviction {
+                    node,
+                    reason: EvictionReason::ExpiresAt,
+                });
+            }
+        }
+        let mut evicting: HashSet<NodeId> = planned.iter().map(|e| e.node.id).collect();
+
+        for (kind_str, ttl) in &self.config.expire_after {
+            let ttl = parse_ttl(ttl)?;
+            let kind = match NodeKind::new(kind_str) {
+                Ok(k) => k,
+                Err(_) => continue, // skip invalid kind strings in config
+            };
+            // Nothing can be older than the earliest representable time.
+            let Some(cutoff) = now.checked_sub_signed(ttl) else {
+                continue;
+            };
+            let expired = storage.list_nodes(
+                NodeFilter::new()
+                    .with_kinds(vec![kind])
+                    .created_before(cutoff),
+            )?;
+            for node in expired {
+                if node.importance >= exempt_importance
+                    || self.is_protected(&node)
+                    || !evicting.insert(node.id)
+                {
+                    continue;
+                }
+                planned.push(Eviction {
+                    node,
+                    reason: EvictionReason::Expired,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Evict nodes that have exceeded their TTL and meet all retention conditions,
     /// or breach the max-nodes cap.
-    /// Returns the number of nodes soft-deleted this sweep.
+    /// Returns the number of nodes evicted this sweep.
     pub fn sweep<S: Storage>(&self, storage: &S) -> Result<usize> {
-        let mut deleted = 0;
+        let planned = self.plan(storage)?;
+        self.apply(storage, &planned)
+    }
+
+    /// Decide what a [`sweep`](Self::sweep) would evict, without changing
+    /// anything. Each node appears once, with the first rule that caught it.
+    pub fn plan<S: Storage>(&self, storage: &S) -> Result<Vec<Eviction>> {
         let now = Utc::now();
 
+        // 0. Node expiry and hard per-kind TTLs (no grace / score / edge conditions)
+        let mut planned = Vec::new();
+        self.plan_expired(storage, &mut planned)?;
+        let mut evicting: HashSet<NodeId> = planned.iter().map(|e| e.node.id).collect();
+
         // 1. Per-kind TTLs with conditional checks
         for (kind_str, kind_retention) in &self.config.by_kind {
             if kind_retention.ttl_days == 0 {
@@ -198,10 +511,15 @@ impl RetentionEngine {
                     .created_before(cutoff),
             )?;
             for node in candidates {
-                if self.should_delete(&node, kind_retention, storage)? {
-                    self.cleanup_outbound_edges(node.id, storage)?;
-                    storage.delete_node(node.id)?;
-                    deleted += 1;
+                if evicting.contains(&node.id) {
+                    continue;
+                }
+                if self.should_delete(&node, kind_retention, storage, &evicting)? {
+                    evicting.insert(node.id);
+                    planned.push(Eviction {
+                        node,
+                        reason: EvictionReason::Ttl,
+                    });
                 }
             }
         }
@@ -217,33 +535,46 @@ impl RetentionEngine {
             for node in expired {
                 let kind_str = node.kind.as_str().to_string();
                 // Skip kinds with explicit config (already handled above, or kept forever at 0)
-                if self.config.by_kind.contains_key(&kind_str) {
+                if self.config.by_kind.contains_key(&kind_str) || evicting.contains(&node.id) {
                     continue;
                 }
-                if self.should_delete(&node, &default_retention, storage)? {
-                    self.cleanup_outbound_edges(node.id, storage)?;
-                    storage.delete_node(node.id)?;
-                    deleted += 1;
+                if self.should_delete(&node, &default_retention, storage, &evicting)? {
+                    evicting.insert(node.id);
+                    planned.push(Eviction {
+                        node,
+                        reason: EvictionReason::Ttl,
+                    });
                 }
             }
         }
 
         // 3. Max node cap (unchanged — eviction is immediate, not conditional)
         if let Some(max_cfg) = &self.config.max_nodes {
-            let stats = storage.stats()?;
-            if stats.node_count as usize > max_cfg.limit {
-                let excess = stats.node_count as usize - max_cfg.limit;
-                let to_evict =
-                    self.select_eviction_candidates(storage, excess, &max_cfg.strategy)?;
-                for id in to_evict {
-                    self.cleanup_outbound_edges(id, storage)?;
-                    storage.delete_node(id)?;
-                    deleted += 1;
-                }
+            let live = (storage.stats()?.node_count as usize).saturating_sub(evicting.len());
+            if live > max_cfg.limit {
+                let to_evict = self.select_eviction_candidates(
+                    storage,
+                    live - max_cfg.limit,
+                    &max_cfg.strategy,
+                    &evicting,
+                )?;
+                planned.extend(to_evict.into_iter().map(|node| Eviction {
+                    node,
+                    reason: EvictionReason::MaxNodes,
+                }));
             }
         }
 
-        Ok(deleted)
+        Ok(planned)
+    }
+
+    /// Evict every node in `planned`, as returned by [`plan`](Self::plan).
+    /// Returns the number of nodes evicted.
+    pub fn apply<S: Storage>(&self, storage: &S, planned: &[Eviction]) -> Result<usize> {
+        for eviction in planned {
+            self.evict(&eviction.node, eviction.reason, storage)?;
+        }
+        Ok(planned.len())
     }
 
     /// Hard-delete nodes that have been soft-deleted beyond the grace period.
@@ -256,12 +587,15 @@ impl RetentionEngine {
         };
         let cutoff = Utc::now() - Duration::days(grace as i64);
 
-        // Only fetch soft-deleted nodes updated before the grace cutoff
-        let candidates =
-            storage.list_nodes(NodeFilter::new().deleted_only().updated_before(cutoff))?;
+        // The grace period runs from the delete, not the last edit. Tombstones
+        // from before `deleted_at` existed fall back to `updated_at`.
+        let candidates = storage.list_node_headers(NodeFilter::new().deleted_only())?;
         let mut purged = 0;
-        for node in candidates {
-            storage.hard_delete_node(node.id)?;
+        for header in candidates {
+            if header.deleted_at.unwrap_or(header.updated_at) > cutoff {
+                continue;
+            }
+            storage.hard_delete_node(header.id)?;
             purged += 1;
         }
         Ok(purged)
@@ -272,17 +606,24 @@ impl RetentionEngine {
         storage: &S,
         count: usize,
         strategy: &str,
-    ) -> Result<Vec<Uuid>> {
+        evicting: &HashSet<NodeId>,
+    ) -> Result<Vec<Node>> {
         match strategy {
             "oldest_lowest_importance" => {
-                let mut nodes = storage.list_nodes(NodeFilter::new())?;
-                nodes.sort_by(|a, b| {
+                let mut headers = storage.list_node_headers(NodeFilter::new())?;
+                headers.retain(|h| !self.is_protected_tags(&h.tags) && !evicting.contains(&h.id));
+                headers.sort_by(|a, b| {
                     a.importance
                         .partial_cmp(&b.importance)
                         .unwrap_or(std::cmp::Ordering::Equal)
                         .then(a.created_at.cmp(&b.created_at))
                 });
-                Ok(nodes.into_iter().take(count).map(|n| n.id).collect())
+                headers.truncate(count);
+                let mut nodes = Vec::with_capacity(headers.len());
+                for header in headers {
+                    nodes.extend(storage.get_node(header.id)?);
+                }
+                Ok(nodes)
             }
             _ => Err(CortexError::Validation(format!(
                 "Unknown eviction strategy: {}",
@@ -292,6 +633,13 @@ impl RetentionEngine {
     }
 }
 
+/// A node [`RetentionEngine::plan`] selected for eviction, and why.
+#[derive(Debug, Clone)]
+pub struct Eviction {
+    pub node: Node,
+    pub reason: EvictionReason,
+}
+
 /// Represents a node that has been soft-deleted and is eligible for hard deletion.
 #[derive(Debug)]
 pub struct PendingPurge {
@@ -451,6 +799,120 @@ mod tests {
         assert!(!storage.get_node(high.id).unwrap().unwrap().deleted);
     }
 
+    fn over_quota_engine(storage: &RedbStorage, nodes: usize, limit: usize) -> RetentionEngine {
+        for (i, importance) in [0.1, 0.2, 0.3, 0.4, 0.5]
+            .into_iter()
+            .take(nodes)
+            .enumerate()
+        {
+            let mut node = make_node("fact", importance);
+            node.created_at = Utc::now() - Duration::days(40);
+            // An old expired observation: counted once, under its first rule
+            if i == 0 {
+                node.kind = NodeKind::new("observation").unwrap();
+            }
+            storage.put_node(&node).unwrap();
+        }
+        let config = RetentionConfig {
+            max_nodes: Some(RetentionMaxNodes {
+                limit,
+                strategy: "oldest_lowest_importance".to_string(),
+            }),
+            expire_after: HashMap::from([("observation".to_string(), "30d".to_string())]),
+            ..Default::default()
+        };
+        RetentionEngine::new(config, default_score_decay())
+    }
+
+    #[test]
+    fn test_plan_is_read_only() {
+        let (storage, _dir) = make_storage();
+        let engine = over_quota_engine(&storage, 5, 2);
+
+        let planned = engine.plan(storage.as_ref()).unwrap();
+        let reasons: Vec<EvictionReason> = planned.iter().map(|e| e.reason).collect();
+        assert_eq!(
+            reasons,
+            [
+                EvictionReason::Expired,
+                EvictionReason::MaxNodes,
+                EvictionReason::MaxNodes
+            ]
+        );
+        let ids: HashSet<NodeId> = planned.iter().map(|e| e.node.id).collect();
+        assert_eq!(ids.len(), 3, "no node is planned twice");
+
+        // A dry run leaves the graph untouched
+        assert_eq!(storage.stats().unwrap().node_count, 5);
+        assert_eq!(engine.plan(storage.as_ref()).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_apply_plan_drops_to_max_nodes() {
+        let (storage, _dir) = make_storage();
+        let engine = over_quota_engine(&storage, 5, 2);
+
+        let planned = engine.plan(storage.as_ref()).unwrap();
+        assert_eq!(engine.apply(storage.as_ref(), &planned).unwrap(), 3);
+
+        let live = storage.list_nodes(NodeFilter::new()).unwrap();
+        assert_eq!(live.len(), 2);
+        // The two most important survive
+        let mut importance: Vec<f32> = live.iter().map(|n| n.importance).collect();
+        importance.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(importance, [0.4, 0.5]);
+        assert!(engine.plan(storage.as_ref()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sweep_max_nodes_skips_pinned_nodes() {
+        let (storage, _dir) = make_storage();
+
+        // Oldest and least important — would be the first eviction candidate
+        let mut pinned = make_node("persona", 0.0);
+        pinned.created_at = Utc::now() - Duration::days(100);
+        pinned.data.tags.push(PINNED_TAG.to_string());
+        storage.put_node(&pinned).unwrap();
+
+        let mut kept_by_config = make_node("fact", 0.0);
+        kept_by_config.created_at = Utc::now() - Duration::days(90);
+        kept_by_config.data.tags.push("reference".to_string());
+        storage.put_node(&kept_by_config).unwrap();
+
+        let others: Vec<Node> = [0.2, 0.5, 0.9]
+            .into_iter()
+            .map(|importance| {
+                let node = make_node("fact", importance);
+                storage.put_node(&node).unwrap();
+                node
+            })
+            .collect();
+
+        let config = RetentionConfig {
+            max_nodes: Some(RetentionMaxNodes {
+                limit: 1,
+                strategy: "oldest_lowest_importance".to_string(),
+            }),
+            protected_tags: vec!["reference".to_string()],
+            ..Default::default()
+        };
+        let engine = RetentionEngine::new(config, default_score_decay());
+        let deleted = engine.sweep(storage.as_ref()).unwrap();
+        assert_eq!(deleted, 3);
+
+        assert!(!storage.get_node(pinned.id).unwrap().unwrap().deleted);
+        assert!(
+            !storage
+                .get_node(kept_by_config.id)
+                .unwrap()
+                .unwrap()
+                .deleted
+        );
+        for node in &others {
+            assert!(storage.get_node(node.id).unwrap().unwrap().deleted);
+        }
+    }
+
     #[test]
     fn test_purge_expired_hard_deletes_old_soft_deletes() {
         let (storage, _dir) = make_storage();
@@ -459,21 +921,25 @@ mod tests {
         storage.put_node(&node).unwrap();
         storage.delete_node(node.id).unwrap();
 
-        // Manually set updated_at to past the grace period
+        let config = RetentionConfig {
+            grace_days: 30,
+            ..Default::default()
+        };
+        let engine = RetentionEngine::new(config, default_score_decay());
+        // Still within the grace period
+        assert_eq!(engine.purge_expired(storage.as_ref()).unwrap(), 0);
+        assert!(storage.get_node(node.id).unwrap().unwrap().deleted);
+
+        // Manually set deleted_at to past the grace period
         let mut deleted_node = storage
             .list_nodes(NodeFilter::new().include_deleted())
             .unwrap()
             .into_iter()
             .find(|n| n.id == node.id)
             .unwrap();
-        deleted_node.updated_at = Utc::now() - Duration::days(60);
+        deleted_node.deleted_at = Some(Utc::now() - Duration::days(60));
         storage.put_node(&deleted_node).unwrap();
 
-        let config = RetentionConfig {
-            grace_days: 30,
-            ..Default::default()
-        };
-        let engine = RetentionEngine::new(config, default_score_decay());
         let purged = engine.purge_expired(storage.as_ref()).unwrap();
         assert_eq!(purged, 1);
 
@@ -714,4 +1180,187 @@ mod tests {
             "High-score node should survive with min_score gate"
         );
     }
+
+    #[test]
+    fn test_parse_ttl() {
+        assert_eq!(parse_ttl("30d").unwrap(), Duration::days(30));
+        assert_eq!(parse_ttl("1d12h").unwrap(), Duration::hours(36));
+        assert_eq!(parse_ttl("2w").unwrap(), Duration::days(14));
+        assert!(parse_ttl("").is_err());
+        assert!(parse_ttl("30").is_err());
+        assert!(parse_ttl("5y").is_err());
+        assert!(parse_ttl("0d").is_err());
+        assert!(parse_ttl("0h0m").is_err());
+        assert!(parse_ttl("-5d").is_err());
+        assert!(parse_ttl("+5d").is_err());
+        assert!(parse_ttl("1d-2h").is_err());
+        assert!(parse_ttl("9223372036854775807w").is_err());
+        assert!(parse_ttl("9223372036854775807s9223372036854775807s").is_err());
+    }
+
+    #[test]
+    fn test_evict_expired_removes_only_unprotected_expired_nodes() {
+        let (storage, _dir) = make_storage();
+
+        let aged = |kind: &str, importance: f32, days: i64| {
+            let mut node = make_node(kind, importance);
+            node.created_at = Utc::now() - Duration::days(days);
+            // Accessed just now — hard TTL ignores the grace period
+            node.last_accessed_at = Utc::now();
+            node
+        };
+
+        let expired = aged("observation", 0.3, 40);
+        let fresh = aged("observation", 0.3, 5);
+        let important = aged("observation", 0.9, 40);
+        let mut tagged = aged("observation", 0.3, 40);
+        tagged.data.tags.push("keep".to_string());
+        let other_kind = aged("fact", 0.3, 400);
+        for node in [&expired, &fresh, &important, &tagged, &other_kind] {
+            storage.put_node(node).unwrap();
+        }
+        // A live inbound edge does not protect against the hard TTL
+        storage
+            .put_edge(&Edge::new(
+                other_kind.id,
+                expired.id,
+                Relation::new("related_to").unwrap(),
+                0.8,
+                EdgeProvenance::AutoSimilarity { score: 0.8 },
+            ))
+            .unwrap();
+
+        let mut expire_after = HashMap::new();
+        expire_after.insert("observation".to_string(), "30d".to_string());
+        let config = RetentionConfig {
+            expire_after,
+            protected_tags: vec!["keep".to_string()],
+            ..Default::default()
+        };
+        let engine = RetentionEngine::new(config, default_score_decay());
+        let deleted = engine.evict_expired(storage.as_ref()).unwrap();
+        assert_eq!(deleted, 1);
+
+        assert!(storage.get_node(expired.id).unwrap().unwrap().deleted);
+        for survivor in [&fresh, &important, &tagged, &other_kind] {
+            assert!(!storage.get_node(survivor.id).unwrap().unwrap().deleted);
+        }
+    }
+
+    #[test]
+    fn test_plan_evicts_nodes_past_expires_at() {
+        let (storage, _dir) = make_storage();
+
+        let expiring = |importance: f32, hours: i64| {
+            let mut node = make_node("observation", importance);
+            node.expires_at = Some(Utc::now() + Duration::hours(hours));
+            node
+        };
+        // Importance doesn't exempt a node from its own expiry
+        let past = expiring(0.95, -1);
+        let future = expiring(0.3, 1);
+        let mut pinned = expiring(0.3, -1);
+        pinned.data.tags.push(PINNED_TAG.to_string());
+        // Also past its kind TTL: planned once, under its own expiry
+        let mut both = expiring(0.3, -1);
+        both.created_at = Utc::now() - Duration::days(40);
+        for node in [&past, &future, &pinned, &both] {
+            storage.put_node(node).unwrap();
+        }
+
+        let config = RetentionConfig {
+            expire_after: HashMap::from([("observation".to_string(), "30d".to_string())]),
+            ..Default::default()
+        };
+        let engine = RetentionEngine::new(config, default_score_decay());
+        let planned = engine.plan(storage.as_ref()).unwrap();
+        let mut ids: Vec<NodeId> = planned.iter().map(|e| e.node.id).collect();
+        ids.sort();
+        let mut expected = vec![past.id, both.id];
+        expected.sort();
+        assert_eq!(ids, expected);
+        assert!(planned
+            .iter()
+            .all(|e| e.reason == EvictionReason::ExpiresAt));
+
+        // Without any retention rules configured, expiry still applies
+        let engine = RetentionEngine::new(RetentionConfig::default(), default_score_decay());
+        assert_eq!(engine.evict_expired(storage.as_ref()).unwrap(), 2);
+        assert!(storage.get_node(past.id).unwrap().unwrap().deleted);
+        assert!(!storage.get_node(future.id).unwrap().unwrap().deleted);
+        assert!(!storage.get_node(pinned.id).unwrap().unwrap().deleted);
+    }
+
+    #[test]
+    fn test_archive_eviction_round_trips_through_restore() {
+        let (storage, dir) = make_storage();
+        let archive_path = dir.path().join("archive").join("evicted.jsonl");
+
+        let mut stale = make_node("fact", 0.1);
+        stale.created_at = Utc::now() - Duration::days(10);
+        stale.data.tags.push("stale".to_string());
+        stale.embedding = Some(vec![0.1, 0.2, 0.3]);
+        stale.access_count = 4;
+        let keeper = make_node("fact", 0.9);
+        storage.put_node(&stale).unwrap();
+        storage.put_node(&keeper).unwrap();
+
+        let outbound = Edge::new(
+            stale.id,
+            keeper.id,
+            Relation::new("supports").unwrap(),
+            0.7,
+            EdgeProvenance::Manual {
+                created_by: "test".to_string(),
+            },
+        );
+        let inbound = Edge::new(
+            keeper.id,
+            stale.id,
+            Relation::new("related_to").unwrap(),
+            0.4,
+            EdgeProvenance::AutoSimilarity { score: 0.4 },
+        );
+        storage.put_edge(&outbound).unwrap();
+        storage.put_edge(&inbound).unwrap();
+        let stale = storage.get_node(stale.id).unwrap().unwrap();
+
+        let config = RetentionConfig {
+            max_nodes: Some(RetentionMaxNodes {
+                limit: 1,
+                strategy: "oldest_lowest_importance".to_string(),
+            }),
+            eviction: EvictionMode::Archive {
+                path: archive_path.clone(),
+            },
+            ..Default::default()
+        };
+        let engine = RetentionEngine::new(config, default_score_decay());
+        assert_eq!(engine.sweep(storage.as_ref()).unwrap(), 1);
+
+        // Gone from the hot graph entirely, edges included
+        assert!(storage.get_node(stale.id).unwrap().is_none());
+        assert!(storage.edges_to(keeper.id).unwrap().is_empty());
+        assert!(storage.edges_from(keeper.id).unwrap().is_empty());
+
+        let contents = std::fs::read_to_string(&archive_path).unwrap();
+        let record: ArchiveRecord = serde_json::from_str(contents.trim()).unwrap();
+        assert_eq!(record.reason, EvictionReason::MaxNodes);
+        assert_eq!(record.node.id, stale.id);
+        assert!(record.archived_at <= Utc::now());
+
+        let summary = restore_archive(storage.as_ref(), &archive_path).unwrap();
+        assert_eq!(
+            summary,
+            RestoreSummary {
+                nodes: 1,
+                edges: 2,
+                skipped_edges: 0
+            }
+        );
+
+        assert_eq!(storage.get_node(stale.id).unwrap().unwrap(), stale);
+        assert_eq!(storage.get_edge(outbound.id).unwrap().unwrap(), outbound);
+        assert_eq!(storage.get_edge(inbound.id).unwrap().unwrap(), inbound);
+    }
 }
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// One section that differs between two prompt versions.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SectionDiff {
+    pub key: String,
+    /// Section text in the `from` version; `None` when the section was added.
+    pub before: Option<String>,
+    /// Section text in the `to` version; `None` when the section was removed.
+    pub after: Option<String>,
+}
+
+/// Section-level differences between two resolved versions of a prompt.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PromptDiff {
+    pub slug: String,
+    pub branch: String,
+    pub from_version: u32,
+    pub to_version: u32,
+    pub added: Vec<SectionDiff>,
+    pub removed: Vec<SectionDiff>,
+    pub changed: Vec<SectionDiff>,
+}
+
+impl PromptDiff {
+    /// Compare two resolved section maps. Keys are reported in sorted order.
+    pub fn between(
+        slug: &str,
+        branch: &str,
+        from_version: u32,
+        to_version: u32,
+        before: &HashMap<String, serde_json::Value>,
+        after: &HashMap<String, serde_json::Value>,
+    ) -> Self {
+        let keys: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+        let mut diff = Self {
+            slug: slug.to_string(),
+            branch: branch.to_string(),
+            from_version,
+            to_version,
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        };
+        for key in keys {
+            let section = SectionDiff {
+                key: key.clone(),
+                before: before.get(key).map(section_text),
+                after: after.get(key).map(section_text),
+            };
+            match (&section.before, &section.after) {
+                (None, Some(_)) => diff.added.push(section),
+                (Some(_), None) => diff.removed.push(section),
+                (Some(b), Some(a)) if b != a => diff.changed.push(section),
+                _ => {}
+            }
+        }
+        diff
+    }
+
+    /// True when both versions resolve to the same sections.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Render as unified-diff-style text: one `@@` block per section, with
+    /// `-` for lines only in `from`, `+` for lines only in `to`.
+    pub fn to_unified(&self) -> String {
+        let mut sections: Vec<(&SectionDiff, &str)> = self
+            .changed
+            .iter()
+            .map(|s| (s, "changed"))
+            .chain(self.added.iter().map(|s| (s, "added")))
+            .chain(self.removed.iter().map(|s| (s, "removed")))
+            .collect();
+        sections.sort_by(|a, b| a.0.key.cmp(&b.0.key));
+
+        let mut out = format!(
+            "--- {slug}@{branch}/v{from}\n+++ {slug}@{branch}/v{to}\n",
+            slug = self.slug,
+            branch = self.branch,
+            from = self.from_version,
+            to = self.to_version,
+        );
+        for (section, status) in sections {
+            out.push_str(&format!("@@ {} ({}) @@\n", section.key, status));
+            let before: Vec<&str> = section
+                .before
+                .as_deref()
+                .map_or(vec![], |t| t.lines().collect());
+            let after: Vec<&str> = section
+                .after
+                .as_deref()
+                .map_or(vec![], |t| t.lines().collect());
+            for line in diff_lines(&before, &after) {
+                out.push_str(&line);
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+/// Text of a section value: strings verbatim, anything else as pretty JSON.
+fn section_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    }
+}
+
+/// Line diff via longest common subsequence, prefixed ` `, `-` or `+`.
+fn diff_lines(before: &[&str], after: &[&str]) -> Vec<String> {
+    let (n, m) = (before.len(), after.len());
+    // lcs[i][j] = LCS length of before[i..] and after[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if before[i] == after[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if before[i] == after[j] {
+            out.push(format!(" {}", before[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            out.push(format!("-{}", before[i]));
+            i += 1;
+        } else {
+            out.push(format!("+{}", after[j]));
+            j += 1;
+        }
+    }
+    out.extend(before[i..].iter().map(|l| format!("-{}", l)));
+    out.extend(after[j..].iter().map(|l| format!("+{}", l)));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_lines_keeps_common_lines() {
+        let lines = diff_lines(&["a", "b", "c"], &["a", "x", "c"]);
+        assert_eq!(lines, [" a", "-b", "+x", " c"]);
+    }
+
+    #[test]
+    fn unified_output_marks_each_section() {
+        let before = HashMap::from([
+            ("tone".to_string(), serde_json::json!("calm\nbrief")),
+            ("old".to_string(), serde_json::json!("gone")),
+        ]);
+        let after = HashMap::from([
+            ("tone".to_string(), serde_json::json!("calm\nthorough")),
+            ("new".to_string(), serde_json::json!({"k": 1})),
+        ]);
+        let diff = PromptDiff::between("p", "main", 1, 2, &before, &after);
+        let text = diff.to_unified();
+        assert!(text.starts_with("--- p@main/v1\n+++ p@main/v2\n"));
+        assert!(text.contains("@@ new (added) @@\n+{\n+  \"k\": 1\n+}\n"));
+        assert!(text.contains("@@ old (removed) @@\n-gone\n"));
+        assert!(text.contains("@@ tone (changed) @@\n calm\n-brief\n+thorough\n"));
+    }
+}
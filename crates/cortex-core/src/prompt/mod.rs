@@ -1,12 +1,20 @@
+mod diff;
 mod model;
 mod resolver;
 pub mod rollback;
 pub mod selection;
+pub mod template;
 
-pub use model::{PromptContent, PromptInfo, PromptVersionInfo, ResolvedPrompt};
+pub use diff::{PromptDiff, SectionDiff};
+pub use model::{
+    MergeStrategy, PromptContent, PromptInfo, PromptMerge, PromptVersionInfo, ResolvedPrompt,
+};
 pub use resolver::PromptResolver;
 pub use rollback::{
-    ActiveDeploymentInfo, RollbackConfig, RollbackMonitor, RollbackResult, RollbackStatus,
-    RollbackSummary, RollbackTrigger,
+    ActiveDeploymentInfo, CanaryRollout, RollbackConfig, RollbackMonitor, RollbackResult,
+    RollbackStatus, RollbackSummary, RollbackTrigger,
+};
+pub use selection::{
+    decay_weight, observation_score, score_variant, update_edge_weight, update_edge_weight_decayed,
+    ContextSignals, LinUcbArm, SelectionConfig, SelectionStrategy, VariantEvidence,
 };
-pub use selection::{observation_score, score_variant, update_edge_weight, ContextSignals};
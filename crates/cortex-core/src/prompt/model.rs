@@ -30,6 +30,31 @@ fn default_version() -> u32 {
     1
 }
 
+/// How [`PromptResolver::merge_branch`](super::PromptResolver::merge_branch)
+/// settles a section both branches changed differently.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// Take the source branch's section.
+    PreferSource,
+    /// Keep the target branch's section.
+    PreferTarget,
+    /// Refuse to merge.
+    Fail,
+}
+
+/// Outcome of merging one prompt branch into another.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PromptMerge {
+    /// The new version created on the target branch.
+    pub node_id: NodeId,
+    pub version: u32,
+    /// Source-branch version that was merged in.
+    pub source_version: u32,
+    /// Section keys both branches changed, settled by the strategy.
+    pub conflicts: Vec<String>,
+}
+
 /// A fully resolved prompt with inheritance applied.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ResolvedPrompt {
@@ -3,11 +3,15 @@ use std::sync::Arc;
 
 use crate::error::{CortexError, Result};
 use crate::kinds::defaults::prompt as prompt_kind;
-use crate::relations::defaults::{branched_from, inherits_from, supersedes, used_by};
+use crate::relations::defaults::{branched_from, inherits_from, merged_from, supersedes, used_by};
 use crate::storage::{NodeFilter, Storage};
 use crate::types::{Edge, EdgeProvenance, Node, NodeId, Source};
 
-use super::model::{PromptContent, PromptInfo, PromptVersionInfo, ResolvedPrompt};
+use super::diff::PromptDiff;
+use super::model::{
+    MergeStrategy, PromptContent, PromptInfo, PromptMerge, PromptVersionInfo, ResolvedPrompt,
+};
+use super::template;
 
 pub struct PromptResolver<S: Storage> {
     storage: Arc<S>,
@@ -167,6 +171,36 @@ impl<S: Storage> PromptResolver<S> {
         })
     }
 
+    /// Resolve the HEAD of `slug`+`branch` and fill `{{variable}}` placeholders
+    /// in its merged sections from `vars` (e.g. the agent node's metadata).
+    ///
+    /// With `strict`, a placeholder missing from `vars` is an error; otherwise
+    /// it is left verbatim. `raw_content` is returned unrendered.
+    pub fn resolve_with_vars(
+        &self,
+        slug: &str,
+        branch: &str,
+        vars: &HashMap<String, serde_json::Value>,
+        strict: bool,
+    ) -> Result<ResolvedPrompt> {
+        let head = self.find_head(slug, branch)?.ok_or_else(|| {
+            CortexError::Validation(format!(
+                "Prompt '{}' on branch '{}' not found",
+                slug, branch
+            ))
+        })?;
+        let mut resolved = self.resolve(&head)?;
+        for (key, value) in resolved.content.iter_mut() {
+            *value = template::render_value(value, vars, strict).map_err(|e| match e {
+                CortexError::Validation(msg) => {
+                    CortexError::Validation(format!("{msg} in section '{key}' of '{slug}'"))
+                }
+                other => other,
+            })?;
+        }
+        Ok(resolved)
+    }
+
     /// Parse PromptContent from a node's body JSON.
     pub fn parse_content(&self, node: &Node) -> Result<PromptContent> {
         serde_json::from_str(&node.data.body).map_err(|e| {
@@ -204,6 +238,23 @@ impl<S: Storage> PromptResolver<S> {
         content: PromptContent,
         author: &str,
     ) -> Result<NodeId> {
+        let (new_node, edge) = self.next_version(slug, branch, content, author)?;
+        self.storage.transaction(|tx| {
+            tx.put_node(&new_node)?;
+            tx.put_edge(&edge)
+        })?;
+        Ok(new_node.id)
+    }
+
+    /// The node for the version after `slug`+`branch`'s HEAD, and its
+    /// `supersedes` edge to HEAD, ready to be written together.
+    fn next_version(
+        &self,
+        slug: &str,
+        branch: &str,
+        content: PromptContent,
+        author: &str,
+    ) -> Result<(Node, Edge)> {
         let head = self.find_head(slug, branch)?.ok_or_else(|| {
             CortexError::Validation(format!(
                 "Prompt '{}' on branch '{}' not found",
@@ -217,8 +268,6 @@ impl<S: Storage> PromptResolver<S> {
         fixed_content.slug = slug.to_string();
 
         let new_node = self.build_node(&fixed_content, branch, head_version + 1, author)?;
-        self.storage.put_node(&new_node)?;
-
         let edge = Edge::new(
             new_node.id,
             head.id,
@@ -228,9 +277,7 @@ impl<S: Storage> PromptResolver<S> {
                 created_by: author.to_string(),
             },
         );
-        self.storage.put_edge(&edge)?;
-
-        Ok(new_node.id)
+        Ok((new_node, edge))
     }
 
     /// Fork a prompt onto a new branch.
@@ -275,6 +322,177 @@ impl<S: Storage> PromptResolver<S> {
         Ok(new_node.id)
     }
 
+    /// Merge the HEAD of `from_branch` into `into_branch` as a new version.
+    ///
+    /// Sections are merged three ways against the last common version: the
+    /// source version merged most recently, or else the version `from_branch`
+    /// was forked from. A section only one branch changed takes that change
+    /// (including removal); one both changed differently is a conflict,
+    /// settled by `strategy`. `override_sections` merge the same way and
+    /// metadata conflicts always keep the target's value. The new version
+    /// supersedes the target HEAD, records `merged_from` in its metadata,
+    /// and gets a `merged_from` edge to the source HEAD.
+    pub fn merge_branch(
+        &self,
+        slug: &str,
+        from_branch: &str,
+        into_branch: &str,
+        strategy: MergeStrategy,
+        author: &str,
+    ) -> Result<PromptMerge> {
+        if from_branch == into_branch {
+            return Err(CortexError::Validation(format!(
+                "Cannot merge '{}@{}' into itself",
+                slug, from_branch
+            )));
+        }
+        let not_found = |branch: &str| {
+            CortexError::Validation(format!(
+                "Prompt '{}' on branch '{}' not found",
+                slug, branch
+            ))
+        };
+        let source_node = self
+            .find_head(slug, from_branch)?
+            .ok_or_else(|| not_found(from_branch))?;
+        let target_node = self
+            .find_head(slug, into_branch)?
+            .ok_or_else(|| not_found(into_branch))?;
+        let source = self.parse_content(&source_node)?;
+        let target = self.parse_content(&target_node)?;
+        let base = self
+            .merge_base(slug, from_branch, into_branch)?
+            .map(|n| self.parse_content(&n))
+            .transpose()?;
+        let empty = HashMap::new();
+
+        let mut conflicts = Vec::new();
+        let sections = merge_maps(
+            base.as_ref().map_or(&empty, |b| &b.sections),
+            &source.sections,
+            &target.sections,
+            &mut conflicts,
+        );
+        let override_sections = merge_maps(
+            base.as_ref().map_or(&empty, |b| &b.override_sections),
+            &source.override_sections,
+            &target.override_sections,
+            &mut conflicts,
+        );
+        conflicts.sort();
+        conflicts.dedup();
+
+        let (sections, override_sections) = match strategy {
+            MergeStrategy::Fail if !conflicts.is_empty() => {
+                return Err(CortexError::Validation(format!(
+                    "Cannot merge '{}@{}' into '{}': conflicting sections {}",
+                    slug,
+                    from_branch,
+                    into_branch,
+                    conflicts.join(", ")
+                )));
+            }
+            MergeStrategy::PreferSource => {
+                (sections.prefer_source(), override_sections.prefer_source())
+            }
+            _ => (sections.prefer_target(), override_sections.prefer_target()),
+        };
+
+        let mut metadata = merge_maps(
+            base.as_ref().map_or(&empty, |b| &b.metadata),
+            &source.metadata,
+            &target.metadata,
+            &mut Vec::new(),
+        )
+        .prefer_target();
+        metadata.insert(
+            "merged_from".to_string(),
+            serde_json::json!({ "branch": from_branch, "version": source.version }),
+        );
+
+        let merged = PromptContent {
+            slug: slug.to_string(),
+            prompt_type: target.prompt_type,
+            branch: into_branch.to_string(),
+            version: target.version + 1,
+            sections,
+            metadata,
+            override_sections,
+        };
+        let (node, supersedes_edge) = self.next_version(slug, into_branch, merged, author)?;
+        let merged_edge = Edge::new(
+            node.id,
+            source_node.id,
+            merged_from(),
+            1.0,
+            EdgeProvenance::Manual {
+                created_by: author.to_string(),
+            },
+        );
+        // Without its `merged_from` edge, the next merge would pick the
+        // wrong base, so the version and both edges land together.
+        self.storage.transaction(|tx| {
+            tx.put_node(&node)?;
+            tx.put_edge(&supersedes_edge)?;
+            tx.put_edge(&merged_edge)
+        })?;
+
+        Ok(PromptMerge {
+            node_id: node.id,
+            version: target.version + 1,
+            source_version: source.version,
+            conflicts,
+        })
+    }
+
+    /// The last version `from_branch` and `into_branch` had in common: the
+    /// newest source version already merged into the target, or else the
+    /// target version the source branch was forked from.
+    fn merge_base(&self, slug: &str, from_branch: &str, into_branch: &str) -> Result<Option<Node>> {
+        let mut last_merged: Option<(u32, Node)> = None;
+        for version in self.find_versions(slug, Some(into_branch))? {
+            for edge in self.storage.edges_from(version.id)? {
+                if edge.relation != merged_from() {
+                    continue;
+                }
+                let Some(merged) = self.storage.get_node(edge.to)? else {
+                    continue;
+                };
+                let Ok(content) = self.parse_content(&merged) else {
+                    continue;
+                };
+                if content.branch == from_branch
+                    && last_merged
+                        .as_ref()
+                        .is_none_or(|(v, _)| content.version > *v)
+                {
+                    last_merged = Some((content.version, merged));
+                }
+            }
+        }
+        if let Some((_, node)) = last_merged {
+            return Ok(Some(node));
+        }
+
+        let Some(first) = self.get_version(slug, from_branch, 1)? else {
+            return Ok(None);
+        };
+        for edge in self.storage.edges_from(first.id)? {
+            if edge.relation != branched_from() {
+                continue;
+            }
+            if let Some(fork) = self.storage.get_node(edge.to)? {
+                if self
+                    .parse_content(&fork)
+                    .is_ok_and(|c| c.branch == into_branch)
+                {
+                    return Ok(Some(fork));
+                }
+            }
+        }
+        Ok(None)
+    }
+
     /// List the HEAD of every slug+branch combination.
     ///
     /// **Optimised**: builds the superseded set in a single forward-edge pass over
@@ -320,6 +538,38 @@ impl<S: Storage> PromptResolver<S> {
         }))
     }
 
+    /// Compare two versions of `slug`+`branch` section by section.
+    ///
+    /// Both versions are resolved first, so inherited sections and
+    /// `override_sections` are compared as the agent would see them.
+    pub fn diff(
+        &self,
+        slug: &str,
+        branch: &str,
+        from_version: u32,
+        to_version: u32,
+    ) -> Result<PromptDiff> {
+        let resolve_version = |version: u32| -> Result<ResolvedPrompt> {
+            let node = self.get_version(slug, branch, version)?.ok_or_else(|| {
+                CortexError::Validation(format!(
+                    "Prompt '{}@{}/v{}' not found",
+                    slug, branch, version
+                ))
+            })?;
+            self.resolve(&node)
+        };
+        let from = resolve_version(from_version)?;
+        let to = resolve_version(to_version)?;
+        Ok(PromptDiff::between(
+            slug,
+            branch,
+            from_version,
+            to_version,
+            &from.content,
+            &to.content,
+        ))
+    }
+
     /// List version history for a slug+branch, optimised with a single
     /// superseded-set pass (avoids per-node reverse-edge lookups).
     pub fn list_versions(&self, slug: &str, branch: &str) -> Result<Vec<PromptVersionInfo>> {
@@ -382,11 +632,75 @@ impl<S: Storage> PromptResolver<S> {
     }
 }
 
+// ── merge helpers ─────────────────────────────────────────────────────────────
+
+/// A three-way map merge with conflicting keys left unresolved.
+struct MapMerge {
+    merged: HashMap<String, serde_json::Value>,
+    /// (key, source value, target value); `None` means the branch removed it.
+    conflicts: Vec<(String, Option<serde_json::Value>, Option<serde_json::Value>)>,
+}
+
+impl MapMerge {
+    fn prefer_source(self) -> HashMap<String, serde_json::Value> {
+        self.resolve(|source, _| source)
+    }
+
+    fn prefer_target(self) -> HashMap<String, serde_json::Value> {
+        self.resolve(|_, target| target)
+    }
+
+    fn resolve(
+        self,
+        pick: impl Fn(Option<serde_json::Value>, Option<serde_json::Value>) -> Option<serde_json::Value>,
+    ) -> HashMap<String, serde_json::Value> {
+        let mut merged = self.merged;
+        for (key, source, target) in self.conflicts {
+            if let Some(value) = pick(source, target) {
+                merged.insert(key, value);
+            }
+        }
+        merged
+    }
+}
+
+/// Merge `source` into `target` relative to their common `base`, pushing the
+/// key of every conflict onto `conflict_keys`.
+fn merge_maps(
+    base: &HashMap<String, serde_json::Value>,
+    source: &HashMap<String, serde_json::Value>,
+    target: &HashMap<String, serde_json::Value>,
+    conflict_keys: &mut Vec<String>,
+) -> MapMerge {
+    let keys: HashSet<&String> = source.keys().chain(target.keys()).collect();
+    let mut merge = MapMerge {
+        merged: HashMap::new(),
+        conflicts: Vec::new(),
+    };
+    for key in keys {
+        let (b, s, t) = (base.get(key), source.get(key), target.get(key));
+        let value = if s == t || s == b {
+            t
+        } else if t == b {
+            s
+        } else {
+            conflict_keys.push(key.clone());
+            merge.conflicts.push((key.clone(), s.cloned(), t.cloned()));
+            continue;
+        };
+        if let Some(v) = value {
+            merge.merged.insert(key.clone(), v.clone());
+        }
+    }
+    merge
+}
+
 // ── tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::prompt::SectionDiff;
     use crate::storage::RedbStorage;
     use std::collections::HashMap;
     use tempfile::TempDir;
@@ -635,6 +949,145 @@ mod tests {
         );
     }
 
+    // ── merge_branch ──────────────────────────────────────────────────────────
+
+    /// main v1 {role, tone}, forked to dev; then dev and main both move on.
+    fn diverged(r: &PromptResolver<RedbStorage>, dev: &[(&str, &str)], main: &[(&str, &str)]) {
+        r.create_prompt(
+            simple_content("p", "persona", &[("role", "helper"), ("tone", "calm")]),
+            "main",
+            "t",
+        )
+        .unwrap();
+        r.create_branch("p", "main", "dev", None, "t").unwrap();
+        r.create_version("p", "dev", simple_content("p", "persona", dev), "t")
+            .unwrap();
+        r.create_version("p", "main", simple_content("p", "persona", main), "t")
+            .unwrap();
+    }
+
+    fn head_sections(r: &PromptResolver<RedbStorage>, branch: &str) -> HashMap<String, String> {
+        let head = r.find_head("p", branch).unwrap().unwrap();
+        r.parse_content(&head)
+            .unwrap()
+            .sections
+            .into_iter()
+            .map(|(k, v)| (k, v.as_str().unwrap_or_default().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn merge_branch_combines_non_conflicting_changes() {
+        let (storage, _dir) = setup();
+        let r = PromptResolver::new(storage.clone());
+        // dev rewrites role and adds rules; main drops tone and adds format.
+        diverged(
+            &r,
+            &[("role", "reviewer"), ("tone", "calm"), ("rules", "cite")],
+            &[("role", "helper"), ("format", "markdown")],
+        );
+        let dev_head = r.find_head("p", "dev").unwrap().unwrap();
+
+        let merge = r
+            .merge_branch("p", "dev", "main", MergeStrategy::Fail, "t")
+            .unwrap();
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(merge.version, 3);
+        assert_eq!(merge.source_version, 2);
+
+        let sections = head_sections(&r, "main");
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections["role"], "reviewer");
+        assert_eq!(sections["rules"], "cite");
+        assert_eq!(sections["format"], "markdown");
+
+        // Provenance: supersedes the old main HEAD, merged_from the dev HEAD.
+        let head = r.find_head("p", "main").unwrap().unwrap();
+        assert_eq!(head.id, merge.node_id);
+        let edges = storage.edges_from(head.id).unwrap();
+        assert!(edges.iter().any(|e| e.relation == supersedes()));
+        assert!(edges
+            .iter()
+            .any(|e| e.relation == merged_from() && e.to == dev_head.id));
+        let content = r.parse_content(&head).unwrap();
+        assert_eq!(
+            content.metadata["merged_from"],
+            serde_json::json!({ "branch": "dev", "version": 2 })
+        );
+    }
+
+    #[test]
+    fn merge_branch_conflicts_follow_strategy() {
+        let dev = [("role", "reviewer"), ("tone", "calm")];
+        let main = [("role", "mentor"), ("tone", "warm")];
+
+        let (storage, _dir) = setup();
+        let r = PromptResolver::new(storage.clone());
+        diverged(&r, &dev, &main);
+        let err = r
+            .merge_branch("p", "dev", "main", MergeStrategy::Fail, "t")
+            .unwrap_err();
+        assert!(err.to_string().contains("role"), "{err}");
+        assert_eq!(r.list_versions("p", "main").unwrap().len(), 2);
+
+        let merge = r
+            .merge_branch("p", "dev", "main", MergeStrategy::PreferSource, "t")
+            .unwrap();
+        assert_eq!(merge.conflicts, ["role"]);
+        let sections = head_sections(&r, "main");
+        assert_eq!(sections["role"], "reviewer");
+        assert_eq!(sections["tone"], "warm"); // only main changed it
+
+        let (storage, _dir) = setup();
+        let r = PromptResolver::new(storage.clone());
+        diverged(&r, &dev, &main);
+        let merge = r
+            .merge_branch("p", "dev", "main", MergeStrategy::PreferTarget, "t")
+            .unwrap();
+        assert_eq!(merge.conflicts, ["role"]);
+        assert_eq!(head_sections(&r, "main")["role"], "mentor");
+    }
+
+    #[test]
+    fn merge_branch_uses_last_merge_as_base() {
+        let (storage, _dir) = setup();
+        let r = PromptResolver::new(storage.clone());
+        diverged(
+            &r,
+            &[("role", "reviewer"), ("tone", "calm")],
+            &[("role", "helper"), ("tone", "calm")],
+        );
+        r.merge_branch("p", "dev", "main", MergeStrategy::Fail, "t")
+            .unwrap();
+
+        // main edits role again; dev hasn't touched it since the merge.
+        r.create_version(
+            "p",
+            "main",
+            simple_content("p", "persona", &[("role", "mentor"), ("tone", "calm")]),
+            "t",
+        )
+        .unwrap();
+        r.create_version(
+            "p",
+            "dev",
+            simple_content("p", "persona", &[("role", "reviewer"), ("tone", "dry")]),
+            "t",
+        )
+        .unwrap();
+
+        let merge = r
+            .merge_branch("p", "dev", "main", MergeStrategy::Fail, "t")
+            .unwrap();
+        assert!(merge.conflicts.is_empty());
+        let sections = head_sections(&r, "main");
+        assert_eq!(sections["role"], "mentor");
+        assert_eq!(sections["tone"], "dry");
+        assert!(r
+            .merge_branch("p", "main", "main", MergeStrategy::Fail, "t")
+            .is_err());
+    }
+
     // ── list_all_prompts ──────────────────────────────────────────────────────
 
     #[test]
@@ -770,6 +1223,141 @@ mod tests {
         assert!(r.get_version("p", "main", 99).unwrap().is_none());
     }
 
+    // ── diff ──────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_sections() {
+        let (storage, _dir) = setup();
+        let r = PromptResolver::new(storage.clone());
+
+        r.create_prompt(
+            simple_content("p", "persona", &[("role", "helper"), ("tone", "calm")]),
+            "main",
+            "t",
+        )
+        .unwrap();
+        r.create_version(
+            "p",
+            "main",
+            simple_content(
+                "p",
+                "persona",
+                &[("role", "reviewer"), ("rules", "be brief")],
+            ),
+            "t",
+        )
+        .unwrap();
+
+        let diff = r.diff("p", "main", 1, 2).unwrap();
+        assert_eq!(
+            diff.added,
+            [SectionDiff {
+                key: "rules".into(),
+                before: None,
+                after: Some("be brief".into()),
+            }]
+        );
+        assert_eq!(
+            diff.removed,
+            [SectionDiff {
+                key: "tone".into(),
+                before: Some("calm".into()),
+                after: None,
+            }]
+        );
+        assert_eq!(
+            diff.changed,
+            [SectionDiff {
+                key: "role".into(),
+                before: Some("helper".into()),
+                after: Some("reviewer".into()),
+            }]
+        );
+
+        // Reversing the range swaps added and removed.
+        let back = r.diff("p", "main", 2, 1).unwrap();
+        assert_eq!(back.added[0].key, "tone");
+        assert_eq!(back.removed[0].key, "rules");
+        assert!(r.diff("p", "main", 2, 2).unwrap().is_empty());
+        assert!(r.diff("p", "main", 1, 9).is_err());
+    }
+
+    #[test]
+    fn diff_compares_override_sections() {
+        let (storage, _dir) = setup();
+        let r = PromptResolver::new(storage.clone());
+
+        r.create_prompt(
+            simple_content("p", "persona", &[("role", "helper")]),
+            "main",
+            "t",
+        )
+        .unwrap();
+        let mut v2 = simple_content("p", "persona", &[("role", "helper")]);
+        v2.override_sections
+            .insert("role".into(), serde_json::json!("strict helper"));
+        r.create_version("p", "main", v2, "t").unwrap();
+
+        let diff = r.diff("p", "main", 1, 2).unwrap();
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.changed[0].key, "role");
+        assert_eq!(diff.changed[0].after.as_deref(), Some("strict helper"));
+    }
+
+    // ── resolve_with_vars ─────────────────────────────────────────────────────
+
+    #[test]
+    fn resolve_with_vars_fills_inherited_sections() {
+        let (storage, _dir) = setup();
+        let r = PromptResolver::new(storage.clone());
+
+        let base = r
+            .create_prompt(
+                simple_content("base", "persona", &[("identity", "You are {{agent}}.")]),
+                "main",
+                "t",
+            )
+            .unwrap();
+        let child = r
+            .create_prompt(
+                simple_content(
+                    "child",
+                    "skill",
+                    &[("channel", "Reply on {{channel}}; literal \\{{channel}}.")],
+                ),
+                "main",
+                "t",
+            )
+            .unwrap();
+        link_inherits(&storage, child, base);
+
+        let vars = HashMap::from([
+            ("agent".to_string(), serde_json::json!("kai")),
+            ("channel".to_string(), serde_json::json!("slack")),
+        ]);
+        let resolved = r.resolve_with_vars("child", "main", &vars, true).unwrap();
+        assert_eq!(resolved.content["identity"], "You are kai.");
+        assert_eq!(
+            resolved.content["channel"],
+            "Reply on slack; literal {{channel}}."
+        );
+        // Stored content is untouched.
+        assert_eq!(
+            resolved.raw_content.sections["channel"],
+            "Reply on {{channel}}; literal \\{{channel}}."
+        );
+
+        let partial = HashMap::from([("channel".to_string(), serde_json::json!("slack"))]);
+        let err = r
+            .resolve_with_vars("child", "main", &partial, true)
+            .unwrap_err();
+        assert!(err.to_string().contains("'agent'"), "{err}");
+        let lenient = r
+            .resolve_with_vars("child", "main", &partial, false)
+            .unwrap();
+        assert_eq!(lenient.content["identity"], "You are {{agent}}.");
+    }
+
     // ── resolve (inheritance) ─────────────────────────────────────────────────
 
     fn link_inherits(storage: &Arc<RedbStorage>, child_id: NodeId, parent_id: NodeId) {
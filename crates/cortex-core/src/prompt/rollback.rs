@@ -45,6 +45,10 @@ pub struct RollbackConfig {
     pub cooldown_base_hours: u32,
     /// Number of rollbacks before a version is quarantined (requires manual override).
     pub max_rollbacks_before_quarantine: u32,
+    /// Clean observations (score ≥ 0.4) of a canary needed for each ramp step.
+    pub canary_ramp_interval: u32,
+    /// Percentage points a canary's rollout grows by at each ramp step.
+    pub canary_ramp_step: u8,
 }
 
 impl Default for RollbackConfig {
@@ -61,6 +65,8 @@ impl Default for RollbackConfig {
             consecutive_negative_limit: 3,
             cooldown_base_hours: 1,
             max_rollbacks_before_quarantine: 3,
+            canary_ramp_interval: 3,
+            canary_ramp_step: 25,
         }
     }
 }
@@ -87,6 +93,12 @@ pub enum RollbackTrigger {
     ConsecutiveNegative {
         count: u32,
     },
+    /// Operator-requested rollback via [`RollbackMonitor::rollback_to`].
+    Manual {
+        target_version: u32,
+        /// How many versions back from HEAD the target is.
+        steps: u32,
+    },
 }
 
 impl RollbackTrigger {
@@ -96,6 +108,7 @@ impl RollbackTrigger {
             Self::SentimentSigma { .. } => "sentiment_sigma",
             Self::AbsoluteCorrectionIncrease { .. } => "absolute_correction_increase",
             Self::ConsecutiveNegative { .. } => "consecutive_negative",
+            Self::Manual { .. } => "manual",
         }
     }
 }
@@ -141,6 +154,31 @@ pub struct ActiveDeploymentInfo {
     pub mean_correction: f32,
     pub mean_sentiment: f32,
     pub consecutive_negative: u32,
+    /// Share of selections routed to this version; 100 unless it is a canary.
+    pub rollout_percent: u8,
+}
+
+/// A canary deployment still ramping up, as seen by variant selection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanaryRollout {
+    pub deployment_node_id: NodeId,
+    /// The newly deployed version.
+    pub canary_node_id: NodeId,
+    /// The version it replaces, which keeps the remaining traffic.
+    pub stable_node_id: NodeId,
+    pub rollout_percent: u8,
+}
+
+impl CanaryRollout {
+    /// Pick the version one selection should use: the canary with probability
+    /// `rollout_percent / 100`, otherwise the stable version.
+    pub fn route<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> NodeId {
+        if rng.gen_range(0..100u8) < self.rollout_percent {
+            self.canary_node_id
+        } else {
+            self.stable_node_id
+        }
+    }
 }
 
 /// Current rollback status for a prompt slug+branch.
@@ -184,6 +222,149 @@ impl<S: Storage> RollbackMonitor<S> {
         prompt_node_id: NodeId,
         agent_name: &str,
         baseline_obs: Vec<(f32, f32)>,
+    ) -> Result<NodeId> {
+        self.deploy(
+            slug,
+            branch,
+            version,
+            prompt_node_id,
+            agent_name,
+            baseline_obs,
+            None,
+        )
+    }
+
+    /// Record a canary deployment that starts on `rollout_percent` of selections.
+    ///
+    /// The version `prompt_node_id` supersedes keeps the rest of the traffic.
+    /// The agent is bound to the canary with the same `uses` weight it has
+    /// for that version, so selection can route between the two. Each run of
+    /// [`RollbackConfig::canary_ramp_interval`] clean observations raises the
+    /// rollout by [`RollbackConfig::canary_ramp_step`]; the canary is promoted
+    /// once it reaches 100% or outlives the monitoring window, and a rollback
+    /// while it ramps aborts it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_canary_deployment(
+        &self,
+        slug: &str,
+        branch: &str,
+        version: u32,
+        prompt_node_id: NodeId,
+        agent_name: &str,
+        baseline_obs: Vec<(f32, f32)>,
+        rollout_percent: u8,
+    ) -> Result<NodeId> {
+        if rollout_percent >= 100 {
+            return self.record_deployment(
+                slug,
+                branch,
+                version,
+                prompt_node_id,
+                agent_name,
+                baseline_obs,
+            );
+        }
+        let stable = self.superseded_by(prompt_node_id)?.ok_or_else(|| {
+            crate::CortexError::Validation(format!(
+                "Cannot canary {}/{} v{}: no previous version to share traffic with",
+                slug, branch, version
+            ))
+        })?;
+
+        let mut uses_edge = None;
+        if let Some(agent) = self.find_agent_for_prompt(agent_name, stable.id)? {
+            let uses_rel = rels::uses();
+            let stable_weight = self
+                .storage
+                .edges_between(agent.id, stable.id)?
+                .into_iter()
+                .filter(|e| e.relation == uses_rel)
+                .map(|e| e.weight)
+                .fold(0.0f32, f32::max);
+            let existing = self
+                .storage
+                .edges_between(agent.id, prompt_node_id)?
+                .into_iter()
+                .find(|e| e.relation == uses_rel);
+            uses_edge = match existing {
+                Some(mut edge) if edge.weight < stable_weight => {
+                    edge.weight = stable_weight;
+                    edge.updated_at = Utc::now();
+                    Some(edge)
+                }
+                Some(_) => None,
+                None => Some(Edge::new(
+                    agent.id,
+                    prompt_node_id,
+                    uses_rel,
+                    stable_weight,
+                    EdgeProvenance::AutoStructural {
+                        rule: "rollback_monitor".into(),
+                    },
+                )),
+            };
+        }
+
+        self.deploy(
+            slug,
+            branch,
+            version,
+            prompt_node_id,
+            agent_name,
+            baseline_obs,
+            Some((stable.id, rollout_percent, uses_edge)),
+        )
+    }
+
+    /// The canary still ramping up between `prompt_node_id` and its
+    /// neighbour, whichever of the two `prompt_node_id` is.
+    pub fn active_canary(&self, prompt_node_id: NodeId) -> Result<Option<CanaryRollout>> {
+        // Canaries that replace `prompt_node_id` point at it via `supersedes`.
+        let supersedes_rel = rels::supersedes();
+        let candidates: Vec<NodeId> = std::iter::once(prompt_node_id)
+            .chain(
+                self.storage
+                    .edges_to(prompt_node_id)?
+                    .into_iter()
+                    .filter(|e| e.relation == supersedes_rel)
+                    .map(|e| e.from),
+            )
+            .collect();
+
+        let deployment_rel = rels::deployed();
+        for version_id in candidates {
+            let deployment = self
+                .storage
+                .edges_to(version_id)?
+                .into_iter()
+                .filter(|e| e.relation == deployment_rel)
+                .filter_map(|e| self.storage.get_node(e.from).ok().flatten())
+                .filter(|n| n.kind == kinds::event() && is_active_deployment(n))
+                .max_by_key(|n| n.created_at);
+            let Some(deployment) = deployment else {
+                continue;
+            };
+            if let Some(canary) = parse_canary(&deployment, version_id) {
+                if canary.canary_node_id == prompt_node_id
+                    || canary.stable_node_id == prompt_node_id
+                {
+                    return Ok(Some(canary));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn deploy(
+        &self,
+        slug: &str,
+        branch: &str,
+        version: u32,
+        prompt_node_id: NodeId,
+        agent_name: &str,
+        baseline_obs: Vec<(f32, f32)>,
+        canary: Option<(NodeId, u8, Option<Edge>)>,
     ) -> Result<NodeId> {
         let (
             baseline_correction,
@@ -192,7 +373,7 @@ impl<S: Storage> RollbackMonitor<S> {
             baseline_stddev_sentiment,
         ) = compute_baseline_stats(&baseline_obs);
 
-        let body = serde_json::json!({
+        let mut body = serde_json::json!({
             "event_type": "deployment",
             "slug": slug,
             "branch": branch,
@@ -213,6 +394,11 @@ impl<S: Storage> RollbackMonitor<S> {
             "consecutive_negative": 0u32,
             "status": "monitoring",
         });
+        if let Some((stable_node_id, rollout_percent, _)) = &canary {
+            body["canary_from_node_id"] = stable_node_id.to_string().into();
+            body["rollout_percent"] = (*rollout_percent).into();
+            body["clean_observations"] = 0u32.into();
+        }
 
         let deployment_node = Node::new(
             kinds::event(),
@@ -225,10 +411,8 @@ impl<S: Storage> RollbackMonitor<S> {
             },
             1.0,
         );
-        self.storage.put_node(&deployment_node)?;
-
         // Link: deployment_event --deployed--> prompt_version
-        self.storage.put_edge(&Edge::new(
+        let deployed_edge = Edge::new(
             deployment_node.id,
             prompt_node_id,
             rels::deployed(),
@@ -236,7 +420,18 @@ impl<S: Storage> RollbackMonitor<S> {
             EdgeProvenance::Manual {
                 created_by: agent_name.to_string(),
             },
-        ))?;
+        );
+        let uses_edge = canary.and_then(|(_, _, edge)| edge);
+
+        // A canary's agent binding lands with its deployment or not at all.
+        self.storage.transaction(|tx| {
+            tx.put_node(&deployment_node)?;
+            tx.put_edge(&deployed_edge)?;
+            if let Some(ref edge) = uses_edge {
+                tx.put_edge(edge)?;
+            }
+            Ok(())
+        })?;
 
         Ok(deployment_node.id)
     }
@@ -331,19 +526,24 @@ impl<S: Storage> RollbackMonitor<S> {
             "monitoring"
         };
 
-        // Link observation to deployment event for audit trail.
-        self.storage.put_edge(&Edge::new(
-            obs_node_id,
-            deployment_node.id,
-            rels::observed_with(),
-            1.0,
-            EdgeProvenance::AutoStructural {
-                rule: "rollback_monitor".into(),
-            },
-        ))?;
+        // Canary ramp: every `canary_ramp_interval` clean observations widen the
+        // rollout; surviving the monitoring window completes it.
+        let prev_rollout = body["rollout_percent"].as_u64().map(|p| p.min(100) as u8);
+        let prev_clean = body["clean_observations"].as_u64().unwrap_or(0) as u32;
+        let clean = prev_clean + u32::from(obs_score >= 0.4);
+        let rollout = prev_rollout.map(|prev| {
+            let interval = self.config.canary_ramp_interval.max(1);
+            if n >= monitoring_window {
+                100
+            } else if obs_score >= 0.4 && clean.is_multiple_of(interval) {
+                prev.saturating_add(self.config.canary_ramp_step).min(100)
+            } else {
+                prev
+            }
+        });
 
         // Persist updated stats into deployment node body.
-        let new_body = serde_json::json!({
+        let mut new_body = serde_json::json!({
             "event_type": "deployment",
             "slug": body["slug"],
             "branch": body["branch"],
@@ -364,9 +564,48 @@ impl<S: Storage> RollbackMonitor<S> {
             "consecutive_negative": consecutive_negative,
             "status": new_status,
         });
+        if let Some(rollout) = rollout {
+            new_body["canary_from_node_id"] = body["canary_from_node_id"].clone();
+            new_body["rollout_percent"] = rollout.into();
+            new_body["clean_observations"] = clean.into();
+        }
         deployment_node.data.body = new_body.to_string();
         deployment_node.updated_at = Utc::now();
-        self.storage.put_node(&deployment_node)?;
+
+        let promotion = match (prev_rollout, rollout) {
+            (Some(prev), Some(100)) if prev < 100 => {
+                self.promote_canary(&new_body, prompt_node_id)?
+            }
+            _ => Vec::new(),
+        };
+
+        // Link the observation to the deployment event for the audit trail,
+        // together with the updated stats and any canary promotion.
+        self.storage.transaction(|tx| {
+            tx.put_edge(&Edge::new(
+                obs_node_id,
+                deployment_node.id,
+                rels::observed_with(),
+                1.0,
+                EdgeProvenance::AutoStructural {
+                    rule: "rollback_monitor".into(),
+                },
+            ))?;
+            tx.put_node(&deployment_node)?;
+            for edge in &promotion {
+                tx.put_edge(edge)?;
+            }
+            Ok(())
+        })?;
+        if !promotion.is_empty() {
+            log::info!(
+                "canary promoted: {}/{} v{} now serves all of {}'s traffic",
+                new_body["slug"].as_str().unwrap_or("unknown"),
+                new_body["branch"].as_str().unwrap_or("main"),
+                new_body["version"],
+                new_body["agent_name"].as_str().unwrap_or("system")
+            );
+        }
 
         // After monitoring_window observations with no trigger → stable, we're done.
         if n >= monitoring_window {
@@ -521,6 +760,47 @@ impl<S: Storage> RollbackMonitor<S> {
 
     // ── Private helpers ────────────────────────────────────────────────────────
 
+    /// The `uses` edge updates that move the deploying agent fully onto a
+    /// canary that finished ramping: it uses the canary at least as strongly
+    /// as the version it replaced, whose `uses` edge is depressed to 0.1.
+    fn promote_canary(
+        &self,
+        deployment_body: &serde_json::Value,
+        canary_node_id: NodeId,
+    ) -> Result<Vec<Edge>> {
+        let Some(stable_node_id) = deployment_body["canary_from_node_id"]
+            .as_str()
+            .and_then(|s| s.parse::<NodeId>().ok())
+        else {
+            return Ok(Vec::new());
+        };
+        let agent_name = deployment_body["agent_name"].as_str().unwrap_or("system");
+        let Some(agent) = self.find_agent_for_prompt(agent_name, canary_node_id)? else {
+            return Ok(Vec::new());
+        };
+
+        let uses_rel = rels::uses();
+        let mut updates = Vec::new();
+        let mut stable_weight = 0.0f32;
+        for mut edge in self.storage.edges_between(agent.id, stable_node_id)? {
+            if edge.relation == uses_rel {
+                stable_weight = stable_weight.max(edge.weight);
+                edge.weight = 0.1;
+                edge.updated_at = Utc::now();
+                updates.push(edge);
+            }
+        }
+        for mut edge in self.storage.edges_between(agent.id, canary_node_id)? {
+            if edge.relation == uses_rel && edge.weight < stable_weight {
+                edge.weight = stable_weight;
+                edge.updated_at = Utc::now();
+                updates.push(edge);
+            }
+        }
+
+        Ok(updates)
+    }
+
     /// True if `prompt_node_id` has an active rollback cooldown window.
     ///
     /// Uses edge traversal: finds rollback event nodes that point to this prompt
@@ -564,23 +844,11 @@ impl<S: Storage> RollbackMonitor<S> {
 
         // Find the previous version via the `supersedes` edge.
         // Layout: new_version --supersedes--> prev_version
-        let supersedes_rel = rels::supersedes();
-        let prev_id = self
-            .storage
-            .edges_from(prompt_node_id)?
-            .into_iter()
-            .find(|e| e.relation == supersedes_rel)
-            .map(|e| e.to);
-
-        let prev_node = prev_id.and_then(|id| self.storage.get_node(id).ok().flatten());
+        let prev_node = self.superseded_by(prompt_node_id)?;
 
         let (to_node_id, to_version) = match prev_node {
             Some(ref n) => {
-                let ver: u32 = serde_json::from_str::<serde_json::Value>(&n.data.body)
-                    .ok()
-                    .and_then(|b| b["version"].as_u64())
-                    .unwrap_or_else(|| from_version.saturating_sub(1) as u64)
-                    as u32;
+                let ver = version_of(n).unwrap_or_else(|| from_version.saturating_sub(1));
                 (n.id, ver)
             }
             None => {
@@ -591,16 +859,159 @@ impl<S: Storage> RollbackMonitor<S> {
             }
         };
 
-        let existing_rollbacks = self.count_rollbacks(slug, branch)?;
-        let rollback_count = existing_rollbacks + 1;
+        let agents: Vec<Node> = self
+            .find_agent_for_prompt(agent_name, prompt_node_id)?
+            .into_iter()
+            .collect();
+
+        self.apply_rollback(RollbackPlan {
+            slug,
+            branch,
+            from_node_id: prompt_node_id,
+            from_version,
+            to_node_id,
+            to_version,
+            skipped: Vec::new(),
+            agents,
+            deployment_node: Some(deployment_node),
+            trigger,
+        })
+    }
+
+    /// Manually roll `slug`+`branch` back from its HEAD to `target_version`.
+    ///
+    /// Walks the `supersedes` chain from HEAD, so any earlier version can be
+    /// chosen, not only the immediate predecessor. Every version stepped over
+    /// has its `uses` edges depressed like HEAD's, and each agent using one of
+    /// them is pointed at the target. Fails if the target is not an earlier
+    /// version on the branch or is quarantined.
+    pub fn rollback_to(
+        &self,
+        slug: &str,
+        branch: &str,
+        target_version: u32,
+    ) -> Result<RollbackResult> {
+        use crate::prompt::PromptResolver;
+        let resolver = PromptResolver::new(self.storage.clone());
+        let head = resolver.find_head(slug, branch)?.ok_or_else(|| {
+            crate::CortexError::Validation(format!("Prompt '{}@{}' not found", slug, branch))
+        })?;
+        let from_version = version_of(&head).unwrap_or(1);
+
+        if target_version >= from_version {
+            return Err(crate::CortexError::Validation(format!(
+                "Cannot rollback {}/{} v{} to v{}: target must be an earlier version",
+                slug, branch, from_version, target_version
+            )));
+        }
+
+        // Walk HEAD --supersedes--> ... until the target version is reached.
+        let mut skipped = Vec::new();
+        let mut current = head.id;
+        let target = loop {
+            let Some(prev) = self.superseded_by(current)? else {
+                return Err(crate::CortexError::Validation(format!(
+                    "Cannot rollback {}/{} to v{}: version not found in the supersedes chain",
+                    slug, branch, target_version
+                )));
+            };
+            if version_of(&prev) == Some(target_version) {
+                break prev;
+            }
+            if skipped.contains(&prev.id) || prev.id == head.id {
+                return Err(crate::CortexError::Validation(format!(
+                    "Cannot rollback {}/{}: supersedes chain has a cycle",
+                    slug, branch
+                )));
+            }
+            skipped.push(prev.id);
+            current = prev.id;
+        };
+
+        if target.data.tags.iter().any(|t| t == "quarantined") {
+            return Err(crate::CortexError::Validation(format!(
+                "Cannot rollback {}/{} to v{}: that version is quarantined",
+                slug, branch, target_version
+            )));
+        }
 
-        // Cooldown doubles on each rollback, capped at 168 h (1 week).
-        let cooldown_hours = (self.config.cooldown_base_hours as u64
-            * (1u64 << (rollback_count - 1).min(7)))
-        .min(168) as u32;
+        // Every agent using HEAD or a skipped version moves to the target.
+        let uses_rel = rels::uses();
+        let agent_kind = kinds::agent();
+        let mut agents: Vec<Node> = Vec::new();
+        for version_id in std::iter::once(head.id).chain(skipped.iter().copied()) {
+            for edge in self.storage.edges_to(version_id)? {
+                if edge.relation != uses_rel || agents.iter().any(|a| a.id == edge.from) {
+                    continue;
+                }
+                if let Some(agent) = self.storage.get_node(edge.from)? {
+                    if agent.kind == agent_kind {
+                        agents.push(agent);
+                    }
+                }
+            }
+        }
+
+        let deployment_node = self
+            .storage
+            .edges_to(head.id)?
+            .into_iter()
+            .filter(|e| e.relation == rels::deployed())
+            .filter_map(|e| self.storage.get_node(e.from).ok().flatten())
+            .filter(|n| n.kind == kinds::event() && is_active_deployment(n))
+            .max_by_key(|n| n.created_at);
+
+        self.apply_rollback(RollbackPlan {
+            slug,
+            branch,
+            from_node_id: head.id,
+            from_version,
+            to_node_id: target.id,
+            to_version: target_version,
+            skipped,
+            agents,
+            deployment_node,
+            trigger: RollbackTrigger::Manual {
+                target_version,
+                steps: from_version - target_version,
+            },
+        })
+    }
+
+    /// Record the audit trail for a rollback and depress agents' `uses` edges
+    /// to the rolled-back version(s), in one transaction.
+    ///
+    /// Automatic rollbacks escalate: each doubles the cooldown and enough of
+    /// them quarantine the version. Manual rollbacks are counted separately,
+    /// set no cooldown or quarantine, and also point each agent at the target.
+    fn apply_rollback(&self, plan: RollbackPlan<'_>) -> Result<RollbackResult> {
+        let RollbackPlan {
+            slug,
+            branch,
+            from_node_id: prompt_node_id,
+            from_version,
+            to_node_id,
+            to_version,
+            skipped,
+            agents,
+            deployment_node,
+            trigger,
+        } = plan;
+
+        let manual = matches!(trigger, RollbackTrigger::Manual { .. });
+        let rollback_count = self.count_rollbacks(slug, branch, manual)? + 1;
+
+        // Cooldown doubles on each automatic rollback, capped at 168 h (1 week).
+        let cooldown_hours = if manual {
+            0
+        } else {
+            (self.config.cooldown_base_hours as u64 * (1u64 << (rollback_count - 1).min(7)))
+                .min(168) as u32
+        };
         let cooldown_expires_at = Utc::now() + Duration::hours(cooldown_hours as i64);
 
-        let is_quarantined = rollback_count >= self.config.max_rollbacks_before_quarantine;
+        let is_quarantined =
+            !manual && rollback_count >= self.config.max_rollbacks_before_quarantine;
 
         log::warn!(
             "prompt rollback: {}/{} v{} → v{} (trigger: {}, rollback #{}, cooldown: {}h, quarantined: {})",
@@ -639,73 +1050,132 @@ impl<S: Storage> RollbackMonitor<S> {
             1.0,
         );
         rollback_node.data.tags.push("rollback".to_string());
-        self.storage.put_node(&rollback_node)?;
-
-        // rollback --rolled_back--> from_version
-        self.storage.put_edge(&Edge::new(
-            rollback_node.id,
-            prompt_node_id,
-            rels::rolled_back(),
-            1.0,
-            EdgeProvenance::AutoStructural {
-                rule: "rollback_monitor".into(),
-            },
-        ))?;
-        // rollback --rolled_back_to--> to_version
-        self.storage.put_edge(&Edge::new(
-            rollback_node.id,
-            to_node_id,
-            rels::rolled_back_to(),
-            1.0,
-            EdgeProvenance::AutoStructural {
-                rule: "rollback_monitor".into(),
-            },
-        ))?;
 
         // Tag the rolled-back version.
-        if let Ok(Some(mut prompt_node)) = self.storage.get_node(prompt_node_id) {
-            if !prompt_node
-                .data
-                .tags
-                .contains(&"auto-rolled-back".to_string())
-            {
-                prompt_node.data.tags.push("auto-rolled-back".to_string());
-            }
-            if is_quarantined && !prompt_node.data.tags.contains(&"quarantined".to_string()) {
-                prompt_node.data.tags.push("quarantined".to_string());
-            }
-            prompt_node.updated_at = Utc::now();
-            self.storage.put_node(&prompt_node)?;
-        }
+        let rolled_back_tag = match trigger {
+            RollbackTrigger::Manual { .. } => "rolled-back",
+            _ => "auto-rolled-back",
+        };
+        let tagged_prompt = self
+            .storage
+            .get_node(prompt_node_id)?
+            .map(|mut prompt_node| {
+                if !prompt_node.data.tags.iter().any(|t| t == rolled_back_tag) {
+                    prompt_node.data.tags.push(rolled_back_tag.to_string());
+                }
+                if is_quarantined && !prompt_node.data.tags.contains(&"quarantined".to_string()) {
+                    prompt_node.data.tags.push("quarantined".to_string());
+                }
+                prompt_node.updated_at = Utc::now();
+                prompt_node
+            });
 
         // Update deployment event status (take by value — no clone needed).
-        let mut updated_dep = deployment_node;
-        if let Ok(mut dep_body) = serde_json::from_str::<serde_json::Value>(&updated_dep.data.body)
-        {
-            dep_body["status"] = serde_json::json!(if is_quarantined {
-                "quarantined"
-            } else {
-                "rolled_back"
-            });
-            updated_dep.data.body = dep_body.to_string();
-        }
-        updated_dep.updated_at = Utc::now();
-        self.storage.put_node(&updated_dep)?;
+        let updated_dep = deployment_node.map(|mut updated_dep| {
+            if let Ok(mut dep_body) =
+                serde_json::from_str::<serde_json::Value>(&updated_dep.data.body)
+            {
+                dep_body["status"] = serde_json::json!(if is_quarantined {
+                    "quarantined"
+                } else {
+                    "rolled_back"
+                });
+                // A canary still ramping up is aborted: no more traffic goes to it.
+                if dep_body["rollout_percent"]
+                    .as_u64()
+                    .is_some_and(|p| p < 100)
+                {
+                    dep_body["rollout_percent"] = 0.into();
+                    dep_body["canary_aborted"] = true.into();
+                }
+                updated_dep.data.body = dep_body.to_string();
+            }
+            updated_dep.updated_at = Utc::now();
+            updated_dep
+        });
 
-        // Depress ALL `uses` edges from agent → rolled-back prompt version to 0.1.
-        if let Some(agent_node) = self.find_agent_for_prompt(agent_name, prompt_node_id)? {
-            let uses_rel = rels::uses();
-            if let Ok(edges) = self.storage.edges_between(agent_node.id, prompt_node_id) {
-                for mut edge in edges {
+        // Depress ALL `uses` edges from each agent → rolled-back prompt version(s)
+        // to 0.1. A manual rollback also makes sure the agent uses the target
+        // at least as strongly as it used the version it is leaving.
+        let uses_rel = rels::uses();
+        let mut uses_updates = Vec::new();
+        for agent_node in &agents {
+            let mut strongest: Option<f32> = None;
+            for version_id in std::iter::once(prompt_node_id).chain(skipped.iter().copied()) {
+                for mut edge in self.storage.edges_between(agent_node.id, version_id)? {
                     if edge.relation == uses_rel {
+                        strongest = Some(strongest.map_or(edge.weight, |w| w.max(edge.weight)));
                         edge.weight = 0.1;
                         edge.updated_at = Utc::now();
-                        let _ = self.storage.put_edge(&edge);
+                        uses_updates.push(edge);
                     }
                 }
             }
+            let Some(strongest) = strongest.filter(|_| manual) else {
+                continue;
+            };
+            let existing = self
+                .storage
+                .edges_between(agent_node.id, to_node_id)?
+                .into_iter()
+                .find(|e| e.relation == uses_rel);
+            match existing {
+                Some(mut edge) if edge.weight < strongest => {
+                    edge.weight = strongest;
+                    edge.updated_at = Utc::now();
+                    uses_updates.push(edge);
+                }
+                Some(_) => {}
+                None => uses_updates.push(Edge::new(
+                    agent_node.id,
+                    to_node_id,
+                    uses_rel.clone(),
+                    strongest,
+                    EdgeProvenance::AutoStructural {
+                        rule: "rollback_monitor".into(),
+                    },
+                )),
+            }
         }
 
+        // Apply every graph mutation in one transaction so a failure never
+        // leaves a rollback event without its edges or status updates.
+        self.storage.transaction(|tx| {
+            tx.put_node(&rollback_node)?;
+
+            // rollback --rolled_back--> from_version
+            tx.put_edge(&Edge::new(
+                rollback_node.id,
+                prompt_node_id,
+                rels::rolled_back(),
+                1.0,
+                EdgeProvenance::AutoStructural {
+                    rule: "rollback_monitor".into(),
+                },
+            ))?;
+            // rollback --rolled_back_to--> to_version
+            tx.put_edge(&Edge::new(
+                rollback_node.id,
+                to_node_id,
+                rels::rolled_back_to(),
+                1.0,
+                EdgeProvenance::AutoStructural {
+                    rule: "rollback_monitor".into(),
+                },
+            ))?;
+
+            if let Some(ref prompt_node) = tagged_prompt {
+                tx.put_node(prompt_node)?;
+            }
+            if let Some(ref dep) = updated_dep {
+                tx.put_node(dep)?;
+            }
+            for edge in &uses_updates {
+                tx.put_edge(edge)?;
+            }
+            Ok(())
+        })?;
+
         Ok(RollbackResult {
             rollback_node_id: rollback_node.id,
             from_node_id: prompt_node_id,
@@ -720,22 +1190,55 @@ impl<S: Storage> RollbackMonitor<S> {
         })
     }
 
-    fn count_rollbacks(&self, slug: &str, branch: &str) -> Result<u32> {
-        Ok(self.list_rollback_events(slug, branch)?.len() as u32)
+    /// The version `prompt_node_id` supersedes, if any.
+    fn superseded_by(&self, prompt_node_id: NodeId) -> Result<Option<Node>> {
+        let supersedes_rel = rels::supersedes();
+        let prev_id = self
+            .storage
+            .edges_from(prompt_node_id)?
+            .into_iter()
+            .find(|e| e.relation == supersedes_rel)
+            .map(|e| e.to);
+        match prev_id {
+            Some(id) => self.storage.get_node(id),
+            None => Ok(None),
+        }
+    }
+
+    /// Rollbacks recorded for `slug`+`branch`, manual or automatic ones only.
+    fn count_rollbacks(&self, slug: &str, branch: &str, manual: bool) -> Result<u32> {
+        Ok(self
+            .list_rollback_events(slug, branch)?
+            .iter()
+            .filter(|n| {
+                let body =
+                    serde_json::from_str::<serde_json::Value>(&n.data.body).unwrap_or_default();
+                (body["trigger"]["kind"] == "manual") == manual
+            })
+            .count() as u32)
     }
 
     /// List rollback event nodes for `slug`+`branch`, sorted newest-first.
     ///
     /// Uses a combined kind+tag filter so only the small set of `event` nodes
-    /// tagged `"rollback"` are deserialised, rather than the entire event table.
+    /// tagged `"rollback"` are read, and only as headers. Bodies are loaded
+    /// for the events whose title names this `slug`/`branch`, then checked.
     fn list_rollback_events(&self, slug: &str, branch: &str) -> Result<Vec<Node>> {
-        let mut events: Vec<Node> = self
-            .storage
-            .list_nodes(
-                NodeFilter::new()
-                    .with_kinds(vec![kinds::event()])
-                    .with_tags(vec!["rollback".to_string()]),
-            )?
+        let title_prefix = format!("rollback:{}/{}/v", slug, branch);
+        let mut events = Vec::new();
+        for header in self.storage.list_node_headers(
+            NodeFilter::new()
+                .with_kinds(vec![kinds::event()])
+                .with_tags(vec!["rollback".to_string()]),
+        )? {
+            if !header.title.starts_with(&title_prefix) {
+                continue;
+            }
+            if let Some(node) = self.storage.get_node(header.id)? {
+                events.push(node);
+            }
+        }
+        let mut events: Vec<Node> = events
             .into_iter()
             .filter(|n| {
                 serde_json::from_str::<serde_json::Value>(&n.data.body)
@@ -747,7 +1250,7 @@ impl<S: Storage> RollbackMonitor<S> {
                     .unwrap_or(false)
             })
             .collect();
-        events.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        events.sort_by_key(|e| std::cmp::Reverse(e.created_at));
         Ok(events)
     }
 
@@ -772,8 +1275,33 @@ impl<S: Storage> RollbackMonitor<S> {
     }
 }
 
+/// Everything [`RollbackMonitor::apply_rollback`] needs to record one rollback.
+struct RollbackPlan<'a> {
+    slug: &'a str,
+    branch: &'a str,
+    from_node_id: NodeId,
+    from_version: u32,
+    to_node_id: NodeId,
+    to_version: u32,
+    /// Versions between `from` and `to` that are rolled back along with `from`.
+    skipped: Vec<NodeId>,
+    /// Agents whose `uses` edges move from the rolled-back version(s) to `to`.
+    agents: Vec<Node>,
+    /// Deployment event to mark rolled back, if one is being monitored.
+    deployment_node: Option<Node>,
+    trigger: RollbackTrigger,
+}
+
 // ── Pure helpers ───────────────────────────────────────────────────────────────
 
+/// The `version` field of a prompt version node's body, if present.
+fn version_of(n: &Node) -> Option<u32> {
+    serde_json::from_str::<serde_json::Value>(&n.data.body)
+        .ok()
+        .and_then(|b| b["version"].as_u64())
+        .map(|v| v as u32)
+}
+
 /// Return true if node body has `"event_type":"deployment"` and `"status":"monitoring"`.
 fn is_active_deployment(n: &Node) -> bool {
     serde_json::from_str::<serde_json::Value>(&n.data.body)
@@ -803,6 +1331,24 @@ fn parse_active_deployment_info(n: &Node) -> Option<ActiveDeploymentInfo> {
         mean_correction: body["mean_correction"].as_f64().unwrap_or(0.0) as f32,
         mean_sentiment: body["mean_sentiment"].as_f64().unwrap_or(0.5) as f32,
         consecutive_negative: body["consecutive_negative"].as_u64().unwrap_or(0) as u32,
+        rollout_percent: body["rollout_percent"].as_u64().unwrap_or(100).min(100) as u8,
+    })
+}
+
+/// The canary described by an active deployment of `canary_node_id`, if it
+/// is one and has not reached full rollout yet.
+fn parse_canary(n: &Node, canary_node_id: NodeId) -> Option<CanaryRollout> {
+    let body = serde_json::from_str::<serde_json::Value>(&n.data.body).ok()?;
+    let rollout_percent = body["rollout_percent"].as_u64()?;
+    if rollout_percent >= 100 {
+        return None;
+    }
+    let stable_node_id = body["canary_from_node_id"].as_str()?.parse().ok()?;
+    Some(CanaryRollout {
+        deployment_node_id: n.id,
+        canary_node_id,
+        stable_node_id,
+        rollout_percent: rollout_percent as u8,
     })
 }
 
@@ -1418,4 +1964,299 @@ mod tests {
         );
         assert_eq!(events[0].id, rb_event.id);
     }
+
+    /// Extend a two-version chain with v3 and bind agent `kai` to it via `uses`.
+    /// Returns (v1, v2, v3, agent) node IDs.
+    fn create_three_version_chain(
+        storage: &Arc<RedbStorage>,
+        slug: &str,
+    ) -> (NodeId, NodeId, NodeId, NodeId) {
+        use std::collections::HashMap;
+
+        let (v1_id, v2_id) = create_prompt_chain(storage, slug);
+        let v3_content = PromptContent {
+            slug: slug.to_string(),
+            prompt_type: "skill".to_string(),
+            branch: "main".to_string(),
+            version: 3,
+            sections: HashMap::from([(
+                "system".to_string(),
+                serde_json::json!("You are the most helpful assistant."),
+            )]),
+            metadata: Default::default(),
+            override_sections: Default::default(),
+        };
+        let v3_id = PromptResolver::new(storage.clone())
+            .create_version(slug, "main", v3_content, "test")
+            .unwrap();
+
+        let agent = Node::new(
+            kinds::agent(),
+            "kai".to_string(),
+            String::new(),
+            Source {
+                agent: "test".to_string(),
+                session: None,
+                channel: None,
+            },
+            0.5,
+        );
+        storage.put_node(&agent).unwrap();
+        storage
+            .put_edge(&Edge::new(
+                agent.id,
+                v3_id,
+                rels::uses(),
+                0.8,
+                EdgeProvenance::Manual {
+                    created_by: "test".into(),
+                },
+            ))
+            .unwrap();
+
+        (v1_id, v2_id, v3_id, agent.id)
+    }
+
+    fn uses_weight(storage: &Arc<RedbStorage>, agent: NodeId, prompt: NodeId) -> Option<f32> {
+        storage
+            .edges_between(agent, prompt)
+            .unwrap()
+            .into_iter()
+            .find(|e| e.relation == rels::uses())
+            .map(|e| e.weight)
+    }
+
+    #[test]
+    fn rollback_to_steps_back_two_versions() {
+        let (storage, _tmp) = make_storage();
+        let monitor = make_monitor(storage.clone(), RollbackConfig::default());
+        let (v1_id, v2_id, v3_id, agent_id) = create_three_version_chain(&storage, "multi");
+
+        let rb = monitor.rollback_to("multi", "main", 1).unwrap();
+        assert_eq!(rb.from_node_id, v3_id);
+        assert_eq!(rb.from_version, 3);
+        assert_eq!(rb.to_node_id, v1_id);
+        assert_eq!(rb.to_version, 1);
+        assert!(matches!(
+            rb.trigger,
+            RollbackTrigger::Manual {
+                target_version: 1,
+                steps: 2
+            }
+        ));
+
+        // The agent now uses v1 as strongly as it used v3; v3 is depressed.
+        assert_eq!(uses_weight(&storage, agent_id, v1_id), Some(0.8));
+        assert_eq!(uses_weight(&storage, agent_id, v3_id), Some(0.1));
+        assert_eq!(uses_weight(&storage, agent_id, v2_id), None);
+
+        // Same audit trail as an automatic rollback.
+        let rb_edges = storage.edges_from(rb.rollback_node_id).unwrap();
+        assert!(rb_edges
+            .iter()
+            .any(|e| e.to == v3_id && e.relation == rels::rolled_back()));
+        assert!(rb_edges
+            .iter()
+            .any(|e| e.to == v1_id && e.relation == rels::rolled_back_to()));
+        let v3 = storage.get_node(v3_id).unwrap().unwrap();
+        assert!(v3.data.tags.contains(&"rolled-back".to_string()));
+
+        let status = monitor.get_status("multi", "main").unwrap().unwrap();
+        assert_eq!(status.rollback_count, 1);
+        assert_eq!(status.recent_rollbacks[0].trigger, "manual");
+    }
+
+    #[test]
+    fn manual_rollbacks_do_not_escalate_automatic_ones() {
+        let (storage, _tmp) = make_storage();
+        let config = RollbackConfig {
+            max_rollbacks_before_quarantine: 2,
+            ..RollbackConfig::default()
+        };
+        let monitor = make_monitor(storage.clone(), config);
+        let (_v1_id, _v2_id, v3_id, _agent_id) = create_three_version_chain(&storage, "manual");
+
+        let rb = monitor.rollback_to("manual", "main", 2).unwrap();
+        assert!(!rb.is_quarantined);
+        assert_eq!(rb.cooldown_hours, 0);
+        assert!(!monitor.is_in_cooldown(v3_id).unwrap());
+        let rb = monitor.rollback_to("manual", "main", 1).unwrap();
+        assert_eq!(rb.rollback_count, 2);
+        assert!(!rb.is_quarantined, "manual rollbacks never quarantine");
+        assert_eq!(monitor.count_rollbacks("manual", "main", false).unwrap(), 0);
+    }
+
+    #[test]
+    fn rollback_to_rejects_quarantined_or_later_target() {
+        let (storage, _tmp) = make_storage();
+        let monitor = make_monitor(storage.clone(), RollbackConfig::default());
+        let (v1_id, _v2_id, _v3_id, _agent_id) = create_three_version_chain(&storage, "guarded");
+
+        assert!(monitor.rollback_to("guarded", "main", 3).is_err());
+        assert!(monitor.rollback_to("guarded", "main", 7).is_err());
+        assert!(monitor.rollback_to("missing", "main", 1).is_err());
+
+        let mut v1 = storage.get_node(v1_id).unwrap().unwrap();
+        v1.data.tags.push("quarantined".to_string());
+        storage.put_node(&v1).unwrap();
+
+        let err = monitor.rollback_to("guarded", "main", 1).unwrap_err();
+        assert!(err.to_string().contains("quarantined"), "{err}");
+        // Nothing was recorded for the refused rollback.
+        assert!(monitor
+            .list_rollback_events("guarded", "main")
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Bind agent `kai` to v1 of a two-version chain and deploy v2 as a
+    /// canary. Returns (v1, v2, agent, deployment) node IDs.
+    fn deploy_canary(
+        storage: &Arc<RedbStorage>,
+        monitor: &RollbackMonitor<RedbStorage>,
+        slug: &str,
+        rollout_percent: u8,
+    ) -> (NodeId, NodeId, NodeId, NodeId) {
+        let (v1_id, v2_id) = create_prompt_chain(storage, slug);
+        let agent = Node::new(
+            kinds::agent(),
+            "kai".to_string(),
+            String::new(),
+            Source {
+                agent: "test".to_string(),
+                session: None,
+                channel: None,
+            },
+            0.5,
+        );
+        storage.put_node(&agent).unwrap();
+        storage
+            .put_edge(&Edge::new(
+                agent.id,
+                v1_id,
+                rels::uses(),
+                0.8,
+                EdgeProvenance::Manual {
+                    created_by: "test".into(),
+                },
+            ))
+            .unwrap();
+        let dep_id = monitor
+            .record_canary_deployment(
+                slug,
+                "main",
+                2,
+                v2_id,
+                "kai",
+                vec![(0.1, 0.8)],
+                rollout_percent,
+            )
+            .unwrap();
+        (v1_id, v2_id, agent.id, dep_id)
+    }
+
+    #[test]
+    fn canary_routes_rollout_share_to_new_version() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let (storage, _tmp) = make_storage();
+        let monitor = make_monitor(storage.clone(), RollbackConfig::default());
+        let (v1_id, v2_id, agent_id, dep_id) = deploy_canary(&storage, &monitor, "canary", 30);
+
+        // The agent can now select either version, at the same weight.
+        assert_eq!(uses_weight(&storage, agent_id, v2_id), Some(0.8));
+
+        // Both sides of the canary resolve to the same rollout.
+        let canary = monitor.active_canary(v1_id).unwrap().unwrap();
+        assert_eq!(canary.deployment_node_id, dep_id);
+        assert_eq!(canary.canary_node_id, v2_id);
+        assert_eq!(canary.stable_node_id, v1_id);
+        assert_eq!(canary.rollout_percent, 30);
+        let from_canary = monitor.active_canary(v2_id).unwrap().unwrap();
+        assert_eq!(from_canary.deployment_node_id, dep_id);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let routed_to_canary = (0..2000)
+            .filter(|_| canary.route(&mut rng) == v2_id)
+            .count();
+        assert!(
+            (500..700).contains(&routed_to_canary),
+            "expected ~30% canary traffic, got {routed_to_canary}/2000"
+        );
+
+        // Plain deployments are not canaries.
+        let (_w1, w2) = create_prompt_chain(&storage, "plain");
+        monitor
+            .record_deployment("plain", "main", 2, w2, "kai", vec![(0.1, 0.8)])
+            .unwrap();
+        assert!(monitor.active_canary(w2).unwrap().is_none());
+    }
+
+    #[test]
+    fn canary_ramps_up_and_promotes_on_clean_observations() {
+        let (storage, _tmp) = make_storage();
+        let cfg = RollbackConfig {
+            canary_ramp_interval: 2,
+            canary_ramp_step: 50,
+            ..Default::default()
+        };
+        let monitor = make_monitor(storage.clone(), cfg);
+        let (v1_id, v2_id, agent_id, _dep_id) = deploy_canary(&storage, &monitor, "ramp", 10);
+
+        for _ in 0..2 {
+            let obs_id = make_obs_node(&storage);
+            monitor
+                .process_observation(obs_id, v2_id, 0.1, 0.85, 0.9)
+                .unwrap();
+        }
+        let canary = monitor.active_canary(v1_id).unwrap().unwrap();
+        assert_eq!(canary.rollout_percent, 60);
+
+        for _ in 0..2 {
+            let obs_id = make_obs_node(&storage);
+            monitor
+                .process_observation(obs_id, v2_id, 0.1, 0.85, 0.9)
+                .unwrap();
+        }
+        // Full rollout: routing stops and the agent moves onto v2.
+        assert!(monitor.active_canary(v1_id).unwrap().is_none());
+        assert_eq!(uses_weight(&storage, agent_id, v2_id), Some(0.8));
+        assert_eq!(uses_weight(&storage, agent_id, v1_id), Some(0.1));
+    }
+
+    #[test]
+    fn canary_aborts_when_rollback_fires() {
+        let (storage, _tmp) = make_storage();
+        let cfg = RollbackConfig {
+            min_samples_before_check: 1,
+            consecutive_negative_limit: 3,
+            correction_rate_rollback: 99.0, // disable sigma check
+            absolute_correction_increase: 99.0,
+            sentiment_rollback: 99.0,
+            ..Default::default()
+        };
+        let monitor = make_monitor(storage.clone(), cfg);
+        let (v1_id, v2_id, agent_id, dep_id) = deploy_canary(&storage, &monitor, "abort", 20);
+
+        let mut result = None;
+        for _ in 0..3 {
+            let obs_id = make_obs_node(&storage);
+            result = monitor
+                .process_observation(obs_id, v2_id, 0.9, 0.2, 0.1)
+                .unwrap();
+        }
+        let rb = result.expect("degraded canary must roll back");
+        assert_eq!(rb.from_node_id, v2_id);
+        assert_eq!(rb.to_node_id, v1_id);
+
+        // The rollout is aborted and all traffic returns to v1.
+        assert!(monitor.active_canary(v1_id).unwrap().is_none());
+        let dep = storage.get_node(dep_id).unwrap().unwrap();
+        let body: serde_json::Value = serde_json::from_str(&dep.data.body).unwrap();
+        assert_eq!(body["status"], "rolled_back");
+        assert_eq!(body["rollout_percent"], 0);
+        assert_eq!(body["canary_aborted"], true);
+        assert_eq!(uses_weight(&storage, agent_id, v2_id), Some(0.1));
+        assert_eq!(uses_weight(&storage, agent_id, v1_id), Some(0.8));
+    }
 }
@@ -4,6 +4,12 @@ use std::collections::HashMap;
 /// Blend weight between historical edge weight and context fit (50/50).
 const BLEND: f32 = 0.5;
 
+/// Task types with a `task_<type>` signal and a slot in [`ContextSignals::features`].
+pub const TASK_TYPES: [&str; 5] = ["coding", "planning", "casual", "crisis", "reflection"];
+
+/// Length of [`ContextSignals::features`].
+pub const FEATURE_COUNT: usize = 10;
+
 /// Context signals extracted from the current conversation session.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextSignals {
@@ -94,7 +100,7 @@ impl ContextSignals {
         m.insert("topic_shift_high".into(), self.topic_shift);
         m.insert("energy_high".into(), self.energy);
         // Task-type one-hot
-        for tt in ["coding", "planning", "casual", "crisis", "reflection"] {
+        for tt in TASK_TYPES {
             m.insert(
                 format!("task_{tt}"),
                 if tt.eq_ignore_ascii_case(&self.task_type) {
@@ -106,6 +112,25 @@ impl ContextSignals {
         }
         m
     }
+
+    /// The signals as a feature vector for [`LinUcbArm`]: a constant 1.0
+    /// bias, sentiment, the task type one-hot over [`TASK_TYPES`], correction
+    /// rate, topic shift and energy. An unknown task type leaves the one-hot
+    /// all zero.
+    pub fn features(&self) -> [f64; FEATURE_COUNT] {
+        let mut x = [0.0; FEATURE_COUNT];
+        x[0] = 1.0;
+        x[1] = self.sentiment as f64;
+        for (i, tt) in TASK_TYPES.iter().enumerate() {
+            if tt.eq_ignore_ascii_case(&self.task_type) {
+                x[2 + i] = 1.0;
+            }
+        }
+        x[7] = self.correction_rate as f64;
+        x[8] = self.topic_shift as f64;
+        x[9] = self.energy as f64;
+        x
+    }
 }
 
 /// Compute the normalised context fit for a prompt variant.
@@ -189,8 +214,406 @@ pub fn observation_score(sentiment: f32, correction_count: u32, task_outcome: &s
 /// After ~22 observations from a neutral start (0.5) with perfect scores,
 /// the weight converges to ~0.9.
 pub fn update_edge_weight(old_weight: f32, obs_score: f32) -> f32 {
-    const ALPHA: f32 = 0.1;
-    (ALPHA.mul_add(obs_score - old_weight, old_weight)).clamp(0.0, 1.0)
+    (EMA_ALPHA.mul_add(obs_score - old_weight, old_weight)).clamp(0.0, 1.0)
+}
+
+/// EMA smoothing factor for [`update_edge_weight`].
+const EMA_ALPHA: f32 = 0.1;
+
+/// Configuration for prompt variant scoring (`[prompt_selection]` in cortex.toml).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SelectionConfig {
+    /// Age in hours at which an observation counts half as much as a fresh one.
+    /// `0` disables decay, so every observation counts equally.
+    pub observation_half_life_hours: f64,
+    /// Width of the LinUCB confidence bonus. Higher explores more.
+    pub linucb_alpha: f64,
+}
+
+impl Default for SelectionConfig {
+    fn default() -> Self {
+        Self {
+            observation_half_life_hours: 0.0,
+            linucb_alpha: 0.5,
+        }
+    }
+}
+
+/// Weight of an observation `age` old under a `half_life_hours` half-life.
+///
+/// Returns 1.0 for fresh (or future-dated) observations and when decay is
+/// disabled (`half_life_hours <= 0`), halving every `half_life_hours` after that.
+pub fn decay_weight(age: chrono::Duration, half_life_hours: f64) -> f64 {
+    if half_life_hours <= 0.0 {
+        return 1.0;
+    }
+    let age_hours = age.num_seconds().max(0) as f64 / 3600.0;
+    0.5f64.powf(age_hours / half_life_hours)
+}
+
+/// [`update_edge_weight`] for an edge last updated `elapsed` ago.
+///
+/// The old weight summarises observations at least `elapsed` old, so its share
+/// of the EMA decays by [`decay_weight`] before the new score is blended in.
+/// With no elapsed time (or decay disabled) this matches `update_edge_weight`;
+/// after many half-lives the new observation dominates.
+pub fn update_edge_weight_decayed(
+    old_weight: f32,
+    obs_score: f32,
+    elapsed: chrono::Duration,
+    half_life_hours: f64,
+) -> f32 {
+    let retained = (1.0 - EMA_ALPHA) * decay_weight(elapsed, half_life_hours) as f32;
+    ((1.0 - retained).mul_add(obs_score - old_weight, old_weight)).clamp(0.0, 1.0)
+}
+
+/// How `active-variant` trades exploring variants against exploiting the best one.
+///
+/// Deserializing goes through [`FromStr`](std::str::FromStr), so config, query
+/// strings and the CLI all accept the same spellings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", try_from = "String")]
+pub enum SelectionStrategy {
+    /// With probability `epsilon` pick uniformly at random, otherwise the top score.
+    #[default]
+    EpsilonGreedy,
+    /// Sample each variant's success rate from its Beta posterior and pick the
+    /// highest draw, so exploration concentrates on variants that might still win.
+    ThompsonSampling,
+    /// LinUCB contextual bandit: learn per-variant linear weights over the
+    /// context features and pick the highest upper confidence bound, so the
+    /// choice can differ by task type, sentiment and so on.
+    #[serde(rename = "linucb")]
+    LinUcb,
+}
+
+/// Every spelling [`SelectionStrategy`] parses from, canonical name first.
+const STRATEGY_NAMES: &[(&str, SelectionStrategy)] = &[
+    ("epsilon_greedy", SelectionStrategy::EpsilonGreedy),
+    ("epsilon-greedy", SelectionStrategy::EpsilonGreedy),
+    ("thompson_sampling", SelectionStrategy::ThompsonSampling),
+    ("thompson-sampling", SelectionStrategy::ThompsonSampling),
+    ("thompson", SelectionStrategy::ThompsonSampling),
+    ("linucb", SelectionStrategy::LinUcb),
+    ("lin_ucb", SelectionStrategy::LinUcb),
+    ("lin-ucb", SelectionStrategy::LinUcb),
+];
+
+impl SelectionStrategy {
+    pub const ALL: [Self; 3] = [Self::EpsilonGreedy, Self::ThompsonSampling, Self::LinUcb];
+
+    /// The canonical name, as serialized.
+    pub fn as_str(self) -> &'static str {
+        self.names().next().unwrap_or_default()
+    }
+
+    /// Other spellings accepted when parsing.
+    pub fn aliases(self) -> impl Iterator<Item = &'static str> {
+        self.names().skip(1)
+    }
+
+    fn names(self) -> impl Iterator<Item = &'static str> {
+        STRATEGY_NAMES
+            .iter()
+            .filter(move |(_, s)| *s == self)
+            .map(|(name, _)| *name)
+    }
+}
+
+impl std::fmt::Display for SelectionStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<String> for SelectionStrategy {
+    type Error = crate::error::CortexError;
+
+    fn try_from(s: String) -> crate::error::Result<Self> {
+        s.parse()
+    }
+}
+
+impl std::str::FromStr for SelectionStrategy {
+    type Err = crate::error::CortexError;
+
+    fn from_str(s: &str) -> crate::error::Result<Self> {
+        STRATEGY_NAMES
+            .iter()
+            .find(|(name, _)| *name == s)
+            .map(|(_, strategy)| *strategy)
+            .ok_or_else(|| {
+                crate::error::CortexError::Validation(format!(
+                    "Unknown selection strategy '{}' (expected epsilon_greedy, thompson_sampling or linucb)",
+                    s
+                ))
+            })
+    }
+}
+
+/// Observation evidence for one variant, as pseudo-counts of a Beta posterior.
+///
+/// Each observation contributes its score to `successes` and the remainder to
+/// `failures`, so a 0.8 observation counts as 0.8 of a success.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VariantEvidence {
+    pub successes: f64,
+    pub failures: f64,
+}
+
+impl VariantEvidence {
+    /// Build evidence from observation scores in \[0.0, 1.0\].
+    pub fn from_scores(scores: impl IntoIterator<Item = f64>) -> Self {
+        Self::from_weighted_scores(scores.into_iter().map(|s| (s, 1.0)))
+    }
+
+    /// Build evidence from `(score, weight)` pairs, e.g. weighted by [`decay_weight`].
+    /// An observation of weight 0.5 counts as half an observation.
+    pub fn from_weighted_scores(scores: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        scores.into_iter().fold(Self::default(), |mut acc, (s, w)| {
+            let s = s.clamp(0.0, 1.0);
+            let w = w.max(0.0);
+            acc.successes += s * w;
+            acc.failures += (1.0 - s) * w;
+            acc
+        })
+    }
+
+    /// Draw a success rate from Beta(1 + successes, 1 + failures).
+    /// The uniform prior means a variant with no observations samples anywhere in \[0, 1\].
+    pub fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        sample_beta(rng, 1.0 + self.successes, 1.0 + self.failures)
+    }
+}
+
+/// Epsilon-greedy choice over `scores`: explore uniformly with probability
+/// `epsilon`, otherwise take the highest score. Returns `None` when empty.
+pub fn select_epsilon_greedy<R: rand::Rng + ?Sized>(
+    scores: &[f32],
+    epsilon: f32,
+    rng: &mut R,
+) -> Option<usize> {
+    if scores.is_empty() {
+        return None;
+    }
+    if rng.gen::<f32>() < epsilon.clamp(0.0, 1.0) {
+        return Some(rng.gen_range(0..scores.len()));
+    }
+    argmax(scores.iter().copied())
+}
+
+/// Thompson sampling over `arms`: draw one success rate per variant, blend it
+/// with that variant's context fit the same way [`score_variant`] blends edge
+/// weight, and pick the highest. `context_fits[i]` belongs to `arms[i]`; missing
+/// entries mean no context weights.
+///
+/// Returns the chosen index and every blended draw, or `None` when `arms` is empty.
+pub fn select_thompson<R: rand::Rng + ?Sized>(
+    arms: &[VariantEvidence],
+    context_fits: &[Option<f32>],
+    rng: &mut R,
+) -> Option<(usize, Vec<f32>)> {
+    let draws: Vec<f32> = arms
+        .iter()
+        .enumerate()
+        .map(|(i, arm)| {
+            let draw = arm.sample(rng) as f32;
+            match context_fits.get(i).copied().flatten() {
+                None => draw,
+                Some(fit) => (BLEND * draw + (1.0 - BLEND) * fit).clamp(0.0, 1.0),
+            }
+        })
+        .collect();
+    argmax(draws.iter().copied()).map(|i| (i, draws))
+}
+
+/// One variant's LinUCB state: a ridge regression of observation score on
+/// [`ContextSignals::features`], kept as the weighted sums `G = Σ w·x·xᵀ` and
+/// `b = Σ w·score·x`. Each observation adds to both in O(d²) and
+/// [`decay`](Self::decay) scales both, so a stored arm can be aged and
+/// folded forward one observation at a time. The design matrix `A = I + G`
+/// is only factored when an estimate or bound is asked for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinUcbArm {
+    /// G, row-major.
+    gram: [[f64; FEATURE_COUNT]; FEATURE_COUNT],
+    b: [f64; FEATURE_COUNT],
+}
+
+impl Default for LinUcbArm {
+    fn default() -> Self {
+        Self {
+            gram: [[0.0; FEATURE_COUNT]; FEATURE_COUNT],
+            b: [0.0; FEATURE_COUNT],
+        }
+    }
+}
+
+impl LinUcbArm {
+    /// An arm with no observations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold in an observation `score` in \[0.0, 1.0\] seen under `signals`,
+    /// counted `weight` times (e.g. its [`decay_weight`]).
+    pub fn observe(&mut self, signals: &ContextSignals, score: f64, weight: f64) {
+        let w = weight.max(0.0);
+        if w == 0.0 {
+            return;
+        }
+        let x = signals.features();
+        for (row, xi) in self.gram.iter_mut().zip(x) {
+            for (cell, xj) in row.iter_mut().zip(x) {
+                *cell += w * xi * xj;
+            }
+        }
+        let score = score.clamp(0.0, 1.0);
+        for (b, xi) in self.b.iter_mut().zip(x) {
+            *b += w * score * xi;
+        }
+    }
+
+    /// Scale every observation folded in so far by `weight` in \[0.0, 1.0\],
+    /// e.g. the [`decay_weight`] of the time since the arm was last updated.
+    pub fn decay(&mut self, weight: f64) {
+        let w = weight.clamp(0.0, 1.0);
+        for cell in self.gram.iter_mut().flatten() {
+            *cell *= w;
+        }
+        for b in &mut self.b {
+            *b *= w;
+        }
+    }
+
+    /// Expected score under `signals` from the weights learned so far.
+    pub fn estimate(&self, signals: &ContextSignals) -> f64 {
+        let l = self.cholesky();
+        let theta = backward(&l, &forward(&l, &self.b));
+        dot(&theta, &signals.features())
+    }
+
+    /// [`estimate`](Self::estimate) plus `alpha` times its standard error, which
+    /// shrinks as observations in similar contexts accumulate.
+    pub fn upper_bound(&self, signals: &ContextSignals, alpha: f64) -> f64 {
+        let x = signals.features();
+        let l = self.cholesky();
+        let theta = backward(&l, &forward(&l, &self.b));
+        // xᵀ·A⁻¹·x = |L⁻¹·x|²
+        let z = forward(&l, &x);
+        dot(&theta, &x) + alpha * dot(&z, &z).sqrt()
+    }
+
+    /// Lower-triangular `L` with `L·Lᵀ = I + G`. `G` is positive
+    /// semi-definite, so every pivot is at least 1.
+    fn cholesky(&self) -> [[f64; FEATURE_COUNT]; FEATURE_COUNT] {
+        let mut l = [[0.0; FEATURE_COUNT]; FEATURE_COUNT];
+        for i in 0..FEATURE_COUNT {
+            for j in 0..=i {
+                let a = self.gram[i][j] + if i == j { 1.0 } else { 0.0 };
+                let sum = a - (0..j).map(|k| l[i][k] * l[j][k]).sum::<f64>();
+                l[i][j] = if i == j {
+                    sum.max(f64::MIN_POSITIVE).sqrt()
+                } else {
+                    sum / l[j][j]
+                };
+            }
+        }
+        l
+    }
+}
+
+/// Solve `L·y = v` for lower-triangular `l`.
+fn forward(
+    l: &[[f64; FEATURE_COUNT]; FEATURE_COUNT],
+    v: &[f64; FEATURE_COUNT],
+) -> [f64; FEATURE_COUNT] {
+    let mut y = [0.0; FEATURE_COUNT];
+    for i in 0..FEATURE_COUNT {
+        let sum = v[i] - (0..i).map(|k| l[i][k] * y[k]).sum::<f64>();
+        y[i] = sum / l[i][i];
+    }
+    y
+}
+
+/// Solve `Lᵀ·x = y` for lower-triangular `l`.
+fn backward(
+    l: &[[f64; FEATURE_COUNT]; FEATURE_COUNT],
+    y: &[f64; FEATURE_COUNT],
+) -> [f64; FEATURE_COUNT] {
+    let mut x = [0.0; FEATURE_COUNT];
+    for i in (0..FEATURE_COUNT).rev() {
+        let sum = y[i] - (i + 1..FEATURE_COUNT).map(|k| l[k][i] * x[k]).sum::<f64>();
+        x[i] = sum / l[i][i];
+    }
+    x
+}
+
+fn dot(a: &[f64; FEATURE_COUNT], b: &[f64; FEATURE_COUNT]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// LinUCB choice over `arms` for the current `signals`: the arm with the
+/// highest upper confidence bound. Returns the chosen index and every bound,
+/// or `None` when `arms` is empty.
+pub fn select_linucb(
+    arms: &[LinUcbArm],
+    signals: &ContextSignals,
+    alpha: f64,
+) -> Option<(usize, Vec<f32>)> {
+    let bounds: Vec<f32> = arms
+        .iter()
+        .map(|arm| arm.upper_bound(signals, alpha) as f32)
+        .collect();
+    argmax(bounds.iter().copied()).map(|i| (i, bounds))
+}
+
+fn argmax<T: PartialOrd>(values: impl Iterator<Item = T>) -> Option<usize> {
+    values
+        .enumerate()
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(i, _)| i)
+}
+
+/// Beta(alpha, beta) via two Gamma draws.
+fn sample_beta<R: rand::Rng + ?Sized>(rng: &mut R, alpha: f64, beta: f64) -> f64 {
+    let x = sample_gamma(rng, alpha);
+    let y = sample_gamma(rng, beta);
+    if x + y <= 0.0 {
+        0.5
+    } else {
+        x / (x + y)
+    }
+}
+
+/// Gamma(shape, 1) using Marsaglia & Tsang's method. Requires `shape > 0`.
+fn sample_gamma<R: rand::Rng + ?Sized>(rng: &mut R, shape: f64) -> f64 {
+    if shape < 1.0 {
+        // Boost to shape + 1 and scale back down by U^(1/shape)
+        let u: f64 = rng.gen();
+        return sample_gamma(rng, shape + 1.0) * u.powf(1.0 / shape);
+    }
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = sample_standard_normal(rng);
+        let v = (1.0 + c * x).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u: f64 = rng.gen();
+        if u.ln() < 0.5 * x * x + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
+
+/// Standard normal via Box–Muller.
+fn sample_standard_normal<R: rand::Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>(); // (0, 1] so ln() is finite
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
 }
 
 #[cfg(test)]
@@ -418,7 +841,7 @@ mod tests {
         };
         let cw = serde_json::json!({ "energy_high": 1.0 });
         let score = score_variant(1.0, Some(&cw), &signals);
-        assert!(score <= 1.0 && score >= 0.0, "out of range: {score}");
+        assert!((0.0..=1.0).contains(&score), "out of range: {score}");
     }
 
     // ── observation_score ─────────────────────────────────────────────────────
@@ -529,6 +952,240 @@ mod tests {
         assert!((w - 0.3).abs() < 0.01, "failed to converge to 0.3: {w}");
     }
 
+    // ── Observation decay ─────────────────────────────────────────────────────
+
+    #[test]
+    fn decay_weight_halves_each_half_life() {
+        let hl = 24.0;
+        assert!((decay_weight(chrono::Duration::zero(), hl) - 1.0).abs() < 1e-9);
+        assert!((decay_weight(chrono::Duration::hours(24), hl) - 0.5).abs() < 1e-9);
+        assert!((decay_weight(chrono::Duration::hours(72), hl) - 0.125).abs() < 1e-9);
+        // Future-dated observations are treated as fresh
+        assert!((decay_weight(chrono::Duration::hours(-5), hl) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn decay_weight_disabled_by_zero_half_life() {
+        assert_eq!(decay_weight(chrono::Duration::days(365), 0.0), 1.0);
+    }
+
+    #[test]
+    fn update_edge_weight_decayed_matches_plain_when_fresh() {
+        let plain = update_edge_weight(0.8, 0.2);
+        let decayed = update_edge_weight_decayed(0.8, 0.2, chrono::Duration::zero(), 168.0);
+        assert!((plain - decayed).abs() < 1e-6);
+    }
+
+    #[test]
+    fn update_edge_weight_decayed_favours_new_score_after_long_gap() {
+        // A stale good weight should move much further toward a new bad score
+        let fresh = update_edge_weight_decayed(0.9, 0.1, chrono::Duration::hours(1), 168.0);
+        let stale = update_edge_weight_decayed(0.9, 0.1, chrono::Duration::days(60), 168.0);
+        assert!(stale < fresh, "stale={stale} fresh={fresh}");
+        assert!(
+            stale < 0.2,
+            "stale weight should be near the new score: {stale}"
+        );
+    }
+
+    #[test]
+    fn variant_evidence_weighted_scores() {
+        let ev = VariantEvidence::from_weighted_scores([(1.0, 0.5), (0.0, 2.0)]);
+        assert!((ev.successes - 0.5).abs() < 1e-9);
+        assert!((ev.failures - 2.0).abs() < 1e-9);
+    }
+
+    // ── Variant selection strategies ──────────────────────────────────────────
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn evidence(successes: f64, failures: f64) -> VariantEvidence {
+        VariantEvidence {
+            successes,
+            failures,
+        }
+    }
+
+    #[test]
+    fn selection_strategy_parses() {
+        assert_eq!(
+            "thompson_sampling".parse::<SelectionStrategy>().unwrap(),
+            SelectionStrategy::ThompsonSampling
+        );
+        assert_eq!(
+            "epsilon-greedy".parse::<SelectionStrategy>().unwrap(),
+            SelectionStrategy::EpsilonGreedy
+        );
+        assert_eq!(
+            "linucb".parse::<SelectionStrategy>().unwrap(),
+            SelectionStrategy::LinUcb
+        );
+        assert!("softmax".parse::<SelectionStrategy>().is_err());
+    }
+
+    #[test]
+    fn selection_strategy_serde_matches_from_str() {
+        for strategy in SelectionStrategy::ALL {
+            let json = serde_json::to_value(strategy).unwrap();
+            assert_eq!(json, strategy.as_str());
+            assert_eq!(
+                strategy.as_str().parse::<SelectionStrategy>().unwrap(),
+                strategy
+            );
+            assert_eq!(
+                serde_json::from_value::<SelectionStrategy>(json).unwrap(),
+                strategy
+            );
+        }
+        assert_eq!(
+            serde_json::from_str::<SelectionStrategy>("\"thompson\"").unwrap(),
+            SelectionStrategy::ThompsonSampling
+        );
+        assert!(serde_json::from_str::<SelectionStrategy>("\"softmax\"").is_err());
+    }
+
+    #[test]
+    fn variant_evidence_from_scores_splits_each_observation() {
+        let ev = VariantEvidence::from_scores([1.0, 0.25, 0.0]);
+        assert!((ev.successes - 1.25).abs() < 1e-9);
+        assert!((ev.failures - 1.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn epsilon_greedy_zero_epsilon_exploits() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..100 {
+            assert_eq!(
+                select_epsilon_greedy(&[0.2, 0.9, 0.5], 0.0, &mut rng),
+                Some(1)
+            );
+        }
+        assert_eq!(select_epsilon_greedy(&[], 0.5, &mut rng), None);
+    }
+
+    #[test]
+    fn thompson_superior_variant_wins_large_majority() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let arms = [
+            evidence(45.0, 5.0),
+            evidence(10.0, 40.0),
+            evidence(12.0, 38.0),
+        ];
+        let wins = (0..1000)
+            .filter(|_| select_thompson(&arms, &[], &mut rng).unwrap().0 == 0)
+            .count();
+        assert!(wins > 950, "superior variant won only {wins}/1000");
+    }
+
+    #[test]
+    fn thompson_near_tie_keeps_exploring() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let arms = [evidence(10.0, 10.0), evidence(9.0, 11.0)];
+        let first = (0..1000)
+            .filter(|_| select_thompson(&arms, &[], &mut rng).unwrap().0 == 0)
+            .count();
+        assert!(
+            (300..=800).contains(&first),
+            "near tie should split selections, first won {first}/1000"
+        );
+    }
+
+    #[test]
+    fn thompson_draws_stay_in_unit_interval() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let arms = [evidence(0.0, 0.0), evidence(0.3, 0.0), evidence(500.0, 1.0)];
+        for _ in 0..200 {
+            let (_, draws) = select_thompson(&arms, &[], &mut rng).unwrap();
+            assert!(draws.iter().all(|d| (0.0..=1.0).contains(d)), "{draws:?}");
+        }
+        assert!(select_thompson(&[], &[], &mut rng).is_none());
+    }
+
+    fn task(task_type: &str) -> ContextSignals {
+        ContextSignals {
+            task_type: task_type.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn linucb_learns_to_route_by_task_type() {
+        let (coding, crisis) = (task("coding"), task("crisis"));
+        // Variant 0 suits coding, variant 1 suits crisis
+        let score = |arm: usize, signals: &ContextSignals| match (arm, signals.task_type.as_str()) {
+            (0, "coding") | (1, "crisis") => 0.9,
+            _ => 0.2,
+        };
+
+        let mut arms = vec![LinUcbArm::new(), LinUcbArm::new()];
+        let mut late_correct = 0;
+        for round in 0..200 {
+            let (signals, best) = if round % 2 == 0 {
+                (&coding, 0)
+            } else {
+                (&crisis, 1)
+            };
+            let (chosen, _) = select_linucb(&arms, signals, 0.5).unwrap();
+            if round >= 150 && chosen == best {
+                late_correct += 1;
+            }
+            arms[chosen].observe(signals, score(chosen, signals), 1.0);
+        }
+
+        assert_eq!(late_correct, 50);
+        assert_eq!(select_linucb(&arms, &coding, 0.5).unwrap().0, 0);
+        assert_eq!(select_linucb(&arms, &crisis, 0.5).unwrap().0, 1);
+        assert!((arms[0].estimate(&coding) - 0.9).abs() < 0.05);
+        assert!(arms[0].estimate(&crisis) < arms[0].estimate(&coding));
+    }
+
+    #[test]
+    fn linucb_bound_narrows_with_observations() {
+        let signals = task("planning");
+        let mut arm = LinUcbArm::new();
+        assert_eq!(arm.estimate(&signals), 0.0);
+        let fresh = arm.upper_bound(&signals, 1.0);
+        for _ in 0..20 {
+            arm.observe(&signals, 0.6, 1.0);
+        }
+        // Zero-weight observations change nothing
+        let before = arm.clone();
+        arm.observe(&signals, 1.0, 0.0);
+        assert_eq!(arm, before);
+
+        let width = arm.upper_bound(&signals, 1.0) - arm.estimate(&signals);
+        assert!(width < fresh / 4.0, "width {width} vs fresh {fresh}");
+        assert!((arm.estimate(&signals) - 0.6).abs() < 0.05);
+        assert!(select_linucb(&[], &signals, 1.0).is_none());
+    }
+
+    #[test]
+    fn linucb_decay_matches_weighted_observations() {
+        let (coding, crisis) = (task("coding"), task("crisis"));
+        // Folding in observations and decaying between them...
+        let mut aged = LinUcbArm::new();
+        aged.observe(&coding, 0.9, 1.0);
+        aged.decay(0.5);
+        aged.observe(&crisis, 0.2, 1.0);
+        aged.decay(0.25);
+        // ...matches weighting each by its total decay up front
+        let mut direct = LinUcbArm::new();
+        direct.observe(&coding, 0.9, 0.125);
+        direct.observe(&crisis, 0.2, 0.25);
+        for signals in [&coding, &crisis] {
+            assert!(
+                (aged.upper_bound(signals, 1.0) - direct.upper_bound(signals, 1.0)).abs() < 1e-12
+            );
+        }
+
+        // Fully decayed, the arm is as uncertain as a fresh one
+        aged.decay(0.0);
+        assert_eq!(aged, LinUcbArm::new());
+
+        let json = serde_json::to_value(&direct).unwrap();
+        assert_eq!(serde_json::from_value::<LinUcbArm>(json).unwrap(), direct);
+    }
+
     // ── to_signal_map (regression) ────────────────────────────────────────────
 
     #[test]
@@ -0,0 +1,123 @@
+//! `{{variable}}` interpolation for prompt sections.
+//!
+//! A placeholder is `{{name}}`, optionally padded with spaces, where `name`
+//! is made of letters, digits, `_`, `-` and `.`. A backslash before the
+//! opening braces (`\{{name}}`) emits them literally. Anything else between
+//! braces is left as-is.
+
+use std::collections::HashMap;
+
+use crate::error::{CortexError, Result};
+
+/// Replace the placeholders in `text` with values from `vars`.
+///
+/// String values are inserted verbatim and other JSON values in their JSON
+/// form. A placeholder with no value is an error when `strict`, otherwise
+/// it is left verbatim.
+pub fn render(
+    text: &str,
+    vars: &HashMap<String, serde_json::Value>,
+    strict: bool,
+) -> Result<String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find("{{") {
+        if rest[..pos].ends_with('\\') {
+            out.push_str(&rest[..pos - 1]);
+            out.push_str("{{");
+            rest = &rest[pos + 2..];
+            continue;
+        }
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[pos..]);
+            return Ok(out);
+        };
+        let name = after[..end].trim();
+        if !is_variable_name(name) {
+            out.push_str("{{");
+            rest = after;
+            continue;
+        }
+        match vars.get(name) {
+            Some(serde_json::Value::String(s)) => out.push_str(s),
+            Some(value) => out.push_str(&value.to_string()),
+            None if strict => {
+                return Err(CortexError::Validation(format!(
+                    "Unresolved template variable '{}'",
+                    name
+                )))
+            }
+            None => out.push_str(&rest[pos..pos + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Apply [`render`] to every string inside a section value.
+pub fn render_value(
+    value: &serde_json::Value,
+    vars: &HashMap<String, serde_json::Value>,
+    strict: bool,
+) -> Result<serde_json::Value> {
+    Ok(match value {
+        serde_json::Value::String(s) => serde_json::Value::String(render(s, vars, strict)?),
+        serde_json::Value::Array(items) => serde_json::Value::Array(
+            items
+                .iter()
+                .map(|v| render_value(v, vars, strict))
+                .collect::<Result<_>>()?,
+        ),
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(k, v)| Ok((k.clone(), render_value(v, vars, strict)?)))
+                .collect::<Result<_>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> HashMap<String, serde_json::Value> {
+        HashMap::from([
+            ("agent".to_string(), serde_json::json!("kai")),
+            ("max_tokens".to_string(), serde_json::json!(512)),
+        ])
+    }
+
+    #[test]
+    fn render_substitutes_known_variables() {
+        let text = render("I am {{agent}}, limit {{ max_tokens }}.", &vars(), true).unwrap();
+        assert_eq!(text, "I am kai, limit 512.");
+    }
+
+    #[test]
+    fn render_missing_variable_strict_and_lenient() {
+        let err = render("Channel: {{channel}}", &vars(), true).unwrap_err();
+        assert!(err.to_string().contains("channel"), "{err}");
+        let text = render("Channel: {{ channel }} / {{agent}}", &vars(), false).unwrap();
+        assert_eq!(text, "Channel: {{ channel }} / kai");
+    }
+
+    #[test]
+    fn render_escapes_and_ignores_non_placeholders() {
+        let text = render(r"Write \{{agent}} for {{agent}}", &vars(), true).unwrap();
+        assert_eq!(text, "Write {{agent}} for kai");
+        // Not variable names, or never closed: kept literally even when strict.
+        let text = render("json {{\"a\": 1}} and {{ }} and {{agent", &vars(), true).unwrap();
+        assert_eq!(text, "json {{\"a\": 1}} and {{ }} and {{agent");
+    }
+}
@@ -6,10 +6,19 @@
 //! created_after:7d AND kind:fact
 //! importance>=0.5 AND NOT kind:event
 //! (kind:decision OR kind:pattern) AND tags:architecture
+//! kind:fact tag:infra importance>=0.7 after:2024-01-01
 //! ```
+//!
+//! Predicates separated only by whitespace are ANDed. `tag`, `after` and
+//! `before` are short for `tags`, `created_after` and `created_before`.
+//!
+//! `tags:a,b` and `tags:a OR tags:b` match nodes with any of the tags;
+//! `tags:a AND tags:b` matches nodes with all of them.
 
-use crate::storage::NodeFilter;
-use crate::types::NodeKind;
+use crate::error::CortexError;
+use crate::storage::{NodeFilter, Storage};
+use crate::types::{Node, NodeKind};
+use crate::vector::{cosine_similarity, embedding_input, EmbeddingService};
 use chrono::{DateTime, Utc};
 
 // ---------------------------------------------------------------------------
@@ -225,9 +234,9 @@ fn tokenize(input: &str) -> Result<Vec<PosToken>, ParseError> {
                 while i < len && (chars[i].is_ascii_digit() || chars[i] == '.') {
                     i += 1;
                 }
-                // Check if this is a duration/date suffix (letter follows)
-                if i < len && chars[i].is_ascii_alphabetic() {
-                    // It is something like 7d or 24h or an ISO date fragment --
+                // Check if this is a duration/date suffix (letter or dash follows)
+                if i < len && (chars[i].is_ascii_alphabetic() || chars[i] == '-') {
+                    // It is something like 7d, 24h or 2024-01-01T12:00:00Z --
                     // consume the rest as a word.
                     while i < len
                         && !chars[i].is_ascii_whitespace()
@@ -367,11 +376,17 @@ impl Parser {
         Ok(left)
     }
 
-    /// `and_expr = unary ("AND" unary)*`
+    /// `and_expr = unary (["AND"] unary)*` -- juxtaposed predicates are ANDed
     fn parse_and(&mut self) -> Result<FilterExpr, ParseError> {
         let mut left = self.parse_unary()?;
-        while self.peek() == Some(&Token::And) {
-            self.advance();
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.advance();
+                }
+                Some(Token::Word(_)) | Some(Token::LParen) | Some(Token::Not) => {}
+                _ => break,
+            }
             let right = self.parse_unary()?;
             left = FilterExpr::And(Box::new(left), Box::new(right));
         }
@@ -425,42 +440,42 @@ impl Parser {
 
         match field_name.as_str() {
             "kind" => {
-                self.expect(&Token::Colon)?;
+                self.expect_colon("kind")?;
                 let values = self.parse_comma_values()?;
                 Ok(FilterExpr::Field(FieldFilter::Kind(values)))
             }
-            "tags" => {
-                self.expect(&Token::Colon)?;
+            "tags" | "tag" => {
+                self.expect_colon(&field_name)?;
                 let values = self.parse_comma_values()?;
                 Ok(FilterExpr::Field(FieldFilter::Tags(values)))
             }
             "agent" => {
-                self.expect(&Token::Colon)?;
+                self.expect_colon("agent")?;
                 let value = self.parse_value()?;
                 Ok(FilterExpr::Field(FieldFilter::Agent(value)))
             }
             "importance" => {
-                let op = self.parse_cmp_op()?;
+                let op = self.parse_cmp_op(&field_name)?;
                 let num = self.parse_number()?;
                 Ok(FilterExpr::Field(FieldFilter::Importance {
                     op,
                     value: num as f32,
                 }))
             }
-            "created_after" => {
-                self.expect(&Token::Colon)?;
+            "created_after" | "after" => {
+                self.expect_colon(&field_name)?;
                 let value = self.parse_value()?;
                 let dt = parse_duration_or_date(&value, pos)?;
                 Ok(FilterExpr::Field(FieldFilter::CreatedAfter(dt)))
             }
-            "created_before" => {
-                self.expect(&Token::Colon)?;
+            "created_before" | "before" => {
+                self.expect_colon(&field_name)?;
                 let value = self.parse_value()?;
                 let dt = parse_duration_or_date(&value, pos)?;
                 Ok(FilterExpr::Field(FieldFilter::CreatedBefore(dt)))
             }
             "deleted" => {
-                self.expect(&Token::Colon)?;
+                self.expect_colon("deleted")?;
                 let value = self.parse_value()?;
                 let b = match value.as_str() {
                     "true" => true,
@@ -478,19 +493,42 @@ impl Parser {
                 Ok(FilterExpr::Field(FieldFilter::Deleted(b)))
             }
             "limit" => {
-                self.expect(&Token::Colon)?;
+                self.expect_colon("limit")?;
                 let num = self.parse_number()?;
                 Ok(FilterExpr::Field(FieldFilter::Limit(num as usize)))
             }
             other => Err(ParseError {
-                message: format!("Unknown field: '{}'", other),
+                message: format!(
+                    "Unknown field: '{}'. Expected one of: kind, tag(s), agent, importance, \
+                     after/created_after, before/created_before, deleted, limit",
+                    other
+                ),
                 position: pos,
             }),
         }
     }
 
-    /// Parse a comparison operator token.
-    fn parse_cmp_op(&mut self) -> Result<CmpOp, ParseError> {
+    /// Expect the `:` after `field`, with an example in the error.
+    fn expect_colon(&mut self, field: &str) -> Result<(), ParseError> {
+        if self.peek() == Some(&Token::Colon) {
+            self.advance();
+            return Ok(());
+        }
+        let found = match self.peek() {
+            Some(t) => format!("{:?}", t),
+            None => "end of input".to_string(),
+        };
+        Err(ParseError {
+            message: format!(
+                "Expected ':' after '{}' (e.g. {}:value), found {}",
+                field, field, found
+            ),
+            position: self.current_pos(),
+        })
+    }
+
+    /// Parse the comparison operator after `field`.
+    fn parse_cmp_op(&mut self, field: &str) -> Result<CmpOp, ParseError> {
         let pos = self.current_pos();
         match self.advance() {
             Some(PosToken {
@@ -498,11 +536,17 @@ impl Parser {
                 ..
             }) => Ok(*op),
             Some(pt) => Err(ParseError {
-                message: format!("Expected comparison operator, found {:?}", pt.token),
+                message: format!(
+                    "Expected comparison operator after '{}' (e.g. {}>=0.7), found {:?}",
+                    field, field, pt.token
+                ),
                 position: pt.pos,
             }),
             None => Err(ParseError {
-                message: "Expected comparison operator, found end of input".to_string(),
+                message: format!(
+                    "Expected comparison operator after '{}' (e.g. {}>=0.7), found end of input",
+                    field, field
+                ),
                 position: pos,
             }),
         }
@@ -579,15 +623,19 @@ impl Parser {
 // Duration / date parsing
 // ---------------------------------------------------------------------------
 
-/// Parse a relative duration (`7d`, `24h`, `30m`) or an ISO-8601 date string.
+/// Parse a relative duration (`7d`, `24h`, `30m`), a date (`2024-01-01`,
+/// midnight UTC) or an ISO-8601 timestamp.
 /// Durations are computed as `Utc::now() - duration`.
 fn parse_duration_or_date(value: &str, pos: usize) -> Result<DateTime<Utc>, ParseError> {
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_time(chrono::NaiveTime::MIN).and_utc());
+    }
     let len = value.len();
     if len < 2 {
         // Try ISO-8601 parse
         return value.parse::<DateTime<Utc>>().map_err(|_| ParseError {
             message: format!(
-                "Invalid duration or date: '{}'. Use 7d, 24h, 30m, or ISO-8601.",
+                "Invalid duration or date: '{}'. Use 7d, 24h, 30m, YYYY-MM-DD or ISO-8601.",
                 value
             ),
             position: pos,
@@ -615,7 +663,7 @@ fn parse_duration_or_date(value: &str, pos: usize) -> Result<DateTime<Utc>, Pars
             // Try ISO-8601 parse
             value.parse::<DateTime<Utc>>().map_err(|_| ParseError {
                 message: format!(
-                    "Invalid duration or date: '{}'. Use 7d, 24h, 30m, or ISO-8601.",
+                    "Invalid duration or date: '{}'. Use 7d, 24h, 30m, YYYY-MM-DD or ISO-8601.",
                     value
                 ),
                 position: pos,
@@ -784,14 +832,25 @@ fn apply_field(field: &FieldFilter, filter: &mut NodeFilter) -> Result<(), Compi
                 None => filter.kinds = Some(kinds),
             }
         }
-        FieldFilter::Tags(values) => match filter.tags.as_mut() {
-            Some(existing) => {
-                for v in values {
-                    existing.push(v.clone());
+        FieldFilter::Tags(values) => {
+            let all_tags = filter.all_tags;
+            match filter.tags.as_mut() {
+                // A second tag predicate ANDed onto the first requires every
+                // tag, which a list (matching any of its tags) can't take part in.
+                Some(existing) => {
+                    if values.len() > 1 || (existing.len() > 1 && !all_tags) {
+                        return Err(CompileError {
+                            message:
+                                "A tag list (tags:a,b) cannot be ANDed with another tag predicate"
+                                    .to_string(),
+                        });
+                    }
+                    existing.extend(values.iter().cloned());
+                    filter.all_tags = true;
                 }
+                None => filter.tags = Some(values.clone()),
             }
-            None => filter.tags = Some(values.clone()),
-        },
+        }
         FieldFilter::Agent(value) => {
             filter.source_agent = Some(value.clone());
         }
@@ -852,6 +911,80 @@ pub fn parse_and_compile(input: &str) -> Result<NodeFilter, QueryError> {
     Ok(filter)
 }
 
+// ---------------------------------------------------------------------------
+// Execution
+// ---------------------------------------------------------------------------
+
+/// A node matched by [`run_query`]. `score` is the cosine similarity to the
+/// `like` text, when one was given.
+#[derive(Debug, Clone)]
+pub struct QueryHit {
+    pub node: Node,
+    pub score: Option<f32>,
+}
+
+/// Run a filter expression against `storage`.
+///
+/// Without `like`, matches come back in storage order. With `like`, every
+/// match is ranked by similarity to that text and `limit:` applies after the
+/// ranking, so it keeps the closest matches rather than the first ones.
+/// Nodes without a stored embedding are embedded on the fly.
+pub fn run_query<S: Storage, E: EmbeddingService>(
+    storage: &S,
+    embedder: &E,
+    query: &str,
+    like: Option<&str>,
+) -> crate::Result<Vec<QueryHit>> {
+    let mut filter =
+        parse_and_compile(query).map_err(|e| CortexError::Validation(e.to_string()))?;
+
+    let Some(like) = like.map(str::trim).filter(|l| !l.is_empty()) else {
+        let nodes = storage.list_nodes(filter)?;
+        return Ok(nodes
+            .into_iter()
+            .map(|node| QueryHit { node, score: None })
+            .collect());
+    };
+
+    let limit = filter.limit.take();
+    let mut nodes = storage.list_nodes(filter)?;
+    let target = embedder.embed(like)?;
+
+    let missing: Vec<usize> = (0..nodes.len())
+        .filter(|&i| nodes[i].embedding.is_none())
+        .collect();
+    if !missing.is_empty() {
+        let texts: Vec<String> = missing
+            .iter()
+            .map(|&i| embedding_input(&nodes[i]))
+            .collect();
+        for (i, embedding) in missing.into_iter().zip(embedder.embed_batch(&texts)?) {
+            nodes[i].embedding = Some(embedding);
+        }
+    }
+
+    let mut hits: Vec<QueryHit> = nodes
+        .into_iter()
+        .map(|node| {
+            let score = node
+                .embedding
+                .as_deref()
+                .map(|e| cosine_similarity(&target, e));
+            QueryHit { node, score }
+        })
+        .collect();
+    hits.sort_by(|a, b| {
+        b.score
+            .unwrap_or(f32::MIN)
+            .partial_cmp(&a.score.unwrap_or(f32::MIN))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    if let Some(limit) = limit {
+        hits.truncate(limit);
+    }
+    Ok(hits)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -1108,6 +1241,25 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_compile_or_tags_matches_any() {
+        let filter = parse_and_compile("tags:rust OR tags:go").unwrap();
+        assert_eq!(filter.tags.unwrap(), vec!["rust", "go"]);
+        assert!(!filter.all_tags);
+    }
+
+    #[test]
+    fn test_compile_and_tags_matches_all() {
+        let filter = parse_and_compile("tags:rust AND tag:go").unwrap();
+        assert_eq!(filter.tags.unwrap(), vec!["rust", "go"]);
+        assert!(filter.all_tags);
+
+        let filter = parse_and_compile("tags:rust,go").unwrap();
+        assert!(!filter.all_tags);
+
+        assert!(parse_and_compile("tags:rust,go AND tags:wasm").is_err());
+    }
+
     #[test]
     fn test_compile_not_kind_fails() {
         let result = parse_and_compile("NOT kind:decision");
@@ -1219,4 +1371,150 @@ mod tests {
             err.message
         );
     }
+
+    #[test]
+    fn test_parse_tag_alias() {
+        let filter = parse_and_compile("tag:infra").unwrap();
+        assert_eq!(filter.tags, Some(vec!["infra".to_string()]));
+    }
+
+    #[test]
+    fn test_parse_plain_date() {
+        let filter = parse_and_compile("after:2024-01-01 before:2024-06-30").unwrap();
+        assert_eq!(
+            filter.created_after.unwrap().to_rfc3339(),
+            "2024-01-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            filter.created_before.unwrap().to_rfc3339(),
+            "2024-06-30T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_implicit_and() {
+        let filter =
+            parse_and_compile("kind:fact tag:infra importance>=0.7 after:2024-01-01").unwrap();
+        assert_eq!(filter.kinds.unwrap(), vec![NodeKind::new("fact").unwrap()]);
+        assert_eq!(filter.tags, Some(vec!["infra".to_string()]));
+        assert_eq!(filter.min_importance, Some(0.7));
+        assert!(filter.created_after.is_some());
+
+        // Same thing spelled out
+        let explicit =
+            parse("kind:fact AND tag:infra AND importance>=0.7 AND after:2024-01-01").unwrap();
+        let implicit = parse("kind:fact tag:infra importance>=0.7 after:2024-01-01").unwrap();
+        assert_eq!(explicit, implicit);
+    }
+
+    #[test]
+    fn test_parse_error_missing_colon() {
+        let err = parse("kind fact").unwrap_err();
+        assert!(err.message.contains("Expected ':' after 'kind'"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_error_missing_operator() {
+        let err = parse("importance 0.7").unwrap_err();
+        assert!(err.message.contains("importance>=0.7"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_error_bad_date() {
+        let err = parse("after:2024-13-45").unwrap_err();
+        assert!(err.message.contains("YYYY-MM-DD"), "{}", err);
+    }
+
+    mod run {
+        use super::*;
+        use crate::storage::RedbStorage;
+        use crate::testing::KeywordEmbedder;
+        use crate::types::Source;
+
+        /// One axis per word, so similarity is exact.
+        fn axis() -> KeywordEmbedder {
+            KeywordEmbedder::new(&["postgres", "kafka", "redis"]).with_background(0.1)
+        }
+
+        fn node(kind: &str, title: &str, tags: &[&str], importance: f32) -> Node {
+            let mut node = Node::new(
+                NodeKind::new(kind).unwrap(),
+                title.to_string(),
+                String::new(),
+                Source {
+                    agent: "test".into(),
+                    session: None,
+                    channel: None,
+                },
+                importance,
+            );
+            node.data.tags = tags.iter().map(|t| t.to_string()).collect();
+            node
+        }
+
+        fn setup() -> (RedbStorage, tempfile::TempDir, Vec<Node>) {
+            let tmp = tempfile::TempDir::new().unwrap();
+            let storage = RedbStorage::open(tmp.path().join("query.redb")).unwrap();
+            let mut postgres = node("fact", "postgres failover", &["infra"], 0.9);
+            postgres.embedding = Some(axis().embed("postgres").unwrap());
+            let nodes = vec![
+                postgres,
+                // No stored embedding: embedded on the fly when ranking
+                node("fact", "kafka retention", &["infra"], 0.8),
+                node("fact", "redis eviction", &["infra"], 0.3),
+                node("decision", "use kafka", &["infra"], 0.9),
+                node("fact", "kafka partitions", &["team"], 0.9),
+            ];
+            storage.put_nodes_batch(&nodes).unwrap();
+            (storage, tmp, nodes)
+        }
+
+        fn titles(hits: &[QueryHit]) -> Vec<&str> {
+            hits.iter().map(|h| h.node.data.title.as_str()).collect()
+        }
+
+        #[test]
+        fn structured_only() {
+            let (storage, _tmp, _) = setup();
+            let hits = run_query(
+                &storage,
+                &axis(),
+                "kind:fact tag:infra importance>=0.7",
+                None,
+            )
+            .unwrap();
+            let mut found = titles(&hits);
+            found.sort();
+            assert_eq!(found, ["kafka retention", "postgres failover"]);
+            assert!(hits.iter().all(|h| h.score.is_none()));
+        }
+
+        #[test]
+        fn structured_and_semantic() {
+            let (storage, _tmp, _) = setup();
+            let hits = run_query(
+                &storage,
+                &axis(),
+                "kind:fact tag:infra",
+                Some("kafka consumer lag"),
+            )
+            .unwrap();
+            // Filter first, then rank: the kafka decision and the team-tagged
+            // kafka fact never make it in
+            assert_eq!(hits.len(), 3);
+            assert_eq!(hits[0].node.data.title, "kafka retention");
+            assert!(hits[0].score.unwrap() > hits[1].score.unwrap());
+
+            // limit keeps the closest, not the first stored
+            let hits = run_query(&storage, &axis(), "tag:infra limit:1", Some("postgres")).unwrap();
+            assert_eq!(titles(&hits), ["postgres failover"]);
+        }
+
+        #[test]
+        fn parse_errors_are_validation_errors() {
+            let (storage, _tmp, _) = setup();
+            let err = run_query(&storage, &axis(), "kind fact", None).unwrap_err();
+            assert!(matches!(err, CortexError::Validation(_)), "{err}");
+        }
+    }
 }
@@ -1,5 +1,26 @@
 use crate::Relation;
 
+/// Relations that read the same both ways: `A related_to B` means
+/// `B related_to A`. Each is its own inverse.
+pub const SYMMETRIC: &[&str] = &[
+    "related_to",
+    "relates_to",
+    "contradicts",
+    "similar_to",
+    "observed_with",
+];
+
+/// Asymmetric relations with a named inverse, as `(relation, inverse)`.
+/// Lookups work in both directions.
+pub const INVERSES: &[(&str, &str)] = &[
+    ("supersedes", "superseded_by"),
+    ("uses", "used_by"),
+    ("depends_on", "dependency_of"),
+    ("informed_by", "informs"),
+    ("instance_of", "has_instance"),
+    ("part_of", "has_part"),
+];
+
 pub mod defaults {
     use super::*;
 
@@ -58,6 +79,10 @@ pub mod defaults {
     pub fn rolled_back_to() -> Relation {
         Relation::new("rolled_back_to").unwrap()
     }
+    /// merge version --merged_from--> source branch version it merged in
+    pub fn merged_from() -> Relation {
+        Relation::new("merged_from").unwrap()
+    }
 
     pub fn all() -> Vec<Relation> {
         vec![
@@ -79,6 +104,7 @@ pub mod defaults {
             observed_by(),
             rolled_back(),
             rolled_back_to(),
+            merged_from(),
         ]
     }
 }
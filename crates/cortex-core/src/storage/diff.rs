@@ -0,0 +1,223 @@
+use crate::error::Result;
+use crate::storage::{NodeFilter, Storage};
+use crate::types::{EdgeId, Node, NodeId};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// A field whose value differs between the two sides.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange<T> {
+    pub a: T,
+    pub b: T,
+}
+
+/// A node present on both sides whose title, body or importance differs.
+/// Unchanged fields are `None`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NodeChange {
+    pub id: NodeId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<FieldChange<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<FieldChange<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub importance: Option<FieldChange<f32>>,
+}
+
+/// How graph `b` differs from graph `a`. Nodes are compared by id, live
+/// nodes only; a node soft-deleted on one side counts as absent there.
+/// Every list is sorted by id.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct GraphDiff {
+    pub nodes_only_in_a: Vec<NodeId>,
+    pub nodes_only_in_b: Vec<NodeId>,
+    pub nodes_changed: Vec<NodeChange>,
+    pub edges_only_in_a: Vec<EdgeId>,
+    pub edges_only_in_b: Vec<EdgeId>,
+}
+
+impl GraphDiff {
+    pub fn is_empty(&self) -> bool {
+        self.nodes_only_in_a.is_empty()
+            && self.nodes_only_in_b.is_empty()
+            && self.nodes_changed.is_empty()
+            && self.edges_only_in_a.is_empty()
+            && self.edges_only_in_b.is_empty()
+    }
+}
+
+/// Compare two graphs. Read-only on both sides.
+pub fn diff_storage<A: Storage, B: Storage>(a: &A, b: &B) -> Result<GraphDiff> {
+    let nodes_a = by_id(a.list_nodes(NodeFilter::new())?);
+    let nodes_b = by_id(b.list_nodes(NodeFilter::new())?);
+
+    let mut diff = GraphDiff {
+        nodes_only_in_a: only_in(&nodes_a, &nodes_b),
+        nodes_only_in_b: only_in(&nodes_b, &nodes_a),
+        ..Default::default()
+    };
+
+    for (id, na) in &nodes_a {
+        let Some(nb) = nodes_b.get(id) else {
+            continue;
+        };
+        let change = NodeChange {
+            id: *id,
+            title: changed(&na.data.title, &nb.data.title),
+            body: changed(&na.data.body, &nb.data.body),
+            importance: changed(&na.importance, &nb.importance),
+        };
+        if change.title.is_some() || change.body.is_some() || change.importance.is_some() {
+            diff.nodes_changed.push(change);
+        }
+    }
+    diff.nodes_changed.sort_by_key(|c| c.id);
+
+    let edges_a: HashSet<EdgeId> = a.list_edges()?.into_iter().map(|e| e.id).collect();
+    let edges_b: HashSet<EdgeId> = b.list_edges()?.into_iter().map(|e| e.id).collect();
+    diff.edges_only_in_a = sorted(edges_a.difference(&edges_b).copied());
+    diff.edges_only_in_b = sorted(edges_b.difference(&edges_a).copied());
+
+    Ok(diff)
+}
+
+fn by_id(nodes: Vec<Node>) -> HashMap<NodeId, Node> {
+    nodes.into_iter().map(|n| (n.id, n)).collect()
+}
+
+fn only_in(this: &HashMap<NodeId, Node>, other: &HashMap<NodeId, Node>) -> Vec<NodeId> {
+    sorted(this.keys().filter(|id| !other.contains_key(id)).copied())
+}
+
+fn sorted<T: Ord>(items: impl Iterator<Item = T>) -> Vec<T> {
+    let mut items: Vec<T> = items.collect();
+    items.sort();
+    items
+}
+
+fn changed<T: PartialEq + Clone>(a: &T, b: &T) -> Option<FieldChange<T>> {
+    (a != b).then(|| FieldChange {
+        a: a.clone(),
+        b: b.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::RedbStorage;
+    use crate::types::{Edge, EdgeProvenance, NodeKind, Relation, Source};
+
+    fn node(title: &str) -> Node {
+        Node::new(
+            NodeKind::new("fact").unwrap(),
+            title.to_string(),
+            format!("{title} body"),
+            Source {
+                agent: "test".into(),
+                session: None,
+                channel: None,
+            },
+            0.5,
+        )
+    }
+
+    fn edge(from: NodeId, to: NodeId) -> Edge {
+        Edge::new(
+            from,
+            to,
+            Relation::new("related_to").unwrap(),
+            0.8,
+            EdgeProvenance::Manual {
+                created_by: "test".into(),
+            },
+        )
+    }
+
+    #[test]
+    fn reports_added_removed_and_modified() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let a = RedbStorage::open(tmp.path().join("a.redb")).unwrap();
+        let b = RedbStorage::open(tmp.path().join("b.redb")).unwrap();
+
+        // Shared on both sides, unchanged
+        let same = node("same");
+        // Shared, then edited on b
+        let edited = node("edited");
+        // Only on a / only on b
+        let removed = node("removed");
+        let added = node("added");
+        // Soft-deleted on b: absent there
+        let deleted = node("deleted");
+
+        a.put_nodes_batch(&[
+            same.clone(),
+            edited.clone(),
+            removed.clone(),
+            deleted.clone(),
+        ])
+        .unwrap();
+        let mut edited_b = edited.clone();
+        edited_b.data.body = "rewritten".into();
+        edited_b.importance = 0.9;
+        b.put_nodes_batch(&[same.clone(), edited_b, added.clone(), deleted.clone()])
+            .unwrap();
+        b.delete_node(deleted.id).unwrap();
+
+        let shared_edge = edge(same.id, edited.id);
+        let edge_a = edge(same.id, removed.id);
+        let edge_b = edge(added.id, same.id);
+        a.put_edge(&shared_edge).unwrap();
+        a.put_edge(&edge_a).unwrap();
+        b.put_edge(&shared_edge).unwrap();
+        b.put_edge(&edge_b).unwrap();
+
+        let diff = diff_storage(&a, &b).unwrap();
+        let mut only_a = vec![removed.id, deleted.id];
+        only_a.sort();
+        assert_eq!(diff.nodes_only_in_a, only_a);
+        assert_eq!(diff.nodes_only_in_b, [added.id]);
+        assert_eq!(
+            diff.nodes_changed,
+            [NodeChange {
+                id: edited.id,
+                title: None,
+                body: Some(FieldChange {
+                    a: "edited body".into(),
+                    b: "rewritten".into(),
+                }),
+                importance: Some(FieldChange { a: 0.5, b: 0.9 }),
+            }]
+        );
+        assert_eq!(diff.edges_only_in_a, [edge_a.id]);
+        assert_eq!(diff.edges_only_in_b, [edge_b.id]);
+
+        // JSON detail leaves unchanged fields out
+        let json = serde_json::to_value(&diff).unwrap();
+        let change = &json["nodes_changed"][0];
+        assert!(change.get("title").is_none());
+        assert_eq!(change["body"]["b"], "rewritten");
+
+        // Swapping the sides swaps the report
+        let reverse = diff_storage(&b, &a).unwrap();
+        assert_eq!(reverse.nodes_only_in_b, diff.nodes_only_in_a);
+        assert_eq!(reverse.edges_only_in_a, diff.edges_only_in_b);
+    }
+
+    #[test]
+    fn identical_graphs_are_empty() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (path_a, path_b) = (tmp.path().join("a.redb"), tmp.path().join("b.redb"));
+        {
+            let a = RedbStorage::open(&path_a).unwrap();
+            let (n, m) = (node("n"), node("m"));
+            a.put_nodes_batch(&[n.clone(), m.clone()]).unwrap();
+            a.put_edge(&edge(n.id, m.id)).unwrap();
+        }
+        std::fs::copy(&path_a, &path_b).unwrap();
+        let a = RedbStorage::open(&path_a).unwrap();
+        let b = RedbStorage::open(&path_b).unwrap();
+
+        assert!(diff_storage(&a, &b).unwrap().is_empty());
+    }
+}
@@ -14,15 +14,24 @@ pub fn derive_key() -> anyhow::Result<[u8; 32]> {
              Run `cortex-server security generate-key` to create one."
         )
     })?;
+    decode_key(&raw_key, "CORTEX_ENCRYPTION_KEY")
+}
+
+/// Decode a base64-encoded 256-bit key, as printed by `generate_key`.
+pub fn parse_key(raw_key: &str) -> anyhow::Result<[u8; 32]> {
+    decode_key(raw_key, "Key")
+}
 
+fn decode_key(raw_key: &str, name: &str) -> anyhow::Result<[u8; 32]> {
     let key_bytes = BASE64
         .decode(raw_key.trim())
-        .map_err(|_| anyhow::anyhow!("CORTEX_ENCRYPTION_KEY is not valid base64"))?;
+        .map_err(|_| anyhow::anyhow!("{} is not valid base64", name))?;
 
     if key_bytes.len() != 32 {
         return Err(anyhow::anyhow!(
-            "CORTEX_ENCRYPTION_KEY must decode to exactly 32 bytes (256 bits), \
+            "{} must decode to exactly 32 bytes (256 bits), \
              got {} bytes",
+            name,
             key_bytes.len()
         ));
     }
@@ -45,17 +54,7 @@ pub fn encrypt_file(path: &std::path::Path, key: &[u8; 32]) -> anyhow::Result<()
     let plaintext = std::fs::read(path)
         .map_err(|e| anyhow::anyhow!("Failed to read file for encryption: {}", e))?;
 
-    let cipher = Aes256Gcm::new_from_slice(key).expect("key is always 32 bytes");
-
-    let nonce_bytes: [u8; 12] = rand::random();
-    let nonce = Nonce::from_slice(&nonce_bytes);
-
-    let ciphertext = cipher
-        .encrypt(nonce, plaintext.as_ref())
-        .map_err(|e| anyhow::anyhow!("Encryption failed: {:?}", e))?;
-
-    let mut output = nonce_bytes.to_vec();
-    output.extend_from_slice(&ciphertext);
+    let output = encrypt_bytes(&plaintext, key)?;
 
     std::fs::write(path, output)
         .map_err(|e| anyhow::anyhow!("Failed to write encrypted file: {}", e))?;
@@ -69,6 +68,57 @@ pub fn decrypt_file(path: &std::path::Path, key: &[u8; 32]) -> anyhow::Result<()
     let data = std::fs::read(path)
         .map_err(|e| anyhow::anyhow!("Failed to read file for decryption: {}", e))?;
 
+    let plaintext = decrypt_bytes(&data, key)?;
+
+    std::fs::write(path, plaintext)
+        .map_err(|e| anyhow::anyhow!("Failed to write decrypted file: {}", e))?;
+    Ok(())
+}
+
+/// Re-encrypt a file from `old_key` to `new_key`.
+///
+/// The re-encrypted copy is staged next to the file and renamed over it, so
+/// the original is left intact if anything fails, including `old_key` not
+/// decrypting it. Copies made earlier (backups) keep their old key.
+pub fn rotate_file_key(
+    path: &std::path::Path,
+    old_key: &[u8; 32],
+    new_key: &[u8; 32],
+) -> anyhow::Result<()> {
+    let data = std::fs::read(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read file for key rotation: {}", e))?;
+
+    let plaintext = decrypt_bytes(&data, old_key)?;
+    let output = encrypt_bytes(&plaintext, new_key)?;
+
+    let mut staged = path.as_os_str().to_owned();
+    staged.push(".rotating");
+    let staged = std::path::PathBuf::from(staged);
+    std::fs::write(&staged, output)
+        .map_err(|e| anyhow::anyhow!("Failed to write re-encrypted file: {}", e))?;
+    std::fs::rename(&staged, path).map_err(|e| {
+        let _ = std::fs::remove_file(&staged);
+        anyhow::anyhow!("Failed to replace encrypted file: {}", e)
+    })?;
+    Ok(())
+}
+
+fn encrypt_bytes(plaintext: &[u8], key: &[u8; 32]) -> anyhow::Result<Vec<u8>> {
+    let cipher = Aes256Gcm::new_from_slice(key).expect("key is always 32 bytes");
+
+    let nonce_bytes: [u8; 12] = rand::random();
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|e| anyhow::anyhow!("Encryption failed: {:?}", e))?;
+
+    let mut output = nonce_bytes.to_vec();
+    output.extend_from_slice(&ciphertext);
+    Ok(output)
+}
+
+fn decrypt_bytes(data: &[u8], key: &[u8; 32]) -> anyhow::Result<Vec<u8>> {
     if data.len() < 12 {
         return Err(anyhow::anyhow!(
             "File is too short to be a valid encrypted database (< 12 bytes)"
@@ -79,13 +129,9 @@ pub fn decrypt_file(path: &std::path::Path, key: &[u8; 32]) -> anyhow::Result<()
     let nonce = Nonce::from_slice(nonce_bytes);
     let cipher = Aes256Gcm::new_from_slice(key).expect("key is always 32 bytes");
 
-    let plaintext = cipher.decrypt(nonce, ciphertext).map_err(|_| {
-        anyhow::anyhow!("Decryption failed — wrong key or corrupt/unencrypted data")
-    })?;
-
-    std::fs::write(path, plaintext)
-        .map_err(|e| anyhow::anyhow!("Failed to write decrypted file: {}", e))?;
-    Ok(())
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| anyhow::anyhow!("Decryption failed — wrong key or corrupt/unencrypted data"))
 }
 
 #[cfg(test)]
@@ -126,6 +172,49 @@ mod tests {
         assert!(decrypt_file(&path, &wrong_key).is_err());
     }
 
+    #[test]
+    fn test_rotate_key() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("test.bin");
+        let original = b"rotate me";
+        std::fs::write(&path, original).unwrap();
+
+        let key_a: [u8; 32] = rand::random();
+        let key_b: [u8; 32] = rand::random();
+        encrypt_file(&path, &key_a).unwrap();
+        let backup = dir.path().join("backup.bin");
+        std::fs::copy(&path, &backup).unwrap();
+
+        // A wrong old key fails and leaves the file as it was
+        let before = std::fs::read(&path).unwrap();
+        assert!(rotate_file_key(&path, &key_b, &key_b).is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), before);
+
+        rotate_file_key(&path, &key_a, &key_b).unwrap();
+
+        let under_a = dir.path().join("under_a.bin");
+        std::fs::copy(&path, &under_a).unwrap();
+        assert!(decrypt_file(&under_a, &key_a).is_err());
+
+        decrypt_file(&path, &key_b).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), original);
+
+        // Earlier backups still open with the old key
+        decrypt_file(&backup, &key_a).unwrap();
+        assert_eq!(std::fs::read(&backup).unwrap(), original);
+    }
+
+    #[test]
+    fn test_parse_key() {
+        let key_b64 = generate_key();
+        assert_eq!(
+            parse_key(&key_b64).unwrap().to_vec(),
+            BASE64.decode(&key_b64).unwrap()
+        );
+        assert!(parse_key("not base64!").is_err());
+        assert!(parse_key(&BASE64.encode(b"tooshort")).is_err());
+    }
+
     #[test]
     fn test_generate_key_is_32_bytes() {
         let key_b64 = generate_key();
@@ -1,12 +1,17 @@
-use crate::types::{NodeKind, Relation};
+use crate::error::{CortexError, Result};
+use crate::types::{Node, NodeId, NodeKind, Relation};
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
 
 /// Filter criteria for querying nodes
 #[derive(Debug, Clone, Default)]
 pub struct NodeFilter {
     pub kinds: Option<Vec<NodeKind>>,
     pub tags: Option<Vec<String>>,
+    /// Require every tag in `tags` instead of at least one
+    pub all_tags: bool,
     pub source_agent: Option<String>,
     pub created_after: Option<DateTime<Utc>>,
     pub created_before: Option<DateTime<Utc>>,
@@ -16,8 +21,12 @@ pub struct NodeFilter {
     pub deleted_only: bool,
     /// Only return nodes with updated_at before this time (useful for purge)
     pub updated_before: Option<DateTime<Utc>>,
+    /// Only return nodes created or updated after this time (incremental scans)
+    pub changed_after: Option<DateTime<Utc>>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
+    /// Resume after this node in listing order
+    pub cursor: Option<NodeCursor>,
 }
 
 impl NodeFilter {
@@ -35,6 +44,14 @@ impl NodeFilter {
     /// Filter by tags (nodes must have at least one of these tags)
     pub fn with_tags(mut self, tags: Vec<String>) -> Self {
         self.tags = Some(tags);
+        self.all_tags = false;
+        self
+    }
+
+    /// Filter by tags (nodes must have all of these tags)
+    pub fn with_all_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = Some(tags);
+        self.all_tags = true;
         self
     }
 
@@ -80,6 +97,12 @@ impl NodeFilter {
         self
     }
 
+    /// Resume listing after the node `cursor` points at
+    pub fn after(mut self, cursor: NodeCursor) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
     /// Only return soft-deleted nodes
     pub fn deleted_only(mut self) -> Self {
         self.deleted_only = true;
@@ -92,6 +115,77 @@ impl NodeFilter {
         self.updated_before = Some(time);
         self
     }
+
+    /// Filter to nodes created or updated after this time
+    pub fn changed_after(mut self, time: DateTime<Utc>) -> Self {
+        self.changed_after = Some(time);
+        self
+    }
+}
+
+/// Position in a node listing, just after the last node of a page.
+///
+/// Listings are ordered newest first by `created_at`, then by id, so a
+/// cursor stays valid while nodes are added. Serialised as an opaque string
+/// for clients to hand back unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeCursor {
+    created_at: DateTime<Utc>,
+    id: NodeId,
+}
+
+impl NodeCursor {
+    /// Cursor pointing at `node`; the next page starts after it.
+    pub fn after(node: &Node) -> Self {
+        Self {
+            created_at: node.created_at,
+            id: node.id,
+        }
+    }
+
+    /// True when `node` comes after the cursor in listing order.
+    pub fn precedes(&self, node: &Node) -> bool {
+        self.precedes_at(node.created_at, node.id)
+    }
+
+    /// True when a node created at `created_at` with `id` comes after the
+    /// cursor in listing order.
+    pub(crate) fn precedes_at(&self, created_at: DateTime<Utc>, id: NodeId) -> bool {
+        (created_at, id) < (self.created_at, self.id)
+    }
+
+    /// The node the cursor points at, as `(created_at, id)`.
+    pub(crate) fn position(&self) -> (DateTime<Utc>, NodeId) {
+        (self.created_at, self.id)
+    }
+}
+
+impl fmt::Display for NodeCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:016x}{:08x}{}",
+            self.created_at.timestamp() as u64,
+            self.created_at.timestamp_subsec_nanos(),
+            self.id.simple()
+        )
+    }
+}
+
+impl FromStr for NodeCursor {
+    type Err = CortexError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || CortexError::Validation(format!("Invalid node cursor: {}", s));
+        if s.len() != 56 || !s.is_ascii() {
+            return Err(invalid());
+        }
+        let secs = u64::from_str_radix(&s[..16], 16).map_err(|_| invalid())? as i64;
+        let nanos = u32::from_str_radix(&s[16..24], 16).map_err(|_| invalid())?;
+        let created_at = DateTime::from_timestamp(secs, nanos).ok_or_else(invalid)?;
+        let id = NodeId::parse_str(&s[24..]).map_err(|_| invalid())?;
+        Ok(Self { created_at, id })
+    }
 }
 
 /// Storage statistics
@@ -102,6 +196,10 @@ pub struct StorageStats {
     pub node_counts_by_kind: HashMap<NodeKind, u64>,
     pub edge_counts_by_relation: HashMap<Relation, u64>,
     pub db_size_bytes: u64,
+    /// Bytes each redb table occupies (data, btree metadata and
+    /// fragmentation), keyed by table name. Free space in the file is not
+    /// attributed to any table.
+    pub table_bytes: BTreeMap<String, u64>,
     pub oldest_node: Option<DateTime<Utc>>,
     pub newest_node: Option<DateTime<Utc>>,
 }
@@ -0,0 +1,181 @@
+//! Decode a [`NodeHeader`] straight from a stored node record.
+//!
+//! bincode has no field tags, so the header is read with a struct laid out
+//! exactly like [`Node`](crate::types::Node). The body, metadata and
+//! embedding are walked over in place: nothing is copied out of the record
+//! for them. Keep [`StoredNodeHeader`] in step with `Node` whenever the
+//! schema version changes.
+
+use crate::error::{CortexError, Result};
+use crate::types::{NodeHeader, NodeId, NodeKind, Source};
+use chrono::{DateTime, Utc};
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::fmt;
+
+/// Decode the header of a node record in the current schema layout.
+pub(crate) fn deserialize_node_header(bytes: &[u8]) -> Result<NodeHeader> {
+    let stored: StoredNodeHeader = bincode::deserialize(bytes).map_err(CortexError::from)?;
+    Ok(NodeHeader {
+        id: stored.id,
+        kind: stored.kind,
+        title: stored.data.title,
+        tags: stored.data.tags,
+        source: stored.source,
+        importance: stored.importance,
+        confidence: stored.confidence,
+        created_at: stored.created_at,
+        updated_at: stored.updated_at,
+        deleted: stored.deleted,
+        expires_at: stored.expires_at,
+        deleted_at: stored.deleted_at,
+    })
+}
+
+/// `Node`, field for field, with the heavy fields skipped.
+#[derive(serde::Deserialize)]
+#[allow(dead_code)] // skipped fields are only read to advance the decoder
+struct StoredNodeHeader {
+    id: NodeId,
+    kind: NodeKind,
+    data: StoredNodeData,
+    embedding: Option<SkipSeq>,
+    source: Source,
+    importance: f32,
+    access_count: u64,
+    last_accessed_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    deleted: bool,
+    confidence: f32,
+    expires_at: Option<DateTime<Utc>>,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+/// `NodeData`: metadata is stored as a map of JSON strings.
+#[derive(serde::Deserialize)]
+#[allow(dead_code)]
+struct StoredNodeData {
+    title: String,
+    body: SkipStr,
+    metadata: SkipMap,
+    tags: Vec<String>,
+}
+
+/// A string read in place and dropped.
+struct SkipStr;
+
+impl<'de> Deserialize<'de> for SkipStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct V;
+        impl Visitor<'_> for V {
+            type Value = SkipStr;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string")
+            }
+            fn visit_str<E>(self, _: &str) -> std::result::Result<SkipStr, E> {
+                Ok(SkipStr)
+            }
+        }
+        deserializer.deserialize_str(V)
+    }
+}
+
+/// A map of strings read in place and dropped.
+struct SkipMap;
+
+impl<'de> Deserialize<'de> for SkipMap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct V;
+        impl<'de> Visitor<'de> for V {
+            type Value = SkipMap;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map of strings")
+            }
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> std::result::Result<SkipMap, A::Error> {
+                while map.next_entry::<SkipStr, SkipStr>()?.is_some() {}
+                Ok(SkipMap)
+            }
+        }
+        deserializer.deserialize_map(V)
+    }
+}
+
+/// An embedding read in place and dropped.
+struct SkipSeq;
+
+impl<'de> Deserialize<'de> for SkipSeq {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct V;
+        impl<'de> Visitor<'de> for V {
+            type Value = SkipSeq;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a sequence of floats")
+            }
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> std::result::Result<SkipSeq, A::Error> {
+                while seq.next_element::<f32>()?.is_some() {}
+                Ok(SkipSeq)
+            }
+        }
+        deserializer.deserialize_seq(V)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Node;
+
+    #[test]
+    fn test_header_matches_full_node() {
+        let mut node = Node::new(
+            NodeKind::new("fact").unwrap(),
+            "Header title".into(),
+            "x".repeat(10_000),
+            Source {
+                agent: "kai".into(),
+                session: Some("s1".into()),
+                channel: None,
+            },
+            0.7,
+        );
+        node.data.tags = vec!["infra".into(), "db".into()];
+        node.data
+            .metadata
+            .insert("url".into(), serde_json::json!("https://example.com"));
+        node.embedding = Some(vec![0.25; 384]);
+        node.confidence = 0.4;
+        node.expires_at = Some(Utc::now());
+        node.deleted = true;
+        node.deleted_at = Some(Utc::now());
+
+        let bytes = bincode::serialize(&node).unwrap();
+        let header = deserialize_node_header(&bytes).unwrap();
+        assert_eq!(header, NodeHeader::from(&node));
+    }
+
+    #[test]
+    fn test_header_without_embedding_or_metadata() {
+        let node = Node::new(
+            NodeKind::new("event").unwrap(),
+            "Bare".into(),
+            String::new(),
+            Source {
+                agent: "sys".into(),
+                session: None,
+                channel: None,
+            },
+            0.5,
+        );
+        let bytes = bincode::serialize(&node).unwrap();
+        assert_eq!(
+            deserialize_node_header(&bytes).unwrap(),
+            NodeHeader::from(&node)
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use crate::error::{CortexError, Result};
+use crate::storage::{NodeCursor, NodeFilter, Storage};
+use crate::types::{Edge, Node, NodeId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+
+/// Nodes read from storage per page while writing.
+const PAGE_SIZE: usize = 500;
+
+/// One line of the canonical JSONL format: a complete node or edge, tagged
+/// `"type": "node"` or `"type": "edge"`. Ids, timestamps, metadata and
+/// embeddings are all kept, so [`read_jsonl`] reproduces what
+/// [`write_jsonl`] wrote.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JsonlRecord {
+    Node(Node),
+    Edge(Edge),
+}
+
+/// Records written by [`write_jsonl`] or read by [`read_jsonl`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct JsonlCounts {
+    pub nodes: usize,
+    pub edges: usize,
+}
+
+/// Write every node matching `filter`, a page at a time, then (with
+/// `include_edges`) every edge whose endpoints were both written.
+///
+/// `filter`'s limit and cursor are replaced by the paging.
+pub fn write_jsonl<S: Storage, W: Write>(
+    storage: &S,
+    filter: NodeFilter,
+    include_edges: bool,
+    out: &mut W,
+) -> Result<JsonlCounts> {
+    let mut counts = JsonlCounts::default();
+    let mut written: HashSet<NodeId> = HashSet::new();
+    let mut cursor: Option<NodeCursor> = None;
+
+    loop {
+        let mut page_filter = filter.clone().with_limit(PAGE_SIZE);
+        page_filter.cursor = cursor.take();
+        let page = storage.list_nodes(page_filter)?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        cursor = Some(NodeCursor::after(last));
+        let full = page.len() == PAGE_SIZE;
+        for node in page {
+            written.insert(node.id);
+            write_record(out, &JsonlRecord::Node(node))?;
+            counts.nodes += 1;
+        }
+        if !full {
+            break;
+        }
+    }
+
+    if include_edges {
+        let mut ids: Vec<NodeId> = written.iter().copied().collect();
+        ids.sort();
+        for id in ids {
+            for edge in storage.edges_from(id)? {
+                if written.contains(&edge.to) {
+                    write_record(out, &JsonlRecord::Edge(edge))?;
+                    counts.edges += 1;
+                }
+            }
+        }
+    }
+
+    out.flush()?;
+    Ok(counts)
+}
+
+fn write_record<W: Write>(out: &mut W, record: &JsonlRecord) -> Result<()> {
+    serde_json::to_writer(&mut *out, record)
+        .map_err(|e| CortexError::Validation(format!("Failed to encode record: {}", e)))?;
+    out.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Parse one line of canonical JSONL. Blank lines give `None`.
+fn parse_jsonl_line(line: &str) -> Result<Option<JsonlRecord>> {
+    if line.trim().is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_str(line)
+        .map(Some)
+        .map_err(|e| CortexError::Validation(e.to_string()))
+}
+
+/// Store every record of a canonical JSONL stream in `target`, as is.
+///
+/// Nodes are written as they are read and edges once all nodes are in,
+/// so edges may appear anywhere in the input. Stops at the first line
+/// that doesn't parse or can't be stored.
+pub fn read_jsonl<T: Storage, R: BufRead>(input: R, target: &T) -> Result<JsonlCounts> {
+    let mut counts = JsonlCounts::default();
+    let mut edges = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let record = parse_jsonl_line(&line?)
+            .map_err(|e| CortexError::Validation(format!("line {}: {}", i + 1, e)))?;
+        match record {
+            Some(JsonlRecord::Node(node)) => {
+                target.put_node(&node)?;
+                counts.nodes += 1;
+            }
+            Some(JsonlRecord::Edge(edge)) => edges.push(edge),
+            None => {}
+        }
+    }
+
+    for edge in &edges {
+        target.put_edge(edge)?;
+        counts.edges += 1;
+    }
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::RedbStorage;
+    use crate::types::{EdgeProvenance, NodeKind, Relation, Source};
+    use tempfile::TempDir;
+
+    fn node(kind: &str, title: &str) -> Node {
+        Node::new(
+            NodeKind::new(kind).unwrap(),
+            title.to_string(),
+            format!("{title} body"),
+            Source {
+                agent: "test".into(),
+                session: Some("s1".into()),
+                channel: None,
+            },
+            0.5,
+        )
+    }
+
+    fn sorted<T: Clone, K: Ord>(items: &[T], key: impl Fn(&T) -> K) -> Vec<T> {
+        let mut items = items.to_vec();
+        items.sort_by_key(key);
+        items
+    }
+
+    #[test]
+    fn test_round_trip_reproduces_graph() {
+        let dir = TempDir::new().unwrap();
+        let source = RedbStorage::open(dir.path().join("source.redb")).unwrap();
+
+        let mut a = node("decision", "Use redb");
+        a.data.tags = vec!["storage".into(), "embedded".into()];
+        a.data
+            .metadata
+            .insert("owner".into(), serde_json::json!({"team": "core", "n": 3}));
+        a.embedding = Some(vec![0.1, -0.25, 0.333_333_3, 1.0e-7]);
+        a.access_count = 4;
+        let mut b = node("fact", "redb is ACID");
+        b.importance = 0.9;
+        let c = node("fact", "Orphan");
+        for n in [&a, &b, &c] {
+            source.put_node(n).unwrap();
+        }
+        let edge = Edge::new(
+            a.id,
+            b.id,
+            Relation::new("depends_on").unwrap(),
+            0.75,
+            EdgeProvenance::Manual {
+                created_by: "test".into(),
+            },
+        );
+        source.put_edge(&edge).unwrap();
+
+        let mut buf = Vec::new();
+        let written = write_jsonl(&source, NodeFilter::new(), true, &mut buf).unwrap();
+        assert_eq!(written, JsonlCounts { nodes: 3, edges: 1 });
+        for line in String::from_utf8(buf.clone()).unwrap().lines() {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert!(matches!(value["type"].as_str(), Some("node" | "edge")));
+        }
+
+        let target = RedbStorage::open(dir.path().join("target.redb")).unwrap();
+        let read = read_jsonl(buf.as_slice(), &target).unwrap();
+        assert_eq!(read, written);
+
+        let by_id = |n: &Node| n.id;
+        assert_eq!(
+            sorted(&target.list_nodes(NodeFilter::new()).unwrap(), by_id),
+            sorted(&source.list_nodes(NodeFilter::new()).unwrap(), by_id)
+        );
+        assert_eq!(target.list_edges().unwrap(), source.list_edges().unwrap());
+        assert_eq!(target.get_node(a.id).unwrap().unwrap(), a);
+    }
+
+    #[test]
+    fn test_filtered_export_keeps_only_inner_edges() {
+        let dir = TempDir::new().unwrap();
+        let storage = RedbStorage::open(dir.path().join("g.redb")).unwrap();
+
+        let mut old = node("fact", "Old");
+        old.created_at = chrono::Utc::now() - chrono::Duration::days(30);
+        let new = node("fact", "New");
+        let newer = node("fact", "Newer");
+        for n in [&old, &new, &newer] {
+            storage.put_node(n).unwrap();
+        }
+        for (from, to) in [(new.id, newer.id), (new.id, old.id)] {
+            let edge = Edge::new(
+                from,
+                to,
+                Relation::new("related_to").unwrap(),
+                0.5,
+                EdgeProvenance::Manual {
+                    created_by: "test".into(),
+                },
+            );
+            storage.put_edge(&edge).unwrap();
+        }
+
+        let filter =
+            NodeFilter::new().created_after(chrono::Utc::now() - chrono::Duration::days(1));
+        let mut buf = Vec::new();
+        let counts = write_jsonl(&storage, filter.clone(), true, &mut buf).unwrap();
+        assert_eq!(counts, JsonlCounts { nodes: 2, edges: 1 });
+
+        let mut buf = Vec::new();
+        let counts = write_jsonl(&storage, filter, false, &mut buf).unwrap();
+        assert_eq!(counts, JsonlCounts { nodes: 2, edges: 0 });
+    }
+
+    #[test]
+    fn test_bad_line_is_reported() {
+        let dir = TempDir::new().unwrap();
+        let storage = RedbStorage::open(dir.path().join("g.redb")).unwrap();
+        let input = "\n{\"type\": \"widget\"}\n";
+        let err = read_jsonl(input.as_bytes(), &storage).unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{}", err);
+    }
+}
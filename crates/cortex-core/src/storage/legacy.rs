@@ -0,0 +1,278 @@
+//! Node layouts from earlier schema versions, for `cortex migrate`.
+//!
+//! bincode is positional, so a record written before a field was added
+//! cannot be read with the current `Node`. Each layout here mirrors the
+//! struct as it was, field for field.
+//!
+//! bincode also ignores trailing bytes, so a newer record can decode
+//! "successfully" as an older layout. Always try the current layout first.
+
+use crate::error::Result;
+use crate::types::{Embedding, Node, NodeData, NodeId, NodeKind, Source};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// `Node` at schemas v2 and v3: no `confidence`. v3 only added the
+/// `nodes_by_title` index, so both write this layout.
+#[derive(Serialize, Deserialize)]
+struct NodeV3 {
+    id: NodeId,
+    kind: NodeKind,
+    data: NodeData,
+    embedding: Option<Embedding>,
+    source: Source,
+    importance: f32,
+    access_count: u64,
+    last_accessed_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    deleted: bool,
+}
+
+/// Decode a v2 or v3 node record. `confidence` comes from the `confidence`
+/// metadata value that `keep_highest_confidence` used to read, clamped to
+/// 0.0 - 1.0, and is 1.0 when there is none.
+pub(super) fn deserialize_node_v3(bytes: &[u8]) -> Result<Node> {
+    let old: NodeV3 = bincode::deserialize(bytes)?;
+    let confidence = old
+        .data
+        .metadata
+        .get("confidence")
+        .and_then(|v| v.as_f64())
+        .map_or(1.0, |c| (c as f32).clamp(0.0, 1.0));
+    Ok(Node {
+        id: old.id,
+        kind: old.kind,
+        data: old.data,
+        embedding: old.embedding,
+        source: old.source,
+        importance: old.importance,
+        access_count: old.access_count,
+        last_accessed_at: old.last_accessed_at,
+        created_at: old.created_at,
+        updated_at: old.updated_at,
+        deleted: old.deleted,
+        confidence,
+        expires_at: None,
+        deleted_at: old.deleted.then_some(old.updated_at),
+    })
+}
+
+/// `Node` at schema v4: no `expires_at`.
+#[derive(Serialize, Deserialize)]
+struct NodeV4 {
+    id: NodeId,
+    kind: NodeKind,
+    data: NodeData,
+    embedding: Option<Embedding>,
+    source: Source,
+    importance: f32,
+    access_count: u64,
+    last_accessed_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    deleted: bool,
+    confidence: f32,
+}
+
+/// Decode a v4 node record. Nodes written before `expires_at` never expire.
+pub(super) fn deserialize_node_v4(bytes: &[u8]) -> Result<Node> {
+    let old: NodeV4 = bincode::deserialize(bytes)?;
+    Ok(Node {
+        id: old.id,
+        kind: old.kind,
+        data: old.data,
+        embedding: old.embedding,
+        source: old.source,
+        importance: old.importance,
+        access_count: old.access_count,
+        last_accessed_at: old.last_accessed_at,
+        created_at: old.created_at,
+        updated_at: old.updated_at,
+        deleted: old.deleted,
+        confidence: old.confidence,
+        expires_at: None,
+        deleted_at: old.deleted.then_some(old.updated_at),
+    })
+}
+
+/// `Node` at schema v5: no `deleted_at`.
+#[derive(Serialize, Deserialize)]
+struct NodeV5 {
+    id: NodeId,
+    kind: NodeKind,
+    data: NodeData,
+    embedding: Option<Embedding>,
+    source: Source,
+    importance: f32,
+    access_count: u64,
+    last_accessed_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    deleted: bool,
+    confidence: f32,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+/// Decode a v5 node record. A tombstone from before `deleted_at` was deleted
+/// at its last update, which is what `delete_node` stamped.
+pub(super) fn deserialize_node_v5(bytes: &[u8]) -> Result<Node> {
+    let old: NodeV5 = bincode::deserialize(bytes)?;
+    Ok(Node {
+        id: old.id,
+        kind: old.kind,
+        data: old.data,
+        embedding: old.embedding,
+        source: old.source,
+        importance: old.importance,
+        access_count: old.access_count,
+        last_accessed_at: old.last_accessed_at,
+        created_at: old.created_at,
+        updated_at: old.updated_at,
+        deleted: old.deleted,
+        confidence: old.confidence,
+        expires_at: old.expires_at,
+        deleted_at: old.deleted.then_some(old.updated_at),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v3_bytes(node: &Node) -> Vec<u8> {
+        bincode::serialize(&NodeV3 {
+            id: node.id,
+            kind: node.kind.clone(),
+            data: node.data.clone(),
+            embedding: node.embedding.clone(),
+            source: node.source.clone(),
+            importance: node.importance,
+            access_count: node.access_count,
+            last_accessed_at: node.last_accessed_at,
+            created_at: node.created_at,
+            updated_at: node.updated_at,
+            deleted: node.deleted,
+        })
+        .unwrap()
+    }
+
+    fn v4_bytes(node: &Node) -> Vec<u8> {
+        bincode::serialize(&NodeV4 {
+            id: node.id,
+            kind: node.kind.clone(),
+            data: node.data.clone(),
+            embedding: node.embedding.clone(),
+            source: node.source.clone(),
+            importance: node.importance,
+            access_count: node.access_count,
+            last_accessed_at: node.last_accessed_at,
+            created_at: node.created_at,
+            updated_at: node.updated_at,
+            deleted: node.deleted,
+            confidence: node.confidence,
+        })
+        .unwrap()
+    }
+
+    fn v5_bytes(node: &Node) -> Vec<u8> {
+        bincode::serialize(&NodeV5 {
+            id: node.id,
+            kind: node.kind.clone(),
+            data: node.data.clone(),
+            embedding: node.embedding.clone(),
+            source: node.source.clone(),
+            importance: node.importance,
+            access_count: node.access_count,
+            last_accessed_at: node.last_accessed_at,
+            created_at: node.created_at,
+            updated_at: node.updated_at,
+            deleted: node.deleted,
+            confidence: node.confidence,
+            expires_at: node.expires_at,
+        })
+        .unwrap()
+    }
+
+    fn node() -> Node {
+        let mut node = Node::new(
+            NodeKind::new("fact").unwrap(),
+            "Old record".into(),
+            "Written before confidence existed".into(),
+            Source {
+                agent: "test".into(),
+                session: None,
+                channel: None,
+            },
+            0.4,
+        );
+        node.embedding = Some(vec![0.1, 0.2]);
+        node.data.tags = vec!["legacy".into()];
+        node
+    }
+
+    #[test]
+    fn v3_nodes_default_to_full_confidence() {
+        let original = node();
+        let bytes = v3_bytes(&original);
+        // The current layout cannot read it
+        assert!(bincode::deserialize::<Node>(&bytes).is_err());
+
+        let upgraded = deserialize_node_v3(&bytes).unwrap();
+        assert_eq!(upgraded.confidence, 1.0);
+        assert_eq!(
+            Node {
+                confidence: original.confidence,
+                ..upgraded.clone()
+            },
+            original
+        );
+        // And round-trips in the new layout
+        let rewritten: Node =
+            bincode::deserialize(&bincode::serialize(&upgraded).unwrap()).unwrap();
+        assert_eq!(rewritten, upgraded);
+    }
+
+    #[test]
+    fn v3_confidence_metadata_is_lifted() {
+        let mut original = node();
+        original
+            .data
+            .metadata
+            .insert("confidence".into(), serde_json::json!(0.3));
+        let upgraded = deserialize_node_v3(&v3_bytes(&original)).unwrap();
+        assert_eq!(upgraded.confidence, 0.3);
+
+        original
+            .data
+            .metadata
+            .insert("confidence".into(), serde_json::json!(7));
+        let upgraded = deserialize_node_v3(&v3_bytes(&original)).unwrap();
+        assert_eq!(upgraded.confidence, 1.0);
+    }
+
+    #[test]
+    fn v4_nodes_never_expire() {
+        let mut original = node();
+        original.confidence = 0.6;
+        let bytes = v4_bytes(&original);
+        assert!(bincode::deserialize::<Node>(&bytes).is_err());
+
+        let upgraded = deserialize_node_v4(&bytes).unwrap();
+        assert_eq!(upgraded, original);
+        assert_eq!(upgraded.expires_at, None);
+    }
+
+    #[test]
+    fn v5_tombstones_were_deleted_at_last_update() {
+        let mut original = node();
+        original.expires_at = Some(original.created_at);
+        let upgraded = deserialize_node_v5(&v5_bytes(&original)).unwrap();
+        assert!(bincode::deserialize::<Node>(&v5_bytes(&original)).is_err());
+        assert_eq!(upgraded, original);
+
+        original.deleted = true;
+        let upgraded = deserialize_node_v5(&v5_bytes(&original)).unwrap();
+        assert_eq!(upgraded.deleted_at, Some(original.updated_at));
+    }
+}
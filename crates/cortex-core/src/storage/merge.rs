@@ -0,0 +1,341 @@
+use crate::error::{CortexError, Result};
+use crate::storage::{NodeFilter, Storage};
+use crate::types::{Node, NodeId};
+use crate::vector::cosine_similarity;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// What to do when a source node matches a node already in the target,
+/// either by id or as a duplicate (same kind and title, similar embedding).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergeConflict {
+    /// Fold the two into the target's node. Title, body and embedding come
+    /// from whichever was updated last; tags and metadata are unioned and
+    /// the higher importance is kept.
+    #[default]
+    KeepNewest,
+    /// Copy the source node as a separate node, under a fresh id if its id
+    /// is taken.
+    KeepBoth,
+}
+
+#[derive(Debug, Clone)]
+pub struct MergeOptions {
+    pub on_conflict: MergeConflict,
+    /// Minimum embedding similarity for two same-titled nodes to count as
+    /// duplicates. Default: 0.92, as for the dedup scanner.
+    pub dedup_threshold: f32,
+}
+
+impl Default for MergeOptions {
+    fn default() -> Self {
+        Self {
+            on_conflict: MergeConflict::default(),
+            dedup_threshold: 0.92,
+        }
+    }
+}
+
+/// Counts from [`merge_storage`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MergeReport {
+    /// Source nodes written to the target as new nodes.
+    pub nodes_added: usize,
+    /// Source nodes folded into an existing target node.
+    pub nodes_merged: usize,
+    /// Soft-deleted source nodes, which are not copied.
+    pub nodes_skipped: usize,
+    pub edges_added: usize,
+    /// Edges already in the target once remapped, or left dangling or
+    /// self-referencing by a merge.
+    pub edges_skipped: usize,
+}
+
+/// Copy the live nodes and edges of `source` into `target`.
+///
+/// A source node that duplicates a target node is resolved per
+/// `options.on_conflict`, and edges are remapped to wherever their
+/// endpoints ended up. `source` is only read.
+pub fn merge_storage<S: Storage, T: Storage>(
+    source: &S,
+    target: &T,
+    options: &MergeOptions,
+) -> Result<MergeReport> {
+    let mut report = MergeReport::default();
+    // Source id -> id of the node it became in the target
+    let mut id_map: HashMap<NodeId, NodeId> = HashMap::new();
+    // (kind, title) -> live target nodes, for duplicate lookups
+    let mut titles: HashMap<(String, String), Vec<NodeId>> = HashMap::new();
+    for h in target.list_node_headers(NodeFilter::new())? {
+        titles
+            .entry((h.kind.as_str().to_string(), h.title))
+            .or_default()
+            .push(h.id);
+    }
+
+    for node in source.list_nodes(NodeFilter::new().include_deleted())? {
+        if node.deleted {
+            report.nodes_skipped += 1;
+            continue;
+        }
+        let existing = match target.get_node(node.id)? {
+            Some(n) => Some(n),
+            None => find_duplicate(target, &titles, &node, options.dedup_threshold)?,
+        };
+        let title_key = (node.kind.as_str().to_string(), node.data.title.clone());
+        let Some(existing) = existing else {
+            target.put_node(&node)?;
+            id_map.insert(node.id, node.id);
+            titles.entry(title_key).or_default().push(node.id);
+            report.nodes_added += 1;
+            continue;
+        };
+        match options.on_conflict {
+            MergeConflict::KeepNewest => {
+                target.put_node(&fold(&existing, &node))?;
+                id_map.insert(node.id, existing.id);
+                report.nodes_merged += 1;
+            }
+            MergeConflict::KeepBoth => {
+                let mut copy = node.clone();
+                if existing.id == node.id {
+                    copy.id = NodeId::now_v7();
+                }
+                target.put_node(&copy)?;
+                id_map.insert(node.id, copy.id);
+                titles.entry(title_key).or_default().push(copy.id);
+                report.nodes_added += 1;
+            }
+        }
+    }
+
+    for mut edge in source.list_edges()? {
+        let (Some(&from), Some(&to)) = (id_map.get(&edge.from), id_map.get(&edge.to)) else {
+            report.edges_skipped += 1;
+            continue;
+        };
+        edge.from = from;
+        edge.to = to;
+        if from == to {
+            report.edges_skipped += 1;
+            continue;
+        }
+        if let Some(existing) = target.get_edge(edge.id)? {
+            if existing.from == from && existing.to == to && existing.relation == edge.relation {
+                report.edges_skipped += 1;
+                continue;
+            }
+            edge.id = crate::types::EdgeId::now_v7();
+        }
+        match target.put_edge(&edge) {
+            Ok(()) => report.edges_added += 1,
+            Err(CortexError::DuplicateEdge { .. }) | Err(CortexError::InvalidEdge { .. }) => {
+                report.edges_skipped += 1
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(report)
+}
+
+/// A live target node of the same kind and title whose embedding is
+/// within `threshold` of `node`'s. Without embeddings on both sides the
+/// bodies must match instead.
+fn find_duplicate<T: Storage>(
+    target: &T,
+    titles: &HashMap<(String, String), Vec<NodeId>>,
+    node: &Node,
+    threshold: f32,
+) -> Result<Option<Node>> {
+    let key = (node.kind.as_str().to_string(), node.data.title.clone());
+    for id in titles.get(&key).into_iter().flatten() {
+        let Some(candidate) = target.get_node(*id)? else {
+            continue;
+        };
+        let duplicate = match (&node.embedding, &candidate.embedding) {
+            (Some(a), Some(b)) if a.len() == b.len() => cosine_similarity(a, b) >= threshold,
+            (None, None) => node.data.body == candidate.data.body,
+            _ => false,
+        };
+        if duplicate {
+            return Ok(Some(candidate));
+        }
+    }
+    Ok(None)
+}
+
+/// `existing` and `incoming` folded into one node under `existing`'s id.
+fn fold(existing: &Node, incoming: &Node) -> Node {
+    let (newer, older) = if incoming.updated_at > existing.updated_at {
+        (incoming, existing)
+    } else {
+        (existing, incoming)
+    };
+    let mut merged = newer.clone();
+    merged.id = existing.id;
+    merged.created_at = existing.created_at.min(incoming.created_at);
+    for tag in &older.data.tags {
+        if !merged.data.tags.contains(tag) {
+            merged.data.tags.push(tag.clone());
+        }
+    }
+    for (key, value) in &older.data.metadata {
+        merged
+            .data
+            .metadata
+            .entry(key.clone())
+            .or_insert_with(|| value.clone());
+    }
+    merged.importance = existing.importance.max(incoming.importance);
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::RedbStorage;
+    use crate::types::{Edge, EdgeProvenance, NodeKind, Relation, Source};
+    use tempfile::TempDir;
+
+    fn node(title: &str, embedding: Vec<f32>) -> Node {
+        let mut node = Node::new(
+            NodeKind::new("fact").unwrap(),
+            title.to_string(),
+            format!("{title} body"),
+            Source {
+                agent: "test".into(),
+                session: None,
+                channel: None,
+            },
+            0.5,
+        );
+        node.embedding = Some(embedding);
+        node
+    }
+
+    fn edge(from: NodeId, to: NodeId) -> Edge {
+        Edge::new(
+            from,
+            to,
+            Relation::new("related_to").unwrap(),
+            0.8,
+            EdgeProvenance::Manual {
+                created_by: "test".into(),
+            },
+        )
+    }
+
+    fn open(dir: &TempDir, name: &str) -> RedbStorage {
+        RedbStorage::open(dir.path().join(name)).unwrap()
+    }
+
+    #[test]
+    fn test_clean_merge_copies_everything() {
+        let dir = TempDir::new().unwrap();
+        let source = open(&dir, "source.redb");
+        let target = open(&dir, "target.redb");
+
+        let a = node("Alpha", vec![1.0, 0.0]);
+        let b = node("Beta", vec![0.0, 1.0]);
+        let gone = node("Gone", vec![1.0, 1.0]);
+        for n in [&a, &b, &gone] {
+            source.put_node(n).unwrap();
+        }
+        source.put_edge(&edge(a.id, b.id)).unwrap();
+        source.delete_node(gone.id).unwrap();
+        target.put_node(&node("Gamma", vec![1.0, 0.0])).unwrap();
+
+        let report = merge_storage(&source, &target, &MergeOptions::default()).unwrap();
+        assert_eq!(
+            report,
+            MergeReport {
+                nodes_added: 2,
+                nodes_skipped: 1,
+                edges_added: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(target.get_node(a.id).unwrap().unwrap().data.title, "Alpha");
+        assert!(target.get_node(gone.id).unwrap().is_none());
+        assert_eq!(target.edges_between(a.id, b.id).unwrap().len(), 1);
+        assert_eq!(target.list_nodes(NodeFilter::new()).unwrap().len(), 3);
+
+        // Merging again finds everything already there
+        let again = merge_storage(&source, &target, &MergeOptions::default()).unwrap();
+        assert_eq!(again.nodes_added, 0);
+        assert_eq!(again.nodes_merged, 2);
+        assert_eq!(again.edges_skipped, 1);
+        assert_eq!(target.list_nodes(NodeFilter::new()).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_duplicates_merge_and_edges_follow() {
+        let dir = TempDir::new().unwrap();
+        let source = open(&dir, "source.redb");
+        let target = open(&dir, "target.redb");
+
+        let mut kept = node("JWT auth", vec![1.0, 0.0]);
+        kept.data.tags = vec!["auth".into()];
+        kept.importance = 0.9;
+        target.put_node(&kept).unwrap();
+
+        // Same title, near-identical embedding, written later
+        let mut dup = node("JWT auth", vec![0.99, 0.05]);
+        dup.data.body = "Tokens are RS256-signed".into();
+        dup.data.tags = vec!["security".into()];
+        dup.updated_at = kept.updated_at + chrono::Duration::seconds(1);
+        // Same title but a different meaning
+        let namesake = node("JWT auth", vec![0.0, 1.0]);
+        let other = node("Sessions", vec![0.5, 0.5]);
+        for n in [&dup, &namesake, &other] {
+            source.put_node(n).unwrap();
+        }
+        source.put_edge(&edge(other.id, dup.id)).unwrap();
+
+        let report = merge_storage(&source, &target, &MergeOptions::default()).unwrap();
+        assert_eq!(report.nodes_merged, 1);
+        assert_eq!(report.nodes_added, 2);
+        assert_eq!(report.edges_added, 1);
+
+        let merged = target.get_node(kept.id).unwrap().unwrap();
+        assert_eq!(merged.data.body, "Tokens are RS256-signed");
+        assert_eq!(merged.data.tags, vec!["security", "auth"]);
+        assert_eq!(merged.importance, 0.9);
+        assert!(target.get_node(dup.id).unwrap().is_none());
+        assert!(target.get_node(namesake.id).unwrap().is_some());
+        // The edge now points at the node the duplicate merged into
+        assert_eq!(target.edges_between(other.id, kept.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_keep_both_copies_conflicts() {
+        let dir = TempDir::new().unwrap();
+        let source = open(&dir, "source.redb");
+        let target = open(&dir, "target.redb");
+
+        let shared = node("Shared", vec![1.0, 0.0]);
+        target.put_node(&shared).unwrap();
+        let mut edited = shared.clone();
+        edited.data.body = "edited elsewhere".into();
+        source.put_node(&edited).unwrap();
+
+        let options = MergeOptions {
+            on_conflict: MergeConflict::KeepBoth,
+            ..Default::default()
+        };
+        let report = merge_storage(&source, &target, &options).unwrap();
+        assert_eq!(report.nodes_added, 1);
+        assert_eq!(report.nodes_merged, 0);
+
+        let nodes = target.list_nodes(NodeFilter::new()).unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(
+            target.get_node(shared.id).unwrap().unwrap().data.body,
+            "Shared body"
+        );
+        assert!(nodes
+            .iter()
+            .any(|n| n.id != shared.id && n.data.body == "edited elsewhere"));
+    }
+}
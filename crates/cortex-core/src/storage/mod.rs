@@ -1,8 +1,22 @@
+mod diff;
 pub mod encrypted;
 mod filters;
+mod header;
+mod jsonl;
+mod legacy;
+mod merge;
 mod redb_storage;
 mod traits;
+mod verify;
 
-pub use filters::{NodeFilter, StorageStats};
-pub use redb_storage::{RedbStorage, CURRENT_SCHEMA_VERSION};
-pub use traits::Storage;
+pub use diff::{diff_storage, FieldChange, GraphDiff, NodeChange};
+pub use filters::{NodeCursor, NodeFilter, StorageStats};
+pub use jsonl::{read_jsonl, write_jsonl, JsonlCounts, JsonlRecord};
+pub use merge::{merge_storage, MergeConflict, MergeOptions, MergeReport};
+pub use redb_storage::{
+    RedbStorage, SnapshotInfo, CURRENT_SCHEMA_VERSION, SNAPSHOT_GRAPH_VERSION_KEY,
+};
+pub use traits::{Storage, StorageTransaction};
+pub use verify::{
+    repair, verify, DuplicateTitle, RelationCycle, RepairSummary, VerifyConfig, VerifyReport,
+};
@@ -1,14 +1,16 @@
 use crate::error::{CortexError, Result};
 use crate::policies::audit::{AuditAction, AuditEntry, AuditLog};
 use crate::storage::filters::{NodeFilter, StorageStats};
-use crate::storage::traits::Storage;
-use crate::types::{Edge, EdgeId, Node, NodeId};
+use crate::storage::traits::{Storage, StorageTransaction};
+use crate::types::{Edge, EdgeId, Node, NodeHeader, NodeId, NodeKind, Relation};
 use chrono::{DateTime, Utc};
 use redb::{
-    Database, MultimapTableDefinition, ReadableMultimapTable, ReadableTable, TableDefinition,
+    Database, Key, MultimapTableDefinition, ReadTransaction, ReadableMultimapTable, ReadableTable,
+    ReadableTableMetadata, TableDefinition, TableStats, Value, WriteTransaction,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 // Table definitions
@@ -27,6 +29,17 @@ const NODES_BY_TAG: MultimapTableDefinition<&str, &[u8; 16]> =
     MultimapTableDefinition::new("nodes_by_tag");
 const NODES_BY_SOURCE: MultimapTableDefinition<&str, &[u8; 16]> =
     MultimapTableDefinition::new("nodes_by_source");
+// (kind, title) → node id. Multimap because titles are not unique.
+const NODES_BY_TITLE: MultimapTableDefinition<(&str, &str), &[u8; 16]> =
+    MultimapTableDefinition::new("nodes_by_title");
+// (created_at seconds, nanoseconds, id) in listing order; rebuilt on open
+// when empty, so it needs no migration.
+const NODES_BY_CREATED: TableDefinition<(i64, u32, &[u8; 16]), ()> =
+    TableDefinition::new("nodes_by_created");
+
+// Ingest dedup key → node id, for idempotent event ingest
+const INGESTED_EVENT_IDS: TableDefinition<&str, &[u8; 16]> =
+    TableDefinition::new("ingested_event_ids");
 
 // Metadata table
 const META: TableDefinition<&str, &[u8]> = TableDefinition::new("meta");
@@ -34,17 +47,35 @@ const META: TableDefinition<&str, &[u8]> = TableDefinition::new("meta");
 /// Current schema version.
 /// v1 = original (NodeKind stored as u8 in nodes_by_kind)
 /// v2 = string-based NodeKind/Relation, nodes_by_kind_v2 table
-pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+/// v3 = nodes_by_title index (backfilled by `cortex migrate`)
+/// v4 = `Node.confidence` (existing nodes rewritten by `cortex migrate`)
+/// v5 = `Node.expires_at` (existing nodes rewritten by `cortex migrate`)
+/// v6 = `Node.deleted_at` (existing nodes rewritten by `cortex migrate`)
+pub const CURRENT_SCHEMA_VERSION: u32 = 6;
 const SCHEMA_VERSION_KEY: &str = "schema_version";
 const STATS_NODE_COUNT_KEY: &str = "stats:node_count";
 const STATS_EDGE_COUNT_KEY: &str = "stats:edge_count";
 
+/// Graph version a snapshot is current up to, written into the snapshot only.
+pub const SNAPSHOT_GRAPH_VERSION_KEY: &str = "snapshot:graph_version";
+
+/// What a snapshot captured. Counts come from the same read transaction as
+/// the copied tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotInfo {
+    pub node_count: u64,
+    pub edge_count: u64,
+    /// `None` when no live graph version was supplied.
+    pub graph_version: Option<u64>,
+}
+
 /// Redb-based storage implementation
 pub struct RedbStorage {
     db: Arc<Database>,
     #[allow(dead_code)]
     path: PathBuf,
     audit_log: Option<Arc<AuditLog>>,
+    read_only: bool,
 }
 
 impl RedbStorage {
@@ -82,6 +113,9 @@ impl RedbStorage {
                 let _ = write_txn.open_multimap_table(EDGES_BY_TO)?;
                 let _ = write_txn.open_multimap_table(NODES_BY_TAG)?;
                 let _ = write_txn.open_multimap_table(NODES_BY_SOURCE)?;
+                let _ = write_txn.open_multimap_table(NODES_BY_TITLE)?;
+                let _ = write_txn.open_table(NODES_BY_CREATED)?;
+                let _ = write_txn.open_table(INGESTED_EVENT_IDS)?;
                 let mut meta = write_txn.open_table(META)?;
                 meta.insert(
                     SCHEMA_VERSION_KEY,
@@ -105,8 +139,11 @@ impl RedbStorage {
                 let _ = write_txn.open_multimap_table(EDGES_BY_TO)?;
                 let _ = write_txn.open_multimap_table(NODES_BY_TAG)?;
                 let _ = write_txn.open_multimap_table(NODES_BY_SOURCE)?;
+                let _ = write_txn.open_multimap_table(NODES_BY_TITLE)?;
+                let _ = write_txn.open_table(INGESTED_EVENT_IDS)?;
                 let _ = write_txn.open_table(META)?;
             }
+            Self::backfill_created_index(&write_txn)?;
             write_txn.commit()?;
         }
 
@@ -114,9 +151,51 @@ impl RedbStorage {
             db: Arc::new(db),
             path,
             audit_log: None,
+            read_only: false,
+        })
+    }
+
+    /// Open an existing database without writing to it.
+    ///
+    /// No tables are created and the schema must already be current. Every
+    /// mutating method returns [`CortexError::ReadOnly`]. redb still locks
+    /// the file, so to inspect a database a server is using, open a
+    /// snapshot of it (`cortex backup`).
+    pub fn open_read_only<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if !path.exists() {
+            return Err(CortexError::Validation(format!(
+                "Database not found at {}",
+                path.display()
+            )));
+        }
+
+        let db = Database::builder()
+            .set_repair_callback(|_| {})
+            .open(&path)?;
+        Self::check_schema_version(&db)?;
+        Self::preflight_check(&db)?;
+
+        Ok(Self {
+            db: Arc::new(db),
+            path,
+            audit_log: None,
+            read_only: true,
         })
     }
 
+    /// Whether this storage was opened with [`RedbStorage::open_read_only`].
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    fn ensure_writable(&self) -> Result<()> {
+        if self.read_only {
+            return Err(CortexError::ReadOnly);
+        }
+        Ok(())
+    }
+
     /// Sample up to 10 node records and hard-fail if ALL of them fail to deserialize.
     ///
     /// This catches schema regressions (e.g. a struct field added without a migration)
@@ -157,6 +236,31 @@ impl RedbStorage {
         Ok(())
     }
 
+    /// Fill `nodes_by_created` for a database written before it existed.
+    fn backfill_created_index(txn: &WriteTransaction) -> Result<()> {
+        let nodes = txn.open_table(NODES)?;
+        let mut created = txn.open_table(NODES_BY_CREATED)?;
+        if !created.is_empty()? || nodes.is_empty()? {
+            return Ok(());
+        }
+        for item in nodes.iter()? {
+            let (key, value) = item?;
+            if let Ok(header) = super::header::deserialize_node_header(value.value()) {
+                created.insert(Self::created_key(header.created_at, key.value()), ())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Key of a node in `nodes_by_created`.
+    fn created_key(created_at: DateTime<Utc>, id: &[u8; 16]) -> (i64, u32, &[u8; 16]) {
+        (
+            created_at.timestamp(),
+            created_at.timestamp_subsec_nanos(),
+            id,
+        )
+    }
+
     /// Check schema version. Returns error if migration is needed.
     fn check_schema_version(db: &Database) -> Result<()> {
         let read_txn = db.begin_read()?;
@@ -191,12 +295,100 @@ impl RedbStorage {
         &self.path
     }
 
+    /// Write a point-in-time copy of the database to `path` while writes
+    /// continue.
+    ///
+    /// Every table is copied from a single read transaction, so the copy is
+    /// one committed state. `graph_version` is loaded just before that
+    /// transaction opens: each mutation it counts is in the copy, and it is
+    /// stored in the copy's META under [`SNAPSHOT_GRAPH_VERSION_KEY`].
+    /// Fails if `path` already exists.
+    pub fn snapshot_versioned(
+        &self,
+        path: &Path,
+        graph_version: Option<&AtomicU64>,
+    ) -> Result<SnapshotInfo> {
+        if path.exists() {
+            return Err(CortexError::Validation(format!(
+                "Snapshot destination {} already exists",
+                path.display()
+            )));
+        }
+        let graph_version = graph_version.map(|v| v.load(Ordering::SeqCst));
+        let read_txn = self.db.begin_read()?;
+
+        let result = Self::copy_snapshot(&read_txn, path, graph_version);
+        if result.is_err() {
+            let _ = std::fs::remove_file(path);
+        }
+        result
+    }
+
+    fn copy_snapshot(
+        read_txn: &ReadTransaction,
+        path: &Path,
+        graph_version: Option<u64>,
+    ) -> Result<SnapshotInfo> {
+        let dest = Database::create(path)?;
+        let write_txn = dest.begin_write()?;
+        copy_table(read_txn, &write_txn, NODES)?;
+        copy_table(read_txn, &write_txn, EDGES)?;
+        copy_table(read_txn, &write_txn, AUDIT_TABLE)?;
+        copy_table(read_txn, &write_txn, INGESTED_EVENT_IDS)?;
+        copy_table(read_txn, &write_txn, META)?;
+        copy_multimap_table(read_txn, &write_txn, NODES_BY_KIND)?;
+        copy_multimap_table(read_txn, &write_txn, EDGES_BY_FROM)?;
+        copy_multimap_table(read_txn, &write_txn, EDGES_BY_TO)?;
+        copy_multimap_table(read_txn, &write_txn, NODES_BY_TAG)?;
+        copy_multimap_table(read_txn, &write_txn, NODES_BY_SOURCE)?;
+        copy_multimap_table(read_txn, &write_txn, NODES_BY_TITLE)?;
+        // Absent from databases only ever opened read-only since the index
+        // was added; opening the snapshot writable rebuilds it.
+        if read_txn.open_table(NODES_BY_CREATED).is_ok() {
+            copy_table(read_txn, &write_txn, NODES_BY_CREATED)?;
+        }
+
+        let meta = read_txn.open_table(META)?;
+        let counter = |key: &str| -> Result<Option<u64>> {
+            Ok(meta.get(key)?.map(|v| {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(v.value());
+                u64::from_le_bytes(bytes)
+            }))
+        };
+        let node_count = match counter(STATS_NODE_COUNT_KEY)? {
+            Some(n) => n,
+            None => read_txn.open_table(NODES)?.len()?,
+        };
+        let edge_count = match counter(STATS_EDGE_COUNT_KEY)? {
+            Some(n) => n,
+            None => read_txn.open_table(EDGES)?.len()?,
+        };
+
+        if let Some(version) = graph_version {
+            let mut meta = write_txn.open_table(META)?;
+            meta.insert(SNAPSHOT_GRAPH_VERSION_KEY, version.to_le_bytes().as_slice())?;
+        }
+        write_txn.commit()?;
+
+        Ok(SnapshotInfo {
+            node_count,
+            edge_count,
+            graph_version,
+        })
+    }
+
     /// Attach an audit log. Returns `self` for builder-style chaining.
     pub fn with_audit_log(mut self, log: Arc<AuditLog>) -> Self {
         self.audit_log = Some(log);
         self
     }
 
+    /// The attached audit log, if any.
+    pub fn audit_log(&self) -> Option<&Arc<AuditLog>> {
+        self.audit_log.as_ref()
+    }
+
     /// Create an AuditLog backed by the same redb Database instance.
     pub fn create_audit_log(&self) -> AuditLog {
         AuditLog::new(self.db.clone())
@@ -236,6 +428,24 @@ impl RedbStorage {
         Self::deserialize_node(bytes)
     }
 
+    /// Public helper for migration: deserialize a node written with the v2
+    /// or v3 layout, before `confidence` existed.
+    pub fn try_deserialize_node_v3(bytes: &[u8]) -> Result<Node> {
+        super::legacy::deserialize_node_v3(bytes)
+    }
+
+    /// Public helper for migration: deserialize a node written with the v4
+    /// layout, before `expires_at` existed.
+    pub fn try_deserialize_node_v4(bytes: &[u8]) -> Result<Node> {
+        super::legacy::deserialize_node_v4(bytes)
+    }
+
+    /// Public helper for migration: deserialize a node written with the v5
+    /// layout, before `deleted_at` existed.
+    pub fn try_deserialize_node_v5(bytes: &[u8]) -> Result<Node> {
+        super::legacy::deserialize_node_v5(bytes)
+    }
+
     /// Serialize an edge to bytes
     fn serialize_edge(edge: &Edge) -> Result<Vec<u8>> {
         bincode::serialize(edge).map_err(CortexError::from)
@@ -281,6 +491,37 @@ impl RedbStorage {
             source_table.insert(node.source.agent.as_str(), &node_id_bytes)?;
         }
 
+        // Update title index
+        {
+            let mut title_table = txn.open_multimap_table(NODES_BY_TITLE)?;
+
+            // Remove old (kind, title) if either changed
+            if let Some(old) = old_node {
+                if old.kind != node.kind || old.data.title != node.data.title {
+                    title_table
+                        .remove((old.kind.as_str(), old.data.title.as_str()), &node_id_bytes)?;
+                }
+            }
+
+            title_table.insert(
+                (node.kind.as_str(), node.data.title.as_str()),
+                &node_id_bytes,
+            )?;
+        }
+
+        // Update creation-order index
+        {
+            let mut created_table = txn.open_table(NODES_BY_CREATED)?;
+
+            if let Some(old) = old_node {
+                if old.created_at != node.created_at {
+                    created_table.remove(Self::created_key(old.created_at, &node_id_bytes))?;
+                }
+            }
+
+            created_table.insert(Self::created_key(node.created_at, &node_id_bytes), ())?;
+        }
+
         // Update tag index
         {
             let mut tag_table = txn.open_multimap_table(NODES_BY_TAG)?;
@@ -341,8 +582,143 @@ impl RedbStorage {
         Ok(())
     }
 
+    /// Shared by `list_nodes` and `list_node_headers`, which differ only in
+    /// how much of each record they decode.
+    fn list_records<T: Listed>(&self, filter: &NodeFilter) -> Result<Vec<T>> {
+        let read_txn = self.db.begin_read()?;
+        let nodes_table = read_txn.open_table(NODES)?;
+
+        let mut nodes = Vec::new();
+
+        // Candidate ids from the secondary indexes, when the filter allows it.
+        // Tags are ORed (union) unless the filter asks for all of them
+        // (intersection); kinds are ORed and then intersected with the tag set.
+        let mut candidates: Option<Vec<NodeId>> = None;
+
+        if let Some(tags) = filter.tags.as_ref().filter(|t| !t.is_empty()) {
+            let tag_index = read_txn.open_multimap_table(NODES_BY_TAG)?;
+            let mut ids: Option<std::collections::HashSet<NodeId>> = None;
+            for tag in tags {
+                let tagged: std::collections::HashSet<NodeId> = tag_index
+                    .get(tag.as_str())?
+                    .map(|result| result.map(|guard| Self::bytes_to_uuid(guard.value())))
+                    .collect::<std::result::Result<_, _>>()?;
+                ids = Some(match ids {
+                    Some(acc) if filter.all_tags => acc.intersection(&tagged).copied().collect(),
+                    Some(mut acc) => {
+                        acc.extend(tagged);
+                        acc
+                    }
+                    None => tagged,
+                });
+                if filter.all_tags && ids.as_ref().is_some_and(|s| s.is_empty()) {
+                    break;
+                }
+            }
+            let mut ids: Vec<NodeId> = ids.unwrap_or_default().into_iter().collect();
+            ids.sort_unstable(); // same order as the kind index
+            candidates = Some(ids);
+        }
+
+        if let Some(ref kinds) = filter.kinds {
+            let kind_index = read_txn.open_multimap_table(NODES_BY_KIND)?;
+            let mut kind_ids = Vec::new();
+            for kind in kinds {
+                for result in kind_index.get(kind.as_str())? {
+                    kind_ids.push(Self::bytes_to_uuid(result?.value()));
+                }
+            }
+            candidates = Some(match candidates {
+                Some(tagged) => {
+                    let kind_set: std::collections::HashSet<NodeId> =
+                        kind_ids.into_iter().collect();
+                    tagged
+                        .into_iter()
+                        .filter(|id| kind_set.contains(id))
+                        .collect()
+                }
+                None => kind_ids,
+            });
+        }
+
+        let offset = filter.offset.unwrap_or(0);
+        let wanted = filter.limit.map(|l| offset.saturating_add(l));
+
+        // Unfiltered by kind or tag, walk `nodes_by_created` newest first when
+        // it covers every node: seek to the cursor and stop as soon as the
+        // page is full. A candidate set is fetched and sorted below instead,
+        // so a small one stays cheap however many nodes there are.
+        let created_index = match candidates {
+            Some(_) => None,
+            None => read_txn
+                .open_table(NODES_BY_CREATED)
+                .ok()
+                .filter(|t| matches!((t.len(), nodes_table.len()), (Ok(a), Ok(b)) if a == b)),
+        };
+        if let Some(created_index) = created_index {
+            let position = filter
+                .cursor
+                .map(|c| c.position())
+                .map(|(at, id)| (at, Self::uuid_to_bytes(&id)));
+            let range = match &position {
+                Some((at, id)) => created_index.range(..Self::created_key(*at, id))?,
+                None => created_index.iter()?,
+            };
+            for item in range.rev() {
+                if wanted.is_some_and(|w| nodes.len() >= w) {
+                    break;
+                }
+                let (key, _) = item?;
+                let (_, _, id_bytes) = key.value();
+                if let Some(bytes) = nodes_table.get(id_bytes)? {
+                    if let Ok(node) = T::decode(bytes.value()) {
+                        if Self::node_matches_filter(node.view(), filter) {
+                            nodes.push(node);
+                        }
+                    }
+                }
+            }
+            return Ok(nodes.into_iter().skip(offset).collect());
+        }
+
+        if let Some(node_ids) = candidates {
+            for node_id in node_ids {
+                let node_id_bytes = Self::uuid_to_bytes(&node_id);
+                if let Some(bytes) = nodes_table.get(&node_id_bytes)? {
+                    if let Ok(node) = T::decode(bytes.value()) {
+                        if Self::node_matches_filter(node.view(), filter) {
+                            nodes.push(node);
+                        }
+                    }
+                }
+            }
+        } else {
+            // Full table scan
+            for item in nodes_table.iter()? {
+                let (_, value) = item?;
+                let node = match T::decode(value.value()) {
+                    Ok(n) => n,
+                    Err(_) => continue, // skip corrupt records
+                };
+                if Self::node_matches_filter(node.view(), filter) {
+                    nodes.push(node);
+                }
+            }
+        }
+
+        // Otherwise sort every match: newest first, ties broken by id, the
+        // same order the index walk produces. The cursor was applied by
+        // `node_matches_filter`.
+        nodes.sort_by_key(|n| {
+            let view = n.view();
+            std::cmp::Reverse((view.created_at, view.id))
+        });
+        nodes.truncate(wanted.unwrap_or(usize::MAX));
+        Ok(nodes.into_iter().skip(offset).collect())
+    }
+
     /// Check if a node matches the filter criteria
-    fn node_matches_filter(node: &Node, filter: &NodeFilter) -> bool {
+    fn node_matches_filter(node: NodeView<'_>, filter: &NodeFilter) -> bool {
         // Check deleted flag
         if !filter.include_deleted && node.deleted {
             return false;
@@ -350,21 +726,26 @@ impl RedbStorage {
 
         // Check kind
         if let Some(ref kinds) = filter.kinds {
-            if !kinds.contains(&node.kind) {
+            if !kinds.contains(node.kind) {
                 return false;
             }
         }
 
-        // Check tags (node must have at least one of the filter tags)
+        // Check tags (at least one filter tag, or every one in all-tags mode)
         if let Some(ref tags) = filter.tags {
-            if !tags.iter().any(|t| node.data.tags.contains(t)) {
+            let matches = if filter.all_tags {
+                tags.iter().all(|t| node.tags.contains(t))
+            } else {
+                tags.iter().any(|t| node.tags.contains(t))
+            };
+            if !matches {
                 return false;
             }
         }
 
         // Check source agent
         if let Some(ref agent) = filter.source_agent {
-            if node.source.agent != *agent {
+            if node.agent != agent.as_str() {
                 return false;
             }
         }
@@ -401,43 +782,20 @@ impl RedbStorage {
             }
         }
 
-        true
-    }
-
-    fn increment_meta_counter(&self, key: &str) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut meta = write_txn.open_table(META)?;
-            let current = meta
-                .get(key)?
-                .map(|v| {
-                    let mut bytes = [0u8; 8];
-                    bytes.copy_from_slice(v.value());
-                    u64::from_le_bytes(bytes)
-                })
-                .unwrap_or(0);
-            meta.insert(key, (current + 1).to_le_bytes().as_slice())?;
+        // Check changed_after
+        if let Some(after) = filter.changed_after {
+            if node.created_at <= after && node.updated_at <= after {
+                return false;
+            }
         }
-        write_txn.commit()?;
-        Ok(())
-    }
 
-    fn decrement_meta_counter(&self, key: &str) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut meta = write_txn.open_table(META)?;
-            let current = meta
-                .get(key)?
-                .map(|v| {
-                    let mut bytes = [0u8; 8];
-                    bytes.copy_from_slice(v.value());
-                    u64::from_le_bytes(bytes)
-                })
-                .unwrap_or(0);
-            meta.insert(key, current.saturating_sub(1).to_le_bytes().as_slice())?;
+        if let Some(ref cursor) = filter.cursor {
+            if !cursor.precedes_at(node.created_at, node.id) {
+                return false;
+            }
         }
-        write_txn.commit()?;
-        Ok(())
+
+        true
     }
 
     fn read_meta_counter(&self, key: &str) -> Result<Option<u64>> {
@@ -450,6 +808,54 @@ impl RedbStorage {
         }))
     }
 
+    /// On-disk size of every table, from redb's per-table btree stats.
+    fn table_bytes(txn: &ReadTransaction) -> Result<BTreeMap<String, u64>> {
+        fn size(stats: TableStats) -> u64 {
+            stats.stored_bytes() + stats.metadata_bytes() + stats.fragmented_bytes()
+        }
+
+        let mut sizes = BTreeMap::new();
+        let mut add = |name: &str, bytes: u64| {
+            sizes.insert(name.to_string(), bytes);
+        };
+        add("nodes", size(txn.open_table(NODES)?.stats()?));
+        add("edges", size(txn.open_table(EDGES)?.stats()?));
+        add("audit", size(txn.open_table(AUDIT_TABLE)?.stats()?));
+        add("meta", size(txn.open_table(META)?.stats()?));
+        add(
+            "ingested_event_ids",
+            size(txn.open_table(INGESTED_EVENT_IDS)?.stats()?),
+        );
+        add(
+            "nodes_by_kind_v2",
+            size(txn.open_multimap_table(NODES_BY_KIND)?.stats()?),
+        );
+        add(
+            "edges_by_from",
+            size(txn.open_multimap_table(EDGES_BY_FROM)?.stats()?),
+        );
+        add(
+            "edges_by_to",
+            size(txn.open_multimap_table(EDGES_BY_TO)?.stats()?),
+        );
+        add(
+            "nodes_by_tag",
+            size(txn.open_multimap_table(NODES_BY_TAG)?.stats()?),
+        );
+        add(
+            "nodes_by_source",
+            size(txn.open_multimap_table(NODES_BY_SOURCE)?.stats()?),
+        );
+        add(
+            "nodes_by_title",
+            size(txn.open_multimap_table(NODES_BY_TITLE)?.stats()?),
+        );
+        if let Ok(table) = txn.open_table(NODES_BY_CREATED) {
+            add("nodes_by_created", size(table.stats()?));
+        }
+        Ok(sizes)
+    }
+
     /// Atomically update the weight of an edge identified by (from, to, relation).
     ///
     /// Reads the edge, applies `f` to its weight, and writes the updated edge
@@ -463,69 +869,69 @@ impl RedbStorage {
         relation: &crate::types::Relation,
         f: impl FnOnce(f32) -> f32,
     ) -> Result<(f32, f32)> {
-        let from_bytes = Self::uuid_to_bytes(&from);
+        self.ensure_writable()?;
         let write_txn = self.db.begin_write()?;
+        let weights = Self::update_edge_weight_in(&write_txn, from, to, relation, f)?;
+        write_txn.commit()?;
+        Ok(weights)
+    }
 
-        // Find the edge by scanning from-index
-        let edge_id = {
-            let from_index = write_txn.open_multimap_table(EDGES_BY_FROM)?;
-            let edges_table = write_txn.open_table(EDGES)?;
-            let mut found: Option<EdgeId> = None;
-            for result in from_index.get(&from_bytes)? {
-                let eid_bytes = *result?.value();
-                let eid = Self::bytes_to_uuid(&eid_bytes);
-                if let Some(bytes) = edges_table.get(&eid_bytes)? {
-                    let existing: Edge = Self::deserialize_edge(bytes.value())?;
-                    if existing.to == to && existing.relation == *relation {
-                        found = Some(eid);
-                        break;
-                    }
-                }
-            }
-            found
+    /// Node previously ingested under the event dedup key `key`, if any.
+    pub fn ingested_event(&self, key: &str) -> Result<Option<NodeId>> {
+        let read_txn = self.db.begin_read()?;
+        let table = match read_txn.open_table(INGESTED_EVENT_IDS) {
+            Ok(t) => t,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+            Err(e) => return Err(e.into()),
         };
+        Ok(table.get(key)?.map(|v| Self::bytes_to_uuid(v.value())))
+    }
 
-        let edge_id = edge_id.ok_or_else(|| {
-            CortexError::Validation(format!(
-                "No edge found from {} to {} with relation {}",
-                from, to, relation
-            ))
-        })?;
+    /// Store a node for an ingested event, unless the event was seen before.
+    ///
+    /// The key check and the write share one transaction, so concurrent
+    /// redeliveries cannot both store a node. Returns false if `key` was
+    /// already recorded and nothing was written.
+    pub fn put_node_for_event(&self, node: &Node, key: &str) -> Result<bool> {
+        self.ensure_writable()?;
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut events = write_txn.open_table(INGESTED_EVENT_IDS)?;
+            if events.get(key)?.is_some() {
+                return Ok(false);
+            }
+            events.insert(key, &Self::uuid_to_bytes(&node.id))?;
+        }
+        let is_new = self.put_node_in(&write_txn, node)?;
+        if is_new {
+            Self::add_to_meta_counter_in(&write_txn, STATS_NODE_COUNT_KEY, 1)?;
+        }
+        write_txn.commit()?;
 
-        // Read, modify, write — still in the same transaction
-        let edge_id_bytes = Self::uuid_to_bytes(&edge_id);
-        let new_weight = {
-            let mut edges_table = write_txn.open_table(EDGES)?;
-            let bytes = edges_table
-                .get(&edge_id_bytes)?
-                .ok_or_else(|| CortexError::EdgeNotFound(edge_id))?;
-            let mut edge: Edge = Self::deserialize_edge(bytes.value())?;
-            drop(bytes);
-            let old_w = edge.weight;
-            edge.weight = f(edge.weight).clamp(0.0, 1.0);
-            edge.updated_at = chrono::Utc::now();
-            let new_w = edge.weight;
-            let serialized = Self::serialize_edge(&edge)?;
-            edges_table.insert(&edge_id_bytes, serialized.as_slice())?;
-            (old_w, new_w)
-        };
+        self.audit(Self::node_audit_entry(node, is_new));
+        Ok(true)
+    }
 
-        write_txn.commit()?;
-        Ok(new_weight)
+    /// Read a node inside an open write transaction, seeing its writes so far.
+    fn get_node_in(txn: &redb::WriteTransaction, id: NodeId) -> Result<Option<Node>> {
+        let table = txn.open_table(NODES)?;
+        let node = table
+            .get(&Self::uuid_to_bytes(&id))?
+            .map(|bytes| Self::deserialize_node(bytes.value()))
+            .transpose()?;
+        Ok(node)
     }
-}
 
-impl Storage for RedbStorage {
-    fn put_node(&self, node: &Node) -> Result<()> {
+    /// Write a node and its index entries into an open write transaction.
+    /// Returns true if the node did not exist before.
+    fn put_node_in(&self, txn: &redb::WriteTransaction, node: &Node) -> Result<bool> {
         // Validate node
         node.validate().map_err(CortexError::Validation)?;
 
-        let write_txn = self.db.begin_write()?;
-
         // Check if node already exists to get old version
         let node_id_bytes = Self::uuid_to_bytes(&node.id);
         let old_node = {
-            let nodes_table = write_txn.open_table(NODES)?;
+            let nodes_table = txn.open_table(NODES)?;
             let old_bytes = nodes_table
                 .get(&node_id_bytes)?
                 .map(|guard| guard.value().to_vec());
@@ -537,62 +943,371 @@ impl Storage for RedbStorage {
         // Serialize and store node
         let node_bytes = Self::serialize_node(node)?;
         {
-            let mut nodes_table = write_txn.open_table(NODES)?;
-            let node_id_bytes = Self::uuid_to_bytes(&node.id);
+            let mut nodes_table = txn.open_table(NODES)?;
             nodes_table.insert(&node_id_bytes, node_bytes.as_slice())?;
         }
 
         // Update indexes
-        self.update_node_indexes(&write_txn, node, old_node.as_ref())?;
-
-        write_txn.commit()?;
-
-        // Increment node count for new nodes
-        let is_new = old_node.is_none();
-        if is_new {
-            self.increment_meta_counter(STATS_NODE_COUNT_KEY)?;
-        }
-
-        self.audit(AuditEntry {
-            timestamp: Utc::now(),
-            action: if is_new {
-                AuditAction::NodeCreated
-            } else {
-                AuditAction::NodeUpdated
-            },
-            target_id: node.id,
-            actor: node.source.agent.clone(),
-            details: None,
-        });
-
-        Ok(())
-    }
-
-    fn get_node(&self, id: NodeId) -> Result<Option<Node>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(NODES)?;
-        let id_bytes = Self::uuid_to_bytes(&id);
+        self.update_node_indexes(txn, node, old_node.as_ref())?;
 
-        if let Some(bytes) = table.get(&id_bytes)? {
-            let node = Self::deserialize_node(bytes.value())?;
-            Ok(Some(node))
-        } else {
-            Ok(None)
-        }
+        Ok(old_node.is_none())
     }
 
-    fn delete_node(&self, id: NodeId) -> Result<()> {
-        let mut node = self.get_node(id)?.ok_or(CortexError::NodeNotFound(id))?;
+    /// Validate and write an edge into an open write transaction.
+    fn put_edge_in(&self, txn: &redb::WriteTransaction, edge: &Edge) -> Result<()> {
+        // Validate edge
+        edge.validate().map_err(CortexError::Validation)?;
 
-        node.deleted = true;
-        node.updated_at = Utc::now();
+        let from_bytes = Self::uuid_to_bytes(&edge.from);
+        let to_bytes = Self::uuid_to_bytes(&edge.to);
+        let edge_id_bytes = Self::uuid_to_bytes(&edge.id);
 
-        // put_node won't increment (node already exists), decrement manually.
-        // put_node also fires NodeUpdated audit; we override with NodeDeleted below.
-        self.put_node(&node)?;
-        self.decrement_meta_counter(STATS_NODE_COUNT_KEY)?;
+        // 1. Check source and target nodes exist and are not deleted
+        {
+            let nodes_table = txn.open_table(NODES)?;
 
-        // Override the NodeUpdated audit entry emitted by put_node
+            let from_data =
+                nodes_table
+                    .get(&from_bytes)?
+                    .ok_or_else(|| CortexError::InvalidEdge {
+                        reason: format!("Source node {} does not exist", edge.from),
+                    })?;
+            let from_node: Node = Self::deserialize_node(from_data.value())?;
+            if from_node.deleted {
+                return Err(CortexError::InvalidEdge {
+                    reason: format!("Source node {} is deleted", edge.from),
+                });
+            }
+
+            let to_data = nodes_table
+                .get(&to_bytes)?
+                .ok_or_else(|| CortexError::InvalidEdge {
+                    reason: format!("Target node {} does not exist", edge.to),
+                })?;
+            let to_node: Node = Self::deserialize_node(to_data.value())?;
+            if to_node.deleted {
+                return Err(CortexError::InvalidEdge {
+                    reason: format!("Target node {} is deleted", edge.to),
+                });
+            }
+        } // nodes_table dropped
+
+        // 2. Collect existing outgoing edge IDs from the from-index
+        // Copy the raw bytes eagerly so they outlive the table handle
+        let existing_edge_ids: Vec<EdgeId> = {
+            let from_index = txn.open_multimap_table(EDGES_BY_FROM)?;
+            let raw: Vec<[u8; 16]> = from_index
+                .get(&from_bytes)?
+                .map(|r| r.map(|g| *g.value()))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            raw.into_iter().map(|b| Self::bytes_to_uuid(&b)).collect()
+        }; // from_index dropped
+
+        // 3. Check for duplicates (same from + to + relation, different id)
+        {
+            let edges_table = txn.open_table(EDGES)?;
+            for eid in &existing_edge_ids {
+                let eid_bytes = Self::uuid_to_bytes(eid);
+                if let Some(bytes) = edges_table.get(&eid_bytes)? {
+                    let existing: Edge = Self::deserialize_edge(bytes.value())?;
+                    if existing.to == edge.to
+                        && existing.relation == edge.relation
+                        && existing.id != edge.id
+                    {
+                        return Err(CortexError::DuplicateEdge {
+                            from: edge.from,
+                            to: edge.to,
+                            relation: edge.relation.to_string(),
+                        });
+                    }
+                }
+            }
+        } // edges_table dropped
+
+        // 4. Write the edge
+        let edge_bytes = Self::serialize_edge(edge)?;
+        {
+            let mut edges_table = txn.open_table(EDGES)?;
+            edges_table.insert(&edge_id_bytes, edge_bytes.as_slice())?;
+        } // edges_table dropped
+
+        // 5. Update indexes (reopens EDGES_BY_FROM and EDGES_BY_TO — safe after drop above)
+        self.update_edge_indexes(txn, edge)?;
+
+        Ok(())
+    }
+
+    /// Read-modify-write an edge weight identified by (from, to, relation)
+    /// inside an open write transaction. Returns `(old_weight, new_weight)`.
+    fn update_edge_weight_in(
+        txn: &redb::WriteTransaction,
+        from: NodeId,
+        to: NodeId,
+        relation: &crate::types::Relation,
+        f: impl FnOnce(f32) -> f32,
+    ) -> Result<(f32, f32)> {
+        let from_bytes = Self::uuid_to_bytes(&from);
+
+        // Find the edge by scanning from-index
+        let edge_id = {
+            let from_index = txn.open_multimap_table(EDGES_BY_FROM)?;
+            let edges_table = txn.open_table(EDGES)?;
+            let mut found: Option<EdgeId> = None;
+            for result in from_index.get(&from_bytes)? {
+                let eid_bytes = *result?.value();
+                let eid = Self::bytes_to_uuid(&eid_bytes);
+                if let Some(bytes) = edges_table.get(&eid_bytes)? {
+                    let existing: Edge = Self::deserialize_edge(bytes.value())?;
+                    if existing.to == to && existing.relation == *relation {
+                        found = Some(eid);
+                        break;
+                    }
+                }
+            }
+            found
+        };
+
+        let edge_id = edge_id.ok_or_else(|| {
+            CortexError::Validation(format!(
+                "No edge found from {} to {} with relation {}",
+                from, to, relation
+            ))
+        })?;
+
+        // Read, modify, write — still in the same transaction
+        let edge_id_bytes = Self::uuid_to_bytes(&edge_id);
+        let mut edges_table = txn.open_table(EDGES)?;
+        let bytes = edges_table
+            .get(&edge_id_bytes)?
+            .ok_or_else(|| CortexError::EdgeNotFound(edge_id))?;
+        let mut edge: Edge = Self::deserialize_edge(bytes.value())?;
+        drop(bytes);
+        let old_w = edge.weight;
+        edge.weight = f(edge.weight).clamp(0.0, 1.0);
+        edge.updated_at = chrono::Utc::now();
+        let new_w = edge.weight;
+        let serialized = Self::serialize_edge(&edge)?;
+        edges_table.insert(&edge_id_bytes, serialized.as_slice())?;
+        Ok((old_w, new_w))
+    }
+
+    /// Add `delta` to a META counter inside an open write transaction.
+    fn add_to_meta_counter_in(txn: &redb::WriteTransaction, key: &str, delta: u64) -> Result<()> {
+        let mut meta = txn.open_table(META)?;
+        let current = meta
+            .get(key)?
+            .map(|v| {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(v.value());
+                u64::from_le_bytes(bytes)
+            })
+            .unwrap_or(0);
+        meta.insert(key, (current + delta).to_le_bytes().as_slice())?;
+        Ok(())
+    }
+
+    /// Subtract `delta` from a META counter inside an open write transaction.
+    fn sub_from_meta_counter_in(txn: &redb::WriteTransaction, key: &str, delta: u64) -> Result<()> {
+        let mut meta = txn.open_table(META)?;
+        let current = meta
+            .get(key)?
+            .map(|v| {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(v.value());
+                u64::from_le_bytes(bytes)
+            })
+            .unwrap_or(0);
+        meta.insert(key, current.saturating_sub(delta).to_le_bytes().as_slice())?;
+        Ok(())
+    }
+
+    fn node_audit_entry(node: &Node, is_new: bool) -> AuditEntry {
+        AuditEntry {
+            timestamp: Utc::now(),
+            action: if is_new {
+                AuditAction::NodeCreated
+            } else {
+                AuditAction::NodeUpdated
+            },
+            target_id: node.id,
+            actor: node.source.agent.clone(),
+            details: None,
+        }
+    }
+
+    fn edge_audit_entry(edge: &Edge) -> AuditEntry {
+        AuditEntry {
+            timestamp: Utc::now(),
+            action: AuditAction::EdgeCreated,
+            target_id: edge.id,
+            actor: match &edge.provenance {
+                crate::types::EdgeProvenance::Manual { created_by } => created_by.clone(),
+                crate::types::EdgeProvenance::AutoSimilarity { .. } => "auto-linker".to_string(),
+                crate::types::EdgeProvenance::AutoStructural { .. } => "auto-linker".to_string(),
+                crate::types::EdgeProvenance::AutoContradiction { .. } => "auto-linker".to_string(),
+                crate::types::EdgeProvenance::AutoDedup { .. } => "auto-linker".to_string(),
+                crate::types::EdgeProvenance::Imported { source } => source.clone(),
+                crate::types::EdgeProvenance::DerivedInverse { .. } => "inverse".to_string(),
+            },
+            details: None,
+        }
+    }
+}
+
+/// Writes staged by `RedbStorage::transaction`, all in one redb write transaction.
+struct RedbTransaction<'a> {
+    storage: &'a RedbStorage,
+    txn: &'a redb::WriteTransaction,
+    new_nodes: u64,
+    new_edges: u64,
+    audit: Vec<AuditEntry>,
+}
+
+impl StorageTransaction for RedbTransaction<'_> {
+    fn get_node(&self, id: NodeId) -> Result<Option<Node>> {
+        RedbStorage::get_node_in(self.txn, id)
+    }
+
+    fn put_node(&mut self, node: &Node) -> Result<()> {
+        let is_new = self.storage.put_node_in(self.txn, node)?;
+        if is_new {
+            self.new_nodes += 1;
+        }
+        self.audit.push(RedbStorage::node_audit_entry(node, is_new));
+        Ok(())
+    }
+
+    fn put_edge(&mut self, edge: &Edge) -> Result<()> {
+        self.storage.put_edge_in(self.txn, edge)?;
+        self.new_edges += 1;
+        self.audit.push(RedbStorage::edge_audit_entry(edge));
+        Ok(())
+    }
+
+    fn update_edge_weight(
+        &mut self,
+        from: NodeId,
+        to: NodeId,
+        relation: &Relation,
+        f: &dyn Fn(f32) -> f32,
+    ) -> Result<(f32, f32)> {
+        RedbStorage::update_edge_weight_in(self.txn, from, to, relation, f)
+    }
+}
+
+/// The fields `NodeFilter` and listing order look at, borrowed from either
+/// a full node or a header.
+struct NodeView<'a> {
+    id: NodeId,
+    kind: &'a NodeKind,
+    tags: &'a [String],
+    agent: &'a str,
+    importance: f32,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    deleted: bool,
+}
+
+/// A record `list_nodes` / `list_node_headers` can decode and filter.
+trait Listed: Sized {
+    fn decode(bytes: &[u8]) -> Result<Self>;
+    fn view(&self) -> NodeView<'_>;
+}
+
+impl Listed for Node {
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        RedbStorage::deserialize_node(bytes)
+    }
+
+    fn view(&self) -> NodeView<'_> {
+        NodeView {
+            id: self.id,
+            kind: &self.kind,
+            tags: &self.data.tags,
+            agent: &self.source.agent,
+            importance: self.importance,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            deleted: self.deleted,
+        }
+    }
+}
+
+impl Listed for NodeHeader {
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        super::header::deserialize_node_header(bytes)
+    }
+
+    fn view(&self) -> NodeView<'_> {
+        NodeView {
+            id: self.id,
+            kind: &self.kind,
+            tags: &self.tags,
+            agent: &self.source.agent,
+            importance: self.importance,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            deleted: self.deleted,
+        }
+    }
+}
+
+impl Storage for RedbStorage {
+    fn put_node(&self, node: &Node) -> Result<()> {
+        self.ensure_writable()?;
+        let write_txn = self.db.begin_write()?;
+        let is_new = self.put_node_in(&write_txn, node)?;
+        // Count new nodes in the same transaction so snapshots never see
+        // a node without its counter.
+        if is_new {
+            Self::add_to_meta_counter_in(&write_txn, STATS_NODE_COUNT_KEY, 1)?;
+        }
+        write_txn.commit()?;
+
+        self.audit(Self::node_audit_entry(node, is_new));
+
+        Ok(())
+    }
+
+    fn get_node(&self, id: NodeId) -> Result<Option<Node>> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(NODES)?;
+        let id_bytes = Self::uuid_to_bytes(&id);
+
+        if let Some(bytes) = table.get(&id_bytes)? {
+            let node = Self::deserialize_node(bytes.value())?;
+            Ok(Some(node))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn delete_node(&self, id: NodeId) -> Result<()> {
+        self.ensure_writable()?;
+        // Read, check and write in one transaction, so concurrent deletes
+        // can't both pass the check or overwrite a newer update
+        let write_txn = self.db.begin_write()?;
+        let mut node = Self::get_node_in(&write_txn, id)?.ok_or(CortexError::NodeNotFound(id))?;
+        // Deleting twice must not restart the grace period or the counter
+        if node.deleted {
+            write_txn.abort()?;
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        node.deleted = true;
+        node.deleted_at = Some(now);
+        node.updated_at = now;
+
+        // The node already exists, so writing it never counts; decrement in
+        // the same transaction. Logs NodeUpdated like put_node, then
+        // NodeDeleted below.
+        self.put_node_in(&write_txn, &node)?;
+        Self::sub_from_meta_counter_in(&write_txn, STATS_NODE_COUNT_KEY, 1)?;
+        write_txn.commit()?;
+        self.audit(Self::node_audit_entry(&node, false));
+
+        // Record the deletion itself
         self.audit(AuditEntry {
             timestamp: Utc::now(),
             action: AuditAction::NodeDeleted,
@@ -603,7 +1318,36 @@ impl Storage for RedbStorage {
         Ok(())
     }
 
+    fn restore_node(&self, id: NodeId) -> Result<Node> {
+        self.ensure_writable()?;
+        let write_txn = self.db.begin_write()?;
+        let mut node = Self::get_node_in(&write_txn, id)?.ok_or(CortexError::NodeNotFound(id))?;
+        if !node.deleted {
+            write_txn.abort()?;
+            return Ok(node);
+        }
+
+        node.deleted = false;
+        node.deleted_at = None;
+        node.updated_at = Utc::now();
+
+        self.put_node_in(&write_txn, &node)?;
+        Self::add_to_meta_counter_in(&write_txn, STATS_NODE_COUNT_KEY, 1)?;
+        write_txn.commit()?;
+        self.audit(Self::node_audit_entry(&node, false));
+
+        self.audit(AuditEntry {
+            timestamp: Utc::now(),
+            action: AuditAction::NodeRestored,
+            target_id: id,
+            actor: node.source.agent.clone(),
+            details: None,
+        });
+        Ok(node)
+    }
+
     fn hard_delete_node(&self, id: NodeId) -> Result<()> {
+        self.ensure_writable()?;
         // Retrieve the node (may be soft-deleted)
         let lookup = {
             let read_txn = self.db.begin_read()?;
@@ -648,6 +1392,14 @@ impl Storage for RedbStorage {
             let mut source_table = write_txn.open_multimap_table(NODES_BY_SOURCE)?;
             source_table.remove(node.source.agent.as_str(), &id_bytes)?;
         }
+        {
+            let mut title_table = write_txn.open_multimap_table(NODES_BY_TITLE)?;
+            title_table.remove((node.kind.as_str(), node.data.title.as_str()), &id_bytes)?;
+        }
+        {
+            let mut created_table = write_txn.open_table(NODES_BY_CREATED)?;
+            created_table.remove(Self::created_key(node.created_at, &id_bytes))?;
+        }
         {
             let mut tag_table = write_txn.open_multimap_table(NODES_BY_TAG)?;
             for tag in &node.data.tags {
@@ -667,70 +1419,84 @@ impl Storage for RedbStorage {
         Ok(())
     }
 
-    fn list_nodes(&self, filter: NodeFilter) -> Result<Vec<Node>> {
-        let read_txn = self.db.begin_read()?;
-        let nodes_table = read_txn.open_table(NODES)?;
+    fn transaction<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut dyn StorageTransaction) -> Result<T>,
+    {
+        self.ensure_writable()?;
+        let write_txn = self.db.begin_write()?;
+        let mut tx = RedbTransaction {
+            storage: self,
+            txn: &write_txn,
+            new_nodes: 0,
+            new_edges: 0,
+            audit: Vec::new(),
+        };
 
-        let mut nodes = Vec::new();
+        // On error the write transaction is dropped, which aborts it.
+        let value = f(&mut tx)?;
+
+        let RedbTransaction {
+            new_nodes,
+            new_edges,
+            audit,
+            ..
+        } = tx;
+        if new_nodes > 0 {
+            Self::add_to_meta_counter_in(&write_txn, STATS_NODE_COUNT_KEY, new_nodes)?;
+        }
+        if new_edges > 0 {
+            Self::add_to_meta_counter_in(&write_txn, STATS_EDGE_COUNT_KEY, new_edges)?;
+        }
+        write_txn.commit()?;
 
-        // If we have a kind filter, use the index for efficiency
-        if let Some(ref kinds) = filter.kinds {
-            let kind_index = read_txn.open_multimap_table(NODES_BY_KIND)?;
+        for entry in audit {
+            self.audit(entry);
+        }
+        Ok(value)
+    }
 
-            for kind in kinds {
-                let node_ids: Vec<NodeId> = kind_index
-                    .get(kind.as_str())?
-                    .map(|result| result.map(|guard| Self::bytes_to_uuid(guard.value())))
-                    .collect::<std::result::Result<Vec<_>, _>>()?;
-
-                for node_id in node_ids {
-                    let node_id_bytes = Self::uuid_to_bytes(&node_id);
-                    if let Some(bytes) = nodes_table.get(&node_id_bytes)? {
-                        if let Ok(node) = Self::deserialize_node(bytes.value()) {
-                            if Self::node_matches_filter(&node, &filter) {
-                                nodes.push(node);
-                                // Early exit when limit reached (no offset case)
-                                if filter.offset.is_none() {
-                                    if let Some(limit) = filter.limit {
-                                        if nodes.len() >= limit {
-                                            break;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        } else {
-            // Full table scan
-            for item in nodes_table.iter()? {
-                let (_, value) = item?;
-                let node = match Self::deserialize_node(value.value()) {
-                    Ok(n) => n,
-                    Err(_) => continue, // skip corrupt records
+    fn get_node_by_title(&self, kind: &NodeKind, title: &str) -> Result<Option<Node>> {
+        let read_txn = self.db.begin_read()?;
+        let title_index = match read_txn.open_multimap_table(NODES_BY_TITLE) {
+            Ok(t) => t,
+            // Index absent (pre-v3 database opened read-only): fall back to a scan
+            Err(redb::TableError::TableDoesNotExist(_)) => {
+                let headers =
+                    self.list_node_headers(NodeFilter::new().with_kinds(vec![kind.clone()]))?;
+                return match headers.into_iter().find(|h| h.title == title) {
+                    Some(header) => self.get_node(header.id),
+                    None => Ok(None),
                 };
-                if Self::node_matches_filter(&node, &filter) {
-                    nodes.push(node);
-                    // Early exit: if no offset, stop once limit is reached
-                    if filter.offset.is_none() {
-                        if let Some(limit) = filter.limit {
-                            if nodes.len() >= limit {
-                                break;
-                            }
-                        }
-                    }
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let nodes_table = read_txn.open_table(NODES)?;
+
+        // Titles are not unique; match list_nodes ordering and return the newest live node.
+        let mut found: Option<Node> = None;
+        for entry in title_index.get((kind.as_str(), title))? {
+            let id_bytes = *entry?.value();
+            if let Some(bytes) = nodes_table.get(&id_bytes)? {
+                let node = Self::deserialize_node(bytes.value())?;
+                if !node.deleted
+                    && found
+                        .as_ref()
+                        .is_none_or(|f| node.created_at > f.created_at)
+                {
+                    found = Some(node);
                 }
             }
         }
+        Ok(found)
+    }
 
-        // Sort by created_at descending (newest first)
-        nodes.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    fn list_nodes(&self, filter: NodeFilter) -> Result<Vec<Node>> {
+        self.list_records(&filter)
+    }
 
-        // Apply offset and limit
-        let start = filter.offset.unwrap_or(0);
-        let end = filter.limit.map(|l| start + l).unwrap_or(nodes.len());
-        Ok(nodes.into_iter().skip(start).take(end - start).collect())
+    fn list_node_headers(&self, filter: NodeFilter) -> Result<Vec<NodeHeader>> {
+        self.list_records(&filter)
     }
 
     fn count_nodes(&self, filter: NodeFilter) -> Result<u64> {
@@ -740,7 +1506,9 @@ impl Storage for RedbStorage {
             && filter.source_agent.is_none()
             && filter.created_after.is_none()
             && filter.created_before.is_none()
+            && filter.changed_after.is_none()
             && filter.min_importance.is_none()
+            && filter.cursor.is_none()
             && !filter.include_deleted
         {
             if let Some(ref kinds) = filter.kinds {
@@ -750,113 +1518,22 @@ impl Storage for RedbStorage {
                 for kind in kinds {
                     count += kind_index.get(kind.as_str())?.count() as u64;
                 }
-                return Ok(count);
-            }
-        }
-        // Fallback: materialize and count
-        Ok(self.list_nodes(filter)?.len() as u64)
-    }
-
-    fn put_edge(&self, edge: &Edge) -> Result<()> {
-        // Validate edge
-        edge.validate().map_err(CortexError::Validation)?;
-
-        let from_bytes = Self::uuid_to_bytes(&edge.from);
-        let to_bytes = Self::uuid_to_bytes(&edge.to);
-        let edge_id_bytes = Self::uuid_to_bytes(&edge.id);
-
-        // Single write transaction: validate nodes, check duplicates, write — all atomic
-        let write_txn = self.db.begin_write()?;
-
-        // 1. Check source and target nodes exist and are not deleted
-        {
-            let nodes_table = write_txn.open_table(NODES)?;
-
-            let from_data =
-                nodes_table
-                    .get(&from_bytes)?
-                    .ok_or_else(|| CortexError::InvalidEdge {
-                        reason: format!("Source node {} does not exist", edge.from),
-                    })?;
-            let from_node: Node = Self::deserialize_node(from_data.value())?;
-            if from_node.deleted {
-                return Err(CortexError::InvalidEdge {
-                    reason: format!("Source node {} is deleted", edge.from),
-                });
-            }
-
-            let to_data = nodes_table
-                .get(&to_bytes)?
-                .ok_or_else(|| CortexError::InvalidEdge {
-                    reason: format!("Target node {} does not exist", edge.to),
-                })?;
-            let to_node: Node = Self::deserialize_node(to_data.value())?;
-            if to_node.deleted {
-                return Err(CortexError::InvalidEdge {
-                    reason: format!("Target node {} is deleted", edge.to),
-                });
-            }
-        } // nodes_table dropped
-
-        // 2. Collect existing outgoing edge IDs from the from-index
-        // Copy the raw bytes eagerly so they outlive the table handle
-        let existing_edge_ids: Vec<EdgeId> = {
-            let from_index = write_txn.open_multimap_table(EDGES_BY_FROM)?;
-            let raw: Vec<[u8; 16]> = from_index
-                .get(&from_bytes)?
-                .map(|r| r.map(|g| *g.value()))
-                .collect::<std::result::Result<Vec<_>, _>>()?;
-            raw.into_iter().map(|b| Self::bytes_to_uuid(&b)).collect()
-        }; // from_index dropped
-
-        // 3. Check for duplicates (same from + to + relation, different id)
-        {
-            let edges_table = write_txn.open_table(EDGES)?;
-            for eid in &existing_edge_ids {
-                let eid_bytes = Self::uuid_to_bytes(eid);
-                if let Some(bytes) = edges_table.get(&eid_bytes)? {
-                    let existing: Edge = Self::deserialize_edge(bytes.value())?;
-                    if existing.to == edge.to
-                        && existing.relation == edge.relation
-                        && existing.id != edge.id
-                    {
-                        return Err(CortexError::DuplicateEdge {
-                            from: edge.from,
-                            to: edge.to,
-                            relation: edge.relation.to_string(),
-                        });
-                    }
-                }
-            }
-        } // edges_table dropped
-
-        // 4. Write the edge
-        let edge_bytes = Self::serialize_edge(edge)?;
-        {
-            let mut edges_table = write_txn.open_table(EDGES)?;
-            edges_table.insert(&edge_id_bytes, edge_bytes.as_slice())?;
-        } // edges_table dropped
-
-        // 5. Update indexes (reopens EDGES_BY_FROM and EDGES_BY_TO — safe after drop above)
-        self.update_edge_indexes(&write_txn, edge)?;
-
-        write_txn.commit()?;
-        self.increment_meta_counter(STATS_EDGE_COUNT_KEY)?;
-
-        self.audit(AuditEntry {
-            timestamp: Utc::now(),
-            action: AuditAction::EdgeCreated,
-            target_id: edge.id,
-            actor: match &edge.provenance {
-                crate::types::EdgeProvenance::Manual { created_by } => created_by.clone(),
-                crate::types::EdgeProvenance::AutoSimilarity { .. } => "auto-linker".to_string(),
-                crate::types::EdgeProvenance::AutoStructural { .. } => "auto-linker".to_string(),
-                crate::types::EdgeProvenance::AutoContradiction { .. } => "auto-linker".to_string(),
-                crate::types::EdgeProvenance::AutoDedup { .. } => "auto-linker".to_string(),
-                crate::types::EdgeProvenance::Imported { source } => source.clone(),
-            },
-            details: None,
-        });
+                return Ok(count);
+            }
+        }
+        // Fallback: materialize and count
+        Ok(self.list_nodes(filter)?.len() as u64)
+    }
+
+    fn put_edge(&self, edge: &Edge) -> Result<()> {
+        self.ensure_writable()?;
+        // Single write transaction: validate nodes, check duplicates, write — all atomic
+        let write_txn = self.db.begin_write()?;
+        self.put_edge_in(&write_txn, edge)?;
+        Self::add_to_meta_counter_in(&write_txn, STATS_EDGE_COUNT_KEY, 1)?;
+        write_txn.commit()?;
+
+        self.audit(Self::edge_audit_entry(edge));
 
         Ok(())
     }
@@ -875,33 +1552,39 @@ impl Storage for RedbStorage {
     }
 
     fn delete_edge(&self, id: EdgeId) -> Result<()> {
+        self.ensure_writable()?;
         let edge = self.get_edge(id)?.ok_or(CortexError::EdgeNotFound(id))?;
+        // An inverse pair is deleted together, so traversal stays the same
+        // from either endpoint.
+        let mut removed = vec![edge];
+        removed.extend(self.paired_edge(&removed[0])?);
 
         let write_txn = self.db.begin_write()?;
+        for edge in &removed {
+            // Remove from indexes first
+            self.remove_edge_from_indexes(&write_txn, edge)?;
 
-        // Remove from indexes first
-        self.remove_edge_from_indexes(&write_txn, &edge)?;
-
-        // Remove from main table
-        {
+            // Remove from main table
             let mut edges_table = write_txn.open_table(EDGES)?;
-            let edge_id_bytes = Self::uuid_to_bytes(&id);
+            let edge_id_bytes = Self::uuid_to_bytes(&edge.id);
             edges_table.remove(&edge_id_bytes)?;
         }
+        Self::sub_from_meta_counter_in(&write_txn, STATS_EDGE_COUNT_KEY, removed.len() as u64)?;
 
         write_txn.commit()?;
-        self.decrement_meta_counter(STATS_EDGE_COUNT_KEY)?;
 
-        self.audit(AuditEntry {
-            timestamp: Utc::now(),
-            action: AuditAction::EdgePruned,
-            target_id: id,
-            actor: match &edge.provenance {
-                crate::types::EdgeProvenance::Manual { created_by } => created_by.clone(),
-                _ => "auto-linker".to_string(),
-            },
-            details: Some(format!("{} -> {} [{}]", edge.from, edge.to, edge.relation)),
-        });
+        for edge in &removed {
+            self.audit(AuditEntry {
+                timestamp: Utc::now(),
+                action: AuditAction::EdgePruned,
+                target_id: edge.id,
+                actor: match &edge.provenance {
+                    crate::types::EdgeProvenance::Manual { created_by } => created_by.clone(),
+                    _ => "auto-linker".to_string(),
+                },
+                details: Some(format!("{} -> {} [{}]", edge.from, edge.to, edge.relation)),
+            });
+        }
 
         Ok(())
     }
@@ -955,7 +1638,20 @@ impl Storage for RedbStorage {
         Ok(edges_from_node.into_iter().filter(|e| e.to == to).collect())
     }
 
+    fn list_edges(&self) -> Result<Vec<Edge>> {
+        let read_txn = self.db.begin_read()?;
+        let edges_table = read_txn.open_table(EDGES)?;
+
+        let mut edges = Vec::new();
+        for entry in edges_table.iter()? {
+            let (_, bytes) = entry?;
+            edges.push(Self::deserialize_edge(bytes.value())?);
+        }
+        Ok(edges)
+    }
+
     fn put_nodes_batch(&self, nodes: &[Node]) -> Result<()> {
+        self.ensure_writable()?;
         // Validate all nodes first
         for node in nodes {
             node.validate().map_err(CortexError::Validation)?;
@@ -993,6 +1689,7 @@ impl Storage for RedbStorage {
     }
 
     fn put_edges_batch(&self, edges: &[Edge]) -> Result<()> {
+        self.ensure_writable()?;
         // Validate all edges first
         for edge in edges {
             edge.validate().map_err(CortexError::Validation)?;
@@ -1016,6 +1713,7 @@ impl Storage for RedbStorage {
     }
 
     fn put_metadata(&self, key: &str, value: &[u8]) -> Result<()> {
+        self.ensure_writable()?;
         let write_txn = self.db.begin_write()?;
         {
             let mut meta_table = write_txn.open_table(META)?;
@@ -1122,6 +1820,7 @@ impl Storage for RedbStorage {
         }
 
         let db_size_bytes = std::fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0);
+        let table_bytes = Self::table_bytes(&read_txn)?;
 
         Ok(StorageStats {
             node_count,
@@ -1129,15 +1828,14 @@ impl Storage for RedbStorage {
             node_counts_by_kind,
             edge_counts_by_relation,
             db_size_bytes,
+            table_bytes,
             oldest_node,
             newest_node,
         })
     }
 
     fn snapshot(&self, path: &Path) -> Result<()> {
-        std::fs::copy(&self.path, path)
-            .map_err(|e| CortexError::Validation(format!("Failed to create snapshot: {}", e)))?;
-        Ok(())
+        self.snapshot_versioned(path, None).map(|_| ())
     }
 
     fn list_distinct_kinds(&self) -> Result<Vec<crate::types::NodeKind>> {
@@ -1159,6 +1857,37 @@ impl Storage for RedbStorage {
     }
 }
 
+/// Copy every row of `def` from a read transaction into a write transaction.
+fn copy_table<K: Key + 'static, V: Value + 'static>(
+    from: &ReadTransaction,
+    to: &WriteTransaction,
+    def: TableDefinition<K, V>,
+) -> Result<()> {
+    let source = from.open_table(def)?;
+    let mut dest = to.open_table(def)?;
+    for item in source.iter()? {
+        let (key, value) = item?;
+        dest.insert(key.value(), value.value())?;
+    }
+    Ok(())
+}
+
+fn copy_multimap_table<K: Key + 'static, V: Key + 'static>(
+    from: &ReadTransaction,
+    to: &WriteTransaction,
+    def: MultimapTableDefinition<K, V>,
+) -> Result<()> {
+    let source = from.open_multimap_table(def)?;
+    let mut dest = to.open_multimap_table(def)?;
+    for item in source.iter()? {
+        let (key, values) = item?;
+        for value in values {
+            dest.insert(key.value(), value?.value())?;
+        }
+    }
+    Ok(())
+}
+
 /// Build a fully-deterministic Node for schema regression tests.
 /// Every field is hard-coded — no randomness, no wall-clock time.
 #[cfg(test)]
@@ -1190,6 +1919,9 @@ fn make_canonical_node() -> Node {
         created_at: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
         updated_at: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
         deleted: false,
+        confidence: 0.75,
+        expires_at: Some(Utc.timestamp_opt(1_700_086_400, 0).unwrap()),
+        deleted_at: None,
     }
 }
 
@@ -1249,6 +1981,45 @@ mod tests {
         assert!(deleted.deleted);
     }
 
+    #[test]
+    fn test_node_metadata_roundtrip() {
+        let (storage, _temp) = create_test_storage();
+
+        let mut node = create_test_node(NodeKind::new("fact").unwrap(), "With metadata");
+        node.data.metadata.insert(
+            "source_url".into(),
+            serde_json::json!("https://example.com"),
+        );
+        node.data.metadata.insert(
+            "scores".into(),
+            serde_json::json!({"a": 1, "b": [0.5, null]}),
+        );
+        storage.put_node(&node).unwrap();
+
+        let retrieved = storage.get_node(node.id).unwrap().unwrap();
+        assert_eq!(retrieved.data.metadata, node.data.metadata);
+    }
+
+    #[test]
+    fn test_put_node_for_event_is_idempotent() {
+        let (storage, _temp) = create_test_storage();
+        let first = create_test_node(NodeKind::new("fact").unwrap(), "Evidence: tests pass");
+        let redelivered = create_test_node(NodeKind::new("fact").unwrap(), "Evidence: tests pass");
+
+        assert!(storage.ingested_event("evidence:ev-1").unwrap().is_none());
+        assert!(storage.put_node_for_event(&first, "evidence:ev-1").unwrap());
+        assert!(!storage
+            .put_node_for_event(&redelivered, "evidence:ev-1")
+            .unwrap());
+
+        assert_eq!(
+            storage.ingested_event("evidence:ev-1").unwrap(),
+            Some(first.id)
+        );
+        assert!(storage.get_node(redelivered.id).unwrap().is_none());
+        assert_eq!(storage.stats().unwrap().node_count, 1);
+    }
+
     #[test]
     fn test_node_validation() {
         let (storage, _temp) = create_test_storage();
@@ -1467,11 +2238,177 @@ mod tests {
             Some(&1)
         );
     }
+
+    #[test]
+    fn test_snapshot_under_concurrent_writes() {
+        let (storage, temp) = create_test_storage();
+        let storage = Arc::new(storage);
+        let version = Arc::new(AtomicU64::new(0));
+
+        let writer = {
+            let storage = storage.clone();
+            let version = version.clone();
+            std::thread::spawn(move || {
+                let mut prev: Option<Node> = None;
+                for i in 0..200 {
+                    let node = create_test_node(NodeKind::new("fact").unwrap(), &format!("n{i}"));
+                    storage.put_node(&node).unwrap();
+                    version.fetch_add(1, Ordering::SeqCst);
+                    if let Some(prev) = &prev {
+                        let edge = Edge::new(
+                            prev.id,
+                            node.id,
+                            Relation::new("related_to").unwrap(),
+                            0.5,
+                            EdgeProvenance::Manual {
+                                created_by: "test".into(),
+                            },
+                        );
+                        storage.put_edge(&edge).unwrap();
+                        version.fetch_add(1, Ordering::SeqCst);
+                        if i % 10 == 0 {
+                            storage.delete_edge(edge.id).unwrap();
+                            version.fetch_add(1, Ordering::SeqCst);
+                        }
+                    }
+                    prev = Some(node);
+                }
+            })
+        };
+
+        let mut snapshots = Vec::new();
+        for i in 0..10 {
+            let path = temp.path().join(format!("snap-{i}.redb"));
+            let info = storage.snapshot_versioned(&path, Some(&version)).unwrap();
+            snapshots.push((path, info));
+        }
+        writer.join().unwrap();
+
+        for (path, info) in snapshots {
+            let snap = RedbStorage::open(&path).unwrap();
+            let nodes = snap.list_nodes(NodeFilter::new()).unwrap();
+            let edges = snap.list_edges().unwrap();
+            let stats = snap.stats().unwrap();
+
+            assert_eq!(stats.node_count, nodes.len() as u64);
+            assert_eq!(stats.edge_count, edges.len() as u64);
+            assert_eq!(info.node_count, stats.node_count);
+            assert_eq!(info.edge_count, stats.edge_count);
+            for edge in &edges {
+                assert!(snap.get_node(edge.from).unwrap().is_some());
+                assert!(snap.get_node(edge.to).unwrap().is_some());
+                assert_eq!(snap.edges_from(edge.from).unwrap().len(), 1);
+            }
+
+            // Every mutation counted by the recorded version is in the copy:
+            // each node brings at most a put, an edge and an edge delete.
+            let recorded = snap
+                .get_metadata(SNAPSHOT_GRAPH_VERSION_KEY)
+                .unwrap()
+                .unwrap();
+            let recorded = u64::from_le_bytes(recorded.try_into().unwrap());
+            assert_eq!(info.graph_version, Some(recorded));
+            assert!(recorded <= 3 * nodes.len() as u64);
+        }
+
+        // The live store was never blocked and an existing file is refused
+        assert_eq!(storage.stats().unwrap().node_count, 200);
+        let taken = temp.path().join("snap-0.redb");
+        assert!(storage.snapshot_versioned(&taken, None).is_err());
+    }
+
+    #[test]
+    fn test_read_only_rejects_writes() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("ro.redb");
+        let a = create_test_node(NodeKind::new("fact").unwrap(), "A");
+        let b = create_test_node(NodeKind::new("fact").unwrap(), "B");
+        let edge = Edge::new(
+            a.id,
+            b.id,
+            Relation::new("related_to").unwrap(),
+            0.5,
+            EdgeProvenance::Manual {
+                created_by: "test".into(),
+            },
+        );
+        {
+            let storage = RedbStorage::open(&path).unwrap();
+            storage.put_nodes_batch(&[a.clone(), b.clone()]).unwrap();
+            storage.put_edge(&edge).unwrap();
+        }
+
+        let storage = RedbStorage::open_read_only(&path).unwrap();
+        assert!(storage.is_read_only());
+
+        // Reads work
+        assert_eq!(storage.get_node(a.id).unwrap().unwrap().data.title, "A");
+        assert_eq!(storage.list_nodes(NodeFilter::new()).unwrap().len(), 2);
+        assert_eq!(storage.edges_from(a.id).unwrap().len(), 1);
+        assert_eq!(storage.stats().unwrap().edge_count, 1);
+
+        // Writes fail before touching the file
+        let c = create_test_node(NodeKind::new("fact").unwrap(), "C");
+        let read_only = |r: Result<()>| matches!(r, Err(CortexError::ReadOnly));
+        assert!(read_only(storage.put_node(&c)));
+        assert!(read_only(storage.put_nodes_batch(std::slice::from_ref(&c))));
+        assert!(read_only(storage.delete_node(a.id)));
+        assert!(read_only(storage.hard_delete_node(a.id)));
+        assert!(read_only(storage.delete_edge(edge.id)));
+        assert!(read_only(storage.put_edge(&edge)));
+        assert!(read_only(storage.put_metadata("k", b"v")));
+        assert!(read_only(storage.transaction(|tx| tx.put_node(&c))));
+        assert!(storage.get_node(c.id).unwrap().is_none());
+        assert!(!storage.get_node(a.id).unwrap().unwrap().deleted);
+
+        // A missing file is an error, not a new database
+        let missing = temp.path().join("missing.redb");
+        assert!(RedbStorage::open_read_only(&missing).is_err());
+        assert!(!missing.exists());
+    }
+
+    #[test]
+    fn test_stats_table_bytes() {
+        let (storage, _temp) = create_test_storage();
+        let nodes: Vec<Node> = (0..20)
+            .map(|i| create_test_node(NodeKind::new("fact").unwrap(), &format!("n{i}")))
+            .collect();
+        for node in &nodes {
+            storage.put_node(node).unwrap();
+        }
+        for pair in nodes.windows(2) {
+            let edge = Edge::new(
+                pair[0].id,
+                pair[1].id,
+                Relation::new("related_to").unwrap(),
+                0.5,
+                EdgeProvenance::Manual {
+                    created_by: "test".into(),
+                },
+            );
+            storage.put_edge(&edge).unwrap();
+        }
+
+        let stats = storage.stats().unwrap();
+        for table in [
+            "nodes",
+            "edges",
+            "nodes_by_kind_v2",
+            "edges_by_from",
+            "meta",
+        ] {
+            assert!(stats.table_bytes[table] > 0, "{table} is empty");
+        }
+        assert!(stats.table_bytes.contains_key("audit"));
+        assert!(stats.db_size_bytes > 0);
+        assert!(stats.table_bytes.values().sum::<u64>() <= stats.db_size_bytes);
+    }
 }
 
 #[cfg(test)]
 mod optimization_tests {
     use super::*;
+    use crate::storage::filters::NodeCursor;
     use crate::types::*;
     use tempfile::TempDir;
 
@@ -1630,6 +2567,87 @@ mod optimization_tests {
         assert!((retrieved.weight - 0.3).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn test_transaction_commits_all_writes() {
+        let (storage, _temp) = create_test_storage();
+        let kind = NodeKind::new("fact").unwrap();
+        let a = make_node(kind.clone(), "Tx A");
+        let b = make_node(kind, "Tx B");
+        let edge = Edge::new(
+            a.id,
+            b.id,
+            Relation::new("related_to").unwrap(),
+            0.5,
+            EdgeProvenance::Manual {
+                created_by: "test".to_string(),
+            },
+        );
+
+        let weights = storage
+            .transaction(|tx| {
+                tx.put_node(&a)?;
+                tx.put_node(&b)?;
+                // Reads see earlier writes in the same transaction
+                assert!(tx.get_node(a.id)?.is_some());
+                tx.put_edge(&edge)?;
+                tx.update_edge_weight(a.id, b.id, &edge.relation, &|w| w + 0.25)
+            })
+            .unwrap();
+
+        assert_eq!(weights, (0.5, 0.75));
+        assert!(storage.get_node(a.id).unwrap().is_some());
+        assert!(storage.get_node(b.id).unwrap().is_some());
+        let stored = storage.get_edge(edge.id).unwrap().unwrap();
+        assert!((stored.weight - 0.75).abs() < f32::EPSILON);
+
+        let stats = storage.stats().unwrap();
+        assert_eq!(stats.node_count, 2);
+        assert_eq!(stats.edge_count, 1);
+    }
+
+    #[test]
+    fn test_transaction_error_leaves_no_partial_state() {
+        let (storage, _temp) = create_test_storage();
+        let kind = NodeKind::new("fact").unwrap();
+        let existing = make_node(kind.clone(), "Existing");
+        storage.put_node(&existing).unwrap();
+
+        let obs = make_node(kind, "Half-written observation");
+        let good_edge = Edge::new(
+            existing.id,
+            obs.id,
+            Relation::new("related_to").unwrap(),
+            1.0,
+            EdgeProvenance::Manual {
+                created_by: "test".to_string(),
+            },
+        );
+        // Target does not exist — fails after the node and first edge were written
+        let bad_edge = Edge::new(
+            obs.id,
+            uuid::Uuid::now_v7(),
+            Relation::new("related_to").unwrap(),
+            1.0,
+            EdgeProvenance::Manual {
+                created_by: "test".to_string(),
+            },
+        );
+
+        let result = storage.transaction(|tx| {
+            tx.put_node(&obs)?;
+            tx.put_edge(&good_edge)?;
+            tx.put_edge(&bad_edge)
+        });
+        assert!(matches!(result, Err(CortexError::InvalidEdge { .. })));
+
+        assert!(storage.get_node(obs.id).unwrap().is_none());
+        assert!(storage.get_edge(good_edge.id).unwrap().is_none());
+        assert!(storage.edges_from(existing.id).unwrap().is_empty());
+        let stats = storage.stats().unwrap();
+        assert_eq!(stats.node_count, 1);
+        assert_eq!(stats.edge_count, 0);
+    }
+
     #[test]
     fn test_snapshot_and_restore() {
         let (storage, _temp) = create_test_storage();
@@ -1679,6 +2697,153 @@ mod optimization_tests {
         assert_eq!(results.len(), 1);
     }
 
+    #[test]
+    fn test_header_scan_skips_large_bodies() {
+        let (storage, _temp) = create_test_storage();
+        let fact = NodeKind::new("fact").unwrap();
+
+        // 400 nodes with 64 KiB bodies, half of them tagged "hot"
+        let nodes: Vec<Node> = (0..400)
+            .map(|i| {
+                let mut node = make_node(fact.clone(), &format!("node-{}", i));
+                node.data.body = "x".repeat(64 * 1024);
+                node.embedding = Some(vec![0.1; 384]);
+                if i % 2 == 0 {
+                    node.data.tags = vec!["hot".to_string()];
+                }
+                node
+            })
+            .collect();
+        storage.put_nodes_batch(&nodes).unwrap();
+        let filter = || NodeFilter::new().with_tags(vec!["hot".to_string()]);
+
+        let full = storage.list_nodes(filter()).unwrap();
+        let headers = storage.list_node_headers(filter()).unwrap();
+
+        assert_eq!(headers.len(), 200);
+        let expected: Vec<NodeHeader> = full.iter().map(NodeHeader::from).collect();
+        assert_eq!(headers, expected);
+
+        // Paging and cursors behave the same as for full nodes
+        let page = storage
+            .list_node_headers(filter().with_limit(10).with_offset(5))
+            .unwrap();
+        assert_eq!(page, expected[5..15]);
+    }
+
+    #[test]
+    fn test_title_index_lookup_and_rename() {
+        let (storage, _temp) = create_test_storage();
+        let agent = NodeKind::new("agent").unwrap();
+
+        let mut node = make_node(agent.clone(), "kai");
+        storage.put_node(&node).unwrap();
+        // Same title under another kind must not collide
+        storage
+            .put_node(&make_node(NodeKind::new("fact").unwrap(), "kai"))
+            .unwrap();
+
+        let found = storage.get_node_by_title(&agent, "kai").unwrap().unwrap();
+        assert_eq!(found.id, node.id);
+        assert!(storage.get_node_by_title(&agent, "Kai").unwrap().is_none());
+
+        // Rename — old title no longer resolves
+        node.data.title = "kai-v2".to_string();
+        storage.put_node(&node).unwrap();
+        assert!(storage.get_node_by_title(&agent, "kai").unwrap().is_none());
+        assert_eq!(
+            storage
+                .get_node_by_title(&agent, "kai-v2")
+                .unwrap()
+                .unwrap()
+                .id,
+            node.id
+        );
+
+        // Soft-deleted nodes are skipped; hard delete drops the entry
+        storage.delete_node(node.id).unwrap();
+        assert!(storage
+            .get_node_by_title(&agent, "kai-v2")
+            .unwrap()
+            .is_none());
+        storage.hard_delete_node(node.id).unwrap();
+        assert!(storage
+            .get_node_by_title(&agent, "kai-v2")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_title_index_returns_newest_duplicate() {
+        let (storage, _temp) = create_test_storage();
+        let kind = NodeKind::new("prompt").unwrap();
+
+        let mut older = make_node(kind.clone(), "dup");
+        older.created_at = Utc::now() - chrono::Duration::days(1);
+        let newer = make_node(kind.clone(), "dup");
+        storage.put_node(&older).unwrap();
+        storage.put_node(&newer).unwrap();
+
+        let found = storage.get_node_by_title(&kind, "dup").unwrap().unwrap();
+        assert_eq!(found.id, newer.id);
+    }
+
+    #[test]
+    fn test_multi_tag_filter_matches_any_or_all_tags() {
+        let (storage, _temp) = create_test_storage();
+        let event = NodeKind::new("event").unwrap();
+
+        let mut both = make_node(event.clone(), "Rollback of prompt v3");
+        both.data.tags = vec!["rollback".to_string(), "prompt".to_string()];
+        let mut one = make_node(event.clone(), "Unrelated rollback");
+        one.data.tags = vec!["rollback".to_string()];
+        let mut other_kind = make_node(NodeKind::new("fact").unwrap(), "Tagged fact");
+        other_kind.data.tags = vec!["rollback".to_string(), "prompt".to_string()];
+        storage
+            .put_nodes_batch(&[both.clone(), one.clone(), other_kind.clone()])
+            .unwrap();
+
+        let tags = vec!["prompt".to_string(), "missing".to_string()];
+        let results = storage
+            .list_nodes(NodeFilter::new().with_tags(tags.clone()))
+            .unwrap();
+        let ids: Vec<_> = results.iter().map(|n| n.id).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&both.id) && ids.contains(&other_kind.id));
+
+        let tags = vec!["rollback".to_string(), "prompt".to_string()];
+        let results = storage
+            .list_nodes(NodeFilter::new().with_all_tags(tags.clone()))
+            .unwrap();
+        let ids: Vec<_> = results.iter().map(|n| n.id).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&both.id) && ids.contains(&other_kind.id));
+
+        // Intersected with a kind filter
+        let results = storage
+            .list_nodes(
+                NodeFilter::new()
+                    .with_kinds(vec![event.clone()])
+                    .with_all_tags(tags.clone()),
+            )
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, both.id);
+        let results = storage
+            .list_nodes(NodeFilter::new().with_kinds(vec![event]).with_tags(tags))
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        // A tag nobody has empties an all-tags result
+        let results = storage
+            .list_nodes(
+                NodeFilter::new()
+                    .with_all_tags(vec!["rollback".to_string(), "missing".to_string()]),
+            )
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
     #[test]
     fn test_source_index_update_on_agent_change() {
         let (storage, _temp) = create_test_storage();
@@ -1725,6 +2890,69 @@ mod optimization_tests {
         assert_eq!(results.len(), 2);
     }
 
+    #[test]
+    fn test_restore_node() {
+        let (storage, _temp) = create_test_storage();
+
+        let node = make_node(NodeKind::new("fact").unwrap(), "Deleted by mistake");
+        storage.put_node(&node).unwrap();
+        storage.delete_node(node.id).unwrap();
+
+        let tombstone = storage.get_node(node.id).unwrap().unwrap();
+        assert!(tombstone.deleted);
+        let deleted_at = tombstone.deleted_at.unwrap();
+        assert_eq!(storage.stats().unwrap().node_count, 0);
+
+        // A second delete keeps the original deletion time and count
+        storage.delete_node(node.id).unwrap();
+        let tombstone = storage.get_node(node.id).unwrap().unwrap();
+        assert_eq!(tombstone.deleted_at, Some(deleted_at));
+        assert_eq!(storage.stats().unwrap().node_count, 0);
+
+        let restored = storage.restore_node(node.id).unwrap();
+        assert!(!restored.deleted);
+        assert_eq!(restored.deleted_at, None);
+        assert_eq!(storage.get_node(node.id).unwrap().unwrap(), restored);
+        assert_eq!(storage.list_nodes(NodeFilter::new()).unwrap().len(), 1);
+        assert_eq!(storage.stats().unwrap().node_count, 1);
+
+        // Restoring a live node is a no-op
+        storage.restore_node(node.id).unwrap();
+        assert_eq!(storage.stats().unwrap().node_count, 1);
+
+        assert!(matches!(
+            storage.restore_node(uuid::Uuid::now_v7()),
+            Err(CortexError::NodeNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_concurrent_delete_and_restore_count_once() {
+        let (storage, _temp) = create_test_storage();
+        let storage = Arc::new(storage);
+        let node = make_node(NodeKind::new("fact").unwrap(), "Raced");
+        storage.put_node(&node).unwrap();
+
+        let race = |op: fn(&RedbStorage, NodeId)| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    let storage = storage.clone();
+                    std::thread::spawn(move || op(&storage, node.id))
+                })
+                .collect();
+            for handle in handles {
+                handle.join().unwrap();
+            }
+        };
+
+        race(|s, id| s.delete_node(id).unwrap());
+        assert_eq!(storage.stats().unwrap().node_count, 0);
+        race(|s, id| {
+            s.restore_node(id).unwrap();
+        });
+        assert_eq!(storage.stats().unwrap().node_count, 1);
+    }
+
     #[test]
     fn test_importance_filter() {
         let (storage, _temp) = create_test_storage();
@@ -1771,6 +2999,137 @@ mod optimization_tests {
         assert!(page1_ids.iter().all(|id| !page2_ids.contains(id)));
     }
 
+    #[test]
+    fn test_cursor_pagination() {
+        let (storage, _temp) = create_test_storage();
+
+        // Shared timestamps force the id tie-break to matter.
+        let base = chrono::Utc::now();
+        for i in 0..50 {
+            let mut node = make_node(NodeKind::new("fact").unwrap(), &format!("Node {}", i));
+            node.created_at = base - chrono::Duration::seconds(i / 3);
+            storage.put_node(&node).unwrap();
+        }
+
+        let mut pages = Vec::new();
+        let mut cursor = None;
+        loop {
+            let mut filter = NodeFilter::new().with_limit(20);
+            if let Some(c) = cursor {
+                filter = filter.after(c);
+            }
+            let page = storage.list_nodes(filter).unwrap();
+            if page.is_empty() {
+                break;
+            }
+            cursor = page.last().map(NodeCursor::after);
+            pages.push(page);
+        }
+        assert_eq!(
+            pages.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![20, 20, 10]
+        );
+
+        // Every node exactly once, in the same order as a single listing.
+        let paged: Vec<NodeId> = pages.iter().flatten().map(|n| n.id).collect();
+        let all: Vec<NodeId> = storage
+            .list_nodes(NodeFilter::new())
+            .unwrap()
+            .iter()
+            .map(|n| n.id)
+            .collect();
+        assert_eq!(paged, all);
+
+        // Cursors survive a round trip through their string form, and a
+        // node added later doesn't shift the pages already handed out.
+        let cursor = NodeCursor::after(&pages[0][19]);
+        let parsed: NodeCursor = cursor.to_string().parse().unwrap();
+        assert_eq!(parsed, cursor);
+        storage
+            .put_node(&make_node(NodeKind::new("fact").unwrap(), "Newest"))
+            .unwrap();
+        let page2 = storage
+            .list_nodes(NodeFilter::new().with_limit(20).after(parsed))
+            .unwrap();
+        let ids: Vec<NodeId> = page2.iter().map(|n| n.id).collect();
+        assert_eq!(ids, pages[1].iter().map(|n| n.id).collect::<Vec<_>>());
+
+        assert!("not-a-cursor".parse::<NodeCursor>().is_err());
+    }
+
+    #[test]
+    fn test_created_index_matches_sorted_listing() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("created.redb");
+        let storage = RedbStorage::open(&path).unwrap();
+
+        let base = chrono::Utc::now();
+        for i in 0..30 {
+            let kind = if i % 2 == 0 { "fact" } else { "decision" };
+            let mut node = make_node(NodeKind::new(kind).unwrap(), &format!("Node {}", i));
+            // Out of insertion order, with ties.
+            node.created_at = base - chrono::Duration::seconds((i * 7) % 11);
+            storage.put_node(&node).unwrap();
+        }
+        // Moving a node's created_at moves it in the index.
+        let mut moved = storage.list_nodes(NodeFilter::new()).unwrap().remove(5);
+        moved.created_at = base + chrono::Duration::seconds(60);
+        storage.put_node(&moved).unwrap();
+
+        let facts = || NodeFilter::new().with_kinds(vec![NodeKind::new("fact").unwrap()]);
+        let ids = |nodes: Vec<Node>| nodes.into_iter().map(|n| n.id).collect::<Vec<_>>();
+        let listings = |storage: &RedbStorage| {
+            let first = storage.list_nodes(facts().with_limit(4)).unwrap();
+            let cursor = NodeCursor::after(first.last().unwrap());
+            (
+                ids(storage.list_nodes(NodeFilter::new()).unwrap()),
+                ids(first),
+                ids(storage
+                    .list_nodes(facts().with_limit(4).after(cursor))
+                    .unwrap()),
+                ids(storage
+                    .list_nodes(NodeFilter::new().with_offset(3).with_limit(5))
+                    .unwrap()),
+            )
+        };
+
+        let indexed = listings(&storage);
+        assert_eq!(indexed.0[0], moved.id);
+        assert_eq!(indexed.1.len(), 4);
+        assert_eq!(indexed.2.len(), 4);
+        // Kind-filtered pages are sorted from the kind index, in the same
+        // order as the unfiltered walk of the creation index.
+        let fact_ids: Vec<NodeId> = storage
+            .list_nodes(NodeFilter::new())
+            .unwrap()
+            .into_iter()
+            .filter(|n| n.kind.as_str() == "fact")
+            .map(|n| n.id)
+            .take(8)
+            .collect();
+        assert_eq!([indexed.1.clone(), indexed.2.clone()].concat(), fact_ids);
+
+        // Without the index the listing sorts instead, in the same order.
+        let txn = storage.db.begin_write().unwrap();
+        txn.delete_table(NODES_BY_CREATED).unwrap();
+        txn.commit().unwrap();
+        assert_eq!(listings(&storage), indexed);
+
+        // Reopening rebuilds the index from the nodes.
+        drop(storage);
+        let storage = RedbStorage::open(&path).unwrap();
+        let len = storage
+            .db
+            .begin_read()
+            .unwrap()
+            .open_table(NODES_BY_CREATED)
+            .unwrap()
+            .len()
+            .unwrap();
+        assert_eq!(len, 30);
+        assert_eq!(listings(&storage), indexed);
+    }
+
     #[test]
     fn test_concurrent_read_during_iteration() {
         let (storage, _temp) = create_test_storage();
@@ -1828,8 +3187,9 @@ mod schema_regression_tests {
         // Generated by: cargo test -p cortex-core generate_golden_node_bytes -- --nocapture
         // Node struct: id, kind, data(title, body, metadata, tags), embedding,
         //              source(agent, session, channel), importance, access_count,
-        //              last_accessed_at, created_at, updated_at, deleted
-        // Schema version: 2  (CURRENT_SCHEMA_VERSION)
+        //              last_accessed_at, created_at, updated_at, deleted, confidence,
+        //              expires_at, deleted_at
+        // Schema version: 6  (CURRENT_SCHEMA_VERSION)
         #[rustfmt::skip]
         const GOLDEN_NODE_BYTES: &[u8] = &[
             16, 0, 0, 0, 0, 0, 0, 0, 1, 146, 171, 205, 239, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11,
@@ -1853,6 +3213,10 @@ mod schema_regression_tests {
             20, 0, 0, 0, 0, 0, 0, 0, 50, 48, 50, 51, 45, 49, 49, 45, 49, 52, 84, 50, 50, 58, 49,
             51, 58, 50, 48, 90,
             0,
+            0, 0, 64, 63,
+            1, 20, 0, 0, 0, 0, 0, 0, 0, 50, 48, 50, 51, 45, 49, 49, 45, 49, 53, 84, 50, 50, 58,
+            49, 51, 58, 50, 48, 90,
+            0,
         ];
 
         // Verify current serialization matches the golden snapshot.
@@ -1877,10 +3241,38 @@ mod schema_regression_tests {
             .expect("Golden bytes failed to deserialize — regenerate them");
         assert_eq!(recovered.data.title, "Schema regression test");
         assert_eq!(recovered.importance, 0.5);
+        assert_eq!(recovered.confidence, 0.75);
+        assert_eq!(recovered.expires_at, make_canonical_node().expires_at);
         assert_eq!(recovered.access_count, 0);
         assert!(!recovered.deleted);
     }
 
+    /// JSON exports written before `confidence` existed still import.
+    #[test]
+    fn test_node_json_without_confidence_defaults_to_one() {
+        let node = make_canonical_node();
+        let json = serde_json::to_value(&node).unwrap();
+        assert_eq!(json["confidence"], 0.75);
+        let recovered: Node = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(recovered, node);
+
+        let mut old = json;
+        old.as_object_mut().unwrap().remove("confidence");
+        let recovered: Node = serde_json::from_value(old).unwrap();
+        assert_eq!(recovered.confidence, 1.0);
+    }
+
+    /// Likewise for `expires_at`: absent means the node never expires.
+    #[test]
+    fn test_node_json_without_expires_at_never_expires() {
+        let mut old = serde_json::to_value(make_canonical_node()).unwrap();
+        assert_eq!(old["expires_at"], "2023-11-15T22:13:20Z");
+        old.as_object_mut().unwrap().remove("expires_at");
+        let recovered: Node = serde_json::from_value(old).unwrap();
+        assert_eq!(recovered.expires_at, None);
+        assert!(!recovered.is_expired());
+    }
+
     /// Pre-flight check: a fresh database should always pass.
     #[test]
     fn test_preflight_passes_on_fresh_db() {
@@ -1,6 +1,6 @@
 use crate::error::Result;
 use crate::storage::filters::{NodeFilter, StorageStats};
-use crate::types::{Edge, EdgeId, Node, NodeId};
+use crate::types::{Edge, EdgeId, Node, NodeHeader, NodeId, NodeKind, Relation};
 use std::path::Path;
 
 /// Storage trait for the graph database
@@ -13,9 +13,18 @@ pub trait Storage: Send + Sync {
     /// Retrieve a node by ID
     fn get_node(&self, id: NodeId) -> Result<Option<Node>>;
 
-    /// Soft delete a node (sets tombstone flag)
+    /// Soft delete a node (sets tombstone flag and `deleted_at`)
     fn delete_node(&self, id: NodeId) -> Result<()>;
 
+    /// Undo a soft delete. The node must still exist, i.e. not yet purged.
+    /// Default implementation returns an error for backends without tombstones.
+    fn restore_node(&self, id: NodeId) -> Result<Node> {
+        let _ = id;
+        Err(crate::error::CortexError::Validation(
+            "restore_node is not supported by this storage backend".to_string(),
+        ))
+    }
+
     /// Permanently remove a node and its edges from storage.
     /// Only call after the node has been soft-deleted and the grace period has passed.
     /// Default implementation returns an error for backends that do not support hard deletion.
@@ -26,9 +35,31 @@ pub trait Storage: Send + Sync {
         ))
     }
 
+    /// Find the newest non-deleted node of `kind` whose title is exactly `title`.
+    /// Default implementation scans `list_nodes`; indexed backends should override.
+    fn get_node_by_title(&self, kind: &NodeKind, title: &str) -> Result<Option<Node>> {
+        let headers = self.list_node_headers(NodeFilter::new().with_kinds(vec![kind.clone()]))?;
+        match headers.into_iter().find(|h| h.title == title) {
+            Some(header) => self.get_node(header.id),
+            None => Ok(None),
+        }
+    }
+
     /// List nodes matching the filter
     fn list_nodes(&self, filter: NodeFilter) -> Result<Vec<Node>>;
 
+    /// Like `list_nodes`, but without bodies, metadata or embeddings. Scans
+    /// that only look at titles, tags, importance or timestamps should use
+    /// this. The default implementation strips full nodes; backends should
+    /// override it to skip decoding them.
+    fn list_node_headers(&self, filter: NodeFilter) -> Result<Vec<NodeHeader>> {
+        Ok(self
+            .list_nodes(filter)?
+            .iter()
+            .map(NodeHeader::from)
+            .collect())
+    }
+
     /// Count nodes matching the filter
     fn count_nodes(&self, filter: NodeFilter) -> Result<u64>;
 
@@ -40,7 +71,8 @@ pub trait Storage: Send + Sync {
     /// Retrieve an edge by ID
     fn get_edge(&self, id: EdgeId) -> Result<Option<Edge>>;
 
-    /// Delete an edge (hard delete, edges don't use tombstones)
+    /// Delete an edge (hard delete, edges don't use tombstones). Its
+    /// [`paired_edge`](Self::paired_edge), if any, is deleted with it.
     fn delete_edge(&self, id: EdgeId) -> Result<()>;
 
     /// Get all edges originating from a node
@@ -52,6 +84,29 @@ pub trait Storage: Send + Sync {
     /// Get all edges between two specific nodes
     fn edges_between(&self, from: NodeId, to: NodeId) -> Result<Vec<Edge>>;
 
+    /// The other half of an inverse pair: the edge derived from `edge` by
+    /// [`put_edge_with_inverse`](Self::put_edge_with_inverse), or, if
+    /// `edge` is itself the derived one, the edge it was derived from.
+    fn paired_edge(&self, edge: &Edge) -> Result<Option<Edge>> {
+        if let crate::types::EdgeProvenance::DerivedInverse { of } = edge.provenance {
+            return self.get_edge(of);
+        }
+        Ok(self.edges_between(edge.to, edge.from)?.into_iter().find(|e| {
+            matches!(e.provenance, crate::types::EdgeProvenance::DerivedInverse { of } if of == edge.id)
+        }))
+    }
+
+    /// List every stored edge, including ones whose endpoints are gone.
+    /// Default implementation walks `edges_from` for every node (deleted included),
+    /// so it misses edges whose source node no longer exists; backends should override.
+    fn list_edges(&self) -> Result<Vec<Edge>> {
+        let mut edges = Vec::new();
+        for node in self.list_nodes(NodeFilter::new().include_deleted())? {
+            edges.extend(self.edges_from(node.id)?);
+        }
+        Ok(edges)
+    }
+
     // === Batch Operations ===
 
     /// Insert or update multiple nodes in a single transaction
@@ -60,6 +115,43 @@ pub trait Storage: Send + Sync {
     /// Insert or update multiple edges in a single transaction
     fn put_edges_batch(&self, edges: &[Edge]) -> Result<()>;
 
+    // === Transactions ===
+
+    /// Run `f` against a single write transaction. Every write made through
+    /// the `StorageTransaction` commits together if `f` returns `Ok`, and none
+    /// of them are visible if `f` (or any write inside it) returns `Err`.
+    /// Default implementation returns an error for backends without transactions.
+    fn transaction<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut dyn StorageTransaction) -> Result<T>,
+        Self: Sized,
+    {
+        let _ = f;
+        Err(crate::error::CortexError::Validation(
+            "transaction is not supported by this storage backend".to_string(),
+        ))
+    }
+
+    /// Store `edge` and, if its relation has an inverse, the inverse edge
+    /// (see [`Edge::inverse`]), in one transaction. An inverse that
+    /// already exists is left alone. Returns the inverse edge written.
+    fn put_edge_with_inverse(&self, edge: &Edge) -> Result<Option<Edge>>
+    where
+        Self: Sized,
+    {
+        self.transaction(|tx| {
+            tx.put_edge(edge)?;
+            let Some(inverse) = edge.inverse() else {
+                return Ok(None);
+            };
+            match tx.put_edge(&inverse) {
+                Ok(()) => Ok(Some(inverse)),
+                Err(crate::error::CortexError::DuplicateEdge { .. }) => Ok(None),
+                Err(e) => Err(e),
+            }
+        })
+    }
+
     // === Metadata ===
 
     /// Store metadata key-value pair
@@ -76,7 +168,8 @@ pub trait Storage: Send + Sync {
     /// Get database statistics
     fn stats(&self) -> Result<StorageStats>;
 
-    /// Create a file-level backup/snapshot
+    /// Write a consistent point-in-time copy of the store to `path`.
+    /// Writers are not blocked while it runs.
     fn snapshot(&self, path: &Path) -> Result<()>;
 
     /// List all distinct node kinds that exist in storage.
@@ -85,3 +178,28 @@ pub trait Storage: Send + Sync {
         Ok(vec![])
     }
 }
+
+/// Writes staged inside `Storage::transaction`.
+///
+/// Reads see writes already made in the same transaction. Validation matches
+/// the corresponding `Storage` methods; an error aborts the whole transaction.
+pub trait StorageTransaction {
+    /// Retrieve a node by ID
+    fn get_node(&self, id: NodeId) -> Result<Option<Node>>;
+
+    /// Store a node (insert or update)
+    fn put_node(&mut self, node: &Node) -> Result<()>;
+
+    /// Store an edge (insert or update)
+    fn put_edge(&mut self, edge: &Edge) -> Result<()>;
+
+    /// Apply `f` to the weight of the edge (from, to, relation).
+    /// Returns `(old_weight, new_weight)`; the new weight is clamped to 0.0-1.0.
+    fn update_edge_weight(
+        &mut self,
+        from: NodeId,
+        to: NodeId,
+        relation: &Relation,
+        f: &dyn Fn(f32) -> f32,
+    ) -> Result<(f32, f32)>;
+}
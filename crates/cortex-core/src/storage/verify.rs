@@ -0,0 +1,390 @@
+use crate::error::Result;
+use crate::graph::cycles::cycles_in;
+use crate::kinds::defaults as kinds;
+use crate::relations::defaults as relations;
+use crate::storage::{NodeFilter, Storage};
+use crate::types::{EdgeId, NodeId, NodeKind, Relation};
+use crate::vector::{embedding_input, EmbeddingService};
+use std::collections::{HashMap, HashSet};
+
+/// What [`verify`] checks against.
+#[derive(Debug, Clone)]
+pub struct VerifyConfig {
+    /// Dimension every stored embedding should have (the model's).
+    pub embedding_dim: usize,
+    /// Kinds whose titles are used as names and must not repeat.
+    pub unique_title_kinds: Vec<NodeKind>,
+    /// Relations that must not form cycles (something can't supersede or
+    /// depend on itself, however indirectly).
+    pub acyclic_relations: Vec<Relation>,
+}
+
+impl VerifyConfig {
+    pub fn new(embedding_dim: usize) -> Self {
+        Self {
+            embedding_dim,
+            unique_title_kinds: vec![kinds::agent(), kinds::prompt()],
+            acyclic_relations: vec![relations::supersedes(), relations::depends_on()],
+        }
+    }
+}
+
+/// Live nodes of one kind sharing a title.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateTitle {
+    pub kind: NodeKind,
+    pub title: String,
+    pub ids: Vec<NodeId>,
+}
+
+/// Live nodes joined in a cycle by edges of one relation, in edge order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationCycle {
+    pub relation: Relation,
+    pub nodes: Vec<NodeId>,
+}
+
+/// Everything [`verify`] found. Empty lists mean that check passed.
+#[derive(Debug, Clone, Default)]
+pub struct VerifyReport {
+    pub nodes_checked: usize,
+    pub edges_checked: usize,
+    /// Edges whose source or target node is not in storage at all.
+    /// Edges to soft-deleted nodes are expected until the hard delete.
+    pub dangling_edges: Vec<EdgeId>,
+    /// Live nodes with an embedding of the wrong length.
+    pub wrong_dimension: Vec<NodeId>,
+    /// Live observations with no edges in either direction.
+    pub orphaned_observations: Vec<NodeId>,
+    pub duplicate_titles: Vec<DuplicateTitle>,
+    /// Cycles in the relations listed in [`VerifyConfig::acyclic_relations`].
+    pub relation_cycles: Vec<RelationCycle>,
+}
+
+impl VerifyReport {
+    /// Total issues across every check.
+    pub fn issue_count(&self) -> usize {
+        self.dangling_edges.len()
+            + self.wrong_dimension.len()
+            + self.orphaned_observations.len()
+            + self.duplicate_titles.len()
+            + self.relation_cycles.len()
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.issue_count() == 0
+    }
+}
+
+/// What [`repair`] changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepairSummary {
+    pub edges_pruned: usize,
+    pub nodes_reembedded: usize,
+}
+
+/// Scan `storage` for structural problems. Read-only.
+pub fn verify<S: Storage>(storage: &S, config: &VerifyConfig) -> Result<VerifyReport> {
+    let nodes = storage.list_nodes(NodeFilter::new().include_deleted())?;
+    let edges = storage.list_edges()?;
+    let mut report = VerifyReport {
+        nodes_checked: nodes.len(),
+        edges_checked: edges.len(),
+        ..Default::default()
+    };
+
+    let existing: HashSet<NodeId> = nodes.iter().map(|n| n.id).collect();
+    let mut linked: HashSet<NodeId> = HashSet::new();
+    for edge in &edges {
+        if !existing.contains(&edge.from) || !existing.contains(&edge.to) {
+            report.dangling_edges.push(edge.id);
+        }
+        linked.insert(edge.from);
+        linked.insert(edge.to);
+    }
+
+    let observation = kinds::observation();
+    let mut titles: HashMap<(&NodeKind, &str), Vec<NodeId>> = HashMap::new();
+    for node in nodes.iter().filter(|n| !n.deleted) {
+        if node
+            .embedding
+            .as_ref()
+            .is_some_and(|e| e.len() != config.embedding_dim)
+        {
+            report.wrong_dimension.push(node.id);
+        }
+        if node.kind == observation && !linked.contains(&node.id) {
+            report.orphaned_observations.push(node.id);
+        }
+        if config.unique_title_kinds.contains(&node.kind) {
+            titles
+                .entry((&node.kind, node.data.title.as_str()))
+                .or_default()
+                .push(node.id);
+        }
+    }
+
+    let mut duplicates: Vec<DuplicateTitle> = titles
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .map(|((kind, title), ids)| DuplicateTitle {
+            kind: kind.clone(),
+            title: title.to_string(),
+            ids,
+        })
+        .collect();
+    duplicates.sort_by(|a, b| (a.kind.as_str(), &a.title).cmp(&(b.kind.as_str(), &b.title)));
+    report.duplicate_titles = duplicates;
+
+    let live: Vec<NodeId> = nodes.iter().filter(|n| !n.deleted).map(|n| n.id).collect();
+    let live_set: HashSet<NodeId> = live.iter().copied().collect();
+    for relation in &config.acyclic_relations {
+        let mut adjacency: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for edge in edges.iter().filter(|e| &e.relation == relation) {
+            if live_set.contains(&edge.from) && live_set.contains(&edge.to) {
+                adjacency.entry(edge.from).or_default().push(edge.to);
+            }
+        }
+        for cycle in cycles_in(&live, &adjacency) {
+            report.relation_cycles.push(RelationCycle {
+                relation: relation.clone(),
+                nodes: cycle,
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+/// Fix what can be fixed mechanically: delete dangling edges and re-embed
+/// nodes with wrong-dimension embeddings. Orphaned observations, duplicate
+/// titles and relation cycles need a human decision and are left alone.
+pub fn repair<S: Storage, E: EmbeddingService>(
+    storage: &S,
+    embedder: &E,
+    report: &VerifyReport,
+) -> Result<RepairSummary> {
+    let mut summary = RepairSummary::default();
+
+    for &id in &report.dangling_edges {
+        storage.delete_edge(id)?;
+        summary.edges_pruned += 1;
+    }
+
+    let mut nodes = Vec::with_capacity(report.wrong_dimension.len());
+    for &id in &report.wrong_dimension {
+        if let Some(node) = storage.get_node(id)? {
+            nodes.push(node);
+        }
+    }
+    if !nodes.is_empty() {
+        let texts: Vec<String> = nodes.iter().map(embedding_input).collect();
+        let embeddings = embedder.embed_batch(&texts)?;
+        for (node, embedding) in nodes.iter_mut().zip(embeddings) {
+            node.embedding = Some(embedding);
+        }
+        storage.put_nodes_batch(&nodes)?;
+        summary.nodes_reembedded = nodes.len();
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::RedbStorage;
+    use crate::testing::FixedEmbedder;
+    use crate::types::{Edge, EdgeProvenance, Node, Relation, Source};
+
+    const DIM: usize = 3;
+
+    fn setup() -> (RedbStorage, tempfile::TempDir) {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let storage = RedbStorage::open(tmp.path().join("verify.redb")).unwrap();
+        (storage, tmp)
+    }
+
+    fn node(kind: NodeKind, title: &str) -> Node {
+        let mut node = Node::new(
+            kind,
+            title.to_string(),
+            "body".to_string(),
+            Source {
+                agent: "test".into(),
+                session: None,
+                channel: None,
+            },
+            0.5,
+        );
+        node.embedding = Some(vec![0.5; DIM]);
+        node
+    }
+
+    fn edge(from: NodeId, to: NodeId) -> Edge {
+        Edge::new(
+            from,
+            to,
+            Relation::new("related_to").unwrap(),
+            0.8,
+            EdgeProvenance::Manual {
+                created_by: "test".into(),
+            },
+        )
+    }
+
+    #[test]
+    fn healthy_graph_is_clean() {
+        let (storage, _tmp) = setup();
+        let fact = node(kinds::fact(), "fact");
+        let obs = node(kinds::observation(), "seen");
+        storage
+            .put_nodes_batch(&[fact.clone(), obs.clone()])
+            .unwrap();
+        storage.put_edge(&edge(obs.id, fact.id)).unwrap();
+
+        let report = verify(&storage, &VerifyConfig::new(DIM)).unwrap();
+        assert!(report.is_clean(), "{report:?}");
+        assert_eq!((report.nodes_checked, report.edges_checked), (2, 1));
+    }
+
+    #[test]
+    fn detects_and_prunes_dangling_edges() {
+        let (storage, _tmp) = setup();
+        let a = node(kinds::fact(), "a");
+        let b = node(kinds::fact(), "b");
+        storage.put_nodes_batch(&[a.clone(), b.clone()]).unwrap();
+        let good = edge(a.id, b.id);
+        // Batch writes skip the endpoint check, which is how dangling edges get in
+        let dangling = edge(a.id, uuid::Uuid::now_v7());
+        let both_gone = edge(uuid::Uuid::now_v7(), uuid::Uuid::now_v7());
+        storage
+            .put_edges_batch(&[good.clone(), dangling.clone(), both_gone.clone()])
+            .unwrap();
+
+        let report = verify(&storage, &VerifyConfig::new(DIM)).unwrap();
+        let mut found = report.dangling_edges.clone();
+        found.sort();
+        let mut expected = vec![dangling.id, both_gone.id];
+        expected.sort();
+        assert_eq!(found, expected);
+        assert_eq!(report.issue_count(), 2);
+
+        let fixed = repair(&storage, &FixedEmbedder(vec![1.0; DIM]), &report).unwrap();
+        assert_eq!(fixed.edges_pruned, 2);
+        assert!(verify(&storage, &VerifyConfig::new(DIM))
+            .unwrap()
+            .is_clean());
+        assert!(storage.get_edge(good.id).unwrap().is_some());
+    }
+
+    #[test]
+    fn detects_and_reembeds_wrong_dimension() {
+        let (storage, _tmp) = setup();
+        let ok = node(kinds::fact(), "ok");
+        let mut stale = node(kinds::fact(), "stale");
+        stale.embedding = Some(vec![0.1; DIM + 2]);
+        // No embedding at all is a coverage gap, not corruption
+        let mut bare = node(kinds::fact(), "bare");
+        bare.embedding = None;
+        storage
+            .put_nodes_batch(&[ok.clone(), stale.clone(), bare])
+            .unwrap();
+
+        let report = verify(&storage, &VerifyConfig::new(DIM)).unwrap();
+        assert_eq!(report.wrong_dimension, [stale.id]);
+
+        let fixed = repair(&storage, &FixedEmbedder(vec![1.0; DIM]), &report).unwrap();
+        assert_eq!(fixed.nodes_reembedded, 1);
+        let stored = storage.get_node(stale.id).unwrap().unwrap();
+        assert_eq!(stored.embedding, Some(vec![1.0; DIM]));
+        assert!(verify(&storage, &VerifyConfig::new(DIM))
+            .unwrap()
+            .is_clean());
+    }
+
+    #[test]
+    fn detects_orphaned_observations() {
+        let (storage, _tmp) = setup();
+        let orphan = node(kinds::observation(), "nobody links me");
+        let linked = node(kinds::observation(), "linked");
+        let fact = node(kinds::fact(), "a lone fact is fine");
+        storage
+            .put_nodes_batch(&[orphan.clone(), linked.clone(), fact.clone()])
+            .unwrap();
+        storage.put_edge(&edge(fact.id, linked.id)).unwrap();
+
+        let report = verify(&storage, &VerifyConfig::new(DIM)).unwrap();
+        assert_eq!(report.orphaned_observations, [orphan.id]);
+
+        // Left for a human to decide
+        let fixed = repair(&storage, &FixedEmbedder(vec![1.0; DIM]), &report).unwrap();
+        assert_eq!(fixed, RepairSummary::default());
+    }
+
+    #[test]
+    fn detects_duplicate_titles_for_unique_kinds() {
+        let (storage, _tmp) = setup();
+        let kai = node(kinds::agent(), "kai");
+        let kai_again = node(kinds::agent(), "kai");
+        let other = node(kinds::agent(), "other");
+        // Facts may share titles
+        let f1 = node(kinds::fact(), "same");
+        let f2 = node(kinds::fact(), "same");
+        // A soft-deleted duplicate doesn't count
+        let mut gone = node(kinds::agent(), "other");
+        gone.deleted = true;
+        storage
+            .put_nodes_batch(&[kai.clone(), kai_again.clone(), other, f1, f2, gone])
+            .unwrap();
+
+        let report = verify(&storage, &VerifyConfig::new(DIM)).unwrap();
+        assert_eq!(report.duplicate_titles.len(), 1);
+        let dup = &report.duplicate_titles[0];
+        assert_eq!((dup.kind.as_str(), dup.title.as_str()), ("agent", "kai"));
+        let mut ids = dup.ids.clone();
+        ids.sort();
+        let mut expected = vec![kai.id, kai_again.id];
+        expected.sort();
+        assert_eq!(ids, expected);
+    }
+
+    #[test]
+    fn detects_supersedes_cycles() {
+        let (storage, _tmp) = setup();
+        let v1 = node(kinds::decision(), "v1");
+        let v2 = node(kinds::decision(), "v2");
+        let v3 = node(kinds::decision(), "v3");
+        storage
+            .put_nodes_batch(&[v1.clone(), v2.clone(), v3.clone()])
+            .unwrap();
+        let supersedes = |from: NodeId, to: NodeId| {
+            let mut e = edge(from, to);
+            e.relation = relations::supersedes();
+            e
+        };
+        storage.put_edge(&supersedes(v3.id, v2.id)).unwrap();
+        storage.put_edge(&supersedes(v2.id, v1.id)).unwrap();
+        // A related_to cycle is fine
+        storage.put_edge(&edge(v1.id, v3.id)).unwrap();
+        storage.put_edge(&edge(v3.id, v1.id)).unwrap();
+        assert!(verify(&storage, &VerifyConfig::new(DIM))
+            .unwrap()
+            .is_clean());
+
+        storage.put_edge(&supersedes(v1.id, v3.id)).unwrap();
+        let report = verify(&storage, &VerifyConfig::new(DIM)).unwrap();
+        assert_eq!(report.relation_cycles.len(), 1);
+        let cycle = &report.relation_cycles[0];
+        assert_eq!(cycle.relation, relations::supersedes());
+        let mut ids = cycle.nodes.clone();
+        ids.sort();
+        let mut expected = vec![v1.id, v2.id, v3.id];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        // Left for a human to decide
+        let fixed = repair(&storage, &FixedEmbedder(vec![1.0; DIM]), &report).unwrap();
+        assert_eq!(fixed, RepairSummary::default());
+    }
+}
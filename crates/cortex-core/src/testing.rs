@@ -0,0 +1,118 @@
+//! Test doubles shared by the crate's unit tests. None of them loads a model.
+
+use crate::error::Result;
+use crate::types::Embedding;
+use crate::vector::EmbeddingService;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Embeds every text to the same vector. Tests that need distinct nodes set
+/// their embeddings directly.
+pub(crate) struct FixedEmbedder(pub Vec<f32>);
+
+impl EmbeddingService for FixedEmbedder {
+    fn embed(&self, _text: &str) -> Result<Embedding> {
+        Ok(self.0.clone())
+    }
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Embedding>> {
+        texts.iter().map(|t| self.embed(t)).collect()
+    }
+    fn dimension(&self) -> usize {
+        self.0.len()
+    }
+    fn model_name(&self) -> &str {
+        "fixed"
+    }
+}
+
+/// One axis per keyword, set when the text contains the word, plus a last
+/// axis for texts containing none of them. Similarity is exact.
+pub(crate) struct KeywordEmbedder {
+    words: &'static [&'static str],
+    scale: f32,
+    background: Option<f32>,
+}
+
+impl KeywordEmbedder {
+    pub fn new(words: &'static [&'static str]) -> Self {
+        Self {
+            words,
+            scale: 1.0,
+            background: None,
+        }
+    }
+
+    /// Set matched axes to `scale` instead of 1.0.
+    pub fn scaled(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the last axis to `value` on every vector, matched or not.
+    pub fn with_background(mut self, value: f32) -> Self {
+        self.background = Some(value);
+        self
+    }
+}
+
+impl EmbeddingService for KeywordEmbedder {
+    fn embed(&self, text: &str) -> Result<Embedding> {
+        let mut v = vec![0.0; self.words.len() + 1];
+        for (i, w) in self.words.iter().enumerate() {
+            if text.contains(w) {
+                v[i] = self.scale;
+            }
+        }
+        let last = self.words.len();
+        v[last] = match self.background {
+            Some(value) => value,
+            None if v.iter().all(|x| *x == 0.0) => self.scale,
+            None => 0.0,
+        };
+        Ok(v)
+    }
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Embedding>> {
+        texts.iter().map(|t| self.embed(t)).collect()
+    }
+    fn dimension(&self) -> usize {
+        self.words.len() + 1
+    }
+    fn model_name(&self) -> &str {
+        "keyword"
+    }
+}
+
+/// Counts the texts the wrapped embedder is asked to embed.
+pub(crate) struct CountingEmbedder<E> {
+    inner: E,
+    calls: AtomicUsize,
+}
+
+impl<E> CountingEmbedder<E> {
+    pub fn new(inner: E) -> Self {
+        Self {
+            inner,
+            calls: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+impl<E: EmbeddingService> EmbeddingService for CountingEmbedder<E> {
+    fn embed(&self, text: &str) -> Result<Embedding> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.embed(text)
+    }
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Embedding>> {
+        self.calls.fetch_add(texts.len(), Ordering::SeqCst);
+        self.inner.embed_batch(texts)
+    }
+    fn dimension(&self) -> usize {
+        self.inner.dimension()
+    }
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+}
@@ -15,6 +15,12 @@ fn default_last_accessed_at() -> DateTime<Utc> {
     DateTime::<Utc>::UNIX_EPOCH
 }
 
+/// Serde default for `confidence`: nodes written before the field existed are
+/// taken at face value.
+fn default_confidence() -> f32 {
+    1.0
+}
+
 /// Type alias for edge identifiers
 pub type EdgeId = Uuid;
 
@@ -65,6 +71,23 @@ pub struct Node {
     /// Soft delete. Nodes are never physically removed,
     /// only tombstoned. Allows undo and audit.
     pub deleted: bool,
+
+    /// How sure we are that the content is true (0.0 - 1.0), independent of
+    /// how much it matters. Contradictions resolved with
+    /// `keep_highest_confidence` keep the more confident node.
+    #[serde(default = "default_confidence")]
+    pub confidence: f32,
+
+    /// When this knowledge stops being true (a temporary constraint, an
+    /// event now past). Expired nodes drop out of search, traversal and
+    /// briefings unless asked for, and retention evicts them. None = never.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// When the node was tombstoned. Set with `deleted`, cleared on restore;
+    /// retention counts its grace period from here.
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 /// A node kind identifier. Lowercase alphanumeric + hyphens only.
@@ -89,6 +112,14 @@ impl NodeKind {
         Ok(NodeKind(kind.to_string()))
     }
 
+    /// [`NodeKind::new`], then reject the kind if `registry` is strict and
+    /// doesn't list it.
+    pub fn new_checked(kind: &str, registry: &crate::kinds::KindRegistry) -> Result<Self> {
+        let kind = Self::new(kind)?;
+        registry.check(&kind)?;
+        Ok(kind)
+    }
+
     pub fn as_str(&self) -> &str {
         &self.0
     }
@@ -125,6 +156,45 @@ impl TryFrom<String> for NodeKind {
     }
 }
 
+/// Serde format for node metadata.
+///
+/// bincode cannot deserialize a self-describing `Value`, so binary formats
+/// store each value as a JSON string. An empty map encodes the same either
+/// way. Human-readable formats (JSON) keep plain values.
+mod metadata_format {
+    use serde::de::Error as _;
+    use serde::ser::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value;
+    use std::collections::HashMap;
+
+    pub fn serialize<S: Serializer>(
+        metadata: &HashMap<String, Value>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return metadata.serialize(serializer);
+        }
+        let encoded = metadata
+            .iter()
+            .map(|(k, v)| Ok((k, serde_json::to_string(v).map_err(S::Error::custom)?)))
+            .collect::<Result<HashMap<_, _>, S::Error>>()?;
+        encoded.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<String, Value>, D::Error> {
+        if deserializer.is_human_readable() {
+            return HashMap::deserialize(deserializer);
+        }
+        HashMap::<String, String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(k, v)| Ok((k, serde_json::from_str(&v).map_err(D::Error::custom)?)))
+            .collect()
+    }
+}
+
 /// Node content structure
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct NodeData {
@@ -139,6 +209,7 @@ pub struct NodeData {
     /// Arbitrary key-value metadata. Optional.
     /// Use for: source URLs, file paths, commit SHAs,
     /// agent IDs, task IDs, timestamps of the thing described.
+    #[serde(with = "metadata_format")]
     pub metadata: HashMap<String, Value>,
 
     /// Tags for lightweight categorisation.
@@ -202,6 +273,28 @@ impl Relation {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Whether `A rel B` implies `B rel A` (see [`crate::relations::SYMMETRIC`]).
+    pub fn is_symmetric(&self) -> bool {
+        crate::relations::SYMMETRIC.contains(&self.as_str())
+    }
+
+    /// The relation that reads `B → A` for an edge `A → B`: the relation
+    /// itself when symmetric, its named inverse (see
+    /// [`crate::relations::INVERSES`]) otherwise. `None` when it has neither.
+    pub fn inverse(&self) -> Option<Relation> {
+        if self.is_symmetric() {
+            return Some(self.clone());
+        }
+        crate::relations::INVERSES
+            .iter()
+            .find_map(|&(rel, inv)| match self.as_str() {
+                s if s == rel => Some(inv),
+                s if s == inv => Some(rel),
+                _ => None,
+            })
+            .map(|inv| Relation(inv.to_string()))
+    }
 }
 
 impl std::fmt::Display for Relation {
@@ -267,6 +360,10 @@ pub enum EdgeProvenance {
 
     /// Imported from an external source (Alexandria migration).
     Imported { source: String },
+
+    /// Derived: the inverse of edge `of`, written alongside it so the
+    /// link can be followed from either endpoint.
+    DerivedInverse { of: EdgeId },
 }
 
 /// Source of a node
@@ -309,6 +406,9 @@ impl Node {
             created_at: now,
             updated_at: now,
             deleted: false,
+            confidence: 1.0,
+            expires_at: None,
+            deleted_at: None,
         }
     }
 
@@ -327,6 +427,13 @@ impl Node {
             ));
         }
 
+        if !(0.0..=1.0).contains(&self.confidence) {
+            return Err(format!(
+                "Confidence {} out of range [0.0, 1.0]",
+                self.confidence
+            ));
+        }
+
         // Tags validation
         if self.data.tags.len() > 32 {
             return Err("More than 32 tags".to_string());
@@ -350,6 +457,16 @@ impl Node {
         Ok(())
     }
 
+    /// Whether `expires_at` has passed by `at`.
+    pub fn is_expired_at(&self, at: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|t| t <= at)
+    }
+
+    /// Whether `expires_at` has passed.
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(Utc::now())
+    }
+
     /// Increment access count and update last-accessed timestamp.
     /// Called when this node is returned in a search result or included in a briefing.
     pub fn record_access(&mut self) {
@@ -360,6 +477,51 @@ impl Node {
     }
 }
 
+/// A node without its body, metadata or embedding: what scans need to
+/// filter, sort and pick nodes. Load the full [`Node`] with `get_node` for
+/// the ones you keep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeHeader {
+    pub id: NodeId,
+    pub kind: NodeKind,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub source: Source,
+    pub importance: f32,
+    pub confidence: f32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl NodeHeader {
+    /// Whether `expires_at` has passed by `at`.
+    pub fn is_expired_at(&self, at: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|t| t <= at)
+    }
+}
+
+impl From<&Node> for NodeHeader {
+    fn from(node: &Node) -> Self {
+        Self {
+            id: node.id,
+            kind: node.kind.clone(),
+            title: node.data.title.clone(),
+            tags: node.data.tags.clone(),
+            source: node.source.clone(),
+            importance: node.importance,
+            confidence: node.confidence,
+            created_at: node.created_at,
+            updated_at: node.updated_at,
+            deleted: node.deleted,
+            expires_at: node.expires_at,
+            deleted_at: node.deleted_at,
+        }
+    }
+}
+
 impl Edge {
     /// Create a new edge with the given parameters
     pub fn new(
@@ -397,6 +559,20 @@ impl Edge {
         Ok(())
     }
 
+    /// The reverse of this edge under [`Relation::inverse`], with the same
+    /// weight and `DerivedInverse` provenance. `None` if the relation has
+    /// no inverse.
+    pub fn inverse(&self) -> Option<Edge> {
+        let relation = self.relation.inverse()?;
+        Some(Edge::new(
+            self.to,
+            self.from,
+            relation,
+            self.weight,
+            EdgeProvenance::DerivedInverse { of: self.id },
+        ))
+    }
+
     /// Update weight and timestamp
     pub fn update_weight(&mut self, new_weight: f32) {
         self.weight = new_weight.clamp(0.0, 1.0);
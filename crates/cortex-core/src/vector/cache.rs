@@ -0,0 +1,257 @@
+use crate::error::Result;
+use crate::types::Embedding;
+use crate::vector::EmbeddingService;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Default number of embeddings kept by `CachingEmbeddingService`.
+pub const DEFAULT_EMBEDDING_CACHE_CAPACITY: usize = 10_000;
+
+/// Hit/miss counters for an embedding cache.
+#[derive(Debug, Clone, Default)]
+pub struct EmbeddingCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub capacity: usize,
+}
+
+impl EmbeddingCacheStats {
+    /// Fraction of lookups served from the cache (0.0 when nothing was looked up).
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// LRU bookkeeping: key → (embedding, last-use tick), plus tick → key for eviction order.
+#[derive(Default)]
+struct LruState {
+    entries: HashMap<u64, (Embedding, u64)>,
+    order: BTreeMap<u64, u64>,
+    tick: u64,
+}
+
+impl LruState {
+    fn get(&mut self, key: u64) -> Option<Embedding> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (embedding, last_used) = self.entries.get_mut(&key)?;
+        self.order.remove(last_used);
+        *last_used = tick;
+        self.order.insert(tick, key);
+        Some(embedding.clone())
+    }
+
+    fn put(&mut self, key: u64, embedding: Embedding, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some((_, old_tick)) = self.entries.insert(key, (embedding, tick)) {
+            self.order.remove(&old_tick);
+        }
+        self.order.insert(tick, key);
+
+        while self.entries.len() > capacity {
+            match self.order.pop_first() {
+                Some((_, oldest)) => {
+                    self.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// `EmbeddingService` decorator that caches embeddings in an LRU keyed by a
+/// hash of the input text.
+///
+/// Re-imports and retries typically embed the exact same `embedding_input`
+/// string again; a hit returns the cached vector without touching the model.
+pub struct CachingEmbeddingService<E: EmbeddingService> {
+    inner: E,
+    capacity: usize,
+    state: Mutex<LruState>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<E: EmbeddingService> CachingEmbeddingService<E> {
+    /// Wrap `inner` with a cache of `DEFAULT_EMBEDDING_CACHE_CAPACITY` entries.
+    pub fn new(inner: E) -> Self {
+        Self::with_capacity(inner, DEFAULT_EMBEDDING_CACHE_CAPACITY)
+    }
+
+    /// Wrap `inner` with a cache holding at most `capacity` embeddings.
+    /// A capacity of 0 disables caching.
+    pub fn with_capacity(inner: E, capacity: usize) -> Self {
+        Self {
+            inner,
+            capacity,
+            state: Mutex::new(LruState::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// The wrapped embedding service.
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    /// Current hit/miss counters and occupancy.
+    pub fn stats(&self) -> EmbeddingCacheStats {
+        EmbeddingCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.state.lock().unwrap().entries.len(),
+            capacity: self.capacity,
+        }
+    }
+
+    /// Drop all cached embeddings (e.g. after a model change).
+    pub fn clear(&self) {
+        *self.state.lock().unwrap() = LruState::default();
+    }
+
+    /// Cache key for an input string. Includes the model name so a swapped
+    /// inner model never serves vectors from a different embedding space.
+    fn key(&self, text: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.inner.model_name().hash(&mut hasher);
+        text.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn lookup(&self, key: u64) -> Option<Embedding> {
+        let hit = self.state.lock().unwrap().get(key);
+        if hit.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        hit
+    }
+
+    fn store(&self, key: u64, embedding: Embedding) {
+        self.state
+            .lock()
+            .unwrap()
+            .put(key, embedding, self.capacity);
+    }
+}
+
+impl<E: EmbeddingService> EmbeddingService for CachingEmbeddingService<E> {
+    fn embed(&self, text: &str) -> Result<Embedding> {
+        let key = self.key(text);
+        if let Some(embedding) = self.lookup(key) {
+            return Ok(embedding);
+        }
+        let embedding = self.inner.embed(text)?;
+        self.store(key, embedding.clone());
+        Ok(embedding)
+    }
+
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Embedding>> {
+        let keys: Vec<u64> = texts.iter().map(|t| self.key(t)).collect();
+        let mut results: Vec<Option<Embedding>> = keys.iter().map(|k| self.lookup(*k)).collect();
+
+        // Only send misses to the model, in one batch.
+        let missing: Vec<usize> = (0..texts.len()).filter(|i| results[*i].is_none()).collect();
+        if !missing.is_empty() {
+            let batch: Vec<String> = missing.iter().map(|i| texts[*i].clone()).collect();
+            let embedded = self.inner.embed_batch(&batch)?;
+            for (i, embedding) in missing.into_iter().zip(embedded) {
+                self.store(keys[i], embedding.clone());
+                results[i] = Some(embedding);
+            }
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    fn dimension(&self) -> usize {
+        self.inner.dimension()
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{CountingEmbedder, KeywordEmbedder};
+
+    fn counting() -> CountingEmbedder<KeywordEmbedder> {
+        CountingEmbedder::new(KeywordEmbedder::new(&["one", "three", "same"]))
+    }
+
+    #[test]
+    fn test_repeated_input_skips_inner_service() {
+        let cache = CachingEmbeddingService::new(counting());
+
+        let first = cache.embed("Fact: same text").unwrap();
+        let second = cache.embed("Fact: same text").unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(cache.inner().calls(), 1);
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert!((stats.hit_rate() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_lru_evicts_least_recently_used() {
+        let cache = CachingEmbeddingService::with_capacity(counting(), 2);
+
+        cache.embed("a").unwrap();
+        cache.embed("bb").unwrap();
+        cache.embed("a").unwrap(); // touch "a" so "bb" becomes the oldest
+        cache.embed("ccc").unwrap(); // evicts "bb"
+
+        assert_eq!(cache.stats().entries, 2);
+        assert_eq!(cache.inner().calls(), 3);
+
+        cache.embed("a").unwrap();
+        assert_eq!(cache.inner().calls(), 3);
+        cache.embed("bb").unwrap();
+        assert_eq!(cache.inner().calls(), 4);
+    }
+
+    #[test]
+    fn test_batch_only_embeds_misses() {
+        let cache = CachingEmbeddingService::new(counting());
+        cache.embed("one").unwrap();
+
+        let texts = vec!["one".to_string(), "three".to_string(), "one".to_string()];
+        let embeddings = cache.embed_batch(&texts).unwrap();
+
+        assert_eq!(embeddings.len(), 3);
+        assert_eq!(embeddings[0], embeddings[2]);
+        assert_eq!(embeddings[1], counting().embed("three").unwrap());
+        // "one" once up front, then only "three" from the batch
+        assert_eq!(cache.inner().calls(), 2);
+    }
+
+    #[test]
+    fn test_zero_capacity_disables_cache() {
+        let cache = CachingEmbeddingService::with_capacity(counting(), 0);
+        cache.embed("x").unwrap();
+        cache.embed("x").unwrap();
+        assert_eq!(cache.inner().calls(), 2);
+        assert_eq!(cache.stats().entries, 0);
+    }
+}
@@ -86,6 +86,67 @@ impl SimilarityConfig {
     }
 }
 
+/// How far graph context reaches from a search hit, and how much a node
+/// counts at each hop.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct GraphProximity {
+    /// Weight of a node `n` hops away is `hop_decay[n - 1]`. Hops past the
+    /// end of the list keep its last value.
+    /// Default: [0.6]
+    pub hop_decay: Vec<f32>,
+
+    /// Hops to expand. Nodes further away get no graph weight.
+    /// Default: 1
+    pub max_depth: u32,
+}
+
+impl Default for GraphProximity {
+    fn default() -> Self {
+        Self {
+            hop_decay: vec![0.6],
+            max_depth: 1,
+        }
+    }
+}
+
+impl GraphProximity {
+    pub fn new(hop_decay: Vec<f32>, max_depth: u32) -> Self {
+        Self {
+            hop_decay,
+            max_depth,
+        }
+    }
+
+    /// Weight of a node `depth` hops away. 1.0 at depth 0, 0.0 beyond
+    /// `max_depth`.
+    pub fn weight(&self, depth: u32) -> f32 {
+        if depth == 0 {
+            return 1.0;
+        }
+        if depth > self.max_depth {
+            return 0.0;
+        }
+        let i = (depth as usize - 1).min(self.hop_decay.len().saturating_sub(1));
+        self.hop_decay.get(i).copied().unwrap_or(0.0)
+    }
+
+    /// Validate the configuration
+    pub fn validate(&self) -> crate::error::Result<()> {
+        if self.max_depth > 0 && self.hop_decay.is_empty() {
+            return Err(crate::error::CortexError::Validation(
+                "hop_decay needs at least one entry when max_depth > 0".to_string(),
+            ));
+        }
+        if self.hop_decay.iter().any(|w| !(0.0..=1.0).contains(w)) {
+            return Err(crate::error::CortexError::Validation(
+                "hop_decay entries must be between 0.0 and 1.0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,4 +193,22 @@ mod tests {
         assert_eq!(config.auto_link_threshold, 1.0);
         assert_eq!(config.dedup_threshold, 0.0);
     }
+
+    #[test]
+    fn test_graph_proximity_weights() {
+        let default = GraphProximity::default();
+        assert_eq!(default.weight(0), 1.0);
+        assert_eq!(default.weight(1), 0.6);
+        assert_eq!(default.weight(2), 0.0);
+        assert!(default.validate().is_ok());
+
+        let deep = GraphProximity::new(vec![0.7, 0.4], 3);
+        assert_eq!(deep.weight(1), 0.7);
+        assert_eq!(deep.weight(2), 0.4);
+        assert_eq!(deep.weight(3), 0.4);
+        assert_eq!(deep.weight(4), 0.0);
+
+        assert!(GraphProximity::new(vec![], 2).validate().is_err());
+        assert!(GraphProximity::new(vec![1.5], 1).validate().is_err());
+    }
 }
@@ -0,0 +1,221 @@
+use crate::error::{CortexError, Result};
+use crate::types::NodeId;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Position just after the last result of a search page: its score when
+/// the page was served, and its id.
+///
+/// Pages are ordered by score (highest first), then node id, so the same
+/// query pages deterministically even when scores tie. The cursor holds no
+/// offset, so nodes stored between pages don't shift it. Serialised as an
+/// opaque string for clients to hand back unchanged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchCursor {
+    score: f32,
+    id: NodeId,
+}
+
+impl SearchCursor {
+    /// Whether a result scored `score` sorts after this cursor.
+    fn precedes(&self, score: f32, id: &NodeId) -> bool {
+        Self::cmp_key(score, id, self.score, &self.id) == Ordering::Greater
+    }
+
+    fn cmp_key(score: f32, id: &NodeId, other_score: f32, other_id: &NodeId) -> Ordering {
+        other_score.total_cmp(&score).then_with(|| id.cmp(other_id))
+    }
+}
+
+impl fmt::Display for SearchCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:08x}{}", self.score.to_bits(), self.id.simple())
+    }
+}
+
+impl FromStr for SearchCursor {
+    type Err = CortexError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || CortexError::Validation(format!("Invalid search cursor: {}", s));
+        if s.len() != 40 || !s.is_ascii() {
+            return Err(invalid());
+        }
+        let score = u32::from_str_radix(&s[..8], 16).map_err(|_| invalid())?;
+        let id = NodeId::parse_str(&s[8..]).map_err(|_| invalid())?;
+        Ok(Self {
+            score: f32::from_bits(score),
+            id,
+        })
+    }
+}
+
+/// Sort `items` by score then id, skip everything up to and including
+/// `cursor`, and return the next `limit` items plus a cursor if more remain.
+pub fn page_by_score<T>(
+    mut items: Vec<T>,
+    key: impl Fn(&T) -> (f32, NodeId),
+    cursor: Option<&SearchCursor>,
+    limit: usize,
+) -> (Vec<T>, Option<SearchCursor>) {
+    items.sort_by(|a, b| {
+        let (sa, ia) = key(a);
+        let (sb, ib) = key(b);
+        SearchCursor::cmp_key(sa, &ia, sb, &ib)
+    });
+    if let Some(c) = cursor {
+        items.retain(|item| {
+            let (score, id) = key(item);
+            c.precedes(score, &id)
+        });
+    }
+
+    let more = items.len() > limit;
+    items.truncate(limit);
+    let next = match items.last() {
+        Some(last) if more => {
+            let (score, id) = key(last);
+            Some(SearchCursor { score, id })
+        }
+        _ => None,
+    };
+    (items, next)
+}
+
+/// Page a search whose candidates come from `search(n)`: the results drawn
+/// from the best `n` hits, and whether there were fewer than `n` hits to
+/// draw from. `n` grows until a full page follows `cursor` or the hits run
+/// out, so results filtered out after the search don't shorten the page.
+pub fn page_search<T, E>(
+    mut search: impl FnMut(usize) -> std::result::Result<(Vec<T>, bool), E>,
+    key: impl Fn(&T) -> (f32, NodeId),
+    cursor: Option<&SearchCursor>,
+    limit: usize,
+) -> std::result::Result<(Vec<T>, Option<SearchCursor>), E> {
+    let mut fetch = limit + 1;
+    loop {
+        let (items, exhausted) = search(fetch)?;
+        let following = match cursor {
+            Some(c) => items
+                .iter()
+                .filter(|item| {
+                    let (score, id) = key(item);
+                    c.precedes(score, &id)
+                })
+                .count(),
+            None => items.len(),
+        };
+        if exhausted || following > limit {
+            return Ok(page_by_score(items, key, cursor, limit));
+        }
+        fetch *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::{HnswIndex, VectorIndex};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_cursor_round_trips_as_string() {
+        let cursor = SearchCursor {
+            score: 0.8125,
+            id: NodeId::now_v7(),
+        };
+        let parsed: SearchCursor = cursor.to_string().parse().unwrap();
+        assert_eq!(parsed, cursor);
+        assert!("not-a-cursor".parse::<SearchCursor>().is_err());
+    }
+
+    #[test]
+    fn test_pages_cover_all_results_with_tied_scores() {
+        // Scores tie in threes, so ordering must fall back to id
+        let items: Vec<(f32, NodeId)> = (0..30)
+            .map(|i| ((i / 3) as f32 * 0.1, NodeId::now_v7()))
+            .collect();
+
+        let mut seen = Vec::new();
+        let mut cursor: Option<SearchCursor> = None;
+        loop {
+            let (page, next) = page_by_score(items.clone(), |i| *i, cursor.as_ref(), 10);
+            seen.extend(page.iter().map(|(_, id)| *id));
+            match next {
+                Some(next) => cursor = Some(next.to_string().parse().unwrap()),
+                None => break,
+            }
+        }
+
+        assert_eq!(seen.len(), 30);
+        assert_eq!(seen.iter().collect::<HashSet<_>>().len(), 30);
+    }
+
+    #[test]
+    fn test_pages_through_vector_index() {
+        let mut index = HnswIndex::new(3);
+        for i in 0..30 {
+            let x = i as f32 / 30.0;
+            index
+                .insert(NodeId::now_v7(), &vec![1.0, x, 1.0 - x])
+                .unwrap();
+        }
+        index.rebuild().unwrap();
+        let query = vec![1.0, 0.5, 0.5];
+
+        let mut pages = Vec::new();
+        let mut cursor: Option<SearchCursor> = None;
+        loop {
+            let (page, next) = page_search(
+                |n| {
+                    let hits = index.search(&query, n, None)?;
+                    let exhausted = hits.len() < n;
+                    Ok::<_, crate::CortexError>((hits, exhausted))
+                },
+                |r| (r.score, r.node_id),
+                cursor.as_ref(),
+                10,
+            )
+            .unwrap();
+            pages.push(page);
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        assert_eq!(pages.len(), 3);
+        assert!(pages.iter().all(|p| p.len() == 10));
+        let ids: HashSet<NodeId> = pages.iter().flatten().map(|r| r.node_id).collect();
+        assert_eq!(ids.len(), 30);
+        // Page boundaries keep score order
+        let scores: Vec<f32> = pages.iter().flatten().map(|r| r.score).collect();
+        assert!(scores.windows(2).all(|w| w[0] >= w[1]));
+    }
+
+    #[test]
+    fn test_filtered_and_inserted_results_do_not_shorten_pages() {
+        // Every other hit is filtered out after the search
+        let mut items: Vec<(f32, NodeId, bool)> = (0..40)
+            .map(|i| (1.0 - i as f32 / 100.0, NodeId::now_v7(), i % 2 == 0))
+            .collect();
+        let search = |items: &[(f32, NodeId, bool)], n: usize| {
+            let hits = &items[..n.min(items.len())];
+            let kept = hits.iter().filter(|i| i.2).copied().collect();
+            Ok::<_, crate::CortexError>((kept, hits.len() < n))
+        };
+
+        let (first, cursor) = page_search(|n| search(&items, n), |i| (i.0, i.1), None, 5).unwrap();
+        assert_eq!(first.len(), 5);
+
+        // Better hits stored between pages are not counted against the next
+        for _ in 0..10 {
+            items.insert(0, (2.0, NodeId::now_v7(), true));
+        }
+        let (second, _) =
+            page_search(|n| search(&items, n), |i| (i.0, i.1), cursor.as_ref(), 5).unwrap();
+        assert_eq!(second.len(), 5);
+        assert!(second.iter().all(|i| i.0 < first[4].0));
+    }
+}
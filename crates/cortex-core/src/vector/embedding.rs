@@ -58,6 +58,7 @@ impl FastEmbedService {
 }
 
 impl EmbeddingService for FastEmbedService {
+    #[tracing::instrument(name = "embedding.embed", skip_all, fields(model = %self.model_name))]
     fn embed(&self, text: &str) -> Result<Embedding> {
         let embeddings = self
             .model
@@ -70,6 +71,11 @@ impl EmbeddingService for FastEmbedService {
             .ok_or_else(|| CortexError::Validation("No embedding generated".to_string()))
     }
 
+    #[tracing::instrument(
+        name = "embedding.embed_batch",
+        skip_all,
+        fields(model = %self.model_name, texts = texts.len())
+    )]
     fn embed_batch(&self, texts: &[String]) -> Result<Vec<Embedding>> {
         let embeddings = self
             .model
@@ -94,6 +100,21 @@ impl Default for FastEmbedService {
     }
 }
 
+impl<E: EmbeddingService + ?Sized> EmbeddingService for Box<E> {
+    fn embed(&self, text: &str) -> Result<Embedding> {
+        (**self).embed(text)
+    }
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Embedding>> {
+        (**self).embed_batch(texts)
+    }
+    fn dimension(&self) -> usize {
+        (**self).dimension()
+    }
+    fn model_name(&self) -> &str {
+        (**self).model_name()
+    }
+}
+
 impl<E: EmbeddingService> EmbeddingService for std::sync::Arc<E> {
     fn embed(&self, text: &str) -> Result<Embedding> {
         (**self).embed(text)
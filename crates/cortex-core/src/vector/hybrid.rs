@@ -2,7 +2,7 @@ use crate::error::Result;
 use crate::graph::{GraphEngine, TraversalDirection, TraversalRequest};
 use crate::storage::Storage;
 use crate::types::{Node, NodeId, NodeKind};
-use crate::vector::{EmbeddingService, VectorFilter, VectorIndex};
+use crate::vector::{EmbeddingService, GraphProximity, VectorFilter, VectorIndex};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -16,6 +16,13 @@ pub struct HybridQuery {
     /// Graph proximity to anchors boosts ranking.
     pub anchors: Vec<NodeId>,
 
+    /// With no `anchors`, anchor on the top vector hits instead of falling
+    /// back to pure vector ranking.
+    pub auto_anchor: bool,
+
+    /// How many top vector hits become anchors in auto-anchor mode.
+    pub auto_anchor_count: usize,
+
     /// How much to weight vector similarity vs graph proximity.
     /// 0.0 = pure graph, 1.0 = pure vector. Default 0.7.
     pub vector_weight: f32,
@@ -29,6 +36,17 @@ pub struct HybridQuery {
     /// Maximum graph distance from anchors to consider.
     /// Nodes beyond this distance get zero graph proximity score.
     pub max_anchor_depth: u32,
+
+    /// Per-hop proximity weights. When set, its `max_depth` replaces
+    /// `max_anchor_depth`; when unset a node `d` hops from an anchor
+    /// scores `1 / (1 + d)`.
+    pub proximity: Option<GraphProximity>,
+
+    /// Also return nodes whose `expires_at` has passed.
+    pub include_expired: bool,
+
+    /// Also return soft-deleted nodes.
+    pub include_deleted: bool,
 }
 
 impl Default for HybridQuery {
@@ -36,10 +54,15 @@ impl Default for HybridQuery {
         Self {
             query_text: String::new(),
             anchors: Vec::new(),
+            auto_anchor: false,
+            auto_anchor_count: 3,
             vector_weight: 0.7,
             limit: 10,
             kind_filter: None,
             max_anchor_depth: 3,
+            proximity: None,
+            include_expired: false,
+            include_deleted: false,
         }
     }
 }
@@ -57,6 +80,13 @@ impl HybridQuery {
         self
     }
 
+    /// Anchor on the top `count` vector hits when no anchors are given.
+    pub fn with_auto_anchor(mut self, count: usize) -> Self {
+        self.auto_anchor = true;
+        self.auto_anchor_count = count;
+        self
+    }
+
     pub fn with_vector_weight(mut self, weight: f32) -> Self {
         self.vector_weight = weight.clamp(0.0, 1.0);
         self
@@ -76,6 +106,22 @@ impl HybridQuery {
         self.max_anchor_depth = depth;
         self
     }
+
+    /// Score graph proximity with per-hop weights instead of `1 / (1 + d)`.
+    pub fn with_proximity(mut self, proximity: GraphProximity) -> Self {
+        self.proximity = Some(proximity);
+        self
+    }
+
+    pub fn including_expired(mut self) -> Self {
+        self.include_expired = true;
+        self
+    }
+
+    pub fn including_deleted(mut self) -> Self {
+        self.include_deleted = true;
+        self
+    }
 }
 
 /// Result from hybrid search
@@ -126,11 +172,40 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> HybridSear
             vector_filter.as_ref(),
         )?;
 
-        // 3. If no anchors, return pure vector results
-        if query.anchors.is_empty() {
+        let now = chrono::Utc::now();
+        let load = |id: NodeId| -> Result<Option<Node>> {
+            Ok(self
+                .storage
+                .get_node(id)?
+                .filter(|n| query.include_expired || !n.is_expired_at(now))
+                .filter(|n| query.include_deleted || !n.deleted))
+        };
+
+        // 3. Pick anchors: the caller's, or in auto-anchor mode the best
+        //    vector hits that survive the expiry and tombstone checks
+        let anchors = if query.anchors.is_empty() && query.auto_anchor {
+            let mut picked = Vec::new();
+            for vr in &vector_results {
+                if picked.len() == query.auto_anchor_count {
+                    break;
+                }
+                if load(vr.node_id)?.is_some() {
+                    picked.push(vr.node_id);
+                }
+            }
+            picked
+        } else {
+            query.anchors.clone()
+        };
+
+        // 4. If no anchors, return pure vector results
+        if anchors.is_empty() {
             let mut results = Vec::new();
-            for vr in vector_results.into_iter().take(query.limit) {
-                if let Some(node) = self.storage.get_node(vr.node_id)? {
+            for vr in vector_results {
+                if results.len() == query.limit {
+                    break;
+                }
+                if let Some(node) = load(vr.node_id)? {
                     results.push(HybridResult {
                         node,
                         vector_score: vr.score,
@@ -143,14 +218,19 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> HybridSear
             return Ok(results);
         }
 
-        // 4. Compute graph proximity scores
-        let graph_scores = self.compute_graph_proximity(&query.anchors, query.max_anchor_depth)?;
+        // 5. Compute graph proximity scores
+        let graph_scores = self.compute_graph_proximity(
+            &anchors,
+            query.proximity.as_ref(),
+            query.max_anchor_depth,
+            query.include_expired,
+        )?;
 
-        // 5. Combine scores and rank
+        // 6. Combine scores and rank
         let mut hybrid_results = Vec::new();
 
         for vr in vector_results {
-            if let Some(node) = self.storage.get_node(vr.node_id)? {
+            if let Some(node) = load(vr.node_id)? {
                 let graph_score = graph_scores
                     .get(&vr.node_id)
                     .map(|(score, _, _)| *score)
@@ -189,9 +269,15 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> HybridSear
     fn compute_graph_proximity(
         &self,
         anchors: &[NodeId],
-        max_depth: u32,
+        proximity: Option<&GraphProximity>,
+        max_anchor_depth: u32,
+        include_expired: bool,
     ) -> Result<HashMap<NodeId, ProximityEntry>> {
         let mut proximity_scores = HashMap::new();
+        let max_depth = proximity.map_or(max_anchor_depth, |p| p.max_depth);
+        if max_depth == 0 {
+            return Ok(proximity_scores);
+        }
 
         for anchor_id in anchors {
             // Traverse from each anchor
@@ -200,12 +286,16 @@ impl<S: Storage, E: EmbeddingService, V: VectorIndex, G: GraphEngine> HybridSear
                 max_depth: Some(max_depth),
                 direction: TraversalDirection::Both,
                 include_start: false,
+                include_expired,
                 ..Default::default()
             })?;
 
-            // Score based on depth: score = 1.0 / (1.0 + depth)
+            // Score based on depth: the configured hop weight, or 1.0 / (1.0 + depth)
             for (node_id, &depth) in &neighborhood.depths {
-                let score = 1.0 / (1.0 + depth as f32);
+                let score = match proximity {
+                    Some(p) => p.weight(depth),
+                    None => 1.0 / (1.0 + depth as f32),
+                };
 
                 // Keep the highest score (shortest path) to any anchor
                 proximity_scores
@@ -230,6 +320,7 @@ mod tests {
     use super::*;
     use crate::graph::GraphEngineImpl;
     use crate::storage::RedbStorage;
+    use crate::testing::{FixedEmbedder, KeywordEmbedder};
     use crate::types::{Edge, EdgeProvenance, Relation, Source};
     use crate::vector::{FastEmbedService, HnswIndex};
     use std::sync::Arc;
@@ -402,4 +493,299 @@ mod tests {
             .unwrap();
         assert!(connected_result.graph_score > 0.0);
     }
+
+    /// "alpha" nodes on one axis, everything else on another.
+    fn alpha_axis() -> KeywordEmbedder {
+        KeywordEmbedder::new(&["alpha"])
+    }
+
+    #[test]
+    fn test_expired_nodes_excluded_unless_included() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(temp_dir.path().join("expiry.redb")).unwrap());
+
+        let fact = |title: &str| {
+            Node::new(
+                NodeKind::new("fact").unwrap(),
+                title.to_string(),
+                String::new(),
+                Source {
+                    agent: "test".to_string(),
+                    session: None,
+                    channel: None,
+                },
+                0.5,
+            )
+        };
+        let live = fact("alpha still holds");
+        let mut expired = fact("alpha freeze until Friday");
+        expired.expires_at = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        let mut later = fact("alpha freeze next quarter");
+        later.expires_at = Some(chrono::Utc::now() + chrono::Duration::days(30));
+
+        let mut vector_index = HnswIndex::new(2);
+        for node in [&live, &expired, &later] {
+            storage.put_node(node).unwrap();
+            vector_index
+                .insert(node.id, &alpha_axis().embed(&node.data.title).unwrap())
+                .unwrap();
+        }
+        let hybrid = HybridSearch::new(
+            storage.clone(),
+            alpha_axis(),
+            vector_index,
+            GraphEngineImpl::new(storage.clone()),
+        );
+
+        let ids = |query: HybridQuery| -> Vec<NodeId> {
+            let mut ids: Vec<NodeId> = hybrid
+                .search(query)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.node.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        let mut unexpired = vec![live.id, later.id];
+        unexpired.sort();
+        assert_eq!(ids(HybridQuery::new("alpha".into())), unexpired);
+        // An expired node doesn't take up a slot under the limit
+        assert_eq!(
+            ids(HybridQuery::new("alpha".into()).with_limit(2)),
+            unexpired
+        );
+
+        let mut all = vec![live.id, expired.id, later.id];
+        all.sort();
+        assert_eq!(
+            ids(HybridQuery::new("alpha".into()).including_expired()),
+            all
+        );
+    }
+
+    #[test]
+    fn test_deleted_nodes_hidden_until_restored() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(temp_dir.path().join("restore.redb")).unwrap());
+
+        let node = Node::new(
+            NodeKind::new("fact").unwrap(),
+            "alpha was removed by mistake".to_string(),
+            String::new(),
+            Source {
+                agent: "test".to_string(),
+                session: None,
+                channel: None,
+            },
+            0.5,
+        );
+        storage.put_node(&node).unwrap();
+        let mut vector_index = HnswIndex::new(2);
+        vector_index
+            .insert(node.id, &alpha_axis().embed(&node.data.title).unwrap())
+            .unwrap();
+        let hybrid = HybridSearch::new(
+            storage.clone(),
+            alpha_axis(),
+            vector_index,
+            GraphEngineImpl::new(storage.clone()),
+        );
+        let ids = |query: HybridQuery| -> Vec<NodeId> {
+            hybrid
+                .search(query)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.node.id)
+                .collect()
+        };
+        assert_eq!(ids(HybridQuery::new("alpha".into())), vec![node.id]);
+
+        storage.delete_node(node.id).unwrap();
+        assert!(ids(HybridQuery::new("alpha".into())).is_empty());
+        assert_eq!(
+            ids(HybridQuery::new("alpha".into()).including_deleted()),
+            vec![node.id]
+        );
+
+        let restored = storage.restore_node(node.id).unwrap();
+        assert!(!restored.deleted);
+        assert_eq!(restored.deleted_at, None);
+        assert_eq!(ids(HybridQuery::new("alpha".into())), vec![node.id]);
+    }
+
+    #[test]
+    fn test_auto_anchor_matches_manual_anchor() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(temp_dir.path().join("auto.redb")).unwrap());
+        let mut vector_index = HnswIndex::new(2);
+
+        // A unit vector with this cosine similarity to the query
+        let mut add = |title: String, similarity: f32| {
+            let node = Node::new(
+                NodeKind::new("fact").unwrap(),
+                title,
+                String::new(),
+                Source {
+                    agent: "test".to_string(),
+                    session: None,
+                    channel: None,
+                },
+                0.5,
+            );
+            storage.put_node(&node).unwrap();
+            vector_index
+                .insert(
+                    node.id,
+                    &vec![similarity, (1.0 - similarity * similarity).sqrt()],
+                )
+                .unwrap();
+            node.id
+        };
+
+        // Two star-shaped clusters. The hub of the first is the best vector
+        // hit, but its members are less similar than the unlinked noise.
+        let hub_a = add("hub a".into(), 1.0);
+        let members_a: Vec<NodeId> = (0..3).map(|i| add(format!("a{}", i), 0.7)).collect();
+        let hub_b = add("hub b".into(), 0.3);
+        let members_b: Vec<NodeId> = (0..3).map(|i| add(format!("b{}", i), 0.3)).collect();
+        for i in 0..6 {
+            add(format!("noise {}", i), 0.9);
+        }
+        for (hub, members) in [(hub_a, &members_a), (hub_b, &members_b)] {
+            for member in members {
+                storage
+                    .put_edge(&Edge::new(
+                        hub,
+                        *member,
+                        Relation::new("related_to").unwrap(),
+                        1.0,
+                        EdgeProvenance::Manual {
+                            created_by: "test".to_string(),
+                        },
+                    ))
+                    .unwrap();
+            }
+        }
+
+        let hybrid = HybridSearch::new(
+            storage.clone(),
+            FixedEmbedder(vec![1.0, 0.0]),
+            vector_index,
+            GraphEngineImpl::new(storage.clone()),
+        );
+        let query = || {
+            HybridQuery::new("cluster".into())
+                .with_vector_weight(0.5)
+                .with_limit(4)
+        };
+
+        let auto = hybrid.search(query().with_auto_anchor(1)).unwrap();
+        let manual = hybrid.search(query().with_anchors(vec![hub_a])).unwrap();
+        let ids = |results: &[HybridResult]| results.iter().map(|r| r.node.id).collect::<Vec<_>>();
+        assert_eq!(ids(&auto), ids(&manual));
+
+        // The hub's cluster outranks the closer but unrelated noise
+        let mut top: Vec<NodeId> = ids(&auto[..3]);
+        top.sort();
+        let mut expected = members_a.clone();
+        expected.sort();
+        assert_eq!(top, expected);
+        assert!(auto[..3]
+            .iter()
+            .all(|r| r.nearest_anchor == Some((hub_a, 1))));
+        assert_eq!(auto[3].node.id, hub_a);
+
+        // Without anchoring, the noise wins
+        let plain = hybrid.search(query()).unwrap();
+        assert_eq!(plain[0].node.id, hub_a);
+        assert!(plain.iter().all(|r| !members_a.contains(&r.node.id)));
+    }
+
+    #[test]
+    fn test_proximity_depth_and_hop_decay() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(temp_dir.path().join("hops.redb")).unwrap());
+        let mut vector_index = HnswIndex::new(2);
+
+        // Every node equally similar to the query, so only the graph ranks them
+        let mut add = |title: &str| {
+            let node = Node::new(
+                NodeKind::new("fact").unwrap(),
+                title.to_string(),
+                String::new(),
+                Source {
+                    agent: "test".to_string(),
+                    session: None,
+                    channel: None,
+                },
+                0.5,
+            );
+            storage.put_node(&node).unwrap();
+            vector_index.insert(node.id, &vec![0.6, 0.8]).unwrap();
+            node.id
+        };
+        let anchor = add("anchor");
+        let one_hop = add("one hop");
+        let two_hops = add("two hops");
+        let unlinked = add("unlinked");
+        for (from, to) in [(anchor, one_hop), (one_hop, two_hops)] {
+            storage
+                .put_edge(&Edge::new(
+                    from,
+                    to,
+                    Relation::new("related_to").unwrap(),
+                    1.0,
+                    EdgeProvenance::Manual {
+                        created_by: "test".to_string(),
+                    },
+                ))
+                .unwrap();
+        }
+
+        let hybrid = HybridSearch::new(
+            storage.clone(),
+            FixedEmbedder(vec![1.0, 0.0]),
+            vector_index,
+            GraphEngineImpl::new(storage.clone()),
+        );
+        let search = |proximity: GraphProximity| {
+            hybrid
+                .search(
+                    HybridQuery::new("chain".into())
+                        .with_anchors(vec![anchor])
+                        .with_vector_weight(0.5)
+                        .with_proximity(proximity),
+                )
+                .unwrap()
+        };
+        let graph_score = |results: &[HybridResult], id: NodeId| {
+            results
+                .iter()
+                .find(|r| r.node.id == id)
+                .unwrap()
+                .graph_score
+        };
+
+        // Default: one hop at 0.6, nothing further
+        let shallow = search(GraphProximity::default());
+        assert!((graph_score(&shallow, one_hop) - 0.6).abs() < 1e-6);
+        assert_eq!(graph_score(&shallow, two_hops), 0.0);
+
+        // A deeper expansion reaches the second hop, weighted below the first
+        let deep = search(GraphProximity::new(vec![0.7, 0.4], 2));
+        assert!((graph_score(&deep, one_hop) - 0.7).abs() < 1e-6);
+        assert!((graph_score(&deep, two_hops) - 0.4).abs() < 1e-6);
+        assert_eq!(
+            deep.iter()
+                .find(|r| r.node.id == two_hops)
+                .unwrap()
+                .nearest_anchor,
+            Some((anchor, 2))
+        );
+        let rank = |id: NodeId| deep.iter().position(|r| r.node.id == id).unwrap();
+        assert!(rank(one_hop) < rank(two_hops));
+        assert!(rank(two_hops) < rank(unlinked));
+    }
 }
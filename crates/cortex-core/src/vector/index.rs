@@ -1,5 +1,5 @@
 use crate::error::{CortexError, Result};
-use crate::types::{Embedding, NodeId, NodeKind};
+use crate::types::{Embedding, Node, NodeId, NodeKind};
 use instant_distance::{Builder, HnswMap, Point, Search};
 use rayon::prelude::*;
 use std::collections::HashMap;
@@ -14,7 +14,12 @@ pub struct SimilarityResult {
     pub distance: f32, // 1.0 - score
 }
 
-/// Filter for vector searches
+/// Filter for vector searches.
+///
+/// Checked against what the index recorded for each node when it was
+/// inserted with [`VectorIndex::insert_node`]. Vectors inserted with plain
+/// `insert` carry no kind, tags or importance, so they never match a filter
+/// on those.
 #[derive(Debug, Clone, Default)]
 pub struct VectorFilter {
     /// Only search within these node kinds.
@@ -23,6 +28,10 @@ pub struct VectorFilter {
     pub exclude: Option<Vec<NodeId>>,
     /// Only include nodes from this agent.
     pub source_agent: Option<String>,
+    /// Only include nodes carrying every one of these tags.
+    pub tags: Option<Vec<String>>,
+    /// Only include nodes at least this important.
+    pub min_importance: Option<f32>,
 }
 
 impl VectorFilter {
@@ -44,6 +53,25 @@ impl VectorFilter {
         self.source_agent = Some(agent);
         self
     }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    pub fn with_min_importance(mut self, min_importance: f32) -> Self {
+        self.min_importance = Some(min_importance);
+        self
+    }
+
+    /// True when the filter lets every vector through.
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_none()
+            && self.exclude.as_ref().is_none_or(|e| e.is_empty())
+            && self.source_agent.is_none()
+            && self.tags.as_ref().is_none_or(|t| t.is_empty())
+            && self.min_importance.is_none()
+    }
 }
 
 /// Trait for vector similarity search
@@ -51,6 +79,20 @@ pub trait VectorIndex: Send + Sync {
     /// Add a vector with associated node ID.
     fn insert(&mut self, id: NodeId, embedding: &Embedding) -> Result<()>;
 
+    /// Add a node's vector along with the kind, tags, source agent and
+    /// importance a [`VectorFilter`] checks. Re-insert after those change.
+    fn insert_node(&mut self, node: &Node, embedding: &Embedding) -> Result<()> {
+        self.insert(node.id, embedding)
+    }
+
+    /// Refresh the kind, tags, source agent and importance a
+    /// [`VectorFilter`] checks for an indexed node, keeping its vector.
+    /// Call after changing those in place. Does nothing for a node that
+    /// isn't indexed.
+    fn update_metadata(&mut self, node: &Node) {
+        let _ = node;
+    }
+
     /// Remove a vector.
     fn remove(&mut self, id: NodeId) -> Result<()>;
 
@@ -86,6 +128,33 @@ pub trait VectorIndex: Send + Sync {
         self.len() == 0
     }
 
+    /// Stored embedding for a node, if it is indexed.
+    fn get_embedding(&self, id: NodeId) -> Option<Embedding> {
+        let _ = id;
+        None
+    }
+
+    /// K nearest neighbours re-ranked with Maximal Marginal Relevance.
+    ///
+    /// `diversity` is the MMR trade-off in `[0.0, 1.0]`: `0.0` is identical to
+    /// `search`, higher values penalise candidates similar to results that
+    /// were already selected.
+    fn search_diverse(
+        &self,
+        query: &Embedding,
+        k: usize,
+        filter: Option<&VectorFilter>,
+        diversity: f32,
+    ) -> Result<Vec<SimilarityResult>> {
+        if diversity <= 0.0 {
+            return self.search(query, k, filter);
+        }
+        let candidates = self.search(query, k * MMR_CANDIDATE_MULTIPLIER, filter)?;
+        Ok(mmr_rerank(candidates, k, diversity, |id| {
+            self.get_embedding(id)
+        }))
+    }
+
     /// Rebuild the index from scratch (after bulk inserts).
     fn rebuild(&mut self) -> Result<()>;
 
@@ -98,6 +167,61 @@ pub trait VectorIndex: Send + Sync {
         Self: Sized;
 }
 
+/// How many candidates `search_diverse` pulls per requested result before re-ranking.
+pub const MMR_CANDIDATE_MULTIPLIER: usize = 4;
+
+/// Re-rank candidates with Maximal Marginal Relevance.
+///
+/// Greedily picks up to `k` results maximising
+/// `(1 - diversity) × score - diversity × max_sim(candidate, selected)`,
+/// where `score` is each candidate's relevance and similarity between
+/// candidates is computed from `embedding_of`. Candidates without an
+/// embedding are treated as dissimilar to everything. With `diversity == 0.0`
+/// this is a plain top-k by score.
+pub fn mmr_rerank(
+    candidates: Vec<SimilarityResult>,
+    k: usize,
+    diversity: f32,
+    embedding_of: impl Fn(NodeId) -> Option<Embedding>,
+) -> Vec<SimilarityResult> {
+    let diversity = diversity.clamp(0.0, 1.0);
+    let mut remaining: Vec<(SimilarityResult, Option<Embedding>)> = candidates
+        .into_iter()
+        .map(|c| {
+            let emb = embedding_of(c.node_id);
+            (c, emb)
+        })
+        .collect();
+    let mut selected: Vec<(SimilarityResult, Option<Embedding>)> = Vec::new();
+
+    while selected.len() < k && !remaining.is_empty() {
+        let mut best_idx = 0;
+        let mut best_mmr = f32::NEG_INFINITY;
+        for (i, (cand, cand_emb)) in remaining.iter().enumerate() {
+            let redundancy = match cand_emb {
+                Some(ce) => selected
+                    .iter()
+                    .filter_map(|(_, se)| se.as_ref())
+                    .map(|se| cosine_similarity(ce, se))
+                    .fold(0.0_f32, f32::max),
+                None => 0.0,
+            };
+            let mmr = (1.0 - diversity) * cand.score - diversity * redundancy;
+            if mmr > best_mmr {
+                best_mmr = mmr;
+                best_idx = i;
+            }
+        }
+        selected.push(remaining.remove(best_idx));
+    }
+
+    selected.into_iter().map(|(r, _)| r).collect()
+}
+
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    1.0 - EmbeddingPoint(a.to_vec()).distance(&EmbeddingPoint(b.to_vec()))
+}
+
 /// Wrapper that implements VectorIndex for Arc<RwLock<V>>.
 /// Allows using a shared, mutex-guarded index (e.g. from a gRPC service)
 /// with HybridSearch without requiring Clone on the underlying index.
@@ -113,6 +237,12 @@ impl<V: VectorIndex> VectorIndex for RwLockVectorIndex<V> {
     fn insert(&mut self, id: NodeId, embedding: &Embedding) -> Result<()> {
         self.0.write().unwrap().insert(id, embedding)
     }
+    fn insert_node(&mut self, node: &Node, embedding: &Embedding) -> Result<()> {
+        self.0.write().unwrap().insert_node(node, embedding)
+    }
+    fn update_metadata(&mut self, node: &Node) {
+        self.0.write().unwrap().update_metadata(node)
+    }
     fn remove(&mut self, id: NodeId) -> Result<()> {
         self.0.write().unwrap().remove(id)
     }
@@ -146,6 +276,9 @@ impl<V: VectorIndex> VectorIndex for RwLockVectorIndex<V> {
     fn len(&self) -> usize {
         self.0.read().unwrap().len()
     }
+    fn get_embedding(&self, id: NodeId) -> Option<Embedding> {
+        self.0.read().unwrap().get_embedding(id)
+    }
     fn rebuild(&mut self) -> Result<()> {
         self.0.write().unwrap().rebuild()
     }
@@ -197,6 +330,19 @@ pub struct HnswIndex {
 struct NodeMetadata {
     kind: NodeKind,
     source_agent: String,
+    tags: Vec<String>,
+    importance: f32,
+}
+
+impl From<&Node> for NodeMetadata {
+    fn from(node: &Node) -> Self {
+        Self {
+            kind: node.kind.clone(),
+            source_agent: node.source.agent.clone(),
+            tags: node.data.tags.clone(),
+            importance: node.importance,
+        }
+    }
 }
 
 impl HnswIndex {
@@ -215,10 +361,58 @@ impl HnswIndex {
         Self::new(dimension)
     }
 
-    /// Set metadata for a node
+    /// Build an index from the stored embeddings of `nodes` on a pool of
+    /// `threads` worker threads (`0` = one per core, `1` = serial).
+    ///
+    /// Embeddings and their filter metadata are checked and collected on
+    /// the pool, then the HNSW graph is built once; instant-distance's
+    /// builder runs on the installed pool too, so `threads` bounds the whole
+    /// build. Nodes without an embedding, or whose dimension doesn't match,
+    /// are left out; compare `len()` with the input to count them.
+    pub fn build_parallel(dimension: usize, nodes: Vec<Node>, threads: usize) -> Result<Self> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_err(|e| CortexError::Validation(format!("Failed to start index pool: {}", e)))?;
+
+        pool.install(|| {
+            let (vectors, metadata): (HashMap<_, _>, HashMap<_, _>) = nodes
+                .into_par_iter()
+                .filter_map(|mut node| {
+                    let embedding = node.embedding.take()?;
+                    let meta = NodeMetadata::from(&node);
+                    (embedding.len() == dimension)
+                        .then_some(((node.id, embedding), (node.id, meta)))
+                })
+                .unzip();
+            let mut index = Self {
+                index: None,
+                vectors,
+                metadata,
+                dimension,
+            };
+            index.rebuild()?;
+            Ok(index)
+        })
+    }
+
+    /// IDs of every node in the index.
+    pub(crate) fn ids(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.vectors.keys().copied()
+    }
+
+    /// Set the kind and source agent filters see for a node. Tags and
+    /// importance are left as they were (none and 0.0 for a new entry); use
+    /// `insert_node` to record everything.
     pub fn set_metadata(&mut self, id: NodeId, kind: NodeKind, source_agent: String) {
-        self.metadata
-            .insert(id, NodeMetadata { kind, source_agent });
+        let meta = self.metadata.entry(id).or_insert_with(|| NodeMetadata {
+            kind: kind.clone(),
+            source_agent: source_agent.clone(),
+            tags: Vec::new(),
+            importance: 0.0,
+        });
+        meta.kind = kind;
+        meta.source_agent = source_agent;
     }
 
     /// Check if a result matches the filter
@@ -230,20 +424,38 @@ impl HnswIndex {
             }
         }
 
-        // If we have metadata for this node, check filters
-        if let Some(meta) = self.metadata.get(id) {
-            // Check kind filter
-            if let Some(ref kinds) = filter.kinds {
-                if !kinds.contains(&meta.kind) {
-                    return false;
-                }
+        let needs_metadata = filter.kinds.is_some()
+            || filter.source_agent.is_some()
+            || filter.tags.is_some()
+            || filter.min_importance.is_some();
+        if !needs_metadata {
+            return true;
+        }
+        let Some(meta) = self.metadata.get(id) else {
+            return false;
+        };
+
+        if let Some(ref kinds) = filter.kinds {
+            if !kinds.contains(&meta.kind) {
+                return false;
             }
+        }
 
-            // Check source agent filter
-            if let Some(ref agent) = filter.source_agent {
-                if meta.source_agent != *agent {
-                    return false;
-                }
+        if let Some(ref agent) = filter.source_agent {
+            if meta.source_agent != *agent {
+                return false;
+            }
+        }
+
+        if let Some(ref tags) = filter.tags {
+            if !tags.iter().all(|t| meta.tags.contains(t)) {
+                return false;
+            }
+        }
+
+        if let Some(min_importance) = filter.min_importance {
+            if meta.importance < min_importance {
+                return false;
             }
         }
 
@@ -255,6 +467,70 @@ impl HnswIndex {
         (1.0 - distance).clamp(0.0, 1.0)
     }
 
+    /// Top-`k` nearest neighbours from the HNSW graph, or an exact scan
+    /// when the graph isn't built or the filter is too selective.
+    fn search_graph(
+        &self,
+        query: &Embedding,
+        k: usize,
+        filter: Option<&VectorFilter>,
+    ) -> Result<Vec<SimilarityResult>> {
+        if self.vectors.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Auto-rebuild if index doesn't exist yet
+        // Note: this is a read-path rebuild. For mutable self, caller should
+        // use rebuild() explicitly. We use a fallback brute-force search.
+        if self.index.is_none() {
+            return self.brute_force_search(query, k, filter);
+        }
+
+        let index = self.index.as_ref().unwrap();
+        let query_point = EmbeddingPoint(query.clone());
+        let filter = filter.filter(|f| !f.is_empty());
+
+        let mut search = Search::default();
+        let results = index.search(&query_point, &mut search);
+
+        let mut filtered_results = Vec::new();
+
+        // The filter is checked against the metadata side table as each
+        // candidate comes off the graph, so rejected nodes cost no distance
+        // computation beyond the search itself.
+        for item in results {
+            let node_id = *item.value;
+            let distance = item.distance;
+
+            if let Some(f) = filter {
+                if !self.matches_filter(&node_id, f) {
+                    continue;
+                }
+            }
+
+            filtered_results.push(SimilarityResult {
+                node_id,
+                score: Self::distance_to_similarity(distance),
+                distance,
+            });
+
+            if filtered_results.len() >= k {
+                break;
+            }
+        }
+
+        // A selective filter (a rare kind) can reject most of the graph's
+        // candidates. Then score every matching vector exactly, so the
+        // result still holds min(k, matches) nodes.
+        if let Some(f) = filter {
+            if filtered_results.len() < k {
+                return self.brute_force_search(query, k, Some(f));
+            }
+        }
+
+        Ok(filtered_results)
+    }
+
     /// Brute-force fallback search when HNSW index hasn't been built yet
     fn brute_force_search(
         &self,
@@ -266,17 +542,11 @@ impl HnswIndex {
         let mut results: Vec<SimilarityResult> = self
             .vectors
             .iter()
+            .filter(|(id, _)| filter.is_none_or(|f| self.matches_filter(id, f)))
             .map(|(id, vec)| {
                 let distance = query_point.distance(&EmbeddingPoint(vec.clone()));
                 (*id, distance)
             })
-            .filter(|(id, _)| {
-                if let Some(f) = filter {
-                    self.matches_filter(id, f)
-                } else {
-                    true
-                }
-            })
             .map(|(id, distance)| SimilarityResult {
                 node_id: id,
                 score: Self::distance_to_similarity(distance),
@@ -313,6 +583,18 @@ impl VectorIndex for HnswIndex {
         Ok(())
     }
 
+    fn insert_node(&mut self, node: &Node, embedding: &Embedding) -> Result<()> {
+        self.insert(node.id, embedding)?;
+        self.metadata.insert(node.id, NodeMetadata::from(node));
+        Ok(())
+    }
+
+    fn update_metadata(&mut self, node: &Node) {
+        if self.vectors.contains_key(&node.id) {
+            self.metadata.insert(node.id, NodeMetadata::from(node));
+        }
+    }
+
     fn remove(&mut self, id: NodeId) -> Result<()> {
         self.vectors.remove(&id);
         self.metadata.remove(&id);
@@ -322,55 +604,25 @@ impl VectorIndex for HnswIndex {
         Ok(())
     }
 
+    #[tracing::instrument(
+        name = "vector.search",
+        skip_all,
+        fields(
+            k = k,
+            vectors = self.vectors.len(),
+            filtered = filter.is_some(),
+            results = tracing::field::Empty,
+        )
+    )]
     fn search(
         &self,
         query: &Embedding,
         k: usize,
         filter: Option<&VectorFilter>,
     ) -> Result<Vec<SimilarityResult>> {
-        if self.vectors.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        // Auto-rebuild if index doesn't exist yet
-        // Note: this is a read-path rebuild. For mutable self, caller should
-        // use rebuild() explicitly. We use a fallback brute-force search.
-        if self.index.is_none() {
-            return self.brute_force_search(query, k, filter);
-        }
-
-        let index = self.index.as_ref().unwrap();
-        let query_point = EmbeddingPoint(query.clone());
-
-        let mut search = Search::default();
-        let results = index.search(&query_point, &mut search);
-
-        let mut filtered_results = Vec::new();
-
-        for item in results.take(k * 10) {
-            // Take extra to account for filtering
-            let node_id = *item.value;
-            let distance = item.distance;
-
-            // Apply filter
-            if let Some(f) = filter {
-                if !self.matches_filter(&node_id, f) {
-                    continue;
-                }
-            }
-
-            filtered_results.push(SimilarityResult {
-                node_id,
-                score: Self::distance_to_similarity(distance),
-                distance,
-            });
-
-            if filtered_results.len() >= k {
-                break;
-            }
-        }
-
-        Ok(filtered_results)
+        let results = self.search_graph(query, k, filter)?;
+        tracing::Span::current().record("results", results.len());
+        Ok(results)
     }
 
     fn search_threshold(
@@ -413,19 +665,21 @@ impl VectorIndex for HnswIndex {
         self.vectors.len()
     }
 
+    fn get_embedding(&self, id: NodeId) -> Option<Embedding> {
+        self.vectors.get(&id).cloned()
+    }
+
     fn rebuild(&mut self) -> Result<()> {
         if self.vectors.is_empty() {
             self.index = None;
             return Ok(());
         }
 
-        let mut points = Vec::new();
-        let mut values = Vec::new();
-
-        for (id, vec) in &self.vectors {
-            points.push(EmbeddingPoint(vec.clone()));
-            values.push(*id);
-        }
+        let (points, values): (Vec<EmbeddingPoint>, Vec<NodeId>) = self
+            .vectors
+            .par_iter()
+            .map(|(id, vec)| (EmbeddingPoint(vec.clone()), *id))
+            .unzip();
 
         let map = Builder::default().build(points, values);
 
@@ -569,6 +823,7 @@ mod tests {
 #[cfg(test)]
 mod additional_tests {
     use super::*;
+    use std::collections::HashSet;
 
     #[allow(dead_code)]
     fn make_embedding(dim: usize, val: f32) -> Embedding {
@@ -683,6 +938,167 @@ mod additional_tests {
         assert_eq!(results[&id2][0].node_id, id2);
     }
 
+    fn node_with(kind: &str, tags: &[&str], importance: f32, embedding: Embedding) -> Node {
+        let mut node = Node::new(
+            NodeKind::new(kind).unwrap(),
+            "n".into(),
+            String::new(),
+            crate::types::Source {
+                agent: "test".into(),
+                session: None,
+                channel: None,
+            },
+            importance,
+        );
+        node.data.tags = tags.iter().map(|t| t.to_string()).collect();
+        node.embedding = Some(embedding);
+        node
+    }
+
+    /// 200 tight clusters of 10 points, so each cluster centre's top 10 is
+    /// unambiguous. Deterministic LCG, no rand dependency.
+    fn clustered_nodes(dimension: usize) -> (Vec<Node>, Vec<Embedding>) {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 33) as f32 / (1u64 << 31) as f32) - 0.5
+        };
+        let mut nodes = Vec::new();
+        let mut centres = Vec::new();
+        for _ in 0..200 {
+            let centre: Embedding = (0..dimension).map(|_| next()).collect();
+            for _ in 0..10 {
+                let point = centre.iter().map(|c| c + next() * 0.01).collect();
+                nodes.push(node_with("fact", &[], 0.5, point));
+            }
+            centres.push(centre);
+        }
+        (nodes, centres)
+    }
+
+    #[test]
+    fn test_parallel_build_indexes_every_node() {
+        let (nodes, centres) = clustered_nodes(64);
+        // The first 20 clusters of 10
+        let nodes: Vec<Node> = nodes.into_iter().take(200).collect();
+        let index = HnswIndex::build_parallel(64, nodes.clone(), 2).unwrap();
+
+        assert_eq!(index.len(), nodes.len());
+        for node in &nodes {
+            assert_eq!(index.get_embedding(node.id), node.embedding);
+        }
+        for (centre, cluster) in centres.iter().zip(nodes.chunks(10)) {
+            let found: HashSet<NodeId> = index
+                .search(centre, 10, None)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.node_id)
+                .collect();
+            assert_eq!(found, cluster.iter().map(|n| n.id).collect());
+        }
+    }
+
+    #[test]
+    fn test_update_metadata_refreshes_filters() {
+        let mut index = HnswIndex::new(3);
+        let mut node = node_with("fact", &["draft"], 0.2, vec![1.0, 0.0, 0.0]);
+        index
+            .insert_node(&node, node.embedding.as_ref().unwrap())
+            .unwrap();
+        index.rebuild().unwrap();
+        let filter = VectorFilter::new()
+            .with_tags(vec!["final".into()])
+            .with_min_importance(0.5);
+        let query = vec![1.0, 0.0, 0.0];
+        assert!(index.search(&query, 5, Some(&filter)).unwrap().is_empty());
+
+        node.data.tags = vec!["final".into()];
+        node.importance = 0.9;
+        index.update_metadata(&node);
+        assert_eq!(index.search(&query, 5, Some(&filter)).unwrap().len(), 1);
+
+        // Nodes that aren't indexed stay out
+        let other = node_with("fact", &["final"], 0.9, vec![0.0, 1.0, 0.0]);
+        index.update_metadata(&other);
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn test_parallel_build_skips_wrong_dimension() {
+        let ok = node_with("fact", &[], 0.5, vec![1.0, 0.0, 0.0]);
+        let mut unembedded = node_with("fact", &[], 0.5, vec![]);
+        unembedded.embedding = None;
+        let nodes = vec![
+            ok.clone(),
+            node_with("fact", &[], 0.5, vec![1.0, 0.0]),
+            unembedded,
+        ];
+        let index = HnswIndex::build_parallel(3, nodes, 2).unwrap();
+        assert_eq!(index.len(), 1);
+        assert!(index.get_embedding(ok.id).is_some());
+
+        // Metadata comes along, so filters work on a freshly built index
+        let filter = VectorFilter::new().with_kinds(vec![NodeKind::new("fact").unwrap()]);
+        let results = index
+            .search(&vec![1.0, 0.0, 0.0], 5, Some(&filter))
+            .unwrap();
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_rare_kind_filter_returns_full_count() {
+        let (mut nodes, centres) = clustered_nodes(64);
+        // 13 decisions among ~2000 facts, spread across clusters far from the query
+        for node in nodes.iter_mut().skip(37).step_by(160) {
+            node.kind = NodeKind::new("decision").unwrap();
+        }
+        let decisions = nodes
+            .iter()
+            .filter(|n| n.kind.as_str() == "decision")
+            .count();
+        assert_eq!(decisions, 13);
+        let index = HnswIndex::build_parallel(64, nodes, 0).unwrap();
+
+        let filter = VectorFilter::new().with_kinds(vec![NodeKind::new("decision").unwrap()]);
+        let results = index.search(&centres[0], 10, Some(&filter)).unwrap();
+        assert_eq!(results.len(), 10);
+        assert!(results.windows(2).all(|w| w[0].score >= w[1].score));
+
+        // Asking for more than exist returns every match
+        let results = index.search(&centres[0], 50, Some(&filter)).unwrap();
+        assert_eq!(results.len(), decisions);
+    }
+
+    #[test]
+    fn test_filter_by_tags_and_importance() {
+        let mut index = HnswIndex::new(3);
+        let tagged = node_with("fact", &["infra", "db"], 0.9, vec![1.0, 0.0, 0.0]);
+        let untagged = node_with("fact", &["infra"], 0.9, vec![0.9, 0.1, 0.0]);
+        let minor = node_with("fact", &["infra", "db"], 0.2, vec![0.8, 0.2, 0.0]);
+        let bare = NodeId::now_v7();
+        for node in [&tagged, &untagged, &minor] {
+            index
+                .insert_node(node, node.embedding.as_ref().unwrap())
+                .unwrap();
+        }
+        // Inserted without metadata: never matches a metadata filter
+        index.insert(bare, &vec![1.0, 0.0, 0.0]).unwrap();
+        index.rebuild().unwrap();
+
+        let ids = |filter: VectorFilter| -> Vec<NodeId> {
+            let results = index
+                .search(&vec![1.0, 0.0, 0.0], 5, Some(&filter))
+                .unwrap();
+            results.into_iter().map(|r| r.node_id).collect()
+        };
+        let db = || VectorFilter::new().with_tags(vec!["db".into()]);
+        assert_eq!(ids(db()), vec![tagged.id, minor.id]);
+        assert_eq!(ids(db().with_min_importance(0.5)), vec![tagged.id]);
+        assert_eq!(ids(VectorFilter::new()).len(), 4);
+    }
+
     #[test]
     fn test_similarity_score_range() {
         let mut index = HnswIndex::new(3);
@@ -707,6 +1123,68 @@ mod additional_tests {
         assert!(results[0].score > 0.99);
     }
 
+    #[test]
+    fn test_mmr_promotes_outlier_as_diversity_increases() {
+        let mut index = HnswIndex::new(3);
+        let a = NodeId::now_v7();
+        let b = NodeId::now_v7();
+        let c = NodeId::now_v7();
+        let outlier = NodeId::now_v7();
+
+        // Three near-duplicates and one distinct-but-relevant vector
+        index.insert(a, &vec![1.0, 0.0, 0.0]).unwrap();
+        index.insert(b, &vec![0.99, 0.05, 0.0]).unwrap();
+        index.insert(c, &vec![0.98, 0.08, 0.0]).unwrap();
+        index.insert(outlier, &vec![0.6, 0.8, 0.0]).unwrap();
+        index.rebuild().unwrap();
+
+        let query = vec![1.0, 0.2, 0.0];
+        let rank_of_outlier = |diversity: f32| {
+            index
+                .search_diverse(&query, 4, None, diversity)
+                .unwrap()
+                .iter()
+                .position(|r| r.node_id == outlier)
+                .unwrap()
+        };
+
+        let plain = rank_of_outlier(0.0);
+        let mild = rank_of_outlier(0.3);
+        let strong = rank_of_outlier(0.7);
+
+        assert_eq!(plain, 3, "without diversity the outlier ranks last");
+        assert!(mild <= plain);
+        assert!(strong <= mild);
+        assert_eq!(
+            strong, 1,
+            "high diversity should promote the outlier to second"
+        );
+    }
+
+    #[test]
+    fn test_mmr_zero_diversity_matches_search() {
+        let mut index = HnswIndex::new(3);
+        for v in [[1.0, 0.0, 0.0], [0.7, 0.7, 0.0], [0.0, 1.0, 0.0]] {
+            index.insert(NodeId::now_v7(), &v.to_vec()).unwrap();
+        }
+        index.rebuild().unwrap();
+
+        let query = vec![1.0, 0.1, 0.0];
+        let plain: Vec<_> = index
+            .search(&query, 3, None)
+            .unwrap()
+            .iter()
+            .map(|r| r.node_id)
+            .collect();
+        let diverse: Vec<_> = index
+            .search_diverse(&query, 3, None, 0.0)
+            .unwrap()
+            .iter()
+            .map(|r| r.node_id)
+            .collect();
+        assert_eq!(plain, diverse);
+    }
+
     #[test]
     fn test_threshold_returns_only_above() {
         let mut index = HnswIndex::new(3);
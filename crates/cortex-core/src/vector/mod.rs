@@ -1,14 +1,26 @@
+mod cache;
 mod config;
+mod cursor;
 mod embedding;
 mod hybrid;
 mod index;
+mod reindex;
 mod scoring;
 
-pub use config::SimilarityConfig;
+pub use cache::{CachingEmbeddingService, EmbeddingCacheStats, DEFAULT_EMBEDDING_CACHE_CAPACITY};
+pub use config::{GraphProximity, SimilarityConfig};
+pub use cursor::{page_by_score, page_search, SearchCursor};
 pub use embedding::{embedding_input, EmbeddingService, FastEmbedService};
 pub use hybrid::{HybridQuery, HybridResult, HybridSearch};
-pub use index::{HnswIndex, RwLockVectorIndex, SimilarityResult, VectorFilter, VectorIndex};
-pub use scoring::{apply_score_decay, ScoreDecayConfig};
+pub(crate) use index::cosine_similarity;
+pub use index::{
+    mmr_rerank, HnswIndex, RwLockVectorIndex, SimilarityResult, VectorFilter, VectorIndex,
+    MMR_CANDIDATE_MULTIPLIER,
+};
+pub use reindex::{reindex, replay_writes, ReindexSummary};
+pub use scoring::{
+    apply_confidence_weight, apply_recency_boost, apply_score_decay, ScoreDecayConfig,
+};
 
 #[cfg(test)]
 mod tests;
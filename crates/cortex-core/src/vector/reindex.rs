@@ -0,0 +1,310 @@
+use crate::error::Result;
+use crate::storage::{NodeFilter, Storage};
+use crate::types::{Embedding, Node, NodeId};
+use crate::vector::{embedding_input, EmbeddingService, HnswIndex, VectorIndex};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+
+/// Nodes embedded and written back per batch.
+const REINDEX_BATCH_SIZE: usize = 64;
+
+/// What [`reindex`] did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReindexSummary {
+    /// Nodes in the new index.
+    pub indexed: usize,
+    /// Nodes whose embedding was computed (and stored) during the run.
+    pub embedded: usize,
+    /// Nodes left out: the embedding failed or its dimension doesn't match
+    /// the model. Run with `re_embed` after a model change.
+    pub skipped: usize,
+}
+
+/// Build a fresh [`HnswIndex`] from every live node in `storage`.
+///
+/// Stored embeddings are reused; nodes without one are embedded. With
+/// `re_embed`, every node is embedded again, e.g. after switching models.
+/// Embeddings computed here are written back to storage, so the next
+/// startup indexes the same vectors; only the embedding is written, onto
+/// the node as it is at that moment. `progress` is called with
+/// `(done, total)` after each batch.
+///
+/// Nodes are read once at the start, so the result misses writes made while
+/// it is built. The caller notes the time before calling, then passes it to
+/// [`replay_writes`] under the live index's write lock and swaps the result
+/// in for the live index.
+pub fn reindex<S: Storage, E: EmbeddingService>(
+    storage: &S,
+    embedder: &E,
+    re_embed: bool,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<(HnswIndex, ReindexSummary)> {
+    let nodes = storage.list_nodes(NodeFilter::new())?;
+    let total = nodes.len();
+    let mut index = HnswIndex::new(embedder.dimension());
+    let mut summary = ReindexSummary::default();
+
+    let mut done = 0;
+    for batch in nodes.chunks(REINDEX_BATCH_SIZE) {
+        let (stored, missing): (Vec<&Node>, Vec<&Node>) = batch
+            .iter()
+            .partition(|n| !re_embed && n.embedding.is_some());
+
+        for node in stored {
+            let embedding = node.embedding.as_ref().expect("partitioned on is_some");
+            match index.insert_node(node, embedding) {
+                Ok(()) => summary.indexed += 1,
+                Err(_) => summary.skipped += 1,
+            }
+        }
+
+        if !missing.is_empty() {
+            let texts: Vec<String> = missing.iter().map(|n| embedding_input(n)).collect();
+            match embedder.embed_batch(&texts) {
+                Ok(embeddings) => {
+                    let computed: Vec<(&Node, Embedding)> =
+                        missing.into_iter().zip(embeddings).collect();
+                    for (node, embedding) in &computed {
+                        index.insert_node(node, embedding)?;
+                    }
+                    store_embeddings(storage, &computed)?;
+                    summary.indexed += computed.len();
+                    summary.embedded += computed.len();
+                }
+                Err(e) => {
+                    log::warn!("reindex: embedding {} nodes failed: {}", missing.len(), e);
+                    summary.skipped += missing.len();
+                }
+            }
+        }
+
+        done += batch.len();
+        progress(done, total);
+    }
+
+    if !index.is_empty() {
+        index.rebuild()?;
+    }
+    Ok((index, summary))
+}
+
+/// Set the embeddings computed for `nodes` on the stored nodes, in one
+/// transaction. Each node is read again so writes made since [`reindex`]
+/// read it are kept; a node whose text has changed is skipped, as whoever
+/// changed it embedded the new text.
+fn store_embeddings<S: Storage>(storage: &S, computed: &[(&Node, Embedding)]) -> Result<()> {
+    storage.transaction(|tx| {
+        for (read, embedding) in computed {
+            let Some(mut node) = tx.get_node(read.id)? else {
+                continue;
+            };
+            if embedding_input(&node) != embedding_input(read) {
+                continue;
+            }
+            node.embedding = Some(embedding.clone());
+            tx.put_node(&node)?;
+        }
+        Ok(())
+    })
+}
+
+/// Bring `index`, built by [`reindex`] from nodes read at `since`, up to
+/// date with the writes made while it was built. Nodes deleted since are
+/// dropped. Nodes changed since, or missing from `index`, are indexed again
+/// with their stored embedding, or failing that the vector `live` holds for
+/// them. Returns how many entries changed.
+///
+/// Call it with the write lock on `live` held and swap `index` in straight
+/// after, so no write lands in between.
+pub fn replay_writes<S: Storage, V: VectorIndex>(
+    index: &mut HnswIndex,
+    live: &V,
+    storage: &S,
+    since: DateTime<Utc>,
+) -> Result<usize> {
+    let headers = storage.list_node_headers(NodeFilter::new())?;
+    let current: HashSet<NodeId> = headers.iter().map(|h| h.id).collect();
+    let gone: Vec<NodeId> = index.ids().filter(|id| !current.contains(id)).collect();
+    let mut changed = gone.len();
+    for id in gone {
+        index.remove(id)?;
+    }
+
+    for header in &headers {
+        if header.updated_at < since && index.get_embedding(header.id).is_some() {
+            continue;
+        }
+        let Some(node) = storage.get_node(header.id)? else {
+            continue;
+        };
+        let Some(embedding) = node
+            .embedding
+            .clone()
+            .or_else(|| live.get_embedding(node.id))
+        else {
+            continue;
+        };
+        // A vector of the wrong dimension stays out, as in reindex
+        if index.insert_node(&node, &embedding).is_ok() {
+            changed += 1;
+        }
+    }
+
+    if changed > 0 {
+        index.rebuild()?;
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::RedbStorage;
+    use crate::testing::KeywordEmbedder;
+    use crate::types::{NodeKind, Source};
+
+    /// One axis per word, so searches are exact.
+    fn axis(scale: f32) -> KeywordEmbedder {
+        KeywordEmbedder::new(&["alpha", "beta", "gamma"]).scaled(scale)
+    }
+
+    fn node(word: &str) -> Node {
+        Node::new(
+            NodeKind::new("fact").unwrap(),
+            format!("The {word} node"),
+            format!("Everything about {word}."),
+            Source {
+                agent: "test".into(),
+                session: None,
+                channel: None,
+            },
+            0.5,
+        )
+    }
+
+    #[test]
+    fn reindex_restores_search_after_index_loss() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let storage = RedbStorage::open(tmp.path().join("reindex.redb")).unwrap();
+        let embedder = axis(1.0);
+
+        let mut alpha = node("alpha");
+        alpha.embedding = Some(embedder.embed("alpha").unwrap());
+        // No stored embedding: reindex has to compute it
+        let beta = node("beta");
+        storage
+            .put_nodes_batch(&[alpha.clone(), beta.clone()])
+            .unwrap();
+
+        // The live index lost everything (crash, bad import, ...)
+        let query = embedder.embed("beta").unwrap();
+        let lost = HnswIndex::new(4);
+        assert!(lost.search(&query, 1, None).unwrap().is_empty());
+
+        let mut calls = Vec::new();
+        let (index, summary) = reindex(&storage, &embedder, false, |done, total| {
+            calls.push((done, total))
+        })
+        .unwrap();
+        assert_eq!(
+            summary,
+            ReindexSummary {
+                indexed: 2,
+                embedded: 1,
+                skipped: 0
+            }
+        );
+        assert_eq!(calls, [(2, 2)]);
+
+        let hits = index.search(&query, 1, None).unwrap();
+        assert_eq!(hits[0].node_id, beta.id);
+        let hits = index.search(&alpha.embedding.unwrap(), 1, None).unwrap();
+        assert_eq!(hits[0].node_id, alpha.id);
+
+        // The computed embedding was written back
+        let stored = storage.get_node(beta.id).unwrap().unwrap();
+        assert_eq!(stored.embedding, Some(query));
+    }
+
+    #[test]
+    fn embeddings_are_written_onto_the_current_node() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let storage = RedbStorage::open(tmp.path().join("writeback.redb")).unwrap();
+        let embedder = axis(1.0);
+        let alpha = node("alpha");
+        let beta = node("beta");
+        storage
+            .put_nodes_batch(&[alpha.clone(), beta.clone()])
+            .unwrap();
+
+        // Edited after reindex read them: alpha's importance, beta's text
+        let mut edited = alpha.clone();
+        edited.importance = 0.9;
+        storage.put_node(&edited).unwrap();
+        let mut rewritten = beta.clone();
+        rewritten.data.body = "Now about gamma.".into();
+        storage.put_node(&rewritten).unwrap();
+
+        let computed = vec![
+            (&alpha, embedder.embed("alpha").unwrap()),
+            (&beta, embedder.embed("beta").unwrap()),
+        ];
+        store_embeddings(&storage, &computed).unwrap();
+
+        let stored = storage.get_node(alpha.id).unwrap().unwrap();
+        assert_eq!(stored.importance, 0.9);
+        assert_eq!(stored.embedding, Some(computed[0].1.clone()));
+        let stored = storage.get_node(beta.id).unwrap().unwrap();
+        assert_eq!(stored.data.body, "Now about gamma.");
+        assert_eq!(stored.embedding, None);
+    }
+
+    #[test]
+    fn replay_applies_writes_made_during_the_build() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let storage = RedbStorage::open(tmp.path().join("replay.redb")).unwrap();
+        let embedder = axis(1.0);
+        let mut alpha = node("alpha");
+        alpha.embedding = Some(embedder.embed("alpha").unwrap());
+        let mut beta = node("beta");
+        beta.embedding = Some(embedder.embed("beta").unwrap());
+        storage
+            .put_nodes_batch(&[alpha.clone(), beta.clone()])
+            .unwrap();
+
+        let started = Utc::now();
+        let (mut index, _) = reindex(&storage, &embedder, false, |_, _| {}).unwrap();
+
+        // Meanwhile: gamma is created, beta deleted
+        let mut gamma = node("gamma");
+        gamma.embedding = Some(embedder.embed("gamma").unwrap());
+        storage.put_node(&gamma).unwrap();
+        storage.delete_node(beta.id).unwrap();
+
+        let live = HnswIndex::new(4);
+        let replayed = replay_writes(&mut index, &live, &storage, started).unwrap();
+        assert_eq!(replayed, 2);
+        assert_eq!(index.len(), 2);
+        let hits = index
+            .search(gamma.embedding.as_ref().unwrap(), 1, None)
+            .unwrap();
+        assert_eq!(hits[0].node_id, gamma.id);
+        assert!(index.get_embedding(beta.id).is_none());
+    }
+
+    #[test]
+    fn re_embed_replaces_stored_embeddings() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let storage = RedbStorage::open(tmp.path().join("reembed.redb")).unwrap();
+
+        let mut gamma = node("gamma");
+        gamma.embedding = Some(axis(1.0).embed("gamma").unwrap());
+        storage.put_node(&gamma).unwrap();
+
+        let new_model = axis(0.5);
+        let (_, summary) = reindex(&storage, &new_model, true, |_, _| {}).unwrap();
+        assert_eq!((summary.indexed, summary.embedded), (1, 1));
+        let stored = storage.get_node(gamma.id).unwrap().unwrap();
+        assert_eq!(stored.embedding, Some(vec![0.0, 0.0, 0.5, 0.0]));
+    }
+}
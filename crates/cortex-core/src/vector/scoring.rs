@@ -51,6 +51,14 @@ pub struct ScoreDecayConfig {
     /// Per-kind daily decay rate overrides (key = node kind string).
     /// Events decay faster; decisions and patterns stay relevant longer.
     pub by_kind: HashMap<String, f64>,
+
+    /// Half-life in days for the opt-in recency boost (`apply_recency_boost`).
+    /// A node this many days old has its similarity multiplied by 0.5.
+    pub recency_half_life_days: f64,
+
+    /// How much node `confidence` scales search scores (`apply_confidence_weight`).
+    /// `0.0` (default) ignores confidence; `1.0` multiplies by it outright.
+    pub confidence_weight: f32,
 }
 
 impl Default for ScoreDecayConfig {
@@ -71,6 +79,8 @@ impl Default for ScoreDecayConfig {
             echo_cap: 2.0,
             recency_weight: 0.15,
             by_kind,
+            recency_half_life_days: 30.0,
+            confidence_weight: 0.0,
         }
     }
 }
@@ -113,6 +123,49 @@ pub fn apply_score_decay(
     raw_score * (1.0 - recency_bias) + raw_score * temporal_factor * echo_factor * recency_bias
 }
 
+/// Multiply a similarity score by a half-life decay on the node's age.
+///
+/// Unlike `apply_score_decay`, which tracks *idleness* (`last_accessed_at`),
+/// this looks at `created_at`: of two equally similar nodes, the one stored
+/// more recently ranks higher. Opt-in per query.
+///
+/// ```text
+/// boosted = raw × 0.5^(age_days / half_life_days)
+/// ```
+///
+/// `half_life_days` overrides `config.recency_half_life_days` when `Some`.
+/// Returns `raw_score` unchanged when the effective half-life is not positive.
+pub fn apply_recency_boost(
+    node: &Node,
+    raw_score: f32,
+    config: &ScoreDecayConfig,
+    half_life_days: Option<f64>,
+) -> f32 {
+    let half_life = half_life_days.unwrap_or(config.recency_half_life_days);
+    if half_life <= 0.0 {
+        return raw_score;
+    }
+
+    let age_days = Utc::now()
+        .signed_duration_since(node.created_at)
+        .num_seconds()
+        .max(0) as f64
+        / 86_400.0;
+
+    raw_score * 0.5f64.powf(age_days / half_life) as f32
+}
+
+/// Scale a score by the node's confidence, to the extent of
+/// `config.confidence_weight`.
+///
+/// ```text
+/// weighted = raw × (1 - w + w × confidence)
+/// ```
+pub fn apply_confidence_weight(node: &Node, raw_score: f32, config: &ScoreDecayConfig) -> f32 {
+    let w = config.confidence_weight.clamp(0.0, 1.0);
+    raw_score * (1.0 - w + w * node.confidence)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -273,4 +326,53 @@ mod tests {
             result
         );
     }
+
+    #[test]
+    fn test_recency_boost_halves_at_half_life() {
+        let mut node = make_node("fact");
+        node.created_at = Utc::now() - Duration::days(30);
+        let config = ScoreDecayConfig::default();
+        let result = apply_recency_boost(&node, 0.8, &config, None);
+        assert!((result - 0.4).abs() < 0.01, "expected ~0.4, got {}", result);
+
+        // Per-query half-life override
+        let result = apply_recency_boost(&node, 0.8, &config, Some(60.0));
+        assert!((result - 0.8 * 0.5f32.sqrt()).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_recency_boost_ranks_equal_similarity_by_age() {
+        let mut old = make_node("fact");
+        old.created_at = Utc::now() - Duration::days(365);
+        let mut recent = make_node("fact");
+        recent.created_at = Utc::now() - Duration::days(1);
+
+        let config = ScoreDecayConfig::default();
+        let mut ranked: Vec<(&Node, f32)> = [&old, &recent]
+            .into_iter()
+            .map(|n| (n, apply_recency_boost(n, 0.8, &config, None)))
+            .collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+        assert_eq!(ranked[0].0.id, recent.id);
+        assert_eq!(ranked[1].0.id, old.id);
+    }
+
+    #[test]
+    fn test_confidence_weight() {
+        let mut doubtful = make_node("fact");
+        doubtful.confidence = 0.5;
+        let sure = make_node("fact");
+
+        // Off by default
+        let config = ScoreDecayConfig::default();
+        assert_eq!(apply_confidence_weight(&doubtful, 0.8, &config), 0.8);
+
+        let config = ScoreDecayConfig {
+            confidence_weight: 0.5,
+            ..Default::default()
+        };
+        assert!((apply_confidence_weight(&doubtful, 0.8, &config) - 0.6).abs() < 1e-6);
+        assert_eq!(apply_confidence_weight(&sure, 0.8, &config), 0.8);
+    }
 }
@@ -1,12 +1,15 @@
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Only regenerate proto code when the `regenerate` feature flag is set.
     // By default, the pre-generated file in src/generated/ is used so that
-    // end users don't need protoc installed.
-    #[cfg(feature = "regenerate")]
+    // end users don't need protoc installed. `reflection` needs protoc too,
+    // for the descriptor set; the code it generates alongside goes unused.
+    #[cfg(any(feature = "regenerate", feature = "reflection"))]
     {
+        let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR")?);
         tonic_build::configure()
             .build_server(true)
             .build_client(true)
+            .file_descriptor_set_path(out_dir.join("cortex_descriptor.bin"))
             .compile_protos(&["proto/cortex.proto"], &["proto"])?;
     }
 
@@ -15,15 +15,21 @@ pub struct CreateNodeRequest {
     >,
     #[prost(string, repeated, tag = "5")]
     pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
-    /// 0.0-1.0, default 0.5
-    #[prost(float, tag = "6")]
-    pub importance: f32,
+    /// 0.0-1.0; unset = the kind's default_importance, else 0.5
+    #[prost(float, optional, tag = "6")]
+    pub importance: ::core::option::Option<f32>,
     #[prost(string, tag = "7")]
     pub source_agent: ::prost::alloc::string::String,
     #[prost(string, optional, tag = "8")]
     pub source_session: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(string, optional, tag = "9")]
     pub source_channel: ::core::option::Option<::prost::alloc::string::String>,
+    /// 0.0-1.0, default 1.0
+    #[prost(float, optional, tag = "10")]
+    pub confidence: ::core::option::Option<f32>,
+    /// Unset = never expires
+    #[prost(message, optional, tag = "11")]
+    pub expires_at: ::core::option::Option<::prost_types::Timestamp>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetNodeRequest {
@@ -47,12 +53,24 @@ pub struct UpdateNodeRequest {
     pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(float, optional, tag = "6")]
     pub importance: ::core::option::Option<f32>,
+    #[prost(float, optional, tag = "7")]
+    pub confidence: ::core::option::Option<f32>,
+    #[prost(message, optional, tag = "8")]
+    pub expires_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// Remove the expiry; wins over expires_at
+    #[prost(bool, tag = "9")]
+    pub clear_expires_at: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DeleteNodeRequest {
     #[prost(string, tag = "1")]
     pub id: ::prost::alloc::string::String,
 }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RestoreNodeRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct DeleteResponse {
     #[prost(bool, tag = "1")]
@@ -72,6 +90,21 @@ pub struct ListNodesRequest {
     pub limit: u32,
     #[prost(uint32, tag = "6")]
     pub offset: u32,
+    /// next_cursor from the previous page
+    #[prost(string, tag = "7")]
+    pub cursor: ::prost::alloc::string::String,
+    /// Also list soft-deleted nodes
+    #[prost(bool, tag = "8")]
+    pub include_deleted: bool,
+    /// Only nodes created at or after this
+    #[prost(message, optional, tag = "9")]
+    pub created_after: ::core::option::Option<::prost_types::Timestamp>,
+    /// Only nodes created at or before this
+    #[prost(message, optional, tag = "10")]
+    pub created_before: ::core::option::Option<::prost_types::Timestamp>,
+    /// Require every tag in tag_filter, not just one
+    #[prost(bool, tag = "11")]
+    pub all_tags: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ListNodesResponse {
@@ -79,6 +112,9 @@ pub struct ListNodesResponse {
     pub nodes: ::prost::alloc::vec::Vec<NodeResponse>,
     #[prost(uint64, tag = "2")]
     pub total_count: u64,
+    /// Empty on the last page
+    #[prost(string, tag = "3")]
+    pub next_cursor: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct NodeResponse {
@@ -119,6 +155,14 @@ pub struct NodeResponse {
     /// Last time returned in search/briefing
     #[prost(message, optional, tag = "16")]
     pub last_accessed_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(float, tag = "17")]
+    pub confidence: f32,
+    /// Unset = never expires
+    #[prost(message, optional, tag = "18")]
+    pub expires_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// Set only on soft-deleted nodes
+    #[prost(message, optional, tag = "19")]
+    pub deleted_at: ::core::option::Option<::prost_types::Timestamp>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateEdgeRequest {
@@ -132,6 +176,9 @@ pub struct CreateEdgeRequest {
     /// Default 1.0 for manual edges
     #[prost(float, tag = "4")]
     pub weight: f32,
+    /// Also write the inverse edge for symmetric/invertible relations
+    #[prost(bool, tag = "5")]
+    pub inverse: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct EdgeResponse {
@@ -149,6 +196,9 @@ pub struct EdgeResponse {
     pub created_at: ::core::option::Option<::prost_types::Timestamp>,
     #[prost(message, optional, tag = "7")]
     pub updated_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// CreateEdge with inverse: the inverse edge written, if any
+    #[prost(string, tag = "8")]
+    pub inverse_id: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetEdgesRequest {
@@ -189,6 +239,12 @@ pub struct TraverseRequest {
     /// "bfs", "dfs", "weighted"
     #[prost(string, tag = "8")]
     pub strategy: ::prost::alloc::string::String,
+    /// Also return nodes past their expires_at
+    #[prost(bool, tag = "9")]
+    pub include_expired: bool,
+    /// Also return soft-deleted nodes
+    #[prost(bool, tag = "10")]
+    pub include_deleted: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SubgraphResponse {
@@ -204,6 +260,23 @@ pub struct SubgraphResponse {
     #[prost(bool, tag = "5")]
     pub truncated: bool,
 }
+/// One depth level of a streamed traversal. Edges come in the chunk where
+/// their second endpoint arrives.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TraverseChunk {
+    #[prost(uint32, tag = "1")]
+    pub depth: u32,
+    #[prost(message, repeated, tag = "2")]
+    pub nodes: ::prost::alloc::vec::Vec<NodeResponse>,
+    #[prost(message, repeated, tag = "3")]
+    pub edges: ::prost::alloc::vec::Vec<EdgeResponse>,
+    /// Total so far
+    #[prost(uint32, tag = "4")]
+    pub visited_count: u32,
+    /// Budget or limit hit; this is the last chunk
+    #[prost(bool, tag = "5")]
+    pub truncated: bool,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FindPathsRequest {
     #[prost(string, tag = "1")]
@@ -216,6 +289,9 @@ pub struct FindPathsRequest {
     /// Optional depth limit
     #[prost(uint32, tag = "4")]
     pub max_depth: u32,
+    /// "hops" (default) or "weighted" (Dijkstra on 1/weight)
+    #[prost(string, tag = "5")]
+    pub strategy: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PathsResponse {
@@ -254,11 +330,32 @@ pub struct SimilaritySearchRequest {
     /// Default 0.0
     #[prost(float, tag = "4")]
     pub min_score: f32,
+    /// MMR re-rank strength 0.0-1.0, default 0.0 (off)
+    #[prost(float, tag = "5")]
+    pub diversity: f32,
+    /// Multiply scores by a created_at half-life decay, default off
+    #[prost(bool, tag = "6")]
+    pub recency_boost: bool,
+    /// Overrides score_decay.recency_half_life_days
+    #[prost(double, optional, tag = "7")]
+    pub recency_half_life_days: ::core::option::Option<f64>,
+    /// next_cursor from the previous page; not combinable with diversity
+    #[prost(string, tag = "8")]
+    pub cursor: ::prost::alloc::string::String,
+    /// Also return nodes past their expires_at
+    #[prost(bool, tag = "9")]
+    pub include_expired: bool,
+    /// Also return soft-deleted nodes
+    #[prost(bool, tag = "10")]
+    pub include_deleted: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchResponse {
     #[prost(message, repeated, tag = "1")]
     pub results: ::prost::alloc::vec::Vec<SearchResultEntry>,
+    /// Empty on the last page
+    #[prost(string, tag = "2")]
+    pub next_cursor: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchResultEntry {
@@ -268,6 +365,15 @@ pub struct SearchResultEntry {
     pub score: f32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryNodesRequest {
+    /// Filter DSL, see docs/reference/cli.md
+    #[prost(string, tag = "1")]
+    pub query: ::prost::alloc::string::String,
+    /// Optional: rank matches by similarity to this text
+    #[prost(string, tag = "2")]
+    pub like: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct HybridSearchRequest {
     #[prost(string, tag = "1")]
     pub query: ::prost::alloc::string::String,
@@ -283,6 +389,22 @@ pub struct HybridSearchRequest {
     /// Default 3
     #[prost(uint32, tag = "6")]
     pub max_anchor_depth: u32,
+    /// Also return nodes past their expires_at
+    #[prost(bool, tag = "7")]
+    pub include_expired: bool,
+    /// Also return soft-deleted nodes
+    #[prost(bool, tag = "8")]
+    pub include_deleted: bool,
+    /// With no anchor_ids, anchor on the top vector hits
+    #[prost(bool, tag = "9")]
+    pub auto_anchor: bool,
+    /// Default 3
+    #[prost(uint32, tag = "10")]
+    pub auto_anchor_count: u32,
+    /// Graph proximity weight per hop (1-hop first), out to max_anchor_depth.
+    /// Empty scores a node d hops from an anchor 1 / (1 + d).
+    #[prost(float, repeated, tag = "11")]
+    pub hop_decay: ::prost::alloc::vec::Vec<f32>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct HybridSearchResponse {
@@ -312,6 +434,12 @@ pub struct BriefingRequest {
     /// Use compact renderer (~4× density)
     #[prost(bool, tag = "2")]
     pub compact: bool,
+    /// "markdown" (default), "compact" or "json"; overrides compact
+    #[prost(string, tag = "3")]
+    pub format: ::prost::alloc::string::String,
+    /// Regenerate even if a cached briefing is current
+    #[prost(bool, tag = "4")]
+    pub no_cache: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct BriefingResponse {
@@ -339,7 +467,14 @@ pub struct BriefingSection {
     pub nodes: ::prost::alloc::vec::Vec<NodeResponse>,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct StatsRequest {}
+pub struct StatsRequest {
+    /// If > 0, include the top N nodes by PageRank
+    #[prost(uint32, tag = "1")]
+    pub centrality_top: u32,
+    /// Include community detection results
+    #[prost(bool, tag = "2")]
+    pub communities: bool,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct StatsResponse {
     #[prost(uint64, tag = "1")]
@@ -355,6 +490,70 @@ pub struct StatsResponse {
     >,
     #[prost(uint64, tag = "5")]
     pub db_size_bytes: u64,
+    /// Highest PageRank first
+    #[prost(message, repeated, tag = "6")]
+    pub central_nodes: ::prost::alloc::vec::Vec<CentralNode>,
+    /// Largest first, singletons omitted
+    #[prost(message, repeated, tag = "7")]
+    pub communities: ::prost::alloc::vec::Vec<CommunitySummary>,
+    /// Modularity of the full partition (when communities requested)
+    #[prost(float, tag = "8")]
+    pub modularity: f32,
+    /// On-disk bytes per redb table
+    #[prost(map = "string, uint64", tag = "9")]
+    pub table_bytes: ::std::collections::HashMap<::prost::alloc::string::String, u64>,
+    /// Write gate outcomes since startup
+    #[prost(message, optional, tag = "10")]
+    pub gate: ::core::option::Option<GateSummary>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GateSummary {
+    /// Includes nodes stored with warnings
+    #[prost(uint64, tag = "1")]
+    pub accepted: u64,
+    #[prost(uint64, tag = "2")]
+    pub rejected: u64,
+    /// Over the last 60 minutes
+    #[prost(uint64, tag = "3")]
+    pub recent_accepted: u64,
+    #[prost(uint64, tag = "4")]
+    pub recent_rejected: u64,
+    /// Failed checks, over all kinds
+    #[prost(map = "string, uint64", tag = "5")]
+    pub by_check: ::std::collections::HashMap<::prost::alloc::string::String, u64>,
+    /// By check, then kind
+    #[prost(message, repeated, tag = "6")]
+    pub failures: ::prost::alloc::vec::Vec<GateFailure>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GateFailure {
+    #[prost(string, tag = "1")]
+    pub check: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub kind: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "3")]
+    pub count: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CentralNode {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub kind: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub title: ::prost::alloc::string::String,
+    #[prost(float, tag = "4")]
+    pub score: f32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CommunitySummary {
+    #[prost(uint32, tag = "1")]
+    pub size: u32,
+    /// Most internally connected members
+    #[prost(string, repeated, tag = "2")]
+    pub representative_titles: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(float, tag = "3")]
+    pub modularity: f32,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct AutoLinkerStatusRequest {}
@@ -380,9 +579,51 @@ pub struct AutoLinkerStatusResponse {
     pub cursor: ::core::option::Option<::prost_types::Timestamp>,
     #[prost(uint64, tag = "10")]
     pub backlog_size: u64,
+    /// Nodes changed since the cursor at last cycle
+    #[prost(uint64, tag = "11")]
+    pub nodes_scanned: u64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct TriggerAutoLinkRequest {
+    /// Rescan every node instead of only those changed since the cursor
+    #[prost(bool, tag = "1")]
+    pub full: bool,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct TriggerAutoLinkRequest {}
+pub struct PreviewAutoLinkRequest {
+    /// Evaluate every node, not just the pending backlog
+    #[prost(bool, tag = "1")]
+    pub full: bool,
+    /// Max proposals returned (0 = all)
+    #[prost(uint32, tag = "2")]
+    pub limit: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProposedEdgeResponse {
+    #[prost(string, tag = "1")]
+    pub from_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub to_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub from_title: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub to_title: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub relation: ::prost::alloc::string::String,
+    #[prost(float, tag = "6")]
+    pub weight: f32,
+    /// Rule that proposed the edge
+    #[prost(string, tag = "7")]
+    pub rule: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PreviewAutoLinkResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub edges: ::prost::alloc::vec::Vec<ProposedEdgeResponse>,
+    /// Proposals before the limit was applied
+    #[prost(uint32, tag = "2")]
+    pub total: u32,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct TriggerAutoLinkResponse {
     #[prost(bool, tag = "1")]
@@ -391,7 +632,11 @@ pub struct TriggerAutoLinkResponse {
     pub message: ::prost::alloc::string::String,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct ReindexRequest {}
+pub struct ReindexRequest {
+    /// Embed every node again instead of reusing stored embeddings.
+    #[prost(bool, tag = "1")]
+    pub re_embed: bool,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ReindexResponse {
     #[prost(bool, tag = "1")]
@@ -400,6 +645,32 @@ pub struct ReindexResponse {
     pub nodes_reindexed: u64,
     #[prost(string, tag = "3")]
     pub message: ::prost::alloc::string::String,
+    /// Nodes whose embedding was computed during this run.
+    #[prost(uint64, tag = "4")]
+    pub nodes_embedded: u64,
+    /// Nodes left out of the index (embedding failed or wrong dimension).
+    #[prost(uint64, tag = "5")]
+    pub nodes_skipped: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SnapshotRequest {
+    /// Destination file on the server host. Must not already exist.
+    #[prost(string, tag = "1")]
+    pub path: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SnapshotResponse {
+    #[prost(string, tag = "1")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub node_count: u64,
+    #[prost(uint64, tag = "3")]
+    pub edge_count: u64,
+    /// Graph version the snapshot is current up to.
+    #[prost(uint64, tag = "4")]
+    pub graph_version: u64,
+    #[prost(uint64, tag = "5")]
+    pub size_bytes: u64,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct HealthRequest {}
@@ -416,6 +687,62 @@ pub struct HealthResponse {
     #[prost(message, optional, tag = "5")]
     pub auto_linker: ::core::option::Option<AutoLinkerStatusResponse>,
 }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ObserveRequest {
+    /// Agent name (title of the agent node).
+    #[prost(string, tag = "1")]
+    pub agent: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub variant_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub variant_slug: ::prost::alloc::string::String,
+    /// 0.0-1.0, default 0.5.
+    #[prost(float, optional, tag = "4")]
+    pub sentiment_score: ::core::option::Option<f32>,
+    #[prost(uint32, tag = "5")]
+    pub correction_count: u32,
+    /// success | partial | failure | unknown (default).
+    #[prost(string, tag = "6")]
+    pub task_outcome: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag = "7")]
+    pub token_cost: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "8")]
+    pub response_time_ms: ::core::option::Option<u32>,
+    #[prost(float, optional, tag = "9")]
+    pub user_satisfaction: ::core::option::Option<f32>,
+    #[prost(string, optional, tag = "10")]
+    pub topic: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ObserveResponse {
+    #[prost(string, tag = "1")]
+    pub observation_id: ::prost::alloc::string::String,
+    #[prost(float, tag = "2")]
+    pub observation_score: f32,
+    /// Weight of the agent's `uses` edge to the variant before and after.
+    #[prost(float, tag = "3")]
+    pub old_edge_weight: f32,
+    #[prost(float, tag = "4")]
+    pub new_edge_weight: f32,
+    /// Set when this observation triggered an automatic rollback.
+    #[prost(message, optional, tag = "5")]
+    pub rollback: ::core::option::Option<RollbackInfo>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RollbackInfo {
+    #[prost(string, tag = "1")]
+    pub rollback_node_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub from_version: u32,
+    #[prost(uint32, tag = "3")]
+    pub to_version: u32,
+    #[prost(string, tag = "4")]
+    pub trigger: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "5")]
+    pub cooldown_hours: u32,
+    #[prost(bool, tag = "6")]
+    pub is_quarantined: bool,
+}
 /// Generated client implementations.
 pub mod cortex_service_client {
     #![allow(
@@ -595,6 +922,28 @@ pub mod cortex_service_client {
                 .insert(GrpcMethod::new("cortex.v1.CortexService", "DeleteNode"));
             self.inner.unary(req, path, codec).await
         }
+        /// Undo a soft delete.
+        pub async fn restore_node(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RestoreNodeRequest>,
+        ) -> std::result::Result<tonic::Response<super::NodeResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/cortex.v1.CortexService/RestoreNode",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("cortex.v1.CortexService", "RestoreNode"));
+            self.inner.unary(req, path, codec).await
+        }
         /// List nodes with filtering.
         pub async fn list_nodes(
             &mut self,
@@ -714,6 +1063,31 @@ pub mod cortex_service_client {
                 .insert(GrpcMethod::new("cortex.v1.CortexService", "Traverse"));
             self.inner.unary(req, path, codec).await
         }
+        /// Traverse breadth-first, streaming one chunk per depth level.
+        pub async fn traverse_stream(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TraverseRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::TraverseChunk>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/cortex.v1.CortexService/TraverseStream",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("cortex.v1.CortexService", "TraverseStream"));
+            self.inner.server_streaming(req, path, codec).await
+        }
         /// Find paths between two nodes.
         pub async fn find_paths(
             &mut self,
@@ -808,6 +1182,29 @@ pub mod cortex_service_client {
                 .insert(GrpcMethod::new("cortex.v1.CortexService", "HybridSearch"));
             self.inner.unary(req, path, codec).await
         }
+        /// Structured filter query (e.g. "kind:fact tag:infra importance>=0.7"),
+        /// optionally re-ranked by similarity to a text.
+        pub async fn query_nodes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryNodesRequest>,
+        ) -> std::result::Result<tonic::Response<super::SearchResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/cortex.v1.CortexService/QueryNodes",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("cortex.v1.CortexService", "QueryNodes"));
+            self.inner.unary(req, path, codec).await
+        }
         /// Get a synthesised context briefing for an agent.
         pub async fn get_briefing(
             &mut self,
@@ -833,6 +1230,29 @@ pub mod cortex_service_client {
                 .insert(GrpcMethod::new("cortex.v1.CortexService", "GetBriefing"));
             self.inner.unary(req, path, codec).await
         }
+        /// Record how a prompt variant performed for an agent. Updates the
+        /// agent's `uses` edge weight and may trigger an automatic rollback.
+        pub async fn observe(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ObserveRequest>,
+        ) -> std::result::Result<tonic::Response<super::ObserveResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/cortex.v1.CortexService/Observe",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("cortex.v1.CortexService", "Observe"));
+            self.inner.unary(req, path, codec).await
+        }
         /// Get graph statistics.
         pub async fn stats(
             &mut self,
@@ -905,6 +1325,31 @@ pub mod cortex_service_client {
                 .insert(GrpcMethod::new("cortex.v1.CortexService", "TriggerAutoLink"));
             self.inner.unary(req, path, codec).await
         }
+        /// Dry run: edges the auto-linker would create, without writing them.
+        pub async fn preview_auto_link(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PreviewAutoLinkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PreviewAutoLinkResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/cortex.v1.CortexService/PreviewAutoLink",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("cortex.v1.CortexService", "PreviewAutoLink"));
+            self.inner.unary(req, path, codec).await
+        }
         /// Reindex all embeddings (model change).
         pub async fn reindex(
             &mut self,
@@ -930,6 +1375,31 @@ pub mod cortex_service_client {
                 .insert(GrpcMethod::new("cortex.v1.CortexService", "Reindex"));
             self.inner.unary(req, path, codec).await
         }
+        /// Write a consistent point-in-time copy of the database while serving.
+        pub async fn snapshot(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SnapshotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SnapshotResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/cortex.v1.CortexService/Snapshot",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("cortex.v1.CortexService", "Snapshot"));
+            self.inner.unary(req, path, codec).await
+        }
         /// Health check.
         pub async fn health(
             &mut self,
@@ -987,6 +1457,11 @@ pub mod cortex_service_server {
             &self,
             request: tonic::Request<super::DeleteNodeRequest>,
         ) -> std::result::Result<tonic::Response<super::DeleteResponse>, tonic::Status>;
+        /// Undo a soft delete.
+        async fn restore_node(
+            &self,
+            request: tonic::Request<super::RestoreNodeRequest>,
+        ) -> std::result::Result<tonic::Response<super::NodeResponse>, tonic::Status>;
         /// List nodes with filtering.
         async fn list_nodes(
             &self,
@@ -1021,6 +1496,20 @@ pub mod cortex_service_server {
             tonic::Response<super::SubgraphResponse>,
             tonic::Status,
         >;
+        /// Server streaming response type for the TraverseStream method.
+        type TraverseStreamStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::TraverseChunk, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// Traverse breadth-first, streaming one chunk per depth level.
+        async fn traverse_stream(
+            &self,
+            request: tonic::Request<super::TraverseRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::TraverseStreamStream>,
+            tonic::Status,
+        >;
         /// Find paths between two nodes.
         async fn find_paths(
             &self,
@@ -1047,6 +1536,12 @@ pub mod cortex_service_server {
             tonic::Response<super::HybridSearchResponse>,
             tonic::Status,
         >;
+        /// Structured filter query (e.g. "kind:fact tag:infra importance>=0.7"),
+        /// optionally re-ranked by similarity to a text.
+        async fn query_nodes(
+            &self,
+            request: tonic::Request<super::QueryNodesRequest>,
+        ) -> std::result::Result<tonic::Response<super::SearchResponse>, tonic::Status>;
         /// Get a synthesised context briefing for an agent.
         async fn get_briefing(
             &self,
@@ -1055,6 +1550,12 @@ pub mod cortex_service_server {
             tonic::Response<super::BriefingResponse>,
             tonic::Status,
         >;
+        /// Record how a prompt variant performed for an agent. Updates the
+        /// agent's `uses` edge weight and may trigger an automatic rollback.
+        async fn observe(
+            &self,
+            request: tonic::Request<super::ObserveRequest>,
+        ) -> std::result::Result<tonic::Response<super::ObserveResponse>, tonic::Status>;
         /// Get graph statistics.
         async fn stats(
             &self,
@@ -1076,11 +1577,27 @@ pub mod cortex_service_server {
             tonic::Response<super::TriggerAutoLinkResponse>,
             tonic::Status,
         >;
+        /// Dry run: edges the auto-linker would create, without writing them.
+        async fn preview_auto_link(
+            &self,
+            request: tonic::Request<super::PreviewAutoLinkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PreviewAutoLinkResponse>,
+            tonic::Status,
+        >;
         /// Reindex all embeddings (model change).
         async fn reindex(
             &self,
             request: tonic::Request<super::ReindexRequest>,
         ) -> std::result::Result<tonic::Response<super::ReindexResponse>, tonic::Status>;
+        /// Write a consistent point-in-time copy of the database while serving.
+        async fn snapshot(
+            &self,
+            request: tonic::Request<super::SnapshotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SnapshotResponse>,
+            tonic::Status,
+        >;
         /// Health check.
         async fn health(
             &self,
@@ -1343,6 +1860,51 @@ pub mod cortex_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/cortex.v1.CortexService/RestoreNode" => {
+                    #[allow(non_camel_case_types)]
+                    struct RestoreNodeSvc<T: CortexService>(pub Arc<T>);
+                    impl<
+                        T: CortexService,
+                    > tonic::server::UnaryService<super::RestoreNodeRequest>
+                    for RestoreNodeSvc<T> {
+                        type Response = super::NodeResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RestoreNodeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CortexService>::restore_node(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RestoreNodeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/cortex.v1.CortexService/ListNodes" => {
                     #[allow(non_camel_case_types)]
                     struct ListNodesSvc<T: CortexService>(pub Arc<T>);
@@ -1568,6 +2130,53 @@ pub mod cortex_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/cortex.v1.CortexService/TraverseStream" => {
+                    #[allow(non_camel_case_types)]
+                    struct TraverseStreamSvc<T: CortexService>(pub Arc<T>);
+                    impl<
+                        T: CortexService,
+                    > tonic::server::ServerStreamingService<super::TraverseRequest>
+                    for TraverseStreamSvc<T> {
+                        type Response = super::TraverseChunk;
+                        type ResponseStream = T::TraverseStreamStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TraverseRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CortexService>::traverse_stream(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = TraverseStreamSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/cortex.v1.CortexService/FindPaths" => {
                     #[allow(non_camel_case_types)]
                     struct FindPathsSvc<T: CortexService>(pub Arc<T>);
@@ -1749,6 +2358,52 @@ pub mod cortex_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/cortex.v1.CortexService/QueryNodes" => {
+                    #[allow(non_camel_case_types)]
+                    struct QueryNodesSvc<T: CortexService>(pub Arc<T>);
+                    impl<
+                        T: CortexService,
+                    > tonic::server::UnaryService<super::QueryNodesRequest>
+                    for QueryNodesSvc<T> {
+                        type Response = super::SearchResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QueryNodesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CortexService>::query_nodes(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = QueryNodesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/cortex.v1.CortexService/GetBriefing" => {
                     #[allow(non_camel_case_types)]
                     struct GetBriefingSvc<T: CortexService>(pub Arc<T>);
@@ -1794,6 +2449,51 @@ pub mod cortex_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/cortex.v1.CortexService/Observe" => {
+                    #[allow(non_camel_case_types)]
+                    struct ObserveSvc<T: CortexService>(pub Arc<T>);
+                    impl<
+                        T: CortexService,
+                    > tonic::server::UnaryService<super::ObserveRequest>
+                    for ObserveSvc<T> {
+                        type Response = super::ObserveResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ObserveRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CortexService>::observe(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ObserveSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/cortex.v1.CortexService/Stats" => {
                     #[allow(non_camel_case_types)]
                     struct StatsSvc<T: CortexService>(pub Arc<T>);
@@ -1930,6 +2630,52 @@ pub mod cortex_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/cortex.v1.CortexService/PreviewAutoLink" => {
+                    #[allow(non_camel_case_types)]
+                    struct PreviewAutoLinkSvc<T: CortexService>(pub Arc<T>);
+                    impl<
+                        T: CortexService,
+                    > tonic::server::UnaryService<super::PreviewAutoLinkRequest>
+                    for PreviewAutoLinkSvc<T> {
+                        type Response = super::PreviewAutoLinkResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PreviewAutoLinkRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CortexService>::preview_auto_link(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PreviewAutoLinkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/cortex.v1.CortexService/Reindex" => {
                     #[allow(non_camel_case_types)]
                     struct ReindexSvc<T: CortexService>(pub Arc<T>);
@@ -1975,6 +2721,51 @@ pub mod cortex_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/cortex.v1.CortexService/Snapshot" => {
+                    #[allow(non_camel_case_types)]
+                    struct SnapshotSvc<T: CortexService>(pub Arc<T>);
+                    impl<
+                        T: CortexService,
+                    > tonic::server::UnaryService<super::SnapshotRequest>
+                    for SnapshotSvc<T> {
+                        type Response = super::SnapshotResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SnapshotRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CortexService>::snapshot(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SnapshotSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/cortex.v1.CortexService/Health" => {
                     #[allow(non_camel_case_types)]
                     struct HealthSvc<T: CortexService>(pub Arc<T>);
@@ -16,5 +16,9 @@ pub mod cortex {
 
 pub use cortex::v1::*;
 
+/// Encoded `FileDescriptorSet` for `cortex.proto`, for gRPC reflection.
+#[cfg(feature = "reflection")]
+pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("cortex_descriptor");
+
 // Re-export prost_types so generated code can find it
 pub use prost_types;
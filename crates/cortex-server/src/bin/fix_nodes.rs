@@ -117,6 +117,9 @@ fn main() -> anyhow::Result<()> {
                         created_at: old.created_at,
                         updated_at: old.updated_at,
                         deleted: old.deleted,
+                        confidence: 1.0,
+                        expires_at: None,
+                        deleted_at: old.deleted.then_some(old.updated_at),
                     };
 
                     let new_bytes = bincode::serialize(&new_node)?;
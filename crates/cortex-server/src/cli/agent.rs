@@ -1,27 +1,9 @@
 use super::{
-    AgentBindArgs, AgentCommands, AgentHistoryArgs, AgentListArgs, AgentObserveArgs,
+    http_base, AgentBindArgs, AgentCommands, AgentHistoryArgs, AgentListArgs, AgentObserveArgs,
     AgentResolveArgs, AgentSelectArgs, AgentShowArgs, AgentUnbindArgs,
 };
 use anyhow::Result;
 
-/// Derive the HTTP base URL from the gRPC server address by swapping the port.
-/// The gRPC addr defaults to :9090 and HTTP to :9091.
-fn http_base(server: &str) -> String {
-    // server is like "http://localhost:9090" — replace the gRPC port with HTTP port
-    if let Some(stripped) = server.strip_suffix(":9090") {
-        format!("{}:9091", stripped)
-    } else {
-        // Best-effort: assume the HTTP server is on port 9091 of the same host
-        let host = server
-            .trim_start_matches("http://")
-            .trim_start_matches("https://")
-            .split(':')
-            .next()
-            .unwrap_or("localhost");
-        format!("http://{}:9091", host)
-    }
-}
-
 pub async fn run(cmd: AgentCommands, server: &str) -> Result<()> {
     let base = http_base(server);
     match cmd {
@@ -205,7 +187,7 @@ async fn resolve(args: AgentResolveArgs, base: &str) -> Result<()> {
 async fn select(args: AgentSelectArgs, base: &str) -> Result<()> {
     let client = reqwest::Client::new();
     let url = format!(
-        "{}/agents/{}/active-variant?sentiment={}&task_type={}&correction_rate={}&topic_shift={}&energy={}&epsilon={}",
+        "{}/agents/{}/active-variant?sentiment={}&task_type={}&correction_rate={}&topic_shift={}&energy={}&epsilon={}&strategy={}",
         base,
         args.name,
         args.sentiment,
@@ -214,6 +196,7 @@ async fn select(args: AgentSelectArgs, base: &str) -> Result<()> {
         args.topic_shift,
         args.energy,
         args.epsilon,
+        args.strategy,
     );
     let resp =
         client.get(&url).send().await.map_err(|e| {
@@ -246,6 +229,12 @@ async fn select(args: AgentSelectArgs, base: &str) -> Result<()> {
         println!("  Total score:   {:.3}", total);
         println!("  Edge weight:   {:.3}", edge_w);
         println!("  Context score: {:.3}", ctx);
+        if let Some(sampled) = sel["sampled_score"].as_f64() {
+            println!("  Sampled score: {:.3}", sampled);
+        }
+        if let Some(ucb) = sel["ucb_score"].as_f64() {
+            println!("  UCB score:     {:.3}", ucb);
+        }
         if swap {
             println!("  ⚡ Swap recommended (differs from current active variant)");
         }
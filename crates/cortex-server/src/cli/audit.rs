@@ -1,8 +1,8 @@
-use super::AuditArgs;
+use super::{AuditArgs, AuditCommands, AuditExportArgs};
 use crate::config::CortexConfig;
 use anyhow::Result;
 use chrono::{Duration, Utc};
-use cortex_core::policies::audit::AuditFilter;
+use cortex_core::policies::audit::{AuditAction, AuditFilter, AuditLog};
 use cortex_core::RedbStorage;
 use std::sync::Arc;
 
@@ -18,20 +18,17 @@ pub async fn run(args: AuditArgs, config: CortexConfig) -> Result<()> {
     let storage = RedbStorage::open(&db_path)?;
     let audit_log = Arc::new(storage.create_audit_log());
 
-    let since = args.since.as_deref().map(parse_duration).transpose()?;
-    let node_id = args
-        .node
-        .as_deref()
-        .map(|s| uuid::Uuid::parse_str(s).map_err(|_| anyhow::anyhow!("Invalid UUID: {}", s)))
-        .transpose()?;
+    if let Some(AuditCommands::Export(export)) = args.command {
+        return run_export(export, &audit_log);
+    }
 
-    let filter = AuditFilter {
-        since,
-        actor: args.actor.clone(),
-        node_id,
-        action: None,
-        limit: Some(args.limit),
-    };
+    let filter = build_filter(
+        args.since.as_deref(),
+        args.node.as_deref(),
+        args.actor.clone(),
+        args.action.as_deref(),
+        Some(args.limit),
+    )?;
 
     let entries = audit_log.query(filter)?;
 
@@ -67,6 +64,51 @@ pub async fn run(args: AuditArgs, config: CortexConfig) -> Result<()> {
     Ok(())
 }
 
+fn run_export(args: AuditExportArgs, audit_log: &AuditLog) -> Result<()> {
+    let filter = build_filter(
+        args.since.as_deref(),
+        args.node.as_deref(),
+        args.actor,
+        args.action.as_deref(),
+        None,
+    )?;
+
+    let written = match args.output {
+        Some(ref path) => {
+            let file = std::fs::File::create(path)?;
+            audit_log.export(std::io::BufWriter::new(file), filter)?
+        }
+        None => audit_log.export(std::io::BufWriter::new(std::io::stdout().lock()), filter)?,
+    };
+
+    if let Some(ref path) = args.output {
+        eprintln!("Exported {} entries to {}", written, path.display());
+    }
+    Ok(())
+}
+
+fn build_filter(
+    since: Option<&str>,
+    node: Option<&str>,
+    actor: Option<String>,
+    action: Option<&str>,
+    limit: Option<usize>,
+) -> Result<AuditFilter> {
+    let since = since.map(parse_duration).transpose()?;
+    let node_id = node
+        .map(|s| uuid::Uuid::parse_str(s).map_err(|_| anyhow::anyhow!("Invalid UUID: {}", s)))
+        .transpose()?;
+    let action = action.map(|s| s.parse::<AuditAction>()).transpose()?;
+
+    Ok(AuditFilter {
+        since,
+        actor,
+        node_id,
+        action,
+        limit,
+    })
+}
+
 /// Parse a human-readable duration like "24h", "7d", "1h30m" into a UTC timestamp.
 fn parse_duration(s: &str) -> Result<chrono::DateTime<Utc>> {
     let s = s.trim();
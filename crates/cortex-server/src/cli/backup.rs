@@ -1,43 +1,75 @@
-use crate::cli::{BackupArgs, RestoreArgs};
+use crate::cli::{grpc_connect, BackupArgs, RestoreArchiveArgs, RestoreArgs};
 use crate::config::CortexConfig;
 use anyhow::Result;
+use cortex_proto::SnapshotRequest;
 use std::path::Path;
 
-pub async fn run(args: BackupArgs, config: CortexConfig) -> Result<()> {
-    let db_path = config.db_path();
-
-    if !db_path.exists() {
-        anyhow::bail!("Database not found at {}", db_path.display());
+pub async fn run(args: BackupArgs, config: CortexConfig, server: &str) -> Result<()> {
+    if args.path.exists() {
+        anyhow::bail!("Backup destination {} already exists", args.path.display());
     }
-
-    println!(
-        "Creating backup: {} → {}",
-        db_path.display(),
-        args.path.display()
-    );
+    // The server may run from another working directory
+    let path = std::env::current_dir()?.join(&args.path);
 
     // Ensure destination directory exists
-    if let Some(parent) = args.path.parent() {
+    if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
     }
 
-    // Copy the redb file
-    std::fs::copy(&db_path, &args.path)?;
-
-    // Write SHA-256 checksum sidecar
-    let checksum = sha256_file(&args.path)?;
-    let checksum_path = args.path.with_extension("sha256");
-    std::fs::write(
-        &checksum_path,
-        format!("{}  {}\n", checksum, args.path.display()),
-    )?;
+    // A running server holds the database lock, so ask it for the snapshot;
+    // otherwise open the database directly.
+    let info = match grpc_connect(server).await {
+        Ok(mut client) => {
+            println!("Creating backup via {}: {}", server, path.display());
+            let resp = client
+                .snapshot(SnapshotRequest {
+                    path: path.display().to_string(),
+                })
+                .await?
+                .into_inner();
+            cortex_core::SnapshotInfo {
+                node_count: resp.node_count,
+                edge_count: resp.edge_count,
+                graph_version: Some(resp.graph_version),
+            }
+        }
+        Err(_) => {
+            let db_path = config.db_path();
+            if !db_path.exists() {
+                anyhow::bail!("Database not found at {}", db_path.display());
+            }
+            println!(
+                "Creating backup: {} → {}",
+                db_path.display(),
+                path.display()
+            );
+            let storage = cortex_core::RedbStorage::open(&db_path)?;
+            storage.snapshot_versioned(&path, None)?
+        }
+    };
 
     if args.encrypt {
         eprintln!("Warning: --encrypt not yet implemented (CORTEX_ENCRYPTION_KEY not supported)");
     }
 
-    println!("✅ Backup complete: {}", args.path.display());
-    println!("   Checksum: {} ({})", checksum, checksum_path.display());
+    println!("✅ Backup complete: {}", path.display());
+    println!("   {} nodes, {} edges", info.node_count, info.edge_count);
+    if let Some(version) = info.graph_version {
+        println!("   Graph version: {}", version);
+    }
+
+    // Write SHA-256 checksum sidecar. A remote server writes on its own host.
+    if path.exists() {
+        let checksum = sha256_file(&path)?;
+        let checksum_path = path.with_extension("sha256");
+        std::fs::write(
+            &checksum_path,
+            format!("{}  {}\n", checksum, path.display()),
+        )?;
+        println!("   Checksum: {} ({})", checksum, checksum_path.display());
+    } else {
+        eprintln!("Warning: snapshot was written on the server host; no checksum sidecar");
+    }
 
     Ok(())
 }
@@ -102,6 +134,31 @@ pub async fn run_restore(args: RestoreArgs, config: CortexConfig) -> Result<()>
     Ok(())
 }
 
+pub async fn run_restore_archive(args: RestoreArchiveArgs, config: CortexConfig) -> Result<()> {
+    if !args.file.exists() {
+        anyhow::bail!("Archive file not found: {}", args.file.display());
+    }
+
+    let storage = cortex_core::RedbStorage::open(config.db_path())?;
+    let summary = cortex_core::restore_archive(&storage, &args.file)?;
+
+    println!(
+        "✅ Restored {} nodes and {} edges from {}",
+        summary.nodes,
+        summary.edges,
+        args.file.display()
+    );
+    if summary.skipped_edges > 0 {
+        println!(
+            "   Skipped {} edges (missing endpoint or already present).",
+            summary.skipped_edges
+        );
+    }
+    println!("   Restart `cortex serve` to rebuild the vector index.");
+
+    Ok(())
+}
+
 fn sha256_file(path: &Path) -> Result<String> {
     use sha2::{Digest, Sha256};
     let mut file = std::fs::File::open(path)?;
@@ -9,23 +9,19 @@ pub async fn run(args: BriefingArgs, server: &str) -> Result<()> {
         .get_briefing(BriefingRequest {
             agent_id: args.agent_id,
             compact: args.compact,
+            format: if args.format == "json" {
+                "json".into()
+            } else {
+                String::new()
+            },
+            no_cache: args.no_cache,
         })
         .await?
         .into_inner();
 
     match args.format.as_str() {
-        "json" => {
-            println!(
-                "{}",
-                serde_json::json!({
-                    "agent_id": resp.agent_id,
-                    "rendered": resp.rendered,
-                    "generated_at": resp.generated_at,
-                    "nodes_consulted": resp.nodes_consulted,
-                    "cached": resp.cached,
-                })
-            );
-        }
+        // The server already rendered the structured briefing
+        "json" => println!("{}", resp.rendered),
         _ => {
             if resp.cached {
                 eprintln!("(cached, generated at {})", resp.generated_at);
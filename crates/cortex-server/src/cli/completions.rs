@@ -0,0 +1,37 @@
+use crate::cli::{Cli, CompletionsArgs};
+use anyhow::Result;
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::io::Write;
+
+/// Print the completion script for `args.shell` to stdout. The script is
+/// generated from the `Cli` definition, so new subcommands and flags are
+/// picked up without touching this file.
+pub fn run(args: CompletionsArgs) -> Result<()> {
+    write_completions(args.shell, &mut std::io::stdout());
+    Ok(())
+}
+
+fn write_completions(shell: Shell, out: &mut dyn Write) {
+    clap_complete::generate(shell, &mut Cli::command(), "cortex", out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_shell_generates_a_script() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let mut out = Vec::new();
+            write_completions(shell, &mut out);
+            let script = String::from_utf8(out).unwrap();
+            assert!(!script.is_empty(), "{shell} script is empty");
+            // Nested subcommands are included
+            assert!(
+                script.contains("prompt"),
+                "{shell} script lacks subcommands"
+            );
+        }
+    }
+}
@@ -7,6 +7,7 @@ pub async fn run(cmd: ConfigCommands, config_path: &Path) -> Result<()> {
     match cmd {
         ConfigCommands::Validate => validate(config_path),
         ConfigCommands::Show => show(config_path),
+        ConfigCommands::Kinds => kinds(config_path),
     }
 }
 
@@ -40,3 +41,40 @@ fn show(config_path: &Path) -> Result<()> {
     }
     Ok(())
 }
+
+fn kinds(config_path: &Path) -> Result<()> {
+    let config = CortexConfig::load_or_default(config_path);
+    let registry = config.schema.kind_registry();
+    let builtin = cortex_core::kinds::defaults::all();
+
+    println!(
+        "{:<16} {:<24} {:>10}  SOURCE",
+        "KIND", "DISPLAY NAME", "IMPORTANCE"
+    );
+    println!("{}", "─".repeat(62));
+    for kind in registry.iter() {
+        let importance = kind
+            .default_importance
+            .map(|i| format!("{:.2}", i))
+            .unwrap_or_else(|| "-".into());
+        let source = if builtin.contains(&kind.name) {
+            "built-in"
+        } else {
+            "config"
+        };
+        println!(
+            "{:<16} {:<24} {:>10}  {}",
+            kind.name.as_str(),
+            kind.display_name(),
+            importance,
+            source
+        );
+    }
+    println!("{}", "─".repeat(62));
+    if registry.is_strict() {
+        println!("Strict: writes with any other kind are rejected.");
+    } else {
+        println!("Lenient: other kinds are accepted (set schema.strict_kinds to reject them).");
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::cli::DiffArgs;
+use anyhow::{Context, Result};
+use cortex_core::{diff_storage, GraphDiff, RedbStorage};
+use std::path::{Path, PathBuf};
+
+pub async fn run(args: DiffArgs) -> Result<()> {
+    // Opening a redb file for reading still writes its header (and may run
+    // a repair), so work on copies and leave both originals untouched.
+    let tmp = ScratchDir::new()?;
+    let a = open_copy(&args.db_a, &tmp.path().join("a.redb"))?;
+    let b = open_copy(&args.db_b, &tmp.path().join("b.redb"))?;
+    let diff = diff_storage(&a, &b)?;
+
+    match args.format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&diff)?),
+        _ => print_summary(&args, &diff),
+    }
+    Ok(())
+}
+
+/// A directory under the system temp dir, removed with everything in it
+/// when dropped.
+pub(crate) struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    pub(crate) fn new() -> Result<Self> {
+        let path = std::env::temp_dir().join(format!("cortex-{}", uuid::Uuid::now_v7()));
+        std::fs::create_dir(&path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        Ok(Self(path))
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+pub(crate) fn open_copy(path: &Path, copy: &Path) -> Result<RedbStorage> {
+    if !path.is_file() {
+        anyhow::bail!("Database not found: {}", path.display());
+    }
+    std::fs::copy(path, copy).with_context(|| format!("Failed to copy {}", path.display()))?;
+    RedbStorage::open(copy).with_context(|| format!("Failed to open {}", path.display()))
+}
+
+fn print_summary(args: &DiffArgs, diff: &GraphDiff) {
+    println!();
+    println!("a: {}", args.db_a.display());
+    println!("b: {}", args.db_b.display());
+    println!("{}", "─".repeat(50));
+    if diff.is_empty() {
+        println!("No differences.");
+        return;
+    }
+
+    println!("Nodes only in a: {:>8}", diff.nodes_only_in_a.len());
+    println!("Nodes only in b: {:>8}", diff.nodes_only_in_b.len());
+    println!("Nodes changed:   {:>8}", diff.nodes_changed.len());
+    for change in diff.nodes_changed.iter().take(10) {
+        let fields: Vec<&str> = [
+            change.title.as_ref().map(|_| "title"),
+            change.body.as_ref().map(|_| "body"),
+            change.importance.as_ref().map(|_| "importance"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        println!("  {}  {}", change.id, fields.join(", "));
+    }
+    if diff.nodes_changed.len() > 10 {
+        println!("  ... and {} more", diff.nodes_changed.len() - 10);
+    }
+    println!("Edges only in a: {:>8}", diff.edges_only_in_a.len());
+    println!("Edges only in b: {:>8}", diff.edges_only_in_b.len());
+    println!("{}", "─".repeat(50));
+    println!("Use --format json for every id and changed value.");
+}
@@ -17,6 +17,7 @@ async fn create(args: EdgeCreateArgs, server: &str) -> Result<()> {
         to_id: args.to,
         relation: args.relation,
         weight: args.weight,
+        inverse: args.inverse,
     };
 
     let resp = client.create_edge(req).await?.into_inner();
@@ -30,6 +31,7 @@ async fn create(args: EdgeCreateArgs, server: &str) -> Result<()> {
                 "to_id": resp.to_id,
                 "relation": resp.relation,
                 "weight": resp.weight,
+                "inverse_id": (!resp.inverse_id.is_empty()).then_some(&resp.inverse_id),
             })
         );
     } else {
@@ -38,6 +40,9 @@ async fn create(args: EdgeCreateArgs, server: &str) -> Result<()> {
             "  {} --[{}]--> {} (weight: {:.2})",
             resp.from_id, resp.relation, resp.to_id, resp.weight
         );
+        if !resp.inverse_id.is_empty() {
+            println!("Created inverse edge {}", resp.inverse_id);
+        }
     }
 
     Ok(())
@@ -1,23 +1,137 @@
+use crate::cli::diff::{open_copy, ScratchDir};
 use crate::cli::{grpc_connect, ExportArgs};
+use crate::grpc::conversions::{datetime_to_timestamp, timestamp_to_datetime};
 use anyhow::Result;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use cortex_core::graph::export;
+use cortex_core::{
+    write_jsonl, Edge, EdgeProvenance, Node, NodeFilter, NodeKind, Relation, Source, Subgraph,
+};
+use cortex_proto::cortex_service_client::CortexServiceClient;
 use cortex_proto::*;
+use std::collections::HashSet;
 use std::io::Write;
+use std::path::Path;
+use tonic::transport::Channel;
+
+/// Nodes fetched per `ListNodes` call.
+const PAGE_SIZE: u32 = 500;
+
+/// Parse `--since`: a date (`2026-07-01`, from midnight UTC) or an RFC 3339
+/// timestamp.
+pub fn parse_since(s: &str) -> std::result::Result<DateTime<Utc>, String> {
+    parse_time(s, false)
+}
+
+/// Parse `--until`: a date (through the end of that day, UTC) or an RFC 3339
+/// timestamp.
+pub fn parse_until(s: &str) -> std::result::Result<DateTime<Utc>, String> {
+    parse_time(s, true)
+}
+
+fn parse_time(s: &str, end_of_day: bool) -> std::result::Result<DateTime<Utc>, String> {
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        let midnight = date.and_time(NaiveTime::MIN).and_utc();
+        return Ok(if end_of_day {
+            midnight + chrono::Duration::days(1) - chrono::Duration::nanoseconds(1)
+        } else {
+            midnight
+        });
+    }
+    s.parse::<DateTime<Utc>>()
+        .map_err(|_| format!("invalid date '{}': use YYYY-MM-DD or RFC 3339", s))
+}
+
+/// Which nodes to export. Every set field must match. The server applies
+/// the same filter; it is checked again here so a server that predates the
+/// date fields can't widen the export.
+#[derive(Debug, Default, Clone)]
+struct ExportFilter {
+    kind: Option<String>,
+    tags: Vec<String>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+}
+
+impl ExportFilter {
+    fn matches(&self, node: &NodeResponse) -> bool {
+        if self.kind.as_ref().is_some_and(|k| *k != node.kind) {
+            return false;
+        }
+        if !self.tags.iter().all(|t| node.tags.contains(t)) {
+            return false;
+        }
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        let Some(created) = node.created_at.map(timestamp_to_datetime) else {
+            return false;
+        };
+        self.since.is_none_or(|t| created >= t) && self.until.is_none_or(|t| created <= t)
+    }
+
+    fn node_filter(&self) -> Result<NodeFilter> {
+        let mut filter = NodeFilter::new();
+        if let Some(kind) = &self.kind {
+            filter = filter.with_kinds(vec![NodeKind::new(kind)?]);
+        }
+        if !self.tags.is_empty() {
+            filter = filter.with_all_tags(self.tags.clone());
+        }
+        filter.created_after = self.since;
+        filter.created_before = self.until;
+        Ok(filter)
+    }
+
+    fn request(&self) -> ListNodesRequest {
+        ListNodesRequest {
+            kind_filter: self.kind.iter().cloned().collect(),
+            tag_filter: self.tags.clone(),
+            all_tags: true,
+            created_after: self.since.map(datetime_to_timestamp),
+            created_before: self.until.map(datetime_to_timestamp),
+            limit: PAGE_SIZE,
+            ..Default::default()
+        }
+    }
+}
 
 pub async fn run(args: ExportArgs, server: &str) -> Result<()> {
+    let filter = ExportFilter {
+        kind: args.kind.clone(),
+        tags: args.tags.clone(),
+        since: args.since,
+        until: args.until,
+    };
+    if let Some(db) = &args.db {
+        if args.format != "jsonl" {
+            anyhow::bail!("--db only applies to --format jsonl");
+        }
+        return export_jsonl_file(&filter, db, &args);
+    }
     let mut client = grpc_connect(server).await?;
 
-    // Fetch all nodes
-    let kind_filter = args.kind.map(|k| vec![k]).unwrap_or_default();
-    let nodes_resp = client
-        .list_nodes(ListNodesRequest {
-            kind_filter,
-            limit: 100_000,
-            ..Default::default()
-        })
-        .await?
-        .into_inner();
+    if args.format == "jsonl" {
+        return export_jsonl(&mut client, &filter, &args).await;
+    }
 
-    let nodes = &nodes_resp.nodes;
+    let mut nodes = Vec::new();
+    let mut cursor = String::new();
+    loop {
+        let page = client
+            .list_nodes(ListNodesRequest {
+                cursor,
+                ..filter.request()
+            })
+            .await?
+            .into_inner();
+        nodes.extend(page.nodes.into_iter().filter(|n| filter.matches(n)));
+        if page.next_cursor.is_empty() {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+    let nodes = &nodes;
 
     // Fetch edges for each node (collect unique edges)
     let mut all_edge_ids = std::collections::HashSet::new();
@@ -38,25 +152,181 @@ pub async fn run(args: ExportArgs, server: &str) -> Result<()> {
             }
         }
     }
+    if args.subgraph {
+        all_edges = induced_edges(nodes, all_edges);
+    }
 
     let output = match args.format.as_str() {
         "json" => format_json(nodes, &all_edges)?,
-        "jsonl" => format_jsonl(nodes)?,
-        "dot" => format_dot(nodes, &all_edges),
-        "graphml" => format_graphml(nodes, &all_edges),
+        "dot" => export::to_dot(&to_subgraph(nodes, &all_edges)?),
+        "graphml" => export::to_graphml(&to_subgraph(nodes, &all_edges)?),
         other => anyhow::bail!("Unknown export format: {}", other),
     };
+    write_output(&args, &output)
+}
+
+/// Keep only the edges with both endpoints among `nodes`, so a `--subgraph`
+/// export has no edges to nodes it doesn't declare.
+fn induced_edges(nodes: &[NodeResponse], edges: Vec<EdgeResponse>) -> Vec<EdgeResponse> {
+    let ids: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+    edges
+        .into_iter()
+        .filter(|e| ids.contains(e.from_id.as_str()) && ids.contains(e.to_id.as_str()))
+        .collect()
+}
 
-    if let Some(out_path) = args.output {
-        std::fs::write(&out_path, &output)?;
+fn write_output(args: &ExportArgs, output: &str) -> Result<()> {
+    if let Some(out_path) = &args.output {
+        std::fs::write(out_path, output)?;
         println!("Exported to {}", out_path.display());
     } else {
         std::io::stdout().write_all(output.as_bytes())?;
     }
+    Ok(())
+}
 
+/// Write JSONL a page at a time, so memory stays flat however many nodes
+/// match. With `--edges`, edges between exported nodes follow the nodes.
+async fn export_jsonl(
+    client: &mut CortexServiceClient<Channel>,
+    filter: &ExportFilter,
+    args: &ExportArgs,
+) -> Result<()> {
+    let out: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(std::fs::File::create(path)?),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    let mut writer = JsonlWriter::new(std::io::BufWriter::new(out), args.edges);
+
+    let mut cursor = String::new();
+    loop {
+        let page = client
+            .list_nodes(ListNodesRequest {
+                cursor,
+                ..filter.request()
+            })
+            .await?
+            .into_inner();
+        writer.write_nodes(&page.nodes, filter)?;
+        if page.next_cursor.is_empty() {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+
+    if args.edges {
+        for id in writer.exported_ids() {
+            let edges = client
+                .get_edges(GetEdgesRequest {
+                    node_id: id,
+                    direction: "outgoing".into(),
+                })
+                .await?
+                .into_inner()
+                .edges;
+            writer.write_edges(&edges)?;
+        }
+    }
+
+    let (nodes, edges) = writer.finish()?;
+    print_summary(args, nodes, edges);
     Ok(())
 }
 
+/// `--db`: write whole records straight from a copy of the database file,
+/// with ids, timestamps, metadata and embeddings, so `cortex import`
+/// restores them exactly. A running server is left alone.
+fn export_jsonl_file(filter: &ExportFilter, db: &Path, args: &ExportArgs) -> Result<()> {
+    let tmp = ScratchDir::new()?;
+    let storage = open_copy(db, &tmp.path().join("export.redb"))?;
+
+    let out: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(std::fs::File::create(path)?),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    let mut out = std::io::BufWriter::new(out);
+    let counts = write_jsonl(&storage, filter.node_filter()?, args.edges, &mut out)?;
+    print_summary(args, counts.nodes, counts.edges);
+    Ok(())
+}
+
+fn print_summary(args: &ExportArgs, nodes: usize, edges: usize) {
+    if let Some(path) = &args.output {
+        println!(
+            "Exported {} nodes and {} edges to {}",
+            nodes,
+            edges,
+            path.display()
+        );
+    }
+}
+
+/// One JSON object per line: nodes (`"type": "node"`), then edges
+/// (`"type": "edge"`) whose endpoints were both exported.
+struct JsonlWriter<W: Write> {
+    out: W,
+    /// Ids written so far; kept only when edges are wanted.
+    ids: Option<HashSet<String>>,
+    nodes: usize,
+    edges: usize,
+}
+
+impl<W: Write> JsonlWriter<W> {
+    fn new(out: W, with_edges: bool) -> Self {
+        Self {
+            out,
+            ids: with_edges.then(HashSet::new),
+            nodes: 0,
+            edges: 0,
+        }
+    }
+
+    fn write_nodes(&mut self, nodes: &[NodeResponse], filter: &ExportFilter) -> Result<()> {
+        for node in nodes.iter().filter(|n| filter.matches(n)) {
+            let mut line = node_to_json(node);
+            line["type"] = "node".into();
+            line["created_at"] = node
+                .created_at
+                .map(|t| timestamp_to_datetime(t).to_rfc3339())
+                .into();
+            serde_json::to_writer(&mut self.out, &line)?;
+            self.out.write_all(b"\n")?;
+            if let Some(ids) = &mut self.ids {
+                ids.insert(node.id.clone());
+            }
+            self.nodes += 1;
+        }
+        Ok(())
+    }
+
+    fn exported_ids(&self) -> Vec<String> {
+        self.ids.iter().flatten().cloned().collect()
+    }
+
+    fn write_edges(&mut self, edges: &[EdgeResponse]) -> Result<()> {
+        let Some(ids) = &self.ids else {
+            return Ok(());
+        };
+        for edge in edges {
+            if !(ids.contains(&edge.from_id) && ids.contains(&edge.to_id)) {
+                continue;
+            }
+            let mut line = edge_to_json(edge);
+            line["type"] = "edge".into();
+            serde_json::to_writer(&mut self.out, &line)?;
+            self.out.write_all(b"\n")?;
+            self.edges += 1;
+        }
+        Ok(())
+    }
+
+    /// Flush and return the (nodes, edges) written.
+    fn finish(mut self) -> Result<(usize, usize)> {
+        self.out.flush()?;
+        Ok((self.nodes, self.edges))
+    }
+}
+
 fn format_json(nodes: &[NodeResponse], edges: &[EdgeResponse]) -> Result<String> {
     let node_vals: Vec<_> = nodes.iter().map(node_to_json).collect();
     let edge_vals: Vec<_> = edges.iter().map(edge_to_json).collect();
@@ -66,66 +336,40 @@ fn format_json(nodes: &[NodeResponse], edges: &[EdgeResponse]) -> Result<String>
     }))?)
 }
 
-fn format_jsonl(nodes: &[NodeResponse]) -> Result<String> {
-    let mut out = String::new();
-    for node in nodes {
-        out.push_str(&serde_json::to_string(&node_to_json(node))?);
-        out.push('\n');
+/// Rebuild the fetched records as a core subgraph so DOT/GraphML share the
+/// library exporters.
+fn to_subgraph(nodes: &[NodeResponse], edges: &[EdgeResponse]) -> Result<Subgraph> {
+    let mut subgraph = Subgraph::new();
+    for n in nodes {
+        let mut node = Node::new(
+            NodeKind::new(&n.kind)?,
+            n.title.clone(),
+            n.body.clone(),
+            Source {
+                agent: n.source_agent.clone(),
+                session: n.source_session.clone(),
+                channel: n.source_channel.clone(),
+            },
+            n.importance,
+        );
+        node.id = n.id.parse()?;
+        node.data.tags = n.tags.clone();
+        subgraph.nodes.insert(node.id, node);
     }
-    Ok(out)
-}
-
-fn format_dot(nodes: &[NodeResponse], edges: &[EdgeResponse]) -> String {
-    let mut out = String::from("digraph cortex {\n  rankdir=LR;\n");
-    for node in nodes {
-        let label = node.title.replace('"', "\\\"");
-        let id_short = &node.id[..8];
-        out.push_str(&format!(
-            "  \"{}\" [label=\"{}\\n[{}]\" shape=box];\n",
-            id_short, label, node.kind
-        ));
-    }
-    for edge in edges {
-        out.push_str(&format!(
-            "  \"{}\" -> \"{}\" [label=\"{}\"];\n",
-            &edge.from_id[..8],
-            &edge.to_id[..8],
-            edge.relation
-        ));
-    }
-    out.push_str("}\n");
-    out
-}
-
-fn format_graphml(nodes: &[NodeResponse], edges: &[EdgeResponse]) -> String {
-    let mut out = String::from(
-        r#"<?xml version="1.0" encoding="UTF-8"?>
-<graphml xmlns="http://graphml.graphdrawing.org/graphml">
-  <key id="kind" for="node" attr.name="kind" attr.type="string"/>
-  <key id="title" for="node" attr.name="title" attr.type="string"/>
-  <key id="importance" for="node" attr.name="importance" attr.type="float"/>
-  <key id="relation" for="edge" attr.name="relation" attr.type="string"/>
-  <key id="weight" for="edge" attr.name="weight" attr.type="float"/>
-  <graph id="cortex" edgedefault="directed">
-"#,
-    );
-
-    for node in nodes {
-        out.push_str(&format!(
-            "    <node id=\"{}\">\n      <data key=\"kind\">{}</data>\n      <data key=\"title\">{}</data>\n      <data key=\"importance\">{}</data>\n    </node>\n",
-            node.id, xml_escape(&node.kind), xml_escape(&node.title), node.importance
-        ));
-    }
-
-    for edge in edges {
-        out.push_str(&format!(
-            "    <edge source=\"{}\" target=\"{}\">\n      <data key=\"relation\">{}</data>\n      <data key=\"weight\">{}</data>\n    </edge>\n",
-            edge.from_id, edge.to_id, xml_escape(&edge.relation), edge.weight
-        ));
+    for e in edges {
+        let mut edge = Edge::new(
+            e.from_id.parse()?,
+            e.to_id.parse()?,
+            Relation::new(&e.relation)?,
+            e.weight,
+            EdgeProvenance::Manual {
+                created_by: String::new(),
+            },
+        );
+        edge.id = e.id.parse()?;
+        subgraph.edges.push(edge);
     }
-
-    out.push_str("  </graph>\n</graphml>\n");
-    out
+    Ok(subgraph)
 }
 
 fn node_to_json(n: &NodeResponse) -> serde_json::Value {
@@ -150,9 +394,156 @@ fn edge_to_json(e: &EdgeResponse) -> serde_json::Value {
     })
 }
 
-fn xml_escape(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cortex_core::Storage;
+
+    fn node(id: &str, kind: &str, created: &str) -> NodeResponse {
+        NodeResponse {
+            id: id.into(),
+            kind: kind.into(),
+            title: format!("{id} title"),
+            created_at: Some(datetime_to_timestamp(created.parse().unwrap())),
+            ..Default::default()
+        }
+    }
+
+    fn edge(id: &str, from: &str, to: &str) -> EdgeResponse {
+        EdgeResponse {
+            id: id.into(),
+            from_id: from.into(),
+            to_id: to.into(),
+            relation: "related_to".into(),
+            weight: 1.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_date_bounds() {
+        assert_eq!(
+            parse_since("2026-07-01").unwrap(),
+            "2026-07-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        // A bare --until date covers that whole day
+        let until = parse_until("2026-09-30").unwrap();
+        assert!(until > "2026-09-30T23:59:59Z".parse::<DateTime<Utc>>().unwrap());
+        assert!(until < "2026-10-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap());
+        assert!(parse_since("2026-07-01T12:00:00+02:00").is_ok());
+        assert!(parse_since("last quarter").is_err());
+    }
+
+    #[test]
+    fn test_jsonl_export_keeps_only_nodes_in_range() {
+        let filter = ExportFilter {
+            kind: Some("fact".into()),
+            tags: Vec::new(),
+            since: Some(parse_since("2026-07-01").unwrap()),
+            until: Some(parse_until("2026-09-30").unwrap()),
+        };
+        // Two pages, as the server would return them
+        let pages = [
+            vec![
+                node("june", "fact", "2026-06-30T23:59:59Z"),
+                node("july", "fact", "2026-07-01T00:00:00Z"),
+                node("goal", "goal", "2026-08-15T10:00:00Z"),
+            ],
+            vec![
+                node("sept", "fact", "2026-09-30T18:30:00Z"),
+                node("oct", "fact", "2026-10-01T00:00:00Z"),
+            ],
+        ];
+
+        let mut out = Vec::new();
+        let mut writer = JsonlWriter::new(&mut out, true);
+        for page in &pages {
+            writer.write_nodes(page, &filter).unwrap();
+        }
+        let mut exported = writer.exported_ids();
+        exported.sort();
+        assert_eq!(exported, ["july", "sept"]);
+        writer
+            .write_edges(&[edge("in", "july", "sept"), edge("out", "july", "oct")])
+            .unwrap();
+        assert_eq!(writer.finish().unwrap(), (2, 1));
+
+        // Valid NDJSON: one object per line, newline-terminated
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.ends_with('\n'));
+        let lines: Vec<serde_json::Value> = text
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        let ids: Vec<&str> = lines.iter().map(|l| l["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, ["july", "sept", "in"]);
+        assert_eq!(lines[0]["type"], "node");
+        assert_eq!(lines[0]["created_at"], "2026-07-01T00:00:00+00:00");
+        assert_eq!(lines[2]["type"], "edge");
+    }
+
+    #[test]
+    fn test_subgraph_keeps_only_inner_edges() {
+        let nodes = [
+            node("lb", "infra", "2026-07-01T00:00:00Z"),
+            node("db", "infra", "2026-07-01T00:00:00Z"),
+        ];
+        let edges = vec![
+            edge("inner", "lb", "db"),
+            edge("out", "db", "decision"),
+            edge("in", "decision", "lb"),
+        ];
+        let kept: Vec<String> = induced_edges(&nodes, edges)
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(kept, ["inner"]);
+    }
+
+    #[test]
+    fn test_db_export_keeps_only_nodes_in_range() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = cortex_core::RedbStorage::open(dir.path().join("g.redb")).unwrap();
+        let node = |kind: &str, title: &str, created: &str| {
+            let mut node = Node::new(
+                NodeKind::new(kind).unwrap(),
+                title.into(),
+                String::new(),
+                Source {
+                    agent: "test".into(),
+                    session: None,
+                    channel: None,
+                },
+                0.5,
+            );
+            node.created_at = created.parse().unwrap();
+            storage.put_node(&node).unwrap();
+            node
+        };
+        node("fact", "june", "2026-06-30T23:59:59Z");
+        let july = node("fact", "july", "2026-07-01T00:00:00Z");
+        node("goal", "goal", "2026-08-15T10:00:00Z");
+        let sept = node("fact", "sept", "2026-09-30T18:30:00Z");
+        node("fact", "oct", "2026-10-01T00:00:00Z");
+
+        let filter = ExportFilter {
+            kind: Some("fact".into()),
+            tags: Vec::new(),
+            since: Some(parse_since("2026-07-01").unwrap()),
+            until: Some(parse_until("2026-09-30").unwrap()),
+        };
+        let mut out = Vec::new();
+        let counts = write_jsonl(&storage, filter.node_filter().unwrap(), true, &mut out).unwrap();
+        assert_eq!(counts.nodes, 2);
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.ends_with('\n'));
+        let ids: Vec<String> = text
+            .lines()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+            .inspect(|l| assert_eq!(l["type"], "node"))
+            .map(|l| l["id"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(ids, [sept.id.to_string(), july.id.to_string()]);
+    }
 }
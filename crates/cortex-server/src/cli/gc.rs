@@ -0,0 +1,89 @@
+use crate::cli::GcArgs;
+use crate::config::CortexConfig;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use cortex_core::{
+    EvictionMode, EvictionReason, RedbStorage, RetentionEngine, RetentionMaxNodes, Storage,
+};
+
+const DEFAULT_STRATEGY: &str = "oldest_lowest_importance";
+
+pub async fn run(args: GcArgs, config: CortexConfig) -> Result<()> {
+    let mut retention = config.retention.clone();
+    if let Some(limit) = args.max_nodes {
+        let strategy = retention
+            .max_nodes
+            .take()
+            .map(|m| m.strategy)
+            .unwrap_or_else(|| DEFAULT_STRATEGY.to_string());
+        retention.max_nodes = Some(RetentionMaxNodes { limit, strategy });
+    }
+    let archive = match &retention.eviction {
+        EvictionMode::Delete => None,
+        EvictionMode::Archive { path } => Some(path.clone()),
+    };
+
+    let db_path = config.db_path();
+    let storage = RedbStorage::open(&db_path).with_context(|| {
+        format!(
+            "Failed to open {} (stop the server using it first)",
+            db_path.display()
+        )
+    })?;
+    let engine = RetentionEngine::new(retention, config.score_decay.clone());
+    let before = storage.stats()?.node_count;
+    let planned = engine.plan(&storage)?;
+
+    if planned.is_empty() {
+        println!("Nothing to evict ({} live nodes).", before);
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "{:<10} {:>8} {:>10}  {:<12} {:<36}  TITLE",
+        "REASON", "AGE", "IMPORTANCE", "KIND", "ID"
+    );
+    println!("{}", "─".repeat(101));
+    let now = Utc::now();
+    for eviction in &planned {
+        let node = &eviction.node;
+        let reason = match eviction.reason {
+            EvictionReason::ExpiresAt => "expires_at",
+            EvictionReason::Expired => "expired",
+            EvictionReason::Ttl => "ttl",
+            EvictionReason::MaxNodes => "max_nodes",
+        };
+        let title: String = node.data.title.chars().take(40).collect();
+        println!(
+            "{:<10} {:>7}d {:>10.2}  {:<12} {:<36}  {}",
+            reason,
+            (now - node.created_at).num_days(),
+            node.importance,
+            node.kind.as_str(),
+            node.id,
+            title
+        );
+    }
+    println!("{}", "─".repeat(101));
+
+    if args.dry_run {
+        println!(
+            "Dry run: {} of {} nodes would be evicted. Nothing was changed.",
+            planned.len(),
+            before
+        );
+        return Ok(());
+    }
+
+    let evicted = engine.apply(&storage, &planned)?;
+    match archive {
+        Some(path) => println!("Archived {} nodes to {}", evicted, path.display()),
+        None => println!("Soft-deleted {} nodes", evicted),
+    }
+    println!(
+        "{} live nodes remain. The vector index is rebuilt from them when `cortex serve` next starts.",
+        storage.stats()?.node_count
+    );
+    Ok(())
+}
@@ -1,7 +1,12 @@
 use crate::cli::ImportArgs;
 use crate::config::CortexConfig;
+use crate::gate::{describe, GatedWrite};
+use crate::ingest::csv_import::{self, ColumnMap};
+use crate::ingest::{markdown, obsidian};
 use anyhow::{Context, Result};
 use cortex_core::*;
+use std::collections::HashSet;
+use std::io::BufRead;
 use std::sync::Arc;
 
 pub async fn run(args: ImportArgs, config: CortexConfig) -> Result<()> {
@@ -13,6 +18,9 @@ pub async fn run(args: ImportArgs, config: CortexConfig) -> Result<()> {
 
     // Determine format
     let format = args.format.clone().unwrap_or_else(|| {
+        if path.is_dir() {
+            return "obsidian".to_string();
+        }
         match path.extension().and_then(|e| e.to_str()) {
             Some("json") => "json",
             Some("jsonl") | Some("ndjson") => "jsonl",
@@ -25,16 +33,69 @@ pub async fn run(args: ImportArgs, config: CortexConfig) -> Result<()> {
 
     println!("Importing {} as {} format...", path.display(), format);
 
-    // Parse nodes from file
-    let nodes = match format.as_str() {
-        "json" => import_json(path, &args.source)?,
-        "jsonl" => import_jsonl(path, &args.source)?,
-        "csv" => import_csv(path, &args.source)?,
-        "markdown" => import_markdown(path, &args.source)?,
+    if format == "csv" {
+        return import_csv(path, &args, &config);
+    }
+    if format == "jsonl" && is_canonical_jsonl(path)? {
+        return import_canonical_jsonl(path, &args, &config);
+    }
+
+    // Parse nodes (and, for a vault, wikilink edges) from the input
+    let (nodes, edges) = match format.as_str() {
+        "json" => (import_json(path, &args.source)?, Vec::new()),
+        "jsonl" => (import_jsonl(path, &args.source)?, Vec::new()),
+        "markdown" => {
+            let content = std::fs::read_to_string(path)?;
+            let name = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("Untitled");
+            let doc =
+                markdown::import_document(name, &content, &args.source, args.split_by_heading)
+                    .with_context(|| format!("Failed to import {}", path.display()))?;
+            (doc.nodes, doc.edges)
+        }
+        "obsidian" => {
+            let vault = obsidian::import_vault(path, &args.source)?;
+            if !vault.unresolved.is_empty() {
+                println!("⚠️  {} unresolved links:", vault.unresolved.len());
+                for link in &vault.unresolved {
+                    println!("  {} → [[{}]]", link.from, link.target);
+                }
+            }
+            (vault.nodes, vault.edges)
+        }
         other => anyhow::bail!("Unknown format: {}", other),
     };
 
-    println!("Parsed {} nodes", nodes.len());
+    println!("Parsed {} nodes, {} edges", nodes.len(), edges.len());
+
+    // Files skip the rest of the write gate, but not its PII and schema checks
+    let schema =
+        SchemaValidator::new(config.schemas.clone()).with_kinds(config.schema.kind_registry());
+    let gate = GatedWrite::new(&config.write_gate, &schema).skip(true);
+    let mut refused = HashSet::new();
+    let nodes: Vec<Node> = nodes
+        .into_iter()
+        .filter_map(|mut node| {
+            let rejections = gate.screen(&mut node);
+            match gate.settle(&mut node, rejections) {
+                Ok(_) => Some(node),
+                Err(rejections) => {
+                    println!("  Refused {:?}: {}", node.data.title, describe(&rejections));
+                    refused.insert(node.id);
+                    None
+                }
+            }
+        })
+        .collect();
+    let edges: Vec<Edge> = edges
+        .into_iter()
+        .filter(|e| !refused.contains(&e.from) && !refused.contains(&e.to))
+        .collect();
+    if !refused.is_empty() {
+        println!("{} nodes refused by the write gate", refused.len());
+    }
 
     if args.dry_run {
         println!("Dry run — no changes written.");
@@ -66,7 +127,7 @@ pub async fn run(args: ImportArgs, config: CortexConfig) -> Result<()> {
                     continue;
                 }
                 if let Ok(mut idx) = vector_index.write() {
-                    let _ = idx.insert(node.id, &emb);
+                    let _ = idx.insert_node(&node, &emb);
                 }
                 imported += 1;
             }
@@ -83,7 +144,22 @@ pub async fn run(args: ImportArgs, config: CortexConfig) -> Result<()> {
         }
     }
 
-    println!("✅ Imported {} nodes ({} errors)", imported, errors);
+    // Edges last, so both endpoints are already stored
+    let mut linked = 0;
+    for edge in &edges {
+        match storage.put_edge(edge) {
+            Ok(()) => linked += 1,
+            Err(e) => {
+                eprintln!("  Error storing edge {} → {}: {}", edge.from, edge.to, e);
+                errors += 1;
+            }
+        }
+    }
+
+    println!(
+        "✅ Imported {} nodes, {} edges ({} errors)",
+        imported, linked, errors
+    );
 
     Ok(())
 }
@@ -95,17 +171,72 @@ fn import_json(path: &std::path::Path, source: &str) -> Result<Vec<Node>> {
     records.iter().map(|v| json_to_node(v, source)).collect()
 }
 
+/// Whether the first record of a JSONL file is a whole node or edge, as
+/// `cortex export --format jsonl --db` writes. Lines exported through the
+/// server carry the same `type` tag but only the response fields.
+fn is_canonical_jsonl(path: &std::path::Path) -> Result<bool> {
+    let file = std::io::BufReader::new(std::fs::File::open(path)?);
+    for line in file.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let v: serde_json::Value =
+            serde_json::from_str(&line).context("Failed to parse JSONL line")?;
+        return Ok(serde_json::from_value::<JsonlRecord>(v).is_ok());
+    }
+    Ok(false)
+}
+
+/// Restore a canonical export as is: same ids, timestamps, metadata and
+/// embeddings, and the edges between the nodes. Nothing is re-embedded.
+fn import_canonical_jsonl(
+    path: &std::path::Path,
+    args: &ImportArgs,
+    config: &CortexConfig,
+) -> Result<()> {
+    let input = std::io::BufReader::new(std::fs::File::open(path)?);
+
+    if args.dry_run {
+        let (mut nodes, mut edges) = (0, 0);
+        for (i, line) in input.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line).with_context(|| format!("line {}", i + 1))? {
+                JsonlRecord::Node(node) => {
+                    println!("  [{}] {} ({})", node.kind, node.data.title, node.id);
+                    nodes += 1;
+                }
+                JsonlRecord::Edge(_) => edges += 1,
+            }
+        }
+        println!("Parsed {} nodes, {} edges", nodes, edges);
+        println!("Dry run — no changes written.");
+        return Ok(());
+    }
+
+    let storage = RedbStorage::open(config.db_path())?;
+    let counts = read_jsonl(input, &storage)
+        .with_context(|| format!("Failed to import {}", path.display()))?;
+    println!("✅ Imported {} nodes, {} edges", counts.nodes, counts.edges);
+    Ok(())
+}
+
 fn import_jsonl(path: &std::path::Path, source: &str) -> Result<Vec<Node>> {
     let content = std::fs::read_to_string(path)?;
-    content
-        .lines()
-        .filter(|l| !l.trim().is_empty())
-        .map(|line| {
-            let v: serde_json::Value =
-                serde_json::from_str(line).context("Failed to parse JSONL line")?;
-            json_to_node(&v, source)
-        })
-        .collect()
+    let mut nodes = Vec::new();
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let v: serde_json::Value =
+            serde_json::from_str(line).context("Failed to parse JSONL line")?;
+        // `cortex export --edges` lines; nodes are re-created with new ids
+        if v["type"] == "edge" {
+            continue;
+        }
+        nodes.push(json_to_node(&v, source)?);
+    }
+    Ok(nodes)
 }
 
 fn json_to_node(v: &serde_json::Value, source: &str) -> Result<Node> {
@@ -144,64 +275,86 @@ fn json_to_node(v: &serde_json::Value, source: &str) -> Result<Node> {
     Ok(node)
 }
 
-fn import_csv(path: &std::path::Path, source: &str) -> Result<Vec<Node>> {
-    let mut rdr = csv::Reader::from_path(path)?;
-    let mut nodes = Vec::new();
+/// CSV rows go through the write gate before anything is written. Rows it
+/// refuses, and rows that don't map to a node, are reported rather than
+/// failing the import.
+fn import_csv(path: &std::path::Path, args: &ImportArgs, config: &CortexConfig) -> Result<()> {
+    let map = ColumnMap::parse(args.map.as_deref().unwrap_or(""))?;
+    let file = std::fs::File::open(path)?;
+    let rows = csv_import::read_rows(file, &map, &args.tag_delimiter, &args.source)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let total = rows.len();
 
-    for result in rdr.records() {
-        let record = result?;
-
-        let kind_str = record.get(0).unwrap_or("fact");
-        let title = record.get(1).unwrap_or("Untitled").to_string();
-        let body = record.get(2).unwrap_or(title.as_str()).to_string();
-        let tags_str = record.get(3).unwrap_or("");
-
-        let kind = NodeKind::new(kind_str)
-            .map_err(|e| anyhow::anyhow!("Invalid kind '{}': {}", kind_str, e))?;
-
-        let tags: Vec<String> = tags_str
-            .split(';')
-            .map(|t| t.trim().to_string())
-            .filter(|t| !t.is_empty())
-            .collect();
-
-        let mut node = Node::new(
-            kind,
-            title,
-            body,
-            Source {
-                agent: source.to_string(),
-                session: None,
-                channel: None,
-            },
-            0.5,
-        );
-        node.data.tags = tags;
-        nodes.push(node);
+    let storage = RedbStorage::open(config.db_path())?;
+    let embedding_service = FastEmbedService::new()?;
+    let index = load_index(&storage, embedding_service.dimension())?;
+    let schema =
+        SchemaValidator::new(config.schemas.clone()).with_kinds(config.schema.kind_registry());
+    let gated = csv_import::gate_rows(
+        rows,
+        &embedding_service,
+        &index,
+        &storage,
+        &GatedWrite::new(&config.write_gate, &schema),
+    )?;
+
+    if !gated.rejected.is_empty() {
+        println!("Rejected by the write gate:");
+        for row in &gated.rejected {
+            println!("  line {} {:?}:", row.line, row.title);
+            for reason in &row.reasons {
+                println!("    - {}", reason);
+            }
+        }
+    }
+    if !gated.invalid.is_empty() {
+        println!("Invalid rows:");
+        for row in &gated.invalid {
+            println!("  line {}: {}", row.line, row.reasons.join("; "));
+        }
     }
+    println!(
+        "{} rows: {} to import, {} rejected, {} invalid",
+        total,
+        gated.accepted.len(),
+        gated.rejected.len(),
+        gated.invalid.len()
+    );
 
-    Ok(nodes)
-}
+    if args.dry_run {
+        println!("Dry run — no changes written.");
+        return Ok(());
+    }
 
-fn import_markdown(path: &std::path::Path, source: &str) -> Result<Vec<Node>> {
-    let content = std::fs::read_to_string(path)?;
-    let title = path
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("Untitled")
-        .to_string();
-
-    let node = Node::new(
-        NodeKind::new("fact").unwrap(),
-        title,
-        content,
-        Source {
-            agent: source.to_string(),
-            session: None,
-            channel: None,
-        },
-        0.5,
-    );
+    let nodes: Vec<Node> = gated
+        .accepted
+        .into_iter()
+        .map(|(mut node, embedding)| {
+            node.embedding = Some(embedding);
+            node
+        })
+        .collect();
+    for chunk in nodes.chunks(100) {
+        storage.put_nodes_batch(chunk)?;
+    }
+    println!("✅ Imported {} nodes", nodes.len());
+    Ok(())
+}
 
-    Ok(vec![node])
+/// Index the embeddings already in storage, so the gate's conflict check
+/// sees existing nodes.
+fn load_index(storage: &RedbStorage, dimension: usize) -> Result<HnswIndex> {
+    let mut index = HnswIndex::new(dimension);
+    let mut indexed = 0;
+    for node in storage.list_nodes(NodeFilter::new())? {
+        if let Some(emb) = &node.embedding {
+            if index.insert_node(&node, emb).is_ok() {
+                indexed += 1;
+            }
+        }
+    }
+    if indexed > 0 {
+        index.rebuild()?;
+    }
+    Ok(index)
 }
@@ -81,11 +81,12 @@ pub async fn run() -> Result<()> {
             data_dir: std::path::PathBuf::from(&data_dir),
             nats_url: "nats://localhost:4222".into(),
             nats_enabled,
-            max_message_size: 16 * 1024 * 1024,
+            ..ServerConfig::default()
         },
         schema: SchemaConfig::default(),
         embedding: EmbeddingConfig {
             model: model_name.into(),
+            ..EmbeddingConfig::default()
         },
         auto_linker: AutoLinkerTomlConfig {
             enabled: autolinker,
@@ -110,12 +111,17 @@ pub async fn run() -> Result<()> {
             r
         },
         security: SecurityConfig::default(),
+        rate_limit: Default::default(),
         webhooks: vec![],
         plugins: vec![],
         prompt_rollback: Default::default(),
+        prompt_selection: Default::default(),
         score_decay: Default::default(),
         write_gate: Default::default(),
         schemas: Default::default(),
+        nats_publish: Default::default(),
+        warren_mappings: Default::default(),
+        graph_proximity: Default::default(),
     };
 
     let toml_str = toml::to_string_pretty(&config)?;
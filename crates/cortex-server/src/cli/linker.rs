@@ -0,0 +1,74 @@
+use crate::cli::{grpc_connect, truncate, LinkerCommands, LinkerPreviewArgs};
+use anyhow::Result;
+use cortex_proto::*;
+
+pub async fn run(cmd: LinkerCommands, server: &str) -> Result<()> {
+    match cmd {
+        LinkerCommands::Preview(args) => preview(args, server).await,
+    }
+}
+
+async fn preview(args: LinkerPreviewArgs, server: &str) -> Result<()> {
+    let mut client = grpc_connect(server).await?;
+
+    let resp = client
+        .preview_auto_link(PreviewAutoLinkRequest {
+            full: args.full,
+            limit: args.limit,
+        })
+        .await?
+        .into_inner();
+
+    if args.format == "json" {
+        let edges: Vec<_> = resp
+            .edges
+            .iter()
+            .map(|e| {
+                serde_json::json!({
+                    "from_id": e.from_id,
+                    "to_id": e.to_id,
+                    "from_title": e.from_title,
+                    "to_title": e.to_title,
+                    "relation": e.relation,
+                    "weight": e.weight,
+                    "rule": e.rule,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&edges)?);
+        return Ok(());
+    }
+
+    if resp.edges.is_empty() {
+        println!("(no edges would be created)");
+        return Ok(());
+    }
+
+    println!(
+        "{:<24}  {:<6}  {:<16}  {:<28}  {:<28}",
+        "RULE", "WEIGHT", "RELATION", "FROM", "TO"
+    );
+    println!("{}", "─".repeat(110));
+    for e in &resp.edges {
+        println!(
+            "{:<24}  {:<6.3}  {:<16}  {:<28}  {:<28}",
+            truncate(&e.rule, 24),
+            e.weight,
+            truncate(&e.relation, 16),
+            truncate(&e.from_title, 28),
+            truncate(&e.to_title, 28)
+        );
+    }
+    if resp.total as usize > resp.edges.len() {
+        println!(
+            "… {} more (showing {} of {}; use --limit)",
+            resp.total as usize - resp.edges.len(),
+            resp.edges.len(),
+            resp.total
+        );
+    } else {
+        println!("{} proposed edge(s)", resp.total);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::cli::diff::{open_copy, ScratchDir};
+use crate::cli::MergeArgs;
+use anyhow::{Context, Result};
+use cortex_core::{merge_storage, MergeConflict, MergeOptions, RedbStorage};
+
+pub async fn run(args: MergeArgs) -> Result<()> {
+    let options = MergeOptions {
+        on_conflict: match args.on_conflict.as_str() {
+            "keep-both" => MergeConflict::KeepBoth,
+            _ => MergeConflict::KeepNewest,
+        },
+        ..Default::default()
+    };
+
+    // Read the source from a copy, as `cortex diff` does, so it is never
+    // written to. A dry run merges into a copy of the target as well.
+    let tmp = ScratchDir::new()?;
+    let source = open_copy(&args.source, &tmp.path().join("source.redb"))?;
+    let target = if args.dry_run {
+        open_copy(&args.into, &tmp.path().join("target.redb"))?
+    } else {
+        if !args.into.is_file() {
+            anyhow::bail!("Database not found: {}", args.into.display());
+        }
+        RedbStorage::open(&args.into).with_context(|| {
+            format!(
+                "Failed to open {} (stop the server using it first)",
+                args.into.display()
+            )
+        })?
+    };
+
+    let report = merge_storage(&source, &target, &options)?;
+
+    println!();
+    println!(
+        "{} {} into {}",
+        if args.dry_run {
+            "Would merge"
+        } else {
+            "Merged"
+        },
+        args.source.display(),
+        args.into.display()
+    );
+    println!("{}", "─".repeat(50));
+    println!("Nodes added:   {:>8}", report.nodes_added);
+    println!("Nodes merged:  {:>8}", report.nodes_merged);
+    println!(
+        "Nodes skipped: {:>8}  (deleted in source)",
+        report.nodes_skipped
+    );
+    println!("Edges added:   {:>8}", report.edges_added);
+    println!(
+        "Edges skipped: {:>8}  (already present or dangling)",
+        report.edges_skipped
+    );
+    if !args.dry_run && report.nodes_added + report.nodes_merged > 0 {
+        println!();
+        println!("Restart the server on this database to index the new nodes.");
+    }
+    Ok(())
+}
@@ -1,9 +1,11 @@
 use crate::config::CortexConfig;
 use anyhow::Result;
-use redb::{Database, ReadableTable, TableDefinition};
+use redb::{Database, MultimapTableDefinition, ReadableTable, TableDefinition};
 
 const META: TableDefinition<&str, &[u8]> = TableDefinition::new("meta");
 const NODES: TableDefinition<&[u8; 16], &[u8]> = TableDefinition::new("nodes");
+const NODES_BY_TITLE: MultimapTableDefinition<(&str, &str), &[u8; 16]> =
+    MultimapTableDefinition::new("nodes_by_title");
 
 pub async fn run(config: CortexConfig) -> Result<()> {
     let db_path = config.db_path();
@@ -79,6 +81,10 @@ fn read_schema_version(path: &std::path::Path) -> Result<u32> {
 fn apply_migration(path: &std::path::Path, from: u32, to: u32) -> Result<()> {
     match (from, to) {
         (1, 2) => migrate_v1_to_v2(path),
+        (2, 3) => migrate_v2_to_v3(path),
+        (3, 4) => migrate_v3_to_v4(path),
+        (4, 5) => migrate_v4_to_v5(path),
+        (5, 6) => migrate_v5_to_v6(path),
         (f, t) => anyhow::bail!("No migration path from v{} to v{}", f, t),
     }
 }
@@ -86,8 +92,9 @@ fn apply_migration(path: &std::path::Path, from: u32, to: u32) -> Result<()> {
 fn migrate_v1_to_v2(path: &std::path::Path) -> Result<()> {
     // v1 → v2: NodeKind changed from u8 discriminant to UTF-8 string.
     //
-    // If the DB was created fresh with v2 code, all nodes already deserialize correctly.
-    // We just verify a sample node, then write the new schema version.
+    // If the DB was created fresh with v2 code, all nodes already deserialize
+    // with the v2 layout (the same as v3's). We just verify a sample node,
+    // then write the new schema version.
     //
     // If genuine v1 (enum-encoded) data exists, deserialization will fail.
     // In that case, the user must export with the v1 binary and re-import.
@@ -103,7 +110,7 @@ fn migrate_v1_to_v2(path: &std::path::Path) -> Result<()> {
         if let Some(entry) = iter.next() {
             let entry = entry?;
             let bytes = entry.1.value();
-            if cortex_core::storage::RedbStorage::try_deserialize_node(bytes).is_err() {
+            if cortex_core::storage::RedbStorage::try_deserialize_node_v3(bytes).is_err() {
                 anyhow::bail!(
                     "Database contains genuine v1 (enum-encoded) NodeKind data.\n\
                      To migrate: export data with the v1 binary, then re-import:\n\
@@ -126,3 +133,190 @@ fn migrate_v1_to_v2(path: &std::path::Path) -> Result<()> {
 
     Ok(())
 }
+
+fn migrate_v2_to_v3(path: &std::path::Path) -> Result<()> {
+    // v2 → v3: new nodes_by_title index, keyed by (kind, title) → node id.
+    // Backfill it from every readable node in one write transaction. Nodes
+    // are still in the v2 layout until v3 → v4 rewrites them.
+    let db = Database::create(path)?;
+
+    let write_txn = db.begin_write()?;
+    {
+        let nodes = write_txn.open_table(NODES)?;
+        let mut title_index = write_txn.open_multimap_table(NODES_BY_TITLE)?;
+        for entry in nodes.iter()? {
+            let (key, value) = entry?;
+            // Skip corrupt records, same as list_nodes
+            let Ok(node) =
+                cortex_core::storage::RedbStorage::try_deserialize_node_v3(value.value())
+            else {
+                continue;
+            };
+            title_index.insert((node.kind.as_str(), node.data.title.as_str()), key.value())?;
+        }
+
+        let mut meta = write_txn.open_table(META)?;
+        meta.insert("schema_version", "3".as_bytes())?;
+    }
+    write_txn.commit()?;
+
+    Ok(())
+}
+
+fn migrate_v3_to_v4(path: &std::path::Path) -> Result<()> {
+    // v3 → v4: Node gained `confidence`. bincode is positional, so every
+    // record is decoded with the v3 layout and rewritten. Keys and indexes
+    // are unchanged.
+    rewrite_nodes(
+        path,
+        4,
+        cortex_core::storage::RedbStorage::try_deserialize_node_v3,
+    )
+}
+
+fn migrate_v4_to_v5(path: &std::path::Path) -> Result<()> {
+    // v4 → v5: Node gained `expires_at`, rewritten the same way.
+    rewrite_nodes(
+        path,
+        5,
+        cortex_core::storage::RedbStorage::try_deserialize_node_v4,
+    )
+}
+
+fn migrate_v5_to_v6(path: &std::path::Path) -> Result<()> {
+    // v5 → v6: Node gained `deleted_at`. Existing tombstones take their
+    // `updated_at`, which is when `delete_node` stamped them.
+    rewrite_nodes(
+        path,
+        6,
+        cortex_core::storage::RedbStorage::try_deserialize_node_v5,
+    )
+}
+
+/// Rewrite every node record from an older layout into the current one and
+/// set the schema version to `version`.
+///
+/// Records already in the current layout are left alone: an earlier step of
+/// the same run wrote them, and since bincode ignores trailing bytes they
+/// would also decode (wrongly) with `decode_old`.
+fn rewrite_nodes(
+    path: &std::path::Path,
+    version: u32,
+    decode_old: fn(&[u8]) -> cortex_core::Result<cortex_core::Node>,
+) -> Result<()> {
+    use cortex_core::storage::RedbStorage;
+
+    let db = Database::create(path)?;
+
+    let write_txn = db.begin_write()?;
+    let (mut rewritten, mut skipped) = (0usize, 0usize);
+    {
+        let mut nodes = write_txn.open_table(NODES)?;
+        let mut records: Vec<([u8; 16], Vec<u8>)> = Vec::new();
+        for entry in nodes.iter()? {
+            let (key, value) = entry?;
+            records.push((*key.value(), value.value().to_vec()));
+        }
+
+        for (key, bytes) in records {
+            if RedbStorage::try_deserialize_node(&bytes).is_ok() {
+                continue;
+            }
+            // Skip corrupt records, same as list_nodes
+            let Ok(node) = decode_old(&bytes) else {
+                skipped += 1;
+                continue;
+            };
+            let bytes = bincode::serialize(&node)?;
+            nodes.insert(&key, bytes.as_slice())?;
+            rewritten += 1;
+        }
+
+        let mut meta = write_txn.open_table(META)?;
+        meta.insert("schema_version", version.to_string().as_bytes())?;
+    }
+    write_txn.commit()?;
+
+    print!(" {} nodes rewritten", rewritten);
+    if skipped > 0 {
+        print!(" ({} unreadable, left as is)", skipped);
+    }
+    print!(",");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cortex_core::{Node, NodeKind, RedbStorage, Source, Storage};
+
+    /// `Node` as schemas v2 and v3 wrote it, before `confidence`.
+    #[derive(serde::Serialize)]
+    struct NodeV2 {
+        id: cortex_core::NodeId,
+        kind: NodeKind,
+        data: cortex_core::NodeData,
+        embedding: Option<cortex_core::Embedding>,
+        source: Source,
+        importance: f32,
+        access_count: u64,
+        last_accessed_at: chrono::DateTime<chrono::Utc>,
+        created_at: chrono::DateTime<chrono::Utc>,
+        updated_at: chrono::DateTime<chrono::Utc>,
+        deleted: bool,
+    }
+
+    #[test]
+    fn v2_database_migrates_to_current() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("v2.redb");
+        let node = Node::new(
+            NodeKind::new("decision").unwrap(),
+            "Use redb for storage".into(),
+            "Single file, ACID.".into(),
+            Source {
+                agent: "test".into(),
+                session: None,
+                channel: None,
+            },
+            0.7,
+        );
+        let old = NodeV2 {
+            id: node.id,
+            kind: node.kind.clone(),
+            data: node.data.clone(),
+            embedding: None,
+            source: node.source.clone(),
+            importance: node.importance,
+            access_count: 0,
+            last_accessed_at: node.last_accessed_at,
+            created_at: node.created_at,
+            updated_at: node.updated_at,
+            deleted: false,
+        };
+        {
+            let db = Database::create(&path).unwrap();
+            let txn = db.begin_write().unwrap();
+            {
+                let mut nodes = txn.open_table(NODES).unwrap();
+                let bytes = bincode::serialize(&old).unwrap();
+                nodes.insert(node.id.as_bytes(), bytes.as_slice()).unwrap();
+                let mut meta = txn.open_table(META).unwrap();
+                meta.insert("schema_version", "2".as_bytes()).unwrap();
+            }
+            txn.commit().unwrap();
+        }
+
+        for from in 2..cortex_core::CURRENT_SCHEMA_VERSION {
+            apply_migration(&path, from, from + 1).unwrap();
+        }
+
+        let storage = RedbStorage::open(&path).unwrap();
+        let stored = storage.get_node(node.id).unwrap().unwrap();
+        assert_eq!(stored.data.title, "Use redb for storage");
+        let by_title = storage
+            .get_node_by_title(&node.kind, "Use redb for storage")
+            .unwrap();
+        assert_eq!(by_title.map(|n| n.id), Some(node.id));
+    }
+}
@@ -2,22 +2,33 @@ pub mod agent;
 pub mod audit;
 pub mod backup;
 pub mod briefing;
+pub mod completions;
 pub mod config_cmd;
+pub mod diff;
 pub mod doctor;
 pub mod edge;
 pub mod export;
+pub mod gc;
 pub mod import;
 pub mod init;
+pub mod linker;
+pub mod merge;
 pub mod migrate;
 pub mod node;
 pub mod prompt;
+pub mod query;
+pub mod reindex;
 pub mod search;
 pub mod security;
 pub mod shell;
 pub mod stats;
 pub mod traverse;
+pub mod verify;
+pub mod watch;
 
-use clap::{Args, Parser, Subcommand};
+use clap::builder::PossibleValue;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use cortex_core::prompt::SelectionStrategy;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -53,7 +64,7 @@ pub struct Cli {
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Start the gRPC + HTTP server
-    Serve,
+    Serve(ServeArgs),
     /// Interactive setup wizard
     Init,
     /// Interactive REPL
@@ -66,6 +77,8 @@ pub enum Commands {
     Edge(EdgeCommands),
     /// Search the graph
     Search(SearchArgs),
+    /// Find nodes with a filter expression, e.g. "kind:fact tag:infra importance>=0.7"
+    Query(QueryArgs),
     /// Graph traversal from a node
     Traverse(TraverseArgs),
     /// Find shortest path between two nodes
@@ -80,12 +93,29 @@ pub enum Commands {
     Backup(BackupArgs),
     /// Restore from backup
     Restore(RestoreArgs),
+    /// Re-insert nodes and edges from a retention eviction archive
+    RestoreArchive(RestoreArchiveArgs),
     /// Run schema migrations
     Migrate,
+    /// Run retention once: evict expired and over-quota nodes
+    Gc(GcArgs),
     /// Graph statistics
-    Stats,
+    Stats(StatsArgs),
+    /// Print graph changes as they happen
+    Watch(WatchArgs),
+    /// Compare two database files
+    Diff(DiffArgs),
+    /// Copy the nodes and edges of one database file into another
+    Merge(MergeArgs),
+    /// Rebuild the vector index from stored nodes
+    Reindex(ReindexArgs),
+    /// Auto-linker tools
+    #[command(subcommand)]
+    Linker(LinkerCommands),
     /// Diagnose issues
     Doctor,
+    /// Check graph integrity (dangling edges, bad embeddings, duplicates)
+    Verify(VerifyArgs),
     /// Configuration commands
     #[command(subcommand)]
     Config(ConfigCommands),
@@ -94,7 +124,7 @@ pub enum Commands {
     /// Security utilities (key generation, etc.)
     #[command(subcommand)]
     Security(SecurityCommands),
-    /// Start an MCP server (stdio transport for AI agent integration)
+    /// Start an MCP server (stdio or SSE transport for AI agent integration)
     Mcp(McpArgs),
     /// Agent ↔ prompt binding management
     #[command(subcommand)]
@@ -102,6 +132,8 @@ pub enum Commands {
     /// Prompt versioning, branching, and migration (PromptForge integration)
     #[command(subcommand)]
     Prompt(PromptCommands),
+    /// Print a shell completion script to stdout
+    Completions(CompletionsArgs),
 }
 
 // --- MCP args ---
@@ -115,6 +147,14 @@ pub struct McpArgs {
     /// Connect to a running Cortex server via gRPC instead of opening the database directly.
     #[arg(long)]
     pub server: Option<String>,
+
+    /// Transport: `stdio` (one client per process) or `sse` (HTTP, shared by many clients).
+    #[arg(long, default_value = "stdio", value_parser = ["stdio", "sse"])]
+    pub transport: String,
+
+    /// Address to listen on with `--transport sse`.
+    #[arg(long, default_value = "127.0.0.1:9092")]
+    pub bind: std::net::SocketAddr,
 }
 
 #[derive(Subcommand, Debug)]
@@ -123,6 +163,8 @@ pub enum NodeCommands {
     Get(NodeGetArgs),
     List(NodeListArgs),
     Delete(NodeDeleteArgs),
+    /// Undo a soft delete
+    Restore(NodeRestoreArgs),
     /// Show access-tracking stats for a node (access count, last accessed, decay info)
     Stats(NodeStatsArgs),
 }
@@ -133,16 +175,46 @@ pub enum EdgeCommands {
     List(EdgeListArgs),
 }
 
+#[derive(Subcommand, Debug)]
+pub enum LinkerCommands {
+    /// Show the edges the auto-linker would create, without creating them
+    Preview(LinkerPreviewArgs),
+}
+
 #[derive(Subcommand, Debug)]
 pub enum ConfigCommands {
     Validate,
     Show,
+    /// List registered node kinds
+    Kinds,
+}
+
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    /// Serve without writing: write endpoints are refused and background
+    /// linking, retention and ingest are off
+    #[arg(long)]
+    pub read_only: bool,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum SecurityCommands {
     /// Generate a new 256-bit AES encryption key
     GenerateKey,
+    /// Re-encrypt the database under a new key (stop the server first)
+    RotateKey(RotateKeyArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct RotateKeyArgs {
+    /// File holding the current base64 key, or `-` for stdin. Defaults to
+    /// `CORTEX_ENCRYPTION_KEY`
+    #[arg(long, value_name = "PATH")]
+    pub old_key_file: Option<PathBuf>,
+    /// File holding the replacement base64 key (e.g. from `cortex security
+    /// generate-key`), or `-` for stdin. Defaults to `CORTEX_NEW_ENCRYPTION_KEY`
+    #[arg(long, value_name = "PATH")]
+    pub new_key_file: Option<PathBuf>,
 }
 
 // --- Agent args ---
@@ -159,7 +231,7 @@ pub enum AgentCommands {
     Unbind(AgentUnbindArgs),
     /// Show the fully resolved effective prompt for an agent
     Resolve(AgentResolveArgs),
-    /// Select the best prompt variant for the current context (epsilon-greedy)
+    /// Select the best prompt variant for the current context (epsilon-greedy or Thompson sampling)
     Select(AgentSelectArgs),
     /// Show variant swap and performance history
     History(AgentHistoryArgs),
@@ -211,6 +283,37 @@ pub struct AgentResolveArgs {
     pub format: String,
 }
 
+/// `--strategy` values: the names and aliases [`SelectionStrategy`] parses.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrategyArg(pub SelectionStrategy);
+
+impl ValueEnum for StrategyArg {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Self(SelectionStrategy::EpsilonGreedy),
+            Self(SelectionStrategy::ThompsonSampling),
+            Self(SelectionStrategy::LinUcb),
+        ]
+    }
+
+    fn from_str(input: &str, _ignore_case: bool) -> Result<Self, String> {
+        input
+            .parse()
+            .map(Self)
+            .map_err(|e: cortex_core::CortexError| e.to_string())
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.0.as_str()).aliases(self.0.aliases()))
+    }
+}
+
+impl std::fmt::Display for StrategyArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 #[derive(Args, Debug)]
 pub struct AgentSelectArgs {
     /// Agent name
@@ -233,6 +336,9 @@ pub struct AgentSelectArgs {
     /// Exploration rate for epsilon-greedy (0.0 = always exploit)
     #[arg(long, default_value = "0.2")]
     pub epsilon: f32,
+    /// Selection strategy
+    #[arg(long, value_enum, default_value_t = StrategyArg::default())]
+    pub strategy: StrategyArg,
     /// Output format: table (default) | json
     #[arg(long, default_value = "table")]
     pub format: String,
@@ -282,6 +388,8 @@ pub enum PromptCommands {
     List(PromptListArgs),
     /// Show a prompt (resolved with inheritance by default)
     Get(PromptGetArgs),
+    /// Show section changes between two versions of a prompt
+    Diff(PromptDiffArgs),
     /// Import prompts from a migration JSON file
     Migrate(PromptMigrateArgs),
     /// Show aggregate performance metrics for a prompt variant
@@ -290,6 +398,8 @@ pub enum PromptCommands {
     Deploy(PromptDeployArgs),
     /// Show rollback status, cooldown, and quarantine state for a prompt
     RollbackStatus(PromptRollbackStatusArgs),
+    /// Roll a prompt back to an earlier version
+    Rollback(PromptRollbackArgs),
     /// Remove quarantine from a prompt version (allows re-evaluation)
     Unquarantine(PromptUnquarantineArgs),
 }
@@ -319,6 +429,24 @@ pub struct PromptGetArgs {
     pub format: String,
 }
 
+#[derive(Args, Debug)]
+pub struct PromptDiffArgs {
+    /// Prompt slug
+    pub slug: String,
+    /// Version to diff from
+    #[arg(long)]
+    pub from: u32,
+    /// Version to diff to
+    #[arg(long)]
+    pub to: u32,
+    /// Branch (default: main)
+    #[arg(long, default_value = "main")]
+    pub branch: String,
+    /// Output format: text (default) | json
+    #[arg(long, default_value = "text")]
+    pub format: String,
+}
+
 #[derive(Args, Debug)]
 pub struct PromptMigrateArgs {
     /// Path to migration JSON file
@@ -353,6 +481,9 @@ pub struct PromptDeployArgs {
     /// Number of recent observations to use for baseline (default: 20)
     #[arg(long, default_value = "20")]
     pub baseline_sample_size: usize,
+    /// Deploy as a canary on this percentage of selections (100 = full rollout)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub rollout: Option<u8>,
     /// Output format: table (default) | json
     #[arg(long, default_value = "table")]
     pub format: String,
@@ -370,6 +501,21 @@ pub struct PromptRollbackStatusArgs {
     pub format: String,
 }
 
+#[derive(Args, Debug)]
+pub struct PromptRollbackArgs {
+    /// Prompt slug
+    pub slug: String,
+    /// Version to roll back to
+    #[arg(long)]
+    pub to: u32,
+    /// Branch (default: main)
+    #[arg(long, default_value = "main")]
+    pub branch: String,
+    /// Output format: table (default) | json
+    #[arg(long, default_value = "table")]
+    pub format: String,
+}
+
 #[derive(Args, Debug)]
 pub struct PromptUnquarantineArgs {
     /// Prompt slug
@@ -383,6 +529,8 @@ pub struct PromptUnquarantineArgs {
 
 #[derive(Args, Debug)]
 pub struct AuditArgs {
+    #[command(subcommand)]
+    pub command: Option<AuditCommands>,
     /// Only show entries since this duration (e.g. "24h", "7d", "1h30m")
     #[arg(long)]
     pub since: Option<String>,
@@ -392,6 +540,9 @@ pub struct AuditArgs {
     /// Filter by actor name (e.g. "kai", "auto-linker")
     #[arg(long)]
     pub actor: Option<String>,
+    /// Filter by action (e.g. "node.created", "search", "traverse", "briefing")
+    #[arg(long)]
+    pub action: Option<String>,
     /// Output format: table (default) | json
     #[arg(long, default_value = "table")]
     pub format: String,
@@ -400,6 +551,31 @@ pub struct AuditArgs {
     pub limit: usize,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum AuditCommands {
+    /// Stream matching entries as newline-delimited JSON (no limit)
+    Export(AuditExportArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct AuditExportArgs {
+    /// Only export entries since this duration (e.g. "24h", "30d")
+    #[arg(long)]
+    pub since: Option<String>,
+    /// Filter by node/edge ID
+    #[arg(long)]
+    pub node: Option<String>,
+    /// Filter by actor name
+    #[arg(long)]
+    pub actor: Option<String>,
+    /// Filter by action (e.g. "node.created", "search")
+    #[arg(long)]
+    pub action: Option<String>,
+    /// Output file. Defaults to stdout.
+    #[arg(long, short = 'o')]
+    pub output: Option<PathBuf>,
+}
+
 // --- Node args ---
 
 #[derive(Args, Debug)]
@@ -410,8 +586,15 @@ pub struct NodeCreateArgs {
     pub title: String,
     #[arg(long)]
     pub body: Option<String>,
-    #[arg(long, default_value = "0.5")]
-    pub importance: f32,
+    /// 0.0-1.0 (default: the kind's default_importance, else 0.5)
+    #[arg(long)]
+    pub importance: Option<f32>,
+    /// How sure you are the content is true, 0.0-1.0 (default 1.0)
+    #[arg(long)]
+    pub confidence: Option<f32>,
+    /// RFC 3339 time after which the node is treated as expired
+    #[arg(long)]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
     #[arg(long, value_delimiter = ',')]
     pub tags: Vec<String>,
     /// Read body from stdin
@@ -437,6 +620,9 @@ pub struct NodeListArgs {
     pub limit: u32,
     #[arg(long)]
     pub source: Option<String>,
+    /// Also list soft-deleted nodes
+    #[arg(long)]
+    pub include_deleted: bool,
     #[arg(long, default_value = "table")]
     pub format: String,
 }
@@ -449,6 +635,11 @@ pub struct NodeDeleteArgs {
     pub yes: bool,
 }
 
+#[derive(Args, Debug)]
+pub struct NodeRestoreArgs {
+    pub id: String,
+}
+
 #[derive(Args, Debug)]
 pub struct NodeStatsArgs {
     pub id: String,
@@ -468,6 +659,10 @@ pub struct EdgeCreateArgs {
     pub relation: String,
     #[arg(long, default_value = "1.0")]
     pub weight: f32,
+    /// Also create the inverse edge when the relation is symmetric
+    /// (e.g. similar_to) or has a named inverse (supersedes → superseded_by)
+    #[arg(long)]
+    pub inverse: bool,
     #[arg(long, default_value = "table")]
     pub format: String,
 }
@@ -493,6 +688,31 @@ pub struct SearchArgs {
     /// Hybrid search (vector + graph)
     #[arg(long)]
     pub hybrid: bool,
+    /// With --hybrid, anchor on the top N vector hits and rank their graph neighbours
+    #[arg(long, value_name = "N", requires = "hybrid")]
+    pub auto_anchor: Option<u32>,
+    /// MMR diversity (0.0 = off). Higher values spread results across topics.
+    #[arg(long, default_value = "0.0")]
+    pub diversity: f32,
+    /// Also return nodes past their expiry
+    #[arg(long)]
+    pub include_expired: bool,
+    /// Also return soft-deleted nodes
+    #[arg(long)]
+    pub include_deleted: bool,
+    #[arg(long, default_value = "table")]
+    pub format: String,
+}
+
+// --- Query args ---
+
+#[derive(Args, Debug)]
+pub struct QueryArgs {
+    /// Filter expression, e.g. "kind:fact tag:infra importance>=0.7 after:2024-01-01"
+    pub query: String,
+    /// Rank the matches by similarity to this text
+    #[arg(long)]
+    pub like: Option<String>,
     #[arg(long, default_value = "table")]
     pub format: String,
 }
@@ -509,6 +729,12 @@ pub struct TraverseArgs {
     pub direction: String,
     #[arg(long)]
     pub relation: Option<String>,
+    /// Also return nodes past their expiry
+    #[arg(long)]
+    pub include_expired: bool,
+    /// Also return soft-deleted nodes
+    #[arg(long)]
+    pub include_deleted: bool,
     #[arg(long, default_value = "table")]
     pub format: String,
 }
@@ -519,6 +745,9 @@ pub struct PathArgs {
     pub to: String,
     #[arg(long, default_value = "5")]
     pub max_hops: u32,
+    /// Prefer strongly weighted chains over the fewest hops
+    #[arg(long)]
+    pub weighted: bool,
     #[arg(long, default_value = "table")]
     pub format: String,
 }
@@ -541,14 +770,25 @@ pub struct BriefingArgs {
 
 #[derive(Args, Debug)]
 pub struct ImportArgs {
+    /// File to import, or an Obsidian vault directory
     pub file: PathBuf,
-    /// "json", "jsonl", "csv", "markdown" — auto-detected if omitted
+    /// "json", "jsonl", "csv", "markdown", "obsidian" — auto-detected if omitted
     #[arg(long)]
     pub format: Option<String>,
     #[arg(long, default_value = "import")]
     pub source: String,
     #[arg(long)]
     pub dry_run: bool,
+    /// Markdown only: one node per `##` section, each `part_of` a parent node
+    #[arg(long)]
+    pub split_by_heading: bool,
+    /// CSV only: map columns to fields, e.g. `title=Name,body=Description,tags=Labels`.
+    /// Unmapped fields use a column named after the field.
+    #[arg(long)]
+    pub map: Option<String>,
+    /// CSV only: separator between tags in the tags column
+    #[arg(long, default_value = ";")]
+    pub tag_delimiter: String,
 }
 
 // --- Export args ---
@@ -562,6 +802,127 @@ pub struct ExportArgs {
     pub format: String,
     #[arg(long)]
     pub kind: Option<String>,
+    /// Only nodes with this tag (repeatable; nodes must have every tag)
+    #[arg(long = "tag")]
+    pub tags: Vec<String>,
+    /// Only nodes created on or after this date (YYYY-MM-DD) or RFC 3339 time
+    #[arg(long, value_parser = export::parse_since)]
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only nodes created on or before this date (inclusive) or RFC 3339 time
+    #[arg(long, value_parser = export::parse_until)]
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    /// With --format jsonl, also write the edges between exported nodes
+    #[arg(long)]
+    pub edges: bool,
+    /// Export the matching nodes and only the edges among them
+    #[arg(long)]
+    pub subgraph: bool,
+    /// With --format jsonl, read a copy of this database file instead of
+    /// the server and write whole records that `cortex import` restores
+    /// exactly
+    #[arg(long)]
+    pub db: Option<PathBuf>,
+}
+
+// --- Linker args ---
+
+#[derive(Args, Debug)]
+pub struct LinkerPreviewArgs {
+    /// Evaluate every node, not just those changed since the last cycle
+    #[arg(long)]
+    pub full: bool,
+    #[arg(long, default_value = "50")]
+    pub limit: u32,
+    #[arg(long, default_value = "table")]
+    pub format: String,
+}
+
+// --- Stats args ---
+
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+    /// List the N most central nodes by PageRank (default 10)
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "10")]
+    pub centrality: Option<u32>,
+    /// List clusters of tightly-connected nodes
+    #[arg(long)]
+    pub communities: bool,
+}
+
+// --- Reindex args ---
+
+#[derive(Args, Debug)]
+pub struct ReindexArgs {
+    /// Embed every node again instead of reusing stored embeddings
+    /// (needed after changing the embedding model)
+    #[arg(long)]
+    pub re_embed: bool,
+}
+
+// --- Verify args ---
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    /// Prune dangling edges and re-embed wrong-dimension nodes
+    #[arg(long)]
+    pub fix: bool,
+}
+
+// --- Diff args ---
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    pub db_a: PathBuf,
+    pub db_b: PathBuf,
+    /// Output format: summary (default) | json
+    #[arg(long, default_value = "summary")]
+    pub format: String,
+}
+
+// --- Merge args ---
+
+#[derive(Args, Debug)]
+pub struct MergeArgs {
+    /// Database to copy from (left untouched)
+    pub source: PathBuf,
+    /// Database to merge into
+    #[arg(long)]
+    pub into: PathBuf,
+    /// For nodes already in the target: keep-newest (merge into one) | keep-both
+    #[arg(long, default_value = "keep-newest", value_parser = ["keep-newest", "keep-both"])]
+    pub on_conflict: String,
+    /// Report what would happen without changing the target
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+// --- Watch args ---
+
+#[derive(Args, Debug)]
+pub struct WatchArgs {
+    /// Only show nodes of this kind
+    #[arg(long)]
+    pub kind: Option<String>,
+    /// Only show nodes written by this agent
+    #[arg(long)]
+    pub actor: Option<String>,
+    /// Poll the database file instead of streaming from the server
+    #[arg(long)]
+    pub embedded: bool,
+    /// Poll interval in milliseconds for --embedded
+    #[arg(long, default_value = "1000")]
+    pub interval: u64,
+    /// Never colour the output
+    #[arg(long)]
+    pub no_color: bool,
+}
+
+// --- Completions args ---
+
+#[derive(Args, Debug)]
+pub struct CompletionsArgs {
+    #[arg(value_enum)]
+    pub shell: clap_complete::Shell,
 }
 
 // --- Backup / Restore args ---
@@ -580,6 +941,24 @@ pub struct RestoreArgs {
     pub yes: bool,
 }
 
+#[derive(Args, Debug)]
+pub struct RestoreArchiveArgs {
+    /// JSONL archive written by `[retention.eviction] mode = "archive"`
+    pub file: PathBuf,
+}
+
+// --- GC args ---
+
+#[derive(Args, Debug)]
+pub struct GcArgs {
+    /// List what would be evicted without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Cap live nodes at N, overriding `retention.max_nodes.limit`
+    #[arg(long, value_name = "N")]
+    pub max_nodes: Option<usize>,
+}
+
 // --- gRPC client helper ---
 
 use cortex_proto::cortex_service_client::CortexServiceClient;
@@ -597,6 +976,24 @@ pub async fn grpc_connect(server: &str) -> anyhow::Result<CortexServiceClient<Ch
         })
 }
 
+/// Derive the HTTP base URL from the gRPC server address by swapping the port.
+/// The gRPC addr defaults to :9090 and HTTP to :9091.
+pub fn http_base(server: &str) -> String {
+    // server is like "http://localhost:9090" — replace the gRPC port with HTTP port
+    if let Some(stripped) = server.strip_suffix(":9090") {
+        format!("{}:9091", stripped)
+    } else {
+        // Best-effort: assume the HTTP server is on port 9091 of the same host
+        let host = server
+            .trim_start_matches("http://")
+            .trim_start_matches("https://")
+            .split(':')
+            .next()
+            .unwrap_or("localhost");
+        format!("http://{}:9091", host)
+    }
+}
+
 // --- Table printing helpers ---
 
 pub fn print_node_table(nodes: &[cortex_proto::NodeResponse]) {
@@ -607,6 +1004,7 @@ pub fn print_node_table(nodes: &[cortex_proto::NodeResponse]) {
     println!("{:<36}  {:<12}  {:<6}  TITLE", "ID", "KIND", "IMP");
     println!("{}", "─".repeat(80));
     for n in nodes {
+        shell::remember_node(&n.id, &n.title);
         let title = truncate(&n.title, 40);
         println!(
             "{:<36}  {:<12}  {:<6.2}  {}",
@@ -1,6 +1,6 @@
 use crate::cli::{
     grpc_connect, print_node_table, NodeCommands, NodeCreateArgs, NodeDeleteArgs, NodeGetArgs,
-    NodeListArgs, NodeStatsArgs,
+    NodeListArgs, NodeRestoreArgs, NodeStatsArgs,
 };
 use anyhow::Result;
 use cortex_proto::*;
@@ -12,6 +12,7 @@ pub async fn run(cmd: NodeCommands, server: &str) -> Result<()> {
         NodeCommands::Get(args) => get(args, server).await,
         NodeCommands::List(args) => list(args, server).await,
         NodeCommands::Delete(args) => delete(args, server).await,
+        NodeCommands::Restore(args) => restore(args, server).await,
         NodeCommands::Stats(args) => stats(args, server).await,
     }
 }
@@ -33,6 +34,11 @@ async fn create(args: NodeCreateArgs, server: &str) -> Result<()> {
         title: args.title,
         body,
         importance: args.importance,
+        confidence: args.confidence,
+        expires_at: args.expires_at.map(|t| prost_types::Timestamp {
+            seconds: t.timestamp(),
+            nanos: t.timestamp_subsec_nanos() as i32,
+        }),
         tags: args.tags,
         source_agent: "cli".into(),
         ..Default::default()
@@ -74,6 +80,9 @@ async fn get(args: NodeGetArgs, server: &str) -> Result<()> {
                 "title": resp.title,
                 "body": resp.body,
                 "importance": resp.importance,
+                "confidence": resp.confidence,
+                "expires_at": resp.expires_at.as_ref().map(|t| fmt_timestamp(Some(t))),
+                "deleted_at": resp.deleted_at.as_ref().map(|t| fmt_timestamp(Some(t))),
                 "tags": resp.tags,
                 "source_agent": resp.source_agent,
                 "access_count": resp.access_count,
@@ -98,6 +107,7 @@ async fn list(args: NodeListArgs, server: &str) -> Result<()> {
             kind_filter,
             source_agent,
             limit: args.limit,
+            include_deleted: args.include_deleted,
             ..Default::default()
         })
         .await?
@@ -146,7 +156,10 @@ async fn delete(args: NodeDeleteArgs, server: &str) -> Result<()> {
         .into_inner();
 
     if resp.success {
-        println!("Deleted node {}", args.id);
+        println!(
+            "Deleted node {} (undo with `cortex node restore {}`)",
+            args.id, args.id
+        );
     } else {
         println!("Node {} not found", args.id);
     }
@@ -154,6 +167,17 @@ async fn delete(args: NodeDeleteArgs, server: &str) -> Result<()> {
     Ok(())
 }
 
+async fn restore(args: NodeRestoreArgs, server: &str) -> Result<()> {
+    let mut client = grpc_connect(server).await?;
+    let resp = client
+        .restore_node(RestoreNodeRequest { id: args.id })
+        .await?
+        .into_inner();
+
+    println!("Restored node {} ({})", resp.id, resp.title);
+    Ok(())
+}
+
 async fn stats(args: NodeStatsArgs, server: &str) -> Result<()> {
     use cortex_proto::GetNodeRequest;
 
@@ -222,11 +246,19 @@ fn days_since(ts: Option<&prost_types::Timestamp>) -> Option<f64> {
 }
 
 pub fn print_node_detail(n: &NodeResponse) {
+    crate::cli::shell::remember_node(&n.id, &n.title);
     println!("ID:         {}", n.id);
     println!("Kind:       {}", n.kind);
     println!("Title:      {}", n.title);
     println!("Body:       {}", crate::cli::truncate(&n.body, 120));
     println!("Importance: {:.2}", n.importance);
+    println!("Confidence: {:.2}", n.confidence);
+    if n.expires_at.is_some() {
+        println!("Expires:    {}", fmt_timestamp(n.expires_at.as_ref()));
+    }
+    if n.deleted_at.is_some() {
+        println!("Deleted:    {}", fmt_timestamp(n.deleted_at.as_ref()));
+    }
     println!("Tags:       {}", n.tags.join(", "));
     println!("Source:     {}", n.source_agent);
     println!("Access:     {}", n.access_count);
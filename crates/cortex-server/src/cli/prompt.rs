@@ -1,6 +1,7 @@
 use super::{
-    PromptCommands, PromptDeployArgs, PromptGetArgs, PromptListArgs, PromptMigrateArgs,
-    PromptPerformanceArgs, PromptRollbackStatusArgs, PromptUnquarantineArgs,
+    PromptCommands, PromptDeployArgs, PromptDiffArgs, PromptGetArgs, PromptListArgs,
+    PromptMigrateArgs, PromptPerformanceArgs, PromptRollbackArgs, PromptRollbackStatusArgs,
+    PromptUnquarantineArgs,
 };
 use crate::config::CortexConfig;
 use anyhow::Result;
@@ -15,10 +16,12 @@ pub async fn run(cmd: PromptCommands, config: &CortexConfig, server: &str) -> Re
     match cmd {
         PromptCommands::List(args) => list(args, config).await,
         PromptCommands::Get(args) => get(args, config).await,
+        PromptCommands::Diff(args) => diff(args, config).await,
         PromptCommands::Migrate(args) => migrate(args, config).await,
         PromptCommands::Performance(args) => performance(args, server).await,
         PromptCommands::Deploy(args) => deploy(args, server).await,
         PromptCommands::RollbackStatus(args) => rollback_status(args, server).await,
+        PromptCommands::Rollback(args) => rollback(args, server).await,
         PromptCommands::Unquarantine(args) => unquarantine(args, server).await,
     }
 }
@@ -128,6 +131,21 @@ async fn get(args: PromptGetArgs, config: &CortexConfig) -> Result<()> {
     Ok(())
 }
 
+async fn diff(args: PromptDiffArgs, config: &CortexConfig) -> Result<()> {
+    let storage = open_storage(config)?;
+    let resolver = PromptResolver::new(storage);
+    let diff = resolver.diff(&args.slug, &args.branch, args.from, args.to)?;
+    match args.format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&diff)?),
+        _ if diff.is_empty() => println!(
+            "No section changes between v{} and v{}.",
+            args.from, args.to
+        ),
+        _ => print!("{}", diff.to_unified()),
+    }
+    Ok(())
+}
+
 fn print_raw_content(slug: &str, branch: &str, version: u32, content: &PromptContent) {
     println!("Prompt: {}@{}/v{}", slug, branch, version);
     println!("Type:   {}", content.prompt_type);
@@ -250,6 +268,7 @@ async fn deploy(args: PromptDeployArgs, server: &str) -> Result<()> {
         "branch": args.branch,
         "agent_name": args.agent_name,
         "baseline_sample_size": args.baseline_sample_size,
+        "rollout_percent": args.rollout,
     });
     let resp =
         client.post(&url).json(&payload).send().await.map_err(|e| {
@@ -295,6 +314,10 @@ async fn deploy(args: PromptDeployArgs, server: &str) -> Result<()> {
         "  Baseline sample: {}",
         data["baseline_sample_size"].as_u64().unwrap_or(0)
     );
+    let rollout = data["rollout_percent"].as_u64().unwrap_or(100);
+    if rollout < 100 {
+        println!("  Canary rollout:  {}% of selections", rollout);
+    }
     println!();
     println!(
         "Monitoring window active. Use `cortex prompt rollback-status {}` to check.",
@@ -374,6 +397,10 @@ async fn rollback_status(args: PromptRollbackStatusArgs, server: &str) -> Result
             dep["mean_sentiment"].as_f64().unwrap_or(0.0),
             dep["baseline_sentiment"].as_f64().unwrap_or(0.0)
         );
+        let rollout = dep["rollout_percent"].as_u64().unwrap_or(100);
+        if rollout < 100 {
+            println!("    Canary:       {}% of selections", rollout);
+        }
         println!(
             "    Consec neg:   {}",
             dep["consecutive_negative"].as_u64().unwrap_or(0)
@@ -403,6 +430,55 @@ async fn rollback_status(args: PromptRollbackStatusArgs, server: &str) -> Result
     Ok(())
 }
 
+// ── Rollback ─────────────────────────────────────────────────────────────────
+
+async fn rollback(args: PromptRollbackArgs, server: &str) -> Result<()> {
+    let base = http_base(server);
+    let client = reqwest::Client::new();
+    let url = format!("{}/prompts/{}/rollback", base, args.slug);
+    let payload = serde_json::json!({ "branch": args.branch, "to": args.to });
+    let resp =
+        client.post(&url).json(&payload).send().await.map_err(|e| {
+            anyhow::anyhow!("HTTP request failed: {}. Is `cortex serve` running?", e)
+        })?;
+
+    if !resp.status().is_success() {
+        let body: serde_json::Value = resp.json().await?;
+        anyhow::bail!("{}", body["error"].as_str().unwrap_or("unknown error"));
+    }
+
+    let body: serde_json::Value = resp.json().await?;
+    let data = &body["data"];
+
+    if args.format == "json" {
+        println!("{}", serde_json::to_string_pretty(data)?);
+        return Ok(());
+    }
+
+    println!(
+        "Rolled back '{}'@{} from v{} to v{}.",
+        args.slug,
+        args.branch,
+        data["from_version"].as_u64().unwrap_or(0),
+        data["to_version"].as_u64().unwrap_or(0)
+    );
+    println!(
+        "  Rollback node:   {}",
+        data["rollback_node_id"].as_str().unwrap_or("-")
+    );
+    println!(
+        "  Cooldown until:  {}",
+        data["cooldown_expires_at"].as_str().unwrap_or("-")
+    );
+    if data["is_quarantined"].as_bool().unwrap_or(false) {
+        println!(
+            "  v{} is now quarantined (use `cortex prompt unquarantine`).",
+            data["from_version"].as_u64().unwrap_or(0)
+        );
+    }
+    Ok(())
+}
+
 // ── Unquarantine ─────────────────────────────────────────────────────────────
 
 async fn unquarantine(args: PromptUnquarantineArgs, server: &str) -> Result<()> {
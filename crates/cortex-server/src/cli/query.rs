@@ -0,0 +1,77 @@
+use crate::cli::{grpc_connect, truncate, QueryArgs};
+use anyhow::Result;
+use cortex_proto::*;
+
+pub async fn run(args: QueryArgs, server: &str) -> Result<()> {
+    let mut client = grpc_connect(server).await?;
+    let ranked = args.like.is_some();
+
+    let resp = client
+        .query_nodes(QueryNodesRequest {
+            query: args.query,
+            like: args.like.unwrap_or_default(),
+        })
+        .await
+        .map_err(|s| anyhow::anyhow!("{}", s.message()))?
+        .into_inner();
+
+    if args.format == "json" {
+        let results: Vec<_> = resp
+            .results
+            .iter()
+            .filter_map(|r| r.node.as_ref().map(|n| (n, r.score)))
+            .map(|(n, score)| {
+                let mut entry = serde_json::json!({
+                    "id": n.id,
+                    "kind": n.kind,
+                    "title": n.title,
+                    "importance": n.importance,
+                    "tags": n.tags,
+                });
+                if ranked {
+                    entry["score"] = serde_json::json!(score);
+                }
+                entry
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+
+    if resp.results.is_empty() {
+        println!("No nodes match.");
+        return Ok(());
+    }
+
+    if ranked {
+        println!(
+            "{:>4}  {:<6}  {:<12}  {:<36}  TITLE",
+            "RANK", "SCORE", "KIND", "ID"
+        );
+    } else {
+        println!("{:<12}  {:<4}  {:<36}  TITLE", "KIND", "IMP", "ID");
+    }
+    println!("{}", "─".repeat(90));
+    for (i, r) in resp.results.iter().enumerate() {
+        let Some(node) = &r.node else { continue };
+        let title = truncate(&node.title, 35);
+        if ranked {
+            println!(
+                "{:>4}  {:.4}  {:<12}  {:<36}  {}",
+                i + 1,
+                r.score,
+                node.kind,
+                node.id,
+                title
+            );
+        } else {
+            println!(
+                "{:<12}  {:.2}  {:<36}  {}",
+                node.kind, node.importance, node.id, title
+            );
+        }
+    }
+    println!("\n{} node(s)", resp.results.len());
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::cli::{grpc_connect, ReindexArgs};
+use anyhow::Result;
+use cortex_proto::ReindexRequest;
+
+pub async fn run(args: ReindexArgs, server: &str) -> Result<()> {
+    let mut client = grpc_connect(server).await?;
+
+    if args.re_embed {
+        println!("Re-embedding all nodes and rebuilding the vector index...");
+    } else {
+        println!("Rebuilding the vector index...");
+    }
+    println!("(progress is logged by the server)");
+
+    let resp = client
+        .reindex(ReindexRequest {
+            re_embed: args.re_embed,
+        })
+        .await?
+        .into_inner();
+
+    println!("Indexed:  {:>8}", resp.nodes_reindexed);
+    println!("Embedded: {:>8}", resp.nodes_embedded);
+    if resp.nodes_skipped > 0 {
+        println!("Skipped:  {:>8}", resp.nodes_skipped);
+        if !args.re_embed {
+            println!(
+                "Stored embeddings may come from another model; try `cortex reindex --re-embed`."
+            );
+        }
+    }
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::cli::shell::remember_node;
 use crate::cli::{grpc_connect, truncate, SearchArgs};
 use anyhow::Result;
 use cortex_proto::*;
@@ -10,10 +11,17 @@ pub async fn run(args: SearchArgs, server: &str) -> Result<()> {
             .hybrid_search(HybridSearchRequest {
                 query: args.query,
                 limit: args.limit,
+                include_expired: args.include_expired,
+                include_deleted: args.include_deleted,
+                auto_anchor: args.auto_anchor.is_some(),
+                auto_anchor_count: args.auto_anchor.unwrap_or(0),
                 ..Default::default()
             })
             .await?
             .into_inner();
+        for node in resp.results.iter().filter_map(|r| r.node.as_ref()) {
+            remember_node(&node.id, &node.title);
+        }
 
         if args.format == "json" {
             let results: Vec<_> = resp
@@ -66,10 +74,16 @@ pub async fn run(args: SearchArgs, server: &str) -> Result<()> {
             .similarity_search(SimilaritySearchRequest {
                 query: args.query,
                 limit: args.limit,
+                diversity: args.diversity,
+                include_expired: args.include_expired,
+                include_deleted: args.include_deleted,
                 ..Default::default()
             })
             .await?
             .into_inner();
+        for node in resp.results.iter().filter_map(|r| r.node.as_ref()) {
+            remember_node(&node.id, &node.title);
+        }
 
         if args.format == "json" {
             let results: Vec<_> = resp
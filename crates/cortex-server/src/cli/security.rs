@@ -1,10 +1,14 @@
 use super::SecurityCommands;
+use crate::config::CortexConfig;
 use anyhow::Result;
+use cortex_core::storage::encrypted;
+use std::io::Read;
+use std::path::Path;
 
-pub async fn run(cmd: SecurityCommands) -> Result<()> {
+pub async fn run(cmd: SecurityCommands, config: CortexConfig) -> Result<()> {
     match cmd {
         SecurityCommands::GenerateKey => {
-            let key = cortex_core::storage::encrypted::generate_key();
+            let key = encrypted::generate_key();
             println!();
             println!("Generated a new 256-bit AES encryption key.");
             println!("Add to your environment:");
@@ -14,6 +18,77 @@ pub async fn run(cmd: SecurityCommands) -> Result<()> {
             println!("Keep this key safe — data encrypted with it cannot be recovered without it.");
             println!("Store it in a password manager or secrets vault.");
         }
+        SecurityCommands::RotateKey(args) => {
+            if args.old_key_file.as_deref() == Some(Path::new("-"))
+                && args.new_key_file.as_deref() == Some(Path::new("-"))
+            {
+                anyhow::bail!("Only one of --old-key-file and --new-key-file can read stdin");
+            }
+            let old_key = read_key(
+                args.old_key_file.as_deref(),
+                "CORTEX_ENCRYPTION_KEY",
+                "--old-key-file",
+            )?;
+            let new_key = read_key(
+                args.new_key_file.as_deref(),
+                "CORTEX_NEW_ENCRYPTION_KEY",
+                "--new-key-file",
+            )?;
+
+            let db_path = config.db_path();
+            if !db_path.exists() {
+                anyhow::bail!("Database not found at {}", db_path.display());
+            }
+            // A running server works on a decrypted temp copy and
+            // re-encrypts it with its own key on shutdown.
+            if db_path.with_extension("redb.tmp").exists() {
+                anyhow::bail!(
+                    "{} has a decrypted working copy; stop `cortex serve` before rotating",
+                    db_path.display()
+                );
+            }
+
+            encrypted::rotate_file_key(&db_path, &old_key, &new_key)?;
+            println!("✅ Re-encrypted {} under the new key.", db_path.display());
+            println!("Update CORTEX_ENCRYPTION_KEY before the next `cortex serve`.");
+            println!("Backups taken before now still need the old key.");
+        }
     }
     Ok(())
 }
+
+/// Read a base64 key from `file` (`-` for stdin), or from the `env`
+/// variable when no file is given. Keys are never taken as arguments, so
+/// they stay out of shell history and `ps`.
+fn read_key(file: Option<&Path>, env: &str, flag: &str) -> Result<[u8; 32]> {
+    let raw = match file {
+        Some(path) if path == Path::new("-") => {
+            let mut raw = String::new();
+            std::io::stdin().read_to_string(&mut raw)?;
+            raw
+        }
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("{} {}: {}", flag, path.display(), e))?,
+        None => std::env::var(env).map_err(|_| anyhow::anyhow!("Set {} or pass {}", env, flag))?,
+    };
+    encrypted::parse_key(raw.trim()).map_err(|e| anyhow::anyhow!("{}: {}", flag, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_file_is_read_and_trimmed() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = encrypted::generate_key();
+        let path = dir.path().join("new.key");
+        std::fs::write(&path, format!("{key}\n")).unwrap();
+
+        let parsed = read_key(Some(&path), "CORTEX_TEST_UNSET_KEY", "--new-key-file").unwrap();
+        assert_eq!(parsed, encrypted::parse_key(&key).unwrap());
+
+        let err = read_key(None, "CORTEX_TEST_UNSET_KEY", "--new-key-file").unwrap_err();
+        assert!(err.to_string().contains("CORTEX_TEST_UNSET_KEY"), "{err}");
+    }
+}
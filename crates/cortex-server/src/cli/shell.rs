@@ -1,14 +1,236 @@
 use crate::config::CortexConfig;
 use anyhow::Result;
+use clap::CommandFactory;
+use rustyline::completion::{Completer, Pair};
 use rustyline::error::ReadlineError;
-use rustyline::DefaultEditor;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::FileHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// How many recently seen nodes are offered for completion.
+const RECENT_NODES_MAX: usize = 200;
+
+/// Words the REPL handles itself rather than passing to the CLI parser.
+const REPL_VERBS: &[(&str, &str)] = &[
+    ("help", "list CLI commands"),
+    ("\\help", "list REPL verbs"),
+    (
+        "!!",
+        "repeat the previous line (`!! --limit 5` appends to it)",
+    ),
+    ("exit", "leave the shell (also `quit`, Ctrl-D)"),
+];
+
+/// Nodes printed by commands run in this process, newest first, as
+/// (id, title). Titles are empty for ids only seen in typed input.
+static RECENT_NODES: Mutex<VecDeque<(String, String)>> = Mutex::new(VecDeque::new());
+
+/// Record a node shown to the user so the shell can complete its id.
+pub fn remember_node(id: &str, title: &str) {
+    if id.is_empty() {
+        return;
+    }
+    let mut recent = RECENT_NODES.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(pos) = recent.iter().position(|(i, _)| i == id) {
+        let (_, old_title) = recent.remove(pos).unwrap();
+        if title.is_empty() {
+            recent.push_front((id.to_string(), old_title));
+            return;
+        }
+    }
+    recent.push_front((id.to_string(), title.to_string()));
+    recent.truncate(RECENT_NODES_MAX);
+}
+
+/// Remember any node ids typed (or pasted) into `line`.
+fn remember_ids_in(line: &str) {
+    for word in shell_split(line) {
+        if uuid::Uuid::parse_str(&word).is_ok() {
+            remember_node(&word, "");
+        }
+    }
+}
+
+fn recent_nodes() -> Vec<(String, String)> {
+    let recent = RECENT_NODES.lock().unwrap_or_else(|e| e.into_inner());
+    recent.iter().cloned().collect()
+}
+
+/// `~/.cortex/history`, or None when there is no home directory.
+fn history_path() -> Option<PathBuf> {
+    std::env::var("HOME")
+        .ok()
+        .map(|h| PathBuf::from(h).join(".cortex").join("history"))
+}
+
+/// Tab completion for subcommands, flags and recently seen node ids.
+struct ShellHelper {
+    cli: clap::Command,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        Ok(complete(&self.cli, &recent_nodes(), &line[..pos]))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+/// Completion candidates for the word ending at the end of `input`, and
+/// where that word starts.
+///
+/// The words before it walk the `Cli` subcommand tree. A word starting
+/// with `-` completes to the current command's flags; otherwise a command
+/// with subcommands offers those (plus the REPL verbs at the top level),
+/// and any other argument offers recent node ids by prefix, falling back
+/// to recent nodes whose title contains the word.
+fn complete(cli: &clap::Command, recent: &[(String, String)], input: &str) -> (usize, Vec<Pair>) {
+    let start = input.rfind(char::is_whitespace).map(|i| i + 1).unwrap_or(0);
+    let partial = &input[start..];
+
+    let mut cmd = cli;
+    let mut depth = 0;
+    for word in input[..start].split_whitespace() {
+        match cmd.find_subcommand(word) {
+            Some(sub) if !word.starts_with('-') => {
+                cmd = sub;
+                depth += 1;
+            }
+            _ => break,
+        }
+    }
+    let consumed = input[..start].split_whitespace().count();
+
+    let mut out: Vec<Pair> = Vec::new();
+    if partial.starts_with('-') {
+        for arg in cmd.get_arguments().filter(|a| !a.is_hide_set()) {
+            if let Some(long) = arg.get_long() {
+                let flag = format!("--{}", long);
+                if flag.starts_with(partial) && !matches!(long, "server" | "config") {
+                    out.push(plain(flag));
+                }
+            }
+        }
+    } else if consumed == depth && cmd.has_subcommands() {
+        if depth == 0 {
+            for (verb, _) in REPL_VERBS {
+                if verb.starts_with(partial) {
+                    out.push(plain(verb.to_string()));
+                }
+            }
+        }
+        for sub in cmd.get_subcommands().filter(|s| !s.is_hide_set()) {
+            if sub.get_name().starts_with(partial) && !(depth == 0 && sub.get_name() == "shell") {
+                out.push(plain(sub.get_name().to_string()));
+            }
+        }
+    } else {
+        out.extend(
+            recent
+                .iter()
+                .filter(|(id, _)| id.starts_with(partial))
+                .map(node_pair),
+        );
+        if out.is_empty() && partial.chars().count() >= 2 {
+            let needle = partial.to_lowercase();
+            out.extend(
+                recent
+                    .iter()
+                    .filter(|(_, title)| title.to_lowercase().contains(&needle))
+                    .map(node_pair),
+            );
+        }
+    }
+    (start, out)
+}
+
+fn plain(word: String) -> Pair {
+    Pair {
+        display: word.clone(),
+        replacement: format!("{} ", word),
+    }
+}
+
+fn node_pair((id, title): &(String, String)) -> Pair {
+    Pair {
+        display: if title.is_empty() {
+            id.clone()
+        } else {
+            format!("{}  {}", id, super::truncate(title, 40))
+        },
+        replacement: format!("{} ", id),
+    }
+}
+
+/// Expand `!!` to the previous history entry. Only a standalone `!!`
+/// outside quotes is expanded, so `"wow!!"` in a node body is left alone.
+/// None if there is something to expand but no previous entry.
+fn expand_bang_bang(line: &str, previous: Option<&str>) -> Option<String> {
+    let mut expanded = String::with_capacity(line.len());
+    let mut quote = None;
+    let mut chars = line.char_indices();
+    while let Some((i, ch)) = chars.next() {
+        match quote {
+            Some(q) if ch == q => quote = None,
+            Some(_) => {}
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None => {
+                let starts_word = i == 0 || line[..i].ends_with([' ', '\t']);
+                if let Some(after) = line[i..].strip_prefix("!!") {
+                    if starts_word && (after.is_empty() || after.starts_with([' ', '\t'])) {
+                        expanded.push_str(previous?);
+                        chars.next();
+                        continue;
+                    }
+                }
+            }
+        }
+        expanded.push(ch);
+    }
+    Some(expanded)
+}
 
 pub async fn run(config: CortexConfig, server: &str, config_path: &std::path::Path) -> Result<()> {
-    let mut rl = DefaultEditor::new()?;
+    let mut rl: Editor<ShellHelper, FileHistory> = Editor::new()?;
+    rl.set_helper(Some(ShellHelper {
+        cli: super::Cli::command(),
+    }));
+
+    let history = history_path();
+    if let Some(path) = &history {
+        if path.exists() {
+            if let Err(e) = rl.load_history(path) {
+                eprintln!("Could not load history from {}: {}", path.display(), e);
+            }
+        }
+        for line in rl.history().iter() {
+            remember_ids_in(line);
+        }
+    }
 
     println!();
     println!("Cortex shell — connected to {}", server);
-    println!("Type 'help' for available commands, 'exit' to quit.");
+    println!("Type 'help' for commands, '\\help' for shell verbs, 'exit' to quit.");
     println!();
 
     loop {
@@ -19,7 +241,21 @@ pub async fn run(config: CortexConfig, server: &str, config_path: &std::path::Pa
                     continue;
                 }
 
+                let previous = rl.history().iter().next_back().cloned();
+                let line = match expand_bang_bang(&line, previous.as_deref()) {
+                    Some(expanded) if expanded != line => {
+                        println!("{}", expanded);
+                        expanded
+                    }
+                    Some(line) => line,
+                    None => {
+                        eprintln!("!!: no previous command");
+                        continue;
+                    }
+                };
+
                 let _ = rl.add_history_entry(&line);
+                remember_ids_in(&line);
 
                 if line == "exit" || line == "quit" {
                     println!("Goodbye.");
@@ -31,6 +267,11 @@ pub async fn run(config: CortexConfig, server: &str, config_path: &std::path::Pa
                     continue;
                 }
 
+                if line == "\\help" {
+                    print_repl_help();
+                    continue;
+                }
+
                 // Build argv: ["cortex", "--config", <path>, "--server", <server>, ...words]
                 let mut argv = vec![
                     "cortex".to_string(),
@@ -76,6 +317,17 @@ pub async fn run(config: CortexConfig, server: &str, config_path: &std::path::Pa
         }
     }
 
+    if let Some(path) = &history {
+        let saved = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .map_err(anyhow::Error::from)
+            .and_then(|_| rl.save_history(path).map_err(anyhow::Error::from));
+        if let Err(e) = saved {
+            eprintln!("Could not save history to {}: {}", path.display(), e);
+        }
+    }
+
     Ok(())
 }
 
@@ -87,28 +339,38 @@ async fn dispatch(
 ) -> Result<()> {
     use super::Commands;
     match cli.command {
-        Commands::Serve => println!("Use 'exit' first, then run `cortex serve`."),
+        Commands::Serve(_) => println!("Use 'exit' first, then run `cortex serve`."),
         Commands::Init => super::init::run().await?,
         Commands::Shell => println!("Already in shell mode."),
         Commands::Node(cmd) => super::node::run(cmd, server).await?,
         Commands::Edge(cmd) => super::edge::run(cmd, server).await?,
         Commands::Search(a) => super::search::run(a, server).await?,
+        Commands::Query(a) => super::query::run(a, server).await?,
         Commands::Traverse(a) => super::traverse::run(a, server).await?,
         Commands::Path(a) => super::traverse::run_path(a, server).await?,
         Commands::Briefing(a) => super::briefing::run(a, server).await?,
         Commands::Import(a) => super::import::run(a, config).await?,
         Commands::Export(a) => super::export::run(a, server).await?,
-        Commands::Backup(a) => super::backup::run(a, config).await?,
+        Commands::Backup(a) => super::backup::run(a, config, server).await?,
         Commands::Restore(a) => super::backup::run_restore(a, config).await?,
+        Commands::RestoreArchive(a) => super::backup::run_restore_archive(a, config).await?,
         Commands::Migrate => super::migrate::run(config).await?,
-        Commands::Stats => super::stats::run(server).await?,
+        Commands::Gc(a) => super::gc::run(a, config).await?,
+        Commands::Stats(a) => super::stats::run(a, server).await?,
+        Commands::Watch(a) => super::watch::run(a, config, server).await?,
+        Commands::Diff(a) => super::diff::run(a).await?,
+        Commands::Merge(a) => super::merge::run(a).await?,
+        Commands::Reindex(a) => super::reindex::run(a, server).await?,
+        Commands::Linker(cmd) => super::linker::run(cmd, server).await?,
         Commands::Doctor => super::doctor::run(config, server).await?,
+        Commands::Verify(a) => super::verify::run(a, config).await?,
         Commands::Config(cmd) => super::config_cmd::run(cmd, config_path).await?,
         Commands::Audit(a) => super::audit::run(a, config).await?,
-        Commands::Security(c) => super::security::run(c).await?,
+        Commands::Security(c) => super::security::run(c, config).await?,
         Commands::Mcp(_) => println!("Run `cortex mcp` outside the shell to start the MCP server."),
         Commands::Agent(cmd) => super::agent::run(cmd, server).await?,
         Commands::Prompt(cmd) => super::prompt::run(cmd, &config, server).await?,
+        Commands::Completions(a) => super::completions::run(a)?,
     }
     Ok(())
 }
@@ -122,6 +384,7 @@ fn print_help() {
     println!("  edge create --from <id> --to <id> --relation <rel>");
     println!("  edge list --node <id>");
     println!("  search <query> [--hybrid] [--limit N]");
+    println!("  query <filter> [--like <text>]");
     println!("  traverse <id> [--depth N]");
     println!("  path <from> <to>");
     println!("  briefing <agent_id> [--compact]");
@@ -129,13 +392,31 @@ fn print_help() {
     println!("  export [--format json|jsonl|dot|graphml]");
     println!("  backup <path>");
     println!("  restore <path>");
+    println!("  restore-archive <file>");
     println!("  migrate");
+    println!("  gc [--dry-run] [--max-nodes N]");
     println!("  stats");
+    println!("  watch [--kind <kind>] [--actor <agent>]");
+    println!("  reindex [--re-embed]");
+    println!("  diff <db_a> <db_b> [--format json]");
+    println!("  merge <source> --into <target> [--on-conflict keep-newest|keep-both]");
+    println!("  linker preview [--full] [--limit N]");
     println!("  doctor");
+    println!("  verify [--fix]");
     println!("  config validate|show");
     println!("  exit / quit");
 }
 
+fn print_repl_help() {
+    println!("Shell verbs:");
+    for (verb, about) in REPL_VERBS {
+        println!("  {:<6}  {}", verb, about);
+    }
+    println!();
+    println!("Tab completes commands, flags and the ids of nodes shown earlier;");
+    println!("Up/Down and Ctrl-R recall history, kept in ~/.cortex/history.");
+}
+
 /// Simple shell-like word splitting (handles quoted strings).
 fn shell_split(line: &str) -> Vec<String> {
     let mut words = Vec::new();
@@ -169,3 +450,117 @@ fn shell_split(line: &str) -> Vec<String> {
 
     words
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(input: &str, recent: &[(String, String)]) -> (usize, Vec<String>) {
+        let (start, pairs) = complete(&super::super::Cli::command(), recent, input);
+        let words = pairs
+            .into_iter()
+            .map(|p| p.replacement.trim_end().to_string())
+            .collect();
+        (start, words)
+    }
+
+    fn recent() -> Vec<(String, String)> {
+        vec![
+            (
+                "0190a1b2-0000-7000-8000-000000000001".into(),
+                "JWT auth for the API".into(),
+            ),
+            (
+                "0190a1b2-0000-7000-8000-000000000002".into(),
+                "Deploy runbook".into(),
+            ),
+            ("0191ffff-0000-7000-8000-000000000003".into(), String::new()),
+        ]
+    }
+
+    #[test]
+    fn test_completes_top_level_commands_and_verbs() {
+        let (start, found) = words("no", &[]);
+        assert_eq!(start, 0);
+        assert_eq!(found, vec!["node"]);
+
+        let (_, found) = words("\\h", &[]);
+        assert_eq!(found, vec!["\\help"]);
+
+        // Every command is offered on an empty line, but not `shell` itself
+        let (_, found) = words("", &[]);
+        assert!(found.contains(&"search".to_string()));
+        assert!(found.contains(&"exit".to_string()));
+        assert!(!found.contains(&"shell".to_string()));
+    }
+
+    #[test]
+    fn test_completes_nested_subcommands_and_flags() {
+        let (start, found) = words("node g", &[]);
+        assert_eq!(start, 5);
+        assert_eq!(found, vec!["get"]);
+
+        let (_, found) = words("search auth --hy", &[]);
+        assert_eq!(found, vec!["--hybrid"]);
+
+        // Fixed by the shell, so not offered
+        let (_, found) = words("node list --", &[]);
+        assert!(found.contains(&"--kind".to_string()));
+        assert!(!found.contains(&"--server".to_string()));
+    }
+
+    #[test]
+    fn test_completes_recent_node_ids() {
+        let recent = recent();
+
+        let (start, found) = words("node get 0190", &recent);
+        assert_eq!(start, 9);
+        assert_eq!(
+            found,
+            vec![
+                "0190a1b2-0000-7000-8000-000000000001",
+                "0190a1b2-0000-7000-8000-000000000002",
+            ]
+        );
+
+        let (_, found) = words("traverse 0191", &recent);
+        assert_eq!(found, vec!["0191ffff-0000-7000-8000-000000000003"]);
+
+        // No id matches, so titles are searched instead
+        let (_, found) = words("edge create --from AUTH", &recent);
+        assert_eq!(found, vec!["0190a1b2-0000-7000-8000-000000000001"]);
+
+        let (_, found) = words("node get zz", &recent);
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn test_bang_bang_expansion() {
+        assert_eq!(
+            expand_bang_bang("!!", Some("search auth")).as_deref(),
+            Some("search auth")
+        );
+        assert_eq!(
+            expand_bang_bang("!! --limit 5", Some("search auth")).as_deref(),
+            Some("search auth --limit 5")
+        );
+        assert_eq!(expand_bang_bang("!!", None), None);
+        assert_eq!(expand_bang_bang("stats", None).as_deref(), Some("stats"));
+
+        // Only a standalone token outside quotes
+        let prev = Some("stats");
+        for line in [
+            r#"node create --title "Ship it!!""#,
+            "node create --title 'a !! b'",
+            "search wow!!",
+            "search !!x",
+        ] {
+            assert_eq!(expand_bang_bang(line, prev).as_deref(), Some(line));
+            assert_eq!(expand_bang_bang(line, None).as_deref(), Some(line));
+        }
+        assert_eq!(
+            expand_bang_bang(r#"!! "!!""#, prev).as_deref(),
+            Some(r#"stats "!!""#)
+        );
+    }
+}
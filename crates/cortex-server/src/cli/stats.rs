@@ -1,11 +1,17 @@
-use crate::cli::grpc_connect;
+use crate::cli::{grpc_connect, StatsArgs};
 use anyhow::Result;
 use cortex_proto::StatsRequest;
 
-pub async fn run(server: &str) -> Result<()> {
+pub async fn run(args: StatsArgs, server: &str) -> Result<()> {
     let mut client = grpc_connect(server).await?;
 
-    let resp = client.stats(StatsRequest {}).await?.into_inner();
+    let resp = client
+        .stats(StatsRequest {
+            centrality_top: args.centrality.unwrap_or(0),
+            communities: args.communities,
+        })
+        .await?
+        .into_inner();
 
     let db_mb = resp.db_size_bytes as f64 / 1_048_576.0;
 
@@ -28,8 +34,57 @@ pub async fn run(server: &str) -> Result<()> {
     }
 
     println!("DB Size: {:>7.1} MB", db_mb);
+    let mut tables: Vec<_> = resp.table_bytes.iter().filter(|(_, b)| **b > 0).collect();
+    tables.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    for (table, bytes) in &tables {
+        println!("  {:20} {:>10}", table, format_bytes(**bytes));
+    }
     println!("{}", "─".repeat(50));
+
+    if args.centrality.is_some() {
+        println!();
+        println!("Most Central (PageRank)");
+        println!("{}", "─".repeat(50));
+        for (i, node) in resp.central_nodes.iter().enumerate() {
+            println!(
+                "{:>3}. {:.4}  [{}] {}",
+                i + 1,
+                node.score,
+                node.kind,
+                node.title
+            );
+            println!("     {}", node.id);
+        }
+        println!("{}", "─".repeat(50));
+    }
+
+    if args.communities {
+        println!();
+        println!(
+            "Communities ({}, modularity {:.3})",
+            resp.communities.len(),
+            resp.modularity
+        );
+        println!("{}", "─".repeat(50));
+        for (i, community) in resp.communities.iter().enumerate() {
+            println!(
+                "{:>3}. {:>5} nodes  {}",
+                i + 1,
+                community.size,
+                community.representative_titles.join(" · ")
+            );
+        }
+        println!("{}", "─".repeat(50));
+    }
     println!();
 
     Ok(())
 }
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1_048_576 => format!("{:.1} MB", b as f64 / 1_048_576.0),
+        b if b >= 1024 => format!("{:.1} KB", b as f64 / 1024.0),
+        b => format!("{} B", b),
+    }
+}
@@ -14,6 +14,8 @@ pub async fn run(args: TraverseArgs, server: &str) -> Result<()> {
             direction: args.direction,
             relation_filter,
             limit: 200,
+            include_expired: args.include_expired,
+            include_deleted: args.include_deleted,
             ..Default::default()
         })
         .await?
@@ -102,6 +104,7 @@ pub async fn run_path(args: PathArgs, server: &str) -> Result<()> {
             to_id: args.to,
             max_paths: 3,
             max_depth: args.max_hops,
+            strategy: if args.weighted { "weighted" } else { "hops" }.to_string(),
         })
         .await?
         .into_inner();
@@ -0,0 +1,113 @@
+use crate::cli::VerifyArgs;
+use crate::config::CortexConfig;
+use anyhow::Result;
+use cortex_core::{
+    repair, verify, EmbeddingService, FastEmbedService, RedbStorage, VerifyConfig, VerifyReport,
+};
+use std::fmt::Display;
+
+/// Ids printed per issue class; the counts are always complete.
+const MAX_EXAMPLES: usize = 5;
+
+pub async fn run(args: VerifyArgs, config: CortexConfig) -> Result<()> {
+    let storage = RedbStorage::open(config.db_path())?;
+    let embedder = FastEmbedService::new()?;
+    let verify_config = VerifyConfig::new(embedder.dimension());
+
+    let mut report = verify(&storage, &verify_config)?;
+    println!();
+    println!("Cortex Integrity Check");
+    println!("{}", "─".repeat(50));
+    println!(
+        "Checked {} nodes, {} edges",
+        report.nodes_checked, report.edges_checked
+    );
+    print_report(&report);
+
+    if args.fix && !report.is_clean() {
+        let fixed = repair(&storage, &embedder, &report)?;
+        println!("{}", "─".repeat(50));
+        println!("Pruned {} dangling edges", fixed.edges_pruned);
+        println!("Re-embedded {} nodes", fixed.nodes_reembedded);
+        report = verify(&storage, &verify_config)?;
+    }
+    println!("{}", "─".repeat(50));
+
+    if report.is_clean() {
+        println!("No issues found.");
+        return Ok(());
+    }
+    println!("{} issues found.", report.issue_count());
+    if !args.fix {
+        println!(
+            "Run `cortex verify --fix` to prune dangling edges and re-embed wrong-dimension nodes."
+        );
+    }
+    std::process::exit(1);
+}
+
+fn print_report(report: &VerifyReport) {
+    print_class(
+        "Dangling edges",
+        "prunable with --fix",
+        &report.dangling_edges,
+    );
+    print_class(
+        "Wrong-dimension embeddings",
+        "re-embedded with --fix",
+        &report.wrong_dimension,
+    );
+    print_class(
+        "Orphaned observations",
+        "no edges; review manually",
+        &report.orphaned_observations,
+    );
+
+    let dup_ids: usize = report.duplicate_titles.iter().map(|d| d.ids.len()).sum();
+    if report.duplicate_titles.is_empty() {
+        println!("[✓] Duplicate titles: none");
+    } else {
+        println!(
+            "[✗] Duplicate titles: {} titles across {} nodes (merge or rename manually)",
+            report.duplicate_titles.len(),
+            dup_ids
+        );
+        for dup in report.duplicate_titles.iter().take(MAX_EXAMPLES) {
+            let ids: Vec<String> = dup.ids.iter().map(|id| id.to_string()).collect();
+            println!("    {} \"{}\": {}", dup.kind, dup.title, ids.join(", "));
+        }
+    }
+
+    if report.relation_cycles.is_empty() {
+        println!("[✓] Relation cycles: none");
+    } else {
+        println!(
+            "[✗] Relation cycles: {} (remove an edge from each manually)",
+            report.relation_cycles.len()
+        );
+        for cycle in report.relation_cycles.iter().take(MAX_EXAMPLES) {
+            let ids: Vec<String> = cycle.nodes.iter().map(|id| id.to_string()).collect();
+            println!("    {}: {}", cycle.relation, ids.join(" -> "));
+        }
+        if report.relation_cycles.len() > MAX_EXAMPLES {
+            println!(
+                "    ... and {} more",
+                report.relation_cycles.len() - MAX_EXAMPLES
+            );
+        }
+    }
+}
+
+fn print_class<T: Display>(name: &str, hint: &str, ids: &[T]) {
+    if ids.is_empty() {
+        println!("[✓] {}: none", name);
+        return;
+    }
+    println!("[✗] {}: {} ({})", name, ids.len(), hint);
+    for id in ids.iter().take(MAX_EXAMPLES) {
+        println!("    {}", id);
+    }
+    if ids.len() > MAX_EXAMPLES {
+        println!("    ... and {} more", ids.len() - MAX_EXAMPLES);
+    }
+}
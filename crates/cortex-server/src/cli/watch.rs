@@ -0,0 +1,442 @@
+//! `cortex watch` — a `tail -f` of graph mutations.
+//!
+//! By default this follows the server's SSE `/events` stream. With
+//! `--embedded` it polls the database file instead, diffing node headers and
+//! edges between polls; this needs the file to be free between writers, so
+//! use the stream while `cortex serve` is running.
+
+use crate::cli::{http_base, truncate, WatchArgs};
+use crate::config::CortexConfig;
+use crate::observability::GraphEvent;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use cortex_core::{EdgeId, NodeFilter, NodeId, RedbStorage, Storage};
+use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::time::Duration;
+
+/// Palette for node kinds; a kind always gets the same colour.
+const KIND_COLORS: &[&str] = &["32", "33", "34", "35", "36", "91", "92", "94", "95", "96"];
+
+/// Which events to print. Edges carry no kind or agent, so either filter
+/// hides them.
+#[derive(Debug, Default, Clone)]
+pub struct WatchFilter {
+    pub kind: Option<String>,
+    pub actor: Option<String>,
+}
+
+impl WatchFilter {
+    pub fn matches(&self, event: &GraphEvent) -> bool {
+        if self.kind.is_none() && self.actor.is_none() {
+            return true;
+        }
+        if !event.event_type.starts_with("node.") {
+            return false;
+        }
+        let field = |name: &str| event.data[name].as_str().unwrap_or_default().to_string();
+        self.kind.as_ref().is_none_or(|k| *k == field("kind"))
+            && self.actor.as_ref().is_none_or(|a| *a == field("agent"))
+    }
+}
+
+pub async fn run(args: WatchArgs, config: CortexConfig, server: &str) -> Result<()> {
+    let filter = WatchFilter {
+        kind: args.kind,
+        actor: args.actor,
+    };
+    let color = !args.no_color && std::io::stdout().is_terminal();
+    let mut print = |event: GraphEvent| {
+        if filter.matches(&event) {
+            println!("{}", format_event(&event, color));
+        }
+        true
+    };
+
+    let watch = async {
+        if args.embedded {
+            let interval = Duration::from_millis(args.interval.max(50));
+            watch_embedded(&config.db_path(), interval, &mut print).await
+        } else {
+            watch_remote(&http_base(server), &mut print).await
+        }
+    };
+    tokio::select! {
+        res = watch => res,
+        _ = tokio::signal::ctrl_c() => Ok(()),
+    }
+}
+
+/// Follow `{base}/events`, passing each graph event to `on_event` until it
+/// returns false or the server closes the stream.
+pub async fn watch_remote(
+    base: &str,
+    on_event: &mut (dyn FnMut(GraphEvent) -> bool + Send),
+) -> Result<()> {
+    let url = format!("{}/events", base);
+    let mut resp = reqwest::Client::new()
+        .get(&url)
+        .header("accept", "text/event-stream")
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("HTTP request failed: {}. Is `cortex serve` running?", e))?
+        .error_for_status()?;
+
+    let mut buf = String::new();
+    while let Some(chunk) = resp.chunk().await? {
+        buf.push_str(&String::from_utf8_lossy(&chunk));
+        // Frames end with a blank line
+        while let Some(end) = buf.find("\n\n") {
+            let frame: String = buf.drain(..end + 2).collect();
+            let (event, data) = parse_frame(&frame);
+            match event.as_deref() {
+                Some("connected") => eprintln!("Watching {} ({})", url, data),
+                Some("warning") => eprintln!("warning: {}", data),
+                _ if data.is_empty() => {}
+                _ => match serde_json::from_str::<GraphEvent>(&data) {
+                    Ok(event) => {
+                        if !on_event(event) {
+                            return Ok(());
+                        }
+                    }
+                    Err(e) => log::debug!("skipping unreadable event: {}", e),
+                },
+            }
+        }
+    }
+    eprintln!("Server closed the event stream.");
+    Ok(())
+}
+
+/// The `event:` name and joined `data:` lines of one SSE frame.
+fn parse_frame(frame: &str) -> (Option<String>, String) {
+    let mut event = None;
+    let mut data = Vec::new();
+    for line in frame.lines() {
+        if let Some(v) = line.strip_prefix("event:") {
+            event = Some(v.trim_start().to_string());
+        } else if let Some(v) = line.strip_prefix("data:") {
+            data.push(v.strip_prefix(' ').unwrap_or(v));
+        }
+    }
+    (event, data.join("\n"))
+}
+
+/// Poll the database at `path` every `interval`, reopening it each time so
+/// other processes can write in between.
+async fn watch_embedded(
+    path: &std::path::Path,
+    interval: Duration,
+    on_event: &mut (dyn FnMut(GraphEvent) -> bool + Send),
+) -> Result<()> {
+    let mut poller = Poller::new(&RedbStorage::open_read_only(path)?)?;
+    eprintln!(
+        "Watching {} (every {} ms)",
+        path.display(),
+        interval.as_millis()
+    );
+    loop {
+        tokio::time::sleep(interval).await;
+        // Busy while another process has it open; try again next tick
+        let storage = match RedbStorage::open_read_only(path) {
+            Ok(s) => s,
+            Err(e) => {
+                log::debug!("database not readable this tick: {}", e);
+                continue;
+            }
+        };
+        for event in poller.poll(&storage)? {
+            if !on_event(event) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// What an edge event reports: from, to, relation, weight.
+type EdgeSummary = (NodeId, NodeId, String, f32);
+
+/// Turns successive reads of a store into the events the server would have
+/// broadcast for the writes in between.
+pub struct Poller {
+    since: DateTime<Utc>,
+    edges: HashMap<EdgeId, EdgeSummary>,
+}
+
+impl Poller {
+    pub fn new(storage: &impl Storage) -> Result<Self> {
+        Ok(Self {
+            since: Utc::now(),
+            edges: Self::read_edges(storage)?,
+        })
+    }
+
+    fn read_edges(storage: &impl Storage) -> Result<HashMap<EdgeId, EdgeSummary>> {
+        Ok(storage
+            .list_edges()?
+            .into_iter()
+            .map(|e| {
+                (
+                    e.id,
+                    (e.from, e.to, e.relation.as_str().to_string(), e.weight),
+                )
+            })
+            .collect())
+    }
+
+    /// Events for everything that changed since the last poll: nodes oldest
+    /// first, then edges, stamped with the poll time.
+    pub fn poll(&mut self, storage: &impl Storage) -> Result<Vec<GraphEvent>> {
+        let mut events = Vec::new();
+
+        let mut headers = storage.list_node_headers(
+            NodeFilter::new()
+                .include_deleted()
+                .changed_after(self.since),
+        )?;
+        headers.sort_by_key(|h| h.updated_at);
+        let since = self.since;
+        for h in &headers {
+            let data = serde_json::json!({
+                "id": h.id.to_string(),
+                "kind": h.kind.as_str(),
+                "title": h.title,
+                "agent": h.source.agent,
+                "importance": h.importance,
+            });
+            if h.created_at > since {
+                events.push(graph_event("node.created", h.created_at, data.clone()));
+            }
+            if h.deleted_at.is_some_and(|at| at > since) {
+                events.push(graph_event("node.deleted", h.updated_at, data));
+            } else if h.created_at <= since {
+                events.push(graph_event("node.updated", h.updated_at, data));
+            }
+            self.since = self.since.max(h.updated_at);
+        }
+
+        let edges = Self::read_edges(storage)?;
+        let now = Utc::now();
+        let edge_data = |id: &EdgeId, (from, to, relation, weight): &EdgeSummary| {
+            serde_json::json!({
+                "id": id.to_string(),
+                "from": from.to_string(),
+                "to": to.to_string(),
+                "relation": relation,
+                "weight": weight,
+            })
+        };
+        for (id, edge) in &edges {
+            match self.edges.get(id) {
+                None => events.push(graph_event("edge.created", now, edge_data(id, edge))),
+                Some(old) if old.3 != edge.3 => {
+                    events.push(graph_event("edge.updated", now, edge_data(id, edge)))
+                }
+                Some(_) => {}
+            }
+        }
+        for (id, edge) in &self.edges {
+            if !edges.contains_key(id) {
+                events.push(graph_event("edge.deleted", now, edge_data(id, edge)));
+            }
+        }
+        self.edges = edges;
+
+        Ok(events)
+    }
+}
+
+fn graph_event(event_type: &str, at: DateTime<Utc>, data: serde_json::Value) -> GraphEvent {
+    GraphEvent {
+        event_type: event_type.to_string(),
+        timestamp: at.to_rfc3339(),
+        graph_version: 0,
+        data,
+    }
+}
+
+/// One line per event: time, type, then the node's kind, id, title and
+/// agent, or the edge's endpoints, relation and weight.
+pub fn format_event(event: &GraphEvent, color: bool) -> String {
+    let time = DateTime::parse_from_rfc3339(&event.timestamp)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_else(|_| event.timestamp.clone());
+    let d = &event.data;
+    let s = |name: &str| d[name].as_str().unwrap_or("-").to_string();
+
+    if event.event_type.starts_with("edge.") {
+        return format!(
+            "{}  {:<12}  {} -[{}]-> {}  w={:.2}",
+            time,
+            event.event_type,
+            s("from"),
+            s("relation"),
+            s("to"),
+            d["weight"].as_f64().unwrap_or_default()
+        );
+    }
+
+    let kind = s("kind");
+    let padded = format!("{:<12}", kind);
+    let kind = if color {
+        format!("\x1b[{}m{}\x1b[0m", kind_color(&kind), padded)
+    } else {
+        padded
+    };
+    format!(
+        "{}  {:<12}  {}  {}  {}  ({})",
+        time,
+        event.event_type,
+        kind,
+        s("id"),
+        truncate(&s("title"), 50),
+        s("agent")
+    )
+}
+
+fn kind_color(kind: &str) -> &'static str {
+    let hash = kind
+        .bytes()
+        .fold(0usize, |h, b| h.wrapping_mul(31).wrapping_add(b as usize));
+    KIND_COLORS[hash % KIND_COLORS.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::{new_event_bus, sse_stream, EventBusHook};
+    use axum::response::sse::Sse;
+    use axum::{routing::get, Router};
+    use cortex_core::hooks::{MutationAction, MutationHook};
+    use cortex_core::{Edge, EdgeProvenance, Node, NodeKind, Relation, Source};
+    use tempfile::TempDir;
+
+    fn node(kind: &str, title: &str, agent: &str) -> Node {
+        Node::new(
+            NodeKind::new(kind).unwrap(),
+            title.into(),
+            "body".into(),
+            Source {
+                agent: agent.into(),
+                session: None,
+                channel: None,
+            },
+            0.5,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_remote_watch_prints_nodes_created_while_watching() {
+        let bus = new_event_bus(64);
+        let sub = bus.clone();
+        let app = Router::new().route(
+            "/events",
+            get(move || {
+                let rx = sub.subscribe();
+                async move { Sse::new(sse_stream(rx, 0, None, None)) }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let filter = WatchFilter {
+            kind: Some("fact".into()),
+            actor: None,
+        };
+        let watcher = tokio::spawn(async move {
+            let mut lines = Vec::new();
+            watch_remote(&base, &mut |event| {
+                if filter.matches(&event) {
+                    lines.push(format_event(&event, false));
+                }
+                lines.len() < 2
+            })
+            .await
+            .unwrap();
+            lines
+        });
+
+        // Write once the watcher is subscribed
+        while bus.receiver_count() == 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let hook = EventBusHook::new(bus);
+        let first = node("fact", "JWT auth", "kai");
+        hook.on_node_mutation(&first, MutationAction::Created);
+        hook.on_node_mutation(&node("goal", "Ship it", "kai"), MutationAction::Created);
+        hook.on_node_mutation(
+            &node("fact", "Tokens expire", "ava"),
+            MutationAction::Created,
+        );
+
+        let lines = tokio::time::timeout(Duration::from_secs(5), watcher)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("node.created"));
+        assert!(lines[0].contains(&first.id.to_string()));
+        assert!(lines[0].contains("JWT auth"));
+        assert!(lines[0].contains("(kai)"));
+        assert!(lines[1].contains("Tokens expire"));
+    }
+
+    #[test]
+    fn test_poller_reports_changes_between_polls() {
+        let dir = TempDir::new().unwrap();
+        let storage = RedbStorage::open(dir.path().join("cortex.redb")).unwrap();
+        let mut poller = Poller::new(&storage).unwrap();
+        assert!(poller.poll(&storage).unwrap().is_empty());
+
+        let a = node("fact", "JWT auth", "kai");
+        let b = node("goal", "Ship it", "ava");
+        storage.put_node(&a).unwrap();
+        storage.put_node(&b).unwrap();
+        let edge = Edge::new(
+            a.id,
+            b.id,
+            Relation::new("supports").unwrap(),
+            0.8,
+            EdgeProvenance::Manual {
+                created_by: "kai".into(),
+            },
+        );
+        storage.put_edge(&edge).unwrap();
+
+        let events = poller.poll(&storage).unwrap();
+        let types: Vec<_> = events.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(types, ["node.created", "node.created", "edge.created"]);
+        let lines: Vec<_> = events.iter().map(|e| format_event(e, false)).collect();
+        assert!(lines[0].contains("JWT auth"));
+        assert!(lines[1].contains("Ship it"));
+        assert!(lines[2].contains("-[supports]->"));
+
+        let mut a2 = a.clone();
+        a2.data.title = "JWT auth (RS256)".into();
+        a2.updated_at = Utc::now();
+        storage.put_node(&a2).unwrap();
+        storage.delete_node(b.id).unwrap();
+        storage.delete_edge(edge.id).unwrap();
+
+        let events = poller.poll(&storage).unwrap();
+        let types: Vec<_> = events.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(types, ["node.updated", "node.deleted", "edge.deleted"]);
+
+        // The kind filter keeps matching nodes and drops edges
+        let facts = WatchFilter {
+            kind: Some("fact".into()),
+            actor: None,
+        };
+        let kept: Vec<_> = events.iter().filter(|e| facts.matches(e)).collect();
+        assert_eq!(kept.len(), 1);
+        assert!(format_event(kept[0], false).contains("JWT auth (RS256)"));
+
+        // Nothing new since the last poll
+        assert!(poller.poll(&storage).unwrap().is_empty());
+    }
+}
@@ -1,14 +1,22 @@
 use std::collections::HashMap;
 
-use cortex_core::{AutoLinkerConfig, ConfigRule, NodeKind, Relation, SimilarityConfig};
+use cortex_core::briefing::{
+    AgentBriefingConfig, BriefingConfig, BriefingSectionSpec, BRIEFING_SECTIONS,
+};
+use cortex_core::{
+    AutoLinkerConfig, ConfigRule, ContradictionStrategy, GraphProximity, KindDefinition,
+    KindRegistry, NodeKind, Relation, SimilarityConfig, TemporalCoOccurrenceRule,
+};
 
 // Re-export from cortex-core so cortex-server code can use them from config
+pub use crate::observability::publish::NatsPublishConfig;
 #[allow(unused_imports)]
 pub use cortex_core::gate::schema::{FieldSchema, FieldType, KindSchema};
 pub use cortex_core::policies::RetentionConfig;
 #[allow(unused_imports)]
 pub use cortex_core::policies::RetentionMaxNodes;
 pub use cortex_core::prompt::RollbackConfig;
+pub use cortex_core::prompt::SelectionConfig;
 pub use cortex_core::ScoreDecayConfig;
 pub use cortex_core::WriteGateConfig;
 use serde::{Deserialize, Serialize};
@@ -38,17 +46,30 @@ pub struct CortexConfig {
     #[serde(default)]
     pub security: SecurityConfig,
     #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    #[serde(default)]
     pub webhooks: Vec<WebhookConfig>,
     #[serde(default)]
     pub plugins: Vec<PluginConfig>,
     #[serde(default)]
     pub prompt_rollback: RollbackConfig,
     #[serde(default)]
+    pub prompt_selection: SelectionConfig,
+    #[serde(default)]
     pub score_decay: ScoreDecayConfig,
+    /// How far graph expansion in `cortex_recall` reaches, and how much
+    /// each hop counts.
+    #[serde(default)]
+    pub graph_proximity: GraphProximity,
     #[serde(default)]
     pub write_gate: WriteGateConfig,
     #[serde(default)]
     pub schemas: HashMap<String, KindSchema>,
+    #[serde(default)]
+    pub nats_publish: NatsPublishConfig,
+    /// Per-event-type overrides for the Warren event → node mapping.
+    #[serde(default)]
+    pub warren_mappings: HashMap<String, WarrenMappingConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,7 +80,25 @@ pub struct ServerConfig {
     pub data_dir: PathBuf,
     pub nats_url: String,
     pub nats_enabled: bool,
+    /// Consume Warren events through a durable JetStream consumer instead
+    /// of a core subscription, so events published while down are kept.
+    pub nats_jetstream: bool,
+    /// JetStream stream name (created if missing).
+    pub nats_stream: String,
+    /// Durable JetStream consumer name.
+    pub nats_consumer: String,
+    /// Subjects the Warren adapter consumes, NATS wildcards allowed. With
+    /// `nats_jetstream` they are also the subjects a new stream captures.
+    pub nats_subjects: Vec<String>,
+    /// Publish Warren events that fail to ingest to this subject. When
+    /// unset they are appended to `<data_dir>/dead_letters.jsonl`.
+    pub nats_dead_letter_subject: Option<String>,
     pub max_message_size: usize,
+    /// Open the database read-only: write endpoints are refused and the
+    /// auto-linker, retention and ingest loops don't run.
+    pub read_only: bool,
+    /// Threads used to rebuild the vector index at startup. 0 = one per core.
+    pub index_rebuild_threads: usize,
 }
 
 impl Default for ServerConfig {
@@ -70,7 +109,14 @@ impl Default for ServerConfig {
             data_dir: PathBuf::from("./data"),
             nats_url: "nats://localhost:4222".into(),
             nats_enabled: true,
+            nats_jetstream: false,
+            nats_stream: "WARREN".into(),
+            nats_consumer: "cortex".into(),
+            nats_subjects: vec!["warren.>".into()],
+            nats_dead_letter_subject: None,
             max_message_size: 16 * 1024 * 1024,
+            read_only: false,
+            index_rebuild_threads: 0,
         }
     }
 }
@@ -78,12 +124,63 @@ impl Default for ServerConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct SchemaConfig {
-    /// Registered node kinds. Defaults to the 8 built-in kinds.
-    pub node_kinds: Vec<String>,
+    /// Registered node kinds, on top of the built-in ones (which are always
+    /// registered).
+    pub node_kinds: Vec<NodeKindEntry>,
+    /// Reject writes whose kind isn't registered. Off by default: unknown
+    /// kinds are accepted as new kinds.
+    pub strict_kinds: bool,
     /// Registered relation types. Defaults to the 8 built-in relations.
     pub relations: Vec<String>,
 }
 
+/// A `node_kinds` entry: a bare kind name, or a table that also sets how
+/// the kind is displayed and the importance its nodes default to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum NodeKindEntry {
+    Name(String),
+    Detailed {
+        name: String,
+        display_name: Option<String>,
+        default_importance: Option<f32>,
+    },
+}
+
+impl NodeKindEntry {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Name(name) | Self::Detailed { name, .. } => name,
+        }
+    }
+}
+
+impl SchemaConfig {
+    /// The kind registry: built-in kinds plus every valid `node_kinds`
+    /// entry. Invalid entries are reported by [`CortexConfig::validate`].
+    pub fn kind_registry(&self) -> KindRegistry {
+        let mut registry = KindRegistry::new().strict(self.strict_kinds);
+        for entry in &self.node_kinds {
+            let Ok(name) = NodeKind::new(entry.name()) else {
+                continue;
+            };
+            registry.register(match entry {
+                NodeKindEntry::Name(_) => KindDefinition::new(name),
+                NodeKindEntry::Detailed {
+                    display_name,
+                    default_importance,
+                    ..
+                } => KindDefinition {
+                    name,
+                    display_name: display_name.clone(),
+                    default_importance: *default_importance,
+                },
+            });
+        }
+        registry
+    }
+}
+
 impl Default for SchemaConfig {
     fn default() -> Self {
         Self {
@@ -96,7 +193,11 @@ impl Default for SchemaConfig {
                 "preference".into(),
                 "pattern".into(),
                 "observation".into(),
-            ],
+            ]
+            .into_iter()
+            .map(NodeKindEntry::Name)
+            .collect(),
+            strict_kinds: false,
             relations: vec![
                 "informed_by".into(),
                 "led_to".into(),
@@ -115,12 +216,16 @@ impl Default for SchemaConfig {
 #[serde(default)]
 pub struct EmbeddingConfig {
     pub model: String,
+    /// Embeddings kept in the in-memory LRU, keyed by input text. Repeated
+    /// inputs (re-imports, retries) skip the model. 0 disables the cache.
+    pub cache_capacity: usize,
 }
 
 impl Default for EmbeddingConfig {
     fn default() -> Self {
         Self {
             model: "BAAI/bge-small-en-v1.5".into(),
+            cache_capacity: cortex_core::vector::DEFAULT_EMBEDDING_CACHE_CAPACITY,
         }
     }
 }
@@ -134,6 +239,16 @@ pub struct AutoLinkerTomlConfig {
     pub dedup_threshold: f32,
     pub decay_rate_per_day: f32,
     pub max_edges_per_node: usize,
+    /// Link same-session nodes created within this many minutes of each
+    /// other, regardless of similarity. 0 = disabled.
+    pub co_occurrence_window_minutes: u64,
+    /// How detected contradictions are resolved: "flag_only",
+    /// "keep_newest", "keep_highest_importance" or "keep_highest_confidence".
+    pub contradiction_strategy: ContradictionStrategy,
+    /// Add a `supersedes` edge from the winner to the loser of a contradiction.
+    pub supersede_on_contradiction: bool,
+    /// Multiplier for the loser's importance. 1.0 = unchanged.
+    pub loser_importance_factor: f32,
     /// Whether to run legacy hardcoded structural rules.
     /// None = auto (true when no rules defined, false when rules exist).
     pub legacy_rules_enabled: Option<bool>,
@@ -151,6 +266,10 @@ impl Default for AutoLinkerTomlConfig {
             dedup_threshold: 0.92,
             decay_rate_per_day: 0.01,
             max_edges_per_node: 50,
+            co_occurrence_window_minutes: 0,
+            contradiction_strategy: ContradictionStrategy::default(),
+            supersede_on_contradiction: false,
+            loser_importance_factor: 0.5,
             legacy_rules_enabled: None,
             rules: Vec::new(),
         }
@@ -167,6 +286,17 @@ pub struct BriefingTomlConfig {
     pub sections: Vec<BriefingSectionConfig>,
     /// Node kinds to exclude from auto-discovered briefing sections.
     pub exclude_kinds: Vec<String>,
+    /// Per-agent overrides, e.g. `[briefing.agents.kai]`.
+    pub agents: HashMap<String, AgentBriefingConfig>,
+    /// Sections built from node kinds, e.g. `[[briefing.kind_sections]]`.
+    /// Replaces the built-in list when set.
+    pub kind_sections: Option<Vec<BriefingSectionSpec>>,
+    /// Append each node's strongest 1-hop relations to briefings.
+    pub include_relations: bool,
+    /// Relations per node when `include_relations` is on (default 3).
+    pub max_relations_per_node: Option<usize>,
+    /// Keep nodes past their `expires_at` in briefings.
+    pub include_expired: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -205,6 +335,17 @@ impl Default for NatsIngestConfig {
     }
 }
 
+/// Overrides for one Warren event type. Unset fields keep the built-in
+/// mapping. Templates interpolate `{field}` from the event payload.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct WarrenMappingConfig {
+    pub kind: Option<String>,
+    pub importance: Option<f32>,
+    pub title_template: Option<String>,
+    pub body_template: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
 pub struct WebhookIngestConfig {
@@ -230,8 +371,12 @@ pub struct StdinIngestConfig {
 pub struct ObservabilityConfig {
     pub prometheus: bool,
     pub prometheus_port: u16,
+    /// Export tracing spans over OTLP. Needs a build with the `otel` feature.
     pub opentelemetry: bool,
+    /// OTLP/gRPC collector address. Default: `http://localhost:4317`.
     pub otlp_endpoint: Option<String>,
+    /// `service.name` on exported spans. Default: "cortex".
+    pub otlp_service_name: String,
     /// Enable the /metrics endpoint (Prometheus text format). Default: true.
     pub metrics_enabled: bool,
     /// Require bearer token auth on /metrics. Default: false (Prometheus scrapes unauthenticated).
@@ -245,6 +390,7 @@ impl Default for ObservabilityConfig {
             prometheus_port: 0,
             opentelemetry: false,
             otlp_endpoint: None,
+            otlp_service_name: "cortex".into(),
             metrics_enabled: true,
             metrics_require_auth: false,
         }
@@ -259,6 +405,11 @@ pub struct SecurityConfig {
     pub auth_enabled: bool,
     /// Fallback inline token. Prefer CORTEX_AUTH_TOKEN env var.
     pub auth_token: Option<String>,
+    /// Additional bearer tokens, each with its own scope (`[[security.tokens]]`).
+    pub tokens: Vec<ApiToken>,
+    /// Also record searches, traversals, and briefings in the audit log.
+    /// Read volume is high, so this is off by default.
+    pub audit_reads: bool,
 }
 
 impl SecurityConfig {
@@ -269,6 +420,57 @@ impl SecurityConfig {
             .filter(|s| !s.is_empty())
             .or_else(|| self.auth_token.clone())
     }
+
+    /// Every accepted token with its scope. The main token is read-write.
+    pub fn resolved_tokens(&self) -> Vec<(String, TokenScope)> {
+        self.resolved_token()
+            .map(|t| (t, TokenScope::ReadWrite))
+            .into_iter()
+            .chain(
+                self.tokens
+                    .iter()
+                    .filter(|t| !t.token.is_empty())
+                    .map(|t| (t.token.clone(), t.scope)),
+            )
+            .collect()
+    }
+}
+
+/// `[rate_limit]`: request caps shared by the gRPC and HTTP servers.
+/// Zero means no limit, which is the default for all of them.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// Requests in flight at once. Requests over the cap are refused, not queued.
+    pub max_concurrent: usize,
+    /// Requests per second across every method.
+    pub requests_per_second: f64,
+    /// Requests per second for one method, keyed by gRPC method name
+    /// (`SimilaritySearch`) or HTTP route (`/search`, `/briefing/:agent_id`).
+    pub methods: HashMap<String, f64>,
+}
+
+/// A bearer token entry under `[[security.tokens]]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    /// Label for the token's holder, e.g. "grafana". Informational only.
+    #[serde(default)]
+    pub name: Option<String>,
+    pub token: String,
+    #[serde(default)]
+    pub scope: TokenScope,
+}
+
+/// What a bearer token may do on the HTTP API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenScope {
+    /// HTTP `GET`, `HEAD` and `OPTIONS` requests, and gRPC methods that
+    /// don't write (searches, traversals, `GetNode`, `Stats`, ...).
+    #[default]
+    Read,
+    /// Every request.
+    ReadWrite,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -305,22 +507,151 @@ impl CortexConfig {
     /// Validate the config. Returns a list of errors if invalid.
     pub fn validate(&self) -> Vec<String> {
         let mut errors = Vec::new();
-        for kind in &self.schema.node_kinds {
-            if let Err(e) = NodeKind::new(kind) {
+        for entry in &self.schema.node_kinds {
+            if let Err(e) = NodeKind::new(entry.name()) {
                 errors.push(format!("schema.node_kinds: {}", e));
             }
+            if let NodeKindEntry::Detailed {
+                default_importance: Some(importance),
+                ..
+            } = entry
+            {
+                if !(0.0..=1.0).contains(importance) {
+                    errors.push(format!(
+                        "schema.node_kinds.{}.default_importance: must be between 0.0 and 1.0",
+                        entry.name()
+                    ));
+                }
+            }
         }
         for rel in &self.schema.relations {
             if let Err(e) = Relation::new(rel) {
                 errors.push(format!("schema.relations: {}", e));
             }
         }
+        let rates = std::iter::once((
+            "rate_limit.requests_per_second".to_string(),
+            self.rate_limit.requests_per_second,
+        ))
+        .chain(
+            self.rate_limit
+                .methods
+                .iter()
+                .map(|(method, rate)| (format!("rate_limit.methods.{}", method), *rate)),
+        );
+        for (field, rate) in rates {
+            if !rate.is_finite() || rate < 0.0 {
+                errors.push(format!("{}: must be a non-negative number", field));
+            }
+        }
+        for method in self.rate_limit.methods.keys() {
+            if !crate::rate_limit::is_known_method(method) {
+                errors.push(format!(
+                    "rate_limit.methods.{}: not a gRPC method or HTTP route",
+                    method
+                ));
+            }
+        }
+        if let Err(pii_errors) = self.write_gate.pii.validate() {
+            errors.extend(
+                pii_errors
+                    .into_iter()
+                    .map(|e| format!("write_gate.pii.patterns: {}", e)),
+            );
+        }
+        for (kind, ttl) in &self.retention.expire_after {
+            if let Err(e) = cortex_core::policies::retention::parse_ttl(ttl) {
+                errors.push(format!("retention.expire_after.{}: {}", kind, e));
+            }
+        }
+        let alpha = self.prompt_selection.linucb_alpha;
+        if !alpha.is_finite() || alpha < 0.0 {
+            errors.push("prompt_selection.linucb_alpha: must be a non-negative number".into());
+        }
+        if let Err(e) = self.graph_proximity.validate() {
+            errors.push(format!("graph_proximity: {}", e));
+        }
         // Validate auto-linker rules
         for rule in &self.auto_linker.rules {
             if let Err(e) = rule.validate() {
                 errors.push(format!("auto_linker.rules: {}", e));
             }
         }
+        if self.server.nats_subjects.is_empty() {
+            errors.push("server.nats_subjects: must not be empty".into());
+        }
+        for (event_type, mapping) in &self.warren_mappings {
+            #[cfg(feature = "warren")]
+            if !warren_adapter::WarrenEvent::EVENT_TYPES.contains(&event_type.as_str()) {
+                errors.push(format!(
+                    "warren_mappings.{}: unknown event type (expected one of {})",
+                    event_type,
+                    warren_adapter::WarrenEvent::EVENT_TYPES.join(", ")
+                ));
+            }
+            if let Some(kind) = &mapping.kind {
+                if let Err(e) = NodeKind::new(kind) {
+                    errors.push(format!("warren_mappings.{}.kind: {}", event_type, e));
+                }
+            }
+            if let Some(importance) = mapping.importance {
+                if !(0.0..=1.0).contains(&importance) {
+                    errors.push(format!(
+                        "warren_mappings.{}.importance: must be between 0.0 and 1.0",
+                        event_type
+                    ));
+                }
+            }
+        }
+        let kind_sections = self
+            .briefing
+            .kind_sections
+            .clone()
+            .unwrap_or_else(BriefingSectionSpec::defaults);
+        for (i, spec) in kind_sections.iter().enumerate() {
+            if spec.kinds.is_empty() {
+                errors.push(format!(
+                    "briefing.kind_sections[{}].kinds: must not be empty",
+                    i
+                ));
+            }
+            for kind in &spec.kinds {
+                if let Err(e) = NodeKind::new(kind) {
+                    errors.push(format!("briefing.kind_sections[{}].kinds: {}", i, e));
+                }
+            }
+            if let Some(min) = spec.min_importance {
+                if !(0.0..=1.0).contains(&min) {
+                    errors.push(format!(
+                        "briefing.kind_sections[{}].min_importance: must be between 0.0 and 1.0",
+                        i
+                    ));
+                }
+            }
+        }
+        let mut section_keys: Vec<String> =
+            BRIEFING_SECTIONS.iter().map(|s| s.to_string()).collect();
+        section_keys.extend(kind_sections.iter().map(|s| s.key()));
+        for (agent, overrides) in &self.briefing.agents {
+            for section in overrides.sections.iter().flatten() {
+                if !section_keys.contains(section) {
+                    errors.push(format!(
+                        "briefing.agents.{}.sections: unknown section '{}' (expected one of {})",
+                        agent,
+                        section,
+                        section_keys.join(", ")
+                    ));
+                }
+            }
+            if let Some(min) = overrides.min_importance {
+                if !(0.0..=1.0).contains(&min) {
+                    errors.push(format!(
+                        "briefing.agents.{}.min_importance: must be between 0.0 and 1.0",
+                        agent
+                    ));
+                }
+            }
+        }
         errors
     }
 
@@ -362,6 +693,10 @@ impl CortexConfig {
                 cortex_core::DecayConfig::new()
                     .with_daily_decay_rate(self.auto_linker.decay_rate_per_day),
             )
+            .with_max_edges_per_node(self.auto_linker.max_edges_per_node)
+            .with_contradiction_strategy(self.auto_linker.contradiction_strategy)
+            .with_supersede_on_contradiction(self.auto_linker.supersede_on_contradiction)
+            .with_loser_importance_factor(self.auto_linker.loser_importance_factor)
             .with_embedding_model(self.embedding.model.clone())
             .with_rules(self.auto_linker.rules.clone());
 
@@ -369,8 +704,33 @@ impl CortexConfig {
             config = config.with_legacy_rules_enabled(enabled);
         }
 
+        if self.auto_linker.co_occurrence_window_minutes > 0 {
+            config = config.with_co_occurrence(TemporalCoOccurrenceRule {
+                window: chrono::Duration::minutes(
+                    self.auto_linker.co_occurrence_window_minutes as i64,
+                ),
+                ..Default::default()
+            });
+        }
+
         config
     }
+
+    pub fn briefing_config(&self) -> BriefingConfig {
+        BriefingConfig {
+            exclude_kinds: self.briefing.exclude_kinds.clone(),
+            agents: self.briefing.agents.clone(),
+            kind_sections: self
+                .briefing
+                .kind_sections
+                .clone()
+                .unwrap_or_else(BriefingSectionSpec::defaults),
+            include_relations: self.briefing.include_relations,
+            max_relations_per_node: self.briefing.max_relations_per_node.unwrap_or(3),
+            include_expired: self.briefing.include_expired,
+            ..Default::default()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -465,6 +825,271 @@ enabled = true
         assert!(linker_config.use_legacy_rules());
     }
 
+    #[test]
+    fn test_auto_linker_contradiction_strategy() {
+        let toml_str = r#"
+[auto_linker]
+contradiction_strategy = "keep_highest_importance"
+supersede_on_contradiction = true
+loser_importance_factor = 0.25
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        let linker_config = config.auto_linker_config();
+        assert_eq!(
+            linker_config.contradiction_strategy,
+            ContradictionStrategy::KeepHighestImportance
+        );
+        assert!(linker_config.supersede_on_contradiction);
+        assert_eq!(linker_config.loser_importance_factor, 0.25);
+
+        let default: CortexConfig = toml::from_str("").unwrap();
+        assert_eq!(
+            default.auto_linker.contradiction_strategy,
+            ContradictionStrategy::KeepNewest
+        );
+        assert!(!default.auto_linker.supersede_on_contradiction);
+    }
+
+    #[test]
+    fn test_nats_publish_config() {
+        assert!(!CortexConfig::default().nats_publish.enabled);
+
+        let toml_str = r#"
+[nats_publish]
+enabled = true
+buffer = 64
+
+[nats_publish.subjects]
+node_created = "events.cortex.learned"
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.nats_publish.enabled);
+        assert_eq!(config.nats_publish.buffer, 64);
+        assert_eq!(config.nats_publish.url, None);
+        assert_eq!(
+            config.nats_publish.subjects.node_created,
+            "events.cortex.learned"
+        );
+        assert_eq!(
+            config.nats_publish.subjects.edge_created,
+            "cortex.edge.created"
+        );
+    }
+
+    #[test]
+    fn test_security_tokens_config() {
+        let toml_str = r#"
+[security]
+auth_enabled = true
+
+[[security.tokens]]
+name = "grafana"
+token = "dash-token"
+
+[[security.tokens]]
+token = "ci-token"
+scope = "read_write"
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        let tokens = &config.security.tokens;
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].name.as_deref(), Some("grafana"));
+        assert_eq!(tokens[0].scope, TokenScope::Read, "scope defaults to read");
+        assert_eq!(tokens[1].scope, TokenScope::ReadWrite);
+        assert!(config
+            .security
+            .resolved_tokens()
+            .contains(&("dash-token".to_string(), TokenScope::Read)));
+    }
+
+    #[test]
+    fn test_warren_mappings_config() {
+        let toml_str = r#"
+[warren_mappings."gate.rejected"]
+kind = "pattern"
+importance = 0.9
+title_template = "Gate {gate_id} rejected: {reason}"
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        let mapping = &config.warren_mappings["gate.rejected"];
+        assert_eq!(mapping.kind.as_deref(), Some("pattern"));
+        assert_eq!(mapping.importance, Some(0.9));
+        assert_eq!(mapping.body_template, None);
+        assert!(config.validate().is_empty());
+
+        let toml_str = r#"
+[warren_mappings."gate.rejected"]
+kind = "Not A Kind"
+importance = 1.5
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.validate().len(), 2);
+    }
+
+    #[cfg(feature = "warren")]
+    #[test]
+    fn test_unknown_warren_mapping_rejected() {
+        let toml_str = r#"
+[warren_mappings."gate.rejectd"]
+kind = "pattern"
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        let errors = config.validate();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("gate.rejectd"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn test_briefing_agent_overrides_config() {
+        let toml_str = r#"
+[briefing.agents.kai]
+max_items_per_section = 5
+sections = ["goals", "decisions"]
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        let kai = &config.briefing.agents["kai"];
+        assert_eq!(kai.max_items_per_section, Some(5));
+        assert_eq!(kai.max_chars, None);
+        assert!(config.validate().is_empty());
+
+        let toml_str = r#"
+[briefing.agents.kai]
+sections = ["goals", "gossip"]
+min_importance = 2.0
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.validate().len(), 2);
+    }
+
+    #[test]
+    fn test_briefing_kind_sections_config() {
+        let toml_str = r#"
+[[briefing.kind_sections]]
+heading = "Open Risks"
+kinds = ["risk", "constraint"]
+min_importance = 0.6
+order = "recent"
+
+[briefing.agents.kai]
+sections = ["risks"]
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        let specs = config.briefing.kind_sections.as_ref().unwrap();
+        assert_eq!(specs[0].heading, "Open Risks");
+        assert_eq!(specs[0].order, cortex_core::briefing::SectionOrder::Recent);
+        assert_eq!(specs[0].limit, None);
+        assert!(config.validate().is_empty());
+
+        let toml_str = r#"
+[[briefing.kind_sections]]
+heading = "Broken"
+kinds = []
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.validate().len(), 1);
+    }
+
+    #[test]
+    fn test_rate_limit_methods_validated() {
+        let toml_str = r#"
+[rate_limit.methods]
+SimilaritySearch = 20.0
+"/briefing/:agent_id" = 5.0
+SimilarSearch = 20.0
+"/briefing" = -1.0
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        let mut errors = config.validate();
+        errors.sort();
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert!(errors[0].contains("/briefing: must be a non-negative"));
+        assert!(errors[1].contains("/briefing: not a gRPC method"));
+        assert!(errors[2].contains("SimilarSearch: not a gRPC method"));
+    }
+
+    #[test]
+    fn test_retention_ttls_validated() {
+        let toml_str = r#"
+[retention]
+default_ttl_days = 0
+
+[retention.expire_after]
+observation = "30d"
+event = "0d"
+signal = "-1h"
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        let errors = config.validate();
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors
+            .iter()
+            .all(|e| e.starts_with("retention.expire_after.")));
+    }
+
+    #[test]
+    fn test_pii_patterns_validated() {
+        let toml_str = r#"
+[write_gate.pii]
+enabled = true
+
+[[write_gate.pii.patterns]]
+name = "ticket"
+regex = "TCK-\\d+"
+
+[[write_gate.pii.patterns]]
+name = "broken"
+regex = "[a-z"
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        let errors = config.validate();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].starts_with("write_gate.pii.patterns: pattern 'broken'"));
+    }
+
+    #[test]
+    fn test_schema_node_kinds_registry() {
+        let toml_str = r#"
+[schema]
+strict_kinds = true
+node_kinds = [
+    "conversation",
+    { name = "risk", display_name = "Open risk", default_importance = 0.8 },
+]
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.validate().is_empty());
+        let registry = config.schema.kind_registry();
+        assert!(registry.is_strict());
+        let risk = NodeKind::new("risk").unwrap();
+        assert_eq!(registry.get(&risk).unwrap().display_name(), "Open risk");
+        assert_eq!(registry.default_importance(&risk), Some(0.8));
+        // Built-ins stay registered even when not listed
+        assert!(NodeKind::new_checked("decision", &registry).is_ok());
+        assert!(NodeKind::new_checked("conversation", &registry).is_ok());
+        assert!(NodeKind::new_checked("decison", &registry).is_err());
+
+        let toml_str = r#"
+[schema]
+node_kinds = [{ name = "Bad Kind" }, { name = "risk", default_importance = 1.5 }]
+"#;
+        let config: CortexConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.validate().len(), 2);
+    }
+
+    #[test]
+    fn test_linucb_alpha_validation() {
+        let config: CortexConfig =
+            toml::from_str("[prompt_selection]\nlinucb_alpha = 0.0").unwrap();
+        assert!(config.validate().is_empty());
+
+        let config: CortexConfig =
+            toml::from_str("[prompt_selection]\nlinucb_alpha = -0.5").unwrap();
+        assert_eq!(
+            config.validate(),
+            vec!["prompt_selection.linucb_alpha: must be a non-negative number".to_string()]
+        );
+    }
+
     #[test]
     fn test_auto_linker_rules_validation() {
         let config = CortexConfig::default();
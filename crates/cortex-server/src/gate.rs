@@ -0,0 +1,524 @@
+//! The write gate as the server's write paths run it.
+//!
+//! [`GatedWrite`] runs the core [`WriteGate`] checks for one write, settles
+//! the failures under the configured [`GateMode`] and keeps the gate
+//! counters, so every path that creates nodes treats them the same way.
+
+use crate::http::metrics::CortexMetrics;
+use cortex_core::{
+    embedding_input, AuditAction, AuditEntry, AuditLog, Embedding, EmbeddingService, GateCheck,
+    GateMode, GateRejection, GateResult, KindRegistry, Node, PiiOutcome, SchemaValidator, Storage,
+    VectorIndex, WriteGate, WriteGateConfig,
+};
+use std::sync::RwLock;
+
+/// How a stored node got through the gate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GateOutcome {
+    /// Every check passed.
+    Passed,
+    /// Stored with `gate_warnings`.
+    Warned,
+    /// The gate was disabled or overridden; only the schema was checked.
+    Skipped,
+}
+
+impl GateOutcome {
+    /// Label used in `[AUDIT]` log lines.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Passed => "PASS",
+            Self::Warned => "WARN",
+            Self::Skipped => "SKIPPED",
+        }
+    }
+}
+
+/// The gate checks for writes under one gate configuration.
+pub struct GatedWrite<'a> {
+    config: &'a WriteGateConfig,
+    schema: &'a SchemaValidator,
+    metrics: Option<&'a CortexMetrics>,
+    skip: bool,
+    caller: &'a str,
+}
+
+impl<'a> GatedWrite<'a> {
+    pub fn new(config: &'a WriteGateConfig, schema: &'a SchemaValidator) -> Self {
+        Self {
+            config,
+            schema,
+            metrics: None,
+            skip: false,
+            caller: "",
+        }
+    }
+
+    /// Update these counters as writes are settled. Dry runs leave it unset.
+    pub fn with_metrics(mut self, metrics: &'a CortexMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Skip the gate checks for an authorised override. The schema and PII
+    /// checks still run.
+    pub fn skip(mut self, skip: bool) -> Self {
+        self.skip = skip;
+        self
+    }
+
+    /// Who the write claims to come from: the client-supplied `x-agent-id`
+    /// header. Bearer tokens carry no identity, so this is self-asserted and
+    /// any client can claim a name in `[write_gate] trusted_agents`. Without
+    /// one no write is trusted.
+    pub fn caller(mut self, caller: &'a str) -> Self {
+        self.caller = caller;
+        self
+    }
+
+    /// Whether the caller is trusted and so skips the substance and
+    /// specificity checks.
+    pub fn trusted(&self) -> bool {
+        self.active() && !self.caller.is_empty() && self.config.is_trusted(self.caller)
+    }
+
+    /// The node kinds writes may use.
+    pub fn kinds(&self) -> &KindRegistry {
+        self.schema.kinds()
+    }
+
+    fn active(&self) -> bool {
+        self.config.enabled && !self.skip
+    }
+
+    /// Checks that only read the node, run before it is embedded: the kind
+    /// registry, PII, then substance and specificity, then the schema.
+    ///
+    /// Every path that creates nodes screens them, so this is where strict
+    /// kinds are enforced; like PII, the kind check runs even when the rest
+    /// of the gate is skipped or disabled. PII is configured on its own by
+    /// `[write_gate.pii]`. It goes before the text checks so a redacted node
+    /// is what gets checked and embedded; PII warnings are attached to the
+    /// node here.
+    pub fn screen(&self, node: &mut Node) -> Vec<GateRejection> {
+        let mut rejections = Vec::new();
+        if let GateResult::Reject(r) = WriteGate::check_kind(node, self.schema) {
+            rejections.push(r);
+        }
+        match WriteGate::check_pii(node, self.config) {
+            PiiOutcome::Clean => {}
+            PiiOutcome::Reject(r) => rejections.push(r),
+            PiiOutcome::Warn(r) => {
+                self.count(node, std::slice::from_ref(&r), GateMode::Warn);
+                let _ = WriteGate::admit(node, r, GateMode::Warn);
+            }
+            PiiOutcome::Redacted(redacted) => *node = *redacted,
+        }
+        if self.active() && !self.trusted() {
+            rejections.extend(WriteGate::check_text(node, self.config, self.caller));
+        }
+        if let GateResult::Reject(r) = WriteGate::check_schema(node, self.schema) {
+            rejections.push(r);
+        }
+        rejections
+    }
+
+    /// Whether these failures refuse the write. Warn mode stores the node
+    /// anyway, except for personal data found in PII reject mode and an
+    /// unregistered kind under strict kinds.
+    fn refuses(&self, rejections: &[GateRejection]) -> bool {
+        !rejections.is_empty()
+            && (self.config.mode == GateMode::Enforce
+                || rejections
+                    .iter()
+                    .any(|r| matches!(r.check, GateCheck::Pii | GateCheck::Kind)))
+    }
+
+    /// Whether a node that failed screening is refused without embedding it.
+    /// Warn mode goes on to the conflict check so every warning is recorded.
+    pub fn refuses_early(&self, rejections: &[GateRejection]) -> bool {
+        self.refuses(rejections)
+    }
+
+    /// The conflict check, which needs the node's embedding. Failures are
+    /// added to `rejections`.
+    pub fn check<S: Storage, V: VectorIndex>(
+        &self,
+        node: &Node,
+        embedding: &Embedding,
+        index: &V,
+        storage: &S,
+        rejections: &mut Vec<GateRejection>,
+    ) {
+        if !self.active() {
+            return;
+        }
+        if let GateResult::Reject(r) =
+            WriteGate::check_conflict(node, embedding, index, storage, self.config)
+        {
+            rejections.push(r);
+        }
+    }
+
+    /// The conflict check against nodes accepted earlier in the same batch,
+    /// which are not in the index yet. Each must carry its embedding.
+    pub fn check_among(
+        &self,
+        node: &Node,
+        embedding: &Embedding,
+        earlier: &[Node],
+        rejections: &mut Vec<GateRejection>,
+    ) {
+        if !self.active() {
+            return;
+        }
+        if let GateResult::Reject(r) =
+            WriteGate::check_conflict_among(node, embedding, earlier, self.config)
+        {
+            rejections.push(r);
+        }
+    }
+
+    /// Record the result and settle it under the gate mode. `Err` carries
+    /// every failure when the node is refused; otherwise the node carries any
+    /// warnings and may be stored.
+    pub fn settle(
+        &self,
+        node: &mut Node,
+        rejections: Vec<GateRejection>,
+    ) -> Result<GateOutcome, Vec<GateRejection>> {
+        let refused = self.refuses(&rejections);
+        if let (true, Some(metrics)) = (self.active(), self.metrics) {
+            metrics
+                .gate_stats
+                .record(node.kind.as_str(), &rejections, !refused);
+        }
+        if !rejections.is_empty() {
+            if refused {
+                self.count(node, &rejections, GateMode::Enforce);
+                return Err(rejections);
+            }
+            self.count(node, &rejections, GateMode::Warn);
+            for r in rejections {
+                // Warn mode never hands the rejection back
+                let _ = WriteGate::admit(node, r, GateMode::Warn);
+            }
+        }
+        let outcome = if !self.active() {
+            GateOutcome::Skipped
+        } else if WriteGate::warnings(node).is_empty() {
+            GateOutcome::Passed
+        } else {
+            GateOutcome::Warned
+        };
+        if let Some(metrics) = self.metrics {
+            if outcome == GateOutcome::Passed {
+                metrics.gate_passed.inc();
+            } else if outcome == GateOutcome::Skipped {
+                metrics.gate_skipped.inc();
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Gate and embed one new node, leaving its embedding on the node for
+    /// the caller to store. The outer error is a failure to embed; the inner
+    /// one lists every failed check of a refused node.
+    pub fn admit<S: Storage, E: EmbeddingService, V: VectorIndex>(
+        &self,
+        node: &mut Node,
+        storage: &S,
+        embedder: &E,
+        index: &RwLock<V>,
+    ) -> cortex_core::Result<Result<GateOutcome, Vec<GateRejection>>> {
+        let mut rejections = self.screen(node);
+        if self.refuses_early(&rejections) {
+            return Ok(self.settle(node, rejections));
+        }
+        let embedding = embedder.embed(&embedding_input(node))?;
+        self.check(
+            node,
+            &embedding,
+            &*index.read().unwrap(),
+            storage,
+            &mut rejections,
+        );
+        let outcome = self.settle(node, rejections);
+        if outcome.is_ok() {
+            node.embedding = Some(embedding);
+        }
+        Ok(outcome)
+    }
+
+    /// [`admit`](Self::admit) a new node, then store and index it.
+    pub fn create<S: Storage, E: EmbeddingService, V: VectorIndex>(
+        &self,
+        node: &mut Node,
+        storage: &S,
+        embedder: &E,
+        index: &RwLock<V>,
+    ) -> cortex_core::Result<Result<GateOutcome, Vec<GateRejection>>> {
+        let outcome = match self.admit(node, storage, embedder, index)? {
+            Ok(outcome) => outcome,
+            Err(rejections) => return Ok(Err(rejections)),
+        };
+        storage.put_node(node)?;
+        if let Some(embedding) = &node.embedding {
+            index.write().unwrap().insert_node(node, embedding)?;
+        }
+        Ok(Ok(outcome))
+    }
+
+    /// Checks for an edit of `before` into `node`. An edit that changes the
+    /// title or body is screened like a new node, short of the conflict
+    /// check, which would find the node itself; any other edit only has its
+    /// schema checked.
+    pub fn update(
+        &self,
+        before: &Node,
+        node: &mut Node,
+    ) -> Result<GateOutcome, Vec<GateRejection>> {
+        let text_changed =
+            before.data.title != node.data.title || before.data.body != node.data.body;
+        let rejections = if text_changed {
+            self.screen(node)
+        } else {
+            match WriteGate::check_schema(node, self.schema) {
+                GateResult::Reject(r) => vec![r],
+                GateResult::Pass => Vec::new(),
+            }
+        };
+        self.settle(node, rejections)
+    }
+
+    fn count(&self, node: &Node, failed: &[GateRejection], mode: GateMode) {
+        if let Some(metrics) = self.metrics {
+            metrics.count_gate_failures(node, failed, mode);
+        }
+    }
+}
+
+/// Record that `caller` stored `node` without the substance and specificity
+/// checks. A bypass is part of the write, so it goes in the audit log even
+/// when read auditing is off.
+pub fn record_bypass(log: &AuditLog, caller: &str, node: &Node) {
+    let entry = AuditEntry {
+        timestamp: chrono::Utc::now(),
+        action: AuditAction::GateBypassed,
+        target_id: node.id,
+        actor: caller.to_string(),
+        details: Some(
+            serde_json::json!({
+                "source_agent": node.source.agent,
+                "skipped": ["substance", "specificity"],
+            })
+            .to_string(),
+        ),
+    };
+    if let Err(e) = log.log(entry) {
+        tracing::error!("Audit log write failed: {}", e);
+    }
+}
+
+/// One line naming every failed check, for callers that report errors as
+/// text.
+pub fn describe(rejections: &[GateRejection]) -> String {
+    let failures: Vec<String> = rejections
+        .iter()
+        .map(|r| format!("{}: {}", r.check, r.reason))
+        .collect();
+    format!("Write gate: {}", failures.join("; "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{CountingEmbedder, HashEmbedder};
+    use cortex_core::{HnswIndex, NodeKind, RedbStorage, Source};
+
+    fn open() -> (RedbStorage, RwLock<HnswIndex>, tempfile::TempDir) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = RedbStorage::open(dir.path().join("gate.redb")).unwrap();
+        let index = RwLock::new(HnswIndex::new(HashEmbedder::DIMENSION));
+        (storage, index, dir)
+    }
+
+    /// Too short on both counts for the default gate.
+    fn substandard() -> Node {
+        Node::new(
+            NodeKind::new("fact").unwrap(),
+            "short".into(),
+            "tiny".into(),
+            Source {
+                agent: "tester".into(),
+                session: None,
+                channel: None,
+            },
+            0.5,
+        )
+    }
+
+    #[test]
+    fn warn_mode_stores_substandard_node_with_warnings() {
+        let (storage, index, _dir) = open();
+        let config = WriteGateConfig {
+            mode: GateMode::Warn,
+            ..Default::default()
+        };
+        let schema = SchemaValidator::new(Default::default());
+        let metrics = CortexMetrics::new();
+        let gate = GatedWrite::new(&config, &schema).with_metrics(&metrics);
+
+        let mut node = substandard();
+        let outcome = gate
+            .create(&mut node, &storage, &HashEmbedder, &index)
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome, GateOutcome::Warned);
+
+        let stored = storage.get_node(node.id).unwrap().unwrap();
+        let warnings = WriteGate::warnings(&stored);
+        assert!(warnings.len() >= 2, "{warnings:?}");
+        assert!(warnings.iter().any(|w| w.contains("Title too short")));
+        assert!(stored.embedding.is_some());
+        assert_eq!(index.read().unwrap().len(), 1);
+        assert_eq!(metrics.gate_passed.get(), 0);
+        assert_eq!(metrics.gate_stats.snapshot().accepted, 1);
+    }
+
+    #[test]
+    fn enforce_mode_rejects_substandard_node() {
+        let (storage, index, _dir) = open();
+        let config = WriteGateConfig::default();
+        assert_eq!(config.mode, GateMode::Enforce);
+        let schema = SchemaValidator::new(Default::default());
+        let metrics = CortexMetrics::new();
+        let gate = GatedWrite::new(&config, &schema).with_metrics(&metrics);
+        let embedder = CountingEmbedder::default();
+
+        let mut node = substandard();
+        let rejections = gate
+            .create(&mut node, &storage, &embedder, &index)
+            .unwrap()
+            .unwrap_err();
+        assert!(rejections.len() >= 2, "{rejections:?}");
+        assert_eq!(embedder.calls(), 0, "refused before embedding");
+        assert!(storage.get_node(node.id).unwrap().is_none());
+        assert_eq!(index.read().unwrap().len(), 0);
+        assert_eq!(metrics.gate_stats.snapshot().rejected, 1);
+    }
+
+    #[test]
+    fn skipped_gate_still_stores() {
+        let (storage, index, _dir) = open();
+        let config = WriteGateConfig::default();
+        let schema = SchemaValidator::new(Default::default());
+        let metrics = CortexMetrics::new();
+        let gate = GatedWrite::new(&config, &schema)
+            .with_metrics(&metrics)
+            .skip(true);
+
+        let mut node = substandard();
+        let outcome = gate
+            .create(&mut node, &storage, &HashEmbedder, &index)
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome, GateOutcome::Skipped);
+        assert!(WriteGate::warnings(&node).is_empty());
+        assert!(storage.get_node(node.id).unwrap().is_some());
+        assert_eq!(metrics.gate_skipped.get(), 1);
+    }
+
+    #[test]
+    fn trust_follows_the_caller_not_the_source_agent() {
+        let (storage, index, _dir) = open();
+        let config = WriteGateConfig {
+            trusted_agents: vec!["ingest".into()],
+            ..Default::default()
+        };
+        let schema = SchemaValidator::new(Default::default());
+
+        let mut claimed = substandard();
+        claimed.source.agent = "ingest".into();
+        let gate = GatedWrite::new(&config, &schema).caller("tester");
+        assert!(!gate.trusted());
+        assert!(gate
+            .create(&mut claimed, &storage, &HashEmbedder, &index)
+            .unwrap()
+            .is_err());
+
+        let gate = GatedWrite::new(&config, &schema).caller("ingest");
+        assert!(gate.trusted());
+        let mut node = substandard();
+        let outcome = gate
+            .create(&mut node, &storage, &HashEmbedder, &index)
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome, GateOutcome::Passed);
+    }
+
+    #[test]
+    fn pii_rejection_holds_in_warn_mode_and_under_override() {
+        let (storage, index, _dir) = open();
+        let mut config = WriteGateConfig {
+            mode: GateMode::Warn,
+            ..Default::default()
+        };
+        config.pii.enabled = true;
+        let schema = SchemaValidator::new(Default::default());
+
+        for skip in [false, true] {
+            let gate = GatedWrite::new(&config, &schema).skip(skip);
+            let mut node = substandard();
+            node.data.body = "Reach the customer at jane.doe@example.com".into();
+            let rejections = gate
+                .create(&mut node, &storage, &HashEmbedder, &index)
+                .unwrap()
+                .unwrap_err();
+            assert!(rejections.iter().any(|r| r.check == GateCheck::Pii));
+            assert!(storage.get_node(node.id).unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn unregistered_kind_is_refused_in_warn_mode_and_under_override() {
+        let (storage, index, _dir) = open();
+        let config = WriteGateConfig {
+            mode: GateMode::Warn,
+            ..Default::default()
+        };
+        let schema = SchemaValidator::new(Default::default())
+            .with_kinds(cortex_core::KindRegistry::new().strict(true));
+
+        for skip in [false, true] {
+            let gate = GatedWrite::new(&config, &schema).skip(skip);
+            let mut node = substandard();
+            node.kind = NodeKind::new("hunch").unwrap();
+            let rejections = gate
+                .create(&mut node, &storage, &HashEmbedder, &index)
+                .unwrap()
+                .unwrap_err();
+            assert!(rejections.iter().any(|r| r.check == GateCheck::Kind));
+            assert!(storage.get_node(node.id).unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn edits_are_screened_only_when_the_text_changes() {
+        let config = WriteGateConfig::default();
+        let schema = SchemaValidator::new(Default::default());
+        let gate = GatedWrite::new(&config, &schema);
+
+        let before = substandard();
+        let mut node = before.clone();
+        node.importance = 0.9;
+        assert_eq!(
+            gate.update(&before, &mut node).unwrap(),
+            GateOutcome::Passed
+        );
+
+        node.data.title = "tiny".into();
+        let rejections = gate.update(&before, &mut node).unwrap_err();
+        assert!(rejections.iter().any(|r| r.check == GateCheck::Substance));
+    }
+}
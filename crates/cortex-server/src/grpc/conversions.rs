@@ -19,6 +19,9 @@ pub fn node_to_response(node: &Node, edge_count: usize) -> NodeResponse {
             .collect(),
         tags: node.data.tags.clone(),
         importance: node.importance,
+        confidence: node.confidence,
+        expires_at: node.expires_at.map(datetime_to_timestamp),
+        deleted_at: node.deleted_at.map(datetime_to_timestamp),
         source_agent: node.source.agent.clone(),
         source_session: node.source.session.clone(),
         source_channel: node.source.channel.clone(),
@@ -41,6 +44,7 @@ pub fn edge_to_response(edge: &Edge) -> EdgeResponse {
         weight: edge.weight,
         created_at: Some(datetime_to_timestamp(edge.created_at)),
         updated_at: Some(datetime_to_timestamp(edge.updated_at)),
+        inverse_id: String::new(),
     }
 }
 
@@ -99,6 +103,14 @@ pub fn parse_strategy(s: &str) -> TraversalStrategy {
     }
 }
 
+/// Parse a path-finding strategy string. Unknown values fall back to hop count.
+pub fn parse_path_strategy(s: &str) -> PathStrategy {
+    match s.to_lowercase().as_str() {
+        "weighted" => PathStrategy::Weighted,
+        _ => PathStrategy::HopCount,
+    }
+}
+
 /// Parse VectorFilter from kind strings
 pub fn parse_kind_filter(kinds: &[String]) -> Result<Vec<NodeKind>> {
     kinds.iter().map(|s| parse_node_kind(s)).collect()
@@ -124,6 +136,31 @@ pub fn stats_to_response(stats: StorageStats, db_size: u64) -> StatsResponse {
         nodes_by_kind,
         edges_by_relation,
         db_size_bytes: db_size,
+        central_nodes: Vec::new(),
+        communities: Vec::new(),
+        modularity: 0.0,
+        table_bytes: stats.table_bytes.into_iter().collect(),
+        gate: None,
+    }
+}
+
+/// Convert a write gate tally to its proto summary
+pub fn gate_stats_to_response(snapshot: GateStatsSnapshot) -> GateSummary {
+    GateSummary {
+        accepted: snapshot.accepted,
+        rejected: snapshot.rejected,
+        recent_accepted: snapshot.recent_accepted,
+        recent_rejected: snapshot.recent_rejected,
+        by_check: snapshot.by_check.into_iter().collect(),
+        failures: snapshot
+            .failures
+            .into_iter()
+            .map(|f| GateFailure {
+                check: f.check,
+                kind: f.kind,
+                count: f.count,
+            })
+            .collect(),
     }
 }
 
@@ -307,6 +344,13 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_path_strategy() {
+        assert_eq!(parse_path_strategy("weighted"), PathStrategy::Weighted);
+        assert_eq!(parse_path_strategy("hops"), PathStrategy::HopCount);
+        assert_eq!(parse_path_strategy(""), PathStrategy::HopCount);
+    }
+
     #[test]
     fn test_parse_strategy_defaults_to_bfs() {
         assert!(matches!(parse_strategy("unknown"), TraversalStrategy::Bfs));
@@ -412,6 +456,7 @@ mod tests {
             node_counts_by_kind: by_kind,
             edge_counts_by_relation: by_relation,
             db_size_bytes: 1024,
+            table_bytes: [("nodes".to_string(), 512u64)].into(),
             oldest_node: None,
             newest_node: None,
         };
@@ -420,6 +465,7 @@ mod tests {
         assert_eq!(response.node_count, 15);
         assert_eq!(response.edge_count, 20);
         assert_eq!(response.db_size_bytes, 2048);
+        assert_eq!(response.table_bytes["nodes"], 512);
         // Debug impl produces PascalCase
         assert!(response.nodes_by_kind.contains_key("Fact"));
         assert!(response.nodes_by_kind.contains_key("Decision"));
@@ -0,0 +1,76 @@
+//! Standard gRPC health checking (`grpc.health.v1.Health`) for load balancers
+//! and Kubernetes readiness probes. The health service is served without the
+//! auth interceptor, since probes carry no token.
+
+use super::CortexServiceImpl;
+use cortex_proto::cortex_service_server::CortexServiceServer;
+use tonic::server::NamedService;
+use tonic_health::pb::health_server::{Health, HealthServer};
+use tonic_health::server::HealthReporter;
+use tonic_health::ServingStatus;
+
+/// Name probes ask about: `cortex.v1.CortexService`.
+pub const SERVICE_NAME: &str = <CortexServiceServer<CortexServiceImpl> as NamedService>::NAME;
+
+/// Build the health service. Both the whole server (`""`) and
+/// [`SERVICE_NAME`] report NOT_SERVING until [`set_status`] says otherwise.
+pub async fn health_service() -> (HealthReporter, HealthServer<impl Health>) {
+    let (mut reporter, service) = tonic_health::server::health_reporter();
+    set_status(&mut reporter, ServingStatus::NotServing).await;
+    (reporter, service)
+}
+
+/// Report `status` for the whole server and for [`SERVICE_NAME`].
+pub async fn set_status(reporter: &mut HealthReporter, status: ServingStatus) {
+    reporter.set_service_status("", status).await;
+    reporter.set_service_status(SERVICE_NAME, status).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic_health::pb::health_check_response::ServingStatus as Status;
+    use tonic_health::pb::health_client::HealthClient;
+    use tonic_health::pb::HealthCheckRequest;
+
+    async fn check(client: &mut HealthClient<tonic::transport::Channel>, service: &str) -> Status {
+        let resp = client
+            .check(HealthCheckRequest {
+                service: service.to_string(),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        Status::try_from(resp.status).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reports_serving_after_initialization() {
+        let (mut reporter, service) = health_service().await;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming =
+            tonic::transport::server::TcpIncoming::from_listener(listener, true, None).unwrap();
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_incoming(incoming),
+        );
+
+        let channel = tonic::transport::Endpoint::from_shared(format!("http://{}", addr))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        let mut client = HealthClient::new(channel);
+        assert_eq!(check(&mut client, "").await, Status::NotServing);
+        assert_eq!(check(&mut client, SERVICE_NAME).await, Status::NotServing);
+
+        set_status(&mut reporter, ServingStatus::Serving).await;
+        assert_eq!(check(&mut client, "").await, Status::Serving);
+        assert_eq!(check(&mut client, SERVICE_NAME).await, Status::Serving);
+
+        server.abort();
+    }
+}
@@ -1,11 +1,41 @@
 #![allow(dead_code)]
-mod conversions;
+pub mod conversions;
+pub mod health;
 mod service;
 
 pub use service::CortexServiceImpl;
 
 use tonic::{Request, Status};
 
+/// Every `CortexService` method, by the name used in its request path.
+pub const METHODS: &[&str] = &[
+    "AutoLinkerStatus",
+    "CreateEdge",
+    "CreateNode",
+    "DeleteEdge",
+    "DeleteNode",
+    "FindPaths",
+    "GetBriefing",
+    "GetEdges",
+    "GetNode",
+    "Health",
+    "HybridSearch",
+    "ListNodes",
+    "Neighborhood",
+    "Observe",
+    "PreviewAutoLink",
+    "QueryNodes",
+    "Reindex",
+    "RestoreNode",
+    "SimilaritySearch",
+    "Snapshot",
+    "Stats",
+    "Traverse",
+    "TraverseStream",
+    "TriggerAutoLink",
+    "UpdateNode",
+];
+
 /// Helper to extract metadata from gRPC requests
 pub fn get_metadata<T>(request: &Request<T>, key: &str) -> Option<String> {
     request
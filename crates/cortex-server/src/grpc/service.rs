@@ -1,10 +1,15 @@
+use crate::gate::{GateOutcome, GatedWrite};
 use crate::grpc::conversions::*;
-use cortex_core::briefing::BriefingEngine;
+use crate::http::metrics::CortexMetrics;
+use crate::http::ServerEmbedder;
+use crate::observations::{ObservationContext, ObserveBody};
+use cortex_core::briefing::{BriefingEngine, BriefingFormat};
 use cortex_core::*;
 // cortex_core::* imports a 1-arg `Result<T>` alias; re-import std's 2-arg form
 // so that tonic handler return types like `Result<Response<T>, Status>` resolve correctly.
 use cortex_proto::cortex_service_server::CortexService;
 use cortex_proto::*;
+use std::pin::Pin;
 use std::result::Result;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -15,25 +20,34 @@ use tonic::{Request, Response, Status};
 /// Concrete briefing engine type used by the server
 type ServerBriefingEngine = BriefingEngine<
     RedbStorage,
-    Arc<FastEmbedService>,
+    Arc<ServerEmbedder>,
     RwLockVectorIndex<HnswIndex>,
     Arc<GraphEngineImpl<RedbStorage>>,
 >;
 
 /// Concrete auto-linker type used by the server
 type ServerAutoLinker =
-    AutoLinker<RedbStorage, FastEmbedService, HnswIndex, GraphEngineImpl<RedbStorage>>;
+    AutoLinker<RedbStorage, ServerEmbedder, HnswIndex, GraphEngineImpl<RedbStorage>>;
 
 pub struct CortexServiceImpl {
     storage: Arc<RedbStorage>,
     graph_engine: Arc<GraphEngineImpl<RedbStorage>>,
     vector_index: Arc<StdRwLock<HnswIndex>>,
-    embedding_service: Arc<FastEmbedService>,
+    embedding_service: Arc<ServerEmbedder>,
     auto_linker: Arc<StdRwLock<ServerAutoLinker>>,
     graph_version: Arc<AtomicU64>,
     briefing_engine: Arc<ServerBriefingEngine>,
     hooks: Arc<HookRegistry>,
     schema_validator: Arc<SchemaValidator>,
+    /// Shared with the HTTP server; swapped on config reload.
+    write_gate: Arc<StdRwLock<WriteGateConfig>>,
+    score_decay: ScoreDecayConfig,
+    read_audit: Option<Arc<AuditLog>>,
+    /// Shared with the HTTP server, so gate counters cover both.
+    metrics: Arc<CortexMetrics>,
+    /// Needed by `Observe`; without it the RPC is unimplemented.
+    observations: Option<ObservationContext>,
+    read_only: bool,
     start_time: Instant,
 }
 
@@ -43,12 +57,16 @@ impl CortexServiceImpl {
         storage: Arc<RedbStorage>,
         graph_engine: Arc<GraphEngineImpl<RedbStorage>>,
         vector_index: Arc<StdRwLock<HnswIndex>>,
-        embedding_service: Arc<FastEmbedService>,
+        embedding_service: Arc<ServerEmbedder>,
         auto_linker: Arc<StdRwLock<ServerAutoLinker>>,
         graph_version: Arc<AtomicU64>,
         briefing_engine: Arc<ServerBriefingEngine>,
         hooks: Arc<HookRegistry>,
         schema_validator: Arc<SchemaValidator>,
+        write_gate: Arc<StdRwLock<WriteGateConfig>>,
+        score_decay: ScoreDecayConfig,
+        read_audit: Option<Arc<AuditLog>>,
+        metrics: Arc<CortexMetrics>,
     ) -> Self {
         Self {
             storage,
@@ -60,19 +78,140 @@ impl CortexServiceImpl {
             briefing_engine,
             hooks,
             schema_validator,
+            write_gate,
+            score_decay,
+            read_audit,
+            metrics,
+            observations: None,
+            read_only: false,
             start_time: Instant::now(),
         }
     }
 
+    /// Enable `Observe`, recording through the same path as the HTTP
+    /// `/agents/:name/observe` route.
+    pub fn with_observations(mut self, observations: ObservationContext) -> Self {
+        self.observations = Some(observations);
+        self
+    }
+
+    /// Refuse every write RPC with `FailedPrecondition`.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn ensure_writable(&self) -> Result<(), Status> {
+        if self.read_only {
+            return Err(Status::failed_precondition("Server is read-only"));
+        }
+        Ok(())
+    }
+
     fn get_edge_count(&self, node_id: NodeId) -> usize {
-        let outgoing = self.storage.edges_from(node_id).unwrap_or_default();
-        let incoming = self.storage.edges_to(node_id).unwrap_or_default();
-        outgoing.len() + incoming.len()
+        edge_count(&self.storage, node_id)
     }
 
     fn bump_version(&self) {
         self.graph_version.fetch_add(1, Ordering::Relaxed);
     }
+
+    /// Record a read in the audit log when `security.audit_reads` is on.
+    fn audit_read(
+        &self,
+        action: AuditAction,
+        actor: &str,
+        target_id: uuid::Uuid,
+        details: serde_json::Value,
+    ) {
+        if let Some(ref log) = self.read_audit {
+            log.log_read(action, actor, target_id, details);
+        }
+    }
+}
+
+fn edge_count(storage: &RedbStorage, node_id: NodeId) -> usize {
+    let outgoing = storage.edges_from(node_id).unwrap_or_default();
+    let incoming = storage.edges_to(node_id).unwrap_or_default();
+    outgoing.len() + incoming.len()
+}
+
+/// The core traversal described by a `Traverse` or `TraverseStream` request.
+#[allow(clippy::result_large_err)]
+fn traversal_request(req: &TraverseRequest) -> Result<TraversalRequest, Status> {
+    let start: std::result::Result<Vec<_>, _> = req
+        .start_ids
+        .iter()
+        .map(|s| s.parse::<uuid::Uuid>())
+        .collect();
+    let start = start.map_err(|e| Status::invalid_argument(format!("Invalid start_ids: {}", e)))?;
+
+    let mut traverse_req = TraversalRequest {
+        start,
+        max_depth: if req.max_depth > 0 {
+            Some(req.max_depth)
+        } else {
+            None
+        },
+        direction: parse_direction(&req.direction),
+        strategy: parse_strategy(&req.strategy),
+        limit: if req.limit > 0 {
+            Some(req.limit as usize)
+        } else {
+            None
+        },
+        include_expired: req.include_expired,
+        include_deleted: req.include_deleted,
+        ..Default::default()
+    };
+
+    if !req.relation_filter.is_empty() {
+        let relations: std::result::Result<Vec<_>, _> = req
+            .relation_filter
+            .iter()
+            .map(|s| parse_relation(s))
+            .collect();
+        traverse_req.relation_filter =
+            Some(relations.map_err(|e| Status::invalid_argument(e.to_string()))?);
+    }
+
+    if !req.kind_filter.is_empty() {
+        let kinds: std::result::Result<Vec<_>, _> =
+            req.kind_filter.iter().map(|s| parse_node_kind(s)).collect();
+        traverse_req.kind_filter =
+            Some(kinds.map_err(|e| Status::invalid_argument(e.to_string()))?);
+    }
+
+    if req.min_weight > 0.0 {
+        traverse_req.min_weight = Some(req.min_weight);
+    }
+
+    Ok(traverse_req)
+}
+
+/// `FailedPrecondition` naming every failed gate check, so the caller can
+/// fix them all at once.
+fn gate_status(rejections: &[GateRejection]) -> Status {
+    Status::failed_precondition(crate::gate::describe(rejections))
+}
+
+#[allow(clippy::result_large_err)]
+fn check_confidence(confidence: f32) -> Result<f32, Status> {
+    if (0.0..=1.0).contains(&confidence) {
+        Ok(confidence)
+    } else {
+        Err(Status::invalid_argument(format!(
+            "confidence {} out of range [0.0, 1.0]",
+            confidence
+        )))
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn check_expires_at(ts: prost_types::Timestamp) -> Result<chrono::DateTime<chrono::Utc>, Status> {
+    chrono::DateTime::from_timestamp(ts.seconds, ts.nanos.max(0) as u32)
+        .ok_or_else(|| Status::invalid_argument("expires_at is out of range"))
 }
 
 #[tonic::async_trait]
@@ -81,6 +220,7 @@ impl CortexService for CortexServiceImpl {
         &self,
         request: Request<CreateNodeRequest>,
     ) -> Result<Response<NodeResponse>, Status> {
+        self.ensure_writable()?;
         let agent_id = crate::grpc::get_metadata(&request, "x-agent-id")
             .unwrap_or_else(|| "anonymous".to_string());
         let req = request.into_inner();
@@ -94,7 +234,15 @@ impl CortexService for CortexServiceImpl {
             channel: req.source_channel,
         };
 
-        let mut node = Node::new(kind, req.title, req.body, source, req.importance);
+        let importance = self
+            .schema_validator
+            .kinds()
+            .importance(&kind, req.importance);
+        let mut node = Node::new(kind, req.title, req.body, source, importance);
+        if let Some(confidence) = req.confidence {
+            node.confidence = check_confidence(confidence)?;
+        }
+        node.expires_at = req.expires_at.map(check_expires_at).transpose()?;
 
         // Proto metadata is HashMap<String, String>; node metadata is HashMap<String, Value>
         node.data.metadata = req
@@ -104,32 +252,23 @@ impl CortexService for CortexServiceImpl {
             .collect();
         node.data.tags = req.tags;
 
-        // Schema validation
-        if let cortex_core::GateResult::Reject(r) =
-            cortex_core::WriteGate::check_schema(&node, &self.schema_validator)
-        {
-            return Err(Status::failed_precondition(r.reason));
-        }
-
-        // Generate embedding
-        let text = embedding_input(&node);
-        let embedding = self
-            .embedding_service
-            .embed(&text)
-            .map_err(|e| Status::internal(e.to_string()))?;
-        node.embedding = Some(embedding.clone());
-
-        // Store node
-        self.storage
-            .put_node(&node)
-            .map_err(|e| Status::internal(e.to_string()))?;
-
-        // Index embedding
-        {
-            let mut index = self.vector_index.write().unwrap();
-            index
-                .insert(node.id, &embedding)
-                .map_err(|e| Status::internal(e.to_string()))?;
+        // Write gate; warn mode stores the node with `gate_warnings`
+        let gate_config = self.write_gate.read().unwrap().clone();
+        let gate = GatedWrite::new(&gate_config, &self.schema_validator)
+            .with_metrics(&self.metrics)
+            .caller(&agent_id);
+        let outcome = gate
+            .create(
+                &mut node,
+                &*self.storage,
+                &*self.embedding_service,
+                &*self.vector_index,
+            )
+            .map_err(|e| Status::internal(e.to_string()))?
+            .map_err(|rejections| gate_status(&rejections))?;
+        let trusted = gate.trusted();
+        if let (true, Some(log)) = (trusted, self.storage.audit_log()) {
+            crate::gate::record_bypass(log, &agent_id, &node);
         }
 
         self.bump_version();
@@ -137,8 +276,13 @@ impl CortexService for CortexServiceImpl {
             .notify_node(&node, cortex_core::MutationAction::Created);
 
         tracing::info!(
-            "[AUDIT] gRPC CreateNode agent={} title={:?} kind={:?}",
+            "[AUDIT] gRPC CreateNode agent={} gate={} title={:?} kind={:?}",
             agent_id,
+            if trusted && outcome == GateOutcome::Passed {
+                "TRUSTED"
+            } else {
+                outcome.as_str()
+            },
             node.data.title,
             node.kind
         );
@@ -171,17 +315,20 @@ impl CortexService for CortexServiceImpl {
         &self,
         request: Request<UpdateNodeRequest>,
     ) -> Result<Response<NodeResponse>, Status> {
+        self.ensure_writable()?;
+        let agent_id = crate::grpc::get_metadata(&request, "x-agent-id").unwrap_or_default();
         let req = request.into_inner();
         let node_id = req
             .id
             .parse::<uuid::Uuid>()
             .map_err(|e| Status::invalid_argument(format!("Invalid UUID: {}", e)))?;
 
-        let mut node = self
+        let before = self
             .storage
             .get_node(node_id)
             .map_err(|e| Status::internal(e.to_string()))?
             .ok_or_else(|| Status::not_found("Node not found"))?;
+        let mut node = before.clone();
 
         // Update fields
         if let Some(title) = req.title {
@@ -203,13 +350,21 @@ impl CortexService for CortexServiceImpl {
         if let Some(importance) = req.importance {
             node.importance = importance;
         }
-
-        // Schema validation
-        if let cortex_core::GateResult::Reject(r) =
-            cortex_core::WriteGate::check_schema(&node, &self.schema_validator)
-        {
-            return Err(Status::failed_precondition(r.reason));
+        if let Some(confidence) = req.confidence {
+            node.confidence = check_confidence(confidence)?;
         }
+        if req.clear_expires_at {
+            node.expires_at = None;
+        } else if let Some(expires_at) = req.expires_at {
+            node.expires_at = Some(check_expires_at(expires_at)?);
+        }
+
+        // Edited text goes through the write gate again
+        let gate_config = self.write_gate.read().unwrap().clone();
+        GatedWrite::new(&gate_config, &self.schema_validator)
+            .caller(&agent_id)
+            .update(&before, &mut node)
+            .map_err(|rejections| gate_status(&rejections))?;
 
         // Re-generate embedding
         let text = embedding_input(&node);
@@ -229,7 +384,7 @@ impl CortexService for CortexServiceImpl {
         {
             let mut index = self.vector_index.write().unwrap();
             index
-                .insert(node.id, &embedding)
+                .insert_node(&node, &embedding)
                 .map_err(|e| Status::internal(e.to_string()))?;
         }
 
@@ -245,6 +400,7 @@ impl CortexService for CortexServiceImpl {
         &self,
         request: Request<DeleteNodeRequest>,
     ) -> Result<Response<DeleteResponse>, Status> {
+        self.ensure_writable()?;
         let agent_id = crate::grpc::get_metadata(&request, "x-agent-id")
             .unwrap_or_else(|| "anonymous".to_string());
         let req = request.into_inner();
@@ -270,6 +426,42 @@ impl CortexService for CortexServiceImpl {
         Ok(Response::new(DeleteResponse { success: true }))
     }
 
+    async fn restore_node(
+        &self,
+        request: Request<RestoreNodeRequest>,
+    ) -> Result<Response<NodeResponse>, Status> {
+        self.ensure_writable()?;
+        let agent_id = crate::grpc::get_metadata(&request, "x-agent-id")
+            .unwrap_or_else(|| "anonymous".to_string());
+        let req = request.into_inner();
+        let node_id = req
+            .id
+            .parse::<uuid::Uuid>()
+            .map_err(|e| Status::invalid_argument(format!("Invalid UUID: {}", e)))?;
+
+        let node = self.storage.restore_node(node_id).map_err(|e| match e {
+            CortexError::NodeNotFound(_) => Status::not_found("Node not found"),
+            e => Status::internal(e.to_string()),
+        })?;
+
+        // Index rebuilds skip tombstones, so the embedding may be gone
+        if let Some(embedding) = &node.embedding {
+            let mut index = self.vector_index.write().unwrap();
+            index
+                .insert_node(&node, embedding)
+                .map_err(|e| Status::internal(e.to_string()))?;
+        }
+
+        self.bump_version();
+        self.hooks
+            .notify_node(&node, cortex_core::MutationAction::Updated);
+
+        tracing::info!("[AUDIT] gRPC RestoreNode agent={} id={}", agent_id, req.id);
+
+        let edge_count = self.get_edge_count(node.id);
+        Ok(Response::new(node_to_response(&node, edge_count)))
+    }
+
     async fn list_nodes(
         &self,
         request: Request<ListNodesRequest>,
@@ -285,7 +477,11 @@ impl CortexService for CortexServiceImpl {
         }
 
         if !req.tag_filter.is_empty() {
-            filter = filter.with_tags(req.tag_filter);
+            filter = if req.all_tags {
+                filter.with_all_tags(req.tag_filter)
+            } else {
+                filter.with_tags(req.tag_filter)
+            };
         }
 
         if !req.source_agent.is_empty() {
@@ -296,24 +492,53 @@ impl CortexService for CortexServiceImpl {
             filter = filter.with_min_importance(req.min_importance);
         }
 
-        if req.limit > 0 {
-            filter = filter.with_limit(req.limit as usize);
-        }
-
         if req.offset > 0 {
             filter = filter.with_offset(req.offset as usize);
         }
 
-        let nodes = self
+        if req.include_deleted {
+            filter = filter.include_deleted();
+        }
+
+        if let Some(ts) = req.created_after {
+            filter = filter.created_after(timestamp_to_datetime(ts));
+        }
+
+        if let Some(ts) = req.created_before {
+            filter = filter.created_before(timestamp_to_datetime(ts));
+        }
+
+        let total_count = self
             .storage
-            .list_nodes(filter.clone())
+            .count_nodes(filter.clone())
             .map_err(|e| Status::internal(e.to_string()))?;
 
-        let total_count = self
+        // One extra node tells whether another page follows.
+        if req.limit > 0 {
+            filter = filter.with_limit((req.limit as usize).saturating_add(1));
+        }
+
+        if !req.cursor.is_empty() {
+            let cursor = req
+                .cursor
+                .parse::<NodeCursor>()
+                .map_err(|e| Status::invalid_argument(e.to_string()))?;
+            filter = filter.after(cursor);
+        }
+
+        let mut nodes = self
             .storage
-            .count_nodes(filter)
+            .list_nodes(filter)
             .map_err(|e| Status::internal(e.to_string()))?;
 
+        let limit = req.limit as usize;
+        let next_cursor = if limit > 0 && nodes.len() > limit {
+            nodes.truncate(limit);
+            nodes.last().map(NodeCursor::after)
+        } else {
+            None
+        };
+
         let node_responses: Vec<_> = nodes
             .iter()
             .map(|n| {
@@ -325,6 +550,7 @@ impl CortexService for CortexServiceImpl {
         Ok(Response::new(ListNodesResponse {
             nodes: node_responses,
             total_count,
+            next_cursor: next_cursor.map(|c| c.to_string()).unwrap_or_default(),
         }))
     }
 
@@ -332,6 +558,7 @@ impl CortexService for CortexServiceImpl {
         &self,
         request: Request<CreateEdgeRequest>,
     ) -> Result<Response<EdgeResponse>, Status> {
+        self.ensure_writable()?;
         let agent_id = crate::grpc::get_metadata(&request, "x-agent-id")
             .unwrap_or_else(|| "anonymous".to_string());
         let req = request.into_inner();
@@ -358,23 +585,43 @@ impl CortexService for CortexServiceImpl {
             },
         );
 
-        self.storage
-            .put_edge(&edge)
-            .map_err(|e| Status::internal(e.to_string()))?;
+        let gate = WriteGate::check_edge(
+            &edge,
+            self.storage.as_ref(),
+            &self.write_gate.read().unwrap(),
+        )
+        .map_err(|e| Status::internal(e.to_string()))?;
+        if let GateResult::Reject(r) = gate {
+            return Err(Status::failed_precondition(r.reason));
+        }
+
+        let inverse = if req.inverse {
+            self.storage.put_edge_with_inverse(&edge)
+        } else {
+            self.storage.put_edge(&edge).map(|()| None)
+        }
+        .map_err(|e| Status::internal(e.to_string()))?;
 
         self.bump_version();
-        self.hooks
-            .notify_edge(&edge, cortex_core::MutationAction::Created);
+        for e in std::iter::once(&edge).chain(&inverse) {
+            self.hooks
+                .notify_edge(e, cortex_core::MutationAction::Created);
+        }
 
         tracing::info!(
-            "[AUDIT] gRPC CreateEdge agent={} from={} to={} relation={}",
+            "[AUDIT] gRPC CreateEdge agent={} from={} to={} relation={} inverse={}",
             agent_id,
             req.from_id,
             req.to_id,
-            req.relation
+            req.relation,
+            inverse.is_some()
         );
 
-        Ok(Response::new(edge_to_response(&edge)))
+        let mut response = edge_to_response(&edge);
+        if let Some(inverse) = inverse {
+            response.inverse_id = inverse.id.to_string();
+        }
+        Ok(Response::new(response))
     }
 
     async fn get_edges(
@@ -430,6 +677,7 @@ impl CortexService for CortexServiceImpl {
         &self,
         request: Request<DeleteEdgeRequest>,
     ) -> Result<Response<DeleteResponse>, Status> {
+        self.ensure_writable()?;
         let agent_id = crate::grpc::get_metadata(&request, "x-agent-id")
             .unwrap_or_else(|| "anonymous".to_string());
         let req = request.into_inner();
@@ -438,16 +686,25 @@ impl CortexService for CortexServiceImpl {
             .parse::<uuid::Uuid>()
             .map_err(|e| Status::invalid_argument(format!("Invalid UUID: {}", e)))?;
 
-        let edge_for_hook = self.storage.get_edge(edge_id).ok().flatten();
+        let mut edges_for_hook: Vec<_> = self
+            .storage
+            .get_edge(edge_id)
+            .ok()
+            .flatten()
+            .into_iter()
+            .collect();
+        if let Some(edge) = edges_for_hook.first() {
+            edges_for_hook.extend(self.storage.paired_edge(edge).ok().flatten());
+        }
 
         self.storage
             .delete_edge(edge_id)
             .map_err(|e| Status::internal(e.to_string()))?;
 
         self.bump_version();
-        if let Some(edge) = edge_for_hook {
+        for edge in &edges_for_hook {
             self.hooks
-                .notify_edge(&edge, cortex_core::MutationAction::Deleted);
+                .notify_edge(edge, cortex_core::MutationAction::Deleted);
         }
 
         tracing::info!("[AUDIT] gRPC DeleteEdge agent={} id={}", agent_id, req.id);
@@ -459,56 +716,12 @@ impl CortexService for CortexServiceImpl {
         &self,
         request: Request<TraverseRequest>,
     ) -> Result<Response<SubgraphResponse>, Status> {
+        let agent_id = crate::grpc::get_metadata(&request, "x-agent-id")
+            .unwrap_or_else(|| "anonymous".to_string());
         let req = request.into_inner();
 
-        let start: std::result::Result<Vec<_>, _> = req
-            .start_ids
-            .iter()
-            .map(|s| s.parse::<uuid::Uuid>())
-            .collect();
-        let start =
-            start.map_err(|e| Status::invalid_argument(format!("Invalid start_ids: {}", e)))?;
-
-        let direction = parse_direction(&req.direction);
-        let strategy = parse_strategy(&req.strategy);
-
-        let mut traverse_req = TraversalRequest {
-            start,
-            max_depth: if req.max_depth > 0 {
-                Some(req.max_depth)
-            } else {
-                None
-            },
-            direction,
-            strategy,
-            limit: if req.limit > 0 {
-                Some(req.limit as usize)
-            } else {
-                None
-            },
-            ..Default::default()
-        };
-
-        if !req.relation_filter.is_empty() {
-            let relations: std::result::Result<Vec<_>, _> = req
-                .relation_filter
-                .iter()
-                .map(|s| parse_relation(s))
-                .collect();
-            traverse_req.relation_filter =
-                Some(relations.map_err(|e| Status::invalid_argument(e.to_string()))?);
-        }
-
-        if !req.kind_filter.is_empty() {
-            let kinds: std::result::Result<Vec<_>, _> =
-                req.kind_filter.iter().map(|s| parse_node_kind(s)).collect();
-            traverse_req.kind_filter =
-                Some(kinds.map_err(|e| Status::invalid_argument(e.to_string()))?);
-        }
-
-        if req.min_weight > 0.0 {
-            traverse_req.min_weight = Some(req.min_weight);
-        }
+        let traverse_req = traversal_request(&req)?;
+        let audit_subject = traverse_req.start.first().copied().unwrap_or_default();
 
         let subgraph = self
             .graph_engine
@@ -532,6 +745,16 @@ impl CortexService for CortexServiceImpl {
             .map(|(k, v)| (k.to_string(), v))
             .collect();
 
+        self.audit_read(
+            AuditAction::Traverse,
+            &agent_id,
+            audit_subject,
+            serde_json::json!({
+                "start_ids": req.start_ids,
+                "nodes": nodes.iter().map(|n| n.id.as_str()).collect::<Vec<_>>(),
+            }),
+        );
+
         Ok(Response::new(SubgraphResponse {
             nodes,
             edges,
@@ -541,6 +764,67 @@ impl CortexService for CortexServiceImpl {
         }))
     }
 
+    type TraverseStreamStream =
+        Pin<Box<dyn futures::Stream<Item = Result<TraverseChunk, Status>> + Send>>;
+
+    async fn traverse_stream(
+        &self,
+        request: Request<TraverseRequest>,
+    ) -> Result<Response<Self::TraverseStreamStream>, Status> {
+        let agent_id = crate::grpc::get_metadata(&request, "x-agent-id")
+            .unwrap_or_else(|| "anonymous".to_string());
+        let req = request.into_inner();
+
+        let traverse_req = traversal_request(&req)?;
+        if traverse_req.strategy != TraversalStrategy::Bfs {
+            return Err(Status::invalid_argument(
+                "TraverseStream only supports the bfs strategy",
+            ));
+        }
+
+        self.audit_read(
+            AuditAction::Traverse,
+            &agent_id,
+            traverse_req.start.first().copied().unwrap_or_default(),
+            serde_json::json!({
+                "start_ids": req.start_ids,
+                "streamed": true,
+            }),
+        );
+
+        let engine = self.graph_engine.clone();
+        let storage = self.storage.clone();
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        tokio::task::spawn_blocking(move || {
+            for level in engine.traverse_stream(traverse_req) {
+                let chunk = level
+                    .map(|level| TraverseChunk {
+                        depth: level.depth,
+                        nodes: level
+                            .nodes
+                            .iter()
+                            .map(|n| node_to_response(n, edge_count(&storage, n.id)))
+                            .collect(),
+                        edges: level.edges.iter().map(edge_to_response).collect(),
+                        visited_count: level.visited_count as u32,
+                        truncated: level.truncated,
+                    })
+                    .map_err(|e| Status::internal(e.to_string()));
+                // Stop walking once the client has gone away
+                if tx.blocking_send(chunk).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let stream = async_stream::stream! {
+            while let Some(chunk) = rx.recv().await {
+                yield chunk;
+            }
+        };
+        Ok(Response::new(Box::pin(stream)))
+    }
+
     async fn find_paths(
         &self,
         request: Request<FindPathsRequest>,
@@ -569,6 +853,7 @@ impl CortexService for CortexServiceImpl {
             } else {
                 None
             },
+            strategy: parse_path_strategy(&req.strategy),
             ..Default::default()
         };
 
@@ -641,6 +926,8 @@ impl CortexService for CortexServiceImpl {
         &self,
         request: Request<SimilaritySearchRequest>,
     ) -> Result<Response<SearchResponse>, Status> {
+        let agent_id = crate::grpc::get_metadata(&request, "x-agent-id")
+            .unwrap_or_else(|| "anonymous".to_string());
         let req = request.into_inner();
 
         let embedding = self
@@ -661,37 +948,140 @@ impl CortexService for CortexServiceImpl {
             filter = filter.with_kinds(kinds.map_err(|e| Status::invalid_argument(e.to_string()))?);
         }
 
-        let index = self
-            .vector_index
-            .read()
-            .map_err(|_| Status::unavailable("Vector index is being rebuilt, try again shortly"))?;
-        let results = if req.min_score > 0.0 {
-            index
-                .search_threshold(&embedding, req.min_score, Some(&filter))
-                .map_err(|e| Status::internal(e.to_string()))?
+        let cursor = if req.cursor.is_empty() {
+            None
         } else {
-            index
-                .search(&embedding, limit, Some(&filter))
-                .map_err(|e| Status::internal(e.to_string()))?
+            if req.diversity > 0.0 {
+                return Err(Status::invalid_argument(
+                    "cursor cannot be combined with diversity",
+                ));
+            }
+            Some(
+                req.cursor
+                    .parse::<SearchCursor>()
+                    .map_err(|e| Status::invalid_argument(e.to_string()))?,
+            )
         };
-        drop(index);
 
-        let search_results: Vec<_> = results
-            .iter()
-            .filter_map(|r| {
-                self.storage.get_node(r.node_id).ok().flatten().map(|node| {
-                    let edge_count = self.get_edge_count(node.id);
-                    SearchResultEntry {
-                        node: Some(node_to_response(&node, edge_count)),
-                        score: r.score,
-                    }
+        // Results drawn from the best `n` candidates, and whether the index
+        // has no more to give.
+        let score = |n: usize| -> Result<(Vec<(Node, f32)>, bool), Box<Status>> {
+            // The recency boost and confidence weighting can reorder results,
+            // so give them a wider pool.
+            let candidate_limit = if req.recency_boost || self.score_decay.confidence_weight > 0.0 {
+                (n * 3).max(30)
+            } else {
+                n
+            };
+
+            // Diversity re-ranks a wider pool, after the recency boost and
+            // confidence weighting have settled each candidate's score.
+            let pool = if req.diversity > 0.0 {
+                candidate_limit * cortex_core::vector::MMR_CANDIDATE_MULTIPLIER
+            } else {
+                candidate_limit
+            };
+
+            let index = self.vector_index.read().map_err(|_| {
+                Box::new(Status::unavailable(
+                    "Vector index is being rebuilt, try again shortly",
+                ))
+            })?;
+            let results = if req.min_score > 0.0 {
+                index.search_threshold(&embedding, req.min_score, Some(&filter))
+            } else {
+                index.search(&embedding, pool, Some(&filter))
+            }
+            .map_err(|e| Box::new(Status::internal(e.to_string())))?;
+            drop(index);
+            let exhausted = req.min_score > 0.0 || results.len() < pool;
+
+            let scored = results
+                .iter()
+                .filter_map(|r| {
+                    let node = self
+                        .storage
+                        .get_node(r.node_id)
+                        .ok()
+                        .flatten()
+                        .filter(|n| req.include_expired || !n.is_expired())
+                        .filter(|n| req.include_deleted || !n.deleted)?;
+                    let score = if req.recency_boost {
+                        apply_recency_boost(
+                            &node,
+                            r.score,
+                            &self.score_decay,
+                            req.recency_half_life_days,
+                        )
+                    } else {
+                        r.score
+                    };
+                    let score = apply_confidence_weight(&node, score, &self.score_decay);
+                    Some((node, score))
                 })
+                .collect();
+            Ok((scored, exhausted))
+        };
+
+        let mut scored;
+        let next_cursor = if req.diversity > 0.0 {
+            scored = score(limit).map_err(|e| *e)?.0;
+            let mut by_id: std::collections::HashMap<NodeId, Node> =
+                scored.iter().map(|(n, _)| (n.id, n.clone())).collect();
+            let candidates = scored
+                .iter()
+                .map(|(node, score)| SimilarityResult {
+                    node_id: node.id,
+                    score: *score,
+                    distance: 1.0 - score,
+                })
+                .collect();
+            scored = mmr_rerank(candidates, limit, req.diversity, |id| {
+                by_id.get(&id).and_then(|n| n.embedding.clone())
+            })
+            .into_iter()
+            .filter_map(|r| Some((by_id.remove(&r.node_id)?, r.score)))
+            .collect();
+            None
+        } else {
+            let (page, next) = page_search(
+                score,
+                |(node, score)| (*score, node.id),
+                cursor.as_ref(),
+                limit,
+            )
+            .map_err(|e| *e)?;
+            scored = page;
+            next
+        };
+
+        let search_results: Vec<_> = scored
+            .into_iter()
+            .map(|(node, score)| {
+                let edge_count = self.get_edge_count(node.id);
+                SearchResultEntry {
+                    node: Some(node_to_response(&node, edge_count)),
+                    score,
+                }
             })
-            .take(limit)
             .collect();
 
+        self.audit_read(
+            AuditAction::Search,
+            &agent_id,
+            uuid::Uuid::nil(),
+            serde_json::json!({
+                "query": req.query,
+                "results": search_results
+                    .iter()
+                    .filter_map(|r| r.node.as_ref().map(|n| n.id.as_str()))
+                    .collect::<Vec<_>>(),
+            }),
+        );
+
         Ok(Response::new(SearchResponse {
             results: search_results,
+            next_cursor: next_cursor.map(|c| c.to_string()).unwrap_or_default(),
         }))
     }
 
@@ -699,7 +1089,10 @@ impl CortexService for CortexServiceImpl {
         &self,
         request: Request<HybridSearchRequest>,
     ) -> Result<Response<HybridSearchResponse>, Status> {
+        let agent_id = crate::grpc::get_metadata(&request, "x-agent-id")
+            .unwrap_or_else(|| "anonymous".to_string());
         let req = request.into_inner();
+        let query_text = req.query.clone();
 
         let anchors: std::result::Result<Vec<_>, _> = req
             .anchor_ids
@@ -709,6 +1102,11 @@ impl CortexService for CortexServiceImpl {
         let anchors =
             anchors.map_err(|e| Status::invalid_argument(format!("Invalid anchor_ids: {}", e)))?;
 
+        let max_anchor_depth = if req.max_anchor_depth > 0 {
+            req.max_anchor_depth
+        } else {
+            3
+        };
         let mut query = HybridQuery::new(req.query)
             .with_anchors(anchors)
             .with_vector_weight(if req.vector_weight > 0.0 {
@@ -721,11 +1119,7 @@ impl CortexService for CortexServiceImpl {
             } else {
                 10
             })
-            .with_max_anchor_depth(if req.max_anchor_depth > 0 {
-                req.max_anchor_depth
-            } else {
-                3
-            });
+            .with_max_anchor_depth(max_anchor_depth);
 
         if !req.kind_filter.is_empty() {
             let kinds: std::result::Result<Vec<_>, _> =
@@ -733,6 +1127,26 @@ impl CortexService for CortexServiceImpl {
             query =
                 query.with_kind_filter(kinds.map_err(|e| Status::invalid_argument(e.to_string()))?);
         }
+        if req.include_expired {
+            query = query.including_expired();
+        }
+        if req.include_deleted {
+            query = query.including_deleted();
+        }
+        if !req.hop_decay.is_empty() {
+            let proximity = GraphProximity::new(req.hop_decay, max_anchor_depth);
+            proximity
+                .validate()
+                .map_err(|e| Status::invalid_argument(e.to_string()))?;
+            query = query.with_proximity(proximity);
+        }
+        if req.auto_anchor {
+            query = query.with_auto_anchor(if req.auto_anchor_count > 0 {
+                req.auto_anchor_count as usize
+            } else {
+                3
+            });
+        }
 
         // Arc<E> and Arc<G> implement EmbeddingService/GraphEngine via blanket impls.
         // RwLockVectorIndex wraps Arc<RwLock<V>> to implement VectorIndex.
@@ -762,25 +1176,77 @@ impl CortexService for CortexServiceImpl {
             })
             .collect();
 
+        self.audit_read(
+            AuditAction::Search,
+            &agent_id,
+            uuid::Uuid::nil(),
+            serde_json::json!({
+                "query": query_text,
+                "hybrid": true,
+                "results": results.iter().map(|r| r.node.id.to_string()).collect::<Vec<_>>(),
+            }),
+        );
+
         Ok(Response::new(HybridSearchResponse {
             results: hybrid_results,
         }))
     }
 
+    async fn query_nodes(
+        &self,
+        request: Request<QueryNodesRequest>,
+    ) -> Result<Response<SearchResponse>, Status> {
+        let req = request.into_inner();
+        let like = (!req.like.is_empty()).then_some(req.like.as_str());
+
+        let hits =
+            run_query(&*self.storage, &*self.embedding_service, &req.query, like).map_err(|e| {
+                match e {
+                    CortexError::Validation(msg) => Status::invalid_argument(msg),
+                    e => Status::internal(e.to_string()),
+                }
+            })?;
+
+        let results = hits
+            .into_iter()
+            .map(|hit| {
+                let edge_count = self.get_edge_count(hit.node.id);
+                SearchResultEntry {
+                    node: Some(node_to_response(&hit.node, edge_count)),
+                    score: hit.score.unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        Ok(Response::new(SearchResponse {
+            results,
+            next_cursor: String::new(),
+        }))
+    }
+
     async fn get_briefing(
         &self,
         request: Request<BriefingRequest>,
     ) -> Result<Response<BriefingResponse>, Status> {
+        let caller = crate::grpc::get_metadata(&request, "x-agent-id");
         let req = request.into_inner();
         let agent_id = &req.agent_id;
-        let compact = req.compact;
+        let format = match req.format.as_str() {
+            "" if req.compact => BriefingFormat::Compact,
+            "" => BriefingFormat::Markdown,
+            other => other
+                .parse()
+                .map_err(|e: CortexError| Status::invalid_argument(e.to_string()))?,
+        };
 
-        let briefing = self
-            .briefing_engine
-            .generate(agent_id)
-            .map_err(|e| Status::internal(e.to_string()))?;
+        let briefing = if req.no_cache {
+            self.briefing_engine.generate_uncached(agent_id)
+        } else {
+            self.briefing_engine.generate(agent_id)
+        }
+        .map_err(|e| Status::internal(e.to_string()))?;
 
-        let rendered = self.briefing_engine.render(&briefing, compact);
+        let rendered = self.briefing_engine.render_as(&briefing, format);
 
         // Convert cortex_core::briefing::BriefingSection to proto BriefingSection
         let sections: Vec<BriefingSection> = briefing
@@ -799,6 +1265,19 @@ impl CortexService for CortexServiceImpl {
             })
             .collect();
 
+        self.audit_read(
+            AuditAction::Briefing,
+            caller.as_deref().unwrap_or(agent_id),
+            uuid::Uuid::nil(),
+            serde_json::json!({
+                "agent_id": agent_id,
+                "nodes": sections
+                    .iter()
+                    .flat_map(|s| s.nodes.iter().map(|n| n.id.as_str()))
+                    .collect::<Vec<_>>(),
+            }),
+        );
+
         Ok(Response::new(BriefingResponse {
             agent_id: briefing.agent_id.clone(),
             rendered,
@@ -809,10 +1288,71 @@ impl CortexService for CortexServiceImpl {
         }))
     }
 
+    async fn observe(
+        &self,
+        request: Request<ObserveRequest>,
+    ) -> Result<Response<ObserveResponse>, Status> {
+        self.ensure_writable()?;
+        let observations = self
+            .observations
+            .as_ref()
+            .ok_or_else(|| Status::unimplemented("Observations are not enabled"))?;
+        let req = request.into_inner();
+
+        let agent = self
+            .storage
+            .get_node_by_title(&kinds::defaults::agent(), &req.agent)
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| Status::not_found(format!("Agent '{}' not found", req.agent)))?;
+        let variant_id: NodeId = req
+            .variant_id
+            .parse()
+            .map_err(|_| Status::invalid_argument("Invalid variant_id UUID"))?;
+
+        let body = ObserveBody {
+            variant_id: req.variant_id,
+            variant_slug: req.variant_slug,
+            sentiment_score: req.sentiment_score.unwrap_or(0.5),
+            correction_count: req.correction_count,
+            task_outcome: if req.task_outcome.is_empty() {
+                "unknown".to_string()
+            } else {
+                req.task_outcome
+            },
+            token_cost: req.token_cost,
+            response_time_ms: req.response_time_ms,
+            user_satisfaction: req.user_satisfaction,
+            context_signals: None,
+            topic: req.topic,
+            session_length: None,
+            message_count: None,
+        };
+        let recorded = observations
+            .record(&self.storage, &agent, variant_id, &body)
+            .map_err(crate::grpc::to_status)?;
+        self.bump_version();
+
+        Ok(Response::new(ObserveResponse {
+            observation_id: recorded.observation_id.to_string(),
+            observation_score: recorded.observation_score,
+            old_edge_weight: recorded.old_edge_weight,
+            new_edge_weight: recorded.new_edge_weight,
+            rollback: recorded.rollback.map(|r| RollbackInfo {
+                rollback_node_id: r.rollback_node_id.to_string(),
+                from_version: r.from_version,
+                to_version: r.to_version,
+                trigger: r.trigger.kind_str().to_string(),
+                cooldown_hours: r.cooldown_hours,
+                is_quarantined: r.is_quarantined,
+            }),
+        }))
+    }
+
     async fn stats(
         &self,
-        _request: Request<StatsRequest>,
+        request: Request<StatsRequest>,
     ) -> Result<Response<StatsResponse>, Status> {
+        let req = request.into_inner();
         let stats = self
             .storage
             .stats()
@@ -823,7 +1363,51 @@ impl CortexService for CortexServiceImpl {
             .map(|m| m.len())
             .unwrap_or(0);
 
-        Ok(Response::new(stats_to_response(stats, db_size)))
+        let mut response = stats_to_response(stats, db_size);
+        response.gate = Some(gate_stats_to_response(self.metrics.gate_stats.snapshot()));
+
+        if req.centrality_top > 0 {
+            let scores = self
+                .graph_engine
+                .pagerank(PageRankConfig::default())
+                .map_err(|e| Status::internal(e.to_string()))?;
+            let mut ranked: Vec<_> = scores.into_iter().collect();
+            ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            for (id, score) in ranked.into_iter().take(req.centrality_top as usize) {
+                if let Ok(Some(node)) = self.storage.get_node(id) {
+                    response.central_nodes.push(CentralNode {
+                        id: id.to_string(),
+                        kind: format!("{:?}", node.kind),
+                        title: node.data.title,
+                        score,
+                    });
+                }
+            }
+        }
+
+        if req.communities {
+            let communities = self
+                .graph_engine
+                .detect_communities(CommunityConfig::default())
+                .map_err(|e| Status::internal(e.to_string()))?;
+            response.modularity = communities.iter().map(|c| c.modularity).sum();
+            for community in communities.iter().filter(|c| c.nodes.len() > 1) {
+                let representative_titles = community
+                    .nodes
+                    .iter()
+                    .take(3)
+                    .filter_map(|id| self.storage.get_node(*id).ok().flatten())
+                    .map(|n| n.data.title)
+                    .collect();
+                response.communities.push(CommunitySummary {
+                    size: community.nodes.len() as u32,
+                    representative_titles,
+                    modularity: community.modularity,
+                });
+            }
+        }
+
+        Ok(Response::new(response))
     }
 
     async fn auto_linker_status(
@@ -844,16 +1428,24 @@ impl CortexService for CortexServiceImpl {
             last_cycle_duration_ms: metrics.last_cycle_duration.as_millis() as u64,
             cursor: Some(datetime_to_timestamp(metrics.cursor)),
             backlog_size: metrics.backlog_size,
+            nodes_scanned: metrics.nodes_scanned,
         }))
     }
 
     async fn trigger_auto_link(
         &self,
-        _request: Request<TriggerAutoLinkRequest>,
+        request: Request<TriggerAutoLinkRequest>,
     ) -> Result<Response<TriggerAutoLinkResponse>, Status> {
+        self.ensure_writable()?;
+        let full = request.into_inner().full;
         let mut linker = self.auto_linker.write().unwrap();
 
-        match linker.run_cycle() {
+        let result = if full {
+            linker.run_full_cycle()
+        } else {
+            linker.run_cycle()
+        };
+        match result {
             Ok(()) => Ok(Response::new(TriggerAutoLinkResponse {
                 success: true,
                 message: "Auto-link cycle completed successfully".to_string(),
@@ -865,45 +1457,130 @@ impl CortexService for CortexServiceImpl {
         }
     }
 
-    async fn reindex(
+    async fn preview_auto_link(
         &self,
-        _request: Request<ReindexRequest>,
-    ) -> Result<Response<ReindexResponse>, Status> {
-        let nodes = self
-            .storage
-            .list_nodes(NodeFilter::new())
-            .map_err(|e| Status::internal(e.to_string()))?;
+        request: Request<PreviewAutoLinkRequest>,
+    ) -> Result<Response<PreviewAutoLinkResponse>, Status> {
+        let req = request.into_inner();
+        let proposals = {
+            let linker = self.auto_linker.read().unwrap();
+            if req.full {
+                linker.preview_full_cycle()
+            } else {
+                linker.preview_cycle()
+            }
+        }
+        .map_err(|e| Status::internal(e.to_string()))?;
 
-        // Generate all embeddings without holding the write lock — embedding is CPU-bound
-        // and can take seconds for large graphs. Holding the lock would block all reads.
-        let pairs: Vec<(NodeId, Vec<f32>)> = nodes
+        let total = proposals.len() as u32;
+        let limit = if req.limit == 0 {
+            proposals.len()
+        } else {
+            req.limit as usize
+        };
+        let title = |id| {
+            self.storage
+                .get_node(id)
+                .ok()
+                .flatten()
+                .map(|n| n.data.title)
+                .unwrap_or_default()
+        };
+        let edges = proposals
             .iter()
-            .filter_map(|node| {
-                let text = embedding_input(node);
-                self.embedding_service
-                    .embed(&text)
-                    .ok()
-                    .map(|emb| (node.id, emb))
+            .take(limit)
+            .map(|p| ProposedEdgeResponse {
+                from_id: p.from.to_string(),
+                to_id: p.to.to_string(),
+                from_title: title(p.from),
+                to_title: title(p.to),
+                relation: p.relation.to_string(),
+                weight: p.weight,
+                rule: p.rule().to_string(),
             })
             .collect();
 
-        let reindexed = pairs.len();
+        Ok(Response::new(PreviewAutoLinkResponse { edges, total }))
+    }
 
-        // Acquire lock only for the fast batch-insert step
-        {
-            let mut index = self.vector_index.write().unwrap();
-            for (id, emb) in &pairs {
-                let _ = index.insert(*id, emb);
-            }
-            if let Err(e) = index.rebuild() {
-                return Err(Status::internal(format!("Failed to rebuild index: {}", e)));
+    async fn reindex(
+        &self,
+        request: Request<ReindexRequest>,
+    ) -> Result<Response<ReindexResponse>, Status> {
+        self.ensure_writable()?;
+        let re_embed = request.into_inner().re_embed;
+
+        let storage = self.storage.clone();
+        let embedder = self.embedding_service.clone();
+        let live = self.vector_index.clone();
+        let summary = tokio::task::spawn_blocking(move || {
+            // Build the new index without holding the write lock — embedding is CPU-bound
+            // and can take seconds for large graphs. Holding the lock would block all reads.
+            let started = chrono::Utc::now();
+            let (mut index, summary) =
+                cortex_core::reindex(&*storage, &*embedder, re_embed, |done, total| {
+                    tracing::info!("Reindex: {}/{} nodes", done, total)
+                })?;
+
+            // Catch up on writes made during the build, then swap rather than
+            // insert, so entries for deleted nodes don't linger
+            let mut live = live.write().unwrap();
+            let replayed = cortex_core::replay_writes(&mut index, &*live, &*storage, started)?;
+            if replayed > 0 {
+                tracing::info!(
+                    "Reindex: replayed {} writes made during the build",
+                    replayed
+                );
             }
-        }
+            *live = index;
+            Ok::<_, CortexError>(summary)
+        })
+        .await
+        .map_err(|e| Status::internal(format!("Reindex failed: {}", e)))?
+        .map_err(|e| Status::internal(format!("Reindex failed: {}", e)))?;
+        self.bump_version();
 
         Ok(Response::new(ReindexResponse {
             success: true,
-            nodes_reindexed: reindexed as u64,
-            message: format!("Reindexed {} nodes", reindexed),
+            nodes_reindexed: summary.indexed as u64,
+            message: format!(
+                "Reindexed {} nodes ({} embedded, {} skipped)",
+                summary.indexed, summary.embedded, summary.skipped
+            ),
+            nodes_embedded: summary.embedded as u64,
+            nodes_skipped: summary.skipped as u64,
+        }))
+    }
+
+    async fn snapshot(
+        &self,
+        request: Request<SnapshotRequest>,
+    ) -> Result<Response<SnapshotResponse>, Status> {
+        let path = std::path::PathBuf::from(request.into_inner().path);
+        if path.as_os_str().is_empty() {
+            return Err(Status::invalid_argument("path is required"));
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| Status::internal(format!("Snapshot failed: {}", e)))?;
+        }
+
+        // Reads from a single redb read transaction; writers keep going.
+        let info = self
+            .storage
+            .snapshot_versioned(&path, Some(&self.graph_version))
+            .map_err(|e| match e {
+                CortexError::Validation(msg) => Status::invalid_argument(msg),
+                e => Status::internal(format!("Snapshot failed: {}", e)),
+            })?;
+        let size_bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+        Ok(Response::new(SnapshotResponse {
+            path: path.display().to_string(),
+            node_count: info.node_count,
+            edge_count: info.edge_count,
+            graph_version: info.graph_version.unwrap_or(0),
+            size_bytes,
         }))
     }
 
@@ -939,7 +1616,202 @@ impl CortexService for CortexServiceImpl {
                 last_cycle_duration_ms: linker_metrics.last_cycle_duration.as_millis() as u64,
                 cursor: Some(datetime_to_timestamp(linker_metrics.cursor)),
                 backlog_size: linker_metrics.backlog_size,
+                nodes_scanned: linker_metrics.nodes_scanned,
             }),
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CortexConfig;
+    use crate::webhooks::WebhookRegistry;
+    use cortex_client::{CortexClient, ObserveParams};
+    use cortex_proto::cortex_service_server::CortexServiceServer;
+
+    /// The service as `serve` wires it, over `storage`, with `Observe` on.
+    fn service(storage: Arc<RedbStorage>) -> CortexServiceImpl {
+        let config = CortexConfig::default();
+        let graph_version = Arc::new(AtomicU64::new(0));
+        let graph_engine = Arc::new(GraphEngineImpl::new(storage.clone()));
+        let embedding_service: Arc<ServerEmbedder> = Arc::new(CachingEmbeddingService::new(
+            Box::new(crate::test_support::HashEmbedder),
+        ));
+        let vector_index = Arc::new(StdRwLock::new(HnswIndex::new(
+            embedding_service.dimension(),
+        )));
+        let auto_linker = Arc::new(StdRwLock::new(
+            AutoLinker::new(
+                storage.clone(),
+                graph_engine.clone(),
+                vector_index.clone(),
+                embedding_service.clone(),
+                config.auto_linker_config(),
+            )
+            .unwrap(),
+        ));
+        let briefing_engine = Arc::new(BriefingEngine::new(
+            storage.clone(),
+            graph_engine.clone(),
+            RwLockVectorIndex(vector_index.clone()),
+            embedding_service.clone(),
+            graph_version.clone(),
+            config.briefing_config(),
+        ));
+        let metrics = Arc::new(CortexMetrics::new());
+        let observations = ObservationContext {
+            selection_config: config.prompt_selection.clone(),
+            rollback_config: Arc::new(StdRwLock::new(Default::default())),
+            metrics: metrics.clone(),
+            webhooks: Arc::new(WebhookRegistry::load(storage.clone(), &[]).unwrap()),
+            nats_publisher: None,
+        };
+        CortexServiceImpl::new(
+            storage.clone(),
+            graph_engine,
+            vector_index,
+            embedding_service,
+            auto_linker,
+            graph_version,
+            briefing_engine,
+            Arc::new(HookRegistry::new()),
+            Arc::new(SchemaValidator::new(config.schemas.clone())),
+            Arc::new(StdRwLock::new(WriteGateConfig::default())),
+            config.score_decay.clone(),
+            None,
+            metrics,
+        )
+        .with_observations(observations)
+    }
+
+    /// Serve [`service`] on a local port and connect a client to it.
+    async fn serve(
+        storage: Arc<RedbStorage>,
+    ) -> (
+        CortexClient,
+        tokio::task::JoinHandle<Result<(), tonic::transport::Error>>,
+    ) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming =
+            tonic::transport::server::TcpIncoming::from_listener(listener, true, None).unwrap();
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(CortexServiceServer::new(service(storage)))
+                .serve_with_incoming(incoming),
+        );
+        let client = CortexClient::connect(format!("http://{}", addr))
+            .await
+            .unwrap();
+        (client, server)
+    }
+
+    #[tokio::test]
+    async fn test_gate_outcomes_are_counted_in_stats() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("grpc.redb")).unwrap());
+        let (mut client, server) = serve(storage).await;
+
+        // Too short on both counts for the default gate
+        let err = client
+            .create_node(CreateNodeRequest {
+                kind: "fact".into(),
+                title: "short".into(),
+                body: "tiny".into(),
+                source_agent: "test".into(),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        let status = err.downcast_ref::<Status>().unwrap();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+        let gate = client.stats().await.unwrap().gate.unwrap();
+        assert_eq!((gate.accepted, gate.rejected), (0, 1));
+        assert!(gate.by_check.values().sum::<u64>() >= 2, "{gate:?}");
+        assert!(gate.failures.iter().all(|f| f.kind == "fact"));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_observe_over_grpc() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("grpc.redb")).unwrap());
+        let source = Source {
+            agent: "test".into(),
+            session: None,
+            channel: None,
+        };
+        let agent = Node::new(
+            kinds::defaults::agent(),
+            "kai".into(),
+            String::new(),
+            source.clone(),
+            0.5,
+        );
+        let variant = Node::new(
+            kinds::defaults::prompt(),
+            "kai-concise".into(),
+            r#"{"version": 1}"#.into(),
+            source,
+            0.5,
+        );
+        storage.put_node(&agent).unwrap();
+        storage.put_node(&variant).unwrap();
+        storage
+            .put_edge(&Edge::new(
+                agent.id,
+                variant.id,
+                relations::defaults::uses(),
+                0.5,
+                EdgeProvenance::Manual {
+                    created_by: "test".into(),
+                },
+            ))
+            .unwrap();
+
+        let (mut client, server) = serve(storage.clone()).await;
+        let resp = client
+            .observe(
+                "kai",
+                ObserveParams {
+                    variant_id: variant.id.to_string(),
+                    variant_slug: "kai-concise".into(),
+                    sentiment: 0.9,
+                    task_outcome: "success".into(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!((resp.old_edge_weight - 0.5).abs() < 1e-6);
+        assert!(resp.new_edge_weight > resp.old_edge_weight);
+        assert!(resp.rollback.is_none());
+        let obs_id: NodeId = resp.observation_id.parse().unwrap();
+        let obs = storage.get_node(obs_id).unwrap().unwrap();
+        assert_eq!(obs.kind, kinds::defaults::observation());
+        assert_eq!(
+            storage.get_node(agent.id).unwrap().unwrap().data.metadata["active_variant_id"],
+            variant.id.to_string()
+        );
+
+        // An unknown agent is NotFound, not an internal error
+        let err = client
+            .observe(
+                "nobody",
+                ObserveParams {
+                    variant_id: variant.id.to_string(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        let status = err.downcast_ref::<Status>().unwrap();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+
+        server.abort();
+    }
+}
@@ -1,63 +1,329 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
 use axum::{
     extract::Request,
-    http::StatusCode,
+    http::{Method, StatusCode},
     middleware::Next,
     response::{IntoResponse, Json, Response},
 };
 
 use super::JsonResponse;
+use crate::config::{CortexConfig, TokenScope};
+use axum::http;
+use tonic::body::BoxBody;
 
-/// Bearer token auth middleware. Skips `/health` and (by default) `/metrics`.
-/// Short-circuits if auth is disabled.
-pub async fn check(
-    req: Request,
-    next: Next,
-    auth_enabled: bool,
-    token: Option<String>,
-    metrics_require_auth: bool,
-) -> Response {
-    if !auth_enabled {
-        return next.run(req).await;
-    }
-
-    let path = req.uri().path();
-    if path == "/health" {
-        return next.run(req).await;
-    }
-    if path == "/metrics" && !metrics_require_auth {
-        return next.run(req).await;
-    }
-
-    let expected = match token.as_deref() {
-        Some(t) => t,
-        None => {
-            return (
+/// Path prefix of every `CortexService` gRPC call.
+const GRPC_SERVICE: &str = "/cortex.v1.CortexService/";
+
+/// `CortexService` methods that don't change the graph. gRPC calls are all
+/// `POST`s, so they can't be told apart by HTTP method.
+const GRPC_READS: &[&str] = &[
+    "GetNode",
+    "ListNodes",
+    "GetEdges",
+    "Traverse",
+    "TraverseStream",
+    "FindPaths",
+    "Neighborhood",
+    "SimilaritySearch",
+    "HybridSearch",
+    "QueryNodes",
+    "GetBriefing",
+    "Stats",
+    "AutoLinkerStatus",
+    "PreviewAutoLink",
+    "Health",
+];
+
+/// Whether a request only reads, judged by the route it targets: gRPC calls
+/// by method name, HTTP routes by verb (every HTTP read is a `GET`).
+pub fn is_read(method: &Method, path: &str) -> bool {
+    match path.strip_prefix(GRPC_SERVICE) {
+        Some(rpc) => GRPC_READS.contains(&rpc),
+        None => matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS),
+    }
+}
+
+/// Bearer tokens accepted by the HTTP API.
+#[derive(Debug, Clone, Default)]
+pub struct HttpAuth {
+    pub enabled: bool,
+    pub tokens: Vec<(String, TokenScope)>,
+    /// When false, `/metrics` is served without a token.
+    pub metrics_require_auth: bool,
+}
+
+impl HttpAuth {
+    pub fn from_config(config: &CortexConfig) -> Self {
+        Self {
+            enabled: config.security.auth_enabled,
+            tokens: config.security.resolved_tokens(),
+            metrics_require_auth: config.observability.metrics_require_auth,
+        }
+    }
+
+    /// Decide whether a request may proceed, given its `Authorization` header.
+    fn authorize(
+        &self,
+        method: &Method,
+        path: &str,
+        authorization: Option<&str>,
+    ) -> Result<(), (StatusCode, &'static str)> {
+        if !self.enabled || path == "/health" {
+            return Ok(());
+        }
+        if path == "/metrics" && !self.metrics_require_auth {
+            return Ok(());
+        }
+        if self.tokens.is_empty() {
+            return Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(JsonResponse::<()>::err(
-                    "Auth enabled but no token configured",
-                )),
-            )
-                .into_response();
-        }
-    };
-
-    match req.headers().get("authorization") {
-        Some(value) => {
-            let value = value.to_str().unwrap_or("");
-            if value.starts_with("Bearer ") && &value[7..] == expected {
-                next.run(req).await
-            } else {
-                (
-                    StatusCode::UNAUTHORIZED,
-                    Json(JsonResponse::<()>::err("Invalid token")),
-                )
-                    .into_response()
+                "Auth enabled but no token configured",
+            ));
+        }
+
+        let Some(value) = authorization else {
+            return Err((StatusCode::UNAUTHORIZED, "Missing Authorization header"));
+        };
+        let scope = value
+            .strip_prefix("Bearer ")
+            .and_then(|presented| {
+                self.tokens
+                    .iter()
+                    .find(|(token, _)| token == presented)
+                    .map(|(_, scope)| *scope)
+            })
+            .ok_or((StatusCode::UNAUTHORIZED, "Invalid token"))?;
+
+        if scope == TokenScope::Read && !is_read(method, path) {
+            return Err((StatusCode::FORBIDDEN, "Token is read-only"));
+        }
+        Ok(())
+    }
+}
+
+/// Bearer token auth middleware. Skips `/health` and (by default) `/metrics`.
+/// Read-scoped tokens may only reach routes for which [`is_read`] holds.
+pub async fn check(req: Request, next: Next, auth: Arc<HttpAuth>) -> Response {
+    let authorization = req
+        .headers()
+        .get("authorization")
+        .map(|v| v.to_str().unwrap_or(""));
+    match auth.authorize(req.method(), req.uri().path(), authorization) {
+        Ok(()) => next.run(req).await,
+        Err((status, msg)) => (status, Json(JsonResponse::<()>::err(msg))).into_response(),
+    }
+}
+
+/// Tower layer applying [`HttpAuth`] to `CortexService` calls on the gRPC
+/// server. The health and reflection services stay open.
+#[derive(Clone)]
+pub struct GrpcAuthLayer {
+    auth: Arc<HttpAuth>,
+}
+
+impl GrpcAuthLayer {
+    pub fn new(auth: Arc<HttpAuth>) -> Self {
+        Self { auth }
+    }
+}
+
+impl<S> tower::Layer<S> for GrpcAuthLayer {
+    type Service = GrpcAuth<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GrpcAuth {
+            inner,
+            auth: self.auth.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct GrpcAuth<S> {
+    inner: S,
+    auth: Arc<HttpAuth>,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for GrpcAuth<S>
+where
+    S: tower::Service<http::Request<B>, Response = http::Response<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let path = req.uri().path();
+        if path.starts_with(GRPC_SERVICE) {
+            let authorization = req
+                .headers()
+                .get("authorization")
+                .map(|v| v.to_str().unwrap_or(""));
+            if let Err((status, msg)) = self.auth.authorize(req.method(), path, authorization) {
+                let status = match status {
+                    StatusCode::UNAUTHORIZED => tonic::Status::unauthenticated(msg),
+                    StatusCode::FORBIDDEN => tonic::Status::permission_denied(msg),
+                    _ => tonic::Status::internal(msg),
+                };
+                let response = status.into_http();
+                return Box::pin(async move { Ok(response) });
             }
         }
-        None => (
-            StatusCode::UNAUTHORIZED,
-            Json(JsonResponse::<()>::err("Missing Authorization header")),
-        )
-            .into_response(),
+        Box::pin(self.inner.call(req))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn router(auth: HttpAuth) -> Router {
+        let auth = Arc::new(auth);
+        Router::new()
+            .route("/health", get(|| async { "ok" }))
+            .route("/metrics", get(|| async { "cortex_node_count 0" }))
+            .route(
+                "/nodes",
+                get(|| async { "[]" }).post(|| async { "created" }),
+            )
+            .layer(axum::middleware::from_fn(move |req, next| {
+                let auth = auth.clone();
+                async move { check(req, next, auth).await }
+            }))
+    }
+
+    fn enabled() -> HttpAuth {
+        HttpAuth {
+            enabled: true,
+            tokens: vec![
+                ("admin-token".into(), TokenScope::ReadWrite),
+                ("dash-token".into(), TokenScope::Read),
+            ],
+            metrics_require_auth: false,
+        }
+    }
+
+    async fn status(app: &Router, method: &str, path: &str, token: Option<&str>) -> StatusCode {
+        let mut req = Request::builder().method(method).uri(path);
+        if let Some(token) = token {
+            req = req.header("authorization", format!("Bearer {}", token));
+        }
+        app.clone()
+            .oneshot(req.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn missing_and_invalid_tokens_are_rejected() {
+        let app = router(enabled());
+        assert_eq!(
+            status(&app, "GET", "/nodes", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(&app, "GET", "/nodes", Some("wrong")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(&app, "GET", "/nodes", Some("admin-token")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn health_and_metrics_are_exempt() {
+        let app = router(enabled());
+        assert_eq!(status(&app, "GET", "/health", None).await, StatusCode::OK);
+        assert_eq!(status(&app, "GET", "/metrics", None).await, StatusCode::OK);
+
+        let app = router(HttpAuth {
+            metrics_require_auth: true,
+            ..enabled()
+        });
+        assert_eq!(
+            status(&app, "GET", "/metrics", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(&app, "GET", "/metrics", Some("dash-token")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn read_scope_cannot_write() {
+        let app = router(enabled());
+        assert_eq!(
+            status(&app, "GET", "/nodes", Some("dash-token")).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(&app, "POST", "/nodes", Some("dash-token")).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status(&app, "POST", "/nodes", Some("admin-token")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn read_scope_is_judged_by_grpc_method() {
+        use tower::Layer;
+
+        let svc = GrpcAuthLayer::new(Arc::new(enabled())).layer(tower::service_fn(
+            |_req: http::Request<()>| async {
+                Ok::<_, std::convert::Infallible>(http::Response::new(tonic::body::empty_body()))
+            },
+        ));
+        let code = |rpc: &str, token: Option<&str>| {
+            let mut req = http::Request::builder()
+                .method("POST")
+                .uri(format!("/cortex.v1.CortexService/{rpc}"));
+            if let Some(token) = token {
+                req = req.header("authorization", format!("Bearer {}", token));
+            }
+            let call = svc.clone().oneshot(req.body(()).unwrap());
+            async move {
+                call.await
+                    .unwrap()
+                    .headers()
+                    .get("grpc-status")
+                    .map(|v| v.to_str().unwrap().to_string())
+            }
+        };
+        let status = |code: tonic::Code| Some((code as i32).to_string());
+
+        // Every gRPC call is a POST; searches are still reads.
+        assert_eq!(code("SimilaritySearch", Some("dash-token")).await, None);
+        assert_eq!(code("GetNode", Some("dash-token")).await, None);
+        assert_eq!(
+            code("CreateNode", Some("dash-token")).await,
+            status(tonic::Code::PermissionDenied)
+        );
+        assert_eq!(code("CreateNode", Some("admin-token")).await, None);
+        assert_eq!(
+            code("GetNode", None).await,
+            status(tonic::Code::Unauthenticated)
+        );
+    }
+
+    #[tokio::test]
+    async fn disabled_auth_lets_everything_through() {
+        let app = router(HttpAuth::default());
+        assert_eq!(status(&app, "POST", "/nodes", None).await, StatusCode::OK);
     }
 }
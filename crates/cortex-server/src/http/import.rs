@@ -0,0 +1,462 @@
+/// Bulk node import over HTTP.
+///
+/// Endpoint:
+///   POST /nodes/import  — JSON array or NDJSON of node bodies; `?dry_run=true` validates only
+use super::routes::{CreateNodeBody, GateDetail};
+use super::{AppResult, AppState, JsonResponse};
+use crate::gate::{record_bypass, GatedWrite};
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::{IntoResponse, Json},
+};
+use cortex_core::{
+    embedding_input, Embedding, EmbeddingService, GateRejection, MutationAction, Node, Storage,
+    VectorIndex, WriteGate,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct ImportQuery {
+    /// Validate every item without writing anything.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// `skip` bypasses the gate, with `X-Gate-Override: true`, as on `POST /nodes`.
+    pub gate: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    /// Stored.
+    Created,
+    /// Would be stored; only reported by dry runs.
+    Valid,
+    /// Refused by the write gate.
+    Rejected,
+    /// Not a valid node body.
+    Invalid,
+}
+
+/// Outcome for one input item, reported in input order.
+#[derive(Serialize, Debug)]
+pub struct ImportItemResult {
+    pub index: usize,
+    pub status: ImportStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rejections: Vec<GateDetail>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub gate_warnings: Vec<String>,
+}
+
+impl ImportItemResult {
+    fn invalid(index: usize, error: String) -> Self {
+        Self {
+            index,
+            status: ImportStatus::Invalid,
+            id: None,
+            error: Some(error),
+            rejections: Vec::new(),
+            gate_warnings: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ImportResponse {
+    pub dry_run: bool,
+    pub created: usize,
+    pub valid: usize,
+    pub rejected: usize,
+    pub invalid: usize,
+    pub results: Vec<ImportItemResult>,
+}
+
+impl ImportResponse {
+    fn new(dry_run: bool, results: Vec<ImportItemResult>) -> Self {
+        let count = |status| results.iter().filter(|r| r.status == status).count();
+        Self {
+            dry_run,
+            created: count(ImportStatus::Created),
+            valid: count(ImportStatus::Valid),
+            rejected: count(ImportStatus::Rejected),
+            invalid: count(ImportStatus::Invalid),
+            results,
+        }
+    }
+}
+
+/// Split the request body into node bodies. A body starting with `[` is a
+/// JSON array; anything else is NDJSON, one node per non-blank line. Items
+/// that don't parse are kept as errors so they get their own result.
+fn parse_items(text: &str) -> anyhow::Result<Vec<Result<CreateNodeBody, String>>> {
+    let parse = |value: serde_json::Value| serde_json::from_value(value).map_err(|e| e.to_string());
+    if text.trim_start().starts_with('[') {
+        let values: Vec<serde_json::Value> =
+            serde_json::from_str(text).map_err(|e| anyhow::anyhow!("Invalid JSON array: {}", e))?;
+        Ok(values.into_iter().map(parse).collect())
+    } else {
+        Ok(text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str::<serde_json::Value>(line)
+                    .map_err(|e| e.to_string())
+                    .and_then(parse)
+            })
+            .collect())
+    }
+}
+
+/// An item that passed screening, with its embedding, still to be checked
+/// for conflicts.
+struct Candidate {
+    slot: usize,
+    node: Node,
+    rejections: Vec<GateRejection>,
+    embedding: Embedding,
+}
+
+/// Screen every item as `POST /nodes` does and embed the survivors in one
+/// batch: everything that can run without the vector index lock. Returns a
+/// result per item (accepted items are `Valid`) and the candidates.
+fn prepare_items<E: EmbeddingService>(
+    items: Vec<Result<CreateNodeBody, String>>,
+    agent_id: &str,
+    gate: &GatedWrite<'_>,
+    embedder: &E,
+) -> anyhow::Result<(Vec<ImportItemResult>, Vec<Candidate>)> {
+    let mut results = Vec::with_capacity(items.len());
+    // (index in results, node, failures found before embedding)
+    let mut screened: Vec<(usize, Node, Vec<GateRejection>)> = Vec::new();
+    for (index, item) in items.into_iter().enumerate() {
+        let mut node = match item
+            .map_err(anyhow::Error::msg)
+            .and_then(|b| b.into_node(agent_id, gate.kinds()))
+        {
+            Ok(node) => node,
+            Err(e) => {
+                results.push(ImportItemResult::invalid(index, e.to_string()));
+                continue;
+            }
+        };
+        let rejections = gate.screen(&mut node);
+        screened.push((results.len(), node, rejections));
+        results.push(ImportItemResult {
+            index,
+            status: ImportStatus::Valid,
+            id: None,
+            error: None,
+            rejections: Vec::new(),
+            gate_warnings: Vec::new(),
+        });
+    }
+
+    // Items refused on the cheap checks are settled without embedding them
+    let (refused, screened): (Vec<_>, Vec<_>) = screened
+        .into_iter()
+        .partition(|(_, _, rejections)| gate.refuses_early(rejections));
+    for (slot, mut node, rejections) in refused {
+        if let Err(rejections) = gate.settle(&mut node, rejections) {
+            results[slot].status = ImportStatus::Rejected;
+            results[slot].rejections = rejections.into_iter().map(GateDetail::from).collect();
+        }
+    }
+
+    let texts: Vec<String> = screened
+        .iter()
+        .map(|(_, n, _)| embedding_input(n))
+        .collect();
+    let embeddings = if texts.is_empty() {
+        Vec::new()
+    } else {
+        embedder.embed_batch(&texts)?
+    };
+    let candidates = screened
+        .into_iter()
+        .zip(embeddings)
+        .map(|((slot, node, rejections), embedding)| Candidate {
+            slot,
+            node,
+            rejections,
+            embedding,
+        })
+        .collect();
+    Ok((results, candidates))
+}
+
+/// Check each candidate for conflicts with stored nodes and with the items
+/// accepted before it in the same import, then settle it. Returns the
+/// accepted nodes, each carrying its embedding.
+fn check_candidates<S: Storage, V: VectorIndex>(
+    results: &mut [ImportItemResult],
+    candidates: Vec<Candidate>,
+    gate: &GatedWrite<'_>,
+    index: &V,
+    storage: &S,
+) -> Vec<Node> {
+    let mut accepted: Vec<Node> = Vec::with_capacity(candidates.len());
+    for Candidate {
+        slot,
+        mut node,
+        mut rejections,
+        embedding,
+    } in candidates
+    {
+        gate.check(&node, &embedding, index, storage, &mut rejections);
+        gate.check_among(&node, &embedding, &accepted, &mut rejections);
+        let result = &mut results[slot];
+        if let Err(rejections) = gate.settle(&mut node, rejections) {
+            result.status = ImportStatus::Rejected;
+            result.rejections = rejections.into_iter().map(GateDetail::from).collect();
+            continue;
+        }
+        node.embedding = Some(embedding);
+        result.id = Some(node.id.to_string());
+        result.gate_warnings = WriteGate::warnings(&node);
+        accepted.push(node);
+    }
+    accepted
+}
+
+/// Store accepted nodes in one transaction and index their embeddings.
+fn write_nodes<S: Storage, V: VectorIndex>(
+    storage: &S,
+    index: &mut V,
+    accepted: &[Node],
+) -> anyhow::Result<()> {
+    storage.put_nodes_batch(accepted)?;
+    for node in accepted {
+        if let Some(embedding) = &node.embedding {
+            index.insert_node(node, embedding)?;
+        }
+    }
+    Ok(())
+}
+
+/// What [`run_import`] did.
+struct ImportRun {
+    results: Vec<ImportItemResult>,
+    /// Nodes written, empty for a dry run.
+    stored: Vec<Node>,
+    /// Whether the caller bypassed the gate.
+    trusted: bool,
+}
+
+/// Check and, unless `dry_run`, write one import. Blocking: embeds the batch
+/// and holds the vector index lock. The write lock is held from the conflict
+/// check to the write so no other node can land in between.
+fn run_import(
+    state: &AppState,
+    items: Vec<Result<CreateNodeBody, String>>,
+    agent_id: &str,
+    skip_gate: bool,
+    dry_run: bool,
+) -> anyhow::Result<ImportRun> {
+    let gate = state.write_gate.read().unwrap().clone();
+    let mut checks = GatedWrite::new(&gate, &state.schema_validator)
+        .skip(skip_gate)
+        .caller(agent_id);
+    if !dry_run {
+        checks = checks.with_metrics(&state.metrics);
+    }
+    let (mut results, candidates) =
+        prepare_items(items, agent_id, &checks, &*state.embedding_service)?;
+
+    let stored = if dry_run {
+        let index = state.vector_index.read().unwrap();
+        check_candidates(&mut results, candidates, &checks, &*index, &*state.storage);
+        Vec::new()
+    } else {
+        let mut index = state.vector_index.write().unwrap();
+        let accepted =
+            check_candidates(&mut results, candidates, &checks, &*index, &*state.storage);
+        if !accepted.is_empty() {
+            write_nodes(&*state.storage, &mut *index, &accepted)?;
+            for result in results
+                .iter_mut()
+                .filter(|r| r.status == ImportStatus::Valid)
+            {
+                result.status = ImportStatus::Created;
+            }
+        }
+        accepted
+    };
+    Ok(ImportRun {
+        results,
+        stored,
+        trusted: checks.trusted(),
+    })
+}
+
+// ── POST /nodes/import ────────────────────────────────────────────────────────
+
+pub async fn import_nodes(
+    State(state): State<AppState>,
+    Query(query): Query<ImportQuery>,
+    headers: HeaderMap,
+    body: String,
+) -> AppResult<impl IntoResponse> {
+    let agent_id = headers
+        .get("x-agent-id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("anonymous")
+        .to_string();
+    let skip_gate = query.gate.as_deref() == Some("skip")
+        && headers.get("x-gate-override").and_then(|v| v.to_str().ok()) == Some("true");
+
+    let items = parse_items(&body)?;
+    let total = items.len();
+    let dry_run = query.dry_run;
+    let run = {
+        let state = state.clone();
+        let agent_id = agent_id.clone();
+        tokio::task::spawn_blocking(move || {
+            run_import(&state, items, &agent_id, skip_gate, dry_run)
+        })
+        .await
+        .map_err(anyhow::Error::from)??
+    };
+
+    if !run.stored.is_empty() {
+        state.bump_graph_version();
+        for node in &run.stored {
+            if let (true, Some(log)) = (run.trusted, state.storage.audit_log()) {
+                record_bypass(log, &agent_id, node);
+            }
+            state.hooks.notify_node(node, MutationAction::Created);
+        }
+    }
+
+    let response = ImportResponse::new(dry_run, run.results);
+    tracing::info!(
+        "[AUDIT] POST /nodes/import agent={} items={} created={} rejected={} invalid={} dry_run={}",
+        agent_id,
+        total,
+        response.created,
+        response.rejected,
+        response.invalid,
+        dry_run,
+    );
+    Ok(Json(JsonResponse::ok(response)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::metrics::CortexMetrics;
+    use super::*;
+    use crate::test_support::HashEmbedder;
+    use cortex_core::{HnswIndex, NodeFilter, RedbStorage, SchemaValidator, WriteGateConfig};
+
+    const BATCH: &str = r#"
+{"kind": "decision", "title": "Use redb for embedded storage", "body": "We chose redb because it gives ACID transactions in a single file.", "tags": ["storage"]}
+{"title": "short", "body": "tiny"}
+{"body": "no title at all"}
+{"kind": "fact", "title": "HNSW index rebuilds lazily", "body": "The vector index is rebuilt on the first search after inserts.", "tags": ["vector"]}
+"#;
+
+    /// Import `batch`, or only check it when `metrics` is `None`.
+    fn run(
+        batch: &str,
+        metrics: Option<&CortexMetrics>,
+    ) -> (RedbStorage, tempfile::TempDir, Vec<ImportItemResult>) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = RedbStorage::open(dir.path().join("import.redb")).unwrap();
+        let mut index = HnswIndex::new(HashEmbedder::DIMENSION);
+        let gate = WriteGateConfig::default();
+        let schema = SchemaValidator::new(Default::default());
+        let mut checks = GatedWrite::new(&gate, &schema);
+        if let Some(metrics) = metrics {
+            checks = checks.with_metrics(metrics);
+        }
+        let (mut results, candidates) = prepare_items(
+            parse_items(batch).unwrap(),
+            "importer",
+            &checks,
+            &HashEmbedder,
+        )
+        .unwrap();
+        let accepted = check_candidates(&mut results, candidates, &checks, &index, &storage);
+        if metrics.is_some() {
+            write_nodes(&storage, &mut index, &accepted).unwrap();
+            for r in results
+                .iter_mut()
+                .filter(|r| r.status == ImportStatus::Valid)
+            {
+                r.status = ImportStatus::Created;
+            }
+        }
+        (storage, dir, results)
+    }
+
+    #[test]
+    fn import_reports_each_item() {
+        let metrics = CortexMetrics::new();
+        let (storage, _dir, results) = run(BATCH, Some(&metrics));
+        let statuses: Vec<ImportStatus> = results.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                ImportStatus::Created,
+                ImportStatus::Rejected,
+                ImportStatus::Invalid,
+                ImportStatus::Created,
+            ]
+        );
+        assert!(results[1].id.is_none());
+        assert!(results[1].rejections.iter().any(|r| r.check == "substance"));
+        assert!(results[2].error.as_deref().unwrap().contains("title"));
+        assert_eq!(metrics.gate_passed.get(), 2);
+        let gate = metrics.gate_stats.snapshot();
+        assert_eq!((gate.accepted, gate.rejected), (2, 1));
+
+        let stored = storage.list_nodes(NodeFilter::new()).unwrap();
+        assert_eq!(stored.len(), 2);
+        assert!(stored.iter().all(|n| n.source.agent == "importer"));
+        for result in [&results[0], &results[3]] {
+            let id = result.id.as_deref().unwrap();
+            assert!(stored.iter().any(|n| n.id.to_string() == id));
+        }
+    }
+
+    #[test]
+    fn dry_run_writes_nothing() {
+        let (storage, _dir, results) = run(BATCH, None);
+        let response = ImportResponse::new(true, results);
+        assert_eq!(
+            (
+                response.valid,
+                response.rejected,
+                response.invalid,
+                response.created
+            ),
+            (2, 1, 1, 0)
+        );
+        assert!(storage.list_nodes(NodeFilter::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn duplicates_within_one_import_are_rejected() {
+        let line = r#"{"kind": "fact", "title": "HNSW index rebuilds lazily", "body": "The vector index is rebuilt on the first search after inserts."}"#;
+        let batch = format!("{line}\n{line}\n");
+        let (storage, _dir, results) = run(&batch, Some(&CortexMetrics::new()));
+        assert_eq!(results[0].status, ImportStatus::Created);
+        assert_eq!(results[1].status, ImportStatus::Rejected);
+        let conflict = &results[1].rejections[0];
+        assert_eq!(conflict.check, "conflict");
+        assert_eq!(conflict.existing_node, results[0].id);
+        assert_eq!(storage.list_nodes(NodeFilter::new()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn parse_items_accepts_json_array() {
+        let items = parse_items(r#"[{"title": "one"}, 42, {"title": "two"}]"#).unwrap();
+        assert_eq!(items.len(), 3);
+        assert!(items[0].is_ok() && items[1].is_err() && items[2].is_ok());
+        assert!(parse_items("[not json").is_err());
+    }
+}
@@ -1,9 +1,19 @@
+use super::AppState;
+use axum::{
+    extract::{FromRef, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+};
+use cortex_core::{
+    EmbeddingCacheStats, GateMode, GateRejection, GateStats, Node, NodeFilter, RedbStorage, Storage,
+};
 use prometheus_client::encoding::EncodeLabelSet;
 use prometheus_client::metrics::counter::Counter;
 use prometheus_client::metrics::family::Family;
 use prometheus_client::metrics::gauge::Gauge;
 use prometheus_client::metrics::histogram::Histogram;
 use prometheus_client::registry::Registry;
+use std::sync::Arc;
 
 // ── Label types ────────────────────────────────────────────────────────────────
 
@@ -20,6 +30,7 @@ pub struct RelationLabel {
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
 pub struct GateCheckLabel {
     pub check: String,
+    pub kind: String,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
@@ -33,6 +44,11 @@ pub struct HttpLabel {
     pub status: String,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct TriggerLabel {
+    pub trigger: String,
+}
+
 // ── Metrics registry ───────────────────────────────────────────────────────────
 
 pub struct CortexMetrics {
@@ -64,7 +80,19 @@ pub struct CortexMetrics {
     // Write gate
     pub gate_passed: Counter,
     pub gate_rejected: Family<GateCheckLabel, Counter>,
+    pub gate_warned: Family<GateCheckLabel, Counter>,
     pub gate_skipped: Counter,
+    /// Gate outcomes for `/stats`: accepted vs rejected (with a rolling
+    /// window) and failures by check and kind.
+    pub gate_stats: GateStats,
+
+    // Ingest
+    pub warren_dead_letters: Counter,
+
+    // Embedding cache — gauges, updated from the cache at scrape time
+    pub embedding_cache_hits: Gauge,
+    pub embedding_cache_misses: Gauge,
+    pub embedding_cache_entries: Gauge,
 
     // Search
     pub search_requests: Family<EndpointLabel, Counter>,
@@ -73,6 +101,9 @@ pub struct CortexMetrics {
     // HTTP request counter (method × status)
     pub http_requests: Family<HttpLabel, Counter>,
 
+    // Prompt rollbacks (automatic and manual), by trigger
+    pub prompt_rollbacks: Family<TriggerLabel, Counter>,
+
     // Echo / fizzle
     pub echo_total_accesses: Gauge,
     pub echo_active_nodes: Gauge,
@@ -204,10 +235,17 @@ impl CortexMetrics {
         let gate_rejected: Family<GateCheckLabel, Counter> = Family::default();
         registry.register(
             "cortex_gate_rejected_total",
-            "Write gate: nodes rejected, by failing check",
+            "Write gate: failed checks that rejected a node, by check and kind",
             gate_rejected.clone(),
         );
 
+        let gate_warned: Family<GateCheckLabel, Counter> = Family::default();
+        registry.register(
+            "cortex_gate_warned_total",
+            "Write gate: failed checks let through in warn mode, by check and kind",
+            gate_warned.clone(),
+        );
+
         let gate_skipped: Counter = Counter::default();
         registry.register(
             "cortex_gate_skipped_total",
@@ -215,6 +253,36 @@ impl CortexMetrics {
             gate_skipped.clone(),
         );
 
+        // Ingest
+        let warren_dead_letters: Counter = Counter::default();
+        registry.register(
+            "cortex_warren_dead_letters_total",
+            "Warren events that failed to ingest and were dead-lettered",
+            warren_dead_letters.clone(),
+        );
+
+        // Embedding cache
+        let embedding_cache_hits: Gauge = Gauge::default();
+        registry.register(
+            "cortex_embedding_cache_hits",
+            "Embeddings served from the cache since startup",
+            embedding_cache_hits.clone(),
+        );
+
+        let embedding_cache_misses: Gauge = Gauge::default();
+        registry.register(
+            "cortex_embedding_cache_misses",
+            "Embeddings computed by the model since startup",
+            embedding_cache_misses.clone(),
+        );
+
+        let embedding_cache_entries: Gauge = Gauge::default();
+        registry.register(
+            "cortex_embedding_cache_entries",
+            "Embeddings currently held in the cache",
+            embedding_cache_entries.clone(),
+        );
+
         // Search
         let search_requests: Family<EndpointLabel, Counter> = Family::default();
         registry.register(
@@ -241,6 +309,14 @@ impl CortexMetrics {
             http_requests.clone(),
         );
 
+        // Prompt rollbacks
+        let prompt_rollbacks: Family<TriggerLabel, Counter> = Family::default();
+        registry.register(
+            "cortex_prompt_rollbacks_total",
+            "Total prompt rollbacks by trigger",
+            prompt_rollbacks.clone(),
+        );
+
         // Echo
         let echo_total_accesses: Gauge = Gauge::default();
         registry.register(
@@ -283,13 +359,199 @@ impl CortexMetrics {
             linker_cycle_duration,
             gate_passed,
             gate_rejected,
+            gate_warned,
             gate_skipped,
+            gate_stats: GateStats::new(),
+            warren_dead_letters,
+            embedding_cache_hits,
+            embedding_cache_misses,
+            embedding_cache_entries,
             search_requests,
             search_duration,
             http_requests,
+            prompt_rollbacks,
             echo_total_accesses,
             echo_active_nodes,
             uptime_seconds,
         }
     }
+
+    /// Count `node`'s failed gate checks, as rejections or (in warn mode)
+    /// warnings, labelled by check and node kind.
+    pub fn count_gate_failures(&self, node: &Node, failed: &[GateRejection], mode: GateMode) {
+        let counter = match mode {
+            GateMode::Enforce => &self.gate_rejected,
+            GateMode::Warn => &self.gate_warned,
+        };
+        for r in failed {
+            counter
+                .get_or_create(&GateCheckLabel {
+                    check: r.check.to_string(),
+                    kind: node.kind.as_str().to_string(),
+                })
+                .inc();
+        }
+    }
+}
+
+// ── GET /metrics ───────────────────────────────────────────────────────────────
+
+/// What a scrape reads: the registry, and where its gauges are refreshed from.
+#[derive(Clone)]
+pub struct MetricsSources {
+    pub metrics: Arc<CortexMetrics>,
+    pub storage: Arc<RedbStorage>,
+    pub embedding_cache: Arc<dyn Fn() -> EmbeddingCacheStats + Send + Sync>,
+    pub start_time: std::time::Instant,
+}
+
+impl FromRef<AppState> for MetricsSources {
+    fn from_ref(state: &AppState) -> Self {
+        let embedder = state.embedding_service.clone();
+        Self {
+            metrics: state.metrics.clone(),
+            storage: state.storage.clone(),
+            embedding_cache: Arc::new(move || embedder.stats()),
+            start_time: state.start_time,
+        }
+    }
+}
+
+pub async fn metrics_handler(State(state): State<MetricsSources>) -> impl IntoResponse {
+    let m = &state.metrics;
+
+    // Storage gauges
+    if let Ok(stats) = state.storage.stats() {
+        m.node_count.set(stats.node_count as i64);
+        m.edge_count.set(stats.edge_count as i64);
+        for (kind, count) in stats.node_counts_by_kind {
+            m.nodes_by_kind
+                .get_or_create(&KindLabel {
+                    kind: format!("{:?}", kind),
+                })
+                .set(count as i64);
+        }
+        for (relation, count) in stats.edge_counts_by_relation {
+            m.edges_by_relation
+                .get_or_create(&RelationLabel {
+                    relation: format!("{:?}", relation),
+                })
+                .set(count as i64);
+        }
+    }
+
+    if let Ok(meta) = std::fs::metadata(state.storage.path()) {
+        m.db_size.set(meta.len() as i64);
+    }
+
+    // Echo / fizzle stats (O(n) node scan — trivial at current scale)
+    if let Ok(nodes) = state.storage.list_nodes(NodeFilter::new()) {
+        let total_accesses: u64 = nodes.iter().map(|n| n.access_count).sum();
+        let active: u64 = nodes.iter().filter(|n| n.access_count > 0).count() as u64;
+        m.echo_total_accesses.set(total_accesses as i64);
+        m.echo_active_nodes.set(active as i64);
+    }
+
+    let cache = (state.embedding_cache)();
+    m.embedding_cache_hits.set(cache.hits as i64);
+    m.embedding_cache_misses.set(cache.misses as i64);
+    m.embedding_cache_entries.set(cache.entries as i64);
+
+    // Uptime
+    m.uptime_seconds
+        .set(state.start_time.elapsed().as_secs() as i64);
+
+    let mut output = String::new();
+    prometheus_client::encoding::text::encode(&mut output, &m.registry).unwrap_or_default();
+
+    (
+        StatusCode::OK,
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        output,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, extract::Request, routing::get, Router};
+    use tower::ServiceExt;
+
+    /// Value of the sample line `name{labels} value`, if present.
+    fn sample(text: &str, series: &str) -> Option<f64> {
+        text.lines()
+            .filter(|l| !l.starts_with('#'))
+            .find_map(|l| l.strip_prefix(series)?.strip_prefix(' ')?.parse().ok())
+    }
+
+    #[tokio::test]
+    async fn metrics_endpoint_exposes_known_series() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("metrics.redb")).unwrap());
+        let m = Arc::new(CortexMetrics::new());
+        m.gate_passed.inc();
+        m.gate_passed.inc();
+        for trigger in ["manual", "consecutive_negative"] {
+            m.prompt_rollbacks
+                .get_or_create(&TriggerLabel {
+                    trigger: trigger.into(),
+                })
+                .inc();
+        }
+        m.search_duration
+            .get_or_create(&EndpointLabel {
+                endpoint: "search".into(),
+            })
+            .observe(0.02);
+
+        let app = Router::new()
+            .route("/metrics", get(metrics_handler))
+            .with_state(MetricsSources {
+                metrics: m,
+                storage,
+                embedding_cache: Arc::new(|| EmbeddingCacheStats {
+                    hits: 3,
+                    misses: 1,
+                    entries: 1,
+                    capacity: 8,
+                }),
+                start_time: std::time::Instant::now(),
+            });
+        let resp = app
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain; version=0.0.4"));
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(text.contains("# TYPE cortex_node_count gauge"), "{text}");
+        assert!(
+            text.contains("# TYPE cortex_gate_passed_total counter"),
+            "{text}"
+        );
+        assert_eq!(sample(&text, "cortex_node_count"), Some(0.0));
+        assert_eq!(sample(&text, "cortex_embedding_cache_hits"), Some(3.0));
+        assert_eq!(sample(&text, "cortex_gate_passed_total_total"), Some(2.0));
+        for trigger in ["manual", "consecutive_negative"] {
+            let series = format!("cortex_prompt_rollbacks_total_total{{trigger=\"{trigger}\"}}");
+            assert_eq!(sample(&text, &series), Some(1.0), "{text}");
+        }
+        assert_eq!(
+            sample(
+                &text,
+                "cortex_search_duration_seconds_count{endpoint=\"search\"}"
+            ),
+            Some(1.0)
+        );
+    }
 }
@@ -1,27 +1,28 @@
 pub mod auth;
+pub mod import;
 pub mod metrics;
 pub mod prompts;
 pub mod rollback;
 mod routes;
 pub mod selection;
 mod viz;
+pub mod webhooks;
 
-pub use routes::create_router;
+pub use routes::{create_router, ROUTES};
 pub use viz::GRAPH_VIZ_HTML;
 
-use cortex_core::{Node, NodeFilter, NodeKind, Storage};
+use cortex_core::{Node, NodeKind, Storage};
 
-/// Find a node by kind and title (linear scan — no title index in storage).
+/// Find a node by kind and title via the storage title index.
 ///
-/// Returns the first node whose `data.title` exactly matches `title`, or `None`.
+/// Returns the newest non-deleted node whose `data.title` exactly matches `title`, or `None`.
 /// Shared by `routes` and `selection` to avoid duplicate implementations.
 pub(super) fn find_by_title(
     storage: &cortex_core::RedbStorage,
     kind: &NodeKind,
     title: &str,
 ) -> cortex_core::Result<Option<Node>> {
-    let nodes = storage.list_nodes(NodeFilter::new().with_kinds(vec![kind.clone()]))?;
-    Ok(nodes.into_iter().find(|n| n.data.title == title))
+    storage.get_node_by_title(kind, title)
 }
 
 use axum::{
@@ -32,7 +33,8 @@ use axum::{
 use cortex_core::briefing::BriefingEngine;
 use cortex_core::prompt::RollbackConfig;
 use cortex_core::{
-    FastEmbedService, GraphEngineImpl, HnswIndex, RedbStorage, RwLockVectorIndex, WriteGateConfig,
+    CachingEmbeddingService, EmbeddingService, GraphEngineImpl, HnswIndex, RedbStorage,
+    RwLockVectorIndex, WriteGateConfig,
 };
 use serde::Serialize;
 use std::sync::atomic::AtomicU64;
@@ -40,17 +42,22 @@ use std::sync::Arc;
 
 pub use metrics::CortexMetrics;
 
+/// The embedding model behind the `[embedding] cache_capacity` LRU, shared
+/// by every server component. Boxed so tests can run the server without
+/// downloading a model.
+pub type ServerEmbedder = CachingEmbeddingService<Box<dyn EmbeddingService>>;
+
 /// Concrete briefing engine type shared across HTTP handlers
 pub type HttpBriefingEngine = BriefingEngine<
     RedbStorage,
-    Arc<FastEmbedService>,
+    Arc<ServerEmbedder>,
     RwLockVectorIndex<HnswIndex>,
     Arc<GraphEngineImpl<RedbStorage>>,
 >;
 
 /// Concrete auto-linker type shared across HTTP handlers
 pub type HttpAutoLinker =
-    cortex_core::AutoLinker<RedbStorage, FastEmbedService, HnswIndex, GraphEngineImpl<RedbStorage>>;
+    cortex_core::AutoLinker<RedbStorage, ServerEmbedder, HnswIndex, GraphEngineImpl<RedbStorage>>;
 
 /// Shared application state
 #[derive(Clone)]
@@ -58,19 +65,48 @@ pub struct AppState {
     pub storage: Arc<cortex_core::RedbStorage>,
     pub graph_engine: Arc<cortex_core::GraphEngineImpl<cortex_core::RedbStorage>>,
     pub vector_index: Arc<std::sync::RwLock<cortex_core::HnswIndex>>,
-    pub embedding_service: Arc<cortex_core::FastEmbedService>,
+    pub embedding_service: Arc<ServerEmbedder>,
     pub auto_linker: Arc<std::sync::RwLock<HttpAutoLinker>>,
     pub graph_version: Arc<AtomicU64>,
     pub briefing_engine: Arc<HttpBriefingEngine>,
     pub metrics: Arc<CortexMetrics>,
     pub start_time: std::time::Instant,
-    pub rollback_config: RollbackConfig,
-    pub webhooks: Vec<crate::config::WebhookConfig>,
+    /// Swapped on config reload; read it per request.
+    pub rollback_config: Arc<std::sync::RwLock<RollbackConfig>>,
+    pub selection_config: cortex_core::prompt::SelectionConfig,
+    /// Webhook subscriptions from config and the `/webhooks` API.
+    pub webhooks: Arc<crate::webhooks::WebhookRegistry>,
     pub score_decay: cortex_core::ScoreDecayConfig,
-    pub write_gate: WriteGateConfig,
+    /// Swapped on config reload; read it per request.
+    pub write_gate: Arc<std::sync::RwLock<WriteGateConfig>>,
     pub event_bus: crate::observability::EventBus,
     pub schema_validator: cortex_core::SchemaValidator,
     pub hooks: Arc<cortex_core::HookRegistry>,
+    /// Outbound NATS publisher, when `nats_publish.enabled` is set.
+    pub nats_publisher: Option<Arc<crate::observability::publish::NatsPublisher>>,
+    /// Set when `security.audit_reads` is on; reads are logged here.
+    pub read_audit: Option<Arc<cortex_core::AuditLog>>,
+}
+
+impl AppState {
+    /// Mark the graph as changed, invalidating version-keyed caches.
+    pub(crate) fn bump_graph_version(&self) {
+        self.graph_version
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Record a read in the audit log when `security.audit_reads` is on.
+    pub(crate) fn audit_read(
+        &self,
+        action: cortex_core::AuditAction,
+        actor: &str,
+        target_id: uuid::Uuid,
+        details: serde_json::Value,
+    ) {
+        if let Some(ref log) = self.read_audit {
+            log.log_read(action, actor, target_id, details);
+        }
+    }
 }
 
 /// JSON response wrapper
@@ -124,3 +160,47 @@ where
 }
 
 pub type AppResult<T> = Result<T, AppError>;
+
+/// Middleware for `cortex serve --read-only`: requests to routes that write
+/// (see [`auth::is_read`]) are answered with 405 before reaching a handler.
+pub async fn reject_writes(req: axum::extract::Request, next: axum::middleware::Next) -> Response {
+    if auth::is_read(req.method(), req.uri().path()) {
+        return next.run(req).await;
+    }
+    (
+        StatusCode::METHOD_NOT_ALLOWED,
+        Json(JsonResponse::<()>::err("Server is read-only")),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, extract::Request, routing::get, Router};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn read_only_rejects_writes() {
+        let app = Router::new()
+            .route(
+                "/nodes",
+                get(|| async { "[]" }).post(|| async { "created" }),
+            )
+            .layer(axum::middleware::from_fn(reject_writes));
+        let status = |method: &'static str| {
+            let app = app.clone();
+            async move {
+                let req = Request::builder().method(method).uri("/nodes");
+                app.oneshot(req.body(Body::empty()).unwrap())
+                    .await
+                    .unwrap()
+                    .status()
+            }
+        };
+
+        assert_eq!(status("GET").await, StatusCode::OK);
+        assert_eq!(status("POST").await, StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(status("DELETE").await, StatusCode::METHOD_NOT_ALLOWED);
+    }
+}
@@ -89,13 +89,16 @@ pub async fn create_prompt(
     };
 
     match resolver.create_prompt(content, &branch, &author) {
-        Ok(node_id) => Ok(Json(JsonResponse::ok(serde_json::json!({
-            "node_id": node_id.to_string(),
-            "slug": body.slug,
-            "version": 1,
-            "branch": branch,
-        })))
-        .into_response()),
+        Ok(node_id) => {
+            state.bump_graph_version();
+            Ok(Json(JsonResponse::ok(serde_json::json!({
+                "node_id": node_id.to_string(),
+                "slug": body.slug,
+                "version": 1,
+                "branch": branch,
+            })))
+            .into_response())
+        }
         Err(cortex_core::CortexError::Validation(msg)) if msg.contains("already exists") => {
             Ok(bad_request(msg))
         }
@@ -203,6 +206,7 @@ pub async fn create_version(
 
     match resolver.create_version(&slug, &branch, content, &author) {
         Ok(node_id) => {
+            state.bump_graph_version();
             let new_node = state.storage.get_node(node_id)?.unwrap();
             let version = resolver
                 .parse_content(&new_node)
@@ -238,14 +242,17 @@ pub async fn create_branch(
         body.base_version,
         &author,
     ) {
-        Ok(node_id) => Ok(Json(JsonResponse::ok(serde_json::json!({
-            "node_id": node_id.to_string(),
-            "slug": slug,
-            "branch": body.new_branch,
-            "from_branch": from_branch,
-            "version": 1,
-        })))
-        .into_response()),
+        Ok(node_id) => {
+            state.bump_graph_version();
+            Ok(Json(JsonResponse::ok(serde_json::json!({
+                "node_id": node_id.to_string(),
+                "slug": slug,
+                "branch": body.new_branch,
+                "from_branch": from_branch,
+                "version": 1,
+            })))
+            .into_response())
+        }
         Err(cortex_core::CortexError::Validation(msg)) => Ok(not_found(msg)),
         Err(e) => Err(e.into()),
     }
@@ -3,7 +3,9 @@
 /// Endpoints:
 ///   POST /prompts/:slug/deploy           — record deployment + snapshot baseline
 ///   GET  /prompts/:slug/rollback-status  — current status (cooldown, quarantine, active window)
+///   POST /prompts/:slug/rollback         — manually roll HEAD back to an earlier version
 ///   POST /prompts/:slug/unquarantine     — manually lift quarantine
+use crate::observations::ObservationContext;
 use super::{AppResult, AppState, JsonResponse};
 use axum::{
     extract::{Path, Query, State},
@@ -29,6 +31,9 @@ pub struct DeployBody {
     /// How many recent observations to use for baseline sampling (default: 20).
     #[serde(default = "default_baseline_sample")]
     pub baseline_sample_size: usize,
+    /// Start as a canary on this share (1–99) of selections; omit for a full rollout.
+    #[serde(default)]
+    pub rollout_percent: Option<u8>,
 }
 
 fn default_branch() -> String {
@@ -48,6 +53,7 @@ struct DeployResponse {
     baseline_correction_rate: f32,
     baseline_sentiment: f32,
     baseline_sample_size: usize,
+    rollout_percent: u8,
 }
 
 pub async fn deploy_prompt(
@@ -109,16 +115,25 @@ pub async fn deploy_prompt(
 
     let (baseline_correction, _, baseline_sentiment, _) = compute_baseline_stats(&baseline_obs);
 
-    let monitor = RollbackMonitor::new(state.storage.clone(), state.rollback_config.clone());
+    let monitor = RollbackMonitor::new(
+        state.storage.clone(),
+        state.rollback_config.read().unwrap().clone(),
+    );
 
-    let deployment_node_id = monitor.record_deployment(
+    let rollout_percent = body.rollout_percent.unwrap_or(100).min(100);
+    if rollout_percent == 0 {
+        return Err(anyhow::anyhow!("rollout_percent must be between 1 and 100").into());
+    }
+    let deployment_node_id = monitor.record_canary_deployment(
         &slug,
         &body.branch,
         version,
         prompt_node_id,
         &body.agent_name,
         baseline_obs,
+        rollout_percent,
     )?;
+    state.bump_graph_version();
 
     Ok(Json(JsonResponse::ok(DeployResponse {
         deployment_node_id: deployment_node_id.to_string(),
@@ -129,6 +144,7 @@ pub async fn deploy_prompt(
         baseline_correction_rate: baseline_correction,
         baseline_sentiment,
         baseline_sample_size: sample_size,
+        rollout_percent,
     })))
 }
 
@@ -145,13 +161,41 @@ pub async fn rollback_status(
     Path(slug): Path<String>,
     Query(q): Query<RollbackStatusQuery>,
 ) -> AppResult<impl IntoResponse> {
-    let monitor = RollbackMonitor::new(state.storage.clone(), state.rollback_config.clone());
+    let monitor = RollbackMonitor::new(
+        state.storage.clone(),
+        state.rollback_config.read().unwrap().clone(),
+    );
     match monitor.get_status(&slug, &q.branch)? {
         Some(s) => Ok(Json(JsonResponse::ok(s))),
         None => Err(anyhow::anyhow!("Prompt '{}@{}' not found", slug, q.branch).into()),
     }
 }
 
+// ── POST /prompts/:slug/rollback ──────────────────────────────────────────────
+
+#[derive(Deserialize)]
+pub struct RollbackBody {
+    #[serde(default = "default_branch")]
+    pub branch: String,
+    /// Version to roll back to; any earlier version on the branch.
+    pub to: u32,
+}
+
+pub async fn rollback_prompt(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Json(body): Json<RollbackBody>,
+) -> AppResult<impl IntoResponse> {
+    let monitor = RollbackMonitor::new(
+        state.storage.clone(),
+        state.rollback_config.read().unwrap().clone(),
+    );
+    let result = monitor.rollback_to(&slug, &body.branch, body.to)?;
+    state.bump_graph_version();
+    ObservationContext::from(&state).announce_rollback(None, &result);
+    Ok(Json(JsonResponse::ok(result)))
+}
+
 // ── POST /prompts/:slug/unquarantine ──────────────────────────────────────────
 
 #[derive(Deserialize)]
@@ -171,8 +215,12 @@ pub async fn unquarantine_prompt(
         .find_head(&slug, &body.branch)?
         .ok_or_else(|| anyhow::anyhow!("Prompt '{}@{}' not found", slug, body.branch))?;
 
-    let monitor = RollbackMonitor::new(state.storage.clone(), state.rollback_config.clone());
+    let monitor = RollbackMonitor::new(
+        state.storage.clone(),
+        state.rollback_config.read().unwrap().clone(),
+    );
     monitor.unquarantine(head.id)?;
+    state.bump_graph_version();
 
     Ok(Json(JsonResponse::ok(serde_json::json!({
         "prompt_node_id": head.id.to_string(),
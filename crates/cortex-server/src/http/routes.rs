@@ -1,7 +1,9 @@
 use super::{
-    metrics::{EndpointLabel, GateCheckLabel},
-    prompts, rollback, selection, AppResult, AppState, JsonResponse, GRAPH_VIZ_HTML,
+    import,
+    metrics::{self, EndpointLabel},
+    prompts, rollback, selection, webhooks, AppResult, AppState, JsonResponse, GRAPH_VIZ_HTML,
 };
+use crate::gate::{record_bypass, GateOutcome, GatedWrite};
 use axum::{
     extract::{Path, Query, State},
     http::{HeaderMap, StatusCode},
@@ -12,22 +14,23 @@ use axum::{
     routing::{get, post, put},
     Router,
 };
+use cortex_core::briefing::BriefingFormat;
 use cortex_core::{
-    apply_score_decay, Edge, EdgeProvenance, GateRejection, GateResult, MutationAction, NodeFilter,
-    NodeKind, Relation, Source, WriteGate, *,
+    apply_confidence_weight, apply_score_decay, Edge, EdgeProvenance, GateRejection, GateResult,
+    MutationAction, NodeFilter, NodeKind, Relation, Source, WriteGate, *,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 // ── Gate rejection response (422 Unprocessable Entity) ────────────────────────
 
-#[derive(Serialize)]
-struct GateDetail {
-    check: String,
+#[derive(Clone, Debug, Serialize)]
+pub struct GateDetail {
+    pub(super) check: String,
     reason: String,
     suggestion: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    existing_node: Option<String>,
+    pub(super) existing_node: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     existing_title: Option<String>,
 }
@@ -36,35 +39,106 @@ struct GateDetail {
 struct GateErrorBody {
     success: bool,
     error: String,
+    /// The first failure, kept for clients that read a single check.
     gate: GateDetail,
+    /// Every failure, so the caller can fix them all at once.
+    rejections: Vec<GateDetail>,
 }
 
-fn gate_rejection_response(rejection: GateRejection) -> Response {
-    let check_name = rejection.check.to_string();
-    let body = GateErrorBody {
-        success: false,
-        error: format!("Write gate: {} check failed", check_name),
-        gate: GateDetail {
-            check: check_name,
+impl From<GateRejection> for GateDetail {
+    fn from(rejection: GateRejection) -> Self {
+        Self {
+            check: rejection.check.to_string(),
             reason: rejection.reason,
             suggestion: rejection.suggestion,
             existing_node: rejection.existing_node,
             existing_title: rejection.existing_title,
-        },
+        }
+    }
+}
+
+fn gate_rejection_response(rejection: GateRejection) -> Response {
+    gate_rejections_response(vec![rejection])
+}
+
+/// 422 listing every failed check. `rejections` must not be empty.
+fn gate_rejections_response(rejections: Vec<GateRejection>) -> Response {
+    let mut checks: Vec<String> = rejections.iter().map(|r| r.check.to_string()).collect();
+    checks.dedup();
+    let rejections: Vec<GateDetail> = rejections.into_iter().map(GateDetail::from).collect();
+    let body = GateErrorBody {
+        success: false,
+        error: format!(
+            "Write gate: {} {} failed",
+            checks.join(", "),
+            if checks.len() == 1 { "check" } else { "checks" }
+        ),
+        gate: rejections[0].clone(),
+        rejections,
     };
     (StatusCode::UNPROCESSABLE_ENTITY, Json(body)).into_response()
 }
 
+/// The path of every route [`create_router`] serves, for checking
+/// `[rate_limit.methods]` keys.
+pub const ROUTES: &[&str] = &[
+    "/health",
+    "/metrics",
+    "/stats",
+    "/nodes",
+    "/nodes/import",
+    "/nodes/:id",
+    "/nodes/:id/restore",
+    "/nodes/:id/neighbors",
+    "/edges",
+    "/edges/:id",
+    "/search",
+    "/search/hybrid",
+    "/viz",
+    "/graph/viz",
+    "/graph/export",
+    "/graph/communities",
+    "/graph/path",
+    "/auto-linker/status",
+    "/auto-linker/trigger",
+    "/auto-linker/preview",
+    "/briefing/:agent_id",
+    "/agents/:name/prompts",
+    "/agents/:name/prompts/:slug",
+    "/agents/:name/resolved-prompt",
+    "/agents/:name/active-variant",
+    "/agents/:name/variant-history",
+    "/agents/:name/observe",
+    "/prompts",
+    "/prompts/:slug/latest",
+    "/prompts/:slug/versions",
+    "/prompts/:slug/versions/:version",
+    "/prompts/:slug/branch",
+    "/prompts/:slug/performance",
+    "/prompts/:slug/performance/by-context",
+    "/events",
+    "/webhooks",
+    "/webhooks/:id",
+    "/events/stream",
+    "/prompts/:slug/deploy",
+    "/prompts/:slug/rollback-status",
+    "/prompts/:slug/rollback",
+    "/prompts/:slug/unquarantine",
+    "/prompts/:slug/versions/:version/performance",
+];
+
 pub fn create_router(state: AppState) -> Router {
     Router::new()
         .route("/health", get(health))
-        .route("/metrics", get(metrics_handler))
+        .route("/metrics", get(metrics::metrics_handler))
         .route("/stats", get(stats))
         .route("/nodes", get(list_nodes).post(create_node))
+        .route("/nodes/import", post(import::import_nodes))
         .route(
             "/nodes/:id",
             get(get_node).delete(delete_node).patch(patch_node),
         )
+        .route("/nodes/:id/restore", post(restore_node))
         .route("/nodes/:id/neighbors", get(node_neighbors))
         .route("/edges", post(create_edge))
         .route("/edges/:id", get(get_edge))
@@ -73,8 +147,11 @@ pub fn create_router(state: AppState) -> Router {
         .route("/viz", get(graph_viz))
         .route("/graph/viz", get(graph_viz))
         .route("/graph/export", get(graph_export))
+        .route("/graph/communities", get(graph_communities))
+        .route("/graph/path", get(graph_path))
         .route("/auto-linker/status", get(auto_linker_status))
         .route("/auto-linker/trigger", post(trigger_auto_link))
+        .route("/auto-linker/preview", get(preview_auto_link))
         .route("/briefing/:agent_id", get(get_briefing))
         .route("/agents/:name/prompts", get(list_agent_prompts))
         .route(
@@ -111,14 +188,28 @@ pub fn create_router(state: AppState) -> Router {
             "/prompts/:slug/performance",
             get(selection::prompt_performance),
         )
+        .route(
+            "/prompts/:slug/performance/by-context",
+            get(selection::prompt_performance_by_context),
+        )
         // Automatic rollback on performance degradation (issue #23)
         // SSE event stream for real-time graph change notifications
+        .route("/events", get(event_stream))
+        .route(
+            "/webhooks",
+            get(webhooks::list_webhooks).post(webhooks::register_webhook),
+        )
+        .route(
+            "/webhooks/:id",
+            get(webhooks::get_webhook).delete(webhooks::delete_webhook),
+        )
         .route("/events/stream", get(event_stream))
         .route("/prompts/:slug/deploy", post(rollback::deploy_prompt))
         .route(
             "/prompts/:slug/rollback-status",
             get(rollback::rollback_status),
         )
+        .route("/prompts/:slug/rollback", post(rollback::rollback_prompt))
         .route(
             "/prompts/:slug/unquarantine",
             post(rollback::unquarantine_prompt),
@@ -145,6 +236,9 @@ struct StatsData {
     nodes_by_kind: HashMap<String, u64>,
     edges_by_relation: HashMap<String, u64>,
     db_size_bytes: u64,
+    table_bytes: BTreeMap<String, u64>,
+    /// Write gate outcomes since startup.
+    gate: GateStatsSnapshot,
 }
 
 async fn health(State(state): State<AppState>) -> AppResult<Json<JsonResponse<HealthResponse>>> {
@@ -175,66 +269,12 @@ async fn health(State(state): State<AppState>) -> AppResult<Json<JsonResponse<He
             nodes_by_kind,
             edges_by_relation,
             db_size_bytes: db_size,
+            table_bytes: stats.table_bytes,
+            gate: state.metrics.gate_stats.snapshot(),
         },
     })))
 }
 
-async fn metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
-    use super::metrics::{KindLabel, RelationLabel};
-    use axum::http::header;
-    use cortex_core::NodeFilter;
-
-    let m = &state.metrics;
-
-    // Storage gauges
-    if let Ok(stats) = state.storage.stats() {
-        m.node_count.set(stats.node_count as i64);
-        m.edge_count.set(stats.edge_count as i64);
-        for (kind, count) in stats.node_counts_by_kind {
-            m.nodes_by_kind
-                .get_or_create(&KindLabel {
-                    kind: format!("{:?}", kind),
-                })
-                .set(count as i64);
-        }
-        for (relation, count) in stats.edge_counts_by_relation {
-            m.edges_by_relation
-                .get_or_create(&RelationLabel {
-                    relation: format!("{:?}", relation),
-                })
-                .set(count as i64);
-        }
-    }
-
-    if let Ok(meta) = std::fs::metadata(state.storage.path()) {
-        m.db_size.set(meta.len() as i64);
-    }
-
-    // Echo / fizzle stats (O(n) node scan — trivial at current scale)
-    if let Ok(nodes) = state.storage.list_nodes(NodeFilter::new()) {
-        let total_accesses: u64 = nodes.iter().map(|n| n.access_count).sum();
-        let active: u64 = nodes.iter().filter(|n| n.access_count > 0).count() as u64;
-        m.echo_total_accesses.set(total_accesses as i64);
-        m.echo_active_nodes.set(active as i64);
-    }
-
-    // Uptime
-    m.uptime_seconds
-        .set(state.start_time.elapsed().as_secs() as i64);
-
-    let mut output = String::new();
-    prometheus_client::encoding::text::encode(&mut output, &m.registry).unwrap_or_default();
-
-    (
-        StatusCode::OK,
-        [(
-            header::CONTENT_TYPE,
-            "text/plain; version=0.0.4; charset=utf-8",
-        )],
-        output,
-    )
-}
-
 async fn stats(State(state): State<AppState>) -> AppResult<Json<JsonResponse<StatsData>>> {
     let stats = state.storage.stats()?;
     let db_size = std::fs::metadata(state.storage.path())
@@ -259,6 +299,8 @@ async fn stats(State(state): State<AppState>) -> AppResult<Json<JsonResponse<Sta
         nodes_by_kind,
         edges_by_relation,
         db_size_bytes: db_size,
+        table_bytes: stats.table_bytes,
+        gate: state.metrics.gate_stats.snapshot(),
     })))
 }
 
@@ -268,6 +310,11 @@ struct ListNodesQuery {
     tag: Option<String>,
     limit: Option<usize>,
     offset: Option<usize>,
+    /// `next_cursor` from the previous page; empty for the first.
+    cursor: Option<String>,
+    /// Also list soft-deleted nodes.
+    #[serde(default)]
+    include_deleted: bool,
 }
 
 #[derive(Serialize)]
@@ -278,20 +325,28 @@ struct NodeData {
     body: String,
     tags: Vec<String>,
     importance: f32,
+    confidence: f32,
     source_agent: String,
     edge_count: usize,
     access_count: u64,
     last_accessed_at: String,
+    expires_at: Option<String>,
+    deleted_at: Option<String>,
 }
 
 async fn list_nodes(
     State(state): State<AppState>,
     Query(query): Query<ListNodesQuery>,
-) -> AppResult<Json<JsonResponse<Vec<NodeData>>>> {
+) -> AppResult<Response> {
     let mut filter = NodeFilter::new();
 
+    // One extra node tells whether another page follows.
     if let Some(limit) = query.limit {
-        filter = filter.with_limit(limit);
+        filter = filter.with_limit(limit.saturating_add(1));
+    }
+
+    if let Some(cursor) = query.cursor.as_deref().filter(|c| !c.is_empty()) {
+        filter = filter.after(cursor.parse::<NodeCursor>()?);
     }
 
     if let Some(offset) = query.offset {
@@ -308,7 +363,18 @@ async fn list_nodes(
         filter = filter.with_kinds(vec![kind]);
     }
 
-    let nodes = state.storage.list_nodes(filter)?;
+    if query.include_deleted {
+        filter = filter.include_deleted();
+    }
+
+    let mut nodes = state.storage.list_nodes(filter)?;
+    let next_cursor = match query.limit {
+        Some(limit) if nodes.len() > limit => {
+            nodes.truncate(limit);
+            nodes.last().map(NodeCursor::after)
+        }
+        _ => None,
+    };
 
     let node_data: Vec<_> = nodes
         .iter()
@@ -324,28 +390,82 @@ async fn list_nodes(
                 body: n.data.body.clone(),
                 tags: n.data.tags.clone(),
                 importance: n.importance,
+                confidence: n.confidence,
                 source_agent: n.source.agent.clone(),
                 edge_count,
                 access_count: n.access_count,
                 last_accessed_at: n.last_accessed_at.to_rfc3339(),
+                expires_at: n.expires_at.map(|t| t.to_rfc3339()),
+                deleted_at: n.deleted_at.map(|t| t.to_rfc3339()),
             }
         })
         .collect();
 
-    Ok(Json(JsonResponse::ok(node_data)))
+    // Same shape rule as search: callers that page get the cursor in the body.
+    let body = if query.cursor.is_some() {
+        Json(JsonResponse::ok(serde_json::json!({
+            "nodes": node_data,
+            "next_cursor": next_cursor.map(|c| c.to_string()),
+        })))
+        .into_response()
+    } else {
+        Json(JsonResponse::ok(node_data)).into_response()
+    };
+    Ok(with_next_cursor(body, next_cursor))
 }
 
 #[derive(Deserialize)]
-struct CreateNodeBody {
+pub(super) struct CreateNodeBody {
     kind: Option<String>,
     title: String,
     body: Option<String>,
     tags: Option<Vec<String>>,
     importance: Option<f32>,
+    confidence: Option<f32>,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
     source_agent: Option<String>,
     metadata: Option<HashMap<String, serde_json::Value>>,
 }
 
+impl CreateNodeBody {
+    /// Build the node to store. `agent_id` is the source agent unless the
+    /// body names one; kind defaults to `fact`, body to the title and
+    /// importance to the kind's registered default, else 0.5. Whether the
+    /// kind is registered is left to the write gate.
+    pub(super) fn into_node(self, agent_id: &str, kinds: &KindRegistry) -> anyhow::Result<Node> {
+        let kind_str = self.kind.as_deref().unwrap_or("fact");
+        let kind = NodeKind::new(kind_str).map_err(|e| anyhow::anyhow!("Invalid kind: {}", e))?;
+        let importance = kinds.importance(&kind, self.importance);
+        let mut node = Node::new(
+            kind,
+            self.title.clone(),
+            self.body.unwrap_or(self.title),
+            Source {
+                agent: self.source_agent.unwrap_or_else(|| agent_id.to_string()),
+                session: None,
+                channel: None,
+            },
+            importance,
+        );
+        node.data.tags = self.tags.unwrap_or_default();
+        if let Some(confidence) = self.confidence {
+            node.confidence = check_confidence(confidence)?;
+        }
+        node.expires_at = self.expires_at;
+        if let Some(metadata) = self.metadata {
+            node.data.metadata = metadata;
+        }
+        Ok(node)
+    }
+}
+
+fn check_confidence(confidence: f32) -> anyhow::Result<f32> {
+    if !(0.0..=1.0).contains(&confidence) {
+        anyhow::bail!("confidence {} out of range [0.0, 1.0]", confidence);
+    }
+    Ok(confidence)
+}
+
 #[derive(Deserialize)]
 struct CreateNodeQuery {
     gate: Option<String>,
@@ -362,146 +482,51 @@ async fn create_node(
         .and_then(|v| v.to_str().ok())
         .unwrap_or("anonymous");
 
-    let kind_str = body.kind.as_deref().unwrap_or("fact");
-    let kind = NodeKind::new(kind_str).map_err(|e| anyhow::anyhow!("Invalid kind: {}", e))?;
-    let importance = body.importance.unwrap_or(0.5);
-    let tags = body.tags.unwrap_or_default();
-    let source_agent = body.source_agent.unwrap_or_else(|| agent_id.to_string());
-    let node_body = body.body.unwrap_or_else(|| body.title.clone());
-
-    let mut node = Node::new(
-        kind,
-        body.title.clone(),
-        node_body.clone(),
-        Source {
-            agent: source_agent,
-            session: None,
-            channel: None,
-        },
-        importance,
-    );
-    node.data.tags = tags;
-    if let Some(metadata) = body.metadata {
-        node.data.metadata = metadata;
-    }
+    let mut node = body.into_node(agent_id, state.schema_validator.kinds())?;
+    let kind_str = node.kind.as_str().to_string();
 
     // ── Write gate ────────────────────────────────────────────────────────────
-    let gate_config = &state.write_gate;
+    let gate_config = state.write_gate.read().unwrap().clone();
     let gate_skipped = query.gate.as_deref() == Some("skip")
         && headers.get("x-gate-override").and_then(|v| v.to_str().ok()) == Some("true");
+    let gate = GatedWrite::new(&gate_config, &state.schema_validator)
+        .with_metrics(&state.metrics)
+        .skip(gate_skipped)
+        .caller(agent_id);
+    let outcome = match gate.create(
+        &mut node,
+        &*state.storage,
+        &*state.embedding_service,
+        &*state.vector_index,
+    )? {
+        Ok(outcome) => outcome,
+        Err(rejections) => return Ok(gate_rejections_response(rejections)),
+    };
 
-    if gate_config.enabled && !gate_skipped {
-        // Check 1: Substance
-        if let GateResult::Reject(r) = WriteGate::check_substance(&node, gate_config) {
-            state
-                .metrics
-                .gate_rejected
-                .get_or_create(&GateCheckLabel {
-                    check: r.check.to_string(),
-                })
-                .inc();
-            return Ok(gate_rejection_response(r));
-        }
-
-        // Check 2: Specificity
-        if let GateResult::Reject(r) = WriteGate::check_specificity(&node, gate_config) {
-            state
-                .metrics
-                .gate_rejected
-                .get_or_create(&GateCheckLabel {
-                    check: r.check.to_string(),
-                })
-                .inc();
-            return Ok(gate_rejection_response(r));
-        }
-
-        // Generate embedding now so the conflict check can use it
-        let embedding = state
-            .embedding_service
-            .embed(&format!("{} {}", node.data.title, node.data.body))?;
-
-        // Check 3: Conflict (read lock — no writes yet)
-        {
-            let index = state.vector_index.read().unwrap();
-            if let GateResult::Reject(r) =
-                WriteGate::check_conflict(&node, &embedding, &*index, &*state.storage, gate_config)
-            {
-                state
-                    .metrics
-                    .gate_rejected
-                    .get_or_create(&GateCheckLabel {
-                        check: r.check.to_string(),
-                    })
-                    .inc();
-                return Ok(gate_rejection_response(r));
-            }
-        }
-
-        // Check 4: Schema validation
-        if let GateResult::Reject(r) = WriteGate::check_schema(&node, &state.schema_validator) {
-            state
-                .metrics
-                .gate_rejected
-                .get_or_create(&GateCheckLabel {
-                    check: r.check.to_string(),
-                })
-                .inc();
-            return Ok(gate_rejection_response(r));
-        }
-
-        // All checks passed — store and index
-        state.storage.put_node(&node)?;
-        {
-            let mut index = state.vector_index.write().unwrap();
-            index.insert(node.id, &embedding)?;
-        }
-
-        state.metrics.gate_passed.inc();
-        tracing::info!(
-            "[AUDIT] POST /nodes agent={} gate=PASS title={:?} kind={}",
-            agent_id,
-            node.data.title,
-            kind_str,
-        );
-    } else {
-        // Schema check still applies even when gate is skipped
-        if let GateResult::Reject(r) = WriteGate::check_schema(&node, &state.schema_validator) {
-            state
-                .metrics
-                .gate_rejected
-                .get_or_create(&GateCheckLabel {
-                    check: r.check.to_string(),
-                })
-                .inc();
-            return Ok(gate_rejection_response(r));
-        }
-
-        // Bypass: generate embedding, store, index without gate checks
-        let embedding = state
-            .embedding_service
-            .embed(&format!("{} {}", node.data.title, node.data.body))?;
-
-        state.storage.put_node(&node)?;
-        {
-            let mut index = state.vector_index.write().unwrap();
-            index.insert(node.id, &embedding)?;
-        }
-
-        state.metrics.gate_skipped.inc();
-        tracing::info!(
-            "[AUDIT] POST /nodes agent={} gate=SKIPPED title={:?} kind={}",
-            agent_id,
-            node.data.title,
-            kind_str,
-        );
+    let trusted = gate.trusted();
+    if let (true, Some(log)) = (trusted, state.storage.audit_log()) {
+        record_bypass(log, agent_id, &node);
     }
+    tracing::info!(
+        "[AUDIT] POST /nodes agent={} gate={} title={:?} kind={}",
+        agent_id,
+        if trusted && outcome == GateOutcome::Passed {
+            "TRUSTED"
+        } else {
+            outcome.as_str()
+        },
+        node.data.title,
+        kind_str,
+    );
 
+    state.bump_graph_version();
     state.hooks.notify_node(&node, MutationAction::Created);
 
     Ok(Json(JsonResponse::ok(serde_json::json!({
         "id": node.id.to_string(),
         "title": node.data.title,
         "kind": kind_str,
+        "gate_warnings": WriteGate::warnings(&node),
     })))
     .into_response())
 }
@@ -512,13 +537,16 @@ struct CreateEdgeBody {
     to_id: String,
     relation: Option<String>,
     weight: Option<f32>,
+    /// Also write the inverse edge for a symmetric or invertible relation.
+    #[serde(default)]
+    inverse: bool,
 }
 
 async fn create_edge(
     State(state): State<AppState>,
     headers: HeaderMap,
     Json(body): Json<CreateEdgeBody>,
-) -> AppResult<impl IntoResponse> {
+) -> AppResult<Response> {
     let agent_id = headers
         .get("x-agent-id")
         .and_then(|v| v.to_str().ok())
@@ -549,15 +577,33 @@ async fn create_edge(
         updated_at: chrono::Utc::now(),
     };
 
-    state.storage.put_edge(&edge)?;
-    state.hooks.notify_edge(&edge, MutationAction::Created);
+    let gate = WriteGate::check_edge(
+        &edge,
+        state.storage.as_ref(),
+        &state.write_gate.read().unwrap(),
+    )?;
+    if let GateResult::Reject(r) = gate {
+        return Ok(gate_rejection_response(r));
+    }
+
+    let inverse = if body.inverse {
+        state.storage.put_edge_with_inverse(&edge)?
+    } else {
+        state.storage.put_edge(&edge)?;
+        None
+    };
+    state.bump_graph_version();
+    for e in std::iter::once(&edge).chain(&inverse) {
+        state.hooks.notify_edge(e, MutationAction::Created);
+    }
 
     tracing::info!(
-        "[AUDIT] POST /edges agent={} from={} to={} relation={}",
+        "[AUDIT] POST /edges agent={} from={} to={} relation={} inverse={}",
         agent_id,
         body.from_id,
         body.to_id,
-        relation_str
+        relation_str,
+        inverse.is_some()
     );
 
     Ok(Json(JsonResponse::ok(serde_json::json!({
@@ -565,7 +611,9 @@ async fn create_edge(
         "from": body.from_id,
         "to": body.to_id,
         "relation": relation_str,
-    }))))
+        "inverse_id": inverse.map(|e| e.id.to_string()),
+    })))
+    .into_response())
 }
 
 #[derive(Deserialize)]
@@ -575,11 +623,20 @@ struct HybridSearchQuery {
     /// Blend weight for temporal freshness in final score.
     /// 0.0 = pure relevance, 1.0 = heavily favour recent nodes.
     recency_bias: Option<f32>,
+    /// `X-Next-Cursor` from the previous page.
+    cursor: Option<String>,
+    /// Also return nodes past their `expires_at`.
+    #[serde(default)]
+    include_expired: bool,
+    /// Also return soft-deleted nodes.
+    #[serde(default)]
+    include_deleted: bool,
 }
 
 async fn hybrid_search(
     State(state): State<AppState>,
     Query(query): Query<HybridSearchQuery>,
+    headers: HeaderMap,
 ) -> AppResult<impl IntoResponse> {
     let t = std::time::Instant::now();
     let embedding = state.embedding_service.embed(&query.q)?;
@@ -588,52 +645,83 @@ async fn hybrid_search(
         .recency_bias
         .unwrap_or(state.score_decay.recency_weight);
 
-    // Fetch extra candidates for re-ranking.
-    let candidate_limit = if state.score_decay.enabled && recency_bias > 0.0 {
-        (limit * 3).max(30)
-    } else {
-        limit * 2
-    };
+    let cursor = parse_search_cursor(query.cursor.as_deref())?;
 
-    let index = state.vector_index.read().unwrap();
-    let vector_results = index.search(&embedding, candidate_limit, None)?;
-    drop(index);
-
-    // For hybrid: combine vector scores with graph connectivity, then apply decay.
-    let mut scored: Vec<(serde_json::Value, f32)> = vector_results
-        .iter()
-        .filter_map(|r| {
+    // Results drawn from the best `n` candidates, plus extra for re-ranking.
+    let score = |n: usize| -> cortex_core::Result<(Vec<ScoredHit>, bool)> {
+        let candidate_limit = if state.score_decay.enabled && recency_bias > 0.0 {
+            (n * 3).max(30)
+        } else {
+            n * 2
+        };
+        let vector_results =
             state
-                .storage
-                .get_node(r.node_id)
-                .ok()
-                .flatten()
-                .map(|node| {
-                    let edge_count = state.storage.edges_from(node.id).unwrap_or_default().len()
-                        + state.storage.edges_to(node.id).unwrap_or_default().len();
-                    let graph_boost = (edge_count as f32 * 0.05).min(0.3);
-                    let combined = r.score + graph_boost;
-                    let final_score =
-                        apply_score_decay(&node, combined, &state.score_decay, recency_bias);
-
-                    let value = serde_json::json!({
-                        "id": node.id.to_string(),
-                        "kind": format!("{:?}", node.kind),
-                        "title": node.data.title,
-                        "body": node.data.body,
-                        "score": final_score,
-                        "vector_score": r.score,
-                        "graph_boost": graph_boost,
-                    });
-                    (value, final_score)
-                })
-        })
-        .collect();
-
-    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-    scored.truncate(limit);
+                .vector_index
+                .read()
+                .unwrap()
+                .search(&embedding, candidate_limit, None)?;
+        let exhausted = vector_results.len() < candidate_limit;
+
+        // For hybrid: combine vector scores with graph connectivity, then apply decay.
+        let scored = vector_results
+            .iter()
+            .filter_map(|r| {
+                state
+                    .storage
+                    .get_node(r.node_id)
+                    .ok()
+                    .flatten()
+                    .filter(|n| query.include_expired || !n.is_expired())
+                    .filter(|n| query.include_deleted || !n.deleted)
+                    .map(|node| {
+                        let edge_count =
+                            state.storage.edges_from(node.id).unwrap_or_default().len()
+                                + state.storage.edges_to(node.id).unwrap_or_default().len();
+                        let graph_boost = (edge_count as f32 * 0.05).min(0.3);
+                        let combined = r.score + graph_boost;
+                        let final_score = apply_confidence_weight(
+                            &node,
+                            apply_score_decay(&node, combined, &state.score_decay, recency_bias),
+                            &state.score_decay,
+                        );
+
+                        let value = serde_json::json!({
+                            "id": node.id.to_string(),
+                            "kind": format!("{:?}", node.kind),
+                            "title": node.data.title,
+                            "body": node.data.body,
+                            "score": final_score,
+                            "vector_score": r.score,
+                            "graph_boost": graph_boost,
+                        });
+                        (node.id, value, final_score)
+                    })
+            })
+            .collect();
+        Ok((scored, exhausted))
+    };
 
-    let results: Vec<serde_json::Value> = scored.into_iter().map(|(v, _)| v).collect();
+    let (page, next_cursor) = page_search(
+        score,
+        |(id, _, score)| (*score, *id),
+        cursor.as_ref(),
+        limit,
+    )?;
+    let results: Vec<serde_json::Value> = page.into_iter().map(|(_, v, _)| v).collect();
+
+    state.audit_read(
+        AuditAction::Search,
+        headers
+            .get("x-agent-id")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("anonymous"),
+        uuid::Uuid::nil(),
+        serde_json::json!({
+            "query": query.q,
+            "hybrid": true,
+            "results": results.iter().filter_map(|v| v["id"].as_str()).collect::<Vec<_>>(),
+        }),
+    );
 
     // Record search metrics
     state
@@ -668,7 +756,7 @@ async fn hybrid_search(
         });
     }
 
-    Ok(Json(JsonResponse::ok(results)))
+    Ok(search_page(results, next_cursor, query.cursor.is_some()))
 }
 
 async fn delete_node(
@@ -685,6 +773,7 @@ async fn delete_node(
     let node_for_hook = state.storage.get_node(node_id).ok().flatten();
 
     state.storage.delete_node(node_id)?;
+    state.bump_graph_version();
 
     if let Some(node) = node_for_hook {
         state.hooks.notify_node(&node, MutationAction::Deleted);
@@ -695,6 +784,35 @@ async fn delete_node(
     Ok(Json(JsonResponse::ok(serde_json::json!({"deleted": id}))))
 }
 
+async fn restore_node(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> AppResult<impl IntoResponse> {
+    let agent_id = headers
+        .get("x-agent-id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("anonymous");
+
+    let node_id: uuid::Uuid = id.parse().map_err(|_| anyhow::anyhow!("Invalid UUID"))?;
+    let node = state.storage.restore_node(node_id)?;
+
+    // Index rebuilds skip tombstones, so the embedding may be gone
+    if let Some(embedding) = &node.embedding {
+        state
+            .vector_index
+            .write()
+            .unwrap()
+            .insert_node(&node, embedding)?;
+    }
+    state.bump_graph_version();
+    state.hooks.notify_node(&node, MutationAction::Updated);
+
+    tracing::info!("[AUDIT] POST /nodes/{}/restore agent={}", id, agent_id);
+
+    Ok(Json(JsonResponse::ok(serde_json::json!({"restored": id}))))
+}
+
 #[derive(Deserialize)]
 struct PatchNodeBody {
     kind: Option<String>,
@@ -702,22 +820,33 @@ struct PatchNodeBody {
     body: Option<String>,
     tags: Option<Vec<String>>,
     importance: Option<f32>,
+    confidence: Option<f32>,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Remove the expiry; wins over `expires_at`.
+    #[serde(default)]
+    clear_expires_at: bool,
     metadata: Option<HashMap<String, serde_json::Value>>,
 }
 
 async fn patch_node(
     State(state): State<AppState>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     Json(patch): Json<PatchNodeBody>,
 ) -> AppResult<impl IntoResponse> {
+    let agent_id = headers
+        .get("x-agent-id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
     let node_id: uuid::Uuid = id.parse().map_err(|_| anyhow::anyhow!("Invalid UUID"))?;
-    let mut node = state
+    let before = state
         .storage
         .get_node(node_id)?
         .ok_or_else(|| anyhow::anyhow!("Node not found"))?;
+    let mut node = before.clone();
 
     if let Some(kind_str) = &patch.kind {
-        node.kind = cortex_core::NodeKind::new(kind_str)
+        node.kind = NodeKind::new_checked(kind_str, state.schema_validator.kinds())
             .map_err(|e| anyhow::anyhow!("Invalid kind: {}", e))?;
     }
     if let Some(title) = patch.title {
@@ -732,17 +861,42 @@ async fn patch_node(
     if let Some(importance) = patch.importance {
         node.importance = importance;
     }
+    if let Some(confidence) = patch.confidence {
+        node.confidence = check_confidence(confidence)?;
+    }
+    if patch.clear_expires_at {
+        node.expires_at = None;
+    } else if let Some(expires_at) = patch.expires_at {
+        node.expires_at = Some(expires_at);
+    }
     if let Some(metadata) = patch.metadata {
         node.data.metadata = metadata;
     }
     node.updated_at = chrono::Utc::now();
 
-    // Schema validation
-    if let GateResult::Reject(r) = WriteGate::check_schema(&node, &state.schema_validator) {
-        return Ok(gate_rejection_response(r).into_response());
+    // Edited text goes through the write gate again
+    let gate_config = state.write_gate.read().unwrap().clone();
+    if let Err(rejections) = GatedWrite::new(&gate_config, &state.schema_validator)
+        .caller(agent_id)
+        .update(&before, &mut node)
+    {
+        return Ok(gate_rejections_response(rejections));
     }
 
+    // Re-embed edited text; either way the index picks up the new kind,
+    // tags and importance for filtered search
+    if node.embedding.is_some() && embedding_input(&node) != embedding_input(&before) {
+        node.embedding = Some(state.embedding_service.embed(&embedding_input(&node))?);
+    }
     state.storage.put_node(&node)?;
+    if let Some(embedding) = &node.embedding {
+        state
+            .vector_index
+            .write()
+            .unwrap()
+            .insert_node(&node, embedding)?;
+    }
+    state.bump_graph_version();
     state.hooks.notify_node(&node, MutationAction::Updated);
 
     Ok(Json(JsonResponse::ok(serde_json::json!({
@@ -774,10 +928,13 @@ async fn get_node(
         body: node.data.body.clone(),
         tags: node.data.tags.clone(),
         importance: node.importance,
+        confidence: node.confidence,
         source_agent: node.source.agent.clone(),
         edge_count: outgoing.len() + incoming.len(),
         access_count: node.access_count,
         last_accessed_at: node.last_accessed_at.to_rfc3339(),
+        expires_at: node.expires_at.map(|t| t.to_rfc3339()),
+        deleted_at: node.deleted_at.map(|t| t.to_rfc3339()),
     };
 
     Ok(Json(JsonResponse::ok(node_data)))
@@ -793,6 +950,7 @@ async fn node_neighbors(
     State(state): State<AppState>,
     Path(id): Path<String>,
     Query(query): Query<NeighborQuery>,
+    headers: HeaderMap,
 ) -> AppResult<impl IntoResponse> {
     let node_id: uuid::Uuid = id.parse().map_err(|_| anyhow::anyhow!("Invalid UUID"))?;
 
@@ -832,14 +990,30 @@ async fn node_neighbors(
                 body: n.data.body.clone(),
                 tags: n.data.tags.clone(),
                 importance: n.importance,
+                confidence: n.confidence,
                 source_agent: n.source.agent.clone(),
                 edge_count: outgoing.len() + incoming.len(),
                 access_count: n.access_count,
                 last_accessed_at: n.last_accessed_at.to_rfc3339(),
+                expires_at: n.expires_at.map(|t| t.to_rfc3339()),
+                deleted_at: n.deleted_at.map(|t| t.to_rfc3339()),
             }
         })
         .collect();
 
+    state.audit_read(
+        AuditAction::Traverse,
+        headers
+            .get("x-agent-id")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("anonymous"),
+        node_id,
+        serde_json::json!({
+            "depth": depth,
+            "nodes": nodes.iter().map(|n| n.id.as_str()).collect::<Vec<_>>(),
+        }),
+    );
+
     Ok(Json(JsonResponse::ok(nodes)))
 }
 
@@ -882,11 +1056,23 @@ struct SearchQuery {
     /// 0.0 = pure relevance (default), 1.0 = heavily favour recent nodes.
     /// Overrides the configured `score_decay.recency_weight` for this query.
     recency_bias: Option<f32>,
+    /// MMR diversity strength. 0.0 = plain ranking (default); higher values
+    /// penalise results that are near-duplicates of ones already returned.
+    diversity: Option<f32>,
+    /// `X-Next-Cursor` from the previous page. Not combinable with `diversity`.
+    cursor: Option<String>,
+    /// Also return nodes past their `expires_at`.
+    #[serde(default)]
+    include_expired: bool,
+    /// Also return soft-deleted nodes.
+    #[serde(default)]
+    include_deleted: bool,
 }
 
 async fn search(
     State(state): State<AppState>,
     Query(query): Query<SearchQuery>,
+    headers: HeaderMap,
 ) -> AppResult<impl IntoResponse> {
     let t = std::time::Instant::now();
     let embedding = state.embedding_service.embed(&query.q)?;
@@ -894,61 +1080,131 @@ async fn search(
     let recency_bias = query
         .recency_bias
         .unwrap_or(state.score_decay.recency_weight);
+    let diversity = query.diversity.unwrap_or(0.0);
+    let cursor = parse_search_cursor(query.cursor.as_deref())?;
+    if cursor.is_some() && diversity > 0.0 {
+        return Err(anyhow::anyhow!("cursor cannot be combined with diversity").into());
+    }
 
-    // Fetch extra candidates so re-ranking by temporal score doesn't cut off
-    // good results that vector-rank lower but are fresher / more accessed.
-    let candidate_limit = if state.score_decay.enabled && recency_bias > 0.0 {
-        (limit * 3).max(30)
-    } else {
-        limit
+    // Results drawn from the best `n` candidates, each paired with its Node
+    // and score decay applied if enabled.
+    let score = |n: usize| -> cortex_core::Result<(Vec<ScoredHit>, bool)> {
+        // Fetch extra candidates so re-ranking by temporal score doesn't cut off
+        // good results that vector-rank lower but are fresher / more accessed.
+        let mut candidate_limit = if state.score_decay.enabled && recency_bias > 0.0 {
+            (n * 3).max(30)
+        } else {
+            n
+        };
+        if diversity > 0.0 {
+            candidate_limit =
+                candidate_limit.max(limit * cortex_core::vector::MMR_CANDIDATE_MULTIPLIER);
+        }
+        let results =
+            state
+                .vector_index
+                .read()
+                .unwrap()
+                .search(&embedding, candidate_limit, None)?;
+        let exhausted = results.len() < candidate_limit;
+
+        let scored = results
+            .iter()
+            .filter_map(|r| {
+                state
+                    .storage
+                    .get_node(r.node_id)
+                    .ok()
+                    .flatten()
+                    .filter(|n| query.include_expired || !n.is_expired())
+                    .filter(|n| query.include_deleted || !n.deleted)
+                    .map(|node| {
+                        let final_score = apply_confidence_weight(
+                            &node,
+                            apply_score_decay(&node, r.score, &state.score_decay, recency_bias),
+                            &state.score_decay,
+                        );
+
+                        let outgoing = state.storage.edges_from(node.id).unwrap_or_default();
+                        let incoming = state.storage.edges_to(node.id).unwrap_or_default();
+
+                        let value = serde_json::json!({
+                            "node": NodeData {
+                                id: node.id.to_string(),
+                                kind: format!("{:?}", node.kind),
+                                title: node.data.title.clone(),
+                                body: node.data.body.clone(),
+                                tags: node.data.tags.clone(),
+                                importance: node.importance,
+                                confidence: node.confidence,
+                                source_agent: node.source.agent.clone(),
+                                edge_count: outgoing.len() + incoming.len(),
+                                access_count: node.access_count,
+                                last_accessed_at: node.last_accessed_at.to_rfc3339(),
+                                expires_at: node.expires_at.map(|t| t.to_rfc3339()),
+                                deleted_at: node.deleted_at.map(|t| t.to_rfc3339()),
+                            },
+                            "score": final_score,
+                            "raw_score": r.score,
+                        });
+                        (node.id, value, final_score)
+                    })
+            })
+            .collect();
+        Ok((scored, exhausted))
     };
 
-    let index = state.vector_index.read().unwrap();
-    let results = index.search(&embedding, candidate_limit, None)?;
-    drop(index);
+    let mut next_cursor = None;
+    let search_results: Vec<serde_json::Value> = if diversity > 0.0 {
+        let (mut scored, _) = score(limit + 1)?;
+        // Re-rank by final score (decay may reshuffle from original vector order).
+        scored.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
 
-    // Pair each raw result with its Node, applying score decay if enabled.
-    let mut scored: Vec<(serde_json::Value, f32)> = results
-        .iter()
-        .filter_map(|r| {
-            state
-                .storage
-                .get_node(r.node_id)
-                .ok()
-                .flatten()
-                .map(|node| {
-                    let final_score =
-                        apply_score_decay(&node, r.score, &state.score_decay, recency_bias);
-
-                    let outgoing = state.storage.edges_from(node.id).unwrap_or_default();
-                    let incoming = state.storage.edges_to(node.id).unwrap_or_default();
-
-                    let value = serde_json::json!({
-                        "node": NodeData {
-                            id: node.id.to_string(),
-                            kind: format!("{:?}", node.kind),
-                            title: node.data.title.clone(),
-                            body: node.data.body.clone(),
-                            tags: node.data.tags.clone(),
-                            importance: node.importance,
-                            source_agent: node.source.agent.clone(),
-                            edge_count: outgoing.len() + incoming.len(),
-                            access_count: node.access_count,
-                            last_accessed_at: node.last_accessed_at.to_rfc3339(),
-                        },
-                        "score": final_score,
-                        "raw_score": r.score,
-                    });
-                    (value, final_score)
-                })
-        })
-        .collect();
+        // MMR over the decayed scores, so freshness still counts as relevance.
+        let candidates: Vec<SimilarityResult> = scored
+            .iter()
+            .map(|(id, _, score)| SimilarityResult {
+                node_id: *id,
+                score: *score,
+                distance: 1.0 - score,
+            })
+            .collect();
+        let index = state.vector_index.read().unwrap();
+        let picked = mmr_rerank(candidates, limit, diversity, |id| index.get_embedding(id));
+        drop(index);
 
-    // Re-rank by final score (decay may reshuffle from original vector order).
-    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-    scored.truncate(limit);
+        let mut by_id: HashMap<NodeId, serde_json::Value> =
+            scored.into_iter().map(|(id, v, _)| (id, v)).collect();
+        picked
+            .iter()
+            .filter_map(|r| by_id.remove(&r.node_id))
+            .collect()
+    } else {
+        let (page, next) = page_search(
+            score,
+            |(id, _, score)| (*score, *id),
+            cursor.as_ref(),
+            limit,
+        )?;
+        next_cursor = next;
+        page.into_iter().map(|(_, v, _)| v).collect()
+    };
 
-    let search_results: Vec<serde_json::Value> = scored.into_iter().map(|(v, _)| v).collect();
+    state.audit_read(
+        AuditAction::Search,
+        headers
+            .get("x-agent-id")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("anonymous"),
+        uuid::Uuid::nil(),
+        serde_json::json!({
+            "query": query.q,
+            "results": search_results
+                .iter()
+                .filter_map(|v| v["node"]["id"].as_str())
+                .collect::<Vec<_>>(),
+        }),
+    );
 
     // Record search metrics
     state
@@ -984,7 +1240,50 @@ async fn search(
         });
     }
 
-    Ok(Json(JsonResponse::ok(search_results)))
+    Ok(search_page(
+        search_results,
+        next_cursor,
+        query.cursor.is_some(),
+    ))
+}
+
+/// A search hit: its node, the JSON returned for it, and its final score.
+type ScoredHit = (NodeId, serde_json::Value, f32);
+
+/// A page of search results. `X-Next-Cursor` is set whenever another page
+/// follows; a caller that passed `cursor` (empty for the first page) gets
+/// `{ "results": [...], "next_cursor": ... }` as the body instead of the
+/// bare array.
+fn search_page(
+    results: Vec<serde_json::Value>,
+    next_cursor: Option<SearchCursor>,
+    paged: bool,
+) -> Response {
+    let body = if paged {
+        Json(JsonResponse::ok(serde_json::json!({
+            "results": results,
+            "next_cursor": next_cursor.map(|c| c.to_string()),
+        })))
+        .into_response()
+    } else {
+        Json(JsonResponse::ok(results)).into_response()
+    };
+    with_next_cursor(body, next_cursor)
+}
+
+fn parse_search_cursor(cursor: Option<&str>) -> Result<Option<SearchCursor>> {
+    cursor.filter(|c| !c.is_empty()).map(str::parse).transpose()
+}
+
+/// Attach `X-Next-Cursor` when another page of results exists.
+fn with_next_cursor(body: impl IntoResponse, next_cursor: Option<impl ToString>) -> Response {
+    let mut response = body.into_response();
+    if let Some(cursor) = next_cursor {
+        if let Ok(value) = cursor.to_string().parse() {
+            response.headers_mut().insert("x-next-cursor", value);
+        }
+    }
+    response
 }
 
 async fn graph_viz() -> Html<&'static str> {
@@ -1039,10 +1338,13 @@ async fn graph_export(State(state): State<AppState>) -> AppResult<Json<JsonRespo
             body: n.data.body.clone(),
             tags: n.data.tags.clone(),
             importance: n.importance,
+            confidence: n.confidence,
             source_agent: n.source.agent.clone(),
             edge_count: edge_counts.get(&n.id).copied().unwrap_or(0),
             access_count: n.access_count,
             last_accessed_at: n.last_accessed_at.to_rfc3339(),
+            expires_at: n.expires_at.map(|t| t.to_rfc3339()),
+            deleted_at: n.deleted_at.map(|t| t.to_rfc3339()),
         })
         .collect();
 
@@ -1052,6 +1354,128 @@ async fn graph_export(State(state): State<AppState>) -> AppResult<Json<JsonRespo
     })))
 }
 
+#[derive(Deserialize)]
+struct CommunitiesQuery {
+    /// Omit clusters smaller than this. Default 2 (hide isolated nodes).
+    min_size: Option<usize>,
+    /// Representative titles per cluster. Default 3.
+    titles: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct CommunityData {
+    size: usize,
+    modularity: f32,
+    representative_titles: Vec<String>,
+    node_ids: Vec<String>,
+}
+
+/// GET /graph/communities — clusters of tightly-connected nodes, largest first
+async fn graph_communities(
+    State(state): State<AppState>,
+    Query(query): Query<CommunitiesQuery>,
+) -> AppResult<impl IntoResponse> {
+    let communities = state
+        .graph_engine
+        .detect_communities(CommunityConfig::default())?;
+    let modularity: f32 = communities.iter().map(|c| c.modularity).sum();
+
+    let min_size = query.min_size.unwrap_or(2);
+    let titles = query.titles.unwrap_or(3);
+    let clusters: Vec<CommunityData> = communities
+        .into_iter()
+        .filter(|c| c.nodes.len() >= min_size)
+        .map(|c| CommunityData {
+            size: c.nodes.len(),
+            modularity: c.modularity,
+            representative_titles: c
+                .nodes
+                .iter()
+                .take(titles)
+                .filter_map(|id| state.storage.get_node(*id).ok().flatten())
+                .map(|n| n.data.title)
+                .collect(),
+            node_ids: c.nodes.iter().map(|id| id.to_string()).collect(),
+        })
+        .collect();
+
+    Ok(Json(JsonResponse::ok(serde_json::json!({
+        "modularity": modularity,
+        "communities": clusters,
+    }))))
+}
+
+#[derive(Deserialize)]
+struct PathQuery {
+    from: String,
+    to: String,
+    max_hops: Option<u32>,
+}
+
+/// GET /graph/path — shortest path by hop count between two nodes
+async fn graph_path(
+    State(state): State<AppState>,
+    Query(query): Query<PathQuery>,
+) -> AppResult<impl IntoResponse> {
+    let from: uuid::Uuid = query
+        .from
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid from UUID"))?;
+    let to: uuid::Uuid = query
+        .to
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid to UUID"))?;
+
+    let paths = state.graph_engine.find_paths(PathRequest {
+        from,
+        to,
+        max_length: query.max_hops,
+        ..Default::default()
+    })?;
+    let Some(path) = paths.paths.into_iter().next() else {
+        return Ok(Json(JsonResponse::ok(serde_json::json!({
+            "found": false,
+            "nodes": [],
+            "edges": [],
+            "length": 0,
+        }))));
+    };
+
+    let nodes: Vec<_> = path
+        .nodes
+        .iter()
+        .filter_map(|id| state.storage.get_node(*id).ok().flatten())
+        .map(|n| {
+            serde_json::json!({
+                "id": n.id.to_string(),
+                "kind": n.kind.as_str(),
+                "title": n.data.title,
+            })
+        })
+        .collect();
+    let edges: Vec<_> = path
+        .edges
+        .iter()
+        .filter_map(|id| state.storage.get_edge(*id).ok().flatten())
+        .map(|e| {
+            serde_json::json!({
+                "id": e.id.to_string(),
+                "from": e.from.to_string(),
+                "to": e.to.to_string(),
+                "relation": e.relation.as_str(),
+                "weight": e.weight,
+            })
+        })
+        .collect();
+
+    Ok(Json(JsonResponse::ok(serde_json::json!({
+        "found": true,
+        "nodes": nodes,
+        "edges": edges,
+        "length": path.length,
+    }))))
+}
+
 async fn auto_linker_status(State(state): State<AppState>) -> AppResult<impl IntoResponse> {
     let linker = state.auto_linker.read().unwrap();
     let metrics = linker.metrics();
@@ -1059,15 +1483,82 @@ async fn auto_linker_status(State(state): State<AppState>) -> AppResult<impl Int
     Ok(Json(JsonResponse::ok(serde_json::json!({
         "cycles": metrics.cycles,
         "nodes_processed": metrics.nodes_processed,
+        "nodes_scanned": metrics.nodes_scanned,
         "edges_created": metrics.edges_created,
         "edges_pruned": metrics.edges_pruned,
         "backlog_size": metrics.backlog_size,
     }))))
 }
 
-async fn trigger_auto_link(State(state): State<AppState>) -> AppResult<impl IntoResponse> {
+#[derive(Deserialize)]
+struct PreviewQuery {
+    /// Evaluate every node instead of only the pending backlog.
+    full: Option<bool>,
+    limit: Option<usize>,
+}
+
+/// GET /auto-linker/preview — edges the next cycle would create, without writing them
+async fn preview_auto_link(
+    State(state): State<AppState>,
+    Query(query): Query<PreviewQuery>,
+) -> AppResult<impl IntoResponse> {
+    let proposals = {
+        let linker = state.auto_linker.read().unwrap();
+        if query.full.unwrap_or(false) {
+            linker.preview_full_cycle()?
+        } else {
+            linker.preview_cycle()?
+        }
+    };
+
+    let total = proposals.len();
+    let title = |id| {
+        state
+            .storage
+            .get_node(id)
+            .ok()
+            .flatten()
+            .map(|n| n.data.title)
+            .unwrap_or_default()
+    };
+    let edges: Vec<_> = proposals
+        .iter()
+        .take(query.limit.unwrap_or(total))
+        .map(|p| {
+            serde_json::json!({
+                "from_id": p.from.to_string(),
+                "to_id": p.to.to_string(),
+                "from_title": title(p.from),
+                "to_title": title(p.to),
+                "relation": p.relation.to_string(),
+                "weight": p.weight,
+                "rule": p.rule(),
+            })
+        })
+        .collect();
+
+    Ok(Json(JsonResponse::ok(serde_json::json!({
+        "total": total,
+        "edges": edges,
+    }))))
+}
+
+#[derive(Deserialize)]
+struct TriggerQuery {
+    /// Rescan every node instead of only those changed since the last cycle.
+    full: Option<bool>,
+}
+
+async fn trigger_auto_link(
+    State(state): State<AppState>,
+    Query(query): Query<TriggerQuery>,
+) -> AppResult<impl IntoResponse> {
     let mut linker = state.auto_linker.write().unwrap();
-    linker.run_cycle()?;
+    if query.full.unwrap_or(false) {
+        linker.run_full_cycle()?;
+    } else {
+        linker.run_cycle()?;
+    }
 
     Ok(Json(JsonResponse::ok(serde_json::json!({
         "message": "Auto-link cycle triggered successfully"
@@ -1077,6 +1568,11 @@ async fn trigger_auto_link(State(state): State<AppState>) -> AppResult<impl Into
 #[derive(Deserialize)]
 struct BriefingQuery {
     compact: Option<bool>,
+    /// RFC 3339 timestamp, or `last` for the agent's previous briefing.
+    since: Option<String>,
+    /// `markdown` (default), `compact`, or `json` for the briefing itself
+    /// as structured data.
+    format: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -1085,12 +1581,24 @@ struct BriefingSectionData {
     nodes: Vec<NodeData>,
 }
 
+#[derive(Serialize)]
+struct BriefingRelationData {
+    relation: String,
+    direction: &'static str,
+    node_id: String,
+    title: String,
+    weight: f32,
+}
+
 #[derive(Serialize)]
 struct BriefingData {
     agent_id: String,
     generated_at: String,
     nodes_consulted: usize,
     sections: Vec<BriefingSectionData>,
+    /// Keyed by node id; only present with `briefing.include_relations`.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    relations: HashMap<String, Vec<BriefingRelationData>>,
     rendered: String,
     cached: bool,
 }
@@ -1099,11 +1607,27 @@ async fn get_briefing(
     State(state): State<AppState>,
     Path(agent_id): Path<String>,
     Query(query): Query<BriefingQuery>,
-) -> AppResult<Json<JsonResponse<BriefingData>>> {
-    let compact = query.compact.unwrap_or(false);
+    headers: HeaderMap,
+) -> AppResult<Response> {
+    let format = match query.format.as_deref() {
+        None | Some("") if query.compact.unwrap_or(false) => BriefingFormat::Compact,
+        None | Some("") => BriefingFormat::Markdown,
+        Some(format) => format.parse()?,
+    };
 
-    let briefing = state.briefing_engine.generate(&agent_id)?;
-    let rendered = state.briefing_engine.render(&briefing, compact);
+    let since = match query.since.as_deref() {
+        None | Some("") => None,
+        Some("last") => state.briefing_engine.last_briefed(&agent_id),
+        Some(ts) => Some(
+            chrono::DateTime::parse_from_rfc3339(ts)
+                .map_err(|e| anyhow::anyhow!("Invalid since timestamp '{}': {}", ts, e))?
+                .with_timezone(&chrono::Utc),
+        ),
+    };
+    let briefing = match since {
+        Some(since) => state.briefing_engine.generate_diff(&agent_id, since)?,
+        None => state.briefing_engine.generate(&agent_id)?,
+    };
 
     let sections: Vec<BriefingSectionData> = briefing
         .sections
@@ -1122,10 +1646,13 @@ async fn get_briefing(
                         body: n.data.body.clone(),
                         tags: n.data.tags.clone(),
                         importance: n.importance,
+                        confidence: n.confidence,
                         source_agent: n.source.agent.clone(),
                         edge_count: outgoing.len() + incoming.len(),
                         access_count: n.access_count,
                         last_accessed_at: n.last_accessed_at.to_rfc3339(),
+                        expires_at: n.expires_at.map(|t| t.to_rfc3339()),
+                        deleted_at: n.deleted_at.map(|t| t.to_rfc3339()),
                     }
                 })
                 .collect();
@@ -1136,14 +1663,53 @@ async fn get_briefing(
         })
         .collect();
 
+    state.audit_read(
+        AuditAction::Briefing,
+        headers
+            .get("x-agent-id")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or(&agent_id),
+        uuid::Uuid::nil(),
+        serde_json::json!({
+            "agent_id": agent_id,
+            "nodes": sections
+                .iter()
+                .flat_map(|s| s.nodes.iter().map(|n| n.id.as_str()))
+                .collect::<Vec<_>>(),
+        }),
+    );
+
+    if format == BriefingFormat::Json {
+        return Ok(Json(JsonResponse::ok(serde_json::to_value(&briefing)?)).into_response());
+    }
+
+    let rendered = state.briefing_engine.render_as(&briefing, format);
     Ok(Json(JsonResponse::ok(BriefingData {
         agent_id: briefing.agent_id.clone(),
         generated_at: briefing.generated_at.to_rfc3339(),
         nodes_consulted: briefing.nodes_consulted,
         sections,
+        relations: briefing
+            .relations
+            .iter()
+            .map(|(id, relations)| {
+                let relations = relations
+                    .iter()
+                    .map(|r| BriefingRelationData {
+                        relation: r.relation.to_string(),
+                        direction: if r.outgoing { "outgoing" } else { "incoming" },
+                        node_id: r.node_id.to_string(),
+                        title: r.title.clone(),
+                        weight: r.weight,
+                    })
+                    .collect();
+                (id.to_string(), relations)
+            })
+            .collect(),
         rendered,
         cached: briefing.cached,
-    })))
+    }))
+    .into_response())
 }
 
 // ── Agent ↔ Prompt Bindings ────────────────────────────────────────────────
@@ -1246,6 +1812,8 @@ async fn bind_prompt(
         updated_at: chrono::Utc::now(),
     };
     state.storage.put_edge(&edge)?;
+    state.bump_graph_version();
+    state.hooks.notify_edge(&edge, MutationAction::Created);
 
     Ok(Json(JsonResponse::ok(serde_json::json!({
         "agent": name,
@@ -1282,9 +1850,13 @@ async fn unbind_prompt(
         .into());
     }
 
-    for edge in to_delete {
+    for edge in &to_delete {
         state.storage.delete_edge(edge.id)?;
     }
+    state.bump_graph_version();
+    for edge in to_delete {
+        state.hooks.notify_edge(edge, MutationAction::Deleted);
+    }
 
     Ok(Json(JsonResponse::ok(serde_json::json!({
         "agent": name,
@@ -1378,38 +1950,23 @@ struct EventStreamQuery {
 async fn event_stream(
     State(state): State<AppState>,
     Query(query): Query<EventStreamQuery>,
+    headers: HeaderMap,
 ) -> Sse<impl futures::stream::Stream<Item = std::result::Result<SseEvent, std::convert::Infallible>>>
 {
-    let mut rx = state.event_bus.subscribe();
+    // Subscribe before reading the version so no change slips between them.
+    let rx = state.event_bus.subscribe();
+    let graph_version = state
+        .graph_version
+        .load(std::sync::atomic::Ordering::Relaxed);
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok());
     let filter: Option<Vec<String>> = query
         .events
         .map(|e| e.split(',').map(|s| s.trim().to_string()).collect());
 
-    let stream = async_stream::stream! {
-        loop {
-            match rx.recv().await {
-                Ok(event) => {
-                    // Apply optional event-type filter
-                    if let Some(ref filter) = filter {
-                        if !filter.contains(&event.event_type) {
-                            continue;
-                        }
-                    }
-                    if let Ok(data) = serde_json::to_string(&event) {
-                        yield Ok(SseEvent::default().event(event.event_type).data(data));
-                    }
-                }
-                Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
-                    yield Ok(
-                        SseEvent::default()
-                            .event("warning")
-                            .data(format!("Dropped {} events (slow consumer)", n)),
-                    );
-                }
-                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
-            }
-        }
-    };
+    let stream = crate::observability::sse_stream(rx, graph_version, last_event_id, filter);
 
     Sse::new(stream).keep_alive(
         KeepAlive::default()
@@ -2,12 +2,14 @@
 /// Usage Tracking & Performance Observations (issue #24)
 ///
 /// Endpoints:
-///   GET  /agents/:name/active-variant              — score all variants, epsilon-greedy select
+///   GET  /agents/:name/active-variant              — score all variants, epsilon-greedy, Thompson or LinUCB select
 ///   GET  /agents/:name/variant-history             — timeline of swap/performance observations
 ///   POST /agents/:name/observe                     — record performance, update edge weight
 ///   GET  /prompts/:slug/performance                — aggregate stats across all contexts
+///   GET  /prompts/:slug/performance/by-context     — aggregate stats per task type, topic or energy
 ///   GET  /prompts/:slug/versions/:v/performance    — aggregate stats for a specific version
 use super::{find_by_title, AppResult, AppState, JsonResponse};
+use crate::observations::{stored_linucb_arm, ObservationContext, ObserveBody};
 use axum::{
     extract::{Path, Query, State},
     response::{IntoResponse, Json},
@@ -16,9 +18,8 @@ use cortex_core::{
     kinds::defaults as kinds,
     prompt::{selection as sel, PromptResolver, RollbackMonitor},
     relations::defaults as rels,
-    Edge, EdgeProvenance, Node, Source, Storage,
+    Node, NodeId, RedbStorage, Storage,
 };
-use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 // ── GET /agents/:name/active-variant ─────────────────────────────────────────
@@ -38,6 +39,11 @@ pub struct ActiveVariantQuery {
     /// Exploration rate for epsilon-greedy (0.0 = always exploit, 1.0 = always random)
     #[serde(default = "default_epsilon")]
     epsilon: f32,
+    /// `epsilon_greedy` (default), `thompson_sampling` or `linucb`
+    #[serde(default)]
+    strategy: sel::SelectionStrategy,
+    /// LinUCB exploration width; defaults to `prompt_selection.linucb_alpha`
+    alpha: Option<f64>,
 }
 
 fn default_half() -> f32 {
@@ -58,6 +64,14 @@ struct VariantScore {
     /// Normalised context fit score (0–1). Equal to `edge_weight` when no context_weights set.
     context_score: f32,
     total_score: f32,
+    /// Thompson draw blended with context fit; only set under `thompson_sampling`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sampled_score: Option<f32>,
+    /// Upper confidence bound for the current context; only set under `linucb`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ucb_score: Option<f32>,
+    #[serde(skip)]
+    fit: Option<f32>,
 }
 
 #[derive(Serialize)]
@@ -66,11 +80,23 @@ struct ActiveVariantResponse {
     selected: Option<VariantScore>,
     current_variant_id: Option<String>,
     swap_recommended: bool,
+    strategy: sel::SelectionStrategy,
     epsilon: f32,
+    /// Set when the selection fell inside a canary rollout.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    canary: Option<CanaryRouting>,
     signals: serde_json::Value,
     all_variants: Vec<VariantScore>,
 }
 
+#[derive(Serialize)]
+struct CanaryRouting {
+    deployment_node_id: String,
+    canary_id: String,
+    stable_id: String,
+    rollout_percent: u8,
+}
+
 pub async fn active_variant(
     State(state): State<AppState>,
     Path(name): Path<String>,
@@ -84,6 +110,12 @@ pub async fn active_variant(
         energy: q.energy,
     };
 
+    if let Some(alpha) = q.alpha {
+        if !(alpha.is_finite() && alpha >= 0.0) {
+            return Err(anyhow::anyhow!("alpha must be a non-negative number").into());
+        }
+    }
+
     let agent = find_by_title(&state.storage, &kinds::agent(), &name)?
         .ok_or_else(|| anyhow::anyhow!("Agent '{}' not found", name))?;
 
@@ -115,6 +147,9 @@ pub async fn active_variant(
                     edge_weight: e.weight,
                     context_score: fit.unwrap_or(e.weight),
                     total_score: total,
+                    sampled_score: None,
+                    ucb_score: None,
+                    fit,
                 }
             })
         })
@@ -126,33 +161,91 @@ pub async fn active_variant(
             selected: None,
             current_variant_id,
             swap_recommended: false,
+            strategy: q.strategy,
             epsilon: q.epsilon,
+            canary: None,
             signals: serde_json::to_value(&signals).unwrap_or_default(),
             all_variants: vec![],
         })));
     }
 
-    // Epsilon-greedy: determine selected id before sorting
+    // Determine selected id before sorting
     let epsilon = q.epsilon.clamp(0.0, 1.0);
     let mut rng = rand::thread_rng();
-    let selected_idx = if rng.gen::<f32>() < epsilon {
-        // Explore: uniform random choice
-        rng.gen_range(0..scores.len())
-    } else {
-        // Exploit: pick highest total_score
-        scores
-            .iter()
-            .enumerate()
-            .max_by(|a, b| {
-                a.1.total_score
-                    .partial_cmp(&b.1.total_score)
-                    .unwrap_or(std::cmp::Ordering::Equal)
+    let selected_idx = match q.strategy {
+        sel::SelectionStrategy::EpsilonGreedy => {
+            let totals: Vec<f32> = scores.iter().map(|s| s.total_score).collect();
+            sel::select_epsilon_greedy(&totals, epsilon, &mut rng)
+        }
+        sel::SelectionStrategy::ThompsonSampling => {
+            let arms: Vec<sel::VariantEvidence> = scores
+                .iter()
+                .map(|s| {
+                    variant_evidence(
+                        &state.storage,
+                        &name,
+                        &s.id,
+                        state.selection_config.observation_half_life_hours,
+                    )
+                })
+                .collect();
+            let fits: Vec<Option<f32>> = scores.iter().map(|s| s.fit).collect();
+            sel::select_thompson(&arms, &fits, &mut rng).map(|(idx, draws)| {
+                for (score, draw) in scores.iter_mut().zip(draws) {
+                    score.sampled_score = Some(draw);
+                }
+                idx
             })
-            .map(|(i, _)| i)
-            .unwrap_or(0)
-    };
+        }
+        sel::SelectionStrategy::LinUcb => {
+            // Arms are kept up to date by /observe; only their decay is applied here
+            let now = chrono::Utc::now();
+            let arms: Vec<sel::LinUcbArm> = scores
+                .iter()
+                .map(|s| {
+                    stored_linucb_arm(
+                        &agent,
+                        &s.id,
+                        state.selection_config.observation_half_life_hours,
+                        now,
+                    )
+                    .unwrap_or_default()
+                })
+                .collect();
+            let alpha = q.alpha.unwrap_or(state.selection_config.linucb_alpha);
+            sel::select_linucb(&arms, &signals, alpha).map(|(idx, bounds)| {
+                for (score, bound) in scores.iter_mut().zip(bounds) {
+                    score.ucb_score = Some(bound);
+                }
+                idx
+            })
+        }
+    }
+    .unwrap_or(0);
     // Capture selected before sort invalidates the index
-    let selected_variant = scores[selected_idx].clone();
+    let mut selected_variant = scores[selected_idx].clone();
+
+    // A canary rollout decides between a new version and the one it replaces,
+    // whichever of the two was picked.
+    let monitor = RollbackMonitor::new(
+        state.storage.clone(),
+        state.rollback_config.read().unwrap().clone(),
+    );
+    let mut canary = None;
+    if let Ok(selected_uuid) = selected_variant.id.parse::<uuid::Uuid>() {
+        if let Some(rollout) = monitor.active_canary(selected_uuid)? {
+            let routed = rollout.route(&mut rng).to_string();
+            if let Some(s) = scores.iter().find(|s| s.id == routed) {
+                selected_variant = s.clone();
+            }
+            canary = Some(CanaryRouting {
+                deployment_node_id: rollout.deployment_node_id.to_string(),
+                canary_id: rollout.canary_node_id.to_string(),
+                stable_id: rollout.stable_node_id.to_string(),
+                rollout_percent: rollout.rollout_percent,
+            });
+        }
+    }
 
     // Sort all_variants by total_score desc for presentation
     scores.sort_by(|a, b| {
@@ -170,13 +263,99 @@ pub async fn active_variant(
         agent: name,
         swap_recommended,
         current_variant_id,
+        strategy: q.strategy,
         epsilon,
+        canary,
         signals: serde_json::to_value(&signals).unwrap_or_default(),
         selected: Some(selected_variant),
         all_variants: scores,
     })))
 }
 
+/// This agent's performance observations of one variant, with their fields.
+fn performance_observations(
+    storage: &cortex_core::RedbStorage,
+    agent: &str,
+    variant_id: &str,
+) -> Vec<(Node, ExtractedObs)> {
+    let Ok(variant_uuid) = variant_id.parse::<uuid::Uuid>() else {
+        return Vec::new();
+    };
+    let informed_rel = rels::informed_by();
+    storage
+        .edges_to(variant_uuid)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|e| e.relation == informed_rel)
+        .filter_map(|e| storage.get_node(e.from).ok().flatten())
+        .filter(|n| n.source.agent == agent)
+        .filter_map(|n| {
+            let ex = extract_obs(&n);
+            (ex.obs_type == "performance").then_some((n, ex))
+        })
+        .collect()
+}
+
+/// Beta evidence for one variant from this agent's performance observations of it.
+fn variant_evidence(
+    storage: &cortex_core::RedbStorage,
+    agent: &str,
+    variant_id: &str,
+    half_life_hours: f64,
+) -> sel::VariantEvidence {
+    let now = chrono::Utc::now();
+    let scores = performance_observations(storage, agent, variant_id)
+        .into_iter()
+        .map(|(n, ex)| {
+            (
+                ex.score,
+                sel::decay_weight(now - n.created_at, half_life_hours),
+            )
+        });
+    sel::VariantEvidence::from_weighted_scores(scores)
+}
+
+/// LinUCB state for one variant, folded from this agent's performance
+/// observations of it in the contexts they were made in. Seeds the stored
+/// arm that `/observe` keeps up to date from then on.
+pub(crate) fn variant_linucb_arm(
+    storage: &cortex_core::RedbStorage,
+    agent: &str,
+    variant_id: &str,
+    half_life_hours: f64,
+) -> sel::LinUcbArm {
+    let now = chrono::Utc::now();
+    let mut arm = sel::LinUcbArm::new();
+    for (n, ex) in performance_observations(storage, agent, variant_id) {
+        let weight = sel::decay_weight(now - n.created_at, half_life_hours);
+        arm.observe(&observation_signals(&n, &ex), ex.score, weight);
+    }
+    arm
+}
+
+/// The context an observation was made in: its recorded `context_signals`,
+/// else the task type from its body and its sentiment score.
+fn observation_signals(n: &Node, ex: &ExtractedObs) -> sel::ContextSignals {
+    if let Some(signals) = n
+        .data
+        .metadata
+        .get("context_signals")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+    {
+        return signals;
+    }
+    let task_type = ex
+        .context
+        .as_ref()
+        .and_then(|c| c.get("task_type"))
+        .and_then(|v| v.as_str());
+    sel::ContextSignals {
+        sentiment: ex.sentiment as f32,
+        task_type: task_type.unwrap_or("casual").to_string(),
+        ..Default::default()
+    }
+}
+
 // ── GET /agents/:name/variant-history ────────────────────────────────────────
 
 #[derive(Deserialize)]
@@ -209,7 +388,7 @@ pub async fn variant_history(
         .filter_map(|e| state.storage.get_node(e.to).ok().flatten())
         .collect();
 
-    raw_nodes.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    raw_nodes.sort_by_key(|n| std::cmp::Reverse(n.created_at));
     raw_nodes.truncate(q.limit);
 
     let observations: Vec<serde_json::Value> = raw_nodes
@@ -253,85 +432,6 @@ pub async fn variant_history(
 
 // ── POST /agents/:name/observe ────────────────────────────────────────────────
 
-/// Rich body JSON stored inside the observation node (issue #24 schema).
-#[derive(Serialize, Deserialize, Default)]
-struct ObsBodyJson {
-    agent: String,
-    prompt_slug: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    prompt_version: Option<u32>,
-    observation_type: String,
-    metrics: ObsMetrics,
-    context: ObsContext,
-}
-
-#[derive(Serialize, Deserialize, Default)]
-struct ObsMetrics {
-    correction_count: u32,
-    sentiment_score: f32,
-    task_completed: bool,
-    task_outcome: String,
-    observation_score: f32,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    token_cost: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    response_time_ms: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    user_satisfaction: Option<f32>,
-}
-
-#[derive(Serialize, Deserialize, Default)]
-struct ObsContext {
-    task_type: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    topic: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    session_length: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    message_count: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    correction_rate: Option<f32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    topic_shift: Option<f32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    energy: Option<f32>,
-}
-
-#[derive(Deserialize)]
-pub struct ObserveBody {
-    /// UUID of the prompt variant node
-    pub variant_id: String,
-    /// Slug/title of the prompt variant (for display)
-    pub variant_slug: String,
-    /// Observed sentiment score: 0.0–1.0
-    #[serde(default = "default_half")]
-    pub sentiment_score: f32,
-    /// Number of corrections the user made
-    #[serde(default)]
-    pub correction_count: u32,
-    /// Outcome: success | partial | failure | unknown
-    #[serde(default = "default_unknown")]
-    pub task_outcome: String,
-    /// Token cost of the interaction
-    pub token_cost: Option<u32>,
-    /// Response time in milliseconds
-    pub response_time_ms: Option<u32>,
-    /// Explicit user satisfaction score (0.0–1.0), if available
-    pub user_satisfaction: Option<f32>,
-    /// The context signals active during this interaction
-    pub context_signals: Option<sel::ContextSignals>,
-    /// Topic/domain of the interaction (e.g. "cortex-development")
-    pub topic: Option<String>,
-    /// Session length in minutes since session start
-    pub session_length: Option<u32>,
-    /// Number of messages in the session
-    pub message_count: Option<u32>,
-}
-
-fn default_unknown() -> String {
-    "unknown".to_string()
-}
-
 pub async fn record_observation(
     State(state): State<AppState>,
     Path(name): Path<String>,
@@ -345,304 +445,11 @@ pub async fn record_observation(
         .parse()
         .map_err(|_| anyhow::anyhow!("Invalid variant_id UUID"))?;
 
-    // Normalise inputs: clamp scores to valid range, canonicalise task_outcome
-    let sentiment_score = body.sentiment_score.clamp(0.0, 1.0);
-    let user_satisfaction = body.user_satisfaction.map(|v| v.clamp(0.0, 1.0));
-    let task_outcome = match body.task_outcome.as_str() {
-        "success" | "partial" | "failure" | "unknown" => body.task_outcome.clone(),
-        _ => "unknown".to_string(),
-    };
-
-    // Compute observation score
-    let obs_score = sel::observation_score(sentiment_score, body.correction_count, &task_outcome);
-
-    // Try to look up the prompt version from the variant node's body JSON
-    let prompt_version: Option<u32> = state
-        .storage
-        .get_node(variant_uuid)
-        .ok()
-        .flatten()
-        .and_then(|n| serde_json::from_str::<serde_json::Value>(&n.data.body).ok())
-        .and_then(|v| v.get("version").and_then(|v| v.as_u64()))
-        .map(|v| v as u32);
-
-    let task_type = body
-        .context_signals
-        .as_ref()
-        .map(|s| s.task_type.clone())
-        .unwrap_or_else(|| "unknown".to_string());
-
-    // Build the rich body JSON (issue #24 schema)
-    let obs_body = ObsBodyJson {
-        agent: name.clone(),
-        prompt_slug: body.variant_slug.clone(),
-        prompt_version,
-        observation_type: "performance".to_string(),
-        metrics: ObsMetrics {
-            correction_count: body.correction_count,
-            sentiment_score,
-            task_completed: task_outcome == "success",
-            task_outcome: task_outcome.clone(),
-            observation_score: obs_score,
-            token_cost: body.token_cost,
-            response_time_ms: body.response_time_ms,
-            user_satisfaction,
-        },
-        context: ObsContext {
-            task_type,
-            topic: body.topic.clone(),
-            session_length: body.session_length,
-            message_count: body.message_count,
-            correction_rate: body.context_signals.as_ref().map(|s| s.correction_rate),
-            topic_shift: body.context_signals.as_ref().map(|s| s.topic_shift),
-            energy: body.context_signals.as_ref().map(|s| s.energy),
-        },
-    };
-    let body_json_str = serde_json::to_string(&obs_body).unwrap_or_default();
-
-    // Title follows issue #24 schema: obs:<agent>:<timestamp>
-    let now = chrono::Utc::now();
-    let obs_title = format!("obs:{}:{}", name, now.to_rfc3339());
-
-    let mut obs_node = Node::new(
-        kinds::observation(),
-        obs_title,
-        body_json_str,
-        Source {
-            agent: name.clone(),
-            session: None,
-            channel: None,
-        },
-        obs_score,
-    );
-
-    // Keep backward-compat metadata entries so existing variant-history still works
-    obs_node.data.metadata.insert(
-        "observation_type".into(),
-        serde_json::Value::String("performance".into()),
-    );
-    obs_node.data.metadata.insert(
-        "variant_id".into(),
-        serde_json::Value::String(body.variant_id.clone()),
-    );
-    obs_node.data.metadata.insert(
-        "variant_slug".into(),
-        serde_json::Value::String(body.variant_slug.clone()),
-    );
-    obs_node
-        .data
-        .metadata
-        .insert("sentiment_score".into(), serde_json::json!(sentiment_score));
-    obs_node.data.metadata.insert(
-        "correction_count".into(),
-        serde_json::json!(body.correction_count),
-    );
-    obs_node.data.metadata.insert(
-        "task_outcome".into(),
-        serde_json::Value::String(task_outcome.clone()),
-    );
-    obs_node
-        .data
-        .metadata
-        .insert("observation_score".into(), serde_json::json!(obs_score));
-    if let Some(tc) = body.token_cost {
-        obs_node
-            .data
-            .metadata
-            .insert("token_cost".into(), serde_json::json!(tc));
-    }
-    if let Some(ref signals) = body.context_signals {
-        obs_node.data.metadata.insert(
-            "context_signals".into(),
-            serde_json::to_value(signals).unwrap_or_default(),
-        );
-    }
-
-    state.storage.put_node(&obs_node)?;
-
-    // Edges: agent --[performed]--> obs (backward compat)
-    //        obs --[informed_by]--> variant (backward compat for performance query)
-    //        obs --[observed_with]--> variant (issue #24 naming)
-    //        obs --[observed_by]--> agent (issue #24 naming)
-    let new_edges = vec![
-        Edge {
-            id: uuid::Uuid::now_v7(),
-            from: agent.id,
-            to: obs_node.id,
-            relation: rels::performed(),
-            weight: 1.0,
-            provenance: EdgeProvenance::Manual {
-                created_by: name.clone(),
-            },
-            created_at: now,
-            updated_at: now,
-        },
-        Edge {
-            id: uuid::Uuid::now_v7(),
-            from: obs_node.id,
-            to: variant_uuid,
-            relation: rels::informed_by(),
-            weight: 1.0,
-            provenance: EdgeProvenance::Manual {
-                created_by: name.clone(),
-            },
-            created_at: now,
-            updated_at: now,
-        },
-        Edge {
-            id: uuid::Uuid::now_v7(),
-            from: obs_node.id,
-            to: variant_uuid,
-            relation: rels::observed_with(),
-            weight: obs_score,
-            provenance: EdgeProvenance::Manual {
-                created_by: name.clone(),
-            },
-            created_at: now,
-            updated_at: now,
-        },
-        Edge {
-            id: uuid::Uuid::now_v7(),
-            from: obs_node.id,
-            to: agent.id,
-            relation: rels::observed_by(),
-            weight: 1.0,
-            provenance: EdgeProvenance::Manual {
-                created_by: name.clone(),
-            },
-            created_at: now,
-            updated_at: now,
-        },
-    ];
-    state.storage.put_edges_batch(&new_edges)?;
+    let recorded =
+        ObservationContext::from(&state).record(&state.storage, &agent, variant_uuid, &body)?;
+    state.bump_graph_version();
 
-    // Atomically update the uses edge weight (single write transaction)
-    let uses_rel = rels::uses();
-    let (old_weight, new_weight) =
-        state
-            .storage
-            .update_edge_weight_atomic(agent.id, variant_uuid, &uses_rel, |w| {
-                sel::update_edge_weight(w, obs_score)
-            })?;
-
-    // Determine if this is a variant swap
-    let current_active = agent
-        .data
-        .metadata
-        .get("active_variant_id")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-    let is_swap = current_active.as_deref() != Some(&body.variant_id);
-
-    // Record a swap observation if the active variant changed.
-    if is_swap {
-        if let Some(ref old_id) = current_active {
-            if let Ok(old_uuid) = old_id.parse::<uuid::Uuid>() {
-                let old_slug = state
-                    .storage
-                    .get_node(old_uuid)
-                    .ok()
-                    .flatten()
-                    .map(|n| n.data.title.clone())
-                    .unwrap_or_default();
-
-                let swap_body_json = serde_json::to_string(&serde_json::json!({
-                    "agent": name,
-                    "observation_type": "swap",
-                    "old_variant_id": old_id,
-                    "old_variant_slug": old_slug,
-                    "new_variant_id": body.variant_id,
-                    "new_variant_slug": body.variant_slug,
-                    "trigger_signal": task_outcome,
-                }))
-                .unwrap_or_default();
-
-                let mut swap_obs = Node::new(
-                    kinds::observation(),
-                    format!("obs:{}:{}", name, now.to_rfc3339()),
-                    swap_body_json,
-                    Source {
-                        agent: name.clone(),
-                        session: None,
-                        channel: None,
-                    },
-                    0.5,
-                );
-                swap_obs.data.metadata.insert(
-                    "observation_type".into(),
-                    serde_json::Value::String("swap".into()),
-                );
-                swap_obs.data.metadata.insert(
-                    "old_variant_id".into(),
-                    serde_json::Value::String(old_id.clone()),
-                );
-                swap_obs.data.metadata.insert(
-                    "old_variant_slug".into(),
-                    serde_json::Value::String(old_slug),
-                );
-                swap_obs.data.metadata.insert(
-                    "new_variant_id".into(),
-                    serde_json::Value::String(body.variant_id.clone()),
-                );
-                swap_obs.data.metadata.insert(
-                    "new_variant_slug".into(),
-                    serde_json::Value::String(body.variant_slug.clone()),
-                );
-                swap_obs.data.metadata.insert(
-                    "trigger_signal".into(),
-                    serde_json::Value::String(task_outcome.clone()),
-                );
-                state.storage.put_node(&swap_obs)?;
-
-                state.storage.put_edge(&Edge {
-                    id: uuid::Uuid::now_v7(),
-                    from: agent.id,
-                    to: swap_obs.id,
-                    relation: rels::performed(),
-                    weight: 1.0,
-                    provenance: EdgeProvenance::Manual {
-                        created_by: name.clone(),
-                    },
-                    created_at: now,
-                    updated_at: now,
-                })?;
-            } else {
-                log::warn!(
-                    "agent '{}' has non-UUID active_variant_id '{}'; skipping swap observation",
-                    name,
-                    old_id
-                );
-            }
-        }
-    }
-
-    // Update agent node metadata with new active_variant_id
-    let mut updated_agent = agent.clone();
-    updated_agent.data.metadata.insert(
-        "active_variant_id".into(),
-        serde_json::Value::String(body.variant_id.clone()),
-    );
-    updated_agent.updated_at = now;
-    state.storage.put_node(&updated_agent)?;
-
-    // ── Rollback monitor check (issue #23) ─────────────────────────────────
-    // Normalise correction_count to a rate (0–1) assuming 5 corrections = rate 1.0.
-    let correction_rate = (body.correction_count as f32 / 5.0).min(1.0);
-    let rollback_result =
-        RollbackMonitor::new(state.storage.clone(), state.rollback_config.clone())
-            .process_observation(
-                obs_node.id,
-                variant_uuid,
-                correction_rate,
-                sentiment_score,
-                obs_score,
-            )
-            .unwrap_or_else(|e| {
-                log::warn!("rollback monitor error for variant {}: {}", variant_uuid, e);
-                None
-            });
-
-    let rollback_info = rollback_result.as_ref().map(|r| {
+    let rollback_info = recorded.rollback.as_ref().map(|r| {
         serde_json::json!({
             "triggered": true,
             "rollback_node_id": r.rollback_node_id.to_string(),
@@ -654,43 +461,29 @@ pub async fn record_observation(
         })
     });
 
-    // Fire rollback notification webhooks (issue #23 — notify_on_rollback)
-    if let Some(ref rb) = rollback_result {
-        for wh in &state.webhooks {
-            if wh.events.iter().any(|e| e == "rollback" || e == "*") {
-                let payload = serde_json::json!({
-                    "event": "prompt.rollback",
-                    "agent": name,
-                    "from_version": rb.from_version,
-                    "to_version": rb.to_version,
-                    "trigger": rb.trigger.kind_str(),
-                    "cooldown_hours": rb.cooldown_hours,
-                    "is_quarantined": rb.is_quarantined,
-                    "rollback_node_id": rb.rollback_node_id.to_string(),
-                });
-                let url = wh.url.clone();
-                // Fire-and-forget in background to avoid blocking the response
-                tokio::spawn(async move {
-                    let client = reqwest::Client::new();
-                    if let Err(e) = client.post(&url).json(&payload).send().await {
-                        log::warn!("rollback webhook to {} failed: {}", url, e);
-                    }
-                });
-            }
-        }
-    }
-
     Ok(Json(JsonResponse::ok(serde_json::json!({
-        "observation_id": obs_node.id.to_string(),
+        "observation_id": recorded.observation_id.to_string(),
         "variant_id": body.variant_id,
         "variant_slug": body.variant_slug,
-        "observation_score": obs_score,
-        "old_edge_weight": old_weight,
-        "new_edge_weight": new_weight,
+        "observation_score": recorded.observation_score,
+        "old_edge_weight": recorded.old_edge_weight,
+        "new_edge_weight": recorded.new_edge_weight,
         "rollback": rollback_info,
     }))))
 }
 
+impl From<&AppState> for ObservationContext {
+    fn from(state: &AppState) -> Self {
+        Self {
+            selection_config: state.selection_config.clone(),
+            rollback_config: state.rollback_config.clone(),
+            metrics: state.metrics.clone(),
+            webhooks: state.webhooks.clone(),
+            nats_publisher: state.nats_publisher.clone(),
+        }
+    }
+}
+
 // ── Shared aggregation helpers ────────────────────────────────────────────────
 
 /// All fields extracted from one observation node — body JSON parsed exactly once,
@@ -823,63 +616,76 @@ fn matches_context_filter(obs: &Node, key: &str, value: &str) -> bool {
         .unwrap_or(false)
 }
 
-fn aggregate_observations(obs_list: &[Node]) -> PerfAggregates {
+/// Aggregate observations, weighting each by its age under `half_life_hours`
+/// (see [`sel::decay_weight`]) so recent behaviour outweighs stale history.
+/// `total_count` and `task_outcomes` stay raw counts; a half-life of 0 gives plain means.
+fn aggregate_observations(obs_list: &[Node], half_life_hours: f64) -> PerfAggregates {
     let total_count = obs_list.len();
+    let now = chrono::Utc::now();
+    let mut weight_sum = 0.0f64;
     let mut sum_score = 0.0f64;
     let mut sum_sentiment = 0.0f64;
-    let mut sum_corrections = 0u64;
-    let mut sum_token_cost = 0u64;
-    let mut token_cost_count = 0usize;
-    let mut sum_response_time = 0u64;
-    let mut response_time_count = 0usize;
+    let mut sum_corrections = 0.0f64;
+    let mut sum_token_cost = 0.0f64;
+    let mut token_cost_weight = 0.0f64;
+    let mut sum_response_time = 0.0f64;
+    let mut response_time_weight = 0.0f64;
     // Pre-allocate for the four known outcomes (success/partial/failure/unknown)
     let mut task_outcomes: std::collections::HashMap<String, u64> =
         std::collections::HashMap::with_capacity(4);
 
     for n in obs_list {
         let ex = extract_obs(n);
-        sum_score += ex.score;
-        sum_sentiment += ex.sentiment;
-        sum_corrections += ex.corrections;
+        let w = sel::decay_weight(now - n.created_at, half_life_hours);
+        weight_sum += w;
+        sum_score += w * ex.score;
+        sum_sentiment += w * ex.sentiment;
+        sum_corrections += w * ex.corrections as f64;
         *task_outcomes.entry(ex.outcome).or_insert(0) += 1;
         if let Some(tc) = ex.token_cost {
-            sum_token_cost += tc;
-            token_cost_count += 1;
+            sum_token_cost += w * tc as f64;
+            token_cost_weight += w;
         }
         if let Some(rt) = ex.response_time_ms {
-            sum_response_time += rt;
-            response_time_count += 1;
+            sum_response_time += w * rt as f64;
+            response_time_weight += w;
         }
     }
 
-    let n = total_count as f64;
+    let mean = |sum: f64, weight: f64| (weight > 0.0).then(|| sum / weight);
     PerfAggregates {
         total_count,
-        avg_score: if total_count > 0 { sum_score / n } else { 0.0 },
-        avg_sentiment: if total_count > 0 {
-            sum_sentiment / n
-        } else {
-            0.0
-        },
-        avg_corrections: if total_count > 0 {
-            sum_corrections as f64 / n
-        } else {
-            0.0
-        },
-        avg_token_cost: if token_cost_count > 0 {
-            Some(sum_token_cost as f64 / token_cost_count as f64)
-        } else {
-            None
-        },
-        avg_response_time_ms: if response_time_count > 0 {
-            Some(sum_response_time as f64 / response_time_count as f64)
-        } else {
-            None
-        },
+        avg_score: mean(sum_score, weight_sum).unwrap_or(0.0),
+        avg_sentiment: mean(sum_sentiment, weight_sum).unwrap_or(0.0),
+        avg_corrections: mean(sum_corrections, weight_sum).unwrap_or(0.0),
+        avg_token_cost: mean(sum_token_cost, token_cost_weight),
+        avg_response_time_ms: mean(sum_response_time, response_time_weight),
         task_outcomes,
     }
 }
 
+/// Performance observations linked to `target` (a prompt or prompt version)
+/// via obs --[informed_by]--> target.
+fn informed_performance_observations(
+    storage: &RedbStorage,
+    target: NodeId,
+) -> anyhow::Result<Vec<Node>> {
+    let informed_rel = rels::informed_by();
+    Ok(storage
+        .edges_to(target)?
+        .into_iter()
+        .filter(|e| e.relation == informed_rel)
+        .filter_map(|e| storage.get_node(e.from).ok().flatten())
+        .filter(|n| {
+            n.data
+                .metadata
+                .get("observation_type")
+                .and_then(|v| v.as_str())
+                == Some("performance")
+        })
+        .collect())
+}
+
 fn build_obs_detail(n: &Node) -> serde_json::Value {
     // Single parse via extract_obs — no double-parse for metrics vs context
     let ex = extract_obs(n);
@@ -924,21 +730,8 @@ pub async fn prompt_performance(
 
     let context_filter = parse_context_filter(q.context.as_deref());
 
-    // Collect all performance observations linked via obs --[informed_by]--> prompt
-    let informed_rel = rels::informed_by();
-    let mut all_obs: Vec<Node> = state
-        .storage
-        .edges_to(prompt.id)?
+    let mut all_obs: Vec<Node> = informed_performance_observations(&state.storage, prompt.id)?
         .into_iter()
-        .filter(|e| e.relation == informed_rel)
-        .filter_map(|e| state.storage.get_node(e.from).ok().flatten())
-        .filter(|n| {
-            n.data
-                .metadata
-                .get("observation_type")
-                .and_then(|v| v.as_str())
-                == Some("performance")
-        })
         .filter(|n| {
             if let Some((ref key, ref val)) = context_filter {
                 matches_context_filter(n, key, val)
@@ -948,9 +741,10 @@ pub async fn prompt_performance(
         })
         .collect();
 
-    all_obs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    all_obs.sort_by_key(|o| std::cmp::Reverse(o.created_at));
 
-    let agg = aggregate_observations(&all_obs);
+    let half_life_hours = state.selection_config.observation_half_life_hours;
+    let agg = aggregate_observations(&all_obs, half_life_hours);
 
     all_obs.truncate(q.limit);
     let observations: Vec<serde_json::Value> = all_obs.iter().map(build_obs_detail).collect();
@@ -966,11 +760,118 @@ pub async fn prompt_performance(
         "avg_token_cost": agg.avg_token_cost,
         "avg_response_time_ms": agg.avg_response_time_ms,
         "task_outcomes": agg.task_outcomes,
+        "observation_half_life_hours": half_life_hours,
         "observations_shown": observations.len(),
         "observations": observations,
     }))))
 }
 
+// ── GET /prompts/:slug/performance/by-context ────────────────────────────────
+
+/// Context field to group observations by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextDimension {
+    #[default]
+    TaskType,
+    Topic,
+    /// `energy` bucketed into low (< 0.33), medium (< 0.67) and high.
+    Energy,
+}
+
+impl ContextDimension {
+    /// The group `obs` falls in; `unknown` when its context lacks the field.
+    fn group_of(self, obs: &ExtractedObs) -> String {
+        let field = |key: &str| obs.context.as_ref().and_then(|c| c.get(key));
+        let group = match self {
+            Self::TaskType => field("task_type")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            Self::Topic => field("topic").and_then(|v| v.as_str()).map(str::to_string),
+            Self::Energy => field("energy").and_then(|v| v.as_f64()).map(|e| {
+                match e {
+                    e if e < 0.33 => "low",
+                    e if e < 0.67 => "medium",
+                    _ => "high",
+                }
+                .to_string()
+            }),
+        };
+        group.unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ByContextQuery {
+    #[serde(default)]
+    dimension: ContextDimension,
+}
+
+/// Aggregates for the observations sharing one value of a context dimension.
+#[derive(Serialize)]
+struct ContextGroup {
+    value: String,
+    observation_count: usize,
+    avg_score: f64,
+    avg_sentiment: f64,
+    avg_correction_count: f64,
+    avg_token_cost: Option<f64>,
+    avg_response_time_ms: Option<f64>,
+    task_outcomes: std::collections::HashMap<String, u64>,
+}
+
+/// Split `obs_list` by `dimension` and aggregate each group, ordered by value.
+fn performance_by_context(
+    obs_list: Vec<Node>,
+    dimension: ContextDimension,
+    half_life_hours: f64,
+) -> Vec<ContextGroup> {
+    let mut groups: std::collections::BTreeMap<String, Vec<Node>> = Default::default();
+    for n in obs_list {
+        let value = dimension.group_of(&extract_obs(&n));
+        groups.entry(value).or_default().push(n);
+    }
+    groups
+        .into_iter()
+        .map(|(value, obs)| {
+            let agg = aggregate_observations(&obs, half_life_hours);
+            ContextGroup {
+                value,
+                observation_count: agg.total_count,
+                avg_score: agg.avg_score,
+                avg_sentiment: agg.avg_sentiment,
+                avg_correction_count: agg.avg_corrections,
+                avg_token_cost: agg.avg_token_cost,
+                avg_response_time_ms: agg.avg_response_time_ms,
+                task_outcomes: agg.task_outcomes,
+            }
+        })
+        .collect()
+}
+
+pub async fn prompt_performance_by_context(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Query(q): Query<ByContextQuery>,
+) -> AppResult<impl IntoResponse> {
+    let prompt = find_by_title(&state.storage, &kinds::prompt(), &slug)?
+        .ok_or_else(|| anyhow::anyhow!("Prompt '{}' not found", slug))?;
+
+    let all_obs = informed_performance_observations(&state.storage, prompt.id)?;
+    let observation_count = all_obs.len();
+    let half_life_hours = state.selection_config.observation_half_life_hours;
+    let groups = performance_by_context(all_obs, q.dimension, half_life_hours);
+
+    Ok(Json(JsonResponse::ok(serde_json::json!({
+        "slug": slug,
+        "prompt_id": prompt.id.to_string(),
+        "dimension": q.dimension,
+        "observation_count": observation_count,
+        "observation_half_life_hours": half_life_hours,
+        "groups": groups,
+    }))))
+}
+
 // ── GET /prompts/:slug/versions/:version/performance ─────────────────────────
 
 #[derive(Deserialize)]
@@ -997,33 +898,22 @@ pub async fn version_performance(
 
     let context_filter = parse_context_filter(q.context.as_deref());
 
-    // Collect all performance observations linked via obs --[informed_by]--> this version node
-    let informed_rel = rels::informed_by();
-    let mut all_obs: Vec<Node> = state
-        .storage
-        .edges_to(version_node.id)?
-        .into_iter()
-        .filter(|e| e.relation == informed_rel)
-        .filter_map(|e| state.storage.get_node(e.from).ok().flatten())
-        .filter(|n| {
-            n.data
-                .metadata
-                .get("observation_type")
-                .and_then(|v| v.as_str())
-                == Some("performance")
-        })
-        .filter(|n| {
-            if let Some((ref key, ref val)) = context_filter {
-                matches_context_filter(n, key, val)
-            } else {
-                true
-            }
-        })
-        .collect();
+    let mut all_obs: Vec<Node> =
+        informed_performance_observations(&state.storage, version_node.id)?
+            .into_iter()
+            .filter(|n| {
+                if let Some((ref key, ref val)) = context_filter {
+                    matches_context_filter(n, key, val)
+                } else {
+                    true
+                }
+            })
+            .collect();
 
-    all_obs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    all_obs.sort_by_key(|o| std::cmp::Reverse(o.created_at));
 
-    let agg = aggregate_observations(&all_obs);
+    let half_life_hours = state.selection_config.observation_half_life_hours;
+    let agg = aggregate_observations(&all_obs, half_life_hours);
 
     all_obs.truncate(q.limit);
     let observations: Vec<serde_json::Value> = all_obs.iter().map(build_obs_detail).collect();
@@ -1041,6 +931,7 @@ pub async fn version_performance(
         "avg_token_cost": agg.avg_token_cost,
         "avg_response_time_ms": agg.avg_response_time_ms,
         "task_outcomes": agg.task_outcomes,
+        "observation_half_life_hours": half_life_hours,
         "observations_shown": observations.len(),
         "observations": observations,
     }))))
@@ -1202,7 +1093,7 @@ mod tests {
 
     #[test]
     fn aggregate_observations_empty() {
-        let agg = aggregate_observations(&[]);
+        let agg = aggregate_observations(&[], 0.0);
         assert_eq!(agg.total_count, 0);
         assert!((agg.avg_score - 0.0).abs() < 1e-9);
     }
@@ -1222,7 +1113,7 @@ mod tests {
             }
         });
         let n = make_obs(&body.to_string());
-        let agg = aggregate_observations(&[n]);
+        let agg = aggregate_observations(&[n], 0.0);
         assert_eq!(agg.total_count, 1);
         assert!((agg.avg_score - 0.8).abs() < 1e-9);
         assert!((agg.avg_sentiment - 0.6).abs() < 1e-9);
@@ -1242,7 +1133,7 @@ mod tests {
             make_obs(&body.to_string())
         };
         let nodes = vec![mk(0.4), mk(0.6), mk(0.8)];
-        let agg = aggregate_observations(&nodes);
+        let agg = aggregate_observations(&nodes, 0.0);
         assert_eq!(agg.total_count, 3);
         assert!((agg.avg_score - (0.4 + 0.6 + 0.8) / 3.0).abs() < 1e-9);
     }
@@ -1259,7 +1150,7 @@ mod tests {
             "metrics": { "observation_score": 0.5, "task_outcome": "success" }
         });
         let nodes = vec![make_obs(&b1.to_string()), make_obs(&b2.to_string())];
-        let agg = aggregate_observations(&nodes);
+        let agg = aggregate_observations(&nodes, 0.0);
         assert_eq!(agg.avg_token_cost, Some(100.0));
     }
 
@@ -1277,11 +1168,109 @@ mod tests {
             mk_outcome("success"),
             mk_outcome("failure"),
         ];
-        let agg = aggregate_observations(&nodes);
+        let agg = aggregate_observations(&nodes, 0.0);
         assert_eq!(agg.task_outcomes.get("success"), Some(&2u64));
         assert_eq!(agg.task_outcomes.get("failure"), Some(&1u64));
     }
 
+    #[test]
+    fn aggregate_observations_decays_old_scores() {
+        let mk = |score: f64, days_ago: i64| {
+            let body = serde_json::json!({
+                "observation_type": "performance",
+                "metrics": { "observation_score": score, "task_outcome": "success" }
+            });
+            let mut n = make_obs(&body.to_string());
+            n.created_at = chrono::Utc::now() - chrono::Duration::days(days_ago);
+            n
+        };
+        // Six months of good history, then a recent regression
+        let mut nodes: Vec<Node> = (0..6).map(|_| mk(0.9, 180)).collect();
+        nodes.extend((0..3).map(|_| mk(0.2, 0)));
+
+        let naive = aggregate_observations(&nodes, 0.0);
+        let decayed = aggregate_observations(&nodes, 168.0);
+        assert_eq!(decayed.total_count, 9);
+        assert!(
+            (naive.avg_score - (6.0 * 0.9 + 3.0 * 0.2) / 9.0).abs() < 1e-9,
+            "naive mean {}",
+            naive.avg_score
+        );
+        assert!(
+            decayed.avg_score < naive.avg_score,
+            "decayed {} should drop below naive {}",
+            decayed.avg_score,
+            naive.avg_score
+        );
+        assert!(
+            (decayed.avg_score - 0.2).abs() < 0.01,
+            "got {}",
+            decayed.avg_score
+        );
+    }
+
+    // ── performance_by_context ──────────────────────────────────────────────
+
+    fn obs_in(context: serde_json::Value, score: f64, outcome: &str) -> Node {
+        let body = serde_json::json!({
+            "observation_type": "performance",
+            "metrics": {
+                "observation_score": score,
+                "sentiment_score": 0.5,
+                "correction_count": 0,
+                "task_outcome": outcome,
+            },
+            "context": context,
+        });
+        make_obs(&body.to_string())
+    }
+
+    #[test]
+    fn performance_by_context_groups_by_task_type() {
+        let task = |t: &str| serde_json::json!({ "task_type": t });
+        let obs = vec![
+            obs_in(task("coding"), 0.9, "success"),
+            obs_in(task("planning"), 0.4, "partial"),
+            obs_in(task("coding"), 0.7, "success"),
+            obs_in(task("planning"), 0.2, "failure"),
+            obs_in(task("planning"), 0.3, "failure"),
+            obs_in(task("crisis"), 0.1, "failure"),
+            obs_in(serde_json::json!({}), 0.5, "unknown"),
+        ];
+        let groups = performance_by_context(obs, ContextDimension::TaskType, 0.0);
+
+        let values: Vec<&str> = groups.iter().map(|g| g.value.as_str()).collect();
+        assert_eq!(values, ["coding", "crisis", "planning", "unknown"]);
+        let group = |v: &str| groups.iter().find(|g| g.value == v).unwrap();
+        assert_eq!(group("coding").observation_count, 2);
+        assert!((group("coding").avg_score - 0.8).abs() < 1e-9);
+        assert_eq!(group("coding").task_outcomes.get("success"), Some(&2));
+        assert_eq!(group("planning").observation_count, 3);
+        assert!((group("planning").avg_score - 0.3).abs() < 1e-9);
+        assert_eq!(group("planning").task_outcomes.get("failure"), Some(&2));
+        assert_eq!(group("crisis").observation_count, 1);
+        assert!((group("crisis").avg_score - 0.1).abs() < 1e-9);
+        assert_eq!(group("unknown").observation_count, 1);
+    }
+
+    #[test]
+    fn performance_by_context_buckets_energy() {
+        let energy = |e: f64| serde_json::json!({ "task_type": "coding", "energy": e });
+        let obs = vec![
+            obs_in(energy(0.1), 0.2, "failure"),
+            obs_in(energy(0.5), 0.6, "success"),
+            obs_in(energy(0.9), 1.0, "success"),
+            obs_in(energy(0.8), 0.8, "success"),
+        ];
+        let groups = performance_by_context(obs, ContextDimension::Energy, 0.0);
+        let summary: Vec<(&str, usize)> = groups
+            .iter()
+            .map(|g| (g.value.as_str(), g.observation_count))
+            .collect();
+        assert_eq!(summary, [("high", 2), ("low", 1), ("medium", 1)]);
+        assert!((groups[0].avg_score - 0.9).abs() < 1e-9);
+    }
+
     // ── build_obs_detail ────────────────────────────────────────────────────
 
     #[test]
@@ -1328,54 +1317,6 @@ mod tests {
         assert!(v["context"].is_null());
     }
 
-    // ── ObsBodyJson serialisation ─────────────────────────────────────────────
-
-    #[test]
-    fn obs_body_json_roundtrip() {
-        let orig = ObsBodyJson {
-            agent: "agent-1".to_string(),
-            prompt_slug: "slug-x".to_string(),
-            prompt_version: Some(2),
-            observation_type: "performance".to_string(),
-            metrics: ObsMetrics {
-                correction_count: 1,
-                sentiment_score: 0.7,
-                task_completed: true,
-                task_outcome: "success".to_string(),
-                observation_score: 0.85,
-                token_cost: Some(99),
-                response_time_ms: Some(300),
-                user_satisfaction: Some(0.8),
-            },
-            context: ObsContext {
-                task_type: "coding".to_string(),
-                topic: Some("rust".to_string()),
-                session_length: Some(5),
-                message_count: Some(20),
-                correction_rate: None,
-                topic_shift: None,
-                energy: None,
-            },
-        };
-        let json = serde_json::to_string(&orig).unwrap();
-        let decoded: ObsBodyJson = serde_json::from_str(&json).unwrap();
-        assert_eq!(decoded.agent, "agent-1");
-        assert_eq!(decoded.prompt_slug, "slug-x");
-        assert_eq!(decoded.metrics.token_cost, Some(99u32));
-        assert_eq!(decoded.context.topic, Some("rust".to_string()));
-    }
-
-    #[test]
-    fn obs_body_json_defaults_for_missing_fields() {
-        // prompt_slug defaults to empty string; prompt_version and optional metrics absent
-        let json = r#"{"agent":"a","prompt_slug":"","observation_type":"performance","metrics":{"correction_count":0,"sentiment_score":0.5,"task_completed":false,"task_outcome":"unknown","observation_score":0.5},"context":{"task_type":"general"}}"#;
-        let decoded: ObsBodyJson = serde_json::from_str(json).unwrap();
-        assert_eq!(decoded.prompt_slug, "");
-        assert!(decoded.prompt_version.is_none());
-        assert!(decoded.metrics.token_cost.is_none());
-        assert!(decoded.context.topic.is_none());
-    }
-
     // ── Input validation ──────────────────────────────────────────────────────
 
     #[test]
@@ -0,0 +1,130 @@
+/// Webhook subscription endpoints.
+///
+/// Endpoints:
+///   GET    /webhooks      — list subscriptions with their last delivery
+///   POST   /webhooks      — register a subscription
+///   GET    /webhooks/:id  — one subscription
+///   DELETE /webhooks/:id  — remove a subscription registered here
+use super::{AppResult, AppState, JsonResponse};
+use crate::webhooks::{DeliveryStatus, Webhook, WebhookError, WebhookSource};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct RegisterWebhookBody {
+    pub url: String,
+    /// Events to deliver (`rollback`, `node.created`, ...). Default: all (`*`).
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Shared secret for the `X-Cortex-Signature` HMAC.
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+/// A subscription as returned by the API: the secret is never echoed.
+#[derive(Serialize)]
+struct WebhookView {
+    id: String,
+    url: String,
+    events: Vec<String>,
+    signed: bool,
+    source: WebhookSource,
+    created_at: String,
+    last_delivery: Option<DeliveryStatus>,
+}
+
+impl From<Webhook> for WebhookView {
+    fn from(hook: Webhook) -> Self {
+        Self {
+            id: hook.id.to_string(),
+            url: hook.url,
+            events: hook.events,
+            signed: hook.secret.is_some(),
+            source: hook.source,
+            created_at: hook.created_at.to_rfc3339(),
+            last_delivery: hook.last_delivery,
+        }
+    }
+}
+
+fn error(status: StatusCode, msg: impl Into<String>) -> Response {
+    (status, Json(JsonResponse::<()>::err(msg))).into_response()
+}
+
+fn invalid_id() -> Response {
+    error(StatusCode::BAD_REQUEST, "Invalid UUID")
+}
+
+// ── GET /webhooks ──────────────────────────────────────────────────────────────
+
+pub async fn list_webhooks(State(state): State<AppState>) -> impl IntoResponse {
+    let hooks: Vec<WebhookView> = state
+        .webhooks
+        .list()
+        .into_iter()
+        .map(WebhookView::from)
+        .collect();
+    Json(JsonResponse::ok(hooks))
+}
+
+// ── POST /webhooks ─────────────────────────────────────────────────────────────
+
+pub async fn register_webhook(
+    State(state): State<AppState>,
+    Json(body): Json<RegisterWebhookBody>,
+) -> AppResult<Response> {
+    match state.webhooks.register(body.url, body.events, body.secret) {
+        Ok(hook) => {
+            tracing::info!("[AUDIT] POST /webhooks id={} url={}", hook.id, hook.url);
+            Ok((
+                StatusCode::CREATED,
+                Json(JsonResponse::ok(WebhookView::from(hook))),
+            )
+                .into_response())
+        }
+        Err(e @ WebhookError::InvalidUrl(_)) => Ok(error(StatusCode::BAD_REQUEST, e.to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// ── GET /webhooks/:id ──────────────────────────────────────────────────────────
+
+pub async fn get_webhook(State(state): State<AppState>, Path(id): Path<String>) -> Response {
+    let Ok(id) = id.parse::<uuid::Uuid>() else {
+        return invalid_id();
+    };
+    match state.webhooks.get(id) {
+        Some(hook) => Json(JsonResponse::ok(WebhookView::from(hook))).into_response(),
+        None => error(StatusCode::NOT_FOUND, format!("Webhook {} not found", id)),
+    }
+}
+
+// ── DELETE /webhooks/:id ───────────────────────────────────────────────────────
+
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> AppResult<Response> {
+    let Ok(id) = id.parse::<uuid::Uuid>() else {
+        return Ok(invalid_id());
+    };
+    match state.webhooks.remove(id) {
+        Ok(true) => {
+            tracing::info!("[AUDIT] DELETE /webhooks/{}", id);
+            Ok(Json(JsonResponse::ok(
+                serde_json::json!({"deleted": id.to_string()}),
+            ))
+            .into_response())
+        }
+        Ok(false) => Ok(error(
+            StatusCode::NOT_FOUND,
+            format!("Webhook {} not found", id),
+        )),
+        Err(e @ WebhookError::FromConfig(_)) => Ok(error(StatusCode::CONFLICT, e.to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
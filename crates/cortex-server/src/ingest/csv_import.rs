@@ -0,0 +1,336 @@
+use crate::gate::GatedWrite;
+use anyhow::{bail, Result};
+use cortex_core::{
+    embedding_input, Embedding, EmbeddingService, GateRejection, Node, NodeKind, Source, Storage,
+    VectorIndex,
+};
+
+/// Node fields a CSV column can fill.
+const FIELDS: [&str; 5] = ["title", "body", "kind", "tags", "importance"];
+
+/// Which column fills each node field.
+///
+/// Fields named in `--map title=Name,body=Description` use that column;
+/// the rest use a column whose header matches the field name, ignoring
+/// case. Only `title` is required.
+#[derive(Debug, Clone, Default)]
+pub struct ColumnMap {
+    overrides: Vec<(String, String)>,
+}
+
+impl ColumnMap {
+    /// Parse a `field=Column,...` spec.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut overrides = Vec::new();
+        for pair in spec.split(',').filter(|p| !p.trim().is_empty()) {
+            let Some((field, column)) = pair.split_once('=') else {
+                bail!("Invalid --map entry '{}': expected field=Column", pair);
+            };
+            let field = field.trim().to_lowercase();
+            if !FIELDS.contains(&field.as_str()) {
+                bail!(
+                    "Unknown field '{}' in --map (expected one of: {})",
+                    field,
+                    FIELDS.join(", ")
+                );
+            }
+            overrides.push((field, column.trim().to_string()));
+        }
+        Ok(Self { overrides })
+    }
+
+    fn resolve(&self, headers: &csv::StringRecord) -> Result<Columns> {
+        let find = |field: &str| -> Result<Option<usize>> {
+            let (name, explicit) = match self.overrides.iter().find(|(f, _)| f == field) {
+                Some((_, column)) => (column.as_str(), true),
+                None => (field, false),
+            };
+            let index = headers
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(name));
+            if index.is_none() && explicit {
+                bail!(
+                    "Column '{}' (mapped to {}) not found; headers are: {}",
+                    name,
+                    field,
+                    headers.iter().collect::<Vec<_>>().join(", ")
+                );
+            }
+            Ok(index)
+        };
+        let Some(title) = find("title")? else {
+            bail!("CSV has no `title` column; map one with --map title=<column>");
+        };
+        Ok(Columns {
+            title,
+            body: find("body")?,
+            kind: find("kind")?,
+            tags: find("tags")?,
+            importance: find("importance")?,
+        })
+    }
+}
+
+/// Column index per field, resolved against the header row.
+struct Columns {
+    title: usize,
+    body: Option<usize>,
+    kind: Option<usize>,
+    tags: Option<usize>,
+    importance: Option<usize>,
+}
+
+/// One data row: the node it maps to, or why it can't be imported.
+#[derive(Debug)]
+pub struct CsvRow {
+    /// Line in the file, counting the header as line 1.
+    pub line: u64,
+    pub node: std::result::Result<Node, String>,
+}
+
+/// Read every data row of a CSV with a header row. `tag_delimiter` splits
+/// the tags cell. A row with a bad value is kept as an error rather than
+/// failing the whole file.
+pub fn read_rows<R: std::io::Read>(
+    reader: R,
+    map: &ColumnMap,
+    tag_delimiter: &str,
+    source: &str,
+) -> Result<Vec<CsvRow>> {
+    let mut rdr = csv::Reader::from_reader(reader);
+    let columns = map.resolve(rdr.headers()?)?;
+
+    let mut rows = Vec::new();
+    for record in rdr.records() {
+        let record = record?;
+        let line = record.position().map_or(0, |p| p.line());
+        rows.push(CsvRow {
+            line,
+            node: row_to_node(&record, &columns, tag_delimiter, source),
+        });
+    }
+    Ok(rows)
+}
+
+fn row_to_node(
+    record: &csv::StringRecord,
+    columns: &Columns,
+    tag_delimiter: &str,
+    source: &str,
+) -> std::result::Result<Node, String> {
+    let cell = |index: Option<usize>| {
+        index
+            .and_then(|i| record.get(i))
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+
+    let title = cell(Some(columns.title)).ok_or("Empty title")?;
+    let kind = cell(columns.kind).unwrap_or("fact").to_lowercase();
+    let kind = NodeKind::new(&kind).map_err(|e| format!("Invalid kind '{}': {}", kind, e))?;
+    let importance = match cell(columns.importance) {
+        None => 0.5,
+        Some(raw) => match raw.parse::<f32>() {
+            Ok(v) if (0.0..=1.0).contains(&v) => v,
+            _ => return Err(format!("Invalid importance '{}' (expected 0 to 1)", raw)),
+        },
+    };
+
+    let mut node = Node::new(
+        kind,
+        title.to_string(),
+        cell(columns.body).unwrap_or(title).to_string(),
+        Source {
+            agent: source.to_string(),
+            session: None,
+            channel: None,
+        },
+        importance,
+    );
+    node.data.tags = cell(columns.tags)
+        .map(|tags| {
+            tags.split(tag_delimiter)
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(node)
+}
+
+/// A row left out of the import, and why.
+#[derive(Debug)]
+pub struct RowReport {
+    pub line: u64,
+    /// Empty for rows that did not map to a node.
+    pub title: String,
+    pub reasons: Vec<String>,
+}
+
+/// Rows split by what the write gate made of them.
+#[derive(Debug, Default)]
+pub struct GatedRows {
+    /// Nodes to store, with the embedding the gate checked.
+    pub accepted: Vec<(Node, Embedding)>,
+    /// Rows the gate refused in enforce mode.
+    pub rejected: Vec<RowReport>,
+    /// Rows that did not map to a node.
+    pub invalid: Vec<RowReport>,
+}
+
+/// Run each row through the same write gate and schema checks as
+/// `POST /nodes`, embedding all rows that pass the cheap checks in one batch.
+///
+/// The conflict check only sees nodes already in `storage`, so duplicate
+/// rows within one file are not caught.
+pub fn gate_rows<S: Storage, E: EmbeddingService, V: VectorIndex>(
+    rows: Vec<CsvRow>,
+    embedder: &E,
+    index: &V,
+    storage: &S,
+    gate: &GatedWrite<'_>,
+) -> Result<GatedRows> {
+    let mut out = GatedRows::default();
+    // (line, node, failures found before embedding)
+    let mut candidates = Vec::new();
+    for row in rows {
+        let mut node = match row.node {
+            Ok(node) => node,
+            Err(reason) => {
+                out.invalid.push(RowReport {
+                    line: row.line,
+                    title: String::new(),
+                    reasons: vec![reason],
+                });
+                continue;
+            }
+        };
+        let rejections = gate.screen(&mut node);
+        if gate.refuses_early(&rejections) {
+            out.rejected.push(rejected_row(row.line, node, rejections));
+            continue;
+        }
+        candidates.push((row.line, node, rejections));
+    }
+
+    let texts: Vec<String> = candidates
+        .iter()
+        .map(|(_, n, _)| embedding_input(n))
+        .collect();
+    let embeddings = if texts.is_empty() {
+        Vec::new()
+    } else {
+        embedder.embed_batch(&texts)?
+    };
+
+    for ((line, mut node, mut rejections), embedding) in candidates.into_iter().zip(embeddings) {
+        gate.check(&node, &embedding, index, storage, &mut rejections);
+        match gate.settle(&mut node, rejections) {
+            Ok(_) => out.accepted.push((node, embedding)),
+            Err(rejections) => out.rejected.push(rejected_row(line, node, rejections)),
+        }
+    }
+    Ok(out)
+}
+
+fn rejected_row(line: u64, node: Node, rejections: Vec<GateRejection>) -> RowReport {
+    RowReport {
+        line,
+        title: node.data.title,
+        reasons: rejections
+            .iter()
+            .map(|r| format!("{}: {}", r.check, r.reason))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::HashEmbedder;
+    use cortex_core::{HnswIndex, RedbStorage, SchemaValidator, WriteGateConfig};
+
+    const SAMPLE: &str = include_str!("../../tests/fixtures/facts.csv");
+
+    fn sample_map() -> ColumnMap {
+        ColumnMap::parse("title=Name,body=Description,kind=Type,tags=Labels").unwrap()
+    }
+
+    #[test]
+    fn map_selects_columns_and_splits_tags() {
+        let rows = read_rows(SAMPLE.as_bytes(), &sample_map(), "|", "sheet").unwrap();
+        assert_eq!(rows.len(), 4);
+
+        let first = rows[0].node.as_ref().unwrap();
+        assert_eq!(first.data.title, "Redb is the storage engine");
+        assert!(first.data.body.starts_with("Cortex stores nodes and edges"));
+        assert_eq!(first.kind.as_str(), "decision");
+        assert_eq!(first.data.tags, ["storage", "rust"]);
+        assert_eq!(first.importance, 0.8);
+        assert_eq!(first.source.agent, "sheet");
+
+        // Line numbers count the header, and bad cells fail only their row
+        assert_eq!(rows[3].line, 5);
+        assert!(rows[3].node.as_ref().unwrap_err().contains("importance"));
+    }
+
+    #[test]
+    fn headers_matching_field_names_need_no_map() {
+        let csv = "Title,Body,Tags\nLazy HNSW rebuilds,Rebuilt on first search,vector; index\n";
+        let rows = read_rows(csv.as_bytes(), &ColumnMap::default(), ";", "sheet").unwrap();
+        let node = rows[0].node.as_ref().unwrap();
+        assert_eq!(node.data.title, "Lazy HNSW rebuilds");
+        assert_eq!(node.kind.as_str(), "fact");
+        assert_eq!(node.data.tags, ["vector", "index"]);
+
+        let err = read_rows("Name\nx\n".as_bytes(), &ColumnMap::default(), ";", "s").unwrap_err();
+        assert!(err.to_string().contains("--map title="), "{err}");
+        let err = read_rows(
+            SAMPLE.as_bytes(),
+            &ColumnMap::parse("title=Nope").unwrap(),
+            ";",
+            "s",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Column 'Nope'"), "{err}");
+        assert!(ColumnMap::parse("colour=Red").is_err());
+    }
+
+    #[test]
+    fn gate_rejections_are_reported_per_row() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = RedbStorage::open(dir.path().join("csv.redb")).unwrap();
+        let index = HnswIndex::new(HashEmbedder::DIMENSION);
+        let rows = read_rows(SAMPLE.as_bytes(), &sample_map(), "|", "sheet").unwrap();
+
+        let config = WriteGateConfig::default();
+        let schema = SchemaValidator::empty();
+        let gated = gate_rows(
+            rows,
+            &HashEmbedder,
+            &index,
+            &storage,
+            &GatedWrite::new(&config, &schema),
+        )
+        .unwrap();
+
+        let accepted: Vec<&str> = gated
+            .accepted
+            .iter()
+            .map(|(n, _)| n.data.title.as_str())
+            .collect();
+        assert_eq!(
+            accepted,
+            ["Redb is the storage engine", "HNSW index rebuilds lazily"]
+        );
+
+        assert_eq!(gated.rejected.len(), 1);
+        let rejected = &gated.rejected[0];
+        assert_eq!((rejected.line, rejected.title.as_str()), (4, "Todo"));
+        assert!(rejected.reasons.iter().any(|r| r.starts_with("substance:")));
+
+        assert_eq!(gated.invalid.len(), 1);
+        assert_eq!(gated.invalid[0].line, 5);
+    }
+}
@@ -0,0 +1,388 @@
+use anyhow::{bail, Result};
+use cortex_core::{Edge, EdgeProvenance, Node, NodeKind, Relation, Source};
+
+/// A frontmatter value: a scalar or a list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrontmatterValue {
+    Scalar(String),
+    List(Vec<String>),
+}
+
+impl FrontmatterValue {
+    /// List items, or a scalar split on commas and whitespace
+    /// (`tags: rust, storage`).
+    fn items(&self) -> Vec<String> {
+        match self {
+            Self::List(items) => items.clone(),
+            Self::Scalar(s) => s
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect(),
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Scalar(s) => serde_json::json!(s),
+            Self::List(items) => serde_json::json!(items),
+        }
+    }
+}
+
+/// Node fields read from a document's frontmatter.
+#[derive(Debug, Clone, Default)]
+pub struct Frontmatter {
+    pub title: Option<String>,
+    /// Already checked to be a valid [`NodeKind`].
+    pub kind: Option<String>,
+    pub tags: Vec<String>,
+    pub importance: Option<f32>,
+    pub aliases: Vec<String>,
+    /// Keys other than the ones above, in file order.
+    pub extra: Vec<(String, FrontmatterValue)>,
+}
+
+impl Frontmatter {
+    /// Copy tags and extra keys onto `node`.
+    pub fn apply(&self, node: &mut Node) {
+        node.data.tags = self.tags.clone();
+        for (key, value) in &self.extra {
+            node.data.metadata.insert(key.clone(), value.to_json());
+        }
+    }
+}
+
+/// Split a leading `---` block from `content` and map it to node fields.
+/// Returns the rest of the document alongside.
+///
+/// Only the flat YAML subset note tools write is understood: `key: value`,
+/// inline lists (`key: [a, b]`) and block lists (`key:` then `- a` lines).
+/// Anything else is an error naming its line in the file.
+pub fn parse_frontmatter(content: &str) -> Result<(Frontmatter, &str)> {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return Ok((Frontmatter::default(), content));
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let fields = parse_fields(&rest[..offset])?;
+            return Ok((map_fields(fields)?, &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    bail!("frontmatter opened on line 1 is never closed with `---`")
+}
+
+/// `(key, value, line)` triples; `line` is 1-based in the whole file.
+fn parse_fields(text: &str) -> Result<Vec<(String, FrontmatterValue, usize)>> {
+    let mut fields: Vec<(String, FrontmatterValue, usize)> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        // Line 1 is the opening `---`
+        let line_no = i + 2;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            match fields.last_mut() {
+                Some((_, FrontmatterValue::List(items), _)) => {
+                    items.push(unquote(item.trim()).to_string())
+                }
+                _ => bail!("frontmatter line {}: list item without a list key", line_no),
+            }
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) {
+            bail!(
+                "frontmatter line {}: nested values are not supported",
+                line_no
+            );
+        }
+        let Some((key, value)) = trimmed.split_once(':') else {
+            bail!("frontmatter line {}: expected `key: value`", line_no);
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            bail!("frontmatter line {}: missing key before `:`", line_no);
+        }
+        let value = value.trim();
+        let value = if value.is_empty() {
+            FrontmatterValue::List(Vec::new())
+        } else if let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            FrontmatterValue::List(
+                inner
+                    .split(',')
+                    .map(|s| unquote(s.trim()).to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+            )
+        } else {
+            FrontmatterValue::Scalar(unquote(value).to_string())
+        };
+        fields.push((key.to_string(), value, line_no));
+    }
+    Ok(fields)
+}
+
+fn map_fields(fields: Vec<(String, FrontmatterValue, usize)>) -> Result<Frontmatter> {
+    let mut fm = Frontmatter::default();
+    for (key, value, line_no) in fields {
+        let scalar = |value: &FrontmatterValue| match value {
+            FrontmatterValue::Scalar(s) => Ok(s.clone()),
+            FrontmatterValue::List(_) => Err(anyhow::anyhow!(
+                "frontmatter line {}: `{}` must be a single value",
+                line_no,
+                key
+            )),
+        };
+        match key.as_str() {
+            "title" => fm.title = Some(scalar(&value)?),
+            "kind" => {
+                let kind = scalar(&value)?.to_lowercase();
+                if let Err(e) = NodeKind::new(&kind) {
+                    bail!(
+                        "frontmatter line {}: invalid kind '{}': {}",
+                        line_no,
+                        kind,
+                        e
+                    );
+                }
+                fm.kind = Some(kind);
+            }
+            "importance" => {
+                let raw = scalar(&value)?;
+                match raw.parse::<f32>() {
+                    Ok(v) if (0.0..=1.0).contains(&v) => fm.importance = Some(v),
+                    _ => bail!(
+                        "frontmatter line {}: importance must be a number from 0 to 1, got '{}'",
+                        line_no,
+                        raw
+                    ),
+                }
+            }
+            "tags" | "tag" => {
+                fm.tags = value
+                    .items()
+                    .into_iter()
+                    .map(|t| t.trim_start_matches('#').to_string())
+                    .collect()
+            }
+            "aliases" | "alias" => {
+                fm.aliases = match value {
+                    FrontmatterValue::List(items) => items,
+                    FrontmatterValue::Scalar(s) => vec![s],
+                }
+            }
+            _ => fm.extra.push((key, value)),
+        }
+    }
+    Ok(fm)
+}
+
+fn unquote(s: &str) -> &str {
+    s.strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .or_else(|| s.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')))
+        .unwrap_or(s)
+}
+
+/// Nodes and edges built from one markdown document.
+#[derive(Debug)]
+pub struct MarkdownImport {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+/// Build nodes from a markdown document.
+///
+/// The title comes from frontmatter `title`, else the first `# ` heading
+/// (which is then dropped from the body), else `name`. With
+/// `split_by_heading`, each `## ` section becomes its own node with a
+/// `part_of` edge to a parent node holding the text before the first
+/// section. Headings inside code fences are ignored.
+pub fn import_document(
+    name: &str,
+    content: &str,
+    source: &str,
+    split_by_heading: bool,
+) -> Result<MarkdownImport> {
+    let (fm, body) = parse_frontmatter(content)?;
+    let lines = heading_lines(body);
+
+    let h1 = lines
+        .iter()
+        .find_map(|(i, line)| line.strip_prefix("# ").map(|t| (*i, t.trim())));
+    let (title, skip) = match (&fm.title, h1) {
+        (Some(title), _) => (title.clone(), None),
+        (None, Some((i, heading))) => (heading.to_string(), Some(i)),
+        (None, None) => (name.to_string(), None),
+    };
+
+    let mut intro = String::new();
+    let mut sections: Vec<(String, String)> = Vec::new();
+    let section_starts: Vec<usize> = if split_by_heading {
+        lines
+            .iter()
+            .filter(|(_, l)| l.starts_with("## "))
+            .map(|(i, _)| *i)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    for (i, line) in body.lines().enumerate() {
+        if Some(i) == skip {
+            continue;
+        }
+        if section_starts.contains(&i) {
+            sections.push((line[3..].trim().to_string(), String::new()));
+            continue;
+        }
+        let text = match sections.last_mut() {
+            Some((_, text)) => text,
+            None => &mut intro,
+        };
+        text.push_str(line);
+        text.push('\n');
+    }
+
+    let kind = NodeKind::new(fm.kind.as_deref().unwrap_or("fact"))?;
+    let importance = fm.importance.unwrap_or(0.5);
+    let make = |title: String, body: &str| {
+        let body = body.trim();
+        let body = if body.is_empty() {
+            title.clone()
+        } else {
+            body.to_string()
+        };
+        let mut node = Node::new(
+            kind.clone(),
+            title,
+            body,
+            Source {
+                agent: source.to_string(),
+                session: None,
+                channel: None,
+            },
+            importance,
+        );
+        fm.apply(&mut node);
+        node
+    };
+
+    let parent = make(title.clone(), &intro);
+    let part_of = Relation::new("part_of")?;
+    let mut nodes = Vec::with_capacity(sections.len() + 1);
+    let mut edges = Vec::with_capacity(sections.len());
+    for (heading, text) in sections {
+        let section = make(format!("{} — {}", title, heading), &text);
+        edges.push(Edge::new(
+            section.id,
+            parent.id,
+            part_of.clone(),
+            1.0,
+            EdgeProvenance::Imported {
+                source: "markdown".to_string(),
+            },
+        ));
+        nodes.push(section);
+    }
+    nodes.insert(0, parent);
+    Ok(MarkdownImport { nodes, edges })
+}
+
+/// `(line index, line)` for heading lines outside ``` fences.
+fn heading_lines(body: &str) -> Vec<(usize, &str)> {
+    let mut in_fence = false;
+    let mut out = Vec::new();
+    for (i, line) in body.lines().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        } else if !in_fence && line.starts_with('#') {
+            out.push((i, line));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frontmatter_maps_to_node_fields() {
+        let doc = "---\nkind: Decision\ntitle: \"Storage engine\"\ntags: [storage, \"#rust\"]\nimportance: 0.8\nstatus: accepted\n---\n# Ignored H1\n\nWe chose redb.\n";
+        let import = import_document("storage", doc, "cli", false).unwrap();
+        assert_eq!(import.nodes.len(), 1);
+        let node = &import.nodes[0];
+        assert_eq!(node.kind.as_str(), "decision");
+        assert_eq!(node.data.title, "Storage engine");
+        assert_eq!(node.data.tags, ["storage", "rust"]);
+        assert_eq!(node.importance, 0.8);
+        assert_eq!(node.data.metadata["status"], "accepted");
+        // An explicit title leaves the H1 in the body
+        assert_eq!(node.data.body, "# Ignored H1\n\nWe chose redb.");
+    }
+
+    #[test]
+    fn h1_then_file_name_are_title_fallbacks() {
+        let import = import_document(
+            "notes",
+            "Intro line.\n# Lazy index rebuilds\n\nBody text.\n",
+            "cli",
+            false,
+        )
+        .unwrap();
+        let node = &import.nodes[0];
+        assert_eq!(node.data.title, "Lazy index rebuilds");
+        assert_eq!(node.data.body, "Intro line.\n\nBody text.");
+        assert_eq!(node.kind.as_str(), "fact");
+
+        let import = import_document("notes", "```\n# not a heading\n```\n", "cli", false).unwrap();
+        assert_eq!(import.nodes[0].data.title, "notes");
+    }
+
+    #[test]
+    fn split_by_heading_links_sections_to_parent() {
+        let doc = "---\ntags: [runbook]\n---\n# Deploys\n\nHow we ship.\n\n## Build\n\nRun the release build.\n\n```sh\n## not a section\n```\n\n## Rollout\n\nCanary first.\n";
+        let import = import_document("deploys", doc, "cli", true).unwrap();
+        let titles: Vec<&str> = import.nodes.iter().map(|n| n.data.title.as_str()).collect();
+        assert_eq!(titles, ["Deploys", "Deploys — Build", "Deploys — Rollout"]);
+        assert_eq!(import.nodes[0].data.body, "How we ship.");
+        assert!(import.nodes[1].data.body.contains("## not a section"));
+        assert_eq!(import.nodes[2].data.body, "Canary first.");
+        assert!(import.nodes.iter().all(|n| n.data.tags == ["runbook"]));
+
+        assert_eq!(import.edges.len(), 2);
+        for (edge, section) in import.edges.iter().zip(&import.nodes[1..]) {
+            assert_eq!((edge.from, edge.to), (section.id, import.nodes[0].id));
+            assert_eq!(edge.relation.as_str(), "part_of");
+        }
+
+        // Without the flag the sections stay in one node
+        let import = import_document("deploys", doc, "cli", false).unwrap();
+        assert_eq!(import.nodes.len(), 1);
+        assert!(import.edges.is_empty());
+    }
+
+    #[test]
+    fn invalid_frontmatter_names_the_line() {
+        let err = |doc: &str| {
+            import_document("x", doc, "cli", false)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err("---\ntitle: ok\njust words\n---\nbody"),
+            "frontmatter line 3: expected `key: value`"
+        );
+        assert!(err("---\nkind: ok\nimportance: high\n---\n")
+            .starts_with("frontmatter line 3: importance"));
+        assert!(err("---\n- stray\n---\n").starts_with("frontmatter line 2: list item"));
+        assert!(err("---\nkind: Not A Kind\n---\n").starts_with("frontmatter line 2: invalid kind"));
+        assert!(err("---\ntitle: x\n").contains("never closed"));
+    }
+}
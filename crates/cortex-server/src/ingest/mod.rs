@@ -1,2 +1,5 @@
+pub mod csv_import;
+pub mod markdown;
 pub mod nats;
+pub mod obsidian;
 pub mod stdin;
@@ -0,0 +1,302 @@
+use super::markdown::{parse_frontmatter, Frontmatter};
+use anyhow::{Context, Result};
+use cortex_core::{Edge, EdgeProvenance, Node, NodeId, NodeKind, Relation, Source};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Edge weight for a `[[wikilink]]`: an explicit reference made by the author.
+const WIKILINK_WEIGHT: f32 = 1.0;
+
+/// One parsed `.md` file.
+#[derive(Debug, Clone)]
+pub struct ObsidianNote {
+    /// Path relative to the vault root, with `/` separators.
+    pub path: String,
+    /// Frontmatter `title`, else the file name.
+    pub title: String,
+    pub frontmatter: Frontmatter,
+    pub body: String,
+    /// `[[wikilink]]` targets, without `#heading` or `|alias` parts.
+    pub links: Vec<String>,
+}
+
+/// A `[[wikilink]]` that matched no note in the vault.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnresolvedLink {
+    /// Vault-relative path of the note containing the link.
+    pub from: String,
+    pub target: String,
+}
+
+/// Nodes and edges built from a vault, ready to store.
+#[derive(Debug)]
+pub struct VaultImport {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+    pub unresolved: Vec<UnresolvedLink>,
+}
+
+/// Read every `.md` file under `dir` into a node, then resolve wikilinks
+/// into `relates_to` edges.
+///
+/// Links are resolved in a second pass over the whole vault, so a note may
+/// link to one that sorts after it. A link matches a note's vault-relative
+/// path, its file name or one of its frontmatter `aliases`, ignoring case.
+/// Hidden directories such as `.obsidian` are skipped, as are links to
+/// attachments (`![[diagram.png]]`).
+pub fn import_vault(dir: &Path, source: &str) -> Result<VaultImport> {
+    let mut files = Vec::new();
+    collect_markdown(dir, &mut files)?;
+    files.sort();
+
+    let mut notes = Vec::with_capacity(files.len());
+    for file in &files {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let rel = file
+            .strip_prefix(dir)
+            .unwrap_or(file)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        notes.push(parse_note(&rel, &content).with_context(|| format!("Invalid note {}", rel))?);
+    }
+
+    // Pass 1: a node per note, and every name each note can be linked by
+    let mut nodes = Vec::with_capacity(notes.len());
+    let mut by_name: HashMap<String, NodeId> = HashMap::new();
+    for note in &notes {
+        let node = note_to_node(note, source)?;
+        let names = std::iter::once(note.path.trim_end_matches(".md"))
+            .chain(std::iter::once(file_stem(&note.path)))
+            .chain(note.frontmatter.aliases.iter().map(String::as_str));
+        for name in names {
+            // On a clash, the first note by path keeps the name
+            by_name.entry(name.to_lowercase()).or_insert(node.id);
+        }
+        nodes.push(node);
+    }
+
+    // Pass 2: links, now that every note has a node
+    let relation = Relation::new("relates_to")?;
+    let mut edges = Vec::new();
+    let mut unresolved = Vec::new();
+    let mut seen = HashSet::new();
+    for (note, node) in notes.iter().zip(&nodes) {
+        for target in &note.links {
+            let key = target.trim_end_matches(".md").to_lowercase();
+            match by_name.get(&key) {
+                Some(&to) if to == node.id => {}
+                Some(&to) => {
+                    if seen.insert((node.id, to)) {
+                        edges.push(Edge::new(
+                            node.id,
+                            to,
+                            relation.clone(),
+                            WIKILINK_WEIGHT,
+                            EdgeProvenance::Imported {
+                                source: "obsidian".to_string(),
+                            },
+                        ));
+                    }
+                }
+                None => unresolved.push(UnresolvedLink {
+                    from: note.path.clone(),
+                    target: target.clone(),
+                }),
+            }
+        }
+    }
+
+    Ok(VaultImport {
+        nodes,
+        edges,
+        unresolved,
+    })
+}
+
+fn collect_markdown(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_markdown(&path, out)?;
+        } else if path.extension().and_then(|e| e.to_str()) == Some("md") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn file_stem(path: &str) -> &str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.strip_suffix(".md").unwrap_or(name)
+}
+
+/// Parse one note. `path` is vault-relative and gives the default title.
+pub fn parse_note(path: &str, content: &str) -> Result<ObsidianNote> {
+    let (frontmatter, body) = parse_frontmatter(content)?;
+    Ok(ObsidianNote {
+        path: path.to_string(),
+        title: frontmatter
+            .title
+            .clone()
+            .unwrap_or_else(|| file_stem(path).to_string()),
+        frontmatter,
+        body: body.trim().to_string(),
+        links: extract_wikilinks(body),
+    })
+}
+
+fn note_to_node(note: &ObsidianNote, source: &str) -> Result<Node> {
+    let fm = &note.frontmatter;
+    let body = if note.body.is_empty() {
+        note.title.clone()
+    } else {
+        note.body.clone()
+    };
+    let mut node = Node::new(
+        NodeKind::new(fm.kind.as_deref().unwrap_or("fact"))?,
+        note.title.clone(),
+        body,
+        Source {
+            agent: source.to_string(),
+            session: None,
+            channel: None,
+        },
+        fm.importance.unwrap_or(0.5),
+    );
+    fm.apply(&mut node);
+    node.data.metadata.insert(
+        "obsidian_path".to_string(),
+        serde_json::Value::String(note.path.clone()),
+    );
+    Ok(node)
+}
+
+/// Targets of every `[[wikilink]]` in `body`, in order, with `#heading`
+/// and `|alias` parts removed. Links to non-markdown files are skipped.
+pub fn extract_wikilinks(body: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("[[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("]]") else {
+            break;
+        };
+        let inner = &after[..end];
+        rest = &after[end + 2..];
+
+        let target = inner.split(['|', '#']).next().unwrap_or("").trim();
+        let attachment = target
+            .rsplit_once('.')
+            .is_some_and(|(_, ext)| ext != "md" && !ext.contains(['/', ' ']));
+        if !target.is_empty() && !attachment {
+            links.push(target.to_string());
+        }
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_vault() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/obsidian_vault")
+    }
+
+    #[test]
+    fn note_reads_frontmatter() {
+        let note = parse_note(
+            "decisions/Storage Engine.md",
+            "---\nkind: decision\ntags:\n  - storage\n  - \"#rust\"\nimportance: 0.8\naliases: [redb]\n---\n\nWe chose redb.\n",
+        )
+        .unwrap();
+        assert_eq!(note.title, "Storage Engine");
+        assert_eq!(note.frontmatter.kind.as_deref(), Some("decision"));
+        assert_eq!(note.frontmatter.tags, ["storage", "rust"]);
+        assert_eq!(note.frontmatter.importance, Some(0.8));
+        assert_eq!(note.frontmatter.aliases, ["redb"]);
+        assert_eq!(note.body, "We chose redb.");
+
+        // No frontmatter: the file name is the title and the whole file the body
+        let note = parse_note("notes/Plain.md", "Just text with a [[Link]].").unwrap();
+        assert_eq!(note.title, "Plain");
+        assert_eq!(note.frontmatter.kind, None);
+        assert_eq!(note.body, "Just text with a [[Link]].");
+    }
+
+    #[test]
+    fn wikilinks_drop_headings_aliases_and_attachments() {
+        let links = extract_wikilinks(
+            "See [[HNSW Index#Rebuilds|the index]], [[notes/Zettel]], ![[diagram.png]] and [[#Local]].",
+        );
+        assert_eq!(links, ["HNSW Index", "notes/Zettel"]);
+    }
+
+    #[test]
+    fn vault_links_resolve_across_files() {
+        let import = import_vault(&fixture_vault(), "obsidian").unwrap();
+
+        let titles: Vec<&str> = import.nodes.iter().map(|n| n.data.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            ["Daily Log", "HNSW Index", "Storage Engine", "Zettel"],
+            "sorted by path, hidden .obsidian skipped"
+        );
+        let id = |title: &str| {
+            import
+                .nodes
+                .iter()
+                .find(|n| n.data.title == title)
+                .unwrap()
+                .id
+        };
+
+        let storage = import
+            .nodes
+            .iter()
+            .find(|n| n.data.title == "Storage Engine")
+            .unwrap();
+        assert_eq!(storage.kind.as_str(), "decision");
+        assert_eq!(storage.data.tags, ["storage", "rust"]);
+        assert_eq!(storage.importance, 0.8);
+
+        let mut pairs: Vec<(NodeId, NodeId)> =
+            import.edges.iter().map(|e| (e.from, e.to)).collect();
+        pairs.sort();
+        let mut expected = vec![
+            // Daily Log links forward, by path and by alias
+            (id("Daily Log"), id("HNSW Index")),
+            (id("Daily Log"), id("Zettel")),
+            (id("Daily Log"), id("Storage Engine")),
+            // HNSW Index links back to a note that sorts after it
+            (id("HNSW Index"), id("Storage Engine")),
+            (id("Storage Engine"), id("HNSW Index")),
+        ];
+        expected.sort();
+        assert_eq!(pairs, expected);
+        assert!(import
+            .edges
+            .iter()
+            .all(|e| e.relation.as_str() == "relates_to"));
+
+        assert_eq!(
+            import.unresolved,
+            [UnresolvedLink {
+                from: "decisions/Storage Engine.md".into(),
+                target: "Missing Note".into(),
+            }]
+        );
+    }
+}
@@ -1,3 +1,7 @@
 //! cortex-server library target — exposes modules needed by integration tests.
 
 pub mod observability;
+
+// Public so the doubles only the binary's tests use are not dead code here
+#[cfg(test)]
+pub mod test_support;
@@ -2,13 +2,21 @@
 mod briefing;
 mod cli;
 mod config;
+mod gate;
 mod grpc;
 mod http;
 mod ingest;
 mod mcp;
 mod migration;
 mod observability;
+mod observations;
+mod rate_limit;
+mod reload;
 mod serve;
+mod telemetry;
+#[cfg(test)]
+mod test_support;
+mod webhooks;
 
 #[cfg(feature = "warren")]
 mod nats;
@@ -20,26 +28,24 @@ use tracing::error;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into()),
-        )
-        .init();
-
     let cli = Cli::parse();
 
     // Load config for commands that need it (serve, local ops)
     let mut config = CortexConfig::load_or_default(&cli.config);
 
+    // Initialize tracing. The guard flushes exported spans on exit.
+    let _telemetry = telemetry::init(&config.observability)?;
+
     // Override data_dir if specified on command line
     if let Some(data_dir) = &cli.data_dir {
         config.server.data_dir = data_dir.clone();
     }
 
     match cli.command {
-        Commands::Serve => {
+        Commands::Serve(args) => {
+            if args.read_only {
+                config.server.read_only = true;
+            }
             config.ensure_data_dir()?;
             let errors = config.validate();
             if !errors.is_empty() {
@@ -48,7 +54,7 @@ async fn main() -> anyhow::Result<()> {
                 }
                 anyhow::bail!("Invalid cortex.toml configuration");
             }
-            serve::run(config).await?;
+            serve::run(config, &cli.config).await?;
         }
 
         Commands::Init => {
@@ -71,6 +77,10 @@ async fn main() -> anyhow::Result<()> {
             cli::search::run(a, &cli.server).await?;
         }
 
+        Commands::Query(a) => {
+            cli::query::run(a, &cli.server).await?;
+        }
+
         Commands::Traverse(a) => {
             cli::traverse::run(a, &cli.server).await?;
         }
@@ -92,25 +102,57 @@ async fn main() -> anyhow::Result<()> {
         }
 
         Commands::Backup(a) => {
-            cli::backup::run(a, config).await?;
+            cli::backup::run(a, config, &cli.server).await?;
         }
 
         Commands::Restore(a) => {
             cli::backup::run_restore(a, config).await?;
         }
 
+        Commands::RestoreArchive(a) => {
+            cli::backup::run_restore_archive(a, config).await?;
+        }
+
         Commands::Migrate => {
             cli::migrate::run(config).await?;
         }
 
-        Commands::Stats => {
-            cli::stats::run(&cli.server).await?;
+        Commands::Gc(a) => {
+            cli::gc::run(a, config).await?;
+        }
+
+        Commands::Stats(a) => {
+            cli::stats::run(a, &cli.server).await?;
+        }
+
+        Commands::Watch(a) => {
+            cli::watch::run(a, config, &cli.server).await?;
+        }
+
+        Commands::Diff(a) => {
+            cli::diff::run(a).await?;
+        }
+
+        Commands::Merge(a) => {
+            cli::merge::run(a).await?;
+        }
+
+        Commands::Reindex(a) => {
+            cli::reindex::run(a, &cli.server).await?;
+        }
+
+        Commands::Linker(cmd) => {
+            cli::linker::run(cmd, &cli.server).await?;
         }
 
         Commands::Doctor => {
             cli::doctor::run(config, &cli.server).await?;
         }
 
+        Commands::Verify(a) => {
+            cli::verify::run(a, config).await?;
+        }
+
         Commands::Config(cmd) => {
             cli::config_cmd::run(cmd, &cli.config).await?;
         }
@@ -120,7 +162,7 @@ async fn main() -> anyhow::Result<()> {
         }
 
         Commands::Security(cmd) => {
-            cli::security::run(cmd).await?;
+            cli::security::run(cmd, config).await?;
         }
 
         Commands::Mcp(args) => {
@@ -129,7 +171,21 @@ async fn main() -> anyhow::Result<()> {
                 .data_dir
                 .or_else(|| Some(config.server.data_dir.clone()));
             let server = args.server;
-            mcp::run(mcp::McpArgs { data_dir, server }).await?;
+            let transport = match args.transport.as_str() {
+                "sse" => mcp::McpTransport::Sse { bind: args.bind },
+                _ => mcp::McpTransport::Stdio,
+            };
+            mcp::run(mcp::McpArgs {
+                data_dir,
+                server,
+                transport,
+                briefing_sections: config.briefing.kind_sections.clone(),
+                graph_proximity: config.graph_proximity.clone(),
+                write_gate: config.write_gate.clone(),
+                schema: cortex_core::SchemaValidator::new(config.schemas.clone())
+                    .with_kinds(config.schema.kind_registry()),
+            })
+            .await?;
         }
 
         Commands::Agent(cmd) => {
@@ -139,6 +195,10 @@ async fn main() -> anyhow::Result<()> {
         Commands::Prompt(cmd) => {
             cli::prompt::run(cmd, &config, &cli.server).await?;
         }
+
+        Commands::Completions(a) => {
+            cli::completions::run(a)?;
+        }
     }
 
     Ok(())
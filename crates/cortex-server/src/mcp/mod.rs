@@ -3,25 +3,59 @@
 //! Exposes Cortex graph memory as tools and resources to any MCP-compatible AI agent
 //! (Claude Desktop, Cursor, Cline, etc.) without requiring `cortex serve`.
 //!
-//! Protocol: JSON-RPC 2.0 over stdin/stdout. All logs go to stderr.
+//! Protocol: JSON-RPC 2.0 over stdin/stdout, or over HTTP with Server-Sent
+//! Events (see [`sse`]). All logs go to stderr.
 
+mod sse;
+mod subscriptions;
+
+use crate::gate::{describe, GatedWrite};
 use anyhow::Result;
+use cortex_core::briefing::{BriefingSectionSpec, SectionOrder};
 use cortex_core::{
-    Cortex, Edge, EdgeProvenance, LibraryConfig, Node, NodeFilter, NodeId, NodeKind, Relation,
-    Source,
+    page_search, Cortex, Edge, EdgeProvenance, GateResult, GraphProximity, LibraryConfig, Node,
+    NodeCursor, NodeFilter, NodeId, NodeKind, Relation, SchemaValidator, SearchCursor, Source,
+    VectorFilter, WriteGate, WriteGateConfig,
 };
 use serde_json::{json, Value};
+use std::net::SocketAddr;
 use std::path::PathBuf;
+use subscriptions::Subscriptions;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use uuid::Uuid;
 
+/// Hop budget for `cortex_path` when the caller doesn't pass `max_hops`.
+const DEFAULT_PATH_HOPS: u32 = 6;
+
 pub struct McpArgs {
     pub data_dir: Option<PathBuf>,
     pub server: Option<String>,
+    pub transport: McpTransport,
+    /// `briefing.kind_sections` from cortex.toml, if set.
+    pub briefing_sections: Option<Vec<BriefingSectionSpec>>,
+    /// `[graph_proximity]` from cortex.toml, for `cortex_recall`.
+    pub graph_proximity: GraphProximity,
+    /// `[write_gate]` from cortex.toml, checked by `cortex_store` and
+    /// `cortex_update`.
+    pub write_gate: WriteGateConfig,
+    /// Schemas and node kinds from cortex.toml.
+    pub schema: SchemaValidator,
+}
+
+/// How MCP clients talk to this process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum McpTransport {
+    /// One client, JSON-RPC lines over stdin/stdout.
+    Stdio,
+    /// Any number of clients over HTTP + Server-Sent Events.
+    Sse { bind: SocketAddr },
 }
 
 pub async fn run(args: McpArgs) -> Result<()> {
     if let Some(ref server) = args.server {
+        if args.transport != McpTransport::Stdio {
+            anyhow::bail!("--server only supports the stdio transport");
+        }
         return run_remote(server).await;
     }
 
@@ -35,7 +69,19 @@ pub async fn run(args: McpArgs) -> Result<()> {
     eprintln!("[cortex-mcp] Opening database: {}", db_path.display());
     eprintln!("[cortex-mcp] Initializing embedding model (first run may download model files)...");
 
-    let cortex = Cortex::open(&db_path, LibraryConfig::default())?;
+    let cortex = Cortex::open(
+        &db_path,
+        LibraryConfig {
+            briefing_sections: args.briefing_sections,
+            graph_proximity: args.graph_proximity,
+            write_gate: Some(args.write_gate),
+            schema: args.schema,
+            ..LibraryConfig::default()
+        },
+    )?;
+    if let McpTransport::Sse { bind } = args.transport {
+        return sse::serve(cortex, bind).await;
+    }
     eprintln!("[cortex-mcp] Ready. Listening on stdio (JSON-RPC 2.0).");
 
     let stdin = tokio::io::stdin();
@@ -43,14 +89,24 @@ pub async fn run(args: McpArgs) -> Result<()> {
     let mut reader = BufReader::new(stdin).lines();
     let mut out = tokio::io::BufWriter::new(stdout);
 
-    while let Some(line) = reader.next_line().await? {
-        let line = line.trim().to_string();
-        if line.is_empty() {
-            continue;
-        }
+    let subs = Subscriptions::default();
+    let mut poll = tokio::time::interval(subscriptions::POLL_INTERVAL);
 
-        if let Some(response) = dispatch(&cortex, &line) {
-            let bytes = serde_json::to_vec(&response)?;
+    loop {
+        let messages = tokio::select! {
+            line = reader.next_line() => {
+                let Some(line) = line? else { break };
+                let line = line.trim().to_string();
+                if line.is_empty() {
+                    continue;
+                }
+                dispatch(&cortex, &subs, &line).into_iter().collect()
+            }
+            _ = poll.tick() => subs.poll(&cortex),
+        };
+
+        for message in messages {
+            let bytes = serde_json::to_vec(&message)?;
             out.write_all(&bytes).await?;
             out.write_all(b"\n").await?;
             out.flush().await?;
@@ -79,7 +135,7 @@ fn default_data_dir() -> PathBuf {
 
 /// Parse an incoming JSON-RPC message and produce a response (if any).
 /// Notifications (no `id`) return None.
-fn dispatch(cortex: &Cortex, line: &str) -> Option<Value> {
+fn dispatch(cortex: &Cortex, subs: &Subscriptions, line: &str) -> Option<Value> {
     let msg: Value = match serde_json::from_str(line) {
         Ok(v) => v,
         Err(e) => {
@@ -104,7 +160,7 @@ fn dispatch(cortex: &Cortex, line: &str) -> Option<Value> {
         .cloned()
         .unwrap_or_else(|| Value::Object(Default::default()));
 
-    let result = route(cortex, &method, &params);
+    let result = route(cortex, subs, &method, &params);
 
     Some(match result {
         Ok(value) => json!({
@@ -123,13 +179,13 @@ fn dispatch(cortex: &Cortex, line: &str) -> Option<Value> {
     })
 }
 
-fn route(cortex: &Cortex, method: &str, params: &Value) -> Result<Value> {
+fn route(cortex: &Cortex, subs: &Subscriptions, method: &str, params: &Value) -> Result<Value> {
     match method {
         "initialize" => Ok(json!({
             "protocolVersion": "2024-11-05",
             "capabilities": {
                 "tools": {},
-                "resources": {}
+                "resources": { "subscribe": true }
             },
             "serverInfo": {
                 "name": "cortex",
@@ -183,6 +239,22 @@ fn route(cortex: &Cortex, method: &str, params: &Value) -> Result<Value> {
             read_resource(cortex, uri)
         }
 
+        "resources/subscribe" => {
+            let uri = params["uri"]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("uri required"))?;
+            subs.subscribe(cortex, uri)?;
+            Ok(json!({}))
+        }
+
+        "resources/unsubscribe" => {
+            let uri = params["uri"]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("uri required"))?;
+            subs.unsubscribe(uri);
+            Ok(json!({}))
+        }
+
         "ping" => Ok(json!({})),
 
         _ => Err(anyhow::anyhow!("Method not found: {}", method)),
@@ -222,11 +294,65 @@ fn tools_schema() -> Value {
                             "type": "number",
                             "description": "0.0 to 1.0. Higher = retained longer, weighted more in search.",
                             "default": 0.5
+                        },
+                        "expires_at": {
+                            "type": "string",
+                            "description": "Optional RFC 3339 time after which this stops being true, e.g. a temporary constraint. Expired knowledge drops out of search and briefings."
                         }
                     },
                     "required": ["title"]
                 }
             },
+            {
+                "name": "cortex_update",
+                "description": "Correct a node already in graph memory. Only the fields you pass are changed. Use this instead of storing a second, conflicting node.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "UUID of the node to update"
+                        },
+                        "title": {
+                            "type": "string",
+                            "description": "New title"
+                        },
+                        "body": {
+                            "type": "string",
+                            "description": "New body"
+                        },
+                        "importance": {
+                            "type": "number",
+                            "description": "New importance, 0.0 to 1.0"
+                        },
+                        "tags": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "Replacement tag list. Lowercase alphanumeric + hyphens only."
+                        }
+                    },
+                    "required": ["id"]
+                }
+            },
+            {
+                "name": "cortex_delete",
+                "description": "Delete a wrong or obsolete node from graph memory. Call without confirm to preview what would be deleted, then again with confirm=true to delete it.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "UUID of the node to delete"
+                        },
+                        "confirm": {
+                            "type": "boolean",
+                            "description": "Must be true to actually delete. Otherwise only a preview is returned.",
+                            "default": false
+                        }
+                    },
+                    "required": ["id"]
+                }
+            },
             {
                 "name": "cortex_search",
                 "description": "Search graph memory by meaning. Returns the most relevant nodes ranked by semantic similarity.",
@@ -245,6 +371,10 @@ fn tools_schema() -> Value {
                         "kind": {
                             "type": "string",
                             "description": "Optional: filter by node kind (e.g. fact, goal, decision)"
+                        },
+                        "cursor": {
+                            "type": "string",
+                            "description": "next_cursor from a previous call, or empty to start paging. When set, results come back as { results, next_cursor }"
                         }
                     },
                     "required": ["query"]
@@ -268,6 +398,10 @@ fn tools_schema() -> Value {
                             "type": "number",
                             "description": "Balance: 0.0 = pure graph, 1.0 = pure vector. Default 0.7",
                             "default": 0.7
+                        },
+                        "cursor": {
+                            "type": "string",
+                            "description": "next_cursor from a previous call, or empty to start paging. When set, results come back as { results, next_cursor }"
                         }
                     },
                     "required": ["query"]
@@ -316,6 +450,29 @@ fn tools_schema() -> Value {
                     "required": ["node_id"]
                 }
             },
+            {
+                "name": "cortex_path",
+                "description": "Find the shortest chain of edges connecting two nodes. Answers \"how are A and B related?\"",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "from_id": {
+                            "type": "string",
+                            "description": "Starting node UUID"
+                        },
+                        "to_id": {
+                            "type": "string",
+                            "description": "Target node UUID"
+                        },
+                        "max_hops": {
+                            "type": "integer",
+                            "description": "Longest path to consider, in edges",
+                            "default": 6
+                        }
+                    },
+                    "required": ["from_id", "to_id"]
+                }
+            },
             {
                 "name": "cortex_relate",
                 "description": "Create a relationship between two nodes in the knowledge graph. Use to explicitly connect related concepts.",
@@ -334,6 +491,11 @@ fn tools_schema() -> Value {
                             "type": "string",
                             "description": "Relationship type: relates_to, supports, contradicts, caused_by, depends_on, similar_to, supersedes",
                             "default": "relates_to"
+                        },
+                        "inverse": {
+                            "type": "boolean",
+                            "description": "Also create the reverse edge for symmetric relations (similar_to, contradicts) or ones with a named inverse (supersedes → superseded_by)",
+                            "default": false
                         }
                     },
                     "required": ["from_id", "to_id"]
@@ -395,8 +557,11 @@ fn call_tool(cortex: &Cortex, name: &str, args: &Value) -> Result<String> {
         "cortex_recall" => tool_recall(cortex, args),
         "cortex_briefing" => tool_briefing(cortex, args),
         "cortex_traverse" => tool_traverse(cortex, args),
+        "cortex_path" => tool_path(cortex, args),
         "cortex_relate" => tool_relate(cortex, args),
         "cortex_observe" => tool_observe(cortex, args),
+        "cortex_update" => tool_update(cortex, args),
+        "cortex_delete" => tool_delete(cortex, args),
         _ => Err(anyhow::anyhow!("Unknown tool: {}", name)),
     }
 }
@@ -412,28 +577,16 @@ fn tool_store(cortex: &Cortex, args: &Value) -> Result<String> {
         .and_then(|v| v.as_str())
         .unwrap_or(&title)
         .to_string();
-    let importance = args
-        .get("importance")
-        .and_then(|v| v.as_f64())
-        .unwrap_or(0.5) as f32;
-
-    // Normalise tags: lowercase, spaces→hyphens, drop invalid chars
-    let tags: Vec<String> = args
-        .get("tags")
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str())
-                .map(|s| s.to_lowercase().replace(' ', "-"))
-                .filter(|s| {
-                    !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
-                })
-                .collect()
-        })
-        .unwrap_or_default();
+    let tags = normalise_tags(args).unwrap_or_default();
 
     let kind = NodeKind::new(kind_str)
         .map_err(|e| anyhow::anyhow!("Invalid kind '{}': {}", kind_str, e))?;
+    let importance = cortex.schema_validator().kinds().importance(
+        &kind,
+        args.get("importance")
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32),
+    );
 
     let mut node = Node::new(
         kind,
@@ -447,7 +600,29 @@ fn tool_store(cortex: &Cortex, args: &Value) -> Result<String> {
         importance,
     );
     node.data.tags = tags;
+    if let Some(expires_at) = args.get("expires_at").and_then(|v| v.as_str()) {
+        let expires_at = chrono::DateTime::parse_from_rfc3339(expires_at)
+            .map_err(|e| anyhow::anyhow!("Invalid expires_at '{}': {}", expires_at, e))?;
+        node.expires_at = Some(expires_at.with_timezone(&chrono::Utc));
+    }
 
+    match write_gate(cortex) {
+        Some(gate) => gate
+            .admit(
+                &mut node,
+                cortex.storage(),
+                cortex.embedding_service(),
+                cortex.vector_index(),
+            )?
+            .map(|_| ())
+            .map_err(|rejections| anyhow::anyhow!(describe(&rejections)))?,
+        // Without a gate, strict kinds still hold
+        None => {
+            if let GateResult::Reject(r) = WriteGate::check_kind(&node, cortex.schema_validator()) {
+                anyhow::bail!(describe(&[r]));
+            }
+        }
+    }
     let id = cortex.store(node)?;
     Ok(serde_json::to_string(&json!({
         "id": id.to_string(),
@@ -455,26 +630,152 @@ fn tool_store(cortex: &Cortex, args: &Value) -> Result<String> {
     }))?)
 }
 
+/// Tags from `args["tags"]`: lowercased, spaces→hyphens, invalid ones dropped.
+fn normalise_tags(args: &Value) -> Option<Vec<String>> {
+    args.get("tags").and_then(|v| v.as_array()).map(|arr| {
+        arr.iter()
+            .filter_map(|v| v.as_str())
+            .map(|s| s.to_lowercase().replace(' ', "-"))
+            .filter(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+            .collect()
+    })
+}
+
+fn parse_node_id(args: &Value) -> Result<NodeId> {
+    let id_str = args["id"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("id is required"))?;
+    Uuid::parse_str(id_str).map_err(|_| anyhow::anyhow!("Invalid id: not a UUID"))
+}
+
+/// The write gate `cortex_store` and `cortex_update` run, when configured.
+fn write_gate(cortex: &Cortex) -> Option<GatedWrite<'_>> {
+    cortex
+        .write_gate()
+        .map(|config| GatedWrite::new(config, cortex.schema_validator()).caller("mcp"))
+}
+
+fn tool_update(cortex: &Cortex, args: &Value) -> Result<String> {
+    let id = parse_node_id(args)?;
+    let before = cortex
+        .get_node(id)?
+        .filter(|n| !n.deleted)
+        .ok_or_else(|| anyhow::anyhow!("Node not found: {}", id))?;
+    let mut node = before.clone();
+
+    let mut changed = Vec::new();
+    if let Some(title) = args.get("title").and_then(|v| v.as_str()) {
+        node.data.title = title.to_string();
+        changed.push("title");
+    }
+    if let Some(body) = args.get("body").and_then(|v| v.as_str()) {
+        node.data.body = body.to_string();
+        changed.push("body");
+    }
+    if let Some(importance) = args.get("importance").and_then(|v| v.as_f64()) {
+        node.importance = (importance as f32).clamp(0.0, 1.0);
+        changed.push("importance");
+    }
+    if let Some(tags) = normalise_tags(args) {
+        node.data.tags = tags;
+        changed.push("tags");
+    }
+    if changed.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Nothing to update: pass title, body, importance or tags"
+        ));
+    }
+
+    if let Some(gate) = write_gate(cortex) {
+        gate.update(&before, &mut node)
+            .map_err(|rejections| anyhow::anyhow!(describe(&rejections)))?;
+    }
+    let title = node.data.title.clone();
+    cortex.update(node)?;
+    Ok(serde_json::to_string(&json!({
+        "id": id.to_string(),
+        "updated": changed,
+        "message": format!("Updated: {title}"),
+    }))?)
+}
+
+fn tool_delete(cortex: &Cortex, args: &Value) -> Result<String> {
+    let id = parse_node_id(args)?;
+    let confirm = args
+        .get("confirm")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let Some(node) = cortex.get_node(id)?.filter(|n| !n.deleted) else {
+        return Ok(serde_json::to_string(&json!({
+            "id": id.to_string(),
+            "deleted": false,
+            "message": "No such node",
+        }))?);
+    };
+    if !confirm {
+        return Ok(serde_json::to_string(&json!({
+            "id": id.to_string(),
+            "deleted": false,
+            "message": format!(
+                "Would delete [{}] {}. Call again with confirm=true to delete.",
+                node.kind.as_str(),
+                node.data.title
+            ),
+        }))?);
+    }
+
+    let deleted = cortex.delete(id)?;
+    Ok(serde_json::to_string(&json!({
+        "id": id.to_string(),
+        "deleted": deleted,
+        "message": format!("Deleted: {}", node.data.title),
+    }))?)
+}
+
 fn tool_search(cortex: &Cortex, args: &Value) -> Result<String> {
     let query = args["query"]
         .as_str()
         .ok_or_else(|| anyhow::anyhow!("query is required"))?;
     let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(10) as usize;
-    let kind_filter = args.get("kind").and_then(|v| v.as_str()).map(String::from);
-
-    // Fetch extra results when kind-filtering so we hit the requested limit
-    let fetch = if kind_filter.is_some() {
-        (limit * 4).max(1)
-    } else {
-        limit.max(1)
-    };
+    let cursor = parse_cursor(args)?;
 
-    let mut results = cortex.search(query, fetch).unwrap_or_default();
-    if let Some(ref k) = kind_filter {
-        results.retain(|(_, n)| n.kind.as_str() == k.as_str());
+    let mut filter = VectorFilter::new();
+    if let Some(kind) = args.get("kind").and_then(|v| v.as_str()) {
+        filter = filter.with_kinds(vec![NodeKind::new(kind)?]);
     }
-    results.truncate(limit);
+    let (results, next_cursor) = page_search(
+        |n| {
+            let results = cortex
+                .search_filtered(query, n, &filter)
+                .unwrap_or_default();
+            let exhausted = results.len() < n;
+            Ok::<_, anyhow::Error>((results, exhausted))
+        },
+        |(s, n)| (*s, n.id),
+        cursor.as_ref(),
+        limit,
+    )?;
+
+    Ok(serde_json::to_string_pretty(&search_page(
+        args,
+        &results,
+        next_cursor,
+    ))?)
+}
+
+fn parse_cursor(args: &Value) -> Result<Option<SearchCursor>> {
+    Ok(args
+        .get("cursor")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(str::parse)
+        .transpose()?)
+}
 
+/// Search results as a bare array, or as `{ results, next_cursor }` when the
+/// caller passed `cursor` (empty for the first page).
+fn search_page(args: &Value, results: &[(f32, Node)], next_cursor: Option<SearchCursor>) -> Value {
     let items: Vec<Value> = results
         .iter()
         .map(|(score, n)| {
@@ -488,8 +789,13 @@ fn tool_search(cortex: &Cortex, args: &Value) -> Result<String> {
             })
         })
         .collect();
-
-    Ok(serde_json::to_string_pretty(&items)?)
+    if args.get("cursor").is_none() {
+        return Value::Array(items);
+    }
+    json!({
+        "results": items,
+        "next_cursor": next_cursor.map(|c| c.to_string()),
+    })
 }
 
 fn tool_recall(cortex: &Cortex, args: &Value) -> Result<String> {
@@ -498,11 +804,31 @@ fn tool_recall(cortex: &Cortex, args: &Value) -> Result<String> {
         .ok_or_else(|| anyhow::anyhow!("query is required"))?;
     let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(10) as usize;
     let _alpha = args.get("alpha").and_then(|v| v.as_f64()).unwrap_or(0.7) as f32;
+    let cursor = parse_cursor(args)?;
+    let (expanded, next_cursor) = page_search(
+        |fetch| Ok::<_, anyhow::Error>(recall_candidates(cortex, query, fetch)),
+        |(s, n)| (*s, n.id),
+        cursor.as_ref(),
+        limit,
+    )?;
+
+    Ok(serde_json::to_string_pretty(&search_page(
+        args,
+        &expanded,
+        next_cursor,
+    ))?)
+}
 
+/// The best `fetch` vector matches for `query` plus their graph neighbours,
+/// and whether the index had no more matches to give.
+fn recall_candidates(cortex: &Cortex, query: &str, fetch: usize) -> (Vec<(f32, Node)>, bool) {
     // Phase 1: vector search
-    let seeds = cortex.search(query, limit).unwrap_or_default();
+    let seeds = cortex.search(query, fetch).unwrap_or_default();
+    let exhausted = seeds.len() < fetch;
 
-    // Phase 2: graph expansion — include 1-hop neighbours of top results
+    // Phase 2: graph expansion — include neighbours of top results, out to
+    // `graph_proximity.max_depth` hops
+    let proximity = cortex.graph_proximity();
     let mut seen: std::collections::HashSet<NodeId> = std::collections::HashSet::new();
     let mut expanded: Vec<(f32, Node)> = Vec::new();
 
@@ -510,36 +836,27 @@ fn tool_recall(cortex: &Cortex, args: &Value) -> Result<String> {
         if seen.insert(node.id) {
             expanded.push((*score, node.clone()));
         }
-        if expanded.len() < limit * 2 {
-            if let Ok(sg) = cortex.traverse(node.id, 1) {
-                for neighbour in sg.nodes.values() {
+        if expanded.len() < fetch * 2 && proximity.max_depth > 0 {
+            if let Ok(sg) = cortex.traverse(node.id, proximity.max_depth) {
+                // Nearest first, so a node reachable two ways keeps its
+                // better score
+                let mut neighbours: Vec<(u32, &Node)> = sg
+                    .nodes
+                    .values()
+                    .map(|n| (sg.depths.get(&n.id).copied().unwrap_or(1), n))
+                    .collect();
+                neighbours.sort_by_key(|(depth, n)| (*depth, n.id));
+                for (depth, neighbour) in neighbours {
                     if seen.insert(neighbour.id) {
-                        // Neighbours get a discounted score
-                        expanded.push((score * 0.6, neighbour.clone()));
+                        // Neighbours get a per-hop discounted score
+                        expanded.push((score * proximity.weight(depth), neighbour.clone()));
                     }
                 }
             }
         }
     }
 
-    expanded.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
-    expanded.truncate(limit);
-
-    let items: Vec<Value> = expanded
-        .iter()
-        .map(|(score, n)| {
-            json!({
-                "id": n.id.to_string(),
-                "kind": n.kind.as_str(),
-                "title": n.data.title,
-                "body": n.data.body,
-                "score": score,
-                "created_at": n.created_at.to_rfc3339(),
-            })
-        })
-        .collect();
-
-    Ok(serde_json::to_string_pretty(&items)?)
+    (expanded, exhausted)
 }
 
 fn tool_briefing(cortex: &Cortex, args: &Value) -> Result<String> {
@@ -551,28 +868,65 @@ fn tool_briefing(cortex: &Cortex, args: &Value) -> Result<String> {
         .get("compact")
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
-    let limit = if compact { 3 } else { 8 };
+    let default_limit = if compact { 3 } else { 8 };
 
     let mut md = String::from("# Context Briefing\n\n");
     let mut has_content = false;
 
-    let sections: &[(&str, &str)] = &[
-        ("goal", "## Active Goals"),
-        ("decision", "## Recent Decisions"),
-        ("pattern", "## Patterns"),
-        ("observation", "## Observations"),
-    ];
+    let defaults = BriefingSectionSpec::defaults();
+    let sections = cortex.briefing_sections().unwrap_or(&defaults);
 
-    for (kind_str, heading) in sections {
-        let kind = NodeKind::new(kind_str).unwrap();
-        let nodes = cortex
-            .list_nodes(NodeFilter::new().with_kinds(vec![kind]).with_limit(limit))
-            .unwrap_or_default();
+    for spec in sections {
+        let kinds: Vec<NodeKind> = spec
+            .kinds
+            .iter()
+            .filter_map(|k| NodeKind::new(k).ok())
+            .collect();
+        let limit = spec.limit.unwrap_or(default_limit);
+        let limit = if compact { limit.min(3) } else { limit };
+        let filter = NodeFilter::new()
+            .with_kinds(kinds)
+            .with_min_importance(spec.min_importance.unwrap_or(0.0));
+        // Expired nodes stay out of briefings, as in the briefing engine
+        let mut nodes: Vec<Node> = match spec.order {
+            // Listing runs newest first, so the most important nodes can be
+            // anywhere in it
+            SectionOrder::Importance => {
+                let mut nodes: Vec<Node> = cortex
+                    .list_nodes(filter)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|n| !n.is_expired())
+                    .collect();
+                nodes.sort_by(|a, b| b.importance.total_cmp(&a.importance));
+                nodes
+            }
+            // ...but the most recent are at the front: page past expired
+            // nodes until the section is full
+            SectionOrder::Recent => {
+                let mut nodes = Vec::new();
+                let mut cursor = None;
+                while nodes.len() < limit {
+                    let mut page_filter = filter.clone().with_limit(limit);
+                    if let Some(c) = cursor {
+                        page_filter = page_filter.after(c);
+                    }
+                    let page = cortex.list_nodes(page_filter).unwrap_or_default();
+                    let full = page.len() == limit;
+                    cursor = page.last().map(NodeCursor::after);
+                    nodes.extend(page.into_iter().filter(|n| !n.is_expired()));
+                    if !full {
+                        break;
+                    }
+                }
+                nodes
+            }
+        };
+        nodes.truncate(limit);
 
         if !nodes.is_empty() {
             has_content = true;
-            md.push_str(heading);
-            md.push('\n');
+            md.push_str(&format!("## {}\n", spec.heading));
             for n in &nodes {
                 if compact {
                     md.push_str(&format!("- {}\n", n.data.title));
@@ -586,25 +940,6 @@ fn tool_briefing(cortex: &Cortex, args: &Value) -> Result<String> {
         }
     }
 
-    // High-importance facts
-    let facts = cortex
-        .list_nodes(
-            NodeFilter::new()
-                .with_kinds(vec![NodeKind::new("fact").unwrap()])
-                .with_min_importance(0.7)
-                .with_limit(limit),
-        )
-        .unwrap_or_default();
-
-    if !facts.is_empty() {
-        has_content = true;
-        md.push_str("## Key Facts\n");
-        for n in &facts {
-            md.push_str(&format!("- {}\n", n.data.title));
-        }
-        md.push('\n');
-    }
-
     if !has_content {
         md.push_str("*No memory stored yet. Use `cortex_store` to add knowledge.*\n");
     }
@@ -665,6 +1000,64 @@ fn tool_traverse(cortex: &Cortex, args: &Value) -> Result<String> {
     }))?)
 }
 
+fn tool_path(cortex: &Cortex, args: &Value) -> Result<String> {
+    let parse = |field: &str| -> Result<NodeId> {
+        let s = args[field]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("{} is required", field))?;
+        Uuid::parse_str(s).map_err(|_| anyhow::anyhow!("Invalid {}: not a valid UUID", field))
+    };
+    let from = parse("from_id")?;
+    let to = parse("to_id")?;
+    let max_hops = args
+        .get("max_hops")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_PATH_HOPS as u64) as u32;
+
+    let Some(path) = cortex.shortest_path(from, to, Some(max_hops))? else {
+        return Ok(serde_json::to_string_pretty(&json!({
+            "found": false,
+            "nodes": [],
+            "edges": [],
+            "length": 0,
+        }))?);
+    };
+
+    let nodes: Vec<Value> = path
+        .nodes
+        .iter()
+        .filter_map(|id| cortex.get_node(*id).ok().flatten())
+        .map(|n| {
+            json!({
+                "id": n.id.to_string(),
+                "kind": n.kind.as_str(),
+                "title": n.data.title,
+            })
+        })
+        .collect();
+    let edges: Vec<Value> = path
+        .edges
+        .iter()
+        .filter_map(|id| cortex.get_edge(*id).ok().flatten())
+        .map(|e| {
+            json!({
+                "id": e.id.to_string(),
+                "from": e.from.to_string(),
+                "to": e.to.to_string(),
+                "relation": e.relation.as_str(),
+                "weight": e.weight,
+            })
+        })
+        .collect();
+
+    Ok(serde_json::to_string_pretty(&json!({
+        "found": true,
+        "nodes": nodes,
+        "edges": edges,
+        "length": path.length,
+    }))?)
+}
+
 fn tool_relate(cortex: &Cortex, args: &Value) -> Result<String> {
     let from_str = args["from_id"]
         .as_str()
@@ -707,10 +1100,16 @@ fn tool_relate(cortex: &Cortex, args: &Value) -> Result<String> {
         },
     );
     let edge_id = edge.id;
-    cortex.create_edge(edge)?;
+    let inverse = if args.get("inverse").and_then(|v| v.as_bool()) == Some(true) {
+        cortex.create_edge_with_inverse(edge)?
+    } else {
+        cortex.create_edge(edge)?;
+        None
+    };
 
     Ok(serde_json::to_string(&json!({
         "id": edge_id.to_string(),
+        "inverse_id": inverse.map(|e| e.id.to_string()),
         "message": format!("Related: {} → {} → {}", from_title, relation_str, to_title),
     }))?)
 }
@@ -810,20 +1209,16 @@ fn tool_observe(cortex: &Cortex, args: &Value) -> Result<String> {
 
     // Link: agent --performed--> observation (best-effort — skip if agent node not found)
     let agent_kind = cortex_core::kinds::defaults::agent();
-    if let Ok(agents) =
-        cortex.list_nodes(cortex_core::storage::NodeFilter::new().with_kinds(vec![agent_kind]))
-    {
-        if let Some(agent) = agents.into_iter().find(|n| n.data.title == agent_name) {
-            let _ = cortex.create_edge(Edge::new(
-                agent.id,
-                obs_id,
-                cortex_core::relations::defaults::performed(),
-                1.0,
-                EdgeProvenance::Manual {
-                    created_by: "mcp".into(),
-                },
-            ));
-        }
+    if let Ok(Some(agent)) = cortex.get_node_by_title(&agent_kind, agent_name) {
+        let _ = cortex.create_edge(Edge::new(
+            agent.id,
+            obs_id,
+            cortex_core::relations::defaults::performed(),
+            1.0,
+            EdgeProvenance::Manual {
+                created_by: "mcp".into(),
+            },
+        ));
     }
 
     // Link: observation --informed_by--> variant
@@ -1056,6 +1451,33 @@ fn tools_list() -> Value {
                     "required": ["title"]
                 }
             },
+            {
+                "name": "cortex_update",
+                "description": "Correct a node already in graph memory",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "string" },
+                        "title": { "type": "string" },
+                        "body": { "type": "string" },
+                        "importance": { "type": "number" },
+                        "tags": { "type": "array", "items": { "type": "string" } }
+                    },
+                    "required": ["id"]
+                }
+            },
+            {
+                "name": "cortex_delete",
+                "description": "Delete a node from graph memory (requires confirm=true)",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "string" },
+                        "confirm": { "type": "boolean", "default": false }
+                    },
+                    "required": ["id"]
+                }
+            },
             {
                 "name": "cortex_search",
                 "description": "Search graph memory by meaning",
@@ -1064,7 +1486,8 @@ fn tools_list() -> Value {
                     "properties": {
                         "query": { "type": "string" },
                         "limit": { "type": "integer", "default": 10 },
-                        "kind": { "type": "string" }
+                        "kind": { "type": "string" },
+                        "cursor": { "type": "string" }
                     },
                     "required": ["query"]
                 }
@@ -1076,7 +1499,8 @@ fn tools_list() -> Value {
                     "type": "object",
                     "properties": {
                         "query": { "type": "string" },
-                        "limit": { "type": "integer", "default": 10 }
+                        "limit": { "type": "integer", "default": 10 },
+                        "cursor": { "type": "string" }
                     },
                     "required": ["query"]
                 }
@@ -1105,6 +1529,19 @@ fn tools_list() -> Value {
                     "required": ["node_id"]
                 }
             },
+            {
+                "name": "cortex_path",
+                "description": "Find the shortest path between two nodes",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "from_id": { "type": "string" },
+                        "to_id": { "type": "string" },
+                        "max_hops": { "type": "integer", "default": 6 }
+                    },
+                    "required": ["from_id", "to_id"]
+                }
+            },
             {
                 "name": "cortex_relate",
                 "description": "Create a relationship between two nodes",
@@ -1113,7 +1550,8 @@ fn tools_list() -> Value {
                     "properties": {
                         "from_id": { "type": "string" },
                         "to_id": { "type": "string" },
-                        "relation": { "type": "string", "default": "relates-to" }
+                        "relation": { "type": "string", "default": "relates-to" },
+                        "inverse": { "type": "boolean", "default": false }
                     },
                     "required": ["from_id", "to_id"]
                 }
@@ -1149,6 +1587,21 @@ fn resources_list() -> Value {
     })
 }
 
+/// GET a search endpoint, passing `cursor` through when the caller gave one
+/// so the body comes back shaped like the local search tools'.
+async fn remote_search_page(
+    http: &reqwest::Client,
+    mut url: String,
+    args: &Value,
+) -> Result<Value> {
+    if let Some(cursor) = args.get("cursor") {
+        url.push_str("&cursor=");
+        url.push_str(&urlencoding::encode(cursor.as_str().unwrap_or("")));
+    }
+    let body: Value = http.get(url).send().await?.json().await?;
+    Ok(body["data"].clone())
+}
+
 async fn remote_tool_call(
     http: &reqwest::Client,
     base_url: &str,
@@ -1181,37 +1634,37 @@ async fn remote_tool_call(
         "cortex_search" => {
             let query = args.get("query").and_then(|v| v.as_str()).unwrap_or("");
             let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(10);
-            let resp: Value = http
-                .get(format!(
+            let page = remote_search_page(
+                http,
+                format!(
                     "{}/search?q={}&limit={}",
                     base_url,
                     urlencoding::encode(query),
                     limit
-                ))
-                .send()
-                .await?
-                .json()
-                .await?;
+                ),
+                args,
+            )
+            .await?;
             Ok(json!({
-                "content": [{ "type": "text", "text": serde_json::to_string_pretty(&resp["data"])? }]
+                "content": [{ "type": "text", "text": serde_json::to_string_pretty(&page)? }]
             }))
         }
         "cortex_recall" => {
             let query = args.get("query").and_then(|v| v.as_str()).unwrap_or("");
             let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(10);
-            let resp: Value = http
-                .get(format!(
+            let page = remote_search_page(
+                http,
+                format!(
                     "{}/search/hybrid?q={}&limit={}",
                     base_url,
                     urlencoding::encode(query),
                     limit
-                ))
-                .send()
-                .await?
-                .json()
-                .await?;
+                ),
+                args,
+            )
+            .await?;
             Ok(json!({
-                "content": [{ "type": "text", "text": serde_json::to_string_pretty(&resp["data"])? }]
+                "content": [{ "type": "text", "text": serde_json::to_string_pretty(&page)? }]
             }))
         }
         "cortex_briefing" => {
@@ -1261,6 +1714,28 @@ async fn remote_tool_call(
                 "content": [{ "type": "text", "text": serde_json::to_string_pretty(&resp["data"])? }]
             }))
         }
+        "cortex_path" => {
+            let from_id = args.get("from_id").and_then(|v| v.as_str()).unwrap_or("");
+            let to_id = args.get("to_id").and_then(|v| v.as_str()).unwrap_or("");
+            let max_hops = args
+                .get("max_hops")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(DEFAULT_PATH_HOPS as u64);
+            let resp: Value = http
+                .get(format!("{}/graph/path", base_url))
+                .query(&[
+                    ("from", from_id.to_string()),
+                    ("to", to_id.to_string()),
+                    ("max_hops", max_hops.to_string()),
+                ])
+                .send()
+                .await?
+                .json()
+                .await?;
+            Ok(json!({
+                "content": [{ "type": "text", "text": serde_json::to_string_pretty(&resp["data"])? }]
+            }))
+        }
         "cortex_observe" => {
             let agent_name = args
                 .get("agent_name")
@@ -1286,9 +1761,13 @@ async fn remote_tool_call(
                 .get("relation")
                 .and_then(|v| v.as_str())
                 .unwrap_or("relates-to");
+            let inverse = args
+                .get("inverse")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
             let resp: Value = http
                 .post(format!("{}/edges", base_url))
-                .json(&json!({ "from_id": from_id, "to_id": to_id, "relation": relation }))
+                .json(&json!({ "from_id": from_id, "to_id": to_id, "relation": relation, "inverse": inverse }))
                 .send()
                 .await?
                 .json()
@@ -1298,6 +1777,70 @@ async fn remote_tool_call(
                 "content": [{ "type": "text", "text": format!("Related: {} -> [{}] -> {} (edge: {})", from_id, relation, to_id, id) }]
             }))
         }
+        "cortex_update" => {
+            let id = parse_node_id(args)?;
+            let mut patch = serde_json::Map::new();
+            for field in ["title", "body", "importance"] {
+                if let Some(v) = args.get(field) {
+                    patch.insert(field.into(), v.clone());
+                }
+            }
+            if let Some(tags) = normalise_tags(args) {
+                patch.insert("tags".into(), json!(tags));
+            }
+            let resp: Value = http
+                .patch(format!("{}/nodes/{}", base_url, id))
+                .json(&patch)
+                .send()
+                .await?
+                .json()
+                .await?;
+            if resp["success"] != json!(true) {
+                return Err(anyhow::anyhow!(
+                    "Update failed: {}",
+                    resp["error"].as_str().unwrap_or("unknown error")
+                ));
+            }
+            Ok(json!({
+                "content": [{ "type": "text", "text": format!("Updated: {} (id: {})", resp["data"]["title"].as_str().unwrap_or(""), id) }]
+            }))
+        }
+        "cortex_delete" => {
+            let id = parse_node_id(args)?;
+            let confirm = args
+                .get("confirm")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let node: Value = http
+                .get(format!("{}/nodes/{}", base_url, id))
+                .send()
+                .await?
+                .json()
+                .await?;
+            let text = if node["success"] != json!(true) {
+                json!({ "id": id.to_string(), "deleted": false, "message": "No such node" })
+            } else if !confirm {
+                json!({
+                    "id": id.to_string(),
+                    "deleted": false,
+                    "message": format!(
+                        "Would delete {}. Call again with confirm=true to delete.",
+                        node["data"]["title"].as_str().unwrap_or("")
+                    ),
+                })
+            } else {
+                let resp: Value = http
+                    .delete(format!("{}/nodes/{}", base_url, id))
+                    .send()
+                    .await?
+                    .json()
+                    .await?;
+                json!({ "id": id.to_string(), "deleted": resp["success"] == json!(true) })
+            };
+            Ok(json!({
+                "content": [{ "type": "text", "text": text.to_string() }]
+            }))
+        }
         _ => Err(anyhow::anyhow!("Unknown tool: {}", name)),
     }
 }
@@ -1341,17 +1884,21 @@ mod tests {
     fn test_dispatch_initialize() {
         let cortex = make_cortex();
         let msg = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2024-11-05","capabilities":{},"clientInfo":{"name":"test","version":"1.0"}}}"#;
-        let resp = dispatch(&cortex, msg).unwrap();
+        let resp = dispatch(&cortex, &Subscriptions::default(), msg).unwrap();
         assert_eq!(resp["id"], 1);
         assert!(resp["result"]["protocolVersion"].as_str().is_some());
         assert!(resp["result"]["capabilities"].is_object());
+        assert_eq!(
+            resp["result"]["capabilities"]["resources"]["subscribe"],
+            true
+        );
     }
 
     #[test]
     fn test_dispatch_tools_list() {
         let cortex = make_cortex();
         let msg = r#"{"jsonrpc":"2.0","id":2,"method":"tools/list","params":{}}"#;
-        let resp = dispatch(&cortex, msg).unwrap();
+        let resp = dispatch(&cortex, &Subscriptions::default(), msg).unwrap();
         let tools = resp["result"]["tools"].as_array().unwrap();
         let names: Vec<&str> = tools.iter().map(|t| t["name"].as_str().unwrap()).collect();
         assert!(names.contains(&"cortex_store"));
@@ -1361,14 +1908,17 @@ mod tests {
         assert!(names.contains(&"cortex_traverse"));
         assert!(names.contains(&"cortex_relate"));
         assert!(names.contains(&"cortex_observe"));
-        assert_eq!(tools.len(), 7);
+        assert!(names.contains(&"cortex_update"));
+        assert!(names.contains(&"cortex_delete"));
+        assert!(names.contains(&"cortex_path"));
+        assert_eq!(tools.len(), 10);
     }
 
     #[test]
     fn test_dispatch_resources_list() {
         let cortex = make_cortex();
         let msg = r#"{"jsonrpc":"2.0","id":3,"method":"resources/list","params":{}}"#;
-        let resp = dispatch(&cortex, msg).unwrap();
+        let resp = dispatch(&cortex, &Subscriptions::default(), msg).unwrap();
         let resources = resp["result"]["resources"].as_array().unwrap();
         assert_eq!(resources.len(), 2);
         let uris: Vec<&str> = resources
@@ -1383,14 +1933,14 @@ mod tests {
     fn test_notification_no_response() {
         let cortex = make_cortex();
         let msg = r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#;
-        let resp = dispatch(&cortex, msg);
+        let resp = dispatch(&cortex, &Subscriptions::default(), msg);
         assert!(resp.is_none(), "Notifications must not produce a response");
     }
 
     #[test]
     fn test_parse_error_returns_error_response() {
         let cortex = make_cortex();
-        let resp = dispatch(&cortex, "this is not json").unwrap();
+        let resp = dispatch(&cortex, &Subscriptions::default(), "this is not json").unwrap();
         assert_eq!(resp["error"]["code"], -32700);
     }
 
@@ -1398,7 +1948,7 @@ mod tests {
     fn test_unknown_method_returns_error() {
         let cortex = make_cortex();
         let msg = r#"{"jsonrpc":"2.0","id":99,"method":"nonexistent","params":{}}"#;
-        let resp = dispatch(&cortex, msg).unwrap();
+        let resp = dispatch(&cortex, &Subscriptions::default(), msg).unwrap();
         assert!(resp["error"].is_object());
     }
 
@@ -1406,7 +1956,7 @@ mod tests {
     fn test_tools_store_missing_title() {
         let cortex = make_cortex();
         let msg = r#"{"jsonrpc":"2.0","id":10,"method":"tools/call","params":{"name":"cortex_store","arguments":{"kind":"fact"}}}"#;
-        let resp = dispatch(&cortex, msg).unwrap();
+        let resp = dispatch(&cortex, &Subscriptions::default(), msg).unwrap();
         // Missing title should produce an error
         assert!(resp.get("error").is_some() || resp["result"]["isError"] == true);
     }
@@ -1415,7 +1965,7 @@ mod tests {
     fn test_resource_stats_empty_graph() {
         let cortex = make_cortex();
         let msg = r#"{"jsonrpc":"2.0","id":20,"method":"resources/read","params":{"uri":"cortex://stats"}}"#;
-        let resp = dispatch(&cortex, msg).unwrap();
+        let resp = dispatch(&cortex, &Subscriptions::default(), msg).unwrap();
         assert!(resp["result"]["contents"].is_array());
         let text = resp["result"]["contents"][0]["text"].as_str().unwrap();
         let stats: Value = serde_json::from_str(text).unwrap();
@@ -1426,9 +1976,247 @@ mod tests {
     fn test_briefing_empty_graph() {
         let cortex = make_cortex();
         let msg = r#"{"jsonrpc":"2.0","id":30,"method":"tools/call","params":{"name":"cortex_briefing","arguments":{}}}"#;
-        let resp = dispatch(&cortex, msg).unwrap();
+        let resp = dispatch(&cortex, &Subscriptions::default(), msg).unwrap();
         let text = resp["result"]["content"][0]["text"].as_str().unwrap();
         let val: Value = serde_json::from_str(text).unwrap();
         assert!(val["briefing"].as_str().unwrap().contains("No memory"));
     }
+
+    #[test]
+    fn test_briefing_leaves_out_expired_nodes() {
+        use cortex_core::Storage;
+
+        let cortex = make_cortex();
+        let mut expired = Cortex::decision("Freeze ends Friday", "", 0.9);
+        expired.expires_at = Some(chrono::Utc::now() - chrono::Duration::hours(1));
+        let live = Cortex::decision("Deploys happen on Tuesdays", "", 0.5);
+        cortex.storage().put_node(&expired).unwrap();
+        cortex.storage().put_node(&live).unwrap();
+
+        let msg = r#"{"jsonrpc":"2.0","id":31,"method":"tools/call","params":{"name":"cortex_briefing","arguments":{}}}"#;
+        let resp = dispatch(&cortex, &Subscriptions::default(), msg).unwrap();
+        let text = resp["result"]["content"][0]["text"].as_str().unwrap();
+        let val: Value = serde_json::from_str(text).unwrap();
+        let briefing = val["briefing"].as_str().unwrap();
+        assert!(
+            briefing.contains("Deploys happen on Tuesdays"),
+            "{briefing}"
+        );
+        assert!(!briefing.contains("Freeze ends Friday"), "{briefing}");
+    }
+
+    fn call(cortex: &Cortex, name: &str, args: Value) -> Value {
+        let msg = json!({
+            "jsonrpc": "2.0", "id": 40, "method": "tools/call",
+            "params": { "name": name, "arguments": args }
+        });
+        let resp = dispatch(cortex, &Subscriptions::default(), &msg.to_string()).unwrap();
+        let text = resp["result"]["content"][0]["text"].as_str().unwrap();
+        serde_json::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn test_tools_update_patches_fields() {
+        let cortex = make_cortex();
+        let id = cortex
+            .store(Cortex::fact("Deploys happen on Fridays", 0.5))
+            .unwrap();
+
+        let resp = call(
+            &cortex,
+            "cortex_update",
+            json!({ "id": id.to_string(), "title": "Deploys happen on Tuesdays", "tags": ["Release Process"] }),
+        );
+        assert_eq!(resp["updated"], json!(["title", "tags"]));
+
+        let node = cortex.get_node(id).unwrap().unwrap();
+        assert_eq!(node.data.title, "Deploys happen on Tuesdays");
+        assert_eq!(node.data.tags, vec!["release-process".to_string()]);
+        assert_eq!(node.importance, 0.5);
+    }
+
+    #[test]
+    fn test_tools_update_clamps_importance_and_runs_gate() {
+        let dir = tempfile::tempdir().unwrap();
+        let cortex = Cortex::open(
+            dir.path().join("test.redb"),
+            LibraryConfig {
+                write_gate: Some(WriteGateConfig::default()),
+                ..LibraryConfig::default()
+            },
+        )
+        .unwrap();
+        let id = cortex
+            .store(Cortex::fact(
+                "Deploys happen on Fridays after the review",
+                0.5,
+            ))
+            .unwrap();
+
+        call(
+            &cortex,
+            "cortex_update",
+            json!({ "id": id.to_string(), "importance": 7.0 }),
+        );
+        assert_eq!(cortex.get_node(id).unwrap().unwrap().importance, 1.0);
+
+        let err = call_tool(
+            &cortex,
+            "cortex_update",
+            &json!({ "id": id.to_string(), "title": "short" }),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Write gate"), "{err}");
+        assert_eq!(
+            cortex.get_node(id).unwrap().unwrap().data.title,
+            "Deploys happen on Fridays after the review"
+        );
+    }
+
+    #[test]
+    fn test_tools_delete_requires_confirm() {
+        let cortex = make_cortex();
+        let id = cortex
+            .store(Cortex::fact("Staging uses the prod database", 0.5))
+            .unwrap();
+
+        let preview = call(&cortex, "cortex_delete", json!({ "id": id.to_string() }));
+        assert_eq!(preview["deleted"], false);
+        assert!(!cortex.get_node(id).unwrap().unwrap().deleted);
+
+        let args = json!({ "id": id.to_string(), "confirm": true });
+        assert_eq!(
+            call(&cortex, "cortex_delete", args.clone())["deleted"],
+            true
+        );
+        assert!(cortex.get_node(id).unwrap().unwrap().deleted);
+        // Already gone: nothing removed the second time
+        assert_eq!(call(&cortex, "cortex_delete", args)["deleted"], false);
+    }
+
+    #[test]
+    fn test_tools_path_between_nodes() {
+        let cortex = make_cortex();
+        let ids: Vec<NodeId> = ["Service A", "Service B", "Service C"]
+            .into_iter()
+            .map(|t| cortex.store(Cortex::fact(t, 0.5)).unwrap())
+            .collect();
+        for pair in ids.windows(2) {
+            cortex
+                .create_edge(Edge::new(
+                    pair[0],
+                    pair[1],
+                    Relation::new("depends_on").unwrap(),
+                    0.8,
+                    EdgeProvenance::Manual {
+                        created_by: "test".into(),
+                    },
+                ))
+                .unwrap();
+        }
+
+        let args = json!({ "from_id": ids[0].to_string(), "to_id": ids[2].to_string() });
+        let resp = call(&cortex, "cortex_path", args);
+        assert_eq!(resp["found"], true);
+        assert_eq!(resp["length"], 2);
+        let path: Vec<&str> = resp["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(path, ["Service A", "Service B", "Service C"]);
+        assert_eq!(resp["edges"][0]["relation"], "depends_on");
+
+        // Out of hop budget
+        let args = json!({
+            "from_id": ids[0].to_string(),
+            "to_id": ids[2].to_string(),
+            "max_hops": 1
+        });
+        let resp = call(&cortex, "cortex_path", args);
+        assert_eq!(resp["found"], false);
+        assert_eq!(resp["nodes"], json!([]));
+    }
+
+    #[test]
+    fn test_resource_subscribe_notifies_on_change() {
+        let cortex = make_cortex();
+        let subs = Subscriptions::default();
+        let watched = cortex.store(Cortex::fact("Watched node", 0.5)).unwrap();
+        let node_uri = format!("cortex://node/{}", watched);
+
+        for uri in ["cortex://stats", node_uri.as_str()] {
+            let msg = json!({
+                "jsonrpc": "2.0", "id": 50, "method": "resources/subscribe",
+                "params": { "uri": uri }
+            });
+            let resp = dispatch(&cortex, &subs, &msg.to_string()).unwrap();
+            assert!(resp.get("error").is_none(), "{}", resp);
+        }
+        assert!(subs.poll(&cortex).is_empty());
+
+        // A new node changes the stats but not the watched node
+        cortex.store(Cortex::fact("Unrelated node", 0.5)).unwrap();
+        let notified = subs.poll(&cortex);
+        assert_eq!(notified.len(), 1);
+        assert_eq!(notified[0]["method"], "notifications/resources/updated");
+        assert_eq!(notified[0]["params"]["uri"], "cortex://stats");
+        assert!(subs.poll(&cortex).is_empty());
+
+        let mut node = cortex.get_node(watched).unwrap().unwrap();
+        node.importance = 0.9;
+        cortex.update(node).unwrap();
+        let mut uris: Vec<String> = subs
+            .poll(&cortex)
+            .iter()
+            .map(|n| n["params"]["uri"].as_str().unwrap().to_string())
+            .collect();
+        uris.sort();
+        assert_eq!(uris, ["cortex://stats".to_string(), node_uri]);
+
+        subs.unsubscribe("cortex://stats");
+        cortex.store(Cortex::fact("Another node", 0.5)).unwrap();
+        assert!(subs.poll(&cortex).is_empty());
+    }
+
+    #[test]
+    fn test_tools_search_pages_without_gaps() {
+        let cortex = make_cortex();
+        for i in 0..30 {
+            cortex
+                .store(Cortex::fact(&format!("Deployment note {}", i), 0.5))
+                .unwrap();
+        }
+
+        let mut ids = Vec::new();
+        let bare = call(
+            &cortex,
+            "cortex_search",
+            json!({ "query": "deployment note" }),
+        );
+        assert_eq!(
+            bare.as_array().unwrap().len(),
+            10,
+            "bare array unless paging"
+        );
+
+        let mut cursor = json!("");
+        for _ in 0..3 {
+            let args = json!({ "query": "deployment note", "limit": 10, "cursor": cursor });
+            let page = call(&cortex, "cortex_search", args);
+            let results = page["results"].as_array().unwrap();
+            assert_eq!(results.len(), 10);
+            ids.extend(
+                results
+                    .iter()
+                    .map(|r| r["id"].as_str().unwrap().to_string()),
+            );
+            cursor = page["next_cursor"].clone();
+        }
+        assert!(cursor.is_null(), "no fourth page");
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 30);
+    }
 }
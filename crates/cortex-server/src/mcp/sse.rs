@@ -0,0 +1,241 @@
+//! MCP over HTTP with Server-Sent Events (MCP spec 2024-11-05).
+//!
+//! A client opens `GET /sse` and receives an `endpoint` event naming its
+//! message URL (`/message?sessionId=...`). It then POSTs JSON-RPC requests
+//! there; each response arrives as a `message` event on its SSE stream.
+//! Many clients can share one Cortex process this way.
+
+use super::dispatch;
+use super::subscriptions::{Subscriptions, POLL_INTERVAL};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+    routing::{get, post},
+    Router,
+};
+use cortex_core::Cortex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+type Sessions = Arc<Mutex<HashMap<Uuid, Session>>>;
+
+#[derive(Clone)]
+struct Session {
+    tx: mpsc::Sender<Value>,
+    subs: Arc<Subscriptions>,
+}
+
+#[derive(Clone)]
+struct SseState {
+    cortex: Arc<Cortex>,
+    sessions: Sessions,
+}
+
+/// Serve MCP over SSE on `bind` until the process exits.
+pub async fn serve(cortex: Cortex, bind: SocketAddr) -> anyhow::Result<()> {
+    let listener = tokio::net::TcpListener::bind(bind).await?;
+    eprintln!(
+        "[cortex-mcp] Ready. Listening on http://{}/sse (MCP over SSE).",
+        listener.local_addr()?
+    );
+    axum::serve(listener, router(Arc::new(cortex))).await?;
+    Ok(())
+}
+
+fn router(cortex: Arc<Cortex>) -> Router {
+    Router::new()
+        .route("/sse", get(open_session))
+        .route("/message", post(post_message))
+        .with_state(SseState {
+            cortex,
+            sessions: Arc::default(),
+        })
+}
+
+/// Removes the session when its SSE stream is dropped.
+struct SessionGuard {
+    id: Uuid,
+    sessions: Sessions,
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        self.sessions.lock().unwrap().remove(&self.id);
+    }
+}
+
+async fn open_session(
+    State(state): State<SseState>,
+) -> Sse<impl futures::stream::Stream<Item = Result<Event, Infallible>>> {
+    let id = Uuid::now_v7();
+    let (tx, mut rx) = mpsc::channel::<Value>(64);
+    let subs = Arc::new(Subscriptions::default());
+    state.sessions.lock().unwrap().insert(
+        id,
+        Session {
+            tx,
+            subs: subs.clone(),
+        },
+    );
+    let guard = SessionGuard {
+        id,
+        sessions: state.sessions.clone(),
+    };
+    let cortex = state.cortex.clone();
+
+    let stream = async_stream::stream! {
+        let _guard = guard;
+        let mut poll = tokio::time::interval(POLL_INTERVAL);
+        yield Ok(Event::default()
+            .event("endpoint")
+            .data(format!("/message?sessionId={}", id)));
+        loop {
+            let messages = tokio::select! {
+                response = rx.recv() => match response {
+                    Some(response) => vec![response],
+                    None => break,
+                },
+                _ = poll.tick() => subs.poll(&cortex),
+            };
+            for message in messages {
+                yield Ok(Event::default().event("message").data(message.to_string()));
+            }
+        }
+    };
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+#[derive(Deserialize)]
+struct MessageQuery {
+    #[serde(rename = "sessionId")]
+    session_id: Uuid,
+}
+
+async fn post_message(
+    State(state): State<SseState>,
+    Query(query): Query<MessageQuery>,
+    body: String,
+) -> StatusCode {
+    let Some(session) = state
+        .sessions
+        .lock()
+        .unwrap()
+        .get(&query.session_id)
+        .cloned()
+    else {
+        return StatusCode::NOT_FOUND;
+    };
+
+    // Tool calls embed text and hit redb, so keep them off the async workers
+    let cortex = state.cortex.clone();
+    let subs = session.subs.clone();
+    let response = match tokio::task::spawn_blocking(move || dispatch(&cortex, &subs, &body)).await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("[cortex-mcp] Dispatch task failed: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR;
+        }
+    };
+    if let Some(response) = response {
+        if session.tx.send(response).await.is_err() {
+            return StatusCode::GONE;
+        }
+    }
+    StatusCode::ACCEPTED
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cortex_core::LibraryConfig;
+
+    /// Minimal SSE reader: yields `(event, data)` pairs from a streaming response.
+    struct SseReader {
+        resp: reqwest::Response,
+        buf: String,
+    }
+
+    impl SseReader {
+        async fn next_event(&mut self) -> (String, String) {
+            loop {
+                if let Some(end) = self.buf.find("\n\n") {
+                    let block: String = self.buf.drain(..end + 2).collect();
+                    let mut event = String::new();
+                    let mut data = String::new();
+                    for line in block.lines() {
+                        if let Some(v) = line.strip_prefix("event:") {
+                            event = v.trim().to_string();
+                        } else if let Some(v) = line.strip_prefix("data:") {
+                            data.push_str(v.trim_start());
+                        }
+                    }
+                    if !event.is_empty() {
+                        return (event, data);
+                    }
+                    continue;
+                }
+                let chunk = self.resp.chunk().await.unwrap().expect("stream closed");
+                self.buf.push_str(&String::from_utf8_lossy(&chunk));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sse_transport_matches_stdio() {
+        let dir = tempfile::tempdir().unwrap();
+        let cortex =
+            Arc::new(Cortex::open(dir.path().join("test.redb"), LibraryConfig::default()).unwrap());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let app = router(cortex.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let http = reqwest::Client::new();
+        let mut events = SseReader {
+            resp: http.get(format!("{base}/sse")).send().await.unwrap(),
+            buf: String::new(),
+        };
+        let (event, endpoint) = events.next_event().await;
+        assert_eq!(event, "endpoint");
+        assert!(endpoint.starts_with("/message?sessionId="));
+
+        let requests = [
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2024-11-05","capabilities":{},"clientInfo":{"name":"test","version":"1.0"}}}"#,
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/list","params":{}}"#,
+        ];
+        for request in requests {
+            let status = http
+                .post(format!("{base}{endpoint}"))
+                .body(request)
+                .send()
+                .await
+                .unwrap()
+                .status();
+            assert_eq!(status, reqwest::StatusCode::ACCEPTED);
+
+            let (event, data) = events.next_event().await;
+            assert_eq!(event, "message");
+            let over_sse: Value = serde_json::from_str(&data).unwrap();
+            let over_stdio = dispatch(&cortex, &Subscriptions::default(), request).unwrap();
+            assert_eq!(over_sse, over_stdio);
+        }
+
+        let unknown = http
+            .post(format!("{base}/message?sessionId={}", Uuid::now_v7()))
+            .body(requests[1])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(unknown.status(), reqwest::StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,116 @@
+//! `resources/subscribe` support.
+//!
+//! Each connection keeps its own [`Subscriptions`]. The transport calls
+//! [`Subscriptions::poll`] periodically; when [`Cortex::graph_version`] has
+//! moved, every subscribed resource that actually changed yields a
+//! `notifications/resources/updated` message.
+
+use anyhow::Result;
+use cortex_core::{Cortex, NodeId};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// How often transports check for changes to subscribed resources.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// What a node resource looked like when last notified: update time,
+/// deleted flag, and its edges by id and weight.
+type NodeStamp = Option<(i64, bool, Vec<(Uuid, u32)>)>;
+
+enum Subscription {
+    Stats,
+    Node { id: NodeId, stamp: NodeStamp },
+}
+
+#[derive(Default)]
+struct State {
+    seen_version: u64,
+    by_uri: HashMap<String, Subscription>,
+}
+
+/// Resources one client is subscribed to.
+#[derive(Default)]
+pub(crate) struct Subscriptions {
+    state: Mutex<State>,
+}
+
+impl Subscriptions {
+    pub(crate) fn subscribe(&self, cortex: &Cortex, uri: &str) -> Result<()> {
+        let subscription = if uri == "cortex://stats" {
+            Subscription::Stats
+        } else if let Some(id_str) = uri.strip_prefix("cortex://node/") {
+            let id = Uuid::parse_str(id_str)
+                .map_err(|_| anyhow::anyhow!("Invalid node ID in URI: {}", id_str))?;
+            Subscription::Node {
+                id,
+                stamp: node_stamp(cortex, id),
+            }
+        } else {
+            return Err(anyhow::anyhow!("Unknown resource URI: {}", uri));
+        };
+
+        let mut state = self.state.lock().unwrap();
+        if state.by_uri.is_empty() {
+            state.seen_version = cortex.graph_version();
+        }
+        state.by_uri.insert(uri.to_string(), subscription);
+        Ok(())
+    }
+
+    pub(crate) fn unsubscribe(&self, uri: &str) {
+        self.state.lock().unwrap().by_uri.remove(uri);
+    }
+
+    /// Notifications for subscribed resources changed since the last poll.
+    pub(crate) fn poll(&self, cortex: &Cortex) -> Vec<Value> {
+        let mut state = self.state.lock().unwrap();
+        let version = cortex.graph_version();
+        if state.by_uri.is_empty() || version == state.seen_version {
+            return Vec::new();
+        }
+        state.seen_version = version;
+
+        let mut notifications = Vec::new();
+        for (uri, subscription) in state.by_uri.iter_mut() {
+            let changed = match subscription {
+                Subscription::Stats => true,
+                Subscription::Node { id, stamp } => {
+                    let current = node_stamp(cortex, *id);
+                    let changed = current != *stamp;
+                    *stamp = current;
+                    changed
+                }
+            };
+            if changed {
+                notifications.push(json!({
+                    "jsonrpc": "2.0",
+                    "method": "notifications/resources/updated",
+                    "params": { "uri": uri },
+                }));
+            }
+        }
+        notifications
+    }
+}
+
+fn node_stamp(cortex: &Cortex, id: NodeId) -> NodeStamp {
+    let node = cortex.get_node(id).ok().flatten()?;
+    let mut edges: Vec<(Uuid, u32)> = cortex
+        .traverse(id, 1)
+        .map(|sg| {
+            sg.edges
+                .iter()
+                .map(|e| (e.id, e.weight.to_bits()))
+                .collect()
+        })
+        .unwrap_or_default();
+    edges.sort_unstable();
+    Some((
+        node.updated_at.timestamp_nanos_opt().unwrap_or_default(),
+        node.deleted,
+        edges,
+    ))
+}
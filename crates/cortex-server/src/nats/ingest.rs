@@ -1,4 +1,8 @@
+use crate::config::WarrenMappingConfig;
+use crate::gate::{describe, GatedWrite};
+use crate::http::metrics::CortexMetrics;
 use cortex_core::*;
+use std::collections::HashMap;
 use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::sync::RwLock as StdRwLock;
@@ -6,16 +10,44 @@ use std::sync::RwLock as StdRwLock;
 /// Thin wrapper around WarrenNatsAdapter for backward compatibility.
 pub struct NatsIngest {
     inner: warren_adapter::WarrenNatsAdapter,
+    subjects: Vec<String>,
 }
 
 impl NatsIngest {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         client: async_nats::Client,
         storage: Arc<RedbStorage>,
-        embedding_service: Arc<FastEmbedService>,
+        embedding_service: Arc<crate::http::ServerEmbedder>,
         vector_index: Arc<StdRwLock<HnswIndex>>,
         graph_version: Arc<AtomicU64>,
+        dead_letters: Arc<warren_adapter::DeadLetterQueue>,
+        mappings: &HashMap<String, WarrenMappingConfig>,
+        write_gate: Arc<StdRwLock<WriteGateConfig>>,
+        schema: SchemaValidator,
+        metrics: Arc<CortexMetrics>,
     ) -> Self {
+        // Events go through the same gate as POST /nodes; refused ones are
+        // dead-lettered rather than retried.
+        let gate = {
+            let storage = storage.clone();
+            let embedding_service = embedding_service.clone();
+            let vector_index = vector_index.clone();
+            move |node: &mut Node| {
+                let config = write_gate.read().unwrap().clone();
+                Ok(GatedWrite::new(&config, &schema)
+                    .with_metrics(&metrics)
+                    .caller("warren")
+                    .admit(
+                        node,
+                        storage.as_ref(),
+                        embedding_service.as_ref(),
+                        &vector_index,
+                    )?
+                    .map(|_| ())
+                    .map_err(|rejections| describe(&rejections)))
+            }
+        };
         Self {
             inner: warren_adapter::WarrenNatsAdapter::new(
                 client,
@@ -23,11 +55,51 @@ impl NatsIngest {
                 embedding_service,
                 vector_index,
                 graph_version,
-            ),
+            )
+            .with_dead_letters(dead_letters)
+            .with_mappings(event_mappings(mappings))
+            .with_gate(Arc::new(gate)),
+            subjects: warren_adapter::default_subjects(),
         }
     }
 
+    /// Consume `subjects` (`server.nats_subjects`) instead of `warren.>`.
+    pub fn with_subjects(mut self, subjects: Vec<String>) -> Self {
+        self.inner = self.inner.with_subjects(subjects.clone());
+        self.subjects = subjects;
+        self
+    }
+
     pub async fn start(&self) -> Result<()> {
         self.inner.start().await
     }
+
+    pub async fn start_jetstream(&self, stream: &str, consumer: &str) -> Result<()> {
+        let config = warren_adapter::JetStreamConfig {
+            stream: stream.to_string(),
+            consumer: consumer.to_string(),
+            subjects: self.subjects.clone(),
+            ..Default::default()
+        };
+        self.inner.start_jetstream(&config).await
+    }
+}
+
+/// Convert `[warren_mappings]` config. Invalid kinds are reported by
+/// `CortexConfig::validate` and fall back to the built-in kind here.
+fn event_mappings(config: &HashMap<String, WarrenMappingConfig>) -> warren_adapter::EventMappings {
+    config.iter().fold(
+        warren_adapter::EventMappings::new(),
+        |acc, (event_type, m)| {
+            acc.with(
+                event_type.clone(),
+                warren_adapter::EventMapping {
+                    kind: m.kind.as_deref().and_then(|k| NodeKind::new(k).ok()),
+                    importance: m.importance,
+                    title_template: m.title_template.clone(),
+                    body_template: m.body_template.clone(),
+                },
+            )
+        },
+    )
 }
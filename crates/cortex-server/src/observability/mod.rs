@@ -1,18 +1,26 @@
 //! Observability — SSE event streaming for real-time graph change notifications.
 
+pub mod publish;
+
+use axum::response::sse::Event as SseEvent;
 use cortex_core::hooks::{MutationAction, MutationHook};
 use cortex_core::{Edge, Node};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use tokio::sync::broadcast;
 
 /// A graph mutation event broadcast to SSE clients.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphEvent {
     /// Event type: "node.created", "node.updated", "node.deleted",
     /// "edge.created", "edge.updated", "edge.deleted"
     pub event_type: String,
     /// ISO-8601 timestamp
     pub timestamp: String,
+    /// Graph version once this change was applied (0 when not tracked)
+    pub graph_version: u64,
     /// Event payload
     pub data: serde_json::Value,
 }
@@ -31,14 +39,33 @@ pub fn new_event_bus(capacity: usize) -> EventBus {
 /// Register this hook so ALL mutations (gRPC, auto-linker, library mode) emit SSE events.
 pub struct EventBusHook {
     bus: EventBus,
+    graph_version: Option<Arc<AtomicU64>>,
 }
 
 impl EventBusHook {
     pub fn new(bus: EventBus) -> Self {
-        Self { bus }
+        Self {
+            bus,
+            graph_version: None,
+        }
+    }
+
+    /// Stamp each event with this counter's value.
+    pub fn with_graph_version(mut self, graph_version: Arc<AtomicU64>) -> Self {
+        self.graph_version = Some(graph_version);
+        self
     }
 
-    fn emit(&self, event: GraphEvent) {
+    fn emit(&self, event_type: &str, data: serde_json::Value) {
+        let event = GraphEvent {
+            event_type: event_type.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            graph_version: self
+                .graph_version
+                .as_ref()
+                .map_or(0, |v| v.load(Ordering::Relaxed)),
+            data,
+        };
         // Ignore send errors — no receivers means no one is listening (that's fine)
         let _ = self.bus.send(event);
     }
@@ -52,17 +79,16 @@ impl MutationHook for EventBusHook {
             MutationAction::Deleted => "node.deleted",
         };
 
-        self.emit(GraphEvent {
-            event_type: event_type.to_string(),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-            data: serde_json::json!({
+        self.emit(
+            event_type,
+            serde_json::json!({
                 "id": node.id.to_string(),
                 "kind": node.kind.as_str(),
                 "title": node.data.title,
                 "agent": node.source.agent,
                 "importance": node.importance,
             }),
-        });
+        );
     }
 
     fn on_edge_mutation(&self, edge: &Edge, action: MutationAction) {
@@ -72,17 +98,67 @@ impl MutationHook for EventBusHook {
             MutationAction::Deleted => "edge.deleted",
         };
 
-        self.emit(GraphEvent {
-            event_type: event_type.to_string(),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-            data: serde_json::json!({
+        self.emit(
+            event_type,
+            serde_json::json!({
                 "id": edge.id.to_string(),
                 "from": edge.from.to_string(),
                 "to": edge.to.to_string(),
                 "relation": edge.relation.as_str(),
                 "weight": edge.weight,
             }),
+        );
+    }
+}
+
+/// SSE frames for the event stream endpoints.
+///
+/// The first frame is `connected`, carrying the current `graph_version` and,
+/// when the client sent `Last-Event-ID`, whether it `missed` changes while
+/// disconnected. Each event frame's SSE `id` is its graph version. `filter`
+/// limits the forwarded event types.
+pub fn sse_stream(
+    mut rx: broadcast::Receiver<GraphEvent>,
+    graph_version: u64,
+    last_event_id: Option<u64>,
+    filter: Option<Vec<String>>,
+) -> impl futures::Stream<Item = Result<SseEvent, Infallible>> {
+    async_stream::stream! {
+        let connected = serde_json::json!({
+            "graph_version": graph_version,
+            "missed": last_event_id.map(|last| last < graph_version),
         });
+        yield Ok(SseEvent::default()
+            .event("connected")
+            .id(graph_version.to_string())
+            .data(connected.to_string()));
+
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    // Apply optional event-type filter
+                    if let Some(ref filter) = filter {
+                        if !filter.contains(&event.event_type) {
+                            continue;
+                        }
+                    }
+                    if let Ok(data) = serde_json::to_string(&event) {
+                        yield Ok(SseEvent::default()
+                            .event(event.event_type)
+                            .id(event.graph_version.to_string())
+                            .data(data));
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    yield Ok(
+                        SseEvent::default()
+                            .event("warning")
+                            .data(format!("Dropped {} events (slow consumer)", n)),
+                    );
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
     }
 }
 
@@ -155,6 +231,19 @@ mod tests {
         assert_eq!(event.data["relation"], "related_to");
     }
 
+    #[test]
+    fn test_event_bus_hook_stamps_graph_version() {
+        let bus = new_event_bus(64);
+        let mut rx = bus.subscribe();
+        let version = Arc::new(AtomicU64::new(41));
+        let hook = EventBusHook::new(bus).with_graph_version(version.clone());
+
+        version.fetch_add(1, Ordering::Relaxed);
+        hook.on_node_mutation(&make_test_node(), MutationAction::Created);
+
+        assert_eq!(rx.try_recv().unwrap().graph_version, 42);
+    }
+
     #[test]
     fn test_event_bus_hook_no_receivers_is_ok() {
         let bus = new_event_bus(64);
@@ -201,6 +290,7 @@ mod tests {
         let event = GraphEvent {
             event_type: "node.created".to_string(),
             timestamp: "2026-01-01T00:00:00+00:00".to_string(),
+            graph_version: 7,
             data: serde_json::json!({"id": "abc", "kind": "fact"}),
         };
         let json = serde_json::to_string(&event).unwrap();
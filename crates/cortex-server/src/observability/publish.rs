@@ -0,0 +1,287 @@
+//! Outbound NATS publishing — lets other services react to graph changes.
+//!
+//! [`NatsPublisher`] is a [`MutationHook`]: committed mutations are queued on
+//! a bounded channel and published by a background task. Writes never wait
+//! on NATS; when the buffer is full, events are dropped and counted.
+
+use async_trait::async_trait;
+use cortex_core::hooks::{MutationAction, MutationHook};
+use cortex_core::{Edge, Node};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// `[nats_publish]` configuration. Disabled by default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NatsPublishConfig {
+    pub enabled: bool,
+    /// NATS server to publish to. Defaults to `server.nats_url`.
+    pub url: Option<String>,
+    /// Events held while NATS is slow before new ones are dropped.
+    pub buffer: usize,
+    /// Publish prompt rollbacks alongside graph mutations.
+    pub rollbacks: bool,
+    pub subjects: PublishSubjects,
+}
+
+impl Default for NatsPublishConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: None,
+            buffer: 1024,
+            rollbacks: true,
+            subjects: PublishSubjects::default(),
+        }
+    }
+}
+
+/// Subject each event type is published to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PublishSubjects {
+    pub node_created: String,
+    pub node_updated: String,
+    pub edge_created: String,
+    pub prompt_rollback: String,
+}
+
+impl Default for PublishSubjects {
+    fn default() -> Self {
+        Self {
+            node_created: "cortex.node.created".into(),
+            node_updated: "cortex.node.updated".into(),
+            edge_created: "cortex.edge.created".into(),
+            prompt_rollback: "cortex.prompt.rollback".into(),
+        }
+    }
+}
+
+/// Destination for outbound events. Implemented for `async_nats::Client`.
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    async fn publish(&self, subject: String, payload: Vec<u8>) -> Result<(), String>;
+}
+
+#[async_trait]
+impl EventSink for async_nats::Client {
+    async fn publish(&self, subject: String, payload: Vec<u8>) -> Result<(), String> {
+        async_nats::Client::publish(self, subject, payload.into())
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+struct OutboundEvent {
+    subject: String,
+    payload: serde_json::Value,
+}
+
+/// Fire-and-forget publisher for node, edge and rollback events.
+pub struct NatsPublisher {
+    tx: mpsc::Sender<OutboundEvent>,
+    subjects: PublishSubjects,
+    rollbacks: bool,
+    dropped: AtomicU64,
+}
+
+impl NatsPublisher {
+    /// Create the publisher and spawn its drain task on the current runtime.
+    pub fn spawn(sink: Arc<dyn EventSink>, config: &NatsPublishConfig) -> Arc<Self> {
+        let (tx, mut rx) = mpsc::channel::<OutboundEvent>(config.buffer.max(1));
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                let payload = event.payload.to_string().into_bytes();
+                if let Err(e) = sink.publish(event.subject.clone(), payload).await {
+                    log::warn!("NATS publish to {} failed: {}", event.subject, e);
+                }
+            }
+        });
+        Arc::new(Self {
+            tx,
+            subjects: config.subjects.clone(),
+            rollbacks: config.rollbacks,
+            dropped: AtomicU64::new(0),
+        })
+    }
+
+    /// Publish a prompt rollback. `details` is merged into the payload; its
+    /// `rollback_node_id`, if any, doubles as the event `id`.
+    pub fn publish_rollback(&self, details: serde_json::Value) {
+        if !self.rollbacks {
+            return;
+        }
+        let id = details
+            .get("rollback_node_id")
+            .cloned()
+            .unwrap_or(serde_json::Value::Null);
+        let mut payload = serde_json::json!({
+            "event": "prompt.rollback",
+            "id": id,
+            "kind": "prompt",
+        });
+        if let (Some(obj), serde_json::Value::Object(extra)) = (payload.as_object_mut(), details) {
+            obj.extend(extra);
+        }
+        self.enqueue(&self.subjects.prompt_rollback, payload);
+    }
+
+    /// Events dropped because the buffer was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    fn enqueue(&self, subject: &str, mut payload: serde_json::Value) {
+        payload["timestamp"] = chrono::Utc::now().to_rfc3339().into();
+        let event = OutboundEvent {
+            subject: subject.to_string(),
+            payload,
+        };
+        if let Err(mpsc::error::TrySendError::Full(event)) = self.tx.try_send(event) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            log::warn!("NATS publish buffer full, dropping {}", event.subject);
+        }
+    }
+}
+
+impl MutationHook for NatsPublisher {
+    fn on_node_mutation(&self, node: &Node, action: MutationAction) {
+        let (event, subject) = match action {
+            MutationAction::Created => ("node.created", &self.subjects.node_created),
+            MutationAction::Updated => ("node.updated", &self.subjects.node_updated),
+            MutationAction::Deleted => return,
+        };
+        self.enqueue(
+            subject,
+            serde_json::json!({
+                "event": event,
+                "id": node.id.to_string(),
+                "kind": node.kind.as_str(),
+            }),
+        );
+    }
+
+    fn on_edge_mutation(&self, edge: &Edge, action: MutationAction) {
+        if action != MutationAction::Created {
+            return;
+        }
+        self.enqueue(
+            &self.subjects.edge_created,
+            serde_json::json!({
+                "event": "edge.created",
+                "id": edge.id.to_string(),
+                "kind": edge.relation.as_str(),
+                "from": edge.from.to_string(),
+                "to": edge.to.to_string(),
+            }),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MemoryBroker;
+    use cortex_core::{EdgeProvenance, NodeKind, Relation, Source};
+
+    /// A broker that never completes a publish.
+    struct StalledBroker;
+
+    #[async_trait]
+    impl EventSink for StalledBroker {
+        async fn publish(&self, _subject: String, _payload: Vec<u8>) -> Result<(), String> {
+            std::future::pending().await
+        }
+    }
+
+    fn make_test_node() -> Node {
+        Node::new(
+            NodeKind::new("fact").unwrap(),
+            "Deploys run from main".to_string(),
+            "Release builds are cut from the main branch".to_string(),
+            Source {
+                agent: "test-agent".to_string(),
+                session: None,
+                channel: None,
+            },
+            0.6,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_publishes_mutations_to_configured_subjects() {
+        let broker = Arc::new(MemoryBroker::default());
+        let mut config = NatsPublishConfig::default();
+        config.subjects.node_created = "graph.new".into();
+        let publisher = NatsPublisher::spawn(broker.clone(), &config);
+
+        let node = make_test_node();
+        let edge = Edge::new(
+            node.id,
+            uuid::Uuid::now_v7(),
+            Relation::new("related_to").unwrap(),
+            0.8,
+            EdgeProvenance::Manual {
+                created_by: "test-agent".to_string(),
+            },
+        );
+        publisher.on_node_mutation(&node, MutationAction::Created);
+        publisher.on_node_mutation(&node, MutationAction::Updated);
+        publisher.on_node_mutation(&node, MutationAction::Deleted);
+        publisher.on_edge_mutation(&edge, MutationAction::Created);
+        publisher.publish_rollback(serde_json::json!({ "agent": "kai", "to_version": 3 }));
+
+        let published = broker.drained(4).await;
+        let subjects: Vec<&str> = published.iter().map(|(s, _)| s.as_str()).collect();
+        assert_eq!(
+            subjects,
+            [
+                "graph.new",
+                "cortex.node.updated",
+                "cortex.edge.created",
+                "cortex.prompt.rollback"
+            ]
+        );
+        assert_eq!(published[0].1["id"], node.id.to_string());
+        assert_eq!(published[0].1["kind"], "fact");
+        assert_eq!(published[2].1["kind"], "related_to");
+        assert_eq!(published[3].1["event"], "prompt.rollback");
+        assert_eq!(published[3].1["to_version"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_slow_broker_does_not_block_writes() {
+        let config = NatsPublishConfig {
+            buffer: 2,
+            ..Default::default()
+        };
+        let publisher = NatsPublisher::spawn(Arc::new(StalledBroker), &config);
+        let node = make_test_node();
+
+        // One event is held by the stalled publish, two fill the buffer
+        for _ in 0..10 {
+            publisher.on_node_mutation(&node, MutationAction::Created);
+            tokio::task::yield_now().await;
+        }
+        assert!(publisher.dropped() >= 7, "dropped {}", publisher.dropped());
+    }
+
+    #[tokio::test]
+    async fn test_rollback_events_can_be_disabled() {
+        let broker = Arc::new(MemoryBroker::default());
+        let config = NatsPublishConfig {
+            rollbacks: false,
+            ..Default::default()
+        };
+        let publisher = NatsPublisher::spawn(broker.clone(), &config);
+
+        publisher.publish_rollback(serde_json::json!({ "agent": "kai", "to_version": 3 }));
+        publisher.on_node_mutation(&make_test_node(), MutationAction::Created);
+
+        let published = broker.drained(1).await;
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].0, "cortex.node.created");
+    }
+}
@@ -0,0 +1,868 @@
+//! Recording prompt performance observations (issue #24).
+//!
+//! An observation is stored as a node linked to the agent and the variant,
+//! folded into the agent's `uses` edge weight, and run past the rollback
+//! monitor. The HTTP `/agents/:name/observe` route and the gRPC `Observe`
+//! RPC both record through [`ObservationContext`].
+
+use crate::http::metrics::CortexMetrics;
+use crate::observability::publish::NatsPublisher;
+use crate::webhooks::WebhookRegistry;
+use cortex_core::{
+    kinds::defaults as kinds,
+    prompt::{selection as sel, RollbackConfig, RollbackMonitor, RollbackResult},
+    relations::defaults as rels,
+    Edge, EdgeProvenance, Node, NodeId, RedbStorage, Source, Storage,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+/// Rich body JSON stored inside the observation node (issue #24 schema).
+#[derive(Serialize, Deserialize, Default)]
+struct ObsBodyJson {
+    agent: String,
+    prompt_slug: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompt_version: Option<u32>,
+    observation_type: String,
+    metrics: ObsMetrics,
+    context: ObsContext,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct ObsMetrics {
+    correction_count: u32,
+    sentiment_score: f32,
+    task_completed: bool,
+    task_outcome: String,
+    observation_score: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token_cost: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_time_ms: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_satisfaction: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct ObsContext {
+    task_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    topic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correction_rate: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    topic_shift: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    energy: Option<f32>,
+}
+
+#[derive(Deserialize)]
+pub struct ObserveBody {
+    /// UUID of the prompt variant node
+    pub variant_id: String,
+    /// Slug/title of the prompt variant (for display)
+    pub variant_slug: String,
+    /// Observed sentiment score: 0.0–1.0
+    #[serde(default = "default_half")]
+    pub sentiment_score: f32,
+    /// Number of corrections the user made
+    #[serde(default)]
+    pub correction_count: u32,
+    /// Outcome: success | partial | failure | unknown
+    #[serde(default = "default_unknown")]
+    pub task_outcome: String,
+    /// Token cost of the interaction
+    pub token_cost: Option<u32>,
+    /// Response time in milliseconds
+    pub response_time_ms: Option<u32>,
+    /// Explicit user satisfaction score (0.0–1.0), if available
+    pub user_satisfaction: Option<f32>,
+    /// The context signals active during this interaction
+    pub context_signals: Option<sel::ContextSignals>,
+    /// Topic/domain of the interaction (e.g. "cortex-development")
+    pub topic: Option<String>,
+    /// Session length in minutes since session start
+    pub session_length: Option<u32>,
+    /// Number of messages in the session
+    pub message_count: Option<u32>,
+}
+
+fn default_half() -> f32 {
+    0.5
+}
+
+fn default_unknown() -> String {
+    "unknown".to_string()
+}
+
+/// Agent metadata key holding the agent's LinUCB arm for each variant it has
+/// observed, keyed by variant id.
+const LINUCB_ARMS_KEY: &str = "linucb_arms";
+
+/// A variant's LinUCB arm as stored on the agent, as of `updated_at`.
+#[derive(Serialize, Deserialize)]
+struct StoredArm {
+    #[serde(flatten)]
+    arm: sel::LinUcbArm,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// `agent`'s stored LinUCB arm for `variant_id`, decayed to `now`. `None`
+/// until an observation of the variant has been recorded for the agent.
+pub fn stored_linucb_arm(
+    agent: &Node,
+    variant_id: &str,
+    half_life_hours: f64,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<sel::LinUcbArm> {
+    let stored: StoredArm = agent
+        .data
+        .metadata
+        .get(LINUCB_ARMS_KEY)?
+        .get(variant_id)
+        .and_then(|v| serde_json::from_value(v.clone()).ok())?;
+    let mut arm = stored.arm;
+    arm.decay(sel::decay_weight(now - stored.updated_at, half_life_hours));
+    Some(arm)
+}
+
+/// What recording an observation needs besides storage: the selection and
+/// rollback settings, and where rollback notifications go. Shared by the
+/// `/agents/:name/observe` route and the gRPC `Observe` RPC.
+#[derive(Clone)]
+pub struct ObservationContext {
+    pub selection_config: sel::SelectionConfig,
+    /// Swapped on config reload; read it per observation.
+    pub rollback_config: Arc<RwLock<RollbackConfig>>,
+    pub metrics: Arc<CortexMetrics>,
+    pub webhooks: Arc<WebhookRegistry>,
+    pub nats_publisher: Option<Arc<NatsPublisher>>,
+}
+
+/// Outcome of recording one observation.
+#[derive(Debug)]
+pub struct RecordedObservation {
+    pub observation_id: NodeId,
+    pub observation_score: f32,
+    /// Weight of the agent's `uses` edge to the variant before and after.
+    pub old_edge_weight: f32,
+    pub new_edge_weight: f32,
+    /// Set when the observation tripped an automatic rollback.
+    pub rollback: Option<RollbackResult>,
+}
+
+impl ObservationContext {
+    /// Record `body` for `agent`, run the rollback monitor, and announce
+    /// any rollback it triggers (metrics, NATS, webhooks).
+    pub fn record(
+        &self,
+        storage: &Arc<RedbStorage>,
+        agent: &Node,
+        variant_uuid: uuid::Uuid,
+        body: &ObserveBody,
+    ) -> anyhow::Result<RecordedObservation> {
+        let rollback_config = self.rollback_config.read().unwrap().clone();
+        let recorded = store_observation(
+            storage,
+            agent,
+            variant_uuid,
+            body,
+            self.selection_config.observation_half_life_hours,
+            rollback_config,
+        )?;
+
+        if let Some(ref rb) = recorded.rollback {
+            self.announce_rollback(Some(&agent.data.title), rb);
+        }
+        Ok(recorded)
+    }
+
+    /// Count a rollback and send its notifications (issue #23 —
+    /// notify_on_rollback). `agent` is the agent whose observation tripped an
+    /// automatic rollback; manual rollbacks have none.
+    pub fn announce_rollback(&self, agent: Option<&str>, rb: &RollbackResult) {
+        self.metrics
+            .prompt_rollbacks
+            .get_or_create(&crate::http::metrics::TriggerLabel {
+                trigger: rb.trigger.kind_str().to_string(),
+            })
+            .inc();
+        let payload = rollback_event_payload(agent, rb);
+        if let Some(ref publisher) = self.nats_publisher {
+            publisher.publish_rollback(payload.clone());
+        }
+        // Delivered in the background so the caller isn't held up
+        self.webhooks.dispatch("rollback", payload);
+    }
+}
+
+/// Write the observation node and its edges, fold its score into the
+/// `uses` edge weight and the agent's LinUCB arm for the variant, then run
+/// the rollback monitor. Sends no notifications; see
+/// [`ObservationContext::record`].
+fn store_observation(
+    storage: &Arc<RedbStorage>,
+    agent: &Node,
+    variant_uuid: uuid::Uuid,
+    body: &ObserveBody,
+    half_life_hours: f64,
+    rollback_config: RollbackConfig,
+) -> anyhow::Result<RecordedObservation> {
+    let name = agent.data.title.clone();
+
+    // Normalise inputs: clamp scores to valid range, canonicalise task_outcome
+    let sentiment_score = body.sentiment_score.clamp(0.0, 1.0);
+    let user_satisfaction = body.user_satisfaction.map(|v| v.clamp(0.0, 1.0));
+    let task_outcome = match body.task_outcome.as_str() {
+        "success" | "partial" | "failure" | "unknown" => body.task_outcome.clone(),
+        _ => "unknown".to_string(),
+    };
+
+    // Compute observation score
+    let obs_score = sel::observation_score(sentiment_score, body.correction_count, &task_outcome);
+
+    // Try to look up the prompt version from the variant node's body JSON
+    let prompt_version: Option<u32> = storage
+        .get_node(variant_uuid)
+        .ok()
+        .flatten()
+        .and_then(|n| serde_json::from_str::<serde_json::Value>(&n.data.body).ok())
+        .and_then(|v| v.get("version").and_then(|v| v.as_u64()))
+        .map(|v| v as u32);
+
+    let task_type = body
+        .context_signals
+        .as_ref()
+        .map(|s| s.task_type.clone())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    // Build the rich body JSON (issue #24 schema)
+    let obs_body = ObsBodyJson {
+        agent: name.clone(),
+        prompt_slug: body.variant_slug.clone(),
+        prompt_version,
+        observation_type: "performance".to_string(),
+        metrics: ObsMetrics {
+            correction_count: body.correction_count,
+            sentiment_score,
+            task_completed: task_outcome == "success",
+            task_outcome: task_outcome.clone(),
+            observation_score: obs_score,
+            token_cost: body.token_cost,
+            response_time_ms: body.response_time_ms,
+            user_satisfaction,
+        },
+        context: ObsContext {
+            task_type: task_type.clone(),
+            topic: body.topic.clone(),
+            session_length: body.session_length,
+            message_count: body.message_count,
+            correction_rate: body.context_signals.as_ref().map(|s| s.correction_rate),
+            topic_shift: body.context_signals.as_ref().map(|s| s.topic_shift),
+            energy: body.context_signals.as_ref().map(|s| s.energy),
+        },
+    };
+    let body_json_str = serde_json::to_string(&obs_body).unwrap_or_default();
+
+    // Title follows issue #24 schema: obs:<agent>:<timestamp>
+    let now = chrono::Utc::now();
+    let obs_title = format!("obs:{}:{}", name, now.to_rfc3339());
+
+    let mut obs_node = Node::new(
+        kinds::observation(),
+        obs_title,
+        body_json_str,
+        Source {
+            agent: name.clone(),
+            session: None,
+            channel: None,
+        },
+        obs_score,
+    );
+
+    // Keep backward-compat metadata entries so existing variant-history still works
+    obs_node.data.metadata.insert(
+        "observation_type".into(),
+        serde_json::Value::String("performance".into()),
+    );
+    obs_node.data.metadata.insert(
+        "variant_id".into(),
+        serde_json::Value::String(body.variant_id.clone()),
+    );
+    obs_node.data.metadata.insert(
+        "variant_slug".into(),
+        serde_json::Value::String(body.variant_slug.clone()),
+    );
+    obs_node
+        .data
+        .metadata
+        .insert("sentiment_score".into(), serde_json::json!(sentiment_score));
+    obs_node.data.metadata.insert(
+        "correction_count".into(),
+        serde_json::json!(body.correction_count),
+    );
+    obs_node.data.metadata.insert(
+        "task_outcome".into(),
+        serde_json::Value::String(task_outcome.clone()),
+    );
+    obs_node
+        .data
+        .metadata
+        .insert("observation_score".into(), serde_json::json!(obs_score));
+    if let Some(tc) = body.token_cost {
+        obs_node
+            .data
+            .metadata
+            .insert("token_cost".into(), serde_json::json!(tc));
+    }
+    if let Some(ref signals) = body.context_signals {
+        obs_node.data.metadata.insert(
+            "context_signals".into(),
+            serde_json::to_value(signals).unwrap_or_default(),
+        );
+    }
+
+    // How long since the `uses` weight last absorbed an observation, so stale
+    // history counts for less against this one.
+    let uses_rel = rels::uses();
+    let since_last_update = storage
+        .edges_between(agent.id, variant_uuid)?
+        .into_iter()
+        .find(|e| e.relation == uses_rel)
+        .map(|e| now - e.updated_at)
+        .unwrap_or_else(chrono::Duration::zero);
+
+    let variant_id = variant_uuid.to_string();
+    let signals = body
+        .context_signals
+        .clone()
+        .unwrap_or_else(|| sel::ContextSignals {
+            sentiment: sentiment_score,
+            task_type,
+            ..Default::default()
+        });
+
+    // Observation node, its edges, the `uses` weight update, any swap
+    // observation, the agent's active variant and its LinUCB arm all commit
+    // together.
+    let (old_weight, new_weight) = storage.transaction(|tx| {
+        // Re-read the agent so a concurrent observation's arm update and
+        // active variant aren't overwritten with the copy read earlier
+        let mut updated_agent = tx
+            .get_node(agent.id)?
+            .ok_or(cortex_core::CortexError::NodeNotFound(agent.id))?;
+
+        tx.put_node(&obs_node)?;
+
+        // Edges: agent --[performed]--> obs (backward compat)
+        //        obs --[informed_by]--> variant (backward compat for performance query)
+        //        obs --[observed_with]--> variant (issue #24 naming)
+        //        obs --[observed_by]--> agent (issue #24 naming)
+        let new_edges = vec![
+            Edge {
+                id: uuid::Uuid::now_v7(),
+                from: agent.id,
+                to: obs_node.id,
+                relation: rels::performed(),
+                weight: 1.0,
+                provenance: EdgeProvenance::Manual {
+                    created_by: name.clone(),
+                },
+                created_at: now,
+                updated_at: now,
+            },
+            Edge {
+                id: uuid::Uuid::now_v7(),
+                from: obs_node.id,
+                to: variant_uuid,
+                relation: rels::informed_by(),
+                weight: 1.0,
+                provenance: EdgeProvenance::Manual {
+                    created_by: name.clone(),
+                },
+                created_at: now,
+                updated_at: now,
+            },
+            Edge {
+                id: uuid::Uuid::now_v7(),
+                from: obs_node.id,
+                to: variant_uuid,
+                relation: rels::observed_with(),
+                weight: obs_score,
+                provenance: EdgeProvenance::Manual {
+                    created_by: name.clone(),
+                },
+                created_at: now,
+                updated_at: now,
+            },
+            Edge {
+                id: uuid::Uuid::now_v7(),
+                from: obs_node.id,
+                to: agent.id,
+                relation: rels::observed_by(),
+                weight: 1.0,
+                provenance: EdgeProvenance::Manual {
+                    created_by: name.clone(),
+                },
+                created_at: now,
+                updated_at: now,
+            },
+        ];
+        for edge in &new_edges {
+            tx.put_edge(edge)?;
+        }
+
+        // Atomically update the uses edge weight (single write transaction)
+        let (old_weight, new_weight) =
+            tx.update_edge_weight(agent.id, variant_uuid, &uses_rel, &|w| {
+                sel::update_edge_weight_decayed(w, obs_score, since_last_update, half_life_hours)
+            })?;
+
+        // Determine if this is a variant swap
+        let current_active = updated_agent
+            .data
+            .metadata
+            .get("active_variant_id")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let is_swap = current_active.as_deref() != Some(&body.variant_id);
+
+        // Record a swap observation if the active variant changed.
+        if is_swap {
+            if let Some(ref old_id) = current_active {
+                if let Ok(old_uuid) = old_id.parse::<uuid::Uuid>() {
+                    let old_slug = tx
+                        .get_node(old_uuid)
+                        .ok()
+                        .flatten()
+                        .map(|n| n.data.title.clone())
+                        .unwrap_or_default();
+
+                    let swap_body_json = serde_json::to_string(&serde_json::json!({
+                        "agent": name,
+                        "observation_type": "swap",
+                        "old_variant_id": old_id,
+                        "old_variant_slug": old_slug,
+                        "new_variant_id": body.variant_id,
+                        "new_variant_slug": body.variant_slug,
+                        "trigger_signal": task_outcome,
+                    }))
+                    .unwrap_or_default();
+
+                    let mut swap_obs = Node::new(
+                        kinds::observation(),
+                        format!("obs:{}:{}", name, now.to_rfc3339()),
+                        swap_body_json,
+                        Source {
+                            agent: name.clone(),
+                            session: None,
+                            channel: None,
+                        },
+                        0.5,
+                    );
+                    swap_obs.data.metadata.insert(
+                        "observation_type".into(),
+                        serde_json::Value::String("swap".into()),
+                    );
+                    swap_obs.data.metadata.insert(
+                        "old_variant_id".into(),
+                        serde_json::Value::String(old_id.clone()),
+                    );
+                    swap_obs.data.metadata.insert(
+                        "old_variant_slug".into(),
+                        serde_json::Value::String(old_slug),
+                    );
+                    swap_obs.data.metadata.insert(
+                        "new_variant_id".into(),
+                        serde_json::Value::String(body.variant_id.clone()),
+                    );
+                    swap_obs.data.metadata.insert(
+                        "new_variant_slug".into(),
+                        serde_json::Value::String(body.variant_slug.clone()),
+                    );
+                    swap_obs.data.metadata.insert(
+                        "trigger_signal".into(),
+                        serde_json::Value::String(task_outcome.clone()),
+                    );
+                    tx.put_node(&swap_obs)?;
+
+                    tx.put_edge(&Edge {
+                        id: uuid::Uuid::now_v7(),
+                        from: agent.id,
+                        to: swap_obs.id,
+                        relation: rels::performed(),
+                        weight: 1.0,
+                        provenance: EdgeProvenance::Manual {
+                            created_by: name.clone(),
+                        },
+                        created_at: now,
+                        updated_at: now,
+                    })?;
+                } else {
+                    log::warn!(
+                        "agent '{}' has non-UUID active_variant_id '{}'; skipping swap observation",
+                        name,
+                        old_id
+                    );
+                }
+            }
+        }
+
+        // The agent's LinUCB arm for the variant, aged to now, with this
+        // observation folded in. The first observation after arms started
+        // being stored seeds it from the agent's earlier observations.
+        let mut arm = stored_linucb_arm(&updated_agent, &variant_id, half_life_hours, now)
+            .unwrap_or_else(|| {
+                crate::http::selection::variant_linucb_arm(
+                    storage,
+                    &name,
+                    &variant_id,
+                    half_life_hours,
+                )
+            });
+        arm.observe(&signals, obs_score as f64, 1.0);
+        let stored_arm = serde_json::to_value(StoredArm {
+            arm,
+            updated_at: now,
+        })
+        .unwrap_or_default();
+
+        // Update agent node metadata with new active_variant_id and arm
+        updated_agent.data.metadata.insert(
+            "active_variant_id".into(),
+            serde_json::Value::String(body.variant_id.clone()),
+        );
+        let arms = updated_agent
+            .data
+            .metadata
+            .entry(LINUCB_ARMS_KEY.into())
+            .or_insert_with(|| serde_json::json!({}));
+        if !arms.is_object() {
+            *arms = serde_json::json!({});
+        }
+        arms[variant_id.as_str()] = stored_arm;
+        updated_agent.updated_at = now;
+        tx.put_node(&updated_agent)?;
+
+        Ok((old_weight, new_weight))
+    })?;
+
+    // ── Rollback monitor check (issue #23) ─────────────────────────────────
+    // Normalise correction_count to a rate (0–1) assuming 5 corrections = rate 1.0.
+    let correction_rate = (body.correction_count as f32 / 5.0).min(1.0);
+    let rollback = RollbackMonitor::new(storage.clone(), rollback_config)
+        .process_observation(
+            obs_node.id,
+            variant_uuid,
+            correction_rate,
+            sentiment_score,
+            obs_score,
+        )
+        .unwrap_or_else(|e| {
+            log::warn!("rollback monitor error for variant {}: {}", variant_uuid, e);
+            None
+        });
+
+    Ok(RecordedObservation {
+        observation_id: obs_node.id,
+        observation_score: obs_score,
+        old_edge_weight: old_weight,
+        new_edge_weight: new_weight,
+        rollback,
+    })
+}
+
+/// Rollback notification shared by webhooks and the NATS publisher.
+fn rollback_event_payload(
+    agent: Option<&str>,
+    rb: &cortex_core::prompt::RollbackResult,
+) -> serde_json::Value {
+    serde_json::json!({
+        "event": "prompt.rollback",
+        "agent": agent,
+        "from_version": rb.from_version,
+        "to_version": rb.to_version,
+        "trigger": rb.trigger.kind_str(),
+        "cooldown_hours": rb.cooldown_hours,
+        "is_quarantined": rb.is_quarantined,
+        "rollback_node_id": rb.rollback_node_id.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── ObsBodyJson serialisation ─────────────────────────────────────────────
+
+    #[test]
+    fn obs_body_json_roundtrip() {
+        let orig = ObsBodyJson {
+            agent: "agent-1".to_string(),
+            prompt_slug: "slug-x".to_string(),
+            prompt_version: Some(2),
+            observation_type: "performance".to_string(),
+            metrics: ObsMetrics {
+                correction_count: 1,
+                sentiment_score: 0.7,
+                task_completed: true,
+                task_outcome: "success".to_string(),
+                observation_score: 0.85,
+                token_cost: Some(99),
+                response_time_ms: Some(300),
+                user_satisfaction: Some(0.8),
+            },
+            context: ObsContext {
+                task_type: "coding".to_string(),
+                topic: Some("rust".to_string()),
+                session_length: Some(5),
+                message_count: Some(20),
+                correction_rate: None,
+                topic_shift: None,
+                energy: None,
+            },
+        };
+        let json = serde_json::to_string(&orig).unwrap();
+        let decoded: ObsBodyJson = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.agent, "agent-1");
+        assert_eq!(decoded.prompt_slug, "slug-x");
+        assert_eq!(decoded.metrics.token_cost, Some(99u32));
+        assert_eq!(decoded.context.topic, Some("rust".to_string()));
+    }
+
+    #[test]
+    fn obs_body_json_defaults_for_missing_fields() {
+        // prompt_slug defaults to empty string; prompt_version and optional metrics absent
+        let json = r#"{"agent":"a","prompt_slug":"","observation_type":"performance","metrics":{"correction_count":0,"sentiment_score":0.5,"task_completed":false,"task_outcome":"unknown","observation_score":0.5},"context":{"task_type":"general"}}"#;
+        let decoded: ObsBodyJson = serde_json::from_str(json).unwrap();
+        assert_eq!(decoded.prompt_slug, "");
+        assert!(decoded.prompt_version.is_none());
+        assert!(decoded.metrics.token_cost.is_none());
+        assert!(decoded.context.topic.is_none());
+    }
+
+    // ── store_observation ───────────────────────────────────────────────────
+
+    #[test]
+    fn store_observation_writes_node_edges_and_weight() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("obs.redb")).unwrap());
+        let source = Source {
+            agent: "test".to_string(),
+            session: None,
+            channel: None,
+        };
+        let agent = Node::new(kinds::agent(), "kai".into(), "".into(), source.clone(), 0.5);
+        let variant = Node::new(
+            kinds::prompt(),
+            "kai-concise".into(),
+            r#"{"version": 2}"#.into(),
+            source,
+            0.5,
+        );
+        storage.put_node(&agent).unwrap();
+        storage.put_node(&variant).unwrap();
+        storage
+            .put_edge(&Edge::new(
+                agent.id,
+                variant.id,
+                rels::uses(),
+                0.5,
+                EdgeProvenance::Manual {
+                    created_by: "test".into(),
+                },
+            ))
+            .unwrap();
+
+        let body: ObserveBody = serde_json::from_value(serde_json::json!({
+            "variant_id": variant.id.to_string(),
+            "variant_slug": "kai-concise",
+            "sentiment_score": 0.9,
+            "task_outcome": "success",
+            "token_cost": 1200,
+        }))
+        .unwrap();
+        let recorded = store_observation(
+            &storage,
+            &agent,
+            variant.id,
+            &body,
+            168.0,
+            RollbackConfig::default(),
+        )
+        .unwrap();
+
+        assert!(recorded.new_edge_weight > recorded.old_edge_weight);
+        assert!((recorded.old_edge_weight - 0.5).abs() < 1e-6);
+        assert!(recorded.rollback.is_none());
+
+        let obs = storage.get_node(recorded.observation_id).unwrap().unwrap();
+        assert_eq!(obs.kind, kinds::observation());
+        let decoded: ObsBodyJson = serde_json::from_str(&obs.data.body).unwrap();
+        assert_eq!(decoded.agent, "kai");
+        assert_eq!(decoded.prompt_version, Some(2));
+        assert_eq!(decoded.metrics.task_outcome, "success");
+        assert_eq!(decoded.metrics.token_cost, Some(1200));
+        assert_eq!(
+            storage
+                .edges_between(recorded.observation_id, variant.id)
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let agent = storage.get_node(agent.id).unwrap().unwrap();
+        assert_eq!(
+            agent.data.metadata["active_variant_id"],
+            variant.id.to_string()
+        );
+    }
+
+    #[test]
+    fn store_observation_keeps_linucb_arm_on_agent() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("obs.redb")).unwrap());
+        let source = Source {
+            agent: "test".to_string(),
+            session: None,
+            channel: None,
+        };
+        let agent = Node::new(kinds::agent(), "kai".into(), "".into(), source.clone(), 0.5);
+        let variant = Node::new(
+            kinds::prompt(),
+            "kai-concise".into(),
+            "".into(),
+            source,
+            0.5,
+        );
+        storage.put_node(&agent).unwrap();
+        storage.put_node(&variant).unwrap();
+        storage
+            .put_edge(&Edge::new(
+                agent.id,
+                variant.id,
+                rels::uses(),
+                0.5,
+                EdgeProvenance::Manual {
+                    created_by: "test".into(),
+                },
+            ))
+            .unwrap();
+        let variant_id = variant.id.to_string();
+        let observe = |agent: &Node, task_type: &str, outcome: &str| {
+            let body: ObserveBody = serde_json::from_value(serde_json::json!({
+                "variant_id": variant_id,
+                "variant_slug": "kai-concise",
+                "task_outcome": outcome,
+                "context_signals": { "task_type": task_type },
+            }))
+            .unwrap();
+            store_observation(
+                &storage,
+                agent,
+                variant.id,
+                &body,
+                0.0,
+                RollbackConfig::default(),
+            )
+            .unwrap();
+            storage.get_node(agent.id).unwrap().unwrap()
+        };
+
+        let agent = observe(&agent, "coding", "success");
+        let now = chrono::Utc::now();
+        let arm = stored_linucb_arm(&agent, &variant_id, 0.0, now).unwrap();
+        let coding = sel::ContextSignals {
+            task_type: "coding".into(),
+            ..Default::default()
+        };
+        assert!(arm.estimate(&coding) > 0.0);
+
+        // An agent without a stored arm (observed before arms were kept)
+        // is seeded from its earlier observations
+        let mut legacy = agent.clone();
+        legacy.data.metadata.remove(LINUCB_ARMS_KEY);
+        storage.put_node(&legacy).unwrap();
+        let agent = observe(&legacy, "crisis", "failure");
+        let seeded = stored_linucb_arm(&agent, &variant_id, 0.0, now).unwrap();
+        let rebuilt = crate::http::selection::variant_linucb_arm(&storage, "kai", &variant_id, 0.0);
+        let crisis = sel::ContextSignals {
+            task_type: "crisis".into(),
+            ..Default::default()
+        };
+        for signals in [&coding, &crisis] {
+            assert!(
+                (seeded.upper_bound(signals, 1.0) - rebuilt.upper_bound(signals, 1.0)).abs() < 1e-6
+            );
+        }
+        assert!(seeded.estimate(&crisis) < seeded.estimate(&coding));
+
+        // Reads decay the stored arm to the time asked for
+        let later = now + chrono::Duration::hours(48);
+        let aged = stored_linucb_arm(&agent, &variant_id, 24.0, later).unwrap();
+        assert!(aged.estimate(&coding) < arm.estimate(&coding));
+        assert!(stored_linucb_arm(&agent, &uuid::Uuid::now_v7().to_string(), 0.0, now).is_none());
+
+        // Observations recorded against the same stale copy of the agent, as
+        // when two /observe calls race, all reach the arm
+        let stale = agent.clone();
+        observe(&stale, "coding", "success");
+        let agent = observe(&stale, "coding", "partial");
+        let arm = stored_linucb_arm(&agent, &variant_id, 0.0, now).unwrap();
+        let rebuilt = crate::http::selection::variant_linucb_arm(&storage, "kai", &variant_id, 0.0);
+        for signals in [&coding, &crisis] {
+            assert!(
+                (arm.upper_bound(signals, 1.0) - rebuilt.upper_bound(signals, 1.0)).abs() < 1e-6
+            );
+        }
+    }
+
+    // ── rollback notifications ──────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn rollback_event_is_published_to_nats() {
+        use crate::observability::publish::NatsPublishConfig;
+        use crate::test_support::MemoryBroker;
+        use cortex_core::prompt::RollbackTrigger;
+
+        let broker = Arc::new(MemoryBroker::default());
+        let mut config = NatsPublishConfig::default();
+        config.subjects.prompt_rollback = "prompts.rolled_back".into();
+        let publisher = NatsPublisher::spawn(broker.clone(), &config);
+
+        let now = chrono::Utc::now();
+        let rb = RollbackResult {
+            rollback_node_id: uuid::Uuid::now_v7(),
+            from_node_id: uuid::Uuid::now_v7(),
+            from_version: 4,
+            to_node_id: uuid::Uuid::now_v7(),
+            to_version: 3,
+            trigger: RollbackTrigger::ConsecutiveNegative { count: 3 },
+            cooldown_hours: 2,
+            cooldown_expires_at: now + chrono::Duration::hours(2),
+            is_quarantined: true,
+            rollback_count: 3,
+        };
+        publisher.publish_rollback(rollback_event_payload(Some("kai"), &rb));
+
+        let published = broker.drained(1).await;
+        assert_eq!(published.len(), 1);
+        let (subject, event) = &published[0];
+        assert_eq!(subject, "prompts.rolled_back");
+        assert_eq!(event["event"], "prompt.rollback");
+        assert_eq!(event["id"], rb.rollback_node_id.to_string());
+        assert_eq!(event["kind"], "prompt");
+        assert_eq!(event["agent"], "kai");
+        assert_eq!(event["from_version"], 4);
+        assert_eq!(event["to_version"], 3);
+        assert_eq!(event["trigger"], "consecutive_negative");
+        assert_eq!(event["cooldown_hours"], 2);
+        assert_eq!(event["is_quarantined"], true);
+        assert_eq!(event["rollback_node_id"], rb.rollback_node_id.to_string());
+    }
+}
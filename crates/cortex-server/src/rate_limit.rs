@@ -0,0 +1,452 @@
+//! Request limits for the gRPC and HTTP servers (`[rate_limit]`).
+//!
+//! One [`RateLimiter`] is shared by both servers: a concurrency cap, a global
+//! token bucket and per-method token buckets. Requests over a limit are
+//! refused straight away with `ResourceExhausted` (gRPC) or 429 (HTTP), so a
+//! runaway client can't queue up work behind the vector index lock.
+
+use crate::config::RateLimitConfig;
+use axum::response::{IntoResponse, Response};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tonic::body::BoxBody;
+use tonic::codegen::http;
+
+/// Token bucket refilled at `rate` tokens per second, holding up to `rate`
+/// (at least one), so a one-second burst at the configured rate is allowed.
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64) -> Self {
+        let capacity = rate.max(1.0);
+        Self {
+            rate,
+            capacity,
+            tokens: capacity,
+            last: Instant::now(),
+        }
+    }
+
+    fn try_take(&mut self) -> bool {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Held for the life of a request; frees its concurrency slot on drop.
+pub struct Permit {
+    _slot: Option<OwnedSemaphorePermit>,
+}
+
+/// A response body holding its request's [`Permit`] until the body ends,
+/// so a streaming response keeps its concurrency slot while it streams.
+struct PermitBody<B> {
+    inner: B,
+    permit: Option<Permit>,
+}
+
+impl<B> hyper::body::Body for PermitBody<B>
+where
+    B: hyper::body::Body + Unpin,
+{
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<hyper::body::Frame<Self::Data>, Self::Error>>> {
+        let frame = Pin::new(&mut self.inner).poll_frame(cx);
+        if let Poll::Ready(None) = frame {
+            self.permit = None;
+        }
+        frame
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> hyper::body::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Whether `key` names a gRPC method or HTTP route, as
+/// `[rate_limit.methods]` keys must.
+pub fn is_known_method(key: &str) -> bool {
+    crate::grpc::METHODS.contains(&key) || crate::http::ROUTES.contains(&key)
+}
+
+/// Why a request was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Exhausted {
+    Concurrency,
+    Rate,
+    MethodRate(String),
+}
+
+impl std::fmt::Display for Exhausted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Exhausted::Concurrency => write!(f, "Too many concurrent requests"),
+            Exhausted::Rate => write!(f, "Rate limit exceeded"),
+            Exhausted::MethodRate(method) => write!(f, "Rate limit exceeded for {}", method),
+        }
+    }
+}
+
+pub struct RateLimiter {
+    concurrency: Option<Arc<Semaphore>>,
+    global: Option<Mutex<TokenBucket>>,
+    methods: HashMap<String, Mutex<TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Build a limiter from config, or None when every limit is off.
+    pub fn from_config(config: &RateLimitConfig) -> Option<Arc<Self>> {
+        let limiter = Self {
+            concurrency: (config.max_concurrent > 0)
+                .then(|| Arc::new(Semaphore::new(config.max_concurrent))),
+            global: (config.requests_per_second > 0.0)
+                .then(|| Mutex::new(TokenBucket::new(config.requests_per_second))),
+            methods: config
+                .methods
+                .iter()
+                .filter(|(_, rate)| **rate > 0.0)
+                .map(|(method, rate)| (method.clone(), Mutex::new(TokenBucket::new(*rate))))
+                .collect(),
+        };
+        let enabled = limiter.concurrency.is_some()
+            || limiter.global.is_some()
+            || !limiter.methods.is_empty();
+        enabled.then(|| Arc::new(limiter))
+    }
+
+    /// Admit one call to `method`. The method's own limit is checked first so
+    /// that a refused call doesn't use up a global token.
+    pub fn try_acquire(&self, method: &str) -> Result<Permit, Exhausted> {
+        if let Some(bucket) = self.methods.get(method) {
+            if !bucket.lock().unwrap().try_take() {
+                return Err(Exhausted::MethodRate(method.to_string()));
+            }
+        }
+        if let Some(bucket) = &self.global {
+            if !bucket.lock().unwrap().try_take() {
+                return Err(Exhausted::Rate);
+            }
+        }
+        match &self.concurrency {
+            Some(semaphore) => semaphore
+                .clone()
+                .try_acquire_owned()
+                .map(|p| Permit { _slot: Some(p) })
+                .map_err(|_| Exhausted::Concurrency),
+            None => Ok(Permit { _slot: None }),
+        }
+    }
+}
+
+/// Method name of a gRPC request path: `SimilaritySearch` for
+/// `/cortex.v1.CortexService/SimilaritySearch`. None for the health and
+/// reflection services, which are never limited.
+fn grpc_method(path: &str) -> Option<&str> {
+    let (service, method) = path.trim_start_matches('/').split_once('/')?;
+    if service.starts_with("grpc.") {
+        return None;
+    }
+    Some(method)
+}
+
+/// Tower layer applying a [`RateLimiter`] to the gRPC server.
+#[derive(Clone)]
+pub struct GrpcRateLimitLayer {
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl GrpcRateLimitLayer {
+    /// A layer that passes everything through when `limiter` is None.
+    pub fn new(limiter: Option<Arc<RateLimiter>>) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<S> tower::Layer<S> for GrpcRateLimitLayer {
+    type Service = GrpcRateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GrpcRateLimit {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct GrpcRateLimit<S> {
+    inner: S,
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for GrpcRateLimit<S>
+where
+    S: tower::Service<http::Request<B>, Response = http::Response<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let permit = match (&self.limiter, grpc_method(req.uri().path())) {
+            (Some(limiter), Some(method)) => match limiter.try_acquire(method) {
+                Ok(permit) => Some(permit),
+                Err(e) => {
+                    let response = tonic::Status::resource_exhausted(e.to_string()).into_http();
+                    return Box::pin(async move { Ok(response) });
+                }
+            },
+            _ => None,
+        };
+        let fut = self.inner.call(req);
+        Box::pin(async move {
+            let response = fut.await?;
+            Ok(response.map(|inner| tonic::body::boxed(PermitBody { inner, permit })))
+        })
+    }
+}
+
+/// HTTP middleware applying a [`RateLimiter`]. Methods are keyed by route
+/// (`/search`, `/nodes/:id`), falling back to the request path. As on gRPC,
+/// the concurrency slot is held until the response body ends.
+pub async fn http(
+    axum::extract::State(limiter): axum::extract::State<Arc<RateLimiter>>,
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    let route = req
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+    match limiter.try_acquire(&route) {
+        Ok(permit) => next.run(req).await.map(|inner| {
+            axum::body::Body::new(PermitBody {
+                inner,
+                permit: Some(permit),
+            })
+        }),
+        Err(e) => (
+            axum::http::StatusCode::TOO_MANY_REQUESTS,
+            axum::Json(crate::http::JsonResponse::<()>::err(e.to_string())),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower::{Layer, ServiceExt};
+
+    fn config(max_concurrent: usize, rps: f64, methods: &[(&str, f64)]) -> RateLimitConfig {
+        RateLimitConfig {
+            max_concurrent,
+            requests_per_second: rps,
+            methods: methods.iter().map(|(m, r)| (m.to_string(), *r)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        assert!(RateLimiter::from_config(&RateLimitConfig::default()).is_none());
+    }
+
+    #[test]
+    fn test_method_rate_limit() {
+        let limiter =
+            RateLimiter::from_config(&config(0, 0.0, &[("SimilaritySearch", 3.0)])).unwrap();
+        for _ in 0..3 {
+            assert!(limiter.try_acquire("SimilaritySearch").is_ok());
+        }
+        assert_eq!(
+            limiter.try_acquire("SimilaritySearch").err(),
+            Some(Exhausted::MethodRate("SimilaritySearch".into()))
+        );
+        // Other methods are unaffected
+        assert!(limiter.try_acquire("GetNode").is_ok());
+    }
+
+    #[test]
+    fn test_global_rate_limit() {
+        let limiter = RateLimiter::from_config(&config(0, 2.0, &[])).unwrap();
+        assert!(limiter.try_acquire("GetNode").is_ok());
+        assert!(limiter.try_acquire("/search").is_ok());
+        assert_eq!(limiter.try_acquire("GetNode").err(), Some(Exhausted::Rate));
+    }
+
+    #[test]
+    fn test_concurrency_released_on_drop() {
+        let limiter = RateLimiter::from_config(&config(1, 0.0, &[])).unwrap();
+        let first = limiter.try_acquire("GetNode").unwrap();
+        assert_eq!(
+            limiter.try_acquire("GetNode").err(),
+            Some(Exhausted::Concurrency)
+        );
+        drop(first);
+        assert!(limiter.try_acquire("GetNode").is_ok());
+    }
+
+    #[test]
+    fn test_known_methods_match_the_service_and_router() {
+        let generated = include_str!("../../cortex-proto/src/generated/cortex.v1.rs");
+        let mut served: Vec<&str> = generated
+            .split("\"/cortex.v1.CortexService/")
+            .skip(1)
+            .map(|rest| &rest[..rest.find('"').unwrap()])
+            .collect();
+        served.sort_unstable();
+        served.dedup();
+        let mut methods = crate::grpc::METHODS.to_vec();
+        methods.sort_unstable();
+        assert_eq!(methods, served);
+
+        let source = include_str!("http/routes.rs");
+        let router = &source[source.find("pub fn create_router").unwrap()..];
+        let routes: Vec<&str> = router
+            .split(".route(")
+            .skip(1)
+            .map(|rest| rest.split('"').nth(1).unwrap())
+            .collect();
+        assert_eq!(crate::http::ROUTES, routes);
+
+        assert!(is_known_method("SimilaritySearch"));
+        assert!(is_known_method("/briefing/:agent_id"));
+        assert!(!is_known_method("/briefing"));
+    }
+
+    #[tokio::test]
+    async fn test_grpc_permit_held_until_body_ends() {
+        let limiter = RateLimiter::from_config(&config(1, 0.0, &[])).unwrap();
+        let svc = GrpcRateLimitLayer::new(Some(limiter)).layer(tower::service_fn(
+            |_req: http::Request<()>| async {
+                Ok::<_, std::convert::Infallible>(http::Response::new(tonic::body::empty_body()))
+            },
+        ));
+        let call = || {
+            let req = http::Request::builder()
+                .uri("/cortex.v1.CortexService/TraverseStream")
+                .body(())
+                .unwrap();
+            svc.clone().oneshot(req)
+        };
+
+        let streaming = call().await.unwrap();
+        assert_eq!(grpc_status(&streaming), None);
+        assert_eq!(
+            grpc_status(&call().await.unwrap()).as_deref(),
+            Some((tonic::Code::ResourceExhausted as i32).to_string().as_str())
+        );
+        let body = streaming.into_body();
+        assert!(hyper::body::Body::is_end_stream(&body));
+        drop(body);
+        assert_eq!(grpc_status(&call().await.unwrap()), None);
+    }
+
+    #[test]
+    fn test_grpc_method() {
+        assert_eq!(
+            grpc_method("/cortex.v1.CortexService/SimilaritySearch"),
+            Some("SimilaritySearch")
+        );
+        assert_eq!(grpc_method("/grpc.health.v1.Health/Check"), None);
+    }
+
+    fn grpc_status(response: &http::Response<BoxBody>) -> Option<String> {
+        response
+            .headers()
+            .get("grpc-status")
+            .map(|v| v.to_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn test_grpc_burst_is_resource_exhausted() {
+        let limiter =
+            RateLimiter::from_config(&config(0, 0.0, &[("SimilaritySearch", 2.0)])).unwrap();
+        let svc = GrpcRateLimitLayer::new(Some(limiter)).layer(tower::service_fn(
+            |_req: http::Request<()>| async {
+                Ok::<_, std::convert::Infallible>(http::Response::new(tonic::body::empty_body()))
+            },
+        ));
+        let call = |path: &str| {
+            let req = http::Request::builder().uri(path).body(()).unwrap();
+            svc.clone().oneshot(req)
+        };
+
+        let path = "/cortex.v1.CortexService/SimilaritySearch";
+        for _ in 0..2 {
+            assert_eq!(grpc_status(&call(path).await.unwrap()), None);
+        }
+        let refused = call(path).await.unwrap();
+        assert_eq!(
+            grpc_status(&refused).as_deref(),
+            Some((tonic::Code::ResourceExhausted as i32).to_string().as_str())
+        );
+        // Health checks are never limited
+        assert_eq!(
+            grpc_status(&call("/grpc.health.v1.Health/Check").await.unwrap()),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_http_burst_is_429() {
+        use axum::{body::Body, routing::get, Router};
+
+        let limiter = RateLimiter::from_config(&config(0, 0.0, &[("/search", 2.0)])).unwrap();
+        let app = Router::new()
+            .route("/search", get(|| async { "[]" }))
+            .route("/stats", get(|| async { "{}" }))
+            .layer(axum::middleware::from_fn_with_state(limiter, http));
+        let status = |uri: &'static str| {
+            let app = app.clone();
+            async move {
+                let req = axum::extract::Request::builder().uri(uri);
+                app.oneshot(req.body(Body::empty()).unwrap())
+                    .await
+                    .unwrap()
+                    .status()
+            }
+        };
+
+        assert_eq!(status("/search").await, axum::http::StatusCode::OK);
+        assert_eq!(status("/search").await, axum::http::StatusCode::OK);
+        assert_eq!(
+            status("/search").await,
+            axum::http::StatusCode::TOO_MANY_REQUESTS
+        );
+        assert_eq!(status("/stats").await, axum::http::StatusCode::OK);
+    }
+}
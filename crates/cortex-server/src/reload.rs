@@ -0,0 +1,227 @@
+//! Config reload without a restart.
+//!
+//! On SIGHUP the server re-reads cortex.toml and applies the sections that
+//! can change in place: `[write_gate]`, `[auto_linker]`, `[prompt_rollback]`
+//! and `[briefing]`. Changes to any other section are logged as needing a
+//! restart and otherwise ignored. A file that doesn't parse or validate is
+//! rejected as a whole and the running config is kept.
+
+use crate::config::{CortexConfig, RollbackConfig, WriteGateConfig};
+use crate::http::{HttpAutoLinker, HttpBriefingEngine};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+/// Handles to the running components whose settings can be swapped.
+#[derive(Clone)]
+pub struct LiveConfig {
+    write_gate: Arc<RwLock<WriteGateConfig>>,
+    rollback: Arc<RwLock<RollbackConfig>>,
+    auto_linker: Option<Arc<RwLock<HttpAutoLinker>>>,
+    briefing: Option<Arc<HttpBriefingEngine>>,
+}
+
+/// What a reload did, by top-level config section.
+#[derive(Debug, Default, PartialEq)]
+pub struct ReloadReport {
+    /// Sections now in effect.
+    pub applied: Vec<String>,
+    /// Changed sections (or keys) that only take effect after a restart.
+    pub restart_required: Vec<String>,
+}
+
+impl LiveConfig {
+    pub fn new(
+        write_gate: Arc<RwLock<WriteGateConfig>>,
+        rollback: Arc<RwLock<RollbackConfig>>,
+    ) -> Self {
+        Self {
+            write_gate,
+            rollback,
+            auto_linker: None,
+            briefing: None,
+        }
+    }
+
+    pub fn with_auto_linker(mut self, linker: Arc<RwLock<HttpAutoLinker>>) -> Self {
+        self.auto_linker = Some(linker);
+        self
+    }
+
+    pub fn with_briefing(mut self, engine: Arc<HttpBriefingEngine>) -> Self {
+        self.briefing = Some(engine);
+        self
+    }
+
+    /// Apply the reloadable sections of `new` that differ from `old`.
+    /// Nothing is applied if the new auto-linker settings are invalid.
+    pub fn apply(&self, old: &CortexConfig, new: &CortexConfig) -> anyhow::Result<ReloadReport> {
+        let linker_config = new.auto_linker_config();
+        linker_config.validate()?;
+
+        let mut report = ReloadReport::default();
+        for section in changed_sections(old, new)? {
+            let applied = match section.as_str() {
+                "write_gate" => {
+                    *self.write_gate.write().unwrap() = new.write_gate.clone();
+                    true
+                }
+                "prompt_rollback" => {
+                    *self.rollback.write().unwrap() = new.prompt_rollback.clone();
+                    true
+                }
+                "auto_linker" => match &self.auto_linker {
+                    Some(linker) => {
+                        linker.write().unwrap().set_config(linker_config.clone())?;
+                        true
+                    }
+                    None => false,
+                },
+                "briefing" => {
+                    // The precompute loop is started once with its agent list
+                    if old.briefing.precompute_agents != new.briefing.precompute_agents {
+                        report
+                            .restart_required
+                            .push("briefing.precompute_agents".into());
+                    }
+                    match &self.briefing {
+                        Some(engine) => {
+                            engine.set_config(new.briefing_config());
+                            true
+                        }
+                        None => false,
+                    }
+                }
+                _ => false,
+            };
+            if applied {
+                report.applied.push(section);
+            } else {
+                report.restart_required.push(section);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Re-read `path` and apply it over `current`. Returns the config now
+    /// in effect, to compare the next reload against.
+    pub fn reload(&self, path: &Path, current: &CortexConfig) -> anyhow::Result<CortexConfig> {
+        let new = CortexConfig::load(path)?;
+        let errors = new.validate();
+        if !errors.is_empty() {
+            anyhow::bail!("invalid config: {}", errors.join("; "));
+        }
+        let report = self.apply(current, &new)?;
+        if report.applied.is_empty() && report.restart_required.is_empty() {
+            info!("Config reload: no changes");
+        }
+        if !report.applied.is_empty() {
+            info!("Config reload: applied [{}]", report.applied.join(", "));
+        }
+        if !report.restart_required.is_empty() {
+            warn!(
+                "Config reload: [{}] changed but require a restart",
+                report.restart_required.join(", ")
+            );
+        }
+        Ok(new)
+    }
+}
+
+/// Top-level sections whose values differ between `old` and `new`, sorted.
+fn changed_sections(old: &CortexConfig, new: &CortexConfig) -> anyhow::Result<Vec<String>> {
+    let old = serde_json::to_value(old)?;
+    let new = serde_json::to_value(new)?;
+    let (Some(old), Some(new)) = (old.as_object(), new.as_object()) else {
+        return Ok(Vec::new());
+    };
+    let mut changed: Vec<String> = new
+        .iter()
+        .filter(|(key, value)| old.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect();
+    changed.sort();
+    Ok(changed)
+}
+
+/// Reload `path` each time the process gets SIGHUP.
+#[cfg(unix)]
+pub fn spawn_sighup_reloader(path: PathBuf, live: LiveConfig) -> std::io::Result<JoinHandle<()>> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangups = signal(SignalKind::hangup())?;
+    // Compare against the file as read, without command-line overrides
+    let mut current = CortexConfig::load_or_default(&path);
+    Ok(tokio::spawn(async move {
+        while hangups.recv().await.is_some() {
+            info!("SIGHUP received, reloading {}", path.display());
+            match live.reload(&path, &current) {
+                Ok(new) => current = new,
+                Err(e) => error!("Config reload failed, keeping current config: {}", e),
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cortex_core::{GateResult, Node, NodeKind, Source, WriteGate};
+
+    fn live() -> LiveConfig {
+        LiveConfig::new(
+            Arc::new(RwLock::new(WriteGateConfig::default())),
+            Arc::new(RwLock::new(RollbackConfig::default())),
+        )
+    }
+
+    fn node(title: &str) -> Node {
+        Node::new(
+            NodeKind::new("fact").unwrap(),
+            title.into(),
+            "Redb stores the graph in a single file under the data dir".into(),
+            Source {
+                agent: "test".into(),
+                session: None,
+                channel: None,
+            },
+            0.5,
+        )
+    }
+
+    #[test]
+    fn test_gate_threshold_change_applies_to_later_writes() {
+        let live = live();
+        let old = CortexConfig::default();
+        let node = node("Redb storage");
+
+        let check = || WriteGate::check_substance(&node, &live.write_gate.read().unwrap());
+        assert!(matches!(check(), GateResult::Pass));
+
+        let mut new = old.clone();
+        new.write_gate.min_title_length = 40;
+        let report = live.apply(&old, &new).unwrap();
+        assert_eq!(report.applied, ["write_gate"]);
+        assert!(report.restart_required.is_empty());
+        assert!(matches!(check(), GateResult::Reject(_)));
+    }
+
+    #[test]
+    fn test_other_sections_need_a_restart() {
+        let live = live();
+        let old = CortexConfig::default();
+        let mut new = old.clone();
+        new.server.http_addr = "127.0.0.1:9999".into();
+        new.prompt_rollback.cooldown_base_hours += 1;
+
+        let report = live.apply(&old, &new).unwrap();
+        assert_eq!(report.applied, ["prompt_rollback"]);
+        assert_eq!(report.restart_required, ["server"]);
+        assert_eq!(
+            live.rollback.read().unwrap().cooldown_base_hours,
+            new.prompt_rollback.cooldown_base_hours
+        );
+        assert_eq!(live.apply(&new, &new).unwrap(), ReloadReport::default());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::config::CortexConfig;
-use crate::http::CortexMetrics;
-use cortex_core::briefing::{BriefingConfig, BriefingEngine};
+use crate::http::{CortexMetrics, ServerEmbedder};
+use cortex_core::briefing::BriefingEngine;
 use cortex_core::storage::encrypted;
 use cortex_core::*;
 use cortex_proto::cortex_service_server::CortexServiceServer;
@@ -30,23 +30,26 @@ impl Drop for EncryptedDbGuard {
     }
 }
 
-pub async fn run(config: CortexConfig) -> anyhow::Result<()> {
+pub async fn run(config: CortexConfig, config_path: &std::path::Path) -> anyhow::Result<()> {
     info!("Starting Cortex server v{}", env!("CARGO_PKG_VERSION"));
     info!("gRPC: {}", config.server.grpc_addr);
     info!("HTTP: {}", config.server.http_addr);
     info!("Data: {:?}", config.server.data_dir);
 
     // Validate auth config early so we fail fast before opening the database.
-    let auth_enabled = config.security.auth_enabled;
-    let auth_token = config.security.resolved_token();
+    let http_auth = Arc::new(crate::http::auth::HttpAuth::from_config(&config));
+    let auth_enabled = http_auth.enabled;
     if auth_enabled {
-        if auth_token.is_none() {
+        if http_auth.tokens.is_empty() {
             return Err(anyhow::anyhow!(
                 "[security] auth_enabled = true but no token found. \
-                 Set CORTEX_AUTH_TOKEN env var or set auth_token in [security] config."
+                 Set CORTEX_AUTH_TOKEN env var, auth_token, or [[security.tokens]] in config."
             ));
         }
-        info!("Bearer token auth: enabled");
+        info!(
+            "Bearer token auth: enabled ({} token(s))",
+            http_auth.tokens.len()
+        );
     } else {
         warn!(
             "Auth disabled — Cortex is open to all connections on {}",
@@ -82,9 +85,22 @@ pub async fn run(config: CortexConfig) -> anyhow::Result<()> {
 
     // Initialize storage with audit log
     info!("Opening database...");
-    let storage_inner = RedbStorage::open(&storage_path)?;
-    let audit_log = Arc::new(storage_inner.create_audit_log());
-    let storage = Arc::new(storage_inner.with_audit_log(audit_log));
+    let read_only = config.server.read_only;
+    let (storage, read_audit) = if read_only {
+        info!("Read-only mode: write endpoints are refused, background writers are off");
+        if config.security.audit_reads {
+            warn!("security.audit_reads is ignored in read-only mode");
+        }
+        (Arc::new(RedbStorage::open_read_only(&storage_path)?), None)
+    } else {
+        let storage_inner = RedbStorage::open(&storage_path)?;
+        let audit_log = Arc::new(storage_inner.create_audit_log());
+        let read_audit = config.security.audit_reads.then(|| audit_log.clone());
+        (
+            Arc::new(storage_inner.with_audit_log(audit_log)),
+            read_audit,
+        )
+    };
     let stats = storage.stats()?;
     info!(
         "Database loaded: {} nodes, {} edges",
@@ -93,57 +109,57 @@ pub async fn run(config: CortexConfig) -> anyhow::Result<()> {
 
     // Initialize embedding service
     info!("Loading embedding model...");
-    let embedding_service = Arc::new(FastEmbedService::new()?);
+    let embedding_service: Arc<ServerEmbedder> = Arc::new(CachingEmbeddingService::with_capacity(
+        Box::new(FastEmbedService::new()?),
+        config.embedding.cache_capacity,
+    ));
     info!("Embedding model loaded: {}", embedding_service.model_name());
 
-    // Initialize vector index
+    // Rebuild the vector index from existing nodes
     info!("Initializing vector index...");
-    let vector_index = Arc::new(StdRwLock::new(HnswIndex::new(
-        embedding_service.dimension(),
-    )));
-
-    // Rebuild index from existing nodes
-    {
-        let nodes = storage.list_nodes(NodeFilter::new())?;
-        let mut index = vector_index.write().unwrap();
-        let mut indexed = 0;
-
-        for node in nodes {
-            if let Some(emb) = &node.embedding {
-                if index.insert(node.id, emb).is_ok() {
-                    indexed += 1;
-                }
-            }
+    let vector_index = {
+        let started = std::time::Instant::now();
+        let index = HnswIndex::build_parallel(
+            embedding_service.dimension(),
+            storage.list_nodes(NodeFilter::new())?,
+            config.server.index_rebuild_threads,
+        )?;
+        if !index.is_empty() {
+            info!(
+                "Indexed {} node embeddings in {:.1?}",
+                index.len(),
+                started.elapsed()
+            );
         }
+        Arc::new(StdRwLock::new(index))
+    };
 
-        if indexed > 0 {
-            index.rebuild()?;
-            info!("Indexed {} node embeddings", indexed);
-        }
-    }
+    // Initialize graph version counter
+    let graph_version = Arc::new(AtomicU64::new(0));
 
     // Initialize graph engine
-    let graph_engine = Arc::new(GraphEngineImpl::new(storage.clone()));
+    let graph_engine =
+        Arc::new(GraphEngineImpl::new(storage.clone()).with_graph_version(graph_version.clone()));
 
     // Initialize auto-linker
     info!("Initializing auto-linker...");
     let auto_linker_config = config.auto_linker_config();
-    let auto_linker = Arc::new(StdRwLock::new(AutoLinker::new(
-        storage.clone(),
-        graph_engine.clone(),
-        vector_index.clone(),
-        embedding_service.clone(),
-        auto_linker_config.clone(),
-    )?));
+    let auto_linker = Arc::new(StdRwLock::new(
+        AutoLinker::new(
+            storage.clone(),
+            graph_engine.clone(),
+            vector_index.clone(),
+            embedding_service.clone(),
+            auto_linker_config.clone(),
+        )?
+        .with_graph_version(graph_version.clone()),
+    ));
 
     info!(
         "Auto-linker initialized (interval: {}s)",
         auto_linker_config.interval.as_secs()
     );
 
-    // Initialize graph version counter
-    let graph_version = Arc::new(AtomicU64::new(0));
-
     // Initialize briefing engine
     info!("Initializing briefing engine...");
     let briefing_engine = Arc::new(BriefingEngine::new(
@@ -152,10 +168,7 @@ pub async fn run(config: CortexConfig) -> anyhow::Result<()> {
         RwLockVectorIndex(vector_index.clone()),
         embedding_service.clone(),
         graph_version.clone(),
-        BriefingConfig {
-            exclude_kinds: config.briefing.exclude_kinds.clone(),
-            ..Default::default()
-        },
+        config.briefing_config(),
     ));
     info!("Briefing engine ready");
 
@@ -164,34 +177,80 @@ pub async fn run(config: CortexConfig) -> anyhow::Result<()> {
 
     // Initialize hook registry and register the event bus hook
     let mut hooks = HookRegistry::new();
-    let event_bus_hook = Arc::new(crate::observability::EventBusHook::new(event_bus.clone()));
+    let event_bus_hook = Arc::new(
+        crate::observability::EventBusHook::new(event_bus.clone())
+            .with_graph_version(graph_version.clone()),
+    );
     hooks.add(event_bus_hook);
+
+    // Webhook subscriptions: config entries plus those registered over HTTP.
+    // Graph events reach them through the event bus.
+    let webhooks = Arc::new(crate::webhooks::WebhookRegistry::load(
+        storage.clone(),
+        &config.webhooks,
+    )?);
+    let webhook_forwarder = crate::webhooks::spawn_event_forwarder(webhooks.clone(), &event_bus);
+    let webhook_flusher = webhooks.spawn_flusher(crate::webhooks::STATUS_FLUSH_INTERVAL);
+
+    // Optionally publish committed mutations to NATS for other services
+    let nats_publisher = if config.nats_publish.enabled {
+        let url = config
+            .nats_publish
+            .url
+            .clone()
+            .unwrap_or_else(|| config.server.nats_url.clone());
+        match async_nats::connect(&url).await {
+            Ok(client) => {
+                info!("Publishing graph mutations to NATS at {}", url);
+                let publisher = crate::observability::publish::NatsPublisher::spawn(
+                    Arc::new(client),
+                    &config.nats_publish,
+                );
+                hooks.add(publisher.clone());
+                Some(publisher)
+            }
+            Err(e) => {
+                error!("Failed to connect to NATS for publishing: {}", e);
+                error!("Continuing without outbound NATS events");
+                None
+            }
+        }
+    } else {
+        None
+    };
     let hooks = Arc::new(hooks);
 
     // Initialize prometheus metrics
     let cortex_metrics = Arc::new(CortexMetrics::new());
-    let metrics_require_auth = config.observability.metrics_require_auth;
+
+    // Settings swapped in place on SIGHUP; see `reload`
+    let write_gate = Arc::new(StdRwLock::new(config.write_gate.clone()));
+    let rollback_config = Arc::new(StdRwLock::new(config.prompt_rollback.clone()));
+    let live_config = crate::reload::LiveConfig::new(write_gate.clone(), rollback_config.clone())
+        .with_auto_linker(auto_linker.clone())
+        .with_briefing(briefing_engine.clone());
 
     // Start auto-linker background task (also runs retention sweep each cycle)
-    let auto_linker_task = {
+    let auto_linker_task = (!read_only).then(|| {
         let linker = auto_linker.clone();
         let storage_for_retention = storage.clone();
-        let interval = auto_linker_config.interval;
         let retention_cfg = config.retention.clone();
         let score_decay_cfg = config.score_decay.clone();
         let has_retention = retention_cfg.default_ttl_days > 0
             || !retention_cfg.by_kind.is_empty()
-            || retention_cfg.max_nodes.is_some();
+            || retention_cfg.max_nodes.is_some()
+            || !retention_cfg.expire_after.is_empty();
         let metrics_for_linker = cortex_metrics.clone();
+        let graph_version = graph_version.clone();
 
         tokio::spawn(async move {
-            let retention_engine = if has_retention {
-                Some(RetentionEngine::new(retention_cfg, score_decay_cfg))
-            } else {
-                None
-            };
+            // Always built: nodes past their own `expires_at` are evicted
+            // even with no retention rules configured
+            let retention = RetentionEngine::new(retention_cfg, score_decay_cfg);
 
             loop {
+                // Read each cycle, so a reloaded interval takes effect
+                let interval = linker.read().unwrap().config().interval;
                 tokio::time::sleep(interval).await;
 
                 {
@@ -218,21 +277,40 @@ pub async fn run(config: CortexConfig) -> anyhow::Result<()> {
                     pm.edge_count.set(m.total_edges as i64);
                 }
 
-                if let Some(ref retention) = retention_engine {
+                let mut removed = 0;
+                if has_retention {
                     match retention.sweep(storage_for_retention.as_ref()) {
                         Ok(0) => {}
-                        Ok(n) => info!("Retention: soft-deleted {} nodes", n),
+                        Ok(n) => {
+                            info!("Retention: soft-deleted {} nodes", n);
+                            removed += n;
+                        }
                         Err(e) => error!("Retention sweep failed: {}", e),
                     }
                     match retention.purge_expired(storage_for_retention.as_ref()) {
                         Ok(0) => {}
-                        Ok(n) => info!("Retention: hard-deleted {} expired nodes", n),
+                        Ok(n) => {
+                            info!("Retention: hard-deleted {} expired nodes", n);
+                            removed += n;
+                        }
                         Err(e) => error!("Retention purge failed: {}", e),
                     }
+                } else {
+                    match retention.evict_expired(storage_for_retention.as_ref()) {
+                        Ok(0) => {}
+                        Ok(n) => {
+                            info!("Retention: evicted {} expired nodes", n);
+                            removed += n;
+                        }
+                        Err(e) => error!("Retention sweep failed: {}", e),
+                    }
+                }
+                if removed > 0 {
+                    graph_version.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
                 }
             }
         })
-    };
+    });
 
     // Start briefing precomputer
     let precompute_agents = if config.briefing.precompute_agents.is_empty() {
@@ -262,7 +340,8 @@ pub async fn run(config: CortexConfig) -> anyhow::Result<()> {
         .file
         .as_ref()
         .map(|f| f.watch_dir.clone())
-        .or_else(|| std::env::var("CORTEX_INGEST_DIR").ok().map(Into::into));
+        .or_else(|| std::env::var("CORTEX_INGEST_DIR").ok().map(Into::into))
+        .filter(|_| !read_only);
 
     let _ingest_task: Option<JoinHandle<()>> = if let Some(ingest_path) = ingest_dir {
         info!("File ingest enabled, watching {:?}", ingest_path);
@@ -289,10 +368,27 @@ pub async fn run(config: CortexConfig) -> anyhow::Result<()> {
         None
     };
 
+    let kinds = config.schema.kind_registry();
+
+    // Probes see NOT_SERVING until everything below has started.
+    let (mut health_reporter, health_service) = crate::grpc::health::health_service().await;
+
+    // One limiter shared by the gRPC and HTTP servers.
+    let rate_limiter = crate::rate_limit::RateLimiter::from_config(&config.rate_limit);
+    if rate_limiter.is_some() {
+        info!(
+            "Rate limiting: max_concurrent={}, requests_per_second={}, {} per-method limit(s)",
+            config.rate_limit.max_concurrent,
+            config.rate_limit.requests_per_second,
+            config.rate_limit.methods.len()
+        );
+    }
+
     // Start gRPC server
     let grpc_task = {
-        let grpc_schema_validator =
-            Arc::new(cortex_core::SchemaValidator::new(config.schemas.clone()));
+        let grpc_schema_validator = Arc::new(
+            cortex_core::SchemaValidator::new(config.schemas.clone()).with_kinds(kinds.clone()),
+        );
 
         let grpc_service = crate::grpc::CortexServiceImpl::new(
             storage.clone(),
@@ -304,43 +400,55 @@ pub async fn run(config: CortexConfig) -> anyhow::Result<()> {
             briefing_engine.clone(),
             hooks.clone(),
             grpc_schema_validator,
-        );
+            write_gate.clone(),
+            config.score_decay.clone(),
+            read_audit.clone(),
+            cortex_metrics.clone(),
+        )
+        .with_observations(crate::observations::ObservationContext {
+            selection_config: config.prompt_selection.clone(),
+            rollback_config: rollback_config.clone(),
+            metrics: cortex_metrics.clone(),
+            webhooks: webhooks.clone(),
+            nats_publisher: nats_publisher.clone(),
+        })
+        .read_only(read_only);
 
         let addr = config.grpc_addr();
-        let grpc_auth_enabled = auth_enabled;
-        let grpc_auth_token = auth_token.clone().map(|t| format!("Bearer {}", t));
+        let grpc_rate_limit = crate::rate_limit::GrpcRateLimitLayer::new(rate_limiter.clone());
+        let grpc_auth = crate::http::auth::GrpcAuthLayer::new(http_auth.clone());
+
+        #[cfg(feature = "reflection")]
+        let reflection = (
+            tonic_reflection::server::Builder::configure()
+                .register_encoded_file_descriptor_set(cortex_proto::FILE_DESCRIPTOR_SET)
+                .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+                .build_v1()?,
+            tonic_reflection::server::Builder::configure()
+                .register_encoded_file_descriptor_set(cortex_proto::FILE_DESCRIPTOR_SET)
+                .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+                .build_v1alpha()?,
+        );
 
         tokio::spawn(async move {
             info!("Starting gRPC server on {}", addr);
-            #[allow(clippy::result_large_err)]
-            let svc = CortexServiceServer::with_interceptor(
-                grpc_service,
-                move |req: tonic::Request<()>| {
-                    if !grpc_auth_enabled {
-                        return Ok(req);
-                    }
-                    let expected = grpc_auth_token.as_deref().unwrap_or("");
-                    match req
-                        .metadata()
-                        .get("authorization")
-                        .and_then(|v| v.to_str().ok())
-                    {
-                        Some(t) if t == expected => Ok(req),
-                        _ => Err(tonic::Status::unauthenticated("Invalid or missing token")),
-                    }
-                },
-            );
-            Server::builder()
-                .add_service(svc)
-                .serve(addr)
-                .await
-                .expect("gRPC server failed");
+            let svc = CortexServiceServer::new(grpc_service);
+            let router = Server::builder()
+                .trace_fn(crate::telemetry::grpc_span)
+                .layer(grpc_auth)
+                .layer(grpc_rate_limit)
+                .add_service(health_service)
+                .add_service(svc);
+            #[cfg(feature = "reflection")]
+            let router = router.add_service(reflection.0).add_service(reflection.1);
+            router.serve(addr).await.expect("gRPC server failed");
         })
     };
 
     // Start HTTP server
     let http_task = {
-        let schema_validator = cortex_core::SchemaValidator::new(config.schemas.clone());
+        let schema_validator =
+            cortex_core::SchemaValidator::new(config.schemas.clone()).with_kinds(kinds);
 
         let app_state = crate::http::AppState {
             storage: storage.clone(),
@@ -352,24 +460,34 @@ pub async fn run(config: CortexConfig) -> anyhow::Result<()> {
             briefing_engine: briefing_engine.clone(),
             metrics: cortex_metrics.clone(),
             start_time: std::time::Instant::now(),
-            rollback_config: config.prompt_rollback.clone(),
-            webhooks: config.webhooks.clone(),
+            rollback_config: rollback_config.clone(),
+            selection_config: config.prompt_selection.clone(),
+            webhooks: webhooks.clone(),
             score_decay: config.score_decay.clone(),
-            write_gate: config.write_gate.clone(),
+            write_gate: write_gate.clone(),
             event_bus: event_bus.clone(),
             schema_validator,
             hooks: hooks.clone(),
+            nats_publisher: nats_publisher.clone(),
+            read_audit: read_audit.clone(),
         };
 
         let metrics_for_mw = cortex_metrics.clone();
-        let http_auth_token = auth_token.clone();
-        let app = crate::http::create_router(app_state)
+        let http_auth = http_auth.clone();
+        let mut app = crate::http::create_router(app_state);
+        if read_only {
+            app = app.layer(axum::middleware::from_fn(crate::http::reject_writes));
+        }
+        if let Some(limiter) = rate_limiter.clone() {
+            app = app.layer(axum::middleware::from_fn_with_state(
+                limiter,
+                crate::rate_limit::http,
+            ));
+        }
+        let app = app
             .layer(axum::middleware::from_fn(move |req, next| {
-                let tok = http_auth_token.clone();
-                async move {
-                    crate::http::auth::check(req, next, auth_enabled, tok, metrics_require_auth)
-                        .await
-                }
+                let auth = http_auth.clone();
+                async move { crate::http::auth::check(req, next, auth).await }
             }))
             .layer(axum::middleware::from_fn(
                 move |req: axum::extract::Request, next: axum::middleware::Next| {
@@ -385,7 +503,12 @@ pub async fn run(config: CortexConfig) -> anyhow::Result<()> {
                         response
                     }
                 },
-            ));
+            ))
+            .layer(
+                tower_http::trace::TraceLayer::new_for_http().make_span_with(
+                    |req: &axum::extract::Request| crate::telemetry::http_span(req),
+                ),
+            );
         let addr = config.http_addr();
 
         tokio::spawn(async move {
@@ -400,7 +523,7 @@ pub async fn run(config: CortexConfig) -> anyhow::Result<()> {
     };
 
     // Optionally start NATS consumer
-    let nats_enabled = config.server.nats_enabled;
+    let nats_enabled = config.server.nats_enabled && !read_only;
     let nats_url = config.server.nats_url.clone();
 
     let nats_task: Option<JoinHandle<()>> = if nats_enabled {
@@ -411,15 +534,46 @@ pub async fn run(config: CortexConfig) -> anyhow::Result<()> {
             match async_nats::connect(&nats_url).await {
                 Ok(client) => {
                     info!("NATS connected (Warren adapter)");
+                    let dead_letters = match &config.server.nats_dead_letter_subject {
+                        Some(subject) => {
+                            warren_adapter::DeadLetterQueue::nats(client.clone(), subject)
+                        }
+                        None => warren_adapter::DeadLetterQueue::file(
+                            config.server.data_dir.join("dead_letters.jsonl"),
+                        ),
+                    };
+                    let dead_letter_counter = cortex_metrics.warren_dead_letters.clone();
+                    let dead_letters = Arc::new(dead_letters.on_record(move || {
+                        dead_letter_counter.inc();
+                    }));
                     let nats_ingest = crate::nats::NatsIngest::new(
                         client,
                         storage.clone(),
                         embedding_service.clone(),
                         vector_index.clone(),
                         graph_version.clone(),
-                    );
+                        dead_letters,
+                        &config.warren_mappings,
+                        write_gate.clone(),
+                        cortex_core::SchemaValidator::new(config.schemas.clone())
+                            .with_kinds(config.schema.kind_registry()),
+                        cortex_metrics.clone(),
+                    )
+                    .with_subjects(config.server.nats_subjects.clone());
+                    let jetstream = config.server.nats_jetstream.then(|| {
+                        (
+                            config.server.nats_stream.clone(),
+                            config.server.nats_consumer.clone(),
+                        )
+                    });
                     Some(tokio::spawn(async move {
-                        if let Err(e) = nats_ingest.start().await {
+                        let result = match jetstream {
+                            Some((stream, consumer)) => {
+                                nats_ingest.start_jetstream(&stream, &consumer).await
+                            }
+                            None => nats_ingest.start().await,
+                        };
+                        if let Err(e) = result {
                             error!("NATS ingest failed: {}", e);
                         }
                     }))
@@ -442,15 +596,36 @@ pub async fn run(config: CortexConfig) -> anyhow::Result<()> {
         None
     };
 
+    #[cfg(unix)]
+    let reload_task = crate::reload::spawn_sighup_reloader(config_path.to_path_buf(), live_config)?;
+    #[cfg(not(unix))]
+    let _ = (config_path, live_config);
+
+    crate::grpc::health::set_status(&mut health_reporter, tonic_health::ServingStatus::Serving)
+        .await;
     info!("Cortex server ready");
 
     // Wait for shutdown signal
     tokio::signal::ctrl_c().await?;
     info!("Shutdown signal received, terminating...");
+    crate::grpc::health::set_status(
+        &mut health_reporter,
+        tonic_health::ServingStatus::NotServing,
+    )
+    .await;
 
     grpc_task.abort();
     http_task.abort();
-    auto_linker_task.abort();
+    webhook_forwarder.abort();
+    webhook_flusher.abort();
+    if let Err(e) = webhooks.flush() {
+        warn!("Could not save webhook delivery status: {}", e);
+    }
+    #[cfg(unix)]
+    reload_task.abort();
+    if let Some(task) = auto_linker_task {
+        task.abort();
+    }
     if let Some(task) = nats_task {
         task.abort();
     }
@@ -0,0 +1,331 @@
+//! Tracing setup: the fmt subscriber, plus OTLP span export when built with
+//! the `otel` feature and `[observability] opentelemetry` is on.
+//!
+//! Every gRPC and HTTP request runs in a span made by [`grpc_span`] or
+//! [`http_span`]. Its parent comes from the request's W3C `traceparent`
+//! header, so Cortex's spans (embedding, vector search, traversal) show up
+//! inside the caller's trace.
+
+use crate::config::ObservabilityConfig;
+use axum::http;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+#[cfg(feature = "otel")]
+const DEFAULT_OTLP_ENDPOINT: &str = "http://localhost:4317";
+
+/// Flushes buffered spans when dropped. Hold it until the process exits.
+#[derive(Default)]
+pub struct TelemetryGuard {
+    #[cfg(feature = "otel")]
+    provider: Option<opentelemetry_sdk::trace::TracerProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otel")]
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("Failed to flush trace spans: {}", e);
+            }
+        }
+    }
+}
+
+/// Install the global subscriber. Call once, from inside the tokio runtime.
+pub fn init(config: &ObservabilityConfig) -> anyhow::Result<TelemetryGuard> {
+    let registry = tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::from_default_env()
+                .add_directive(tracing::Level::INFO.into()),
+        )
+        .with(tracing_subscriber::fmt::layer());
+
+    #[cfg(feature = "otel")]
+    if config.opentelemetry {
+        use opentelemetry::trace::TracerProvider as _;
+
+        let provider = otlp_provider(config)?;
+        registry
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("cortex")))
+            .init();
+        return Ok(TelemetryGuard {
+            provider: Some(provider),
+        });
+    }
+
+    registry.init();
+    #[cfg(not(feature = "otel"))]
+    if config.opentelemetry {
+        tracing::warn!(
+            "[observability] opentelemetry is on, but this build has no `otel` feature; spans are not exported"
+        );
+    }
+    Ok(TelemetryGuard::default())
+}
+
+#[cfg(feature = "otel")]
+fn otlp_provider(
+    config: &ObservabilityConfig,
+) -> anyhow::Result<opentelemetry_sdk::trace::TracerProvider> {
+    use opentelemetry_otlp::WithExportConfig;
+
+    let endpoint = config
+        .otlp_endpoint
+        .clone()
+        .unwrap_or_else(|| DEFAULT_OTLP_ENDPOINT.to_string());
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()?;
+    opentelemetry::global::set_text_map_propagator(
+        opentelemetry_sdk::propagation::TraceContextPropagator::new(),
+    );
+    Ok(opentelemetry_sdk::trace::TracerProvider::builder()
+        .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+        .with_resource(opentelemetry_sdk::Resource::new([
+            opentelemetry::KeyValue::new("service.name", config.otlp_service_name.clone()),
+        ]))
+        .build())
+}
+
+/// Span for one gRPC call, for `Server::trace_fn`.
+pub fn grpc_span<B>(req: &http::Request<B>) -> tracing::Span {
+    let span = tracing::info_span!(
+        "grpc.request",
+        otel.kind = "server",
+        rpc.method = %req.uri().path(),
+    );
+    set_remote_parent(&span, req.headers());
+    span
+}
+
+/// Span for one HTTP request, named by its route (`/nodes/:id`) so ids
+/// don't split one endpoint into many span names.
+pub fn http_span<B>(req: &http::Request<B>) -> tracing::Span {
+    let route = req
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map(|p| p.as_str())
+        .unwrap_or_else(|| req.uri().path());
+    let span = tracing::info_span!(
+        "http.request",
+        otel.kind = "server",
+        http.method = %req.method(),
+        http.route = %route,
+    );
+    set_remote_parent(&span, req.headers());
+    span
+}
+
+#[cfg(feature = "otel")]
+fn set_remote_parent(span: &tracing::Span, headers: &http::HeaderMap) {
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(headers))
+    });
+    span.set_parent(parent);
+}
+
+#[cfg(not(feature = "otel"))]
+fn set_remote_parent(_span: &tracing::Span, _headers: &http::HeaderMap) {}
+
+#[cfg(feature = "otel")]
+struct HeaderExtractor<'a>(&'a http::HeaderMap);
+
+#[cfg(feature = "otel")]
+impl opentelemetry::propagation::Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|k| k.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, extract::Request, routing::get, Router};
+    use cortex_core::{HnswIndex, NodeId, VectorIndex};
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::registry::LookupSpan;
+
+    /// Each new span's name, its parent's name and its fields.
+    type Recorded = Arc<Mutex<Vec<(String, Option<String>, String)>>>;
+
+    struct Capture(Recorded);
+
+    impl<S> tracing_subscriber::Layer<S> for Capture
+    where
+        S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+    {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            id: &tracing::span::Id,
+            ctx: Context<'_, S>,
+        ) {
+            let span = ctx.span(id).unwrap();
+            let parent = span.parent().map(|p| p.name().to_string());
+            let mut fields = String::new();
+            attrs.record(
+                &mut |field: &tracing::field::Field, value: &dyn std::fmt::Debug| {
+                    fields.push_str(&format!("{}={:?} ", field.name(), value));
+                },
+            );
+            self.0
+                .lock()
+                .unwrap()
+                .push((span.name().to_string(), parent, fields));
+        }
+    }
+
+    // Runs without the `otel` feature: the spans exist either way, and
+    // the exporter only ships them.
+    #[tokio::test]
+    async fn test_request_span_wraps_handler_spans() {
+        let recorded = Recorded::default();
+        let subscriber = tracing_subscriber::registry().with(Capture(recorded.clone()));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut index = HnswIndex::new(2);
+        index.insert(NodeId::now_v7(), &vec![1.0, 0.0]).unwrap();
+        let index = Arc::new(index);
+        let app = Router::new()
+            .route(
+                "/nodes/:id",
+                get(move || {
+                    let index = index.clone();
+                    async move {
+                        index
+                            .search(&vec![1.0, 0.0], 5, None)
+                            .unwrap()
+                            .len()
+                            .to_string()
+                    }
+                }),
+            )
+            .layer(
+                tower_http::trace::TraceLayer::new_for_http()
+                    .make_span_with(|req: &Request| http_span(req)),
+            );
+        let req = Request::builder()
+            .uri("/nodes/42")
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(req).await.unwrap();
+
+        let recorded = recorded.lock().unwrap();
+        let (_, _, fields) = recorded
+            .iter()
+            .find(|(name, _, _)| name == "http.request")
+            .unwrap();
+        // Named by route, not by path
+        assert!(fields.contains("http.route=/nodes/:id"), "{fields}");
+        assert!(fields.contains("http.method=GET"), "{fields}");
+        let (_, parent, _) = recorded
+            .iter()
+            .find(|(name, _, _)| name == "vector.search")
+            .unwrap();
+        assert_eq!(parent.as_deref(), Some("http.request"));
+    }
+
+    #[test]
+    fn test_grpc_span_names_the_method() {
+        let recorded = Recorded::default();
+        let subscriber = tracing_subscriber::registry().with(Capture(recorded.clone()));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let req = http::Request::builder()
+            .uri("/cortex.v1.CortexService/SimilaritySearch")
+            .body(())
+            .unwrap();
+        let _span = grpc_span(&req);
+        let recorded = recorded.lock().unwrap();
+        assert_eq!(recorded[0].0, "grpc.request");
+        assert!(recorded[0]
+            .2
+            .contains("rpc.method=/cortex.v1.CortexService/SimilaritySearch"));
+    }
+}
+
+#[cfg(all(test, feature = "otel"))]
+mod otel_tests {
+    use super::*;
+    use axum::{body::Body, extract::Request, routing::get, Router};
+    use cortex_core::{HnswIndex, NodeId, VectorIndex};
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_sdk::testing::trace::InMemorySpanExporter;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_request_span_joins_incoming_trace() {
+        let exporter = InMemorySpanExporter::default();
+        let provider = opentelemetry_sdk::trace::TracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        opentelemetry::global::set_text_map_propagator(
+            opentelemetry_sdk::propagation::TraceContextPropagator::new(),
+        );
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut index = HnswIndex::new(2);
+        index.insert(NodeId::now_v7(), &vec![1.0, 0.0]).unwrap();
+        let index = Arc::new(index);
+        let app = Router::new()
+            .route(
+                "/search",
+                get(move || {
+                    let index = index.clone();
+                    async move {
+                        let hits = index.search(&vec![1.0, 0.0], 5, None).unwrap();
+                        hits.len().to_string()
+                    }
+                }),
+            )
+            .layer(
+                tower_http::trace::TraceLayer::new_for_http()
+                    .make_span_with(|req: &Request| http_span(req)),
+            );
+
+        let trace_id = "4bf92f3577b34da6a3ce929d0e0e4736";
+        let caller_span_id = "00f067aa0ba902b7";
+        let req = Request::builder()
+            .uri("/search")
+            .header(
+                "traceparent",
+                format!("00-{}-{}-01", trace_id, caller_span_id),
+            )
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(req).await.unwrap();
+
+        let spans = exporter.get_finished_spans().unwrap();
+        let request = spans.iter().find(|s| s.name == "http.request").unwrap();
+        let search = spans.iter().find(|s| s.name == "vector.search").unwrap();
+
+        // The request span continues the caller's trace...
+        assert_eq!(request.span_context.trace_id().to_string(), trace_id);
+        assert_eq!(request.parent_span_id.to_string(), caller_span_id);
+        // ...and the index search nests under it, carrying its counts
+        assert_eq!(
+            search.span_context.trace_id(),
+            request.span_context.trace_id()
+        );
+        assert_eq!(search.parent_span_id, request.span_context.span_id());
+        let results = search
+            .attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == "results")
+            .map(|kv| kv.value.to_string());
+        assert_eq!(results.as_deref(), Some("1"));
+    }
+}
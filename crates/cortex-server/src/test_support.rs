@@ -0,0 +1,98 @@
+//! Test doubles shared by the crate's unit tests.
+
+use crate::observability::publish::EventSink;
+use async_trait::async_trait;
+use cortex_core::{Embedding, EmbeddingService};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Pseudo-random vectors keyed by text, so distinct texts are nowhere near
+/// the conflict threshold and the same text always embeds the same.
+pub struct HashEmbedder;
+
+impl HashEmbedder {
+    pub const DIMENSION: usize = 32;
+}
+
+impl EmbeddingService for HashEmbedder {
+    fn embed(&self, text: &str) -> cortex_core::Result<Embedding> {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        text.hash(&mut hasher);
+        let mut x = hasher.finish();
+        Ok((0..Self::DIMENSION)
+            .map(|_| {
+                x = x.wrapping_mul(6364136223846793005).wrapping_add(1);
+                ((x >> 33) as f32 / u32::MAX as f32) - 0.25
+            })
+            .collect())
+    }
+    fn embed_batch(&self, texts: &[String]) -> cortex_core::Result<Vec<Embedding>> {
+        texts.iter().map(|t| self.embed(t)).collect()
+    }
+    fn dimension(&self) -> usize {
+        Self::DIMENSION
+    }
+    fn model_name(&self) -> &str {
+        "hash"
+    }
+}
+
+/// A [`HashEmbedder`] that counts the texts it is asked to embed.
+#[derive(Default)]
+pub struct CountingEmbedder {
+    calls: AtomicUsize,
+}
+
+impl CountingEmbedder {
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+impl EmbeddingService for CountingEmbedder {
+    fn embed(&self, text: &str) -> cortex_core::Result<Embedding> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        HashEmbedder.embed(text)
+    }
+    fn embed_batch(&self, texts: &[String]) -> cortex_core::Result<Vec<Embedding>> {
+        self.calls.fetch_add(texts.len(), Ordering::SeqCst);
+        HashEmbedder.embed_batch(texts)
+    }
+    fn dimension(&self) -> usize {
+        HashEmbedder::DIMENSION
+    }
+    fn model_name(&self) -> &str {
+        "hash"
+    }
+}
+
+/// In-memory stand-in for a NATS server.
+#[derive(Default)]
+pub struct MemoryBroker {
+    pub published: Mutex<Vec<(String, serde_json::Value)>>,
+}
+
+impl MemoryBroker {
+    /// What has been published, once there are `expected` events or after
+    /// half a second, whichever comes first.
+    pub async fn drained(&self, expected: usize) -> Vec<(String, serde_json::Value)> {
+        for _ in 0..50 {
+            if self.published.lock().unwrap().len() >= expected {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        self.published.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl EventSink for MemoryBroker {
+    async fn publish(&self, subject: String, payload: Vec<u8>) -> Result<(), String> {
+        let value = serde_json::from_slice(&payload).map_err(|e| e.to_string())?;
+        self.published.lock().unwrap().push((subject, value));
+        Ok(())
+    }
+}
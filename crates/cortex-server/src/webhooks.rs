@@ -0,0 +1,744 @@
+//! Outbound webhooks: subscriptions, event filtering and signed delivery.
+//!
+//! Subscriptions come from `[[webhooks]]` in cortex.toml, which are fixed
+//! for the life of the process, and from the `/webhooks` HTTP API, which
+//! are stored in the database's meta table and survive restarts.
+//!
+//! Each delivery is a JSON POST. When the subscription has a secret, the
+//! body is signed with HMAC-SHA256 and the hex digest sent as
+//! `X-Cortex-Signature: sha256=<hex>`. Network errors, timeouts, 429 and
+//! 5xx are retried with exponential backoff in a background task, so the
+//! request that raised the event never waits on a receiver. The outcome of
+//! the last delivery is logged and kept on the subscription; for API
+//! subscriptions it is saved by a periodic flush rather than on every
+//! delivery.
+
+use crate::config::WebhookConfig;
+use chrono::{DateTime, Utc};
+use cortex_core::{RedbStorage, Storage};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Meta-table key holding the API-registered subscriptions as JSON.
+const WEBHOOKS_META_KEY: &str = "webhooks";
+
+/// How often [`WebhookRegistry::spawn_flusher`] saves delivery outcomes.
+pub const STATUS_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+pub const SIGNATURE_HEADER: &str = "X-Cortex-Signature";
+pub const EVENT_HEADER: &str = "X-Cortex-Event";
+pub const DELIVERY_HEADER: &str = "X-Cortex-Delivery";
+
+/// Where a subscription was defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookSource {
+    /// `[[webhooks]]` in cortex.toml. Can't be deleted over the API.
+    Config,
+    /// Registered with `POST /webhooks`.
+    Api,
+}
+
+/// Outcome of the most recent delivery to a subscription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeliveryStatus {
+    pub event: String,
+    pub delivered_at: DateTime<Utc>,
+    pub success: bool,
+    /// HTTP status of the final attempt, if the endpoint answered.
+    pub status_code: Option<u16>,
+    pub attempts: u32,
+    pub error: Option<String>,
+}
+
+/// One webhook subscription.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub url: String,
+    /// Event names to deliver: `rollback`, `node.created`, `edge.deleted`,
+    /// ... or `*` for everything.
+    pub events: Vec<String>,
+    /// HMAC-SHA256 key for signing payloads. Never returned by the API.
+    pub secret: Option<String>,
+    pub source: WebhookSource,
+    pub created_at: DateTime<Utc>,
+    pub last_delivery: Option<DeliveryStatus>,
+}
+
+impl Webhook {
+    /// Whether this subscription wants `event`.
+    pub fn accepts(&self, event: &str) -> bool {
+        self.events.iter().any(|e| e == "*" || e == event)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebhookError {
+    #[error("Invalid url: {0}")]
+    InvalidUrl(String),
+    #[error("Webhook {0} is defined in cortex.toml and can't be deleted")]
+    FromConfig(Uuid),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// Retry schedule for failed deliveries: `max_attempts` tries in all,
+/// waiting `base_delay`, then twice that, and so on between them. An
+/// attempt with no response within `timeout` counts as failed.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub timeout: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Hex HMAC-SHA256 of `body` under `secret`, as sent in the signature header
+/// after `sha256=`.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Check a signature header value (`sha256=<hex>`) against `body`, in
+/// constant time. For receivers, and for tests.
+pub fn verify(secret: &str, body: &[u8], header: &str) -> bool {
+    let Some(digest) = header
+        .strip_prefix("sha256=")
+        .and_then(|h| hex::decode(h).ok())
+    else {
+        return false;
+    };
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    mac.verify_slice(&digest).is_ok()
+}
+
+/// Id of a `[[webhooks]]` entry, derived from its url and events so it
+/// stays the same across restarts. `n` tells identical entries apart.
+fn config_hook_id(config: &WebhookConfig, n: usize) -> Uuid {
+    use sha2::Digest;
+
+    let digest = Sha256::new()
+        .chain_update(config.url.as_bytes())
+        .chain_update(b"\n")
+        .chain_update(config.events.join(",").as_bytes())
+        .chain_update(n.to_le_bytes())
+        .finalize();
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    uuid::Builder::from_custom_bytes(bytes).into_uuid()
+}
+
+pub struct WebhookRegistry {
+    storage: Arc<RedbStorage>,
+    hooks: RwLock<Vec<Webhook>>,
+    /// Delivery outcomes changed since the last save.
+    dirty: AtomicBool,
+    /// Serializes saves, so an older snapshot never overwrites a newer one.
+    /// Taken before `hooks`.
+    persisting: Mutex<()>,
+    client: reqwest::Client,
+    retry: RetryPolicy,
+}
+
+impl WebhookRegistry {
+    /// Subscriptions from `config` followed by those stored in the database.
+    pub fn load(storage: Arc<RedbStorage>, config: &[WebhookConfig]) -> anyhow::Result<Self> {
+        let now = Utc::now();
+        let mut hooks: Vec<Webhook> = config
+            .iter()
+            .enumerate()
+            .map(|(i, c)| Webhook {
+                id: config_hook_id(
+                    c,
+                    config[..i]
+                        .iter()
+                        .filter(|p| p.url == c.url && p.events == c.events)
+                        .count(),
+                ),
+                url: c.url.clone(),
+                events: c.events.clone(),
+                secret: c.secret.clone(),
+                source: WebhookSource::Config,
+                created_at: now,
+                last_delivery: None,
+            })
+            .collect();
+        if let Some(bytes) = storage.get_metadata(WEBHOOKS_META_KEY)? {
+            let stored: Vec<Webhook> = serde_json::from_slice(&bytes)?;
+            hooks.extend(stored);
+        }
+        if storage.is_read_only() {
+            log::warn!(
+                "database is read-only: webhooks can't be registered or removed, \
+                 and delivery outcomes won't be saved"
+            );
+        }
+        Ok(Self {
+            storage,
+            hooks: RwLock::new(hooks),
+            dirty: AtomicBool::new(false),
+            persisting: Mutex::new(()),
+            client: reqwest::Client::new(),
+            retry: RetryPolicy::default(),
+        })
+    }
+
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn list(&self) -> Vec<Webhook> {
+        self.hooks.read().unwrap().clone()
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<Webhook> {
+        self.hooks
+            .read()
+            .unwrap()
+            .iter()
+            .find(|h| h.id == id)
+            .cloned()
+    }
+
+    /// Add and persist a subscription. An empty `events` list means `*`.
+    pub fn register(
+        &self,
+        url: String,
+        events: Vec<String>,
+        secret: Option<String>,
+    ) -> Result<Webhook, WebhookError> {
+        let parsed =
+            reqwest::Url::parse(&url).map_err(|e| WebhookError::InvalidUrl(e.to_string()))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(WebhookError::InvalidUrl(
+                "scheme must be http or https".into(),
+            ));
+        }
+        let events = if events.is_empty() {
+            vec!["*".to_string()]
+        } else {
+            events
+        };
+        let hook = Webhook {
+            id: Uuid::now_v7(),
+            url,
+            events,
+            secret: secret.filter(|s| !s.is_empty()),
+            source: WebhookSource::Api,
+            created_at: Utc::now(),
+            last_delivery: None,
+        };
+        let _persisting = self.persisting.lock().unwrap();
+        let mut hooks = self.hooks.write().unwrap();
+        let mut updated = hooks.clone();
+        updated.push(hook.clone());
+        self.persist(&updated)?;
+        *hooks = updated;
+        Ok(hook)
+    }
+
+    /// Remove an API-registered subscription. Returns false if there is none
+    /// with this id; config subscriptions are refused.
+    pub fn remove(&self, id: Uuid) -> Result<bool, WebhookError> {
+        let _persisting = self.persisting.lock().unwrap();
+        let mut hooks = self.hooks.write().unwrap();
+        let Some(pos) = hooks.iter().position(|h| h.id == id) else {
+            return Ok(false);
+        };
+        if hooks[pos].source == WebhookSource::Config {
+            return Err(WebhookError::FromConfig(id));
+        }
+        let mut updated = hooks.clone();
+        updated.remove(pos);
+        self.persist(&updated)?;
+        *hooks = updated;
+        Ok(true)
+    }
+
+    /// Subscriptions that want `event`.
+    pub fn matching(&self, event: &str) -> Vec<Webhook> {
+        self.hooks
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|h| h.accepts(event))
+            .cloned()
+            .collect()
+    }
+
+    /// Deliver `payload` to every subscription that wants `event`, in the
+    /// background.
+    pub fn dispatch(self: &Arc<Self>, event: &str, payload: serde_json::Value) {
+        for hook in self.matching(event) {
+            let registry = self.clone();
+            let event = event.to_string();
+            let payload = payload.clone();
+            tokio::spawn(async move {
+                registry.deliver(&hook, &event, &payload).await;
+            });
+        }
+    }
+
+    /// POST `payload` to `hook`, retrying per the policy, and record the
+    /// outcome on the subscription.
+    pub async fn deliver(
+        &self,
+        hook: &Webhook,
+        event: &str,
+        payload: &serde_json::Value,
+    ) -> DeliveryStatus {
+        let body = serde_json::to_vec(payload).unwrap_or_default();
+        let delivery_id = Uuid::now_v7().to_string();
+        let mut attempts = 0;
+        let (status_code, error) = loop {
+            attempts += 1;
+            let mut req = self
+                .client
+                .post(&hook.url)
+                .header("Content-Type", "application/json")
+                .header(EVENT_HEADER, event)
+                .header(DELIVERY_HEADER, &delivery_id)
+                .timeout(self.retry.timeout)
+                .body(body.clone());
+            if let Some(secret) = &hook.secret {
+                req = req.header(SIGNATURE_HEADER, format!("sha256={}", sign(secret, &body)));
+            }
+            let (status_code, error, retryable) = match req.send().await {
+                Ok(resp) => {
+                    let status = resp.status();
+                    let retryable = status.is_server_error() || status.as_u16() == 429;
+                    let error = (!status.is_success()).then(|| format!("HTTP {}", status));
+                    (Some(status.as_u16()), error, retryable)
+                }
+                Err(e) => (None, Some(e.to_string()), true),
+            };
+            if error.is_none() || !retryable || attempts >= self.retry.max_attempts {
+                break (status_code, error);
+            }
+            tokio::time::sleep(self.retry.base_delay * 2u32.pow(attempts - 1)).await;
+        };
+
+        match &error {
+            Some(e) => log::warn!(
+                "webhook {} to {} failed after {} attempt(s): {}",
+                event,
+                hook.url,
+                attempts,
+                e
+            ),
+            None => log::info!(
+                "webhook {} delivered to {} after {} attempt(s)",
+                event,
+                hook.url,
+                attempts
+            ),
+        }
+        let status = DeliveryStatus {
+            event: event.to_string(),
+            delivered_at: Utc::now(),
+            success: error.is_none(),
+            status_code,
+            attempts,
+            error,
+        };
+        self.record(hook.id, status.clone());
+        status
+    }
+
+    /// Keep `status` on the subscription. API subscriptions are saved by
+    /// the next [`flush`](Self::flush).
+    fn record(&self, id: Uuid, status: DeliveryStatus) {
+        let mut hooks = self.hooks.write().unwrap();
+        let Some(hook) = hooks.iter_mut().find(|h| h.id == id) else {
+            return;
+        };
+        hook.last_delivery = Some(status);
+        if hook.source == WebhookSource::Api && !self.storage.is_read_only() {
+            self.dirty.store(true, Ordering::Release);
+        }
+    }
+
+    /// Save delivery outcomes recorded since the last flush. The database
+    /// write happens outside the subscription lock.
+    pub fn flush(&self) -> anyhow::Result<()> {
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            return Ok(());
+        }
+        let _persisting = self.persisting.lock().unwrap();
+        let hooks = self.hooks.read().unwrap().clone();
+        self.persist(&hooks).inspect_err(|_| {
+            self.dirty.store(true, Ordering::Release);
+        })
+    }
+
+    /// [`flush`](Self::flush) every `every` on the blocking pool, logging
+    /// failures. Call `flush` once more on shutdown.
+    pub fn spawn_flusher(self: &Arc<Self>, every: Duration) -> tokio::task::JoinHandle<()> {
+        let registry = self.clone();
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(every);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticks.tick().await;
+                let registry = registry.clone();
+                match tokio::task::spawn_blocking(move || registry.flush()).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => log::warn!("could not save webhook delivery status: {}", e),
+                    Err(e) => log::warn!("webhook status flush panicked: {}", e),
+                }
+            }
+        })
+    }
+
+    fn persist(&self, hooks: &[Webhook]) -> anyhow::Result<()> {
+        let stored: Vec<&Webhook> = hooks
+            .iter()
+            .filter(|h| h.source == WebhookSource::Api)
+            .collect();
+        self.storage
+            .put_metadata(WEBHOOKS_META_KEY, &serde_json::to_vec(&stored)?)?;
+        Ok(())
+    }
+}
+
+/// Forward graph mutation events from the event bus to matching webhooks.
+pub fn spawn_event_forwarder(
+    registry: Arc<WebhookRegistry>,
+    bus: &crate::observability::EventBus,
+) -> tokio::task::JoinHandle<()> {
+    let mut rx = bus.subscribe();
+    tokio::spawn(async move {
+        use tokio::sync::broadcast::error::RecvError;
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    let payload = serde_json::to_value(&event).unwrap_or_default();
+                    registry.dispatch(&event.event_type, payload);
+                }
+                Err(RecvError::Lagged(n)) => {
+                    log::warn!("webhook forwarder lagged, {} events not delivered", n);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, http::HeaderMap, http::StatusCode, routing::post, Router};
+    use std::sync::atomic::AtomicUsize;
+    use tempfile::TempDir;
+
+    fn registry(dir: &TempDir, config: &[WebhookConfig]) -> WebhookRegistry {
+        let storage = Arc::new(RedbStorage::open(dir.path().join("hooks.redb")).unwrap());
+        WebhookRegistry::load(storage, config)
+            .unwrap()
+            .with_retry(RetryPolicy {
+                max_attempts: 3,
+                base_delay: Duration::from_millis(1),
+                timeout: Duration::from_millis(200),
+            })
+    }
+
+    #[derive(Clone, Default)]
+    struct Receiver {
+        /// Respond 500 to this many requests before accepting.
+        fail_first: usize,
+        /// Answer none of this many requests until the client has timed out.
+        stall_first: usize,
+        calls: Arc<AtomicUsize>,
+        received: Received,
+    }
+
+    /// Headers and body of each accepted request.
+    type Received = Arc<Mutex<Vec<(HeaderMap, Vec<u8>)>>>;
+
+    /// Serve `receiver` on a free local port and return its URL.
+    async fn serve(receiver: Receiver) -> String {
+        let app = Router::new()
+            .route(
+                "/hook",
+                post(
+                    |State(r): State<Receiver>, headers: HeaderMap, body: axum::body::Bytes| async move {
+                        let call = r.calls.fetch_add(1, Ordering::SeqCst);
+                        if call < r.stall_first {
+                            tokio::time::sleep(Duration::from_secs(2)).await;
+                            return StatusCode::OK;
+                        }
+                        if call < r.fail_first {
+                            return StatusCode::INTERNAL_SERVER_ERROR;
+                        }
+                        r.received.lock().unwrap().push((headers, body.to_vec()));
+                        StatusCode::OK
+                    },
+                ),
+            )
+            .with_state(receiver);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}/hook", addr)
+    }
+
+    #[test]
+    fn test_register_persists_across_reload() {
+        let dir = TempDir::new().unwrap();
+        let config = vec![WebhookConfig {
+            url: "http://example.com/rollbacks".into(),
+            events: vec!["rollback".into()],
+            secret: None,
+        }];
+        let hooks = registry(&dir, &config);
+        let hook = hooks
+            .register(
+                "https://example.com/nodes".into(),
+                vec!["node.created".into()],
+                Some("s3cret".into()),
+            )
+            .unwrap();
+        assert!(hooks
+            .register("ftp://example.com".into(), vec![], None)
+            .is_err());
+        drop(hooks);
+
+        let reloaded = registry(&dir, &config);
+        let listed = reloaded.list();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].source, WebhookSource::Config);
+        let stored = reloaded.get(hook.id).unwrap();
+        assert_eq!(stored.url, "https://example.com/nodes");
+        assert_eq!(stored.secret.as_deref(), Some("s3cret"));
+
+        // Config subscriptions stay put; API ones can be removed
+        assert!(reloaded.remove(listed[0].id).is_err());
+        assert!(reloaded.remove(hook.id).unwrap());
+        assert!(!reloaded.remove(hook.id).unwrap());
+        drop(reloaded);
+        assert_eq!(registry(&dir, &config).list().len(), 1);
+    }
+
+    #[test]
+    fn test_config_ids_are_stable_across_restarts() {
+        let dir = TempDir::new().unwrap();
+        let entry = |url: &str| WebhookConfig {
+            url: url.into(),
+            events: vec!["rollback".into()],
+            secret: None,
+        };
+        let config = vec![
+            entry("http://a.test/"),
+            entry("http://b.test/"),
+            entry("http://a.test/"),
+        ];
+        let ids = |hooks: &WebhookRegistry| -> Vec<Uuid> {
+            hooks.list().into_iter().map(|h| h.id).collect()
+        };
+        let first = ids(&registry(&dir, &config));
+        assert_eq!(ids(&registry(&dir, &config)), first);
+        // Identical entries still get their own ids
+        assert_ne!(first[0], first[2]);
+        assert_eq!(
+            first.iter().collect::<std::collections::HashSet<_>>().len(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delivery_status_is_saved_on_flush() {
+        let url = serve(Receiver::default()).await;
+        let dir = TempDir::new().unwrap();
+        let hooks = registry(&dir, &[]);
+        let hook = hooks.register(url, vec![], None).unwrap();
+        hooks
+            .deliver(&hook, "node.created", &serde_json::json!({}))
+            .await;
+
+        let stored = |dir: &TempDir| {
+            let storage = RedbStorage::open_read_only(dir.path().join("hooks.redb")).unwrap();
+            let bytes = storage.get_metadata(WEBHOOKS_META_KEY).unwrap().unwrap();
+            let hooks: Vec<Webhook> = serde_json::from_slice(&bytes).unwrap();
+            hooks[0].last_delivery.clone()
+        };
+        drop(hooks);
+        assert!(stored(&dir).is_none(), "saved before a flush");
+
+        let hooks = registry(&dir, &[]);
+        let status = hooks
+            .deliver(&hook, "node.created", &serde_json::json!({}))
+            .await;
+        hooks.flush().unwrap();
+        drop(hooks);
+        assert_eq!(stored(&dir), Some(status));
+    }
+
+    #[test]
+    fn test_event_filter() {
+        let dir = TempDir::new().unwrap();
+        let hooks = registry(&dir, &[]);
+        let rollback = hooks
+            .register("http://a.test/".into(), vec!["rollback".into()], None)
+            .unwrap();
+        let created = hooks
+            .register("http://b.test/".into(), vec!["node.created".into()], None)
+            .unwrap();
+        let all = hooks
+            .register("http://c.test/".into(), vec![], None)
+            .unwrap();
+        assert_eq!(all.events, vec!["*"]);
+
+        let ids = |event: &str| -> Vec<Uuid> {
+            hooks.matching(event).into_iter().map(|h| h.id).collect()
+        };
+        assert_eq!(ids("rollback"), vec![rollback.id, all.id]);
+        assert_eq!(ids("node.created"), vec![created.id, all.id]);
+        assert_eq!(ids("edge.deleted"), vec![all.id]);
+    }
+
+    #[test]
+    fn test_signature_round_trip() {
+        let body = br#"{"event":"prompt.rollback"}"#;
+        let header = format!("sha256={}", sign("s3cret", body));
+        assert!(verify("s3cret", body, &header));
+        assert!(!verify("wrong", body, &header));
+        assert!(!verify("s3cret", b"{}", &header));
+        assert!(!verify("s3cret", body, "md5=abc"));
+    }
+
+    #[tokio::test]
+    async fn test_delivery_is_signed_and_retried() {
+        let receiver = Receiver {
+            fail_first: 1,
+            ..Default::default()
+        };
+        let url = serve(receiver.clone()).await;
+        let dir = TempDir::new().unwrap();
+        let hooks = registry(&dir, &[]);
+        let hook = hooks
+            .register(url, vec!["rollback".into()], Some("s3cret".into()))
+            .unwrap();
+
+        let payload = serde_json::json!({"event": "prompt.rollback", "agent": "kai"});
+        let status = hooks.deliver(&hook, "rollback", &payload).await;
+        assert!(status.success);
+        assert_eq!(status.attempts, 2);
+        assert_eq!(status.status_code, Some(200));
+        assert_eq!(hooks.get(hook.id).unwrap().last_delivery, Some(status));
+
+        let received = receiver.received.lock().unwrap();
+        let (headers, body) = &received[0];
+        assert_eq!(headers[EVENT_HEADER], "rollback");
+        let signature = headers[SIGNATURE_HEADER].to_str().unwrap();
+        assert!(verify("s3cret", body, signature));
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(body).unwrap(),
+            payload
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delivery_gives_up_after_max_attempts() {
+        let receiver = Receiver {
+            fail_first: usize::MAX,
+            ..Default::default()
+        };
+        let url = serve(receiver.clone()).await;
+        let dir = TempDir::new().unwrap();
+        let hooks = registry(&dir, &[]);
+        let hook = hooks.register(url, vec![], None).unwrap();
+
+        let status = hooks
+            .deliver(&hook, "node.created", &serde_json::json!({}))
+            .await;
+        assert!(!status.success);
+        assert_eq!(status.attempts, 3);
+        assert_eq!(status.status_code, Some(500));
+        assert_eq!(receiver.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_is_retried() {
+        let receiver = Receiver {
+            stall_first: 1,
+            ..Default::default()
+        };
+        let url = serve(receiver.clone()).await;
+        let dir = TempDir::new().unwrap();
+        let hooks = registry(&dir, &[]);
+        let hook = hooks.register(url, vec![], None).unwrap();
+
+        let status = hooks
+            .deliver(&hook, "rollback", &serde_json::json!({}))
+            .await;
+        assert!(status.success);
+        assert_eq!(status.attempts, 2);
+        assert_eq!(receiver.received.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_config_rollback_webhook_is_signed_in_background() {
+        let receiver = Receiver {
+            fail_first: 1,
+            ..Default::default()
+        };
+        let url = serve(receiver.clone()).await;
+        let dir = TempDir::new().unwrap();
+        let config = vec![WebhookConfig {
+            url,
+            events: vec!["rollback".into()],
+            secret: Some("s3cret".into()),
+        }];
+        let hooks = Arc::new(registry(&dir, &config));
+
+        // Returns before anything is delivered
+        let payload = serde_json::json!({"event": "prompt.rollback", "to_version": 2});
+        hooks.dispatch("rollback", payload.clone());
+        hooks.dispatch("node.created", serde_json::json!({}));
+        assert!(hooks.list()[0].last_delivery.is_none());
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while hooks.list()[0].last_delivery.is_none() {
+            assert!(std::time::Instant::now() < deadline, "no delivery recorded");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let status = hooks.list()[0].last_delivery.clone().unwrap();
+        assert!(status.success);
+        assert_eq!(status.attempts, 2);
+
+        // Only the rollback went out, signed with the configured secret
+        let received = receiver.received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        let (headers, body) = &received[0];
+        assert!(verify(
+            "s3cret",
+            body,
+            headers[SIGNATURE_HEADER].to_str().unwrap()
+        ));
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(body).unwrap(),
+            payload
+        );
+    }
+}
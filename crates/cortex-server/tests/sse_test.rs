@@ -1,6 +1,11 @@
+use axum::response::{sse::Sse, IntoResponse};
 use cortex_core::hooks::{MutationAction, MutationHook};
-use cortex_core::{Edge, EdgeProvenance, Node, NodeKind, Relation, Source};
-use cortex_memory::observability::{new_event_bus, EventBusHook, GraphEvent};
+use cortex_core::{Edge, EdgeProvenance, HookRegistry, Node, NodeKind, Relation, Source};
+use cortex_memory::observability::{new_event_bus, sse_stream, EventBusHook, GraphEvent};
+use futures::StreamExt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 fn make_test_node() -> Node {
     Node::new(
@@ -136,3 +141,48 @@ fn test_multiple_sse_subscribers() {
     assert_eq!(event2.event_type, "node.updated");
     assert_eq!(event1.data["title"], event2.data["title"]);
 }
+
+async fn next_frame(body: &mut axum::body::BodyDataStream) -> String {
+    let chunk = tokio::time::timeout(Duration::from_secs(1), body.next())
+        .await
+        .expect("SSE frame within a second")
+        .expect("stream still open")
+        .unwrap();
+    String::from_utf8(chunk.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_create_node_produces_sse_frame() {
+    // Wire the hook the way the server does, connect an SSE client that last
+    // saw version 2, then notify a node creation as POST /nodes does.
+    let bus = new_event_bus(64);
+    let version = Arc::new(AtomicU64::new(3));
+    let mut hooks = HookRegistry::new();
+    hooks.add(Arc::new(
+        EventBusHook::new(bus.clone()).with_graph_version(version.clone()),
+    ));
+
+    let stream = sse_stream(
+        bus.subscribe(),
+        version.load(Ordering::Relaxed),
+        Some(2),
+        None,
+    );
+    let mut body = Sse::new(stream)
+        .into_response()
+        .into_body()
+        .into_data_stream();
+    let connected = next_frame(&mut body).await;
+    assert!(connected.contains("event: connected"), "{connected}");
+    assert!(connected.contains("\"graph_version\":3"), "{connected}");
+    assert!(connected.contains("\"missed\":true"), "{connected}");
+
+    let node = make_test_node();
+    version.fetch_add(1, Ordering::Relaxed);
+    hooks.notify_node(&node, MutationAction::Created);
+
+    let frame = next_frame(&mut body).await;
+    assert!(frame.contains("event: node.created"), "{frame}");
+    assert!(frame.contains("id: 4"), "{frame}");
+    assert!(frame.contains(&node.id.to_string()), "{frame}");
+}
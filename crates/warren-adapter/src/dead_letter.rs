@@ -0,0 +1,125 @@
+//! Dead-letter sink for Warren events that cannot be ingested.
+//!
+//! Failed messages are kept with their subject, raw payload and error, either
+//! appended to a local JSONL file or republished to a NATS subject, so they
+//! can be inspected and replayed instead of vanishing into the logs.
+
+use async_nats::Client;
+use cortex_core::*;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// A message that could not be turned into a node.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeadLetter {
+    pub subject: String,
+    /// Raw payload, lossily decoded as UTF-8.
+    pub payload: String,
+    pub error: String,
+    pub received_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl DeadLetter {
+    pub fn new(subject: &str, payload: &[u8], error: impl ToString) -> Self {
+        Self {
+            subject: subject.to_string(),
+            payload: String::from_utf8_lossy(payload).into_owned(),
+            error: error.to_string(),
+            received_at: chrono::Utc::now(),
+        }
+    }
+}
+
+enum Sink {
+    File { path: PathBuf, lock: Mutex<()> },
+    Nats { client: Client, subject: String },
+}
+
+/// Where dead letters go, plus a running count.
+pub struct DeadLetterQueue {
+    sink: Sink,
+    count: AtomicU64,
+    on_record: Option<Box<dyn Fn() + Send + Sync>>,
+}
+
+impl DeadLetterQueue {
+    /// Append dead letters to a JSONL file.
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self::with_sink(Sink::File {
+            path: path.into(),
+            lock: Mutex::new(()),
+        })
+    }
+
+    /// Republish dead letters as JSON to a NATS subject.
+    pub fn nats(client: Client, subject: impl Into<String>) -> Self {
+        Self::with_sink(Sink::Nats {
+            client,
+            subject: subject.into(),
+        })
+    }
+
+    fn with_sink(sink: Sink) -> Self {
+        Self {
+            sink,
+            count: AtomicU64::new(0),
+            on_record: None,
+        }
+    }
+
+    /// Call `f` for every dead letter, e.g. to bump a metrics counter.
+    pub fn on_record(mut self, f: impl Fn() + Send + Sync + 'static) -> Self {
+        self.on_record = Some(Box::new(f));
+        self
+    }
+
+    /// Number of dead letters recorded since startup.
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Record a dead letter. Counted even if the sink write fails.
+    pub async fn record(&self, letter: DeadLetter) -> Result<()> {
+        tracing::warn!(
+            "Dead-lettering message on {}: {}",
+            letter.subject,
+            letter.error
+        );
+        self.count.fetch_add(1, Ordering::Relaxed);
+        if let Some(f) = &self.on_record {
+            f();
+        }
+
+        let line = serde_json::to_string(&letter)
+            .map_err(|e| CortexError::Validation(format!("Dead letter encode failed: {}", e)))?;
+        match &self.sink {
+            Sink::File { path, lock } => {
+                let _guard = lock.lock().unwrap();
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .and_then(|mut f| writeln!(f, "{}", line))
+                    .map_err(|e| {
+                        CortexError::Validation(format!(
+                            "Dead letter write to {} failed: {}",
+                            path.display(),
+                            e
+                        ))
+                    })
+            }
+            Sink::Nats { client, subject } => client
+                .publish(subject.clone(), line.into())
+                .await
+                .map_err(|e| {
+                    CortexError::Validation(format!(
+                        "Dead letter publish to {} failed: {}",
+                        subject, e
+                    ))
+                }),
+        }
+    }
+}
@@ -1,5 +1,9 @@
+pub mod dead_letter;
+pub mod mapping;
 pub mod nats;
 pub mod types;
 
-pub use nats::WarrenNatsAdapter;
+pub use dead_letter::{DeadLetter, DeadLetterQueue};
+pub use mapping::{EventMapping, EventMappings};
+pub use nats::{default_subjects, JetStreamConfig, NodeGate, WarrenNatsAdapter};
 pub use types::WarrenEvent;
@@ -0,0 +1,220 @@
+//! Configurable Warren event → node mapping.
+//!
+//! Each event type can override the node kind, importance, and title and
+//! body templates. Templates interpolate `{field}` from the event payload,
+//! e.g. `"Gate {gate_id} rejected: {reason}"`. Anything not overridden
+//! keeps the built-in mapping from [`WarrenEvent::to_node`].
+
+use crate::types::WarrenEvent;
+use cortex_core::*;
+use std::collections::HashMap;
+
+/// Overrides for one event type. Unset fields use the built-in mapping.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventMapping {
+    pub kind: Option<NodeKind>,
+    pub importance: Option<f32>,
+    pub title_template: Option<String>,
+    pub body_template: Option<String>,
+}
+
+/// Event type (e.g. `"gate.rejected"`) → overrides.
+#[derive(Debug, Clone, Default)]
+pub struct EventMappings {
+    by_type: HashMap<String, EventMapping>,
+}
+
+impl EventMappings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the mapping for `event_type`.
+    pub fn with(mut self, event_type: impl Into<String>, mapping: EventMapping) -> Self {
+        self.by_type.insert(event_type.into(), mapping);
+        self
+    }
+
+    pub fn get(&self, event_type: &str) -> Option<&EventMapping> {
+        self.by_type.get(event_type)
+    }
+
+    /// Convert `event` to a node, applying any override for its type.
+    pub fn to_node(&self, event: &WarrenEvent, source_agent: &str) -> Node {
+        let mut node = event.to_node(source_agent);
+        let Some(mapping) = self.get(event.event_type()) else {
+            return node;
+        };
+
+        let fields = event_fields(event);
+        if let Some(kind) = &mapping.kind {
+            node.kind = kind.clone();
+        }
+        if let Some(importance) = mapping.importance {
+            node.importance = importance.clamp(0.0, 1.0);
+        }
+        if let Some(template) = &mapping.title_template {
+            node.data.title = interpolate(template, &fields);
+        }
+        if let Some(template) = &mapping.body_template {
+            node.data.body = interpolate(template, &fields);
+        }
+        node
+    }
+}
+
+/// The event's payload fields as strings, keyed by field name.
+fn event_fields(event: &WarrenEvent) -> HashMap<String, String> {
+    let serde_json::Value::Object(map) = serde_json::to_value(event).unwrap_or_default() else {
+        return HashMap::new();
+    };
+    map.into_iter()
+        .map(|(k, v)| {
+            let s = match v {
+                serde_json::Value::String(s) => s,
+                serde_json::Value::Null => String::new(),
+                other => other.to_string(),
+            };
+            (k, s)
+        })
+        .collect()
+}
+
+/// Replace each `{field}` in `template`. Unknown fields are left as written.
+fn interpolate(template: &str, fields: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) if fields.contains_key(&after[..end]) => {
+                out.push_str(&fields[&after[..end]]);
+                rest = &after[end + 1..];
+            }
+            _ => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gate_rejected() -> WarrenEvent {
+        WarrenEvent::GateRejected {
+            gate_id: "gate-7".to_string(),
+            item_id: "item-3".to_string(),
+            stage: "review".to_string(),
+            rejected_by: "mike".to_string(),
+            reason: "missing load test".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_interpolates_event_fields() {
+        let mappings = EventMappings::new().with(
+            "gate.rejected",
+            EventMapping {
+                kind: Some(NodeKind::new("pattern").unwrap()),
+                importance: Some(0.9),
+                title_template: Some("Gate {gate_id} rejected at {stage}".to_string()),
+                body_template: Some("{rejected_by}: {reason} ({unknown})".to_string()),
+            },
+        );
+        let node = mappings.to_node(&gate_rejected(), "warren");
+
+        assert_eq!(node.kind, NodeKind::new("pattern").unwrap());
+        assert_eq!(node.importance, 0.9);
+        assert_eq!(node.data.title, "Gate gate-7 rejected at review");
+        assert_eq!(node.data.body, "mike: missing load test ({unknown})");
+        // Source and dedup key still come from the built-in mapping
+        assert_eq!(node.source.agent, "mike");
+        assert!(node
+            .data
+            .metadata
+            .contains_key(crate::types::EVENT_KEY_METADATA));
+    }
+
+    #[test]
+    fn test_partial_override_keeps_other_defaults() {
+        let mappings = EventMappings::new().with(
+            "gate.rejected",
+            EventMapping {
+                importance: Some(0.95),
+                ..Default::default()
+            },
+        );
+        let default = gate_rejected().to_node("warren");
+        let node = mappings.to_node(&gate_rejected(), "warren");
+
+        assert_eq!(node.importance, 0.95);
+        assert_eq!(node.kind, default.kind);
+        assert_eq!(node.data.title, default.data.title);
+        assert_eq!(node.data.body, default.data.body);
+    }
+
+    #[test]
+    fn test_unmapped_type_uses_builtin_mapping() {
+        let mappings = EventMappings::new().with(
+            "gate.rejected",
+            EventMapping {
+                kind: Some(NodeKind::new("pattern").unwrap()),
+                ..Default::default()
+            },
+        );
+        let event = WarrenEvent::TaskPicked {
+            task_id: "task-1".to_string(),
+            item_id: "item-3".to_string(),
+            picked_by: "kai".to_string(),
+        };
+        let node = mappings.to_node(&event, "warren");
+        let default = event.to_node("warren");
+
+        assert_eq!(node.kind, NodeKind::new("event").unwrap());
+        assert_eq!(node.data.title, default.data.title);
+        assert_eq!(node.importance, default.importance);
+    }
+
+    #[test]
+    fn test_numeric_and_optional_fields() {
+        let mappings = EventMappings::new()
+            .with(
+                "item.completed",
+                EventMapping {
+                    body_template: Some("{title}: {evidence_count} evidence".to_string()),
+                    ..Default::default()
+                },
+            )
+            .with(
+                "stage.advanced",
+                EventMapping {
+                    title_template: Some("{item_id} {previous_stage}->{stage}".to_string()),
+                    ..Default::default()
+                },
+            );
+        let completed = WarrenEvent::ItemCompleted {
+            item_id: "item-1".to_string(),
+            title: "Billing export".to_string(),
+            evidence_count: 4,
+        };
+        let advanced = WarrenEvent::StageAdvanced {
+            item_id: "item-1".to_string(),
+            stage: "draft".to_string(),
+            previous_stage: None,
+        };
+        assert_eq!(
+            mappings.to_node(&completed, "warren").data.body,
+            "Billing export: 4 evidence"
+        );
+        assert_eq!(
+            mappings.to_node(&advanced, "warren").data.title,
+            "item-1 ->draft"
+        );
+    }
+}
@@ -1,24 +1,76 @@
-use super::types::{parse_subject, WarrenEvent};
+use super::dead_letter::{DeadLetter, DeadLetterQueue};
+use super::mapping::EventMappings;
+use super::types::{subject_matches, WarrenEvent};
+use async_nats::jetstream::{self, consumer::pull, consumer::AckPolicy, AckKind};
 use async_nats::Client;
 use cortex_core::*;
 use futures::StreamExt;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::sync::RwLock as StdRwLock;
+use std::time::Duration;
+
+/// Durable JetStream consumer settings.
+#[derive(Debug, Clone)]
+pub struct JetStreamConfig {
+    /// Stream holding Warren events. Created if missing.
+    pub stream: String,
+    /// Durable consumer name. Its ack floor survives restarts.
+    pub consumer: String,
+    /// Subjects captured by the stream when it is created. Keep these
+    /// within the adapter's subjects (see [`WarrenNatsAdapter::with_subjects`]);
+    /// other messages are acked without being ingested.
+    pub subjects: Vec<String>,
+    /// Redelivery delay after a storage failure.
+    pub nak_delay: Duration,
+}
+
+impl Default for JetStreamConfig {
+    fn default() -> Self {
+        Self {
+            stream: "WARREN".into(),
+            consumer: "cortex".into(),
+            subjects: default_subjects(),
+            nak_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Screens a node before it is stored. It may set the embedding or change
+/// the node. The inner error refuses the node for good, with the reason;
+/// the outer one is a failure (e.g. to embed) that redelivery may get past.
+pub type NodeGate = dyn Fn(&mut Node) -> Result<std::result::Result<(), String>> + Send + Sync;
+
+/// Why an event was not ingested.
+enum IngestError {
+    /// The gate refused the node; redelivering the event cannot help.
+    Refused(CortexError),
+    /// Storage, embedding or index failure.
+    Failed(CortexError),
+}
+
+/// Subjects Warren publishes its events on.
+pub fn default_subjects() -> Vec<String> {
+    vec!["warren.>".into()]
+}
 
 pub struct WarrenNatsAdapter {
     client: Client,
     storage: Arc<RedbStorage>,
-    embedding_service: Arc<FastEmbedService>,
+    embedding_service: Arc<dyn EmbeddingService>,
     vector_index: Arc<StdRwLock<HnswIndex>>,
     graph_version: Arc<AtomicU64>,
+    dead_letters: Option<Arc<DeadLetterQueue>>,
+    mappings: EventMappings,
+    gate: Option<Arc<NodeGate>>,
+    subjects: Vec<String>,
 }
 
 impl WarrenNatsAdapter {
     pub fn new(
         client: Client,
         storage: Arc<RedbStorage>,
-        embedding_service: Arc<FastEmbedService>,
+        embedding_service: Arc<dyn EmbeddingService>,
         vector_index: Arc<StdRwLock<HnswIndex>>,
         graph_version: Arc<AtomicU64>,
     ) -> Self {
@@ -28,69 +80,227 @@ impl WarrenNatsAdapter {
             embedding_service,
             vector_index,
             graph_version,
+            dead_letters: None,
+            mappings: EventMappings::default(),
+            gate: None,
+            subjects: default_subjects(),
         }
     }
 
+    /// Override how event types map to nodes.
+    pub fn with_mappings(mut self, mappings: EventMappings) -> Self {
+        self.mappings = mappings;
+        self
+    }
+
+    /// Route messages that cannot be ingested to `queue` instead of
+    /// only logging them.
+    pub fn with_dead_letters(mut self, queue: Arc<DeadLetterQueue>) -> Self {
+        self.dead_letters = Some(queue);
+        self
+    }
+
+    /// Run `gate` on every new node before it is stored. Refused nodes are
+    /// dead-lettered; other gate errors are retried like storage failures.
+    pub fn with_gate(mut self, gate: Arc<NodeGate>) -> Self {
+        self.gate = Some(gate);
+        self
+    }
+
+    /// Consume events on `subjects` (NATS wildcards allowed) instead of
+    /// `warren.>`.
+    pub fn with_subjects(mut self, subjects: Vec<String>) -> Self {
+        self.subjects = subjects;
+        self
+    }
+
     /// Start consuming Warren events
     pub async fn start(&self) -> Result<()> {
-        let mut subscriber = self
-            .client
-            .subscribe("warren.>".to_string())
-            .await
-            .map_err(|e| CortexError::Validation(format!("NATS subscribe failed: {}", e)))?;
+        let mut subscriptions = Vec::with_capacity(self.subjects.len());
+        for subject in &self.subjects {
+            let subscriber = self.client.subscribe(subject.clone()).await.map_err(|e| {
+                CortexError::Validation(format!("NATS subscribe to {} failed: {}", subject, e))
+            })?;
+            subscriptions.push(subscriber);
+        }
+        let mut subscriber = futures::stream::select_all(subscriptions);
 
-        tracing::info!("Warren NATS adapter started, subscribed to warren.>");
+        tracing::info!(
+            "Warren NATS adapter started, subscribed to {}",
+            self.subjects.join(", ")
+        );
 
         while let Some(msg) = subscriber.next().await {
-            if let Err(e) = self.handle_message(msg).await {
-                tracing::error!("Failed to handle NATS message: {}", e);
-            }
+            // Core NATS cannot redeliver, so every failure is dead-lettered
+            self.process(&msg.subject, &msg.payload, false).await;
         }
 
         Ok(())
     }
 
-    async fn handle_message(&self, msg: async_nats::Message) -> Result<()> {
-        let subject_type = parse_subject(&msg.subject);
-        if subject_type.is_none() {
-            return Ok(());
-        }
+    /// Consume Warren events through a durable JetStream pull consumer.
+    ///
+    /// Messages are acked only once the node is committed, so events
+    /// published while Cortex is down are delivered on the next start.
+    /// Storage failures NAK the message for redelivery; unparseable
+    /// payloads and invalid events are dead-lettered and acked.
+    pub async fn start_jetstream(&self, config: &JetStreamConfig) -> Result<()> {
+        let js = jetstream::new(self.client.clone());
+        let stream = js
+            .get_or_create_stream(jetstream::stream::Config {
+                name: config.stream.clone(),
+                subjects: config.subjects.clone(),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| {
+                CortexError::Validation(format!(
+                    "JetStream stream '{}' unavailable: {}",
+                    config.stream, e
+                ))
+            })?;
+        let consumer: jetstream::consumer::PullConsumer = stream
+            .get_or_create_consumer(
+                &config.consumer,
+                pull::Config {
+                    durable_name: Some(config.consumer.clone()),
+                    ack_policy: AckPolicy::Explicit,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| {
+                CortexError::Validation(format!(
+                    "JetStream consumer '{}' unavailable: {}",
+                    config.consumer, e
+                ))
+            })?;
+        let mut messages = consumer
+            .messages()
+            .await
+            .map_err(|e| CortexError::Validation(format!("JetStream pull failed: {}", e)))?;
 
-        let event: WarrenEvent = serde_json::from_slice(&msg.payload)
-            .map_err(|e| CortexError::Validation(format!("Invalid event JSON: {}", e)))?;
+        tracing::info!(
+            "Warren JetStream adapter started, consuming {}/{}",
+            config.stream,
+            config.consumer
+        );
 
-        tracing::debug!("Received Warren event: {:?}", event);
+        while let Some(msg) = messages.next().await {
+            let msg = match msg {
+                Ok(msg) => msg,
+                Err(e) => {
+                    tracing::warn!("JetStream delivery error: {}", e);
+                    continue;
+                }
+            };
+            let ack = match self.process(&msg.subject, &msg.payload, true).await {
+                None => AckKind::Ack,
+                Some(e) => {
+                    tracing::error!("Failed to store Warren event, will retry: {}", e);
+                    AckKind::Nak(Some(config.nak_delay))
+                }
+            };
+            if let Err(e) = msg.ack_with(ack).await {
+                tracing::warn!("Failed to ack JetStream message: {}", e);
+            }
+        }
 
-        let mut node = event.to_node("warren");
+        Ok(())
+    }
 
-        // Check for duplicates by title + source
-        let existing = self.storage.list_nodes(
-            NodeFilter::new()
-                .with_source_agent(node.source.agent.clone())
-                .with_limit(100),
-        )?;
+    /// Decode and ingest one message.
+    ///
+    /// Returns the error when the message should be redelivered: only with
+    /// `can_retry`, and only for storage, embedding and index failures.
+    /// Payloads that can never be ingested (unparseable, unknown type, or
+    /// refused by the gate) are dead-lettered, as is every failure when the
+    /// message cannot be redelivered.
+    async fn process(
+        &self,
+        subject: &async_nats::Subject,
+        payload: &[u8],
+        can_retry: bool,
+    ) -> Option<CortexError> {
+        let error = match self.decode_event(subject, payload) {
+            Ok(None) => return None,
+            Ok(Some(event)) => match self.ingest(&event) {
+                Ok(()) => return None,
+                Err(IngestError::Failed(e)) if can_retry => return Some(e),
+                Err(IngestError::Refused(e) | IngestError::Failed(e)) => e,
+            },
+            Err(e) => e,
+        };
+        self.dead_letter(DeadLetter::new(subject.as_str(), payload, &error))
+            .await;
+        None
+    }
 
-        let duplicate = existing
-            .iter()
-            .any(|n| n.data.title == node.data.title && n.source.session == node.source.session);
+    async fn dead_letter(&self, letter: DeadLetter) {
+        match &self.dead_letters {
+            Some(queue) => {
+                if let Err(e) = queue.record(letter).await {
+                    tracing::error!("{}", e);
+                }
+            }
+            None => tracing::error!(
+                "Failed to handle NATS message on {}: {}",
+                letter.subject,
+                letter.error
+            ),
+        }
+    }
 
-        if duplicate {
-            tracing::debug!("Skipping duplicate event: {}", node.data.title);
-            return Ok(());
+    /// Parse a Warren event, or `None` for subjects outside the adapter's.
+    fn decode_event(
+        &self,
+        subject: &async_nats::Subject,
+        payload: &[u8],
+    ) -> Result<Option<WarrenEvent>> {
+        if !self
+            .subjects
+            .iter()
+            .any(|pattern| subject_matches(pattern, subject.as_str()))
+        {
+            return Ok(None);
         }
+        serde_json::from_slice(payload)
+            .map(Some)
+            .map_err(|e| CortexError::Validation(format!("Invalid event JSON: {}", e)))
+    }
 
-        // Generate embedding
-        let text = embedding_input(&node);
-        let embedding = self.embedding_service.embed(&text)?;
-        node.embedding = Some(embedding.clone());
+    fn ingest(&self, event: &WarrenEvent) -> std::result::Result<(), IngestError> {
+        tracing::debug!("Received Warren event: {:?}", event);
 
-        // Store node
-        self.storage.put_node(&node)?;
+        let mut refusal = None;
+        let stored = ingest_event(&self.storage, event, &self.mappings, |node| {
+            if let Some(gate) = &self.gate {
+                if let Err(reason) = gate(node)? {
+                    refusal = Some(reason.clone());
+                    return Err(CortexError::Validation(reason));
+                }
+            }
+            if node.embedding.is_none() {
+                node.embedding = Some(self.embedding_service.embed(&embedding_input(node))?);
+            }
+            Ok(())
+        });
+        let node = match (stored, refusal) {
+            (_, Some(reason)) => return Err(IngestError::Refused(CortexError::Validation(reason))),
+            (Err(e), None) => return Err(IngestError::Failed(e)),
+            (Ok(None), None) => {
+                tracing::debug!("Skipping redelivered event: {}", event.dedup_key());
+                return Ok(());
+            }
+            (Ok(Some(node)), None) => node,
+        };
 
         // Index embedding
-        {
+        if let Some(embedding) = &node.embedding {
             let mut index = self.vector_index.write().unwrap();
-            index.insert(node.id, &embedding)?;
+            index
+                .insert_node(&node, embedding)
+                .map_err(IngestError::Failed)?;
         }
 
         // Increment graph version so briefing cache invalidates
@@ -101,3 +311,249 @@ impl WarrenNatsAdapter {
         Ok(())
     }
 }
+
+/// Store `event` as a node unless its dedup key was already ingested.
+///
+/// `prepare` embeds the new node and may refuse it. Returns the stored node,
+/// or `None` for a redelivered event. The key is checked before `prepare`
+/// runs so duplicates cost a single lookup.
+pub fn ingest_event(
+    storage: &RedbStorage,
+    event: &WarrenEvent,
+    mappings: &EventMappings,
+    prepare: impl FnOnce(&mut Node) -> Result<()>,
+) -> Result<Option<Node>> {
+    let key = event.dedup_key();
+    if storage.ingested_event(&key)?.is_some() {
+        return Ok(None);
+    }
+
+    let mut node = mappings.to_node(event, "warren");
+    prepare(&mut node)?;
+
+    if !storage.put_node_for_event(&node, &key)? {
+        return Ok(None);
+    }
+    Ok(Some(node))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    struct FixedEmbedder;
+
+    impl EmbeddingService for FixedEmbedder {
+        fn embed(&self, _text: &str) -> Result<Embedding> {
+            Ok(vec![0.5, 0.5, 0.5, 0.5])
+        }
+
+        fn embed_batch(&self, texts: &[String]) -> Result<Vec<Embedding>> {
+            texts.iter().map(|t| self.embed(t)).collect()
+        }
+
+        fn dimension(&self) -> usize {
+            4
+        }
+
+        fn model_name(&self) -> &str {
+            "fixed"
+        }
+    }
+
+    /// Adapter with a file dead-letter queue. The client never connects;
+    /// `process` does not use it.
+    async fn adapter_with_dlq(dir: &TempDir) -> (WarrenNatsAdapter, Arc<DeadLetterQueue>) {
+        let client = async_nats::ConnectOptions::new()
+            .retry_on_initial_connect()
+            .connect("nats://127.0.0.1:1")
+            .await
+            .unwrap();
+        let storage = Arc::new(RedbStorage::open(dir.path().join("test.redb")).unwrap());
+        let queue = Arc::new(DeadLetterQueue::file(dir.path().join("dead_letters.jsonl")));
+        let adapter = WarrenNatsAdapter::new(
+            client,
+            storage,
+            Arc::new(FixedEmbedder),
+            Arc::new(StdRwLock::new(HnswIndex::new(4))),
+            Arc::new(AtomicU64::new(0)),
+        )
+        .with_dead_letters(queue.clone());
+        (adapter, queue)
+    }
+
+    fn read_dead_letters(dir: &TempDir) -> Vec<DeadLetter> {
+        std::fs::read_to_string(dir.path().join("dead_letters.jsonl"))
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_bad_events_are_dead_lettered() {
+        let dir = TempDir::new().unwrap();
+        let (adapter, queue) = adapter_with_dlq(&dir).await;
+        let subject = async_nats::Subject::from("warren.evidence.submitted");
+
+        let malformed = br#"{"type":"evidence.submitted","evidence_id":"#;
+        let unknown = br#"{"type":"sprint.planned","sprint_id":"s-1"}"#;
+        assert!(adapter.process(&subject, malformed, true).await.is_none());
+        assert!(adapter.process(&subject, unknown, true).await.is_none());
+
+        assert_eq!(queue.count(), 2);
+        let letters = read_dead_letters(&dir);
+        assert_eq!(letters.len(), 2);
+        assert_eq!(letters[0].subject, "warren.evidence.submitted");
+        assert_eq!(letters[0].payload.as_bytes(), malformed);
+        assert!(letters[0].error.contains("Invalid event JSON"));
+        assert_eq!(letters[1].payload.as_bytes(), unknown);
+        assert!(
+            letters[1].error.contains("sprint.planned"),
+            "{}",
+            letters[1].error
+        );
+        assert!(adapter
+            .storage
+            .list_nodes(NodeFilter::new())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_valid_and_foreign_events_are_not_dead_lettered() {
+        let dir = TempDir::new().unwrap();
+        let (adapter, queue) = adapter_with_dlq(&dir).await;
+        let payload = br#"{"type":"evidence.submitted","evidence_id":"ev-1","item_id":"item-1",
+            "content":"Schema migration verified on staging","submitted_by":"kai"}"#;
+
+        let warren = async_nats::Subject::from("warren.evidence.submitted");
+        let foreign = async_nats::Subject::from("other.thing");
+        assert!(adapter.process(&warren, payload, true).await.is_none());
+        assert!(adapter.process(&foreign, b"not json", true).await.is_none());
+
+        assert_eq!(queue.count(), 0);
+        assert_eq!(
+            adapter.storage.list_nodes(NodeFilter::new()).unwrap().len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refusals_are_dead_lettered_and_failures_retried() {
+        let dir = TempDir::new().unwrap();
+        let (adapter, queue) = adapter_with_dlq(&dir).await;
+        let subject = async_nats::Subject::from("warren.evidence.submitted");
+        let payload = br#"{"type":"evidence.submitted","evidence_id":"ev-1","item_id":"item-1",
+            "content":"Schema migration verified on staging","submitted_by":"kai"}"#;
+
+        let failing = adapter.with_gate(Arc::new(|_: &mut Node| {
+            Err(CortexError::Validation(
+                "embedding backend unavailable".into(),
+            ))
+        }));
+        assert!(failing.process(&subject, payload, true).await.is_some());
+        assert_eq!(queue.count(), 0);
+        // Core NATS cannot redeliver, so the same failure is dead-lettered
+        assert!(failing.process(&subject, payload, false).await.is_none());
+        assert_eq!(queue.count(), 1);
+
+        let refusing = failing.with_gate(Arc::new(|_: &mut Node| {
+            Ok(Err("Write gate: pii: email".to_string()))
+        }));
+        assert!(refusing.process(&subject, payload, true).await.is_none());
+        assert_eq!(queue.count(), 2);
+        let letters = read_dead_letters(&dir);
+        assert!(letters[1].error.contains("pii"), "{}", letters[1].error);
+        assert!(refusing
+            .storage
+            .list_nodes(NodeFilter::new())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_configured_subjects_replace_warren_prefix() {
+        let dir = TempDir::new().unwrap();
+        let (adapter, queue) = adapter_with_dlq(&dir).await;
+        let adapter = adapter.with_subjects(vec!["pipeline.*.events".into()]);
+        let payload = br#"{"type":"evidence.submitted","evidence_id":"ev-1","item_id":"item-1",
+            "content":"Schema migration verified on staging","submitted_by":"kai"}"#;
+
+        let configured = async_nats::Subject::from("pipeline.prod.events");
+        let default = async_nats::Subject::from("warren.evidence.submitted");
+        assert!(adapter.process(&configured, payload, true).await.is_none());
+        assert!(adapter.process(&default, b"not json", true).await.is_none());
+
+        assert_eq!(queue.count(), 0);
+        assert_eq!(
+            adapter.storage.list_nodes(NodeFilter::new()).unwrap().len(),
+            1
+        );
+    }
+
+    fn evidence() -> WarrenEvent {
+        serde_json::from_str(
+            r#"{"type":"evidence.submitted","evidence_id":"ev-42","item_id":"item-7",
+                "content":"Load test sustained 2k rps with p99 under 80ms","submitted_by":"kai"}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_redelivered_event_creates_one_node() {
+        let dir = TempDir::new().unwrap();
+        let storage = RedbStorage::open(dir.path().join("test.redb")).unwrap();
+        let embed = |node: &mut Node| {
+            node.embedding = Some(vec![0.1, 0.2, 0.3]);
+            Ok(())
+        };
+
+        let first = ingest_event(&storage, &evidence(), &EventMappings::new(), embed).unwrap();
+        let second = ingest_event(&storage, &evidence(), &EventMappings::new(), embed).unwrap();
+
+        let stored = first.expect("first delivery should be ingested");
+        assert!(second.is_none());
+        assert_eq!(storage.list_nodes(NodeFilter::new()).unwrap().len(), 1);
+        assert_eq!(
+            storage.ingested_event("evidence.submitted:ev-42").unwrap(),
+            Some(stored.id)
+        );
+    }
+
+    #[test]
+    fn test_redelivery_skips_embedding() {
+        let dir = TempDir::new().unwrap();
+        let storage = RedbStorage::open(dir.path().join("test.redb")).unwrap();
+        ingest_event(&storage, &evidence(), &EventMappings::new(), |node| {
+            node.embedding = Some(vec![1.0]);
+            Ok(())
+        })
+        .unwrap();
+
+        let result = ingest_event(&storage, &evidence(), &EventMappings::new(), |_| {
+            panic!("duplicate should not be embedded")
+        });
+        assert!(result.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_refused_event_is_not_stored() {
+        let dir = TempDir::new().unwrap();
+        let storage = RedbStorage::open(dir.path().join("test.redb")).unwrap();
+
+        let result = ingest_event(&storage, &evidence(), &EventMappings::new(), |_| {
+            Err(CortexError::Validation(
+                "Write gate: pii: card number".into(),
+            ))
+        });
+
+        assert!(matches!(result, Err(CortexError::Validation(_))));
+        assert!(storage.list_nodes(NodeFilter::new()).unwrap().is_empty());
+        assert!(storage
+            .ingested_event("evidence.submitted:ev-42")
+            .unwrap()
+            .is_none());
+    }
+}
@@ -1,8 +1,8 @@
 use cortex_core::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Warren event types that we ingest
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum WarrenEvent {
     #[serde(rename = "stage.advanced")]
@@ -74,9 +74,89 @@ pub enum WarrenEvent {
     },
 }
 
+/// Node metadata key holding the event's dedup key.
+pub const EVENT_KEY_METADATA: &str = "warren_event_key";
+
 impl WarrenEvent {
-    /// Convert Warren event to Cortex node
+    /// Every `type` tag [`event_type`](Self::event_type) can return.
+    pub const EVENT_TYPES: &'static [&'static str] = &[
+        "stage.advanced",
+        "item.completed",
+        "evidence.submitted",
+        "gate.approved",
+        "gate.rejected",
+        "interaction.created",
+        "task.picked",
+        "autonomy",
+        "refinement",
+    ];
+
+    /// The event's `type` tag, e.g. `"gate.rejected"`.
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            WarrenEvent::StageAdvanced { .. } => "stage.advanced",
+            WarrenEvent::ItemCompleted { .. } => "item.completed",
+            WarrenEvent::EvidenceSubmitted { .. } => "evidence.submitted",
+            WarrenEvent::GateApproved { .. } => "gate.approved",
+            WarrenEvent::GateRejected { .. } => "gate.rejected",
+            WarrenEvent::InteractionCreated { .. } => "interaction.created",
+            WarrenEvent::TaskPicked { .. } => "task.picked",
+            WarrenEvent::AutonomyEvent { .. } => "autonomy",
+            WarrenEvent::RefinementEvent { .. } => "refinement",
+        }
+    }
+
+    /// Stable dedup key derived from the event's natural ID.
+    ///
+    /// A redelivered message yields the same key, so ingest can skip it.
+    /// Autonomy events carry no ID, so their whole payload forms the key.
+    pub fn dedup_key(&self) -> String {
+        match self {
+            WarrenEvent::StageAdvanced { item_id, stage, .. } => {
+                format!("stage.advanced:{}:{}", item_id, stage)
+            }
+            WarrenEvent::ItemCompleted { item_id, .. } => format!("item.completed:{}", item_id),
+            WarrenEvent::EvidenceSubmitted { evidence_id, .. } => {
+                format!("evidence.submitted:{}", evidence_id)
+            }
+            WarrenEvent::GateApproved {
+                gate_id,
+                item_id,
+                stage,
+                ..
+            } => format!("gate.approved:{}:{}:{}", gate_id, item_id, stage),
+            WarrenEvent::GateRejected {
+                gate_id,
+                item_id,
+                stage,
+                ..
+            } => format!("gate.rejected:{}:{}:{}", gate_id, item_id, stage),
+            WarrenEvent::InteractionCreated { interaction_id, .. } => {
+                format!("interaction.created:{}", interaction_id)
+            }
+            WarrenEvent::TaskPicked { task_id, .. } => format!("task.picked:{}", task_id),
+            WarrenEvent::AutonomyEvent {
+                agent_id,
+                action,
+                context,
+            } => format!("autonomy:{}:{}:{}", agent_id, action, context),
+            WarrenEvent::RefinementEvent { refinement_id, .. } => {
+                format!("refinement:{}", refinement_id)
+            }
+        }
+    }
+
+    /// Convert Warren event to Cortex node, tagged with its dedup key.
     pub fn to_node(&self, source_agent: &str) -> Node {
+        let mut node = self.node_for(source_agent);
+        node.data.metadata.insert(
+            EVENT_KEY_METADATA.to_string(),
+            serde_json::Value::String(self.dedup_key()),
+        );
+        node
+    }
+
+    fn node_for(&self, source_agent: &str) -> Node {
         let event = NodeKind::new("event").unwrap();
         let fact = NodeKind::new("fact").unwrap();
         let decision = NodeKind::new("decision").unwrap();
@@ -258,6 +338,21 @@ impl WarrenEvent {
     }
 }
 
+/// Whether `subject` matches the NATS subscription `pattern`: `*` matches
+/// one token and a trailing `>` one or more.
+pub fn subject_matches(pattern: &str, subject: &str) -> bool {
+    let mut tokens = subject.split('.');
+    for wanted in pattern.split('.') {
+        match (wanted, tokens.next()) {
+            (">", Some(token)) => return !token.is_empty(),
+            ("*", Some(token)) if !token.is_empty() => {}
+            (wanted, Some(token)) if wanted == token => {}
+            _ => return false,
+        }
+    }
+    tokens.next().is_none()
+}
+
 /// Parse NATS subject to determine event type
 pub fn parse_subject(subject: &async_nats::Subject) -> Option<&str> {
     let s = subject.as_str();
@@ -292,6 +387,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_event_types_are_known_tags() {
+        for event_type in WarrenEvent::EVENT_TYPES {
+            let err = serde_json::from_str::<WarrenEvent>(&format!(r#"{{"type":"{event_type}"}}"#))
+                .unwrap_err();
+            assert!(
+                !err.to_string().contains("unknown variant"),
+                "{event_type}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_subject_matches_wildcards() {
+        assert!(subject_matches("warren.>", "warren.stage.advanced"));
+        assert!(!subject_matches("warren.>", "warren"));
+        assert!(!subject_matches("warren.>", "warren."));
+        assert!(subject_matches("pipeline.*.events", "pipeline.prod.events"));
+        assert!(!subject_matches(
+            "pipeline.*.events",
+            "pipeline.prod.eu.events"
+        ));
+        assert!(subject_matches("pipeline.events", "pipeline.events"));
+        assert!(!subject_matches("pipeline.events", "pipeline.events.extra"));
+        assert!(!subject_matches("other.>", "warren.stage.advanced"));
+    }
+
     #[test]
     fn test_parse_subject_non_warren_returns_none() {
         assert_eq!(parse_subject(&make_subject("other.event")), None);
@@ -343,6 +465,19 @@ mod tests {
         assert_eq!(node.kind, NodeKind::new("fact").unwrap());
         assert_eq!(node.source.agent, "kai");
         assert_eq!(node.source.session, Some("item-456".to_string()));
+        assert_eq!(
+            node.data.metadata[EVENT_KEY_METADATA],
+            "evidence.submitted:ev-789"
+        );
+    }
+
+    #[test]
+    fn test_dedup_key_is_stable_across_deliveries() {
+        let payload = r#"{"type":"interaction.created","interaction_id":"int-9","agent_id":"kai","content":"hi","channel":"slack"}"#;
+        let first: WarrenEvent = serde_json::from_str(payload).unwrap();
+        let second: WarrenEvent = serde_json::from_str(payload).unwrap();
+        assert_eq!(first.dedup_key(), second.dedup_key());
+        assert_eq!(first.dedup_key(), "interaction.created:int-9");
     }
 
     #[test]
@@ -0,0 +1,133 @@
+//! JetStream durability tests. Requires a nats-server started with `-js`:
+//!
+//! ```sh
+//! nats-server -js &
+//! NATS_URL=nats://localhost:4222 cargo test -p warren-adapter --features jetstream-tests
+//! ```
+#![cfg(feature = "jetstream-tests")]
+
+use cortex_core::*;
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+use std::sync::RwLock as StdRwLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tempfile::TempDir;
+use warren_adapter::{JetStreamConfig, WarrenNatsAdapter};
+
+struct FixedEmbedder;
+
+impl EmbeddingService for FixedEmbedder {
+    fn embed(&self, _text: &str) -> Result<Embedding> {
+        Ok(vec![0.5, 0.5, 0.5, 0.5])
+    }
+
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Embedding>> {
+        texts.iter().map(|t| self.embed(t)).collect()
+    }
+
+    fn dimension(&self) -> usize {
+        4
+    }
+
+    fn model_name(&self) -> &str {
+        "fixed"
+    }
+}
+
+async fn connect() -> async_nats::Client {
+    let url = std::env::var("NATS_URL").unwrap_or_else(|_| "nats://localhost:4222".into());
+    async_nats::connect(url)
+        .await
+        .expect("nats-server not reachable")
+}
+
+fn spawn_adapter(
+    client: async_nats::Client,
+    storage: Arc<RedbStorage>,
+    config: JetStreamConfig,
+) -> tokio::task::JoinHandle<()> {
+    let adapter = WarrenNatsAdapter::new(
+        client,
+        storage,
+        Arc::new(FixedEmbedder),
+        Arc::new(StdRwLock::new(HnswIndex::new(4))),
+        Arc::new(AtomicU64::new(0)),
+    );
+    tokio::spawn(async move {
+        adapter.start_jetstream(&config).await.unwrap();
+    })
+}
+
+async fn publish_evidence(client: &async_nats::Client, prefix: &str, ids: std::ops::Range<u32>) {
+    let js = async_nats::jetstream::new(client.clone());
+    for i in ids {
+        let payload = serde_json::json!({
+            "type": "evidence.submitted",
+            "evidence_id": format!("ev-{i}"),
+            "item_id": "item-1",
+            "content": format!("Benchmark run {i} completed within the latency budget"),
+            "submitted_by": "kai",
+        });
+        js.publish(
+            format!("{prefix}.evidence.submitted"),
+            payload.to_string().into(),
+        )
+        .await
+        .unwrap()
+        .await
+        .unwrap();
+    }
+}
+
+async fn wait_for_nodes(storage: &RedbStorage, expected: usize) -> usize {
+    for _ in 0..100 {
+        let count = storage.list_nodes(NodeFilter::new()).unwrap().len();
+        if count >= expected {
+            return count;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    storage.list_nodes(NodeFilter::new()).unwrap().len()
+}
+
+#[tokio::test]
+async fn events_published_while_stopped_are_not_dropped() {
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let prefix = format!("warren.test{nonce}");
+    let config = JetStreamConfig {
+        stream: format!("WARREN_TEST_{nonce}"),
+        consumer: "cortex-test".into(),
+        subjects: vec![format!("{prefix}.>")],
+        nak_delay: Duration::from_millis(100),
+    };
+
+    let dir = TempDir::new().unwrap();
+    let storage = Arc::new(RedbStorage::open(dir.path().join("test.redb")).unwrap());
+    let client = connect().await;
+
+    // First run creates the stream and consumer
+    let task = spawn_adapter(client.clone(), storage.clone(), config.clone());
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    publish_evidence(&client, &prefix, 0..3).await;
+    assert_eq!(wait_for_nodes(&storage, 3).await, 3);
+    task.abort();
+    let _ = task.await;
+
+    // Published while Cortex is down
+    publish_evidence(&client, &prefix, 3..6).await;
+
+    // Restart resumes from the last ack
+    let task = spawn_adapter(client.clone(), storage.clone(), config.clone());
+    assert_eq!(wait_for_nodes(&storage, 6).await, 6);
+
+    // Nothing already acked is ingested twice
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(storage.list_nodes(NodeFilter::new()).unwrap().len(), 6);
+
+    task.abort();
+    let js = async_nats::jetstream::new(client);
+    js.delete_stream(&config.stream).await.unwrap();
+}